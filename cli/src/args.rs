@@ -0,0 +1,446 @@
+use anchor_client::Cluster;
+use anchor_lang::prelude::Pubkey;
+use clap::{ArgGroup, Args, Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+pub struct ConfigOverride {
+    /// Cluster override
+    ///
+    /// Values = mainnet, testnet, devnet, localnet.
+    /// Default: mainnet
+    #[clap(global = true, long = "provider.cluster", default_value_t = Cluster::Mainnet)]
+    pub cluster: Cluster,
+    /// Wallet override
+    ///
+    /// Example: /path/to/wallet/keypair.json
+    /// Default: ~/.config/solana/id.json
+    #[clap(global = true, long = "provider.wallet")]
+    pub wallet: Option<String>,
+    /// Print program id, accounts and hex data of every instruction instead of sending transactions
+    #[clap(global = true, long)]
+    pub dry_run: bool,
+    /// Priority fee in micro-lamports per compute unit
+    #[clap(global = true, long, conflicts_with = "priority_fee_percentile")]
+    pub priority_fee: Option<u64>,
+    /// Estimate priority fee as this percentile of recent prioritization fees of accounts written by the transaction
+    #[clap(global = true, long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub priority_fee_percentile: Option<u8>,
+    /// Maximum estimated priority fee in micro-lamports per compute unit
+    #[clap(global = true, long, requires = "priority_fee_percentile")]
+    pub max_priority_fee: Option<u64>,
+    /// Lookup table to send v0 transactions with, can be repeated
+    #[clap(global = true, long = "address-lookup-table")]
+    pub address_lookup_tables: Vec<Pubkey>,
+}
+
+/// Dynamic fee parameters, dynamic fee is enabled when filter period is set
+#[derive(Args, Debug)]
+pub struct DynamicFeeArgs {
+    /// Bin step in bps, used to compute volatility
+    #[clap(long, default_value_t = cp_amm::constants::BIN_STEP_BPS_DEFAULT)]
+    pub bin_step: u16,
+    /// Period in seconds that volatility reference is not updated
+    #[clap(long, requires_all = ["decay_period", "reduction_factor", "variable_fee_control", "max_volatility_accumulator"])]
+    pub filter_period: Option<u16>,
+    /// Period in seconds after which volatility reference is reset
+    #[clap(long, requires = "filter_period")]
+    pub decay_period: Option<u16>,
+    /// Decay rate of volatility reference in bps
+    #[clap(long, requires = "filter_period")]
+    pub reduction_factor: Option<u16>,
+    /// Scaling factor of variable fee
+    #[clap(long, requires = "filter_period")]
+    pub variable_fee_control: Option<u32>,
+    /// Maximum volatility accumulator
+    #[clap(long, requires = "filter_period")]
+    pub max_volatility_accumulator: Option<u32>,
+}
+
+/// Amount of liquidity, either a liquidity delta or token amounts. With a single token amount,
+/// the other token amount is derived from current price of pool
+#[derive(Args, Debug)]
+pub struct LiquidityAmountArgs {
+    #[clap(long, conflicts_with_all = ["token_a_amount", "token_b_amount"])]
+    pub liquidity_delta: Option<u128>,
+    /// Token a amount, excluding transfer fee
+    #[clap(long)]
+    pub token_a_amount: Option<u64>,
+    /// Token b amount, excluding transfer fee
+    #[clap(long)]
+    pub token_b_amount: Option<u64>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create static config
+    CreateConfig {
+        /// Index of config
+        #[clap(long)]
+        index: u64,
+        #[clap(long)]
+        sqrt_min_price: u128,
+        #[clap(long)]
+        sqrt_max_price: u128,
+        #[clap(long)]
+        vault_config_key: Pubkey,
+        #[clap(long)]
+        pool_creator_authority: Pubkey,
+        /// 0: slot, 1: timestamp
+        #[clap(long)]
+        activation_type: u8,
+        /// 0: both token, 1: only token b, 2: only token a
+        #[clap(long)]
+        collect_fee_mode: u8,
+        #[clap(long)]
+        trade_fee_numerator: u64,
+        #[clap(long)]
+        protocol_fee_percent: u8,
+        #[clap(long)]
+        partner_fee_percent: u8,
+        #[clap(long)]
+        referral_fee_percent: u8,
+        #[clap(flatten)]
+        dynamic_fee: DynamicFeeArgs,
+        /// Multisig vault as admin, the transaction is printed for proposing to the multisig instead of sent
+        #[clap(long)]
+        multisig: Option<Pubkey>,
+    },
+    /// Create configs of a JSON or YAML manifest, configs already created are skipped
+    CreateConfigBatch {
+        /// Path of manifest, a list of configs with the same fields as create-config
+        #[clap(long)]
+        manifest: String,
+        /// Multisig vault as admin, the transaction is printed for proposing to the multisig instead of sent
+        #[clap(long)]
+        multisig: Option<Pubkey>,
+    },
+    /// Update config, only provided fields are updated
+    UpdateConfig {
+        #[clap(long)]
+        config: Pubkey,
+        #[clap(long)]
+        vault_config_key: Option<Pubkey>,
+        #[clap(long)]
+        pool_creator_authority: Option<Pubkey>,
+        /// 0: slot, 1: timestamp
+        #[clap(long)]
+        activation_type: Option<u8>,
+        #[clap(long)]
+        trade_fee_numerator: Option<u64>,
+        #[clap(long)]
+        protocol_fee_percent: Option<u8>,
+        #[clap(long)]
+        partner_fee_percent: Option<u8>,
+        #[clap(long)]
+        referral_fee_percent: Option<u8>,
+        /// Multisig vault as admin, the transaction is printed for proposing to the multisig instead of sent
+        #[clap(long)]
+        multisig: Option<Pubkey>,
+    },
+    /// Close config
+    CloseConfig {
+        #[clap(long)]
+        config: Pubkey,
+        /// Multisig vault as admin, the transaction is printed for proposing to the multisig instead of sent
+        #[clap(long)]
+        multisig: Option<Pubkey>,
+    },
+    /// Initialize program authority with key of every admin role
+    InitializeProgramAuthority {
+        #[clap(long)]
+        config_admin: Pubkey,
+        #[clap(long)]
+        fee_claimer: Pubkey,
+        #[clap(long)]
+        reward_admin: Pubkey,
+        #[clap(long)]
+        pauser: Pubkey,
+        /// Delay in seconds between proposing and accepting a role
+        #[clap(long, default_value_t = 0)]
+        transfer_delay: u64,
+        /// Multisig vault as admin, the transaction is printed for proposing to the multisig instead of sent
+        #[clap(long)]
+        multisig: Option<Pubkey>,
+    },
+    /// Swap on a pool, printing the quote before sending the transaction
+    Swap {
+        #[clap(long)]
+        pool: Pubkey,
+        /// Mint of input token, token a or token b of pool
+        #[clap(long)]
+        input_mint: Pubkey,
+        /// Amount of input token, including transfer fee
+        #[clap(long)]
+        amount_in: u64,
+        /// Slippage in bps applied to expected amount out as minimum amount out
+        #[clap(long, default_value_t = 50)]
+        slippage_bps: u16,
+        /// Skip confirmation
+        #[clap(long)]
+        yes: bool,
+    },
+    /// Simulate a swap of wallet against the cluster, printing amounts, fees and compute units from the emitted event
+    SimulateSwap {
+        #[clap(long)]
+        pool: Pubkey,
+        /// Mint of input token, token a or token b of pool
+        #[clap(long)]
+        input_mint: Pubkey,
+        /// Amount of input token, including transfer fee
+        #[clap(long)]
+        amount_in: u64,
+        #[clap(long, default_value_t = 0)]
+        minimum_amount_out: u64,
+    },
+    /// Add liquidity to a position, printing token amounts before sending the transaction
+    #[clap(group(ArgGroup::new("required_amount").required(true).multiple(true).args(["liquidity_delta", "token_a_amount", "token_b_amount"])))]
+    AddLiquidity {
+        #[clap(long)]
+        position: Pubkey,
+        /// Token account holding the position nft. Default: position nft account created with the position
+        #[clap(long)]
+        position_nft_account: Option<Pubkey>,
+        #[clap(flatten)]
+        amount: LiquidityAmountArgs,
+        /// Maximum token a amount, including transfer fee. Default: token a amount with slippage
+        #[clap(long)]
+        token_a_amount_threshold: Option<u64>,
+        /// Maximum token b amount, including transfer fee. Default: token b amount with slippage
+        #[clap(long)]
+        token_b_amount_threshold: Option<u64>,
+        /// Slippage in bps applied to token amounts for thresholds not provided
+        #[clap(long, default_value_t = 50)]
+        slippage_bps: u16,
+        /// Skip confirmation
+        #[clap(long)]
+        yes: bool,
+    },
+    /// Remove liquidity from a position, all unlocked liquidity is removed when no amount is provided
+    RemoveLiquidity {
+        #[clap(long)]
+        position: Pubkey,
+        /// Token account holding the position nft. Default: position nft account created with the position
+        #[clap(long)]
+        position_nft_account: Option<Pubkey>,
+        #[clap(flatten)]
+        amount: LiquidityAmountArgs,
+        /// Minimum token a amount, excluding transfer fee. Default: token a amount with slippage
+        #[clap(long)]
+        token_a_amount_threshold: Option<u64>,
+        /// Minimum token b amount, excluding transfer fee. Default: token b amount with slippage
+        #[clap(long)]
+        token_b_amount_threshold: Option<u64>,
+        /// Slippage in bps applied to token amounts for thresholds not provided
+        #[clap(long, default_value_t = 50)]
+        slippage_bps: u16,
+        /// Skip confirmation
+        #[clap(long)]
+        yes: bool,
+    },
+    /// Claim fee of positions held by wallet
+    ClaimPositionFee {
+        /// Position to claim fee of, can be repeated
+        #[clap(long = "position", required_unless_present = "all_pools")]
+        positions: Vec<Pubkey>,
+        /// Claim fee of all positions held by wallet in all pools, one transaction per batch size
+        #[clap(long, conflicts_with = "positions")]
+        all_pools: bool,
+        /// Number of claims per transaction
+        #[clap(long, default_value_t = 3)]
+        batch_size: usize,
+    },
+    /// Claim partner fee of pools of wallet as partner
+    ClaimPartnerFee {
+        /// Pool to claim partner fee of, can be repeated
+        #[clap(long = "pool", required_unless_present = "all_pools")]
+        pools: Vec<Pubkey>,
+        /// Claim from all pools, one transaction per batch size
+        #[clap(long, conflicts_with = "pools")]
+        all_pools: bool,
+        /// Number of claims per transaction
+        #[clap(long, default_value_t = 3)]
+        batch_size: usize,
+    },
+    /// Claim protocol fee of pools by wallet as claim fee operator, to treasury
+    ClaimProtocolFee {
+        /// Pool to claim protocol fee of, can be repeated
+        #[clap(long = "pool", required_unless_present = "all_pools")]
+        pools: Vec<Pubkey>,
+        /// Claim from all pools, one transaction per batch size
+        #[clap(long, conflicts_with = "pools")]
+        all_pools: bool,
+        /// 0: claim both tokens, 1: swap token b into token a, 2: swap token a into token b through the pool.
+        /// Swaps are not protected by a minimum settlement amount
+        #[clap(long, default_value_t = 0)]
+        settlement_mode: u8,
+        /// Number of claims per transaction
+        #[clap(long, default_value_t = 3)]
+        batch_size: usize,
+    },
+    /// Initialize a reward of pool, wallet pays the reward initialization fee if it isn't reward admin
+    InitializeReward {
+        #[clap(long)]
+        pool: Pubkey,
+        /// Reward index, indexes from NUM_REWARDS require reward extension of pool
+        #[clap(long)]
+        reward_index: u8,
+        #[clap(long)]
+        reward_mint: Pubkey,
+        /// Reward duration in seconds
+        #[clap(long)]
+        reward_duration: u64,
+        /// Funder of the reward. Default: wallet
+        #[clap(long)]
+        funder: Option<Pubkey>,
+        /// Duration in seconds claimed rewards are vested over, 0 transfers claimed rewards directly
+        #[clap(long, default_value_t = 0)]
+        claim_vesting_duration: u32,
+    },
+    /// Fund a reward from token account of wallet as funder
+    FundReward {
+        #[clap(long)]
+        pool: Pubkey,
+        #[clap(long)]
+        reward_index: u8,
+        /// Amount of reward token, including transfer fee
+        #[clap(long)]
+        amount: u64,
+        /// Carry forward ineligible reward to the new reward window
+        #[clap(long)]
+        carry_forward: bool,
+    },
+    /// Update duration of a reward by reward admin
+    UpdateRewardDuration {
+        #[clap(long)]
+        pool: Pubkey,
+        #[clap(long)]
+        reward_index: u8,
+        /// New reward duration in seconds
+        #[clap(long)]
+        new_duration: u64,
+        /// Multisig vault as admin, the transaction is printed for proposing to the multisig instead of sent
+        #[clap(long)]
+        multisig: Option<Pubkey>,
+    },
+    /// Update funder of a reward by reward admin or current funder
+    UpdateRewardFunder {
+        #[clap(long)]
+        pool: Pubkey,
+        #[clap(long)]
+        reward_index: u8,
+        #[clap(long)]
+        new_funder: Pubkey,
+    },
+    /// Withdraw reward emitted while pool had no liquidity, to token account of wallet as funder
+    WithdrawIneligibleReward {
+        #[clap(long)]
+        pool: Pubkey,
+        #[clap(long)]
+        reward_index: u8,
+    },
+    /// Run keeper: crank reward updates, refresh vestings and claim fees above thresholds every interval
+    Keeper {
+        /// Path of keeper config TOML file, see keeper.example.toml
+        #[clap(long)]
+        config: String,
+        /// Run a single round and exit
+        #[clap(long)]
+        once: bool,
+    },
+    /// Create or extend an address lookup table of wallet with static accounts of pools: pool, vaults, mints, reward vaults, pool authority and programs
+    PoolLookupTable {
+        /// Pool to add accounts of, can be repeated
+        #[clap(long = "pool", required = true)]
+        pools: Vec<Pubkey>,
+        /// Lookup table to extend. Default: create a new lookup table
+        #[clap(long)]
+        lookup_table: Option<Pubkey>,
+    },
+    /// Mint two test tokens, create a config, initialize a pool of the tokens with liquidity and
+    /// print all addresses. Wallet must be config admin
+    #[cfg(feature = "e2e-test")]
+    Bootstrap {
+        /// Index of config. Default: first unused index
+        #[clap(long)]
+        config_index: Option<u64>,
+        /// Decimals of both tokens
+        #[clap(long, default_value_t = 9)]
+        decimals: u8,
+        /// Amount of each token minted to wallet
+        #[clap(long, default_value_t = 1_000_000_000_000_000)]
+        supply: u64,
+        /// Maximum amount of each token deposited to pool
+        #[clap(long, default_value_t = 100_000_000_000_000)]
+        liquidity_amount: u64,
+        /// Initial sqrt price in Q64.64. Default: price 1
+        #[clap(long, default_value_t = 1 << 64)]
+        sqrt_price: u128,
+        /// Initial ui price of 1 token a in token b, e.g. 1.5, overrides sqrt price
+        #[clap(long)]
+        price: Option<String>,
+        /// Trade fee numerator of config
+        #[clap(long, default_value_t = 2_500_000)]
+        trade_fee_numerator: u64,
+    },
+    /// Subscribe to program logs over websocket and print cp-amm events in real time
+    Monitor {
+        /// Only events of transactions of the pool
+        #[clap(long)]
+        pool: Option<Pubkey>,
+        /// Only events of this name, e.g. EvtSwap, can be repeated
+        #[clap(long = "event")]
+        events: Vec<String>,
+        /// Append events as NDJSON to this file, data is the base64 of the event as emitted by the program
+        #[clap(long)]
+        output: Option<String>,
+    },
+    /// Scrape metrics, prices and reward rates of pools periodically, exposed on a Prometheus
+    /// endpoint and/or appended to a CSV file
+    #[clap(group(ArgGroup::new("exporter").required(true).multiple(true).args(["prometheus", "csv"])))]
+    ExportMetrics {
+        /// Pool to scrape, can be repeated
+        #[clap(long = "pool", required = true)]
+        pools: Vec<Pubkey>,
+        #[clap(long, default_value_t = 60)]
+        interval_seconds: u64,
+        /// Address to serve Prometheus metrics on, e.g. 0.0.0.0:9100
+        #[clap(long)]
+        prometheus: Option<String>,
+        /// CSV file to append rows to
+        #[clap(long)]
+        csv: Option<String>,
+    },
+    /// Show decoded state of a pool
+    ShowPool {
+        /// Pool address
+        #[clap(long)]
+        pool: Pubkey,
+        /// Print as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List pools, optionally filtered by token mints or partner
+    ListPools {
+        /// Only pools with this token a mint
+        #[clap(long)]
+        token_a_mint: Option<Pubkey>,
+        /// Only pools with this token b mint
+        #[clap(long)]
+        token_b_mint: Option<Pubkey>,
+        /// Only pools with this partner
+        #[clap(long)]
+        partner: Option<Pubkey>,
+        /// Print as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Parser, Debug)]
+#[clap(version, about, author)]
+pub struct Cli {
+    #[clap(flatten)]
+    pub config_override: ConfigOverride,
+    #[clap(subcommand)]
+    pub command: Command,
+}
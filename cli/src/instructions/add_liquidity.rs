@@ -0,0 +1,136 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Result};
+use cp_amm::{
+    accounts, instruction,
+    state::{Pool, Position},
+    u128x128_math::Rounding,
+    AddLiquidityParameters,
+};
+use cp_amm_core::transfer_fee::calculate_transfer_fee_included_amount;
+use std::ops::Deref;
+
+use crate::{
+    args::LiquidityAmountArgs,
+    utils::{
+        confirm, derive_event_authority, derive_lp_allowlist_entry, derive_position_nft_account,
+        get_clock, get_liquidity_delta, get_maximum_amount_in, get_mint_info, get_or_create_ata_ix,
+        send_request,
+    },
+};
+
+pub struct AddLiquidityParams {
+    pub position: Pubkey,
+    pub position_nft_account: Option<Pubkey>,
+    pub amount: LiquidityAmountArgs,
+    pub token_a_amount_threshold: Option<u64>,
+    pub token_b_amount_threshold: Option<u64>,
+    pub slippage_bps: u16,
+    pub yes: bool,
+}
+
+pub fn add_liquidity<C: Deref<Target = impl Signer> + Clone>(
+    params: AddLiquidityParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let AddLiquidityParams {
+        position,
+        position_nft_account,
+        amount,
+        token_a_amount_threshold,
+        token_b_amount_threshold,
+        slippage_bps,
+        yes,
+    } = params;
+
+    let position_state: Position = program.account(position)?;
+    let pool = position_state.pool;
+    let pool_state: Pool = program.account(pool)?;
+
+    let liquidity_delta = match amount.liquidity_delta {
+        Some(liquidity_delta) => liquidity_delta,
+        None => get_liquidity_delta(&pool_state, amount.token_a_amount, amount.token_b_amount)?,
+    };
+    ensure!(liquidity_delta > 0, "liquidity delta is zero");
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let token_a = get_mint_info(&rpc_client, pool_state.token_a_mint, clock.epoch)?;
+    let token_b = get_mint_info(&rpc_client, pool_state.token_b_mint, clock.epoch)?;
+
+    // same as the program, amounts are rounded up and include transfer fee
+    let amounts = pool_state.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)?;
+    let total_amount_a = calculate_transfer_fee_included_amount(
+        token_a.transfer_fee.as_ref(),
+        amounts.token_a_amount,
+    )?
+    .amount;
+    let total_amount_b = calculate_transfer_fee_included_amount(
+        token_b.transfer_fee.as_ref(),
+        amounts.token_b_amount,
+    )?
+    .amount;
+    let token_a_amount_threshold = match token_a_amount_threshold {
+        Some(threshold) => threshold,
+        None => get_maximum_amount_in(total_amount_a, slippage_bps)?,
+    };
+    let token_b_amount_threshold = match token_b_amount_threshold {
+        Some(threshold) => threshold,
+        None => get_maximum_amount_in(total_amount_b, slippage_bps)?,
+    };
+
+    println!("Add liquidity {liquidity_delta} to position {position} of pool {pool}");
+    println!("Token a amount: {total_amount_a} (maximum {token_a_amount_threshold})");
+    println!("Token b amount: {total_amount_b} (maximum {token_b_amount_threshold})");
+
+    if !yes && !confirm("Send add liquidity transaction?")? {
+        return Ok(());
+    }
+
+    let owner = program.payer();
+    let (token_a_account, create_token_a_ata_ix) =
+        get_or_create_ata_ix(&rpc_client, &owner, &owner, &token_a)?;
+    let (token_b_account, create_token_b_ata_ix) =
+        get_or_create_ata_ix(&rpc_client, &owner, &owner, &token_b)?;
+
+    let mut request = program.request();
+    for create_ata_ix in [create_token_a_ata_ix, create_token_b_ata_ix]
+        .into_iter()
+        .flatten()
+    {
+        request = request.instruction(create_ata_ix);
+    }
+    let request = request
+        .accounts(accounts::AddLiquidityCtx {
+            pool,
+            position,
+            token_a_account,
+            token_b_account,
+            token_a_vault: pool_state.token_a_vault,
+            token_b_vault: pool_state.token_b_vault,
+            token_a_mint: pool_state.token_a_mint,
+            token_b_mint: pool_state.token_b_mint,
+            position_nft_account: position_nft_account
+                .unwrap_or_else(|| derive_position_nft_account(&position_state.nft_mint)),
+            owner,
+            token_a_program: token_a.token_program,
+            token_b_program: token_b.token_program,
+            lp_allowlist_entry: pool_state
+                .is_permissioned_lp()
+                .then(|| derive_lp_allowlist_entry(&pool, &owner)),
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .args(instruction::AddLiquidity {
+            params: AddLiquidityParameters {
+                liquidity_delta,
+                token_a_amount_threshold,
+                token_b_amount_threshold,
+            },
+        });
+    if let Some(signature) = send_request(request)? {
+        println!("Add liquidity to position {position}. Signature: {signature:#?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use anchor_client::{
+    solana_sdk::{
+        program_pack::Pack, signature::Keypair, signer::Signer, system_instruction::create_account,
+    },
+    Program,
+};
+use anchor_lang::{prelude::Pubkey, system_program};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token::{
+        self,
+        instruction::{initialize_mint2, mint_to},
+        state::Mint,
+    },
+    token_2022,
+};
+use anyhow::{ensure, Result};
+use cp_amm::{
+    accounts,
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    curve, instruction,
+    params::fee_parameters::{BaseFeeParameters, PoolFeeParameters},
+    u128x128_math::Rounding,
+    InitializePoolParameters, StaticConfigParameters,
+};
+use cp_amm_client::pda::{derive_pool, derive_token_vault};
+use rust_sdk::price::ui_price_to_sqrt_price;
+use std::ops::Deref;
+
+use crate::{
+    instructions::send_create_config,
+    utils::{
+        derive_config, derive_event_authority, derive_pool_authority, derive_position,
+        derive_position_nft_account, send_request_with_signers,
+    },
+};
+
+pub struct BootstrapParams {
+    pub config_index: Option<u64>,
+    pub decimals: u8,
+    pub supply: u64,
+    pub liquidity_amount: u64,
+    pub sqrt_price: u128,
+    /// Ui price of 1 token a in token b, overrides sqrt price
+    pub price: Option<String>,
+    pub trade_fee_numerator: u64,
+}
+
+/// Liquidity of the pool seeded with at most amount of each token at the price
+pub fn get_bootstrap_liquidity(sqrt_price: u128, amount: u64) -> Result<u128> {
+    ensure!(
+        sqrt_price > MIN_SQRT_PRICE && sqrt_price < MAX_SQRT_PRICE,
+        "sqrt price must be between min and max sqrt price"
+    );
+    let liquidity = curve::get_liquidity_from_amount_a(sqrt_price, MAX_SQRT_PRICE, amount)?.min(
+        curve::get_liquidity_from_amount_b(MIN_SQRT_PRICE, sqrt_price, amount)?,
+    );
+    ensure!(liquidity > 0, "liquidity amount is too small");
+    Ok(liquidity)
+}
+
+/// Mint two test tokens to wallet, create a config, initialize a pool of the tokens with liquidity
+/// and print all addresses. Wallet must be config admin of the program authority
+pub fn bootstrap<C: Deref<Target = impl Signer> + Clone>(
+    params: BootstrapParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let BootstrapParams {
+        config_index,
+        decimals,
+        supply,
+        liquidity_amount,
+        sqrt_price,
+        price,
+        trade_fee_numerator,
+    } = params;
+    let sqrt_price = match price {
+        Some(price) => ui_price_to_sqrt_price(&price, decimals, decimals, Rounding::Down)?,
+        None => sqrt_price,
+    };
+    ensure!(
+        liquidity_amount <= supply,
+        "liquidity amount must not exceed supply"
+    );
+    let liquidity = get_bootstrap_liquidity(sqrt_price, liquidity_amount)?;
+
+    let rpc_client = program.rpc();
+    let payer = program.payer();
+
+    let config_index = match config_index {
+        Some(config_index) => config_index,
+        None => {
+            let mut config_index = 0;
+            while rpc_client.get_account(&derive_config(config_index)).is_ok() {
+                config_index += 1;
+            }
+            config_index
+        }
+    };
+
+    let token_a_mint = Keypair::new();
+    let token_b_mint = Keypair::new();
+    let mint_rent = rpc_client.get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+    for mint in [&token_a_mint, &token_b_mint] {
+        let ata = get_associated_token_address(&payer, &mint.pubkey());
+        let request = program
+            .request()
+            .instruction(create_account(
+                &payer,
+                &mint.pubkey(),
+                mint_rent,
+                Mint::LEN as u64,
+                &spl_token::ID,
+            ))
+            .instruction(initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &payer,
+                None,
+                decimals,
+            )?)
+            .instruction(create_associated_token_account_idempotent(
+                &payer,
+                &payer,
+                &mint.pubkey(),
+                &spl_token::ID,
+            ))
+            .instruction(mint_to(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &ata,
+                &payer,
+                &[],
+                supply,
+            )?);
+        if let Some(signature) = send_request_with_signers(request, &[mint])? {
+            println!(
+                "Create mint {} with supply {supply}. Signature: {signature:#?}",
+                mint.pubkey()
+            );
+        }
+    }
+
+    let config_parameters = StaticConfigParameters {
+        pool_fees: PoolFeeParameters {
+            base_fee: BaseFeeParameters {
+                cliff_fee_numerator: trade_fee_numerator,
+                ..Default::default()
+            },
+            protocol_fee_percent: 20,
+            ..Default::default()
+        },
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        vault_config_key: Pubkey::default(),
+        pool_creator_authority: Pubkey::default(),
+        activation_type: 0,
+        collect_fee_mode: 0,
+    };
+    let config = send_create_config(program, config_index, config_parameters, None)?;
+
+    let position_nft_mint = Keypair::new();
+    let pool = derive_pool(&config, &token_a_mint.pubkey(), &token_b_mint.pubkey());
+    let position = derive_position(&position_nft_mint.pubkey());
+    let token_a_vault = derive_token_vault(&token_a_mint.pubkey(), &pool);
+    let token_b_vault = derive_token_vault(&token_b_mint.pubkey(), &pool);
+    let payer_token_a = get_associated_token_address(&payer, &token_a_mint.pubkey());
+    let payer_token_b = get_associated_token_address(&payer, &token_b_mint.pubkey());
+    let request = program
+        .request()
+        .accounts(accounts::InitializePoolCtx {
+            creator: payer,
+            position_nft_mint: position_nft_mint.pubkey(),
+            position_nft_account: derive_position_nft_account(&position_nft_mint.pubkey()),
+            payer,
+            config,
+            pool_authority: derive_pool_authority(),
+            pool,
+            position,
+            token_a_mint: token_a_mint.pubkey(),
+            token_b_mint: token_b_mint.pubkey(),
+            token_a_vault,
+            token_b_vault,
+            payer_token_a,
+            payer_token_b,
+            token_a_program: spl_token::ID,
+            token_b_program: spl_token::ID,
+            token_2022_program: token_2022::ID,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .args(instruction::InitializePool {
+            params: InitializePoolParameters {
+                liquidity,
+                sqrt_price,
+                activation_point: None,
+            },
+        });
+    if let Some(signature) = send_request_with_signers(request, &[&position_nft_mint])? {
+        println!("Initialize pool {pool}. Signature: {signature:#?}");
+    }
+
+    println!("Token a mint: {}", token_a_mint.pubkey());
+    println!("Token b mint: {}", token_b_mint.pubkey());
+    println!("Payer token a: {payer_token_a}");
+    println!("Payer token b: {payer_token_b}");
+    println!("Config {config_index}: {config}");
+    println!("Pool: {pool}");
+    println!("Token a vault: {token_a_vault}");
+    println!("Token b vault: {token_b_vault}");
+    println!("Position: {position}");
+    println!("Position nft mint: {}", position_nft_mint.pubkey());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_bootstrap_liquidity() {
+        let sqrt_price = 1u128 << 64;
+        let liquidity = get_bootstrap_liquidity(sqrt_price, 1_000_000_000).unwrap();
+        let amount_a =
+            curve::get_delta_amount_a_unsigned(sqrt_price, MAX_SQRT_PRICE, liquidity, Rounding::Up)
+                .unwrap();
+        let amount_b =
+            curve::get_delta_amount_b_unsigned(MIN_SQRT_PRICE, sqrt_price, liquidity, Rounding::Up)
+                .unwrap();
+        assert!(amount_a <= 1_000_000_000 && amount_b <= 1_000_000_000);
+
+        assert!(get_bootstrap_liquidity(MIN_SQRT_PRICE, 1_000_000_000).is_err());
+        assert!(get_bootstrap_liquidity(MAX_SQRT_PRICE, 1_000_000_000).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use anchor_client::{solana_client::rpc_filter::Memcmp, solana_sdk::signer::Signer, Program};
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use anyhow::{ensure, Result};
+use cp_amm::{accounts, instruction, state::Pool};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem::offset_of,
+    ops::Deref,
+};
+
+use crate::utils::{
+    derive_event_authority, derive_pool_authority, fetch_accounts, get_clock, get_mint_infos,
+    get_or_create_ata_ix, get_program_account_keys, print_totals, send_in_batches,
+};
+
+pub struct ClaimPartnerFeeParams {
+    pub pools: Vec<Pubkey>,
+    pub all_pools: bool,
+    pub batch_size: usize,
+}
+
+pub fn claim_partner_fee<C: Deref<Target = impl Signer> + Clone>(
+    params: ClaimPartnerFeeParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let ClaimPartnerFeeParams {
+        pools,
+        all_pools,
+        batch_size,
+    } = params;
+
+    let rpc_client = program.rpc();
+    let partner = program.payer();
+
+    let pools = if all_pools {
+        // pools of partner only
+        let partner_offset = Pool::DISCRIMINATOR.len() + offset_of!(Pool, partner);
+        get_program_account_keys::<Pool>(
+            &rpc_client,
+            vec![Memcmp::new_base58_encoded(partner_offset, partner.as_ref())],
+        )?
+    } else {
+        pools
+    };
+    let pools = fetch_accounts::<Pool>(&rpc_client, &pools)?;
+    for (pool, pool_state) in pools.iter() {
+        ensure!(
+            pool_state.partner == partner,
+            "{partner} is not partner of pool {pool}"
+        );
+    }
+    let pools: Vec<(Pubkey, Pool)> = pools
+        .into_iter()
+        .filter(|(_, pool)| pool.partner_a_fee > 0 || pool.partner_b_fee > 0)
+        .collect();
+
+    let clock = get_clock(&rpc_client)?;
+    let mints: BTreeSet<Pubkey> = pools
+        .iter()
+        .flat_map(|(_, pool)| [pool.token_a_mint, pool.token_b_mint])
+        .collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let mut totals = BTreeMap::new();
+    let mut item_instructions = vec![];
+    for (pool, pool_state) in pools {
+        let token_a = &mint_infos[&pool_state.token_a_mint];
+        let token_b = &mint_infos[&pool_state.token_b_mint];
+        let (token_a_account, create_token_a_ata_ix) =
+            get_or_create_ata_ix(&rpc_client, &partner, &partner, token_a)?;
+        let (token_b_account, create_token_b_ata_ix) =
+            get_or_create_ata_ix(&rpc_client, &partner, &partner, token_b)?;
+
+        let mut instructions: Vec<_> = [create_token_a_ata_ix, create_token_b_ata_ix]
+            .into_iter()
+            .flatten()
+            .collect();
+        instructions.extend(
+            program
+                .request()
+                .accounts(accounts::ClaimPartnerFeesCtx {
+                    pool_authority: derive_pool_authority(),
+                    pool,
+                    token_a_account,
+                    token_b_account,
+                    token_a_vault: pool_state.token_a_vault,
+                    token_b_vault: pool_state.token_b_vault,
+                    token_a_mint: pool_state.token_a_mint,
+                    token_b_mint: pool_state.token_b_mint,
+                    signer: partner,
+                    partner_fee_receiver_a: None,
+                    partner_fee_receiver_b: None,
+                    token_a_program: token_a.token_program,
+                    token_b_program: token_b.token_program,
+                    event_authority: derive_event_authority(),
+                    program: cp_amm::ID,
+                })
+                .args(instruction::ClaimPartnerFee {
+                    max_amount_a: u64::MAX,
+                    max_amount_b: u64::MAX,
+                })
+                .instructions()?,
+        );
+        item_instructions.push(instructions);
+
+        println!(
+            "Claim partner fee of pool {pool}: {} {}, {} {}",
+            pool_state.partner_a_fee,
+            pool_state.token_a_mint,
+            pool_state.partner_b_fee,
+            pool_state.token_b_mint
+        );
+        *totals.entry(pool_state.token_a_mint).or_insert(0) += pool_state.partner_a_fee;
+        *totals.entry(pool_state.token_b_mint).or_insert(0) += pool_state.partner_b_fee;
+    }
+
+    if item_instructions.is_empty() {
+        println!("No partner fee to claim");
+        return Ok(());
+    }
+
+    send_in_batches(program, item_instructions, batch_size)?;
+    print_totals(&totals);
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use anchor_client::{
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::signer::Signer,
+    Program,
+};
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token_2022;
+use anyhow::{ensure, Result};
+use cp_amm::{
+    accounts, instruction,
+    state::{Pool, Position},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+};
+
+use crate::utils::{
+    derive_event_authority, derive_pool_authority, derive_position, fetch_accounts, get_clock,
+    get_mint_infos, get_or_create_ata_ix, print_totals, send_in_batches,
+};
+
+pub struct ClaimPositionFeeParams {
+    pub positions: Vec<Pubkey>,
+    pub all_pools: bool,
+    pub batch_size: usize,
+}
+
+/// Position nft accounts of owner, keyed by position. Position nfts are token 2022 tokens
+fn get_owner_positions(
+    rpc_client: &anchor_client::solana_client::rpc_client::RpcClient,
+    owner: &Pubkey,
+) -> Result<BTreeMap<Pubkey, Pubkey>> {
+    // token account layout: mint (32 bytes), owner (32 bytes), amount (8 bytes)
+    let token_accounts = rpc_client.get_program_accounts_with_config(
+        &token_2022::ID,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(32, owner.as_ref())),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(64, &1u64.to_le_bytes())),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+    let mut positions = BTreeMap::new();
+    for (token_account, account) in token_accounts {
+        let Some(mint) = account.data.get(..32) else {
+            continue;
+        };
+        let mint = Pubkey::try_from(mint)?;
+        positions.insert(derive_position(&mint), token_account);
+    }
+    Ok(positions)
+}
+
+pub fn claim_position_fee<C: Deref<Target = impl Signer> + Clone>(
+    params: ClaimPositionFeeParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let ClaimPositionFeeParams {
+        positions,
+        all_pools,
+        batch_size,
+    } = params;
+
+    let rpc_client = program.rpc();
+    let owner = program.payer();
+
+    let mut position_nft_accounts = get_owner_positions(&rpc_client, &owner)?;
+    if !all_pools {
+        for position in positions.iter() {
+            ensure!(
+                position_nft_accounts.contains_key(position),
+                "nft of position {position} is not held by {owner}"
+            );
+        }
+        position_nft_accounts.retain(|position, _| positions.contains(position));
+    }
+    let position_keys: Vec<Pubkey> = position_nft_accounts.keys().copied().collect();
+    // non position nfts don't have a position account
+    let positions = fetch_accounts::<Position>(&rpc_client, &position_keys)?;
+
+    let pool_keys: Vec<Pubkey> = positions
+        .iter()
+        .map(|(_, position)| position.pool)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let pools: BTreeMap<Pubkey, Pool> = fetch_accounts::<Pool>(&rpc_client, &pool_keys)?
+        .into_iter()
+        .collect();
+
+    let clock = get_clock(&rpc_client)?;
+    let mints: BTreeSet<Pubkey> = pools
+        .values()
+        .flat_map(|pool| [pool.token_a_mint, pool.token_b_mint])
+        .collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let mut totals = BTreeMap::new();
+    let mut item_instructions = vec![];
+    for (position, mut position_state) in positions {
+        // position with fee receipt claims fee with the receipt
+        if position_state.has_fee_receipt() {
+            continue;
+        }
+        let pool = position_state.pool;
+        let pool_state = &pools[&pool];
+        position_state.update_fee(
+            pool_state.fee_a_per_liquidity(),
+            pool_state.fee_b_per_liquidity(),
+        )?;
+        let fee_a = position_state.fee_a_pending;
+        let fee_b = position_state.fee_b_pending;
+        if fee_a == 0 && fee_b == 0 {
+            continue;
+        }
+
+        let fee_receiver = if position_state.has_fee_beneficiary() {
+            position_state.fee_beneficiary
+        } else {
+            owner
+        };
+        let token_a = &mint_infos[&pool_state.token_a_mint];
+        let token_b = &mint_infos[&pool_state.token_b_mint];
+        let (token_a_account, create_token_a_ata_ix) =
+            get_or_create_ata_ix(&rpc_client, &owner, &fee_receiver, token_a)?;
+        let (token_b_account, create_token_b_ata_ix) =
+            get_or_create_ata_ix(&rpc_client, &owner, &fee_receiver, token_b)?;
+
+        let mut instructions: Vec<_> = [create_token_a_ata_ix, create_token_b_ata_ix]
+            .into_iter()
+            .flatten()
+            .collect();
+        instructions.extend(
+            program
+                .request()
+                .accounts(accounts::ClaimPositionFeeCtx {
+                    pool_authority: derive_pool_authority(),
+                    pool,
+                    position,
+                    token_a_account,
+                    token_b_account,
+                    token_a_vault: pool_state.token_a_vault,
+                    token_b_vault: pool_state.token_b_vault,
+                    token_a_mint: pool_state.token_a_mint,
+                    token_b_mint: pool_state.token_b_mint,
+                    position_nft_account: position_nft_accounts[&position],
+                    owner,
+                    token_a_program: token_a.token_program,
+                    token_b_program: token_b.token_program,
+                    event_authority: derive_event_authority(),
+                    program: cp_amm::ID,
+                })
+                .args(instruction::ClaimPositionFee {
+                    max_amount_a: u64::MAX,
+                    max_amount_b: u64::MAX,
+                })
+                .instructions()?,
+        );
+        item_instructions.push(instructions);
+
+        println!(
+            "Claim fee of position {position}: {fee_a} {}, {fee_b} {}",
+            pool_state.token_a_mint, pool_state.token_b_mint
+        );
+        *totals.entry(pool_state.token_a_mint).or_insert(0) += fee_a;
+        *totals.entry(pool_state.token_b_mint).or_insert(0) += fee_b;
+    }
+
+    if item_instructions.is_empty() {
+        println!("No position fee to claim");
+        return Ok(());
+    }
+
+    send_in_batches(program, item_instructions, batch_size)?;
+    print_totals(&totals);
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cp_amm::{
+    accounts, instruction,
+    state::{ClaimFeeOperator, Pool},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+};
+
+use crate::utils::{
+    derive_claim_fee_operator, derive_event_authority, derive_insurance_fund,
+    derive_insurance_vault, derive_pool_authority, fetch_accounts, get_clock, get_mint_infos,
+    get_or_create_ata_ix, get_program_account_keys, print_totals, send_in_batches,
+};
+
+pub struct ClaimProtocolFeeParams {
+    pub pools: Vec<Pubkey>,
+    pub all_pools: bool,
+    pub settlement_mode: u8,
+    pub batch_size: usize,
+}
+
+pub fn claim_protocol_fee<C: Deref<Target = impl Signer> + Clone>(
+    params: ClaimProtocolFeeParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let ClaimProtocolFeeParams {
+        pools,
+        all_pools,
+        settlement_mode,
+        batch_size,
+    } = params;
+
+    let rpc_client = program.rpc();
+    let operator = program.payer();
+    let claim_fee_operator = derive_claim_fee_operator(&operator);
+    let claim_fee_operator_state: ClaimFeeOperator = program.account(claim_fee_operator)?;
+    let treasury = claim_fee_operator_state.get_treasury();
+
+    let pools = if all_pools {
+        get_program_account_keys::<Pool>(&rpc_client, vec![])?
+    } else {
+        pools
+    };
+    let pools: Vec<(Pubkey, Pool)> = fetch_accounts::<Pool>(&rpc_client, &pools)?
+        .into_iter()
+        .filter(|(_, pool)| pool.protocol_a_fee > 0 || pool.protocol_b_fee > 0)
+        .collect();
+
+    let clock = get_clock(&rpc_client)?;
+    let mints: BTreeSet<Pubkey> = pools
+        .iter()
+        .flat_map(|(_, pool)| [pool.token_a_mint, pool.token_b_mint])
+        .collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let mut totals = BTreeMap::new();
+    let mut item_instructions = vec![];
+    for (pool, pool_state) in pools {
+        let token_a = &mint_infos[&pool_state.token_a_mint];
+        let token_b = &mint_infos[&pool_state.token_b_mint];
+        let (token_a_account, create_token_a_ata_ix) =
+            get_or_create_ata_ix(&rpc_client, &operator, &treasury, token_a)?;
+        let (token_b_account, create_token_b_ata_ix) =
+            get_or_create_ata_ix(&rpc_client, &operator, &treasury, token_b)?;
+
+        // insurance share of protocol fee is routed to insurance vaults of pool
+        let insurance_fund =
+            (pool_state.pool_fees.insurance_fee_percent > 0).then(|| derive_insurance_fund(&pool));
+
+        let mut instructions: Vec<_> = [create_token_a_ata_ix, create_token_b_ata_ix]
+            .into_iter()
+            .flatten()
+            .collect();
+        instructions.extend(
+            program
+                .request()
+                .accounts(accounts::ClaimProtocolFeesCtx {
+                    pool_authority: derive_pool_authority(),
+                    pool,
+                    token_a_vault: pool_state.token_a_vault,
+                    token_b_vault: pool_state.token_b_vault,
+                    token_a_mint: pool_state.token_a_mint,
+                    token_b_mint: pool_state.token_b_mint,
+                    token_a_account,
+                    token_b_account,
+                    claim_fee_operator,
+                    operator,
+                    token_a_program: token_a.token_program,
+                    token_b_program: token_b.token_program,
+                    insurance_fund,
+                    insurance_token_a_vault: insurance_fund.map(|insurance_fund| {
+                        derive_insurance_vault(&insurance_fund, &pool_state.token_a_mint)
+                    }),
+                    insurance_token_b_vault: insurance_fund.map(|insurance_fund| {
+                        derive_insurance_vault(&insurance_fund, &pool_state.token_b_mint)
+                    }),
+                    event_authority: derive_event_authority(),
+                    program: cp_amm::ID,
+                })
+                .args(instruction::ClaimProtocolFee {
+                    max_amount_a: u64::MAX,
+                    max_amount_b: u64::MAX,
+                    settlement_mode,
+                    minimum_settlement_amount: 0,
+                })
+                .instructions()?,
+        );
+        item_instructions.push(instructions);
+
+        println!(
+            "Claim protocol fee of pool {pool}: {} {}, {} {}",
+            pool_state.protocol_a_fee,
+            pool_state.token_a_mint,
+            pool_state.protocol_b_fee,
+            pool_state.token_b_mint
+        );
+        *totals.entry(pool_state.token_a_mint).or_insert(0) += pool_state.protocol_a_fee;
+        *totals.entry(pool_state.token_b_mint).or_insert(0) += pool_state.protocol_b_fee;
+    }
+
+    if item_instructions.is_empty() {
+        println!("No protocol fee to claim");
+        return Ok(());
+    }
+
+    send_in_batches(program, item_instructions, batch_size)?;
+    print_totals(&totals);
+
+    Ok(())
+}
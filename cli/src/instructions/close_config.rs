@@ -0,0 +1,31 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cp_amm::{accounts, instruction};
+use std::ops::Deref;
+
+use crate::utils::{derive_event_authority, derive_program_authority, send_or_print_multisig};
+
+pub fn close_config<C: Deref<Target = impl Signer> + Clone>(
+    config: Pubkey,
+    multisig: Option<Pubkey>,
+    program: &Program<C>,
+) -> Result<()> {
+    let admin = multisig.unwrap_or(program.payer());
+    let request = program
+        .request()
+        .accounts(accounts::CloseConfigCtx {
+            config,
+            program_authority: derive_program_authority(),
+            admin,
+            rent_receiver: admin,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .args(instruction::CloseConfig {});
+    if let Some(signature) = send_or_print_multisig(request, multisig)? {
+        println!("Close config {config}. Signature: {signature:#?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::{prelude::Pubkey, system_program};
+use anyhow::{ensure, Result};
+use cp_amm::{
+    accounts, instruction,
+    params::fee_parameters::{BaseFeeParameters, DynamicFeeParameters, PoolFeeParameters},
+    StaticConfigParameters,
+};
+use std::ops::Deref;
+
+use crate::{
+    args::DynamicFeeArgs,
+    utils::{
+        derive_config, derive_event_authority, derive_program_authority, send_or_print_multisig,
+    },
+};
+
+pub struct CreateConfigParams {
+    pub index: u64,
+    pub sqrt_min_price: u128,
+    pub sqrt_max_price: u128,
+    pub vault_config_key: Pubkey,
+    pub pool_creator_authority: Pubkey,
+    pub activation_type: u8,
+    pub collect_fee_mode: u8,
+    pub trade_fee_numerator: u64,
+    pub protocol_fee_percent: u8,
+    pub partner_fee_percent: u8,
+    pub referral_fee_percent: u8,
+    pub dynamic_fee: DynamicFeeArgs,
+    pub multisig: Option<Pubkey>,
+}
+
+pub fn get_dynamic_fee_parameters(args: &DynamicFeeArgs) -> Result<Option<DynamicFeeParameters>> {
+    let Some(filter_period) = args.filter_period else {
+        return Ok(None);
+    };
+    let (
+        Some(decay_period),
+        Some(reduction_factor),
+        Some(variable_fee_control),
+        Some(max_volatility_accumulator),
+    ) = (
+        args.decay_period,
+        args.reduction_factor,
+        args.variable_fee_control,
+        args.max_volatility_accumulator,
+    )
+    else {
+        anyhow::bail!("All dynamic fee parameters are required when filter period is set");
+    };
+
+    // bin step in Q64 format
+    let bin_step_u128 = (u128::from(args.bin_step) << 64) / 10_000;
+
+    let dynamic_fee = DynamicFeeParameters {
+        bin_step: args.bin_step,
+        bin_step_u128,
+        filter_period,
+        decay_period,
+        reduction_factor,
+        max_volatility_accumulator,
+        variable_fee_control,
+    };
+    ensure!(
+        dynamic_fee.validate().is_ok(),
+        "Invalid dynamic fee parameters"
+    );
+
+    Ok(Some(dynamic_fee))
+}
+
+pub fn create_config<C: Deref<Target = impl Signer> + Clone>(
+    params: CreateConfigParams,
+    program: &Program<C>,
+) -> Result<Pubkey> {
+    let CreateConfigParams {
+        index,
+        sqrt_min_price,
+        sqrt_max_price,
+        vault_config_key,
+        pool_creator_authority,
+        activation_type,
+        collect_fee_mode,
+        trade_fee_numerator,
+        protocol_fee_percent,
+        partner_fee_percent,
+        referral_fee_percent,
+        dynamic_fee,
+        multisig,
+    } = params;
+
+    let config_parameters = StaticConfigParameters {
+        pool_fees: PoolFeeParameters {
+            base_fee: BaseFeeParameters {
+                cliff_fee_numerator: trade_fee_numerator,
+                ..Default::default()
+            },
+            protocol_fee_percent,
+            partner_fee_percent,
+            referral_fee_percent,
+            dynamic_fee: get_dynamic_fee_parameters(&dynamic_fee)?,
+        },
+        sqrt_min_price,
+        sqrt_max_price,
+        vault_config_key,
+        pool_creator_authority,
+        activation_type,
+        collect_fee_mode,
+    };
+
+    send_create_config(program, index, config_parameters, multisig)
+}
+
+/// Create config of index, returns the config address
+pub fn send_create_config<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    index: u64,
+    config_parameters: StaticConfigParameters,
+    multisig: Option<Pubkey>,
+) -> Result<Pubkey> {
+    let config = derive_config(index);
+    let request = program
+        .request()
+        .accounts(accounts::CreateConfigCtx {
+            config,
+            program_authority: derive_program_authority(),
+            admin: multisig.unwrap_or(program.payer()),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .args(instruction::CreateConfig {
+            index,
+            config_parameters,
+        });
+    if let Some(signature) = send_or_print_multisig(request, multisig)? {
+        println!("Create config {config}. Signature: {signature:#?}");
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cp_amm::constants::{BIN_STEP_BPS_DEFAULT, BIN_STEP_BPS_U128_DEFAULT};
+
+    #[test]
+    fn test_get_dynamic_fee_parameters() {
+        let mut args = DynamicFeeArgs {
+            bin_step: BIN_STEP_BPS_DEFAULT,
+            filter_period: None,
+            decay_period: Some(120),
+            reduction_factor: Some(5000),
+            variable_fee_control: Some(2_000_000),
+            max_volatility_accumulator: Some(100_000),
+        };
+        assert!(get_dynamic_fee_parameters(&args).unwrap().is_none());
+
+        args.filter_period = Some(10);
+        let dynamic_fee = get_dynamic_fee_parameters(&args).unwrap().unwrap();
+        assert_eq!(dynamic_fee.bin_step_u128, BIN_STEP_BPS_U128_DEFAULT);
+
+        // filter period must be less than decay period
+        args.filter_period = Some(120);
+        assert!(get_dynamic_fee_parameters(&args).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use cp_amm::{
+    constants::BIN_STEP_BPS_DEFAULT,
+    params::fee_parameters::{BaseFeeParameters, PoolFeeParameters},
+    state::Config,
+    StaticConfigParameters,
+};
+use serde::Deserialize;
+use std::{collections::BTreeSet, ops::Deref, path::Path};
+
+use crate::{
+    args::DynamicFeeArgs,
+    instructions::{get_dynamic_fee_parameters, send_create_config},
+    utils::{derive_config, deserialize_pubkey, fetch_accounts},
+};
+
+/// Config of a manifest, fields are the same as `create-config` flags
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigManifestEntry {
+    pub index: u64,
+    pub sqrt_min_price: u128,
+    pub sqrt_max_price: u128,
+    #[serde(default, deserialize_with = "deserialize_pubkey")]
+    pub vault_config_key: Pubkey,
+    #[serde(default, deserialize_with = "deserialize_pubkey")]
+    pub pool_creator_authority: Pubkey,
+    /// 0: slot, 1: timestamp
+    pub activation_type: u8,
+    pub collect_fee_mode: u8,
+    pub base_fee: BaseFeeManifest,
+    pub protocol_fee_percent: u8,
+    #[serde(default)]
+    pub partner_fee_percent: u8,
+    #[serde(default)]
+    pub referral_fee_percent: u8,
+    pub dynamic_fee: Option<DynamicFeeManifest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaseFeeManifest {
+    pub cliff_fee_numerator: u64,
+    #[serde(default)]
+    pub number_of_period: u16,
+    #[serde(default)]
+    pub period_frequency: u64,
+    #[serde(default)]
+    pub reduction_factor: u64,
+    /// 0: linear, 1: exponential
+    #[serde(default)]
+    pub fee_scheduler_mode: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicFeeManifest {
+    #[serde(default = "default_bin_step")]
+    pub bin_step: u16,
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub variable_fee_control: u32,
+    pub max_volatility_accumulator: u32,
+}
+
+fn default_bin_step() -> u16 {
+    BIN_STEP_BPS_DEFAULT
+}
+
+impl ConfigManifestEntry {
+    pub fn to_static_config_parameters(&self) -> Result<StaticConfigParameters> {
+        let dynamic_fee = match &self.dynamic_fee {
+            Some(dynamic_fee) => get_dynamic_fee_parameters(&DynamicFeeArgs {
+                bin_step: dynamic_fee.bin_step,
+                filter_period: Some(dynamic_fee.filter_period),
+                decay_period: Some(dynamic_fee.decay_period),
+                reduction_factor: Some(dynamic_fee.reduction_factor),
+                variable_fee_control: Some(dynamic_fee.variable_fee_control),
+                max_volatility_accumulator: Some(dynamic_fee.max_volatility_accumulator),
+            })?,
+            None => None,
+        };
+
+        Ok(StaticConfigParameters {
+            pool_fees: PoolFeeParameters {
+                base_fee: BaseFeeParameters {
+                    cliff_fee_numerator: self.base_fee.cliff_fee_numerator,
+                    number_of_period: self.base_fee.number_of_period,
+                    period_frequency: self.base_fee.period_frequency,
+                    reduction_factor: self.base_fee.reduction_factor,
+                    fee_scheduler_mode: self.base_fee.fee_scheduler_mode,
+                },
+                protocol_fee_percent: self.protocol_fee_percent,
+                partner_fee_percent: self.partner_fee_percent,
+                referral_fee_percent: self.referral_fee_percent,
+                dynamic_fee,
+            },
+            sqrt_min_price: self.sqrt_min_price,
+            sqrt_max_price: self.sqrt_max_price,
+            vault_config_key: self.vault_config_key,
+            pool_creator_authority: self.pool_creator_authority,
+            activation_type: self.activation_type,
+            collect_fee_mode: self.collect_fee_mode,
+        })
+    }
+}
+
+/// Parse a manifest of configs, YAML if the file extension is `yaml` or `yml`, JSON otherwise
+pub fn parse_config_manifest(path: &Path, content: &str) -> Result<Vec<ConfigManifestEntry>> {
+    let is_yaml = path
+        .extension()
+        .is_some_and(|extension| extension == "yaml" || extension == "yml");
+    if is_yaml {
+        Ok(serde_yaml::from_str(content)?)
+    } else {
+        Ok(serde_json::from_str(content)?)
+    }
+}
+
+/// Validate configs of a manifest with the program validation, returns the config parameters by index
+pub fn validate_config_manifest(
+    entries: &[ConfigManifestEntry],
+) -> Result<Vec<(u64, StaticConfigParameters)>> {
+    let mut indexes = BTreeSet::new();
+    let mut configs = vec![];
+    for entry in entries {
+        if !indexes.insert(entry.index) {
+            bail!("config index {} is duplicated", entry.index);
+        }
+        let config_parameters = entry
+            .to_static_config_parameters()
+            .with_context(|| format!("invalid config {}", entry.index))?;
+        config_parameters
+            .validate()
+            .map_err(|err| anyhow!("invalid config {}: {err}", entry.index))?;
+        configs.push((entry.index, config_parameters));
+    }
+    Ok(configs)
+}
+
+pub struct CreateConfigBatchParams {
+    pub manifest: String,
+    pub multisig: Option<Pubkey>,
+}
+
+pub fn create_config_batch<C: Deref<Target = impl Signer> + Clone>(
+    params: CreateConfigBatchParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let CreateConfigBatchParams { manifest, multisig } = params;
+
+    let content =
+        std::fs::read_to_string(&manifest).with_context(|| format!("failed to read {manifest}"))?;
+    let entries = parse_config_manifest(Path::new(&manifest), &content)
+        .with_context(|| format!("failed to parse {manifest}"))?;
+    // nothing is sent unless every config is valid
+    let configs = validate_config_manifest(&entries)?;
+
+    // configs created by a previous run are skipped, so an interrupted batch can be run again
+    let rpc_client = program.rpc();
+    let config_keys: Vec<Pubkey> = configs
+        .iter()
+        .map(|(index, _)| derive_config(*index))
+        .collect();
+    let existing_configs: BTreeSet<Pubkey> = fetch_accounts::<Config>(&rpc_client, &config_keys)?
+        .into_iter()
+        .map(|(config, _)| config)
+        .collect();
+
+    let total = configs.len();
+    for (i, (index, config_parameters)) in configs.into_iter().enumerate() {
+        let config = derive_config(index);
+        if existing_configs.contains(&config) {
+            println!(
+                "[{}/{total}] Config {index} {config} exists, skipped",
+                i + 1
+            );
+            continue;
+        }
+        println!("[{}/{total}] Create config {index}", i + 1);
+        send_create_config(program, index, config_parameters, multisig).with_context(|| {
+            format!("failed to create config {index}, run again to resume from it")
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cp_amm::constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+
+    #[test]
+    fn test_validate_config_manifest() {
+        let yaml = format!(
+            "
+- index: 100
+  sqrt_min_price: {MIN_SQRT_PRICE}
+  sqrt_max_price: {MAX_SQRT_PRICE}
+  activation_type: 1
+  collect_fee_mode: 0
+  base_fee:
+    cliff_fee_numerator: 2500000
+  protocol_fee_percent: 20
+- index: 101
+  sqrt_min_price: {MIN_SQRT_PRICE}
+  sqrt_max_price: {MAX_SQRT_PRICE}
+  activation_type: 0
+  collect_fee_mode: 1
+  base_fee:
+    cliff_fee_numerator: 10000000
+  protocol_fee_percent: 20
+  dynamic_fee:
+    filter_period: 10
+    decay_period: 120
+    reduction_factor: 5000
+    variable_fee_control: 2000000
+    max_volatility_accumulator: 100000
+"
+        );
+        let entries = parse_config_manifest(Path::new("configs.yaml"), &yaml).unwrap();
+        let configs = validate_config_manifest(&entries).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert!(configs[1].1.pool_fees.dynamic_fee.is_some());
+
+        let json = format!(
+            r#"[{{
+                "index": 102,
+                "sqrt_min_price": {MIN_SQRT_PRICE},
+                "sqrt_max_price": {MAX_SQRT_PRICE},
+                "activation_type": 0,
+                "collect_fee_mode": 0,
+                "base_fee": {{ "cliff_fee_numerator": 2500000 }},
+                "protocol_fee_percent": 20,
+                "partner_fee_percent": 10
+            }}]"#
+        );
+        let entries = parse_config_manifest(Path::new("configs.json"), &json).unwrap();
+        // partner fee requires pool creator authority
+        assert!(validate_config_manifest(&entries).is_err());
+
+        // duplicated index
+        let mut entries = parse_config_manifest(Path::new("configs.yaml"), &yaml).unwrap();
+        entries[1].index = 100;
+        assert!(validate_config_manifest(&entries).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use cp_amm::{
+    constants::{NUM_REWARDS, REWARD_RATE_SCALE},
+    state::{Pool, PoolMetrics},
+};
+use std::{
+    collections::BTreeSet,
+    fs::OpenOptions,
+    io::{Read, Write},
+    net::TcpListener,
+    ops::Deref,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    instructions::get_price_from_sqrt_price,
+    utils::{fetch_accounts, get_clock, get_mint_infos},
+};
+
+/// Metrics of a pool at a point of time
+#[derive(Debug)]
+pub struct PoolSample {
+    pub pool: Pubkey,
+    pub timestamp: i64,
+    /// Price of token a in token b, adjusted by decimals
+    pub price: f64,
+    pub liquidity: u128,
+    pub metrics: PoolMetrics,
+    /// Reward tokens emitted per second of every reward, 0 when the reward is not active
+    pub reward_rates: [f64; NUM_REWARDS],
+}
+
+impl PoolSample {
+    pub fn new(
+        pool: Pubkey,
+        pool_state: &Pool,
+        decimals_a: u8,
+        decimals_b: u8,
+        timestamp: i64,
+    ) -> Self {
+        let mut reward_rates = [0f64; NUM_REWARDS];
+        for (reward_rate, reward_info) in reward_rates.iter_mut().zip(pool_state.reward_infos) {
+            let is_active = reward_info.initialized()
+                && u64::try_from(timestamp).unwrap_or_default() < reward_info.reward_duration_end;
+            if is_active {
+                *reward_rate =
+                    reward_info.reward_rate as f64 / 2f64.powi(i32::from(REWARD_RATE_SCALE));
+            }
+        }
+        PoolSample {
+            pool,
+            timestamp,
+            price: get_price_from_sqrt_price(pool_state.sqrt_price, decimals_a, decimals_b),
+            liquidity: pool_state.liquidity,
+            metrics: pool_state.metrics,
+            reward_rates,
+        }
+    }
+}
+
+/// Samples in Prometheus text exposition format
+pub fn format_prometheus(samples: &[PoolSample]) -> String {
+    let mut output = String::new();
+    let mut gauge = |name: &str, help: &str, values: Vec<(String, String)>| {
+        output += &format!("# HELP cp_amm_pool_{name} {help}\n# TYPE cp_amm_pool_{name} gauge\n");
+        for (labels, value) in values {
+            output += &format!("cp_amm_pool_{name}{{{labels}}} {value}\n");
+        }
+    };
+    let by_pool = |value: &dyn Fn(&PoolSample) -> String| {
+        samples
+            .iter()
+            .map(|sample| (format!("pool=\"{}\"", sample.pool), value(sample)))
+            .collect()
+    };
+    let by_token = |value_a: &dyn Fn(&PoolSample) -> String,
+                    value_b: &dyn Fn(&PoolSample) -> String| {
+        samples
+            .iter()
+            .flat_map(|sample| {
+                [
+                    (
+                        format!("pool=\"{}\",token=\"a\"", sample.pool),
+                        value_a(sample),
+                    ),
+                    (
+                        format!("pool=\"{}\",token=\"b\"", sample.pool),
+                        value_b(sample),
+                    ),
+                ]
+            })
+            .collect()
+    };
+
+    gauge(
+        "price",
+        "Price of token a in token b",
+        by_pool(&|sample| sample.price.to_string()),
+    );
+    gauge(
+        "liquidity",
+        "Liquidity of pool",
+        by_pool(&|sample| sample.liquidity.to_string()),
+    );
+    gauge(
+        "total_lp_fee",
+        "Total fee of liquidity providers",
+        by_token(
+            &|sample| sample.metrics.total_lp_a_fee.to_string(),
+            &|sample| sample.metrics.total_lp_b_fee.to_string(),
+        ),
+    );
+    gauge(
+        "total_protocol_fee",
+        "Total protocol fee",
+        by_token(
+            &|sample| sample.metrics.total_protocol_a_fee.to_string(),
+            &|sample| sample.metrics.total_protocol_b_fee.to_string(),
+        ),
+    );
+    gauge(
+        "total_partner_fee",
+        "Total partner fee",
+        by_token(
+            &|sample| sample.metrics.total_partner_a_fee.to_string(),
+            &|sample| sample.metrics.total_partner_b_fee.to_string(),
+        ),
+    );
+    gauge(
+        "total_position",
+        "Number of positions",
+        by_pool(&|sample| sample.metrics.total_position.to_string()),
+    );
+    gauge(
+        "reward_rate",
+        "Reward tokens emitted per second",
+        samples
+            .iter()
+            .flat_map(|sample| {
+                sample
+                    .reward_rates
+                    .iter()
+                    .enumerate()
+                    .map(|(index, reward_rate)| {
+                        (
+                            format!("pool=\"{}\",reward_index=\"{index}\"", sample.pool),
+                            reward_rate.to_string(),
+                        )
+                    })
+            })
+            .collect(),
+    );
+    output
+}
+
+pub fn csv_header() -> String {
+    let mut columns = vec![
+        "timestamp",
+        "pool",
+        "price",
+        "liquidity",
+        "total_lp_a_fee",
+        "total_lp_b_fee",
+        "total_protocol_a_fee",
+        "total_protocol_b_fee",
+        "total_partner_a_fee",
+        "total_partner_b_fee",
+        "total_position",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect::<Vec<String>>();
+    columns.extend((0..NUM_REWARDS).map(|index| format!("reward_rate_{index}")));
+    columns.join(",")
+}
+
+pub fn format_csv_row(sample: &PoolSample) -> String {
+    let metrics = &sample.metrics;
+    let mut columns = vec![
+        sample.timestamp.to_string(),
+        sample.pool.to_string(),
+        sample.price.to_string(),
+        sample.liquidity.to_string(),
+        metrics.total_lp_a_fee.to_string(),
+        metrics.total_lp_b_fee.to_string(),
+        metrics.total_protocol_a_fee.to_string(),
+        metrics.total_protocol_b_fee.to_string(),
+        metrics.total_partner_a_fee.to_string(),
+        metrics.total_partner_b_fee.to_string(),
+        metrics.total_position.to_string(),
+    ];
+    columns.extend(sample.reward_rates.iter().map(|rate| rate.to_string()));
+    columns.join(",")
+}
+
+/// Serve the latest metrics to every request, e.g. `GET /metrics` of a Prometheus scraper
+fn serve_prometheus(address: &str, body: Arc<Mutex<String>>) -> Result<()> {
+    let listener =
+        TcpListener::bind(address).with_context(|| format!("failed to listen on {address}"))?;
+    println!("Serving Prometheus metrics on {address}");
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let body = body.lock().map(|body| body.clone()).unwrap_or_default();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    Ok(())
+}
+
+pub struct ExportMetricsParams {
+    pub pools: Vec<Pubkey>,
+    pub interval_seconds: u64,
+    pub prometheus: Option<String>,
+    pub csv: Option<String>,
+}
+
+/// Scrape metrics of pools every interval, exposed on a Prometheus endpoint and/or appended to a
+/// CSV file. A failed scrape is printed and retried in the next interval
+pub fn export_metrics<C: Deref<Target = impl Signer> + Clone>(
+    params: ExportMetricsParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let ExportMetricsParams {
+        pools,
+        interval_seconds,
+        prometheus,
+        csv,
+    } = params;
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let pool_states = fetch_accounts::<Pool>(&rpc_client, &pools)?;
+    let mints: BTreeSet<Pubkey> = pool_states
+        .iter()
+        .flat_map(|(_, pool)| [pool.token_a_mint, pool.token_b_mint])
+        .collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let prometheus_body = Arc::new(Mutex::new(String::new()));
+    if let Some(address) = &prometheus {
+        serve_prometheus(address, prometheus_body.clone())?;
+    }
+    let mut csv_file = match &csv {
+        Some(csv) => {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(csv)
+                .with_context(|| format!("failed to open {csv}"))?;
+            if file.metadata()?.len() == 0 {
+                writeln!(file, "{}", csv_header())?;
+            }
+            Some(file)
+        }
+        None => None,
+    };
+
+    loop {
+        let scrape = || -> Result<Vec<PoolSample>> {
+            let clock = get_clock(&rpc_client)?;
+            fetch_accounts::<Pool>(&rpc_client, &pools)?
+                .into_iter()
+                .map(|(pool, pool_state)| {
+                    Ok(PoolSample::new(
+                        pool,
+                        &pool_state,
+                        mint_infos[&pool_state.token_a_mint].decimals,
+                        mint_infos[&pool_state.token_b_mint].decimals,
+                        clock.unix_timestamp,
+                    ))
+                })
+                .collect()
+        };
+        match scrape() {
+            Ok(samples) => {
+                if let Ok(mut body) = prometheus_body.lock() {
+                    *body = format_prometheus(&samples);
+                }
+                if let Some(file) = csv_file.as_mut() {
+                    for sample in samples.iter() {
+                        writeln!(file, "{}", format_csv_row(sample))?;
+                    }
+                }
+                println!("Scraped metrics of {} pool(s)", samples.len());
+            }
+            Err(err) => println!("Failed to scrape metrics: {err:#}"),
+        }
+        thread::sleep(Duration::from_secs(interval_seconds));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_metrics() {
+        let mut pool_state = Pool {
+            sqrt_price: 1 << 64,
+            liquidity: 1_000,
+            ..Default::default()
+        };
+        pool_state.metrics.total_lp_a_fee = 10;
+        pool_state.metrics.total_position = 2;
+        pool_state.reward_infos[1].initialized = 1;
+        pool_state.reward_infos[1].reward_duration_end = 200;
+        pool_state.reward_infos[1].reward_rate = 5 << 64;
+        let pool = Pubkey::new_unique();
+
+        let sample = PoolSample::new(pool, &pool_state, 6, 6, 100);
+        assert_eq!(sample.price, 1.0);
+        assert_eq!(sample.reward_rates, [0.0, 5.0]);
+        // reward ended
+        let ended_sample = PoolSample::new(pool, &pool_state, 6, 6, 200);
+        assert_eq!(ended_sample.reward_rates, [0.0, 0.0]);
+
+        let prometheus = format_prometheus(&[sample]);
+        assert!(prometheus.contains("# TYPE cp_amm_pool_price gauge\n"));
+        assert!(prometheus.contains(&format!("cp_amm_pool_liquidity{{pool=\"{pool}\"}} 1000\n")));
+        assert!(prometheus.contains(&format!(
+            "cp_amm_pool_total_lp_fee{{pool=\"{pool}\",token=\"a\"}} 10\n"
+        )));
+        assert!(prometheus.contains(&format!(
+            "cp_amm_pool_reward_rate{{pool=\"{pool}\",reward_index=\"1\"}} 5\n"
+        )));
+
+        let sample = PoolSample::new(pool, &pool_state, 6, 6, 100);
+        let row = format_csv_row(&sample);
+        assert_eq!(row.split(',').count(), csv_header().split(',').count());
+        assert!(row.starts_with(&format!("100,{pool},1,1000,10,")));
+    }
+}
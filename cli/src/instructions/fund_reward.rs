@@ -0,0 +1,74 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{ensure, Result};
+use cp_amm::{accounts, instruction, state::Pool};
+use std::ops::Deref;
+
+use crate::utils::{
+    derive_event_authority, derive_program_authority, get_clock, get_mint_info, get_reward_info,
+    get_reward_remaining_accounts, send_request,
+};
+
+pub struct FundRewardParams {
+    pub pool: Pubkey,
+    pub reward_index: u8,
+    pub amount: u64,
+    pub carry_forward: bool,
+}
+
+pub fn fund_reward<C: Deref<Target = impl Signer> + Clone>(
+    params: FundRewardParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let FundRewardParams {
+        pool,
+        reward_index,
+        amount,
+        carry_forward,
+    } = params;
+
+    let pool_state: Pool = program.account(pool)?;
+    let reward_info = get_reward_info(program, pool, &pool_state, reward_index)?;
+    ensure!(
+        reward_info.initialized(),
+        "reward {reward_index} of pool {pool} is not initialized"
+    );
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let reward_mint = get_mint_info(&rpc_client, reward_info.mint, clock.epoch)?;
+    let funder = program.payer();
+    let funder_token_account = get_associated_token_address_with_program_id(
+        &funder,
+        &reward_mint.mint,
+        &reward_mint.token_program,
+    );
+
+    let request = program
+        .request()
+        .accounts(accounts::FundRewardCtx {
+            pool,
+            reward_vault: reward_info.vault,
+            reward_mint: reward_info.mint,
+            funder_token_account,
+            program_authority: derive_program_authority(),
+            funder,
+            token_program: reward_mint.token_program,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .accounts(get_reward_remaining_accounts(&pool, reward_index))
+        .args(instruction::FundReward {
+            reward_index,
+            amount,
+            carry_forward,
+        });
+    if let Some(signature) = send_request(request)? {
+        println!(
+            "Fund {amount} to reward {reward_index} of pool {pool}. Signature: {signature:#?}"
+        );
+    }
+
+    Ok(())
+}
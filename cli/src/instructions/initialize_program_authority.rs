@@ -0,0 +1,30 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::{prelude::Pubkey, system_program};
+use anyhow::Result;
+use cp_amm::{accounts, instruction, InitializeProgramAuthorityParameters};
+use std::ops::Deref;
+
+use crate::utils::{derive_event_authority, derive_program_authority, send_or_print_multisig};
+
+pub fn initialize_program_authority<C: Deref<Target = impl Signer> + Clone>(
+    params: InitializeProgramAuthorityParameters,
+    multisig: Option<Pubkey>,
+    program: &Program<C>,
+) -> Result<()> {
+    let program_authority = derive_program_authority();
+    let request = program
+        .request()
+        .accounts(accounts::InitializeProgramAuthorityCtx {
+            program_authority,
+            admin: multisig.unwrap_or(program.payer()),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .args(instruction::InitializeProgramAuthority { params });
+    if let Some(signature) = send_or_print_multisig(request, multisig)? {
+        println!("Initialize program authority {program_authority}. Signature: {signature:#?}");
+    }
+
+    Ok(())
+}
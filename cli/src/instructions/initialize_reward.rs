@@ -0,0 +1,79 @@
+use anchor_client::{
+    solana_sdk::{instruction::AccountMeta, signer::Signer},
+    Program,
+};
+use anchor_lang::{prelude::Pubkey, system_program};
+use anyhow::Result;
+use cp_amm::{accounts, constants::treasury, instruction};
+use std::ops::Deref;
+
+use crate::utils::{
+    derive_event_authority, derive_pool_authority, derive_program_authority, derive_reward_vault,
+    derive_token_badge, get_clock, get_mint_info, get_reward_remaining_accounts, send_request,
+};
+
+pub struct InitializeRewardParams {
+    pub pool: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub reward_duration: u64,
+    pub funder: Option<Pubkey>,
+    pub claim_vesting_duration: u32,
+}
+
+pub fn initialize_reward<C: Deref<Target = impl Signer> + Clone>(
+    params: InitializeRewardParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let InitializeRewardParams {
+        pool,
+        reward_index,
+        reward_mint,
+        reward_duration,
+        funder,
+        claim_vesting_duration,
+    } = params;
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let reward_mint_info = get_mint_info(&rpc_client, reward_mint, clock.epoch)?;
+    let funder = funder.unwrap_or(program.payer());
+
+    let mut remaining_accounts = get_reward_remaining_accounts(&pool, reward_index);
+    // token badge is only required for mints with extensions not supported by default
+    let token_badge = derive_token_badge(&reward_mint);
+    if rpc_client.get_account(&token_badge).is_ok() {
+        remaining_accounts.push(AccountMeta::new_readonly(token_badge, false));
+    }
+
+    let reward_vault = derive_reward_vault(&pool, reward_index);
+    let request = program
+        .request()
+        .accounts(accounts::InitializeRewardCtx {
+            pool_authority: derive_pool_authority(),
+            pool,
+            reward_vault,
+            reward_mint,
+            program_authority: derive_program_authority(),
+            payer: program.payer(),
+            treasury: treasury::ID,
+            token_program: reward_mint_info.token_program,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .accounts(remaining_accounts)
+        .args(instruction::InitializeReward {
+            reward_index,
+            reward_duration,
+            funder,
+            claim_vesting_duration,
+        });
+    if let Some(signature) = send_request(request)? {
+        println!(
+            "Initialize reward {reward_index} of pool {pool}, reward vault {reward_vault}. Signature: {signature:#?}"
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,425 @@
+use anchor_client::{
+    solana_client::rpc_filter::Memcmp,
+    solana_sdk::{instruction::AccountMeta, signer::Signer},
+    Program,
+};
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use anyhow::{Context, Result};
+use cp_amm::{
+    accounts, instruction,
+    state::{BuybackBurn, Pool, Position, Vesting},
+};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem::offset_of,
+    ops::Deref,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    instructions::{
+        claim_partner_fee, claim_protocol_fee, ClaimPartnerFeeParams, ClaimProtocolFeeParams,
+    },
+    utils::{
+        derive_buyback_burn, derive_event_authority, derive_pool_authority,
+        derive_position_reward_extension, derive_reward_extension, deserialize_pubkeys,
+        fetch_accounts, get_clock, get_current_point, get_mint_infos, get_position_nft_holder,
+        get_program_account_keys, send_in_batches,
+    },
+};
+
+/// Delay before retrying a failed task, the retry sends a new transaction with a fresh blockhash
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeeperConfig {
+    /// Seconds between rounds
+    pub interval_seconds: u64,
+    /// Retries of a failed task in a round
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Number of items per transaction
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    pub update_rewards: Option<UpdateRewardsTask>,
+    pub refresh_vesting: Option<RefreshVestingTask>,
+    pub claim_protocol_fee: Option<ClaimFeeTask>,
+    pub claim_partner_fee: Option<ClaimFeeTask>,
+    pub buyback_and_burn: Option<BuybackAndBurnTask>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateRewardsTask {
+    #[serde(deserialize_with = "deserialize_pubkeys")]
+    pub pools: Vec<Pubkey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BuybackAndBurnTask {
+    #[serde(deserialize_with = "deserialize_pubkeys")]
+    pub pools: Vec<Pubkey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefreshVestingTask {
+    #[serde(deserialize_with = "deserialize_pubkeys")]
+    pub positions: Vec<Pubkey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaimFeeTask {
+    #[serde(deserialize_with = "deserialize_pubkeys")]
+    pub pools: Vec<Pubkey>,
+    /// Claim when fee of token a reaches this amount
+    #[serde(default)]
+    pub min_amount_a: u64,
+    /// Claim when fee of token b reaches this amount
+    #[serde(default)]
+    pub min_amount_b: u64,
+}
+
+impl ClaimFeeTask {
+    fn is_above_threshold(&self, amount_a: u64, amount_b: u64) -> bool {
+        (amount_a > 0 || amount_b > 0)
+            && (amount_a >= self.min_amount_a || amount_b >= self.min_amount_b)
+    }
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_batch_size() -> usize {
+    3
+}
+
+impl KeeperConfig {
+    pub fn load(path: &str) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("failed to read {path}"))?;
+        toml::from_str(&content).with_context(|| format!("failed to parse {path}"))
+    }
+}
+
+pub struct KeeperParams {
+    pub config: String,
+    pub once: bool,
+}
+
+pub fn keeper<C: Deref<Target = impl Signer> + Clone>(
+    params: KeeperParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let KeeperParams { config, once } = params;
+    let config = KeeperConfig::load(&config)?;
+
+    loop {
+        if let Some(task) = &config.update_rewards {
+            run_with_retries(&config, "update rewards", || {
+                crank_update_rewards(program, task, config.batch_size)
+            });
+        }
+        if let Some(task) = &config.refresh_vesting {
+            run_with_retries(&config, "refresh vesting", || {
+                crank_refresh_vesting(program, task, config.batch_size)
+            });
+        }
+        if let Some(task) = &config.claim_protocol_fee {
+            run_with_retries(&config, "claim protocol fee", || {
+                let pools = get_pools_above_threshold(program, task, |pool| {
+                    (pool.protocol_a_fee, pool.protocol_b_fee)
+                })?;
+                claim_protocol_fee(
+                    ClaimProtocolFeeParams {
+                        pools,
+                        all_pools: false,
+                        settlement_mode: 0,
+                        batch_size: config.batch_size,
+                    },
+                    program,
+                )
+            });
+        }
+        if let Some(task) = &config.buyback_and_burn {
+            run_with_retries(&config, "buyback and burn", || {
+                crank_buyback_and_burn(program, task, config.batch_size)
+            });
+        }
+        if let Some(task) = &config.claim_partner_fee {
+            run_with_retries(&config, "claim partner fee", || {
+                let pools = get_pools_above_threshold(program, task, |pool| {
+                    (pool.partner_a_fee, pool.partner_b_fee)
+                })?;
+                claim_partner_fee(
+                    ClaimPartnerFeeParams {
+                        pools,
+                        all_pools: false,
+                        batch_size: config.batch_size,
+                    },
+                    program,
+                )
+            });
+        }
+
+        if once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(config.interval_seconds));
+    }
+}
+
+/// Run a task, retrying on failure. Task state is fetched again on every attempt, so items
+/// completed by a failed attempt are skipped. Errors are printed, the keeper keeps running
+fn run_with_retries(config: &KeeperConfig, name: &str, mut task: impl FnMut() -> Result<()>) {
+    for attempt in 0..=config.max_retries {
+        match task() {
+            Ok(()) => return,
+            Err(err) => {
+                println!("Task {name} failed, attempt {}: {err:#}", attempt + 1);
+                if attempt < config.max_retries {
+                    thread::sleep(RETRY_DELAY);
+                }
+            }
+        }
+    }
+}
+
+fn get_pools_above_threshold<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    task: &ClaimFeeTask,
+    get_fees: impl Fn(&Pool) -> (u64, u64),
+) -> Result<Vec<Pubkey>> {
+    let rpc_client = program.rpc();
+    Ok(fetch_accounts::<Pool>(&rpc_client, &task.pools)?
+        .into_iter()
+        .filter(|(_, pool)| {
+            let (amount_a, amount_b) = get_fees(pool);
+            task.is_above_threshold(amount_a, amount_b)
+        })
+        .map(|(pool, _)| pool)
+        .collect())
+}
+
+fn crank_update_rewards<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    task: &UpdateRewardsTask,
+    batch_size: usize,
+) -> Result<()> {
+    let rpc_client = program.rpc();
+    let pools = fetch_accounts::<Pool>(&rpc_client, &task.pools)?;
+
+    let mut item_instructions = vec![];
+    for (pool, pool_state) in pools {
+        let has_reward = pool_state
+            .reward_infos
+            .iter()
+            .any(|reward_info| reward_info.initialized());
+        if !has_reward && !pool_state.has_reward_extension() {
+            continue;
+        }
+        let mut request = program
+            .request()
+            .accounts(accounts::UpdateRewardsCtx {
+                pool,
+                event_authority: derive_event_authority(),
+                program: cp_amm::ID,
+            })
+            .args(instruction::UpdateRewards {});
+        if pool_state.has_reward_extension() {
+            request = request.accounts(vec![AccountMeta::new(
+                derive_reward_extension(&pool),
+                false,
+            )]);
+        }
+        item_instructions.push(request.instructions()?);
+    }
+
+    if item_instructions.is_empty() {
+        println!("No rewards to update");
+        return Ok(());
+    }
+    send_in_batches(program, item_instructions, batch_size)
+}
+
+fn crank_buyback_and_burn<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    task: &BuybackAndBurnTask,
+    batch_size: usize,
+) -> Result<()> {
+    let rpc_client = program.rpc();
+    let pools: Vec<(Pubkey, Pool)> = fetch_accounts::<Pool>(&rpc_client, &task.pools)?
+        .into_iter()
+        .filter(|(_, pool)| pool.is_buyback_burn_enabled() && pool.protocol_b_fee > 0)
+        .collect();
+    let buyback_burns: Vec<Pubkey> = pools
+        .iter()
+        .map(|(pool, _)| derive_buyback_burn(pool))
+        .collect();
+    let buyback_burns: BTreeMap<Pubkey, (Pubkey, BuybackBurn)> =
+        fetch_accounts::<BuybackBurn>(&rpc_client, &buyback_burns)?
+            .into_iter()
+            .map(|(key, buyback_burn)| (buyback_burn.pool, (key, buyback_burn)))
+            .collect();
+
+    let clock = get_clock(&rpc_client)?;
+    let mints: BTreeSet<Pubkey> = pools.iter().map(|(_, pool)| pool.token_a_mint).collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let mut item_instructions = vec![];
+    for (pool, pool_state) in pools {
+        let Some((buyback_burn, buyback_burn_state)) = buyback_burns.get(&pool) else {
+            continue;
+        };
+        if buyback_burn_state
+            .validate_buyback(clock.unix_timestamp as u64)
+            .is_err()
+        {
+            continue;
+        }
+        item_instructions.push(
+            program
+                .request()
+                .accounts(accounts::BuybackAndBurnCtx {
+                    pool_authority: derive_pool_authority(),
+                    pool,
+                    buyback_burn: *buyback_burn,
+                    token_a_vault: pool_state.token_a_vault,
+                    token_b_vault: pool_state.token_b_vault,
+                    token_a_mint: pool_state.token_a_mint,
+                    token_a_program: mint_infos[&pool_state.token_a_mint].token_program,
+                    event_authority: derive_event_authority(),
+                    program: cp_amm::ID,
+                })
+                .args(instruction::BuybackAndBurn {})
+                .instructions()?,
+        );
+        println!(
+            "Buyback and burn of pool {pool}, up to {} of {} protocol fee",
+            buyback_burn_state.max_amount_per_buyback, pool_state.protocol_b_fee
+        );
+    }
+
+    if item_instructions.is_empty() {
+        println!("No buyback to crank");
+        return Ok(());
+    }
+    send_in_batches(program, item_instructions, batch_size)
+}
+
+fn crank_refresh_vesting<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    task: &RefreshVestingTask,
+    batch_size: usize,
+) -> Result<()> {
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let positions = fetch_accounts::<Position>(&rpc_client, &task.positions)?;
+
+    let mut item_instructions = vec![];
+    for (position, position_state) in positions {
+        let pool_state: Pool = program.account(position_state.pool)?;
+        let current_point = get_current_point(pool_state.activation_type, &clock)?;
+
+        let position_offset = Vesting::DISCRIMINATOR.len() + offset_of!(Vesting, position);
+        let vesting_keys = get_program_account_keys::<Vesting>(
+            &rpc_client,
+            vec![Memcmp::new_base58_encoded(
+                position_offset,
+                position.as_ref(),
+            )],
+        )?;
+        let mut vestings = vec![];
+        for (vesting, vesting_state) in fetch_accounts::<Vesting>(&rpc_client, &vesting_keys)? {
+            if pool_state.is_emergency_mode()
+                || vesting_state.get_new_release_liquidity(current_point)? > 0
+            {
+                vestings.push(vesting);
+            }
+        }
+        if vestings.is_empty() {
+            continue;
+        }
+
+        let (position_nft_account, owner) =
+            get_position_nft_holder(&rpc_client, &position_state.nft_mint)?;
+        let mut remaining_accounts = vec![
+            AccountMeta::new(position_state.pool, false),
+            AccountMeta::new(position, false),
+            AccountMeta::new_readonly(position_nft_account, false),
+            AccountMeta::new(owner, false),
+        ];
+        // rewards of position are updated before releasing boosted vested liquidity
+        if pool_state.has_reward_boost() && pool_state.has_reward_extension() {
+            remaining_accounts.push(AccountMeta::new(
+                derive_reward_extension(&position_state.pool),
+                false,
+            ));
+            remaining_accounts.push(AccountMeta::new(
+                derive_position_reward_extension(&position),
+                false,
+            ));
+        }
+        remaining_accounts.extend(
+            vestings
+                .iter()
+                .map(|vesting| AccountMeta::new(*vesting, false)),
+        );
+        item_instructions.push(
+            program
+                .request()
+                .accounts(accounts::BatchRefreshVestingCtx {
+                    signer: program.payer(),
+                })
+                .accounts(remaining_accounts)
+                .args(instruction::BatchRefreshVesting {
+                    vesting_counts: vec![u8::try_from(vestings.len())?],
+                })
+                .instructions()?,
+        );
+        println!(
+            "Refresh {} vesting(s) of position {position}",
+            vestings.len()
+        );
+    }
+
+    if item_instructions.is_empty() {
+        println!("No vestings to refresh");
+        return Ok(());
+    }
+    send_in_batches(program, item_instructions, batch_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keeper_config() {
+        let config: KeeperConfig =
+            toml::from_str(include_str!("../../keeper.example.toml")).unwrap();
+        assert_eq!(config.interval_seconds, 60);
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.update_rewards.unwrap().pools.len(), 1);
+        assert!(config.refresh_vesting.is_some());
+        assert_eq!(config.buyback_and_burn.unwrap().pools.len(), 1);
+
+        let claim_protocol_fee = config.claim_protocol_fee.unwrap();
+        assert!(!claim_protocol_fee.is_above_threshold(0, 0));
+        assert!(!claim_protocol_fee.is_above_threshold(999_999, 0));
+        assert!(claim_protocol_fee.is_above_threshold(1_000_000, 0));
+        assert!(claim_protocol_fee.is_above_threshold(0, 1_000_000));
+
+        let err = toml::from_str::<KeeperConfig>(
+            "interval_seconds = 1\n[update_rewards]\npools = [\"invalid\"]",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("pools"));
+    }
+}
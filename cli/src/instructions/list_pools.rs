@@ -0,0 +1,85 @@
+use anchor_client::{solana_client::rpc_filter::Memcmp, solana_sdk::signer::Signer, Program};
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use anyhow::Result;
+use cp_amm::state::Pool;
+use std::{collections::BTreeSet, mem::offset_of, ops::Deref};
+
+use crate::{
+    instructions::PoolView,
+    utils::{
+        fetch_accounts, get_clock, get_current_point, get_mint_infos, get_program_account_keys,
+    },
+};
+
+pub struct ListPoolsParams {
+    pub token_a_mint: Option<Pubkey>,
+    pub token_b_mint: Option<Pubkey>,
+    pub partner: Option<Pubkey>,
+    pub json: bool,
+}
+
+pub fn list_pools<C: Deref<Target = impl Signer> + Clone>(
+    params: ListPoolsParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let ListPoolsParams {
+        token_a_mint,
+        token_b_mint,
+        partner,
+        json,
+    } = params;
+
+    let rpc_client = program.rpc();
+
+    let memcmp_filters = [
+        (offset_of!(Pool, token_a_mint), token_a_mint),
+        (offset_of!(Pool, token_b_mint), token_b_mint),
+        (offset_of!(Pool, partner), partner),
+    ]
+    .into_iter()
+    .filter_map(|(offset, key)| {
+        key.map(|key| Memcmp::new_base58_encoded(Pool::DISCRIMINATOR.len() + offset, key.as_ref()))
+    })
+    .collect();
+    let pools = get_program_account_keys::<Pool>(&rpc_client, memcmp_filters)?;
+    let pools = fetch_accounts::<Pool>(&rpc_client, &pools)?;
+
+    let clock = get_clock(&rpc_client)?;
+    let mints: BTreeSet<Pubkey> = pools
+        .iter()
+        .flat_map(|(_, pool)| [pool.token_a_mint, pool.token_b_mint])
+        .collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let mut pool_views = vec![];
+    for (pool, pool_state) in pools.iter() {
+        let current_point = get_current_point(pool_state.activation_type, &clock)?;
+        pool_views.push(PoolView::new(
+            *pool,
+            pool_state,
+            mint_infos[&pool_state.token_a_mint].decimals,
+            mint_infos[&pool_state.token_b_mint].decimals,
+            current_point,
+            clock.unix_timestamp as u64,
+        )?);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&pool_views)?);
+    } else {
+        for pool_view in pool_views.iter() {
+            println!(
+                "{} {}/{} price {} liquidity {} fee numerator {}",
+                pool_view.address,
+                pool_view.token_a_mint,
+                pool_view.token_b_mint,
+                pool_view.price,
+                pool_view.liquidity,
+                pool_view.fees.current_fee_numerator,
+            );
+        }
+        println!("Total pools: {}", pool_views.len());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+pub mod create_config;
+pub use create_config::*;
+pub mod create_config_batch;
+pub use create_config_batch::*;
+pub mod close_config;
+pub use close_config::*;
+pub mod update_config;
+pub use update_config::*;
+pub mod initialize_program_authority;
+pub use initialize_program_authority::*;
+pub mod swap;
+pub use swap::*;
+pub mod add_liquidity;
+pub use add_liquidity::*;
+pub mod remove_liquidity;
+pub use remove_liquidity::*;
+pub mod claim_position_fee;
+pub use claim_position_fee::*;
+pub mod claim_partner_fee;
+pub use claim_partner_fee::*;
+pub mod claim_protocol_fee;
+pub use claim_protocol_fee::*;
+pub mod show_pool;
+pub use show_pool::*;
+pub mod list_pools;
+pub use list_pools::*;
+pub mod simulate_swap;
+pub use simulate_swap::*;
+pub mod initialize_reward;
+pub use initialize_reward::*;
+pub mod fund_reward;
+pub use fund_reward::*;
+pub mod update_reward_duration;
+pub use update_reward_duration::*;
+pub mod update_reward_funder;
+pub use update_reward_funder::*;
+pub mod withdraw_ineligible_reward;
+pub use withdraw_ineligible_reward::*;
+pub mod keeper;
+pub use keeper::*;
+pub mod pool_lookup_table;
+pub use pool_lookup_table::*;
+#[cfg(feature = "e2e-test")]
+pub mod bootstrap;
+#[cfg(feature = "e2e-test")]
+pub use bootstrap::*;
+pub mod monitor;
+pub use monitor::*;
+pub mod export_metrics;
+pub use export_metrics::*;
@@ -0,0 +1,229 @@
+use anchor_client::{
+    solana_client::{
+        pubsub_client::PubsubClient,
+        rpc_client::RpcClient,
+        rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig, instruction::CompiledInstruction,
+        signature::Signature, signer::Signer,
+    },
+    Program,
+};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cp_amm_client::events::{parse_cpi_events, parse_log_events, CpAmmEvent, EVENT_NAMES};
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, UiInstruction, UiTransactionEncoding,
+};
+use std::{fs::OpenOptions, io::Write, ops::Deref, str::FromStr, thread, time::Duration};
+
+/// Delay before subscribing again after the websocket is disconnected
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Attempts to fetch a transaction, a transaction is sometimes not fetchable right after its logs
+const FETCH_TRANSACTION_RETRIES: u32 = 3;
+
+pub struct MonitorParams {
+    pub ws_url: String,
+    pub pool: Option<Pubkey>,
+    pub events: Vec<String>,
+    pub output: Option<String>,
+}
+
+/// A line of the NDJSON output, data is the base64 of the event as emitted by the program
+pub fn format_event_record(slot: u64, signature: &str, event: &CpAmmEvent) -> String {
+    serde_json::json!({
+        "slot": slot,
+        "signature": signature,
+        "event": event.name(),
+        "data": STANDARD.encode(event.data()),
+    })
+    .to_string()
+}
+
+/// Events of a transaction, both emitted by event cpi and logged
+fn get_transaction_events(
+    rpc_client: &RpcClient,
+    signature: &Signature,
+    logs: &[String],
+) -> Result<Vec<CpAmmEvent>> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let mut attempt = 0;
+    let transaction = loop {
+        match rpc_client.get_transaction_with_config(signature, config) {
+            Ok(transaction) => break transaction,
+            Err(_) if attempt < FETCH_TRANSACTION_RETRIES => {
+                attempt += 1;
+                thread::sleep(Duration::from_secs(1));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    let versioned_transaction = transaction
+        .transaction
+        .transaction
+        .decode()
+        .context("failed to decode transaction")?;
+    let meta = transaction
+        .transaction
+        .meta
+        .context("transaction has no status meta")?;
+
+    let mut account_keys = versioned_transaction.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded_addresses) = meta.loaded_addresses {
+        for key in loaded_addresses
+            .writable
+            .iter()
+            .chain(loaded_addresses.readonly.iter())
+        {
+            account_keys.push(Pubkey::from_str(key)?);
+        }
+    }
+
+    let mut inner_instructions = vec![];
+    let inner_instructions_list = match meta.inner_instructions {
+        OptionSerializer::Some(inner_instructions_list) => inner_instructions_list,
+        _ => vec![],
+    };
+    for ui_instruction in inner_instructions_list
+        .into_iter()
+        .flat_map(|inner_instructions| inner_instructions.instructions)
+    {
+        if let UiInstruction::Compiled(instruction) = ui_instruction {
+            inner_instructions.push(CompiledInstruction {
+                program_id_index: instruction.program_id_index,
+                accounts: instruction.accounts,
+                data: bs58::decode(instruction.data).into_vec()?,
+            });
+        }
+    }
+    let mut events = parse_cpi_events(&account_keys, &inner_instructions)?;
+    events.extend(parse_log_events(logs)?);
+    Ok(events)
+}
+
+/// Subscribe to logs of transactions of cp-amm, or of the pool, and print every event. The
+/// subscription is renewed when the websocket is disconnected
+pub fn monitor<C: Deref<Target = impl Signer> + Clone>(
+    params: MonitorParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let MonitorParams {
+        ws_url,
+        pool,
+        events,
+        output,
+    } = params;
+
+    for event in events.iter() {
+        ensure!(
+            EVENT_NAMES.contains(&event.as_str()),
+            "unknown event {event}, events: {}",
+            EVENT_NAMES.join(", ")
+        );
+    }
+    let mut output_file = match &output {
+        Some(output) => Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(output)
+                .with_context(|| format!("failed to open {output}"))?,
+        ),
+        None => None,
+    };
+
+    let rpc_client = program.rpc();
+    let program_invoke = format!("Program {} invoke", cp_amm::ID);
+    // logs subscription filters by a single mentioned address, transactions of the pool are also of cp-amm
+    let filter_address = pool.unwrap_or(cp_amm::ID);
+
+    loop {
+        let (_subscription, receiver) = match PubsubClient::logs_subscribe(
+            &ws_url,
+            RpcTransactionLogsFilter::Mentions(vec![filter_address.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        ) {
+            Ok(subscription) => subscription,
+            Err(err) => {
+                println!("Failed to subscribe to {ws_url}: {err}");
+                thread::sleep(RECONNECT_DELAY);
+                continue;
+            }
+        };
+        println!("Monitoring events of {filter_address}");
+
+        while let Ok(response) = receiver.recv() {
+            let slot = response.context.slot;
+            let logs_response = response.value;
+            if logs_response.err.is_some()
+                || !logs_response
+                    .logs
+                    .iter()
+                    .any(|log| log.starts_with(&program_invoke))
+            {
+                continue;
+            }
+            let signature = Signature::from_str(&logs_response.signature)?;
+            let transaction_events =
+                match get_transaction_events(&rpc_client, &signature, &logs_response.logs) {
+                    Ok(transaction_events) => transaction_events,
+                    Err(err) => {
+                        println!("Failed to get events of {signature}: {err:#}");
+                        continue;
+                    }
+                };
+            for event in transaction_events {
+                if !events.is_empty() && !events.iter().any(|name| name == event.name()) {
+                    continue;
+                }
+                println!("[{slot}] {signature} {}", event.name());
+                if let Some(file) = output_file.as_mut() {
+                    writeln!(
+                        file,
+                        "{}",
+                        format_event_record(slot, &logs_response.signature, &event)
+                    )?;
+                }
+            }
+        }
+
+        println!("Websocket disconnected, subscribing again");
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cp_amm_client::events::decode_event;
+
+    #[test]
+    fn test_format_event_record() {
+        let event = CpAmmEvent::EvtCreatePosition(cp_amm::EvtCreatePosition {
+            pool: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            position_nft_mint: Pubkey::new_unique(),
+        });
+        let record: serde_json::Value =
+            serde_json::from_str(&format_event_record(10, "signature", &event)).unwrap();
+        assert_eq!(record["slot"], 10);
+        assert_eq!(record["event"], "EvtCreatePosition");
+
+        let data = STANDARD.decode(record["data"].as_str().unwrap()).unwrap();
+        assert!(matches!(
+            decode_event(&data).unwrap(),
+            CpAmmEvent::EvtCreatePosition(_)
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+use anchor_client::{
+    solana_sdk::{
+        address_lookup_table::{
+            instruction::{create_lookup_table, extend_lookup_table},
+            state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES},
+        },
+        signer::Signer,
+    },
+    Program,
+};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context, Result};
+use cp_amm::state::Pool;
+use std::{collections::BTreeSet, ops::Deref};
+
+use crate::utils::{
+    derive_event_authority, derive_pool_authority, fetch_accounts, get_clock, get_mint_infos,
+    send_request,
+};
+
+/// Number of addresses per extend instruction, so the transaction fits in the size limit
+const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// Accounts of pool which don't change, used by swap, liquidity and claim instructions
+pub fn get_pool_static_accounts(
+    pool: Pubkey,
+    pool_state: &Pool,
+    token_a_program: Pubkey,
+    token_b_program: Pubkey,
+) -> Vec<Pubkey> {
+    let mut accounts = vec![
+        pool,
+        pool_state.token_a_vault,
+        pool_state.token_b_vault,
+        pool_state.token_a_mint,
+        pool_state.token_b_mint,
+        derive_pool_authority(),
+        derive_event_authority(),
+        cp_amm::ID,
+        token_a_program,
+        token_b_program,
+    ];
+    for reward_info in pool_state.reward_infos.iter() {
+        if reward_info.initialized() {
+            accounts.push(reward_info.vault);
+            accounts.push(reward_info.mint);
+        }
+    }
+    accounts
+}
+
+pub struct PoolLookupTableParams {
+    pub pools: Vec<Pubkey>,
+    pub lookup_table: Option<Pubkey>,
+}
+
+pub fn pool_lookup_table<C: Deref<Target = impl Signer> + Clone>(
+    params: PoolLookupTableParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let PoolLookupTableParams {
+        pools,
+        lookup_table,
+    } = params;
+
+    let rpc_client = program.rpc();
+    let authority = program.payer();
+
+    let pools = fetch_accounts::<Pool>(&rpc_client, &pools)?;
+    let clock = get_clock(&rpc_client)?;
+    let mints: BTreeSet<Pubkey> = pools
+        .iter()
+        .flat_map(|(_, pool)| [pool.token_a_mint, pool.token_b_mint])
+        .collect();
+    let mint_infos = get_mint_infos(&rpc_client, mints, clock.epoch)?;
+
+    let (lookup_table, mut existing_addresses) = match lookup_table {
+        Some(lookup_table) => {
+            let account = rpc_client.get_account(&lookup_table)?;
+            let lookup_table_state = AddressLookupTable::deserialize(&account.data)
+                .with_context(|| format!("{lookup_table} is not an address lookup table"))?;
+            ensure!(
+                lookup_table_state.meta.authority == Some(authority),
+                "{authority} is not authority of lookup table {lookup_table}"
+            );
+            let addresses: BTreeSet<Pubkey> =
+                lookup_table_state.addresses.iter().copied().collect();
+            (lookup_table, addresses)
+        }
+        None => {
+            let (create_lookup_table_ix, lookup_table) =
+                create_lookup_table(authority, authority, rpc_client.get_slot()?);
+            let request = program.request().instruction(create_lookup_table_ix);
+            if let Some(signature) = send_request(request)? {
+                println!("Create lookup table {lookup_table}. Signature: {signature:#?}");
+            }
+            (lookup_table, BTreeSet::new())
+        }
+    };
+
+    let mut new_addresses = vec![];
+    for (pool, pool_state) in pools.iter() {
+        let token_a_program = mint_infos[&pool_state.token_a_mint].token_program;
+        let token_b_program = mint_infos[&pool_state.token_b_mint].token_program;
+        for address in get_pool_static_accounts(*pool, pool_state, token_a_program, token_b_program)
+        {
+            if existing_addresses.insert(address) {
+                new_addresses.push(address);
+            }
+        }
+    }
+    ensure!(
+        existing_addresses.len() <= LOOKUP_TABLE_MAX_ADDRESSES,
+        "lookup table can't have more than {LOOKUP_TABLE_MAX_ADDRESSES} addresses"
+    );
+
+    if new_addresses.is_empty() {
+        println!("Lookup table {lookup_table} has all accounts of pools");
+        return Ok(());
+    }
+    for addresses in new_addresses.chunks(MAX_ADDRESSES_PER_EXTEND) {
+        let request = program.request().instruction(extend_lookup_table(
+            lookup_table,
+            authority,
+            Some(authority),
+            addresses.to_vec(),
+        ));
+        if let Some(signature) = send_request(request)? {
+            println!(
+                "Extend lookup table {lookup_table} with {} address(es). Signature: {signature:#?}",
+                addresses.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_pool_static_accounts() {
+        let mut pool_state = Pool {
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            token_a_vault: Pubkey::new_unique(),
+            token_b_vault: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let accounts = get_pool_static_accounts(
+            Pubkey::new_unique(),
+            &pool_state,
+            anchor_spl::token::ID,
+            anchor_spl::token::ID,
+        );
+        assert_eq!(accounts.len(), 10);
+
+        pool_state.reward_infos[0].initialized = 1;
+        pool_state.reward_infos[0].vault = Pubkey::new_unique();
+        pool_state.reward_infos[0].mint = Pubkey::new_unique();
+        let accounts = get_pool_static_accounts(
+            Pubkey::new_unique(),
+            &pool_state,
+            anchor_spl::token::ID,
+            anchor_spl::token_2022::ID,
+        );
+        assert_eq!(accounts.len(), 12);
+        assert!(accounts.contains(&pool_state.reward_infos[0].vault));
+    }
+}
@@ -0,0 +1,284 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cp_amm::{constants::NUM_REWARDS, state::Pool};
+use rust_sdk::price::sqrt_price_to_ui_price;
+use serde::Serialize;
+use std::ops::Deref;
+
+use crate::utils::{get_clock, get_current_point, get_mint_info};
+
+/// Decimals of ui price shown
+const UI_PRICE_SCALE: u8 = 12;
+
+#[derive(Debug, Serialize)]
+pub struct PoolView {
+    pub address: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_vault: String,
+    pub token_b_vault: String,
+    pub partner: String,
+    pub pool_type: u8,
+    pub pool_status: u8,
+    pub collect_fee_mode: u8,
+    pub activation_type: u8,
+    pub activation_point: u64,
+    /// Price of token a in token b, adjusted by decimals
+    pub price: String,
+    pub sqrt_price: String,
+    pub sqrt_min_price: String,
+    pub sqrt_max_price: String,
+    pub liquidity: String,
+    pub permanent_lock_liquidity: String,
+    pub fees: FeesView,
+    pub protocol_a_fee: u64,
+    pub protocol_b_fee: u64,
+    pub partner_a_fee: u64,
+    pub partner_b_fee: u64,
+    pub metrics: MetricsView,
+    pub reward_infos: Vec<RewardInfoView>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeesView {
+    pub cliff_fee_numerator: u64,
+    pub fee_scheduler_mode: u8,
+    pub number_of_period: u16,
+    pub period_frequency: u64,
+    pub reduction_factor: u64,
+    /// Base fee and variable fee numerator at current point
+    pub current_fee_numerator: String,
+    pub protocol_fee_percent: u8,
+    pub partner_fee_percent: u8,
+    pub referral_fee_percent: u8,
+    pub dynamic_fee: Option<DynamicFeeView>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicFeeView {
+    pub bin_step: u16,
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub variable_fee_control: u32,
+    pub max_volatility_accumulator: u32,
+    pub volatility_accumulator: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricsView {
+    pub total_lp_a_fee: String,
+    pub total_lp_b_fee: String,
+    pub total_protocol_a_fee: u64,
+    pub total_protocol_b_fee: u64,
+    pub total_partner_a_fee: u64,
+    pub total_partner_b_fee: u64,
+    pub total_position: u64,
+    pub total_volume_a: String,
+    pub total_volume_b: String,
+    /// Lp fee of the last 24 hours, estimated from the current and previous fee windows
+    pub rolling_lp_a_fee: u64,
+    pub rolling_lp_b_fee: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RewardInfoView {
+    pub index: usize,
+    pub mint: String,
+    pub vault: String,
+    pub funder: String,
+    pub reward_duration: u64,
+    pub reward_duration_end: u64,
+    pub reward_rate: String,
+    pub last_update_time: u64,
+}
+
+/// Price of token a in token b from sqrt price in Q64.64, adjusted by decimals
+pub fn get_price_from_sqrt_price(sqrt_price: u128, decimals_a: u8, decimals_b: u8) -> f64 {
+    let sqrt_price = sqrt_price as f64 / 2f64.powi(64);
+    sqrt_price * sqrt_price * 10f64.powi(i32::from(decimals_a) - i32::from(decimals_b))
+}
+
+impl PoolView {
+    pub fn new(
+        address: Pubkey,
+        pool: &Pool,
+        decimals_a: u8,
+        decimals_b: u8,
+        current_point: u64,
+        current_timestamp: u64,
+    ) -> Result<Self> {
+        let pool_fees = &pool.pool_fees;
+        let base_fee = &pool_fees.base_fee;
+        let dynamic_fee = &pool_fees.dynamic_fee;
+        let (rolling_lp_a_fee, rolling_lp_b_fee) =
+            pool.volume_metrics.get_rolling_lp_fee(current_timestamp);
+        let current_fee_numerator =
+            pool_fees.get_total_trading_fee(current_point, pool.activation_point)?;
+        let reward_infos = pool
+            .reward_infos
+            .iter()
+            .take(NUM_REWARDS)
+            .enumerate()
+            .filter(|(_, reward_info)| reward_info.initialized())
+            .map(|(index, reward_info)| RewardInfoView {
+                index,
+                mint: reward_info.mint.to_string(),
+                vault: reward_info.vault.to_string(),
+                funder: reward_info.funder.to_string(),
+                reward_duration: reward_info.reward_duration,
+                reward_duration_end: reward_info.reward_duration_end,
+                reward_rate: reward_info.reward_rate.to_string(),
+                last_update_time: reward_info.last_update_time,
+            })
+            .collect();
+
+        Ok(PoolView {
+            address: address.to_string(),
+            token_a_mint: pool.token_a_mint.to_string(),
+            token_b_mint: pool.token_b_mint.to_string(),
+            token_a_vault: pool.token_a_vault.to_string(),
+            token_b_vault: pool.token_b_vault.to_string(),
+            partner: pool.partner.to_string(),
+            pool_type: pool.pool_type,
+            pool_status: pool.pool_status,
+            collect_fee_mode: pool.collect_fee_mode,
+            activation_type: pool.activation_type,
+            activation_point: pool.activation_point,
+            price: sqrt_price_to_ui_price(pool.sqrt_price, decimals_a, decimals_b, UI_PRICE_SCALE)?,
+            sqrt_price: pool.sqrt_price.to_string(),
+            sqrt_min_price: pool.sqrt_min_price.to_string(),
+            sqrt_max_price: pool.sqrt_max_price.to_string(),
+            liquidity: pool.liquidity.to_string(),
+            permanent_lock_liquidity: pool.permanent_lock_liquidity.to_string(),
+            fees: FeesView {
+                cliff_fee_numerator: base_fee.cliff_fee_numerator,
+                fee_scheduler_mode: base_fee.fee_scheduler_mode,
+                number_of_period: base_fee.number_of_period,
+                period_frequency: base_fee.period_frequency,
+                reduction_factor: base_fee.reduction_factor,
+                current_fee_numerator: current_fee_numerator.to_string(),
+                protocol_fee_percent: pool_fees.protocol_fee_percent,
+                partner_fee_percent: pool_fees.partner_fee_percent,
+                referral_fee_percent: pool_fees.referral_fee_percent,
+                dynamic_fee: dynamic_fee.is_dynamic_fee_enable().then(|| DynamicFeeView {
+                    bin_step: dynamic_fee.bin_step,
+                    filter_period: dynamic_fee.filter_period,
+                    decay_period: dynamic_fee.decay_period,
+                    reduction_factor: dynamic_fee.reduction_factor,
+                    variable_fee_control: dynamic_fee.variable_fee_control,
+                    max_volatility_accumulator: dynamic_fee.max_volatility_accumulator,
+                    volatility_accumulator: dynamic_fee.volatility_accumulator.to_string(),
+                }),
+            },
+            protocol_a_fee: pool.protocol_a_fee,
+            protocol_b_fee: pool.protocol_b_fee,
+            partner_a_fee: pool.partner_a_fee,
+            partner_b_fee: pool.partner_b_fee,
+            metrics: MetricsView {
+                total_lp_a_fee: pool.metrics.total_lp_a_fee.to_string(),
+                total_lp_b_fee: pool.metrics.total_lp_b_fee.to_string(),
+                total_protocol_a_fee: pool.metrics.total_protocol_a_fee,
+                total_protocol_b_fee: pool.metrics.total_protocol_b_fee,
+                total_partner_a_fee: pool.metrics.total_partner_a_fee,
+                total_partner_b_fee: pool.metrics.total_partner_b_fee,
+                total_position: pool.metrics.total_position,
+                total_volume_a: pool.volume_metrics.total_volume_a.to_string(),
+                total_volume_b: pool.volume_metrics.total_volume_b.to_string(),
+                rolling_lp_a_fee,
+                rolling_lp_b_fee,
+            },
+            reward_infos,
+        })
+    }
+}
+
+pub struct ShowPoolParams {
+    pub pool: Pubkey,
+    pub json: bool,
+}
+
+pub fn show_pool<C: Deref<Target = impl Signer> + Clone>(
+    params: ShowPoolParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let ShowPoolParams { pool, json } = params;
+
+    let pool_state: Pool = program.account(pool)?;
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let token_a = get_mint_info(&rpc_client, pool_state.token_a_mint, clock.epoch)?;
+    let token_b = get_mint_info(&rpc_client, pool_state.token_b_mint, clock.epoch)?;
+    let current_point = get_current_point(pool_state.activation_type, &clock)?;
+
+    let pool_view = PoolView::new(
+        pool,
+        &pool_state,
+        token_a.decimals,
+        token_b.decimals,
+        current_point,
+        clock.unix_timestamp as u64,
+    )?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&pool_view)?);
+    } else {
+        println!("{pool_view:#?}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cp_amm::u128x128_math::Rounding;
+    use rust_sdk::price::ui_price_to_sqrt_price;
+
+    #[test]
+    fn test_get_price_from_sqrt_price() {
+        let one = 1u128 << 64;
+        assert_eq!(get_price_from_sqrt_price(one, 6, 6), 1.0);
+        assert_eq!(get_price_from_sqrt_price(one * 2, 6, 6), 4.0);
+        // 1 token a with 9 decimals for 1 token b with 6 decimals
+        let price = get_price_from_sqrt_price(one, 9, 6);
+        assert!((price - 1_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ui_price_round_trip() {
+        let one = 1u128 << 64;
+        assert_eq!(
+            sqrt_price_to_ui_price(one, 6, 6, UI_PRICE_SCALE).unwrap(),
+            "1"
+        );
+        assert_eq!(
+            sqrt_price_to_ui_price(one, 9, 6, UI_PRICE_SCALE).unwrap(),
+            "1000"
+        );
+        assert_eq!(
+            sqrt_price_to_ui_price(one, 6, 9, UI_PRICE_SCALE).unwrap(),
+            "0.001"
+        );
+
+        assert_eq!(
+            ui_price_to_sqrt_price("1000", 9, 6, Rounding::Down).unwrap(),
+            one
+        );
+        assert_eq!(
+            ui_price_to_sqrt_price(".25", 6, 6, Rounding::Down).unwrap(),
+            one / 2
+        );
+        for ui_price in ["1.5", "0.000123", "42"] {
+            let sqrt_price = ui_price_to_sqrt_price(ui_price, 9, 6, Rounding::Up).unwrap();
+            assert_eq!(
+                sqrt_price_to_ui_price(sqrt_price, 9, 6, UI_PRICE_SCALE).unwrap(),
+                ui_price
+            );
+        }
+        for ui_price in ["", ".", "0", "-1", "1e5", "1.2.3"] {
+            assert!(ui_price_to_sqrt_price(ui_price, 9, 6, Rounding::Down).is_err());
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use anchor_client::{
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::rpc_config::{
+        RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+    },
+    solana_sdk::{
+        account::Account, instruction::CompiledInstruction, signer::Signer,
+        transaction::Transaction,
+    },
+    Program,
+};
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::StateWithExtensions, state::Account as TokenAccount,
+};
+use anyhow::{bail, ensure, Result};
+use cp_amm::{state::Pool, SwapParameters};
+use cp_amm_client::events::{parse_cpi_events, parse_log_events, CpAmmEvent};
+use solana_transaction_status_client_types::UiInstruction;
+use std::ops::Deref;
+
+use crate::{
+    instructions::get_swap_instructions,
+    utils::{get_clock, get_mint_info},
+};
+
+pub struct SimulateSwapParams {
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+fn get_token_amount(account: &Account) -> Result<u64> {
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&account.data)?
+        .base
+        .amount)
+}
+
+pub fn simulate_swap<C: Deref<Target = impl Signer> + Clone>(
+    params: SimulateSwapParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let SimulateSwapParams {
+        pool,
+        input_mint,
+        amount_in,
+        minimum_amount_out,
+    } = params;
+
+    let pool_state: Pool = program.account(pool)?;
+    ensure!(
+        input_mint == pool_state.token_a_mint || input_mint == pool_state.token_b_mint,
+        "input mint {input_mint} is not a token of pool {pool}"
+    );
+    let a_to_b = input_mint == pool_state.token_a_mint;
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let token_a = get_mint_info(&rpc_client, pool_state.token_a_mint, clock.epoch)?;
+    let token_b = get_mint_info(&rpc_client, pool_state.token_b_mint, clock.epoch)?;
+
+    let (instructions, output_token_account) = get_swap_instructions(
+        program,
+        pool,
+        &pool_state,
+        &token_a,
+        &token_b,
+        a_to_b,
+        SwapParameters {
+            amount_in,
+            minimum_amount_out,
+        },
+    )?;
+    let pre_amount_out = match rpc_client.get_account(&output_token_account) {
+        Ok(account) => get_token_amount(&account)?,
+        Err(_) => 0,
+    };
+
+    // signature isn't verified, so the transaction doesn't need to be signed
+    let transaction = Transaction::new_with_payer(&instructions, Some(&program.payer()));
+    let result = rpc_client
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: vec![output_token_account.to_string()],
+                }),
+                inner_instructions: true,
+                ..Default::default()
+            },
+        )?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    if let Some(err) = result.err {
+        for log in logs.iter() {
+            println!("{log}");
+        }
+        bail!("Simulation failed: {err}");
+    }
+
+    let mut inner_instructions = vec![];
+    for ui_instruction in result
+        .inner_instructions
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|inner_instructions| inner_instructions.instructions)
+    {
+        if let UiInstruction::Compiled(instruction) = ui_instruction {
+            inner_instructions.push(CompiledInstruction {
+                program_id_index: instruction.program_id_index,
+                accounts: instruction.accounts,
+                data: bs58::decode(instruction.data).into_vec()?,
+            });
+        }
+    }
+    let mut events = parse_cpi_events(&transaction.message.account_keys, &inner_instructions)?;
+    events.extend(parse_log_events(&logs)?);
+    let Some(evt_swap) = events.into_iter().find_map(|event| match event {
+        CpAmmEvent::EvtSwap(evt_swap) => Some(evt_swap),
+        _ => None,
+    }) else {
+        bail!("EvtSwap is not found in simulation");
+    };
+
+    let post_amount_out = match result
+        .accounts
+        .and_then(|accounts| accounts.into_iter().next().flatten())
+        .and_then(|account| account.decode::<Account>())
+    {
+        Some(account) => get_token_amount(&account)?,
+        None => pre_amount_out,
+    };
+
+    let swap_result = &evt_swap.swap_result;
+    println!("Simulated swap {amount_in} {input_mint} on pool {pool}");
+    println!("Actual amount in: {}", evt_swap.actual_amount_in);
+    println!("Output amount: {}", swap_result.output_amount);
+    println!(
+        "Amount received: {}",
+        post_amount_out.saturating_sub(pre_amount_out)
+    );
+    println!("LP fee: {}", swap_result.lp_fee);
+    println!("Protocol fee: {}", swap_result.protocol_fee);
+    println!("Partner fee: {}", swap_result.partner_fee);
+    println!("Referral fee: {}", swap_result.referral_fee);
+    println!("Next sqrt price: {}", swap_result.next_sqrt_price);
+    if let Some(units_consumed) = result.units_consumed {
+        println!("Compute units consumed: {units_consumed}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_client::{
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        signer::Signer,
+        sysvar,
+    },
+    Program,
+};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Result};
+use cp_amm::{accounts, instruction, state::Pool, SwapParameters};
+use cp_amm_core::transfer_fee::calculate_transfer_fee_excluded_amount;
+use rust_sdk::quote::get_quote;
+use std::ops::Deref;
+
+use crate::utils::{
+    confirm, derive_event_authority, derive_pool_authority, get_clock, get_minimum_amount_out,
+    get_mint_info, get_or_create_ata_ix, send_request, MintInfo,
+};
+
+pub struct SwapParams {
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub slippage_bps: u16,
+    pub yes: bool,
+}
+
+pub fn swap<C: Deref<Target = impl Signer> + Clone>(
+    params: SwapParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let SwapParams {
+        pool,
+        input_mint,
+        amount_in,
+        slippage_bps,
+        yes,
+    } = params;
+
+    let pool_state: Pool = program.account(pool)?;
+    ensure!(
+        input_mint == pool_state.token_a_mint || input_mint == pool_state.token_b_mint,
+        "input mint {input_mint} is not a token of pool {pool}"
+    );
+    let a_to_b = input_mint == pool_state.token_a_mint;
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let token_a = get_mint_info(&rpc_client, pool_state.token_a_mint, clock.epoch)?;
+    let token_b = get_mint_info(&rpc_client, pool_state.token_b_mint, clock.epoch)?;
+    let (token_in, token_out) = if a_to_b {
+        (&token_a, &token_b)
+    } else {
+        (&token_b, &token_a)
+    };
+
+    // same as the program, transfer fee is charged on amount in and amount out
+    let actual_amount_in =
+        calculate_transfer_fee_excluded_amount(token_in.transfer_fee.as_ref(), amount_in)?.amount;
+    let swap_result = get_quote(
+        &pool_state,
+        u64::try_from(clock.unix_timestamp)?,
+        clock.slot,
+        actual_amount_in,
+        a_to_b,
+        false,
+    )?;
+    let amount_out = calculate_transfer_fee_excluded_amount(
+        token_out.transfer_fee.as_ref(),
+        swap_result.output_amount,
+    )?
+    .amount;
+    let minimum_amount_out = get_minimum_amount_out(amount_out, slippage_bps)?;
+
+    println!(
+        "Swap {amount_in} {} for {} on pool {pool}",
+        token_in.mint, token_out.mint
+    );
+    println!("Expected amount out: {amount_out}");
+    println!("Minimum amount out: {minimum_amount_out} ({slippage_bps} bps slippage)");
+    println!(
+        "Trading fee: {}",
+        swap_result.lp_fee
+            + swap_result.protocol_fee
+            + swap_result.partner_fee
+            + swap_result.referral_fee
+    );
+    println!("Next sqrt price: {}", swap_result.next_sqrt_price);
+
+    if !yes && !confirm("Send swap transaction?")? {
+        return Ok(());
+    }
+
+    let (instructions, _) = get_swap_instructions(
+        program,
+        pool,
+        &pool_state,
+        &token_a,
+        &token_b,
+        a_to_b,
+        SwapParameters {
+            amount_in,
+            minimum_amount_out,
+        },
+    )?;
+    let mut request = program.request();
+    for instruction in instructions {
+        request = request.instruction(instruction);
+    }
+    if let Some(signature) = send_request(request)? {
+        println!("Swap on pool {pool}. Signature: {signature:#?}");
+    }
+
+    Ok(())
+}
+
+/// Swap instruction of payer, preceded by creating output token account if it is missing.
+/// Returns the instructions and output token account
+pub fn get_swap_instructions<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    pool: Pubkey,
+    pool_state: &Pool,
+    token_a: &MintInfo,
+    token_b: &MintInfo,
+    a_to_b: bool,
+    params: SwapParameters,
+) -> Result<(Vec<Instruction>, Pubkey)> {
+    let rpc_client = program.rpc();
+    let payer = program.payer();
+    let (token_in, token_out) = if a_to_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    let (input_token_account, _) = get_or_create_ata_ix(&rpc_client, &payer, &payer, token_in)?;
+    let (output_token_account, create_output_ata_ix) =
+        get_or_create_ata_ix(&rpc_client, &payer, &payer, token_out)?;
+
+    let mut instructions: Vec<Instruction> = create_output_ata_ix.into_iter().collect();
+    instructions.extend(
+        program
+            .request()
+            .accounts(accounts::SwapCtx {
+                pool_authority: derive_pool_authority(),
+                pool,
+                input_token_account,
+                output_token_account,
+                token_a_vault: pool_state.token_a_vault,
+                token_b_vault: pool_state.token_b_vault,
+                token_a_mint: pool_state.token_a_mint,
+                token_b_mint: pool_state.token_b_mint,
+                payer,
+                token_a_program: token_a.token_program,
+                token_b_program: token_b.token_program,
+                referral_token_account: None,
+                event_authority: derive_event_authority(),
+                program: cp_amm::ID,
+            })
+            // optional swap accounts are remaining accounts, instructions sysvar is required by cpi guard
+            .accounts(AccountMeta::new_readonly(sysvar::instructions::ID, false))
+            .args(instruction::Swap { params })
+            .instructions()?,
+    );
+
+    Ok((instructions, output_token_account))
+}
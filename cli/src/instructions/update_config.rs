@@ -0,0 +1,84 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cp_amm::{accounts, instruction, state::Config, UpdateConfigParameters};
+use std::ops::Deref;
+
+use crate::utils::{derive_event_authority, derive_program_authority, send_or_print_multisig};
+
+pub struct UpdateConfigParams {
+    pub config: Pubkey,
+    pub vault_config_key: Option<Pubkey>,
+    pub pool_creator_authority: Option<Pubkey>,
+    pub activation_type: Option<u8>,
+    pub trade_fee_numerator: Option<u64>,
+    pub protocol_fee_percent: Option<u8>,
+    pub partner_fee_percent: Option<u8>,
+    pub referral_fee_percent: Option<u8>,
+    pub multisig: Option<Pubkey>,
+}
+
+pub fn update_config<C: Deref<Target = impl Signer> + Clone>(
+    params: UpdateConfigParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let UpdateConfigParams {
+        config,
+        vault_config_key,
+        pool_creator_authority,
+        activation_type,
+        trade_fee_numerator,
+        protocol_fee_percent,
+        partner_fee_percent,
+        referral_fee_percent,
+        multisig,
+    } = params;
+
+    // fee parameters not set are kept from the current config
+    let pool_fees = if trade_fee_numerator.is_some()
+        || protocol_fee_percent.is_some()
+        || partner_fee_percent.is_some()
+        || referral_fee_percent.is_some()
+    {
+        let config_state: Config = program.account(config)?;
+        let mut pool_fees = config_state.pool_fees.to_pool_fee_parameters();
+        if let Some(trade_fee_numerator) = trade_fee_numerator {
+            pool_fees.base_fee.cliff_fee_numerator = trade_fee_numerator;
+        }
+        if let Some(protocol_fee_percent) = protocol_fee_percent {
+            pool_fees.protocol_fee_percent = protocol_fee_percent;
+        }
+        if let Some(partner_fee_percent) = partner_fee_percent {
+            pool_fees.partner_fee_percent = partner_fee_percent;
+        }
+        if let Some(referral_fee_percent) = referral_fee_percent {
+            pool_fees.referral_fee_percent = referral_fee_percent;
+        }
+        Some(pool_fees)
+    } else {
+        None
+    };
+
+    let request = program
+        .request()
+        .accounts(accounts::UpdateConfigCtx {
+            config,
+            program_authority: derive_program_authority(),
+            admin: multisig.unwrap_or(program.payer()),
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .args(instruction::UpdateConfig {
+            params: UpdateConfigParameters {
+                pool_fees,
+                vault_config_key,
+                pool_creator_authority,
+                activation_type,
+            },
+        });
+    if let Some(signature) = send_or_print_multisig(request, multisig)? {
+        println!("Update config {config}. Signature: {signature:#?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cp_amm::{accounts, instruction};
+use std::ops::Deref;
+
+use crate::utils::{
+    derive_event_authority, derive_program_authority, get_reward_remaining_accounts,
+    send_or_print_multisig,
+};
+
+pub struct UpdateRewardDurationParams {
+    pub pool: Pubkey,
+    pub reward_index: u8,
+    pub new_duration: u64,
+    pub multisig: Option<Pubkey>,
+}
+
+pub fn update_reward_duration<C: Deref<Target = impl Signer> + Clone>(
+    params: UpdateRewardDurationParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let UpdateRewardDurationParams {
+        pool,
+        reward_index,
+        new_duration,
+        multisig,
+    } = params;
+
+    let request = program
+        .request()
+        .accounts(accounts::UpdateRewardDurationCtx {
+            pool,
+            program_authority: derive_program_authority(),
+            admin: multisig.unwrap_or(program.payer()),
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .accounts(get_reward_remaining_accounts(&pool, reward_index))
+        .args(instruction::UpdateRewardDuration {
+            reward_index,
+            new_duration,
+        });
+    if let Some(signature) = send_or_print_multisig(request, multisig)? {
+        println!(
+            "Update duration of reward {reward_index} of pool {pool} to {new_duration}. Signature: {signature:#?}"
+        );
+    }
+
+    Ok(())
+}
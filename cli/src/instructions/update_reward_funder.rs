@@ -0,0 +1,48 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use cp_amm::{accounts, instruction};
+use std::ops::Deref;
+
+use crate::utils::{
+    derive_event_authority, derive_program_authority, get_reward_remaining_accounts, send_request,
+};
+
+pub struct UpdateRewardFunderParams {
+    pub pool: Pubkey,
+    pub reward_index: u8,
+    pub new_funder: Pubkey,
+}
+
+pub fn update_reward_funder<C: Deref<Target = impl Signer> + Clone>(
+    params: UpdateRewardFunderParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let UpdateRewardFunderParams {
+        pool,
+        reward_index,
+        new_funder,
+    } = params;
+
+    let request = program
+        .request()
+        .accounts(accounts::UpdateRewardFunderCtx {
+            pool,
+            program_authority: derive_program_authority(),
+            signer: program.payer(),
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .accounts(get_reward_remaining_accounts(&pool, reward_index))
+        .args(instruction::UpdateRewardFunder {
+            reward_index,
+            new_funder,
+        });
+    if let Some(signature) = send_request(request)? {
+        println!(
+            "Update funder of reward {reward_index} of pool {pool} to {new_funder}. Signature: {signature:#?}"
+        );
+    }
+
+    Ok(())
+}
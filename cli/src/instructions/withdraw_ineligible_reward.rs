@@ -0,0 +1,64 @@
+use anchor_client::{solana_sdk::signer::Signer, Program};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Result};
+use cp_amm::{accounts, instruction, state::Pool};
+use std::ops::Deref;
+
+use crate::utils::{
+    derive_event_authority, derive_pool_authority, derive_program_authority, get_clock,
+    get_mint_info, get_or_create_ata_ix, get_reward_info, get_reward_remaining_accounts,
+    send_request,
+};
+
+pub struct WithdrawIneligibleRewardParams {
+    pub pool: Pubkey,
+    pub reward_index: u8,
+}
+
+pub fn withdraw_ineligible_reward<C: Deref<Target = impl Signer> + Clone>(
+    params: WithdrawIneligibleRewardParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let WithdrawIneligibleRewardParams { pool, reward_index } = params;
+
+    let pool_state: Pool = program.account(pool)?;
+    let reward_info = get_reward_info(program, pool, &pool_state, reward_index)?;
+    ensure!(
+        reward_info.initialized(),
+        "reward {reward_index} of pool {pool} is not initialized"
+    );
+
+    let rpc_client = program.rpc();
+    let clock = get_clock(&rpc_client)?;
+    let reward_mint = get_mint_info(&rpc_client, reward_info.mint, clock.epoch)?;
+    let funder = program.payer();
+    let (funder_token_account, create_funder_ata_ix) =
+        get_or_create_ata_ix(&rpc_client, &funder, &funder, &reward_mint)?;
+
+    let mut request = program.request();
+    if let Some(create_funder_ata_ix) = create_funder_ata_ix {
+        request = request.instruction(create_funder_ata_ix);
+    }
+    let request = request
+        .accounts(accounts::WithdrawIneligibleRewardCtx {
+            pool_authority: derive_pool_authority(),
+            pool,
+            reward_vault: reward_info.vault,
+            reward_mint: reward_info.mint,
+            funder_token_account,
+            program_authority: derive_program_authority(),
+            funder,
+            token_program: reward_mint.token_program,
+            event_authority: derive_event_authority(),
+            program: cp_amm::ID,
+        })
+        .accounts(get_reward_remaining_accounts(&pool, reward_index))
+        .args(instruction::WithdrawIneligibleReward { reward_index });
+    if let Some(signature) = send_request(request)? {
+        println!(
+            "Withdraw ineligible reward {reward_index} of pool {pool}. Signature: {signature:#?}"
+        );
+    }
+
+    Ok(())
+}
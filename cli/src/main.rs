@@ -0,0 +1,439 @@
+mod args;
+mod instructions;
+mod utils;
+
+use std::rc::Rc;
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file},
+    Client,
+};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use cp_amm::InitializeProgramAuthorityParameters;
+
+use crate::{
+    args::{Cli, Command},
+    instructions::*,
+    utils::{
+        fetch_lookup_tables, set_dry_run, set_transaction_options, PriorityFee, TransactionOptions,
+    },
+};
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    set_dry_run(cli.config_override.dry_run);
+
+    let wallet = cli.config_override.wallet.unwrap_or_else(|| {
+        let home = std::env::var("HOME").expect("HOME is not set");
+        format!("{home}/.config/solana/id.json")
+    });
+    let payer =
+        read_keypair_file(&wallet).map_err(|e| anyhow!("Failed to read keypair {wallet}: {e}"))?;
+
+    let rpc_client = RpcClient::new_with_commitment(
+        cli.config_override.cluster.url().to_string(),
+        CommitmentConfig::confirmed(),
+    );
+    let priority_fee = match (
+        cli.config_override.priority_fee,
+        cli.config_override.priority_fee_percentile,
+    ) {
+        (Some(priority_fee), _) => Some(PriorityFee::Fixed(priority_fee)),
+        (None, Some(percentile)) => Some(PriorityFee::Percentile {
+            percentile,
+            max: cli.config_override.max_priority_fee,
+        }),
+        (None, None) => None,
+    };
+    let lookup_tables =
+        fetch_lookup_tables(&rpc_client, &cli.config_override.address_lookup_tables)?;
+    set_transaction_options(TransactionOptions {
+        payer: payer.insecure_clone(),
+        rpc_client,
+        priority_fee,
+        lookup_tables,
+    });
+
+    let ws_url = cli.config_override.cluster.ws_url().to_string();
+    let client = Client::new_with_options(
+        cli.config_override.cluster,
+        Rc::new(payer),
+        CommitmentConfig::confirmed(),
+    );
+    let program = client.program(cp_amm::ID)?;
+
+    match cli.command {
+        Command::CreateConfig {
+            index,
+            sqrt_min_price,
+            sqrt_max_price,
+            vault_config_key,
+            pool_creator_authority,
+            activation_type,
+            collect_fee_mode,
+            trade_fee_numerator,
+            protocol_fee_percent,
+            partner_fee_percent,
+            referral_fee_percent,
+            dynamic_fee,
+            multisig,
+        } => {
+            create_config(
+                CreateConfigParams {
+                    index,
+                    sqrt_min_price,
+                    sqrt_max_price,
+                    vault_config_key,
+                    pool_creator_authority,
+                    activation_type,
+                    collect_fee_mode,
+                    trade_fee_numerator,
+                    protocol_fee_percent,
+                    partner_fee_percent,
+                    referral_fee_percent,
+                    dynamic_fee,
+                    multisig,
+                },
+                &program,
+            )?;
+        }
+        Command::CreateConfigBatch { manifest, multisig } => {
+            create_config_batch(CreateConfigBatchParams { manifest, multisig }, &program)?;
+        }
+        Command::UpdateConfig {
+            config,
+            vault_config_key,
+            pool_creator_authority,
+            activation_type,
+            trade_fee_numerator,
+            protocol_fee_percent,
+            partner_fee_percent,
+            referral_fee_percent,
+            multisig,
+        } => {
+            update_config(
+                UpdateConfigParams {
+                    config,
+                    vault_config_key,
+                    pool_creator_authority,
+                    activation_type,
+                    trade_fee_numerator,
+                    protocol_fee_percent,
+                    partner_fee_percent,
+                    referral_fee_percent,
+                    multisig,
+                },
+                &program,
+            )?;
+        }
+        Command::CloseConfig { config, multisig } => {
+            close_config(config, multisig, &program)?;
+        }
+        Command::InitializeProgramAuthority {
+            config_admin,
+            fee_claimer,
+            reward_admin,
+            pauser,
+            transfer_delay,
+            multisig,
+        } => {
+            initialize_program_authority(
+                InitializeProgramAuthorityParameters {
+                    config_admin,
+                    fee_claimer,
+                    reward_admin,
+                    pauser,
+                    transfer_delay,
+                },
+                multisig,
+                &program,
+            )?;
+        }
+        Command::Swap {
+            pool,
+            input_mint,
+            amount_in,
+            slippage_bps,
+            yes,
+        } => {
+            swap(
+                SwapParams {
+                    pool,
+                    input_mint,
+                    amount_in,
+                    slippage_bps,
+                    yes,
+                },
+                &program,
+            )?;
+        }
+        Command::SimulateSwap {
+            pool,
+            input_mint,
+            amount_in,
+            minimum_amount_out,
+        } => {
+            simulate_swap(
+                SimulateSwapParams {
+                    pool,
+                    input_mint,
+                    amount_in,
+                    minimum_amount_out,
+                },
+                &program,
+            )?;
+        }
+        Command::AddLiquidity {
+            position,
+            position_nft_account,
+            amount,
+            token_a_amount_threshold,
+            token_b_amount_threshold,
+            slippage_bps,
+            yes,
+        } => {
+            add_liquidity(
+                AddLiquidityParams {
+                    position,
+                    position_nft_account,
+                    amount,
+                    token_a_amount_threshold,
+                    token_b_amount_threshold,
+                    slippage_bps,
+                    yes,
+                },
+                &program,
+            )?;
+        }
+        Command::RemoveLiquidity {
+            position,
+            position_nft_account,
+            amount,
+            token_a_amount_threshold,
+            token_b_amount_threshold,
+            slippage_bps,
+            yes,
+        } => {
+            remove_liquidity(
+                RemoveLiquidityParams {
+                    position,
+                    position_nft_account,
+                    amount,
+                    token_a_amount_threshold,
+                    token_b_amount_threshold,
+                    slippage_bps,
+                    yes,
+                },
+                &program,
+            )?;
+        }
+        Command::ClaimPositionFee {
+            positions,
+            all_pools,
+            batch_size,
+        } => {
+            claim_position_fee(
+                ClaimPositionFeeParams {
+                    positions,
+                    all_pools,
+                    batch_size,
+                },
+                &program,
+            )?;
+        }
+        Command::ClaimPartnerFee {
+            pools,
+            all_pools,
+            batch_size,
+        } => {
+            claim_partner_fee(
+                ClaimPartnerFeeParams {
+                    pools,
+                    all_pools,
+                    batch_size,
+                },
+                &program,
+            )?;
+        }
+        Command::ClaimProtocolFee {
+            pools,
+            all_pools,
+            settlement_mode,
+            batch_size,
+        } => {
+            claim_protocol_fee(
+                ClaimProtocolFeeParams {
+                    pools,
+                    all_pools,
+                    settlement_mode,
+                    batch_size,
+                },
+                &program,
+            )?;
+        }
+        Command::InitializeReward {
+            pool,
+            reward_index,
+            reward_mint,
+            reward_duration,
+            funder,
+            claim_vesting_duration,
+        } => {
+            initialize_reward(
+                InitializeRewardParams {
+                    pool,
+                    reward_index,
+                    reward_mint,
+                    reward_duration,
+                    funder,
+                    claim_vesting_duration,
+                },
+                &program,
+            )?;
+        }
+        Command::FundReward {
+            pool,
+            reward_index,
+            amount,
+            carry_forward,
+        } => {
+            fund_reward(
+                FundRewardParams {
+                    pool,
+                    reward_index,
+                    amount,
+                    carry_forward,
+                },
+                &program,
+            )?;
+        }
+        Command::UpdateRewardDuration {
+            pool,
+            reward_index,
+            new_duration,
+            multisig,
+        } => {
+            update_reward_duration(
+                UpdateRewardDurationParams {
+                    pool,
+                    reward_index,
+                    new_duration,
+                    multisig,
+                },
+                &program,
+            )?;
+        }
+        Command::UpdateRewardFunder {
+            pool,
+            reward_index,
+            new_funder,
+        } => {
+            update_reward_funder(
+                UpdateRewardFunderParams {
+                    pool,
+                    reward_index,
+                    new_funder,
+                },
+                &program,
+            )?;
+        }
+        Command::WithdrawIneligibleReward { pool, reward_index } => {
+            withdraw_ineligible_reward(
+                WithdrawIneligibleRewardParams { pool, reward_index },
+                &program,
+            )?;
+        }
+        Command::Keeper { config, once } => {
+            keeper(KeeperParams { config, once }, &program)?;
+        }
+        Command::PoolLookupTable {
+            pools,
+            lookup_table,
+        } => {
+            pool_lookup_table(
+                PoolLookupTableParams {
+                    pools,
+                    lookup_table,
+                },
+                &program,
+            )?;
+        }
+        #[cfg(feature = "e2e-test")]
+        Command::Bootstrap {
+            config_index,
+            decimals,
+            supply,
+            liquidity_amount,
+            sqrt_price,
+            price,
+            trade_fee_numerator,
+        } => {
+            bootstrap(
+                BootstrapParams {
+                    config_index,
+                    decimals,
+                    supply,
+                    liquidity_amount,
+                    sqrt_price,
+                    price,
+                    trade_fee_numerator,
+                },
+                &program,
+            )?;
+        }
+        Command::Monitor {
+            pool,
+            events,
+            output,
+        } => {
+            monitor(
+                MonitorParams {
+                    ws_url,
+                    pool,
+                    events,
+                    output,
+                },
+                &program,
+            )?;
+        }
+        Command::ExportMetrics {
+            pools,
+            interval_seconds,
+            prometheus,
+            csv,
+        } => {
+            export_metrics(
+                ExportMetricsParams {
+                    pools,
+                    interval_seconds,
+                    prometheus,
+                    csv,
+                },
+                &program,
+            )?;
+        }
+        Command::ShowPool { pool, json } => {
+            show_pool(ShowPoolParams { pool, json }, &program)?;
+        }
+        Command::ListPools {
+            token_a_mint,
+            token_b_mint,
+            partner,
+            json,
+        } => {
+            list_pools(
+                ListPoolsParams {
+                    token_a_mint,
+                    token_b_mint,
+                    partner,
+                    json,
+                },
+                &program,
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,792 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+    ops::Deref,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use anchor_client::{
+    solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
+    solana_client::{
+        rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::{
+        account::{from_account, Account},
+        address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+        clock::Clock,
+        compute_budget::ComputeBudgetInstruction,
+        instruction::{AccountMeta, Instruction},
+        message::{v0, VersionedMessage},
+        signature::{Keypair, Signature},
+        signer::Signer,
+        sysvar,
+        transaction::{Transaction, VersionedTransaction},
+    },
+    Program, RequestBuilder,
+};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token_2022::spl_token_2022::{
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::{Account as TokenAccount, Mint},
+    },
+};
+use anyhow::{ensure, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cp_amm::{
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    curve,
+    state::{Pool, RewardExtension, RewardInfo},
+    ActivationType,
+};
+use cp_amm_core::transfer_fee::TransferFee;
+use serde::{Deserialize, Deserializer};
+
+pub fn derive_event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &cp_amm::ID).0
+}
+
+pub fn derive_program_authority() -> Pubkey {
+    Pubkey::find_program_address(
+        &[cp_amm::constants::seeds::PROGRAM_AUTHORITY_PREFIX],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_config(index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::CONFIG_PREFIX,
+            index.to_le_bytes().as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_pool_authority() -> Pubkey {
+    Pubkey::find_program_address(
+        &[cp_amm::constants::seeds::POOL_AUTHORITY_PREFIX],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_position(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::POSITION_PREFIX,
+            position_nft_mint.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_claim_fee_operator(operator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::CLAIM_FEE_OPERATOR_PREFIX,
+            operator.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_position_nft_account(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::POSITION_NFT_ACCOUNT_PREFIX,
+            position_nft_mint.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_token_badge(token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::TOKEN_BADGE_PREFIX,
+            token_mint.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_reward_vault(pool: &Pubkey, reward_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::REWARD_VAULT_PREFIX,
+            pool.as_ref(),
+            reward_index.to_le_bytes().as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_reward_extension(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::REWARD_EXTENSION_PREFIX,
+            pool.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_insurance_fund(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::INSURANCE_FUND_PREFIX,
+            pool.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_buyback_burn(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[cp_amm::constants::seeds::BUYBACK_BURN_PREFIX, pool.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_lp_allowlist_entry(pool: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::LP_ALLOWLIST_PREFIX,
+            pool.as_ref(),
+            wallet.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_insurance_vault(insurance_fund: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::INSURANCE_VAULT_PREFIX,
+            insurance_fund.as_ref(),
+            mint.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_position_reward_extension(position: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            cp_amm::constants::seeds::POSITION_REWARD_EXTENSION_PREFIX,
+            position.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+/// Reward info of pool by reward index, reward extension of pool is fetched for extension rewards
+pub fn get_reward_info<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    pool: Pubkey,
+    pool_state: &Pool,
+    reward_index: u8,
+) -> Result<RewardInfo> {
+    let index = usize::from(reward_index);
+    if index < NUM_REWARDS {
+        return Ok(pool_state.reward_infos[index]);
+    }
+    let extension_index = index - NUM_REWARDS;
+    ensure!(
+        extension_index < NUM_EXTENSION_REWARDS,
+        "invalid reward index {reward_index}"
+    );
+    let reward_extension: RewardExtension = program
+        .account(derive_reward_extension(&pool))
+        .context("pool doesn't have reward extension")?;
+    Ok(reward_extension.reward_infos[extension_index])
+}
+
+/// Remaining accounts of reward endpoints, reward extension of pool is required for extension rewards
+pub fn get_reward_remaining_accounts(pool: &Pubkey, reward_index: u8) -> Vec<AccountMeta> {
+    if usize::from(reward_index) < NUM_REWARDS {
+        vec![]
+    } else {
+        vec![AccountMeta::new(derive_reward_extension(pool), false)]
+    }
+}
+
+pub fn get_clock(rpc_client: &RpcClient) -> Result<Clock> {
+    let account = rpc_client.get_account(&sysvar::clock::ID)?;
+    from_account(&account).context("failed to deserialize clock")
+}
+
+/// Token account holding the position nft and its owner
+pub fn get_position_nft_holder(
+    rpc_client: &RpcClient,
+    position_nft_mint: &Pubkey,
+) -> Result<(Pubkey, Pubkey)> {
+    let holder = rpc_client
+        .get_token_largest_accounts(position_nft_mint)?
+        .into_iter()
+        .find(|balance| balance.amount.amount == "1")
+        .with_context(|| format!("no holder of position nft {position_nft_mint}"))?;
+    let position_nft_account = Pubkey::from_str(&holder.address)?;
+    let account = rpc_client.get_account(&position_nft_account)?;
+    let owner = StateWithExtensions::<TokenAccount>::unpack(&account.data)?
+        .base
+        .owner;
+    Ok((position_nft_account, owner))
+}
+
+/// Current slot or timestamp by activation type of pool
+pub fn get_current_point(activation_type: u8, clock: &Clock) -> Result<u64> {
+    let activation_type =
+        ActivationType::try_from(activation_type).context("invalid activation type")?;
+    Ok(match activation_type {
+        ActivationType::Slot => clock.slot,
+        ActivationType::Timestamp => u64::try_from(clock.unix_timestamp)?,
+    })
+}
+
+/// Token mint with its token program and transfer fee of current epoch
+pub struct MintInfo {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    pub transfer_fee: Option<TransferFee>,
+}
+
+pub fn get_mint_info(rpc_client: &RpcClient, mint: Pubkey, epoch: u64) -> Result<MintInfo> {
+    let account = rpc_client
+        .get_account(&mint)
+        .with_context(|| format!("failed to fetch mint {mint}"))?;
+    parse_mint_info(mint, &account, epoch)
+}
+
+/// Mint info of every mint, fetched in pages
+pub fn get_mint_infos(
+    rpc_client: &RpcClient,
+    mints: BTreeSet<Pubkey>,
+    epoch: u64,
+) -> Result<BTreeMap<Pubkey, MintInfo>> {
+    let mints: Vec<Pubkey> = mints.into_iter().collect();
+    let mut mint_infos = BTreeMap::new();
+    for mints in mints.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for (mint, account) in mints.iter().zip(rpc_client.get_multiple_accounts(mints)?) {
+            let account = account.with_context(|| format!("mint {mint} not found"))?;
+            mint_infos.insert(*mint, parse_mint_info(*mint, &account, epoch)?);
+        }
+    }
+    Ok(mint_infos)
+}
+
+fn parse_mint_info(mint: Pubkey, account: &Account, epoch: u64) -> Result<MintInfo> {
+    let mint_state = StateWithExtensions::<Mint>::unpack(&account.data)?;
+    let transfer_fee =
+        mint_state
+            .get_extension::<TransferFeeConfig>()
+            .ok()
+            .map(|transfer_fee_config| {
+                let epoch_fee = transfer_fee_config.get_epoch_fee(epoch);
+                TransferFee {
+                    transfer_fee_basis_points: epoch_fee.transfer_fee_basis_points.into(),
+                    maximum_fee: epoch_fee.maximum_fee.into(),
+                }
+            });
+    Ok(MintInfo {
+        mint,
+        token_program: account.owner,
+        decimals: mint_state.base.decimals,
+        transfer_fee,
+    })
+}
+
+/// Associated token account of owner, with an instruction creating it if it doesn't exist
+pub fn get_or_create_ata_ix(
+    rpc_client: &RpcClient,
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint_info: &MintInfo,
+) -> Result<(Pubkey, Option<Instruction>)> {
+    let ata = get_associated_token_address_with_program_id(
+        owner,
+        &mint_info.mint,
+        &mint_info.token_program,
+    );
+    let create_ata_ix = if rpc_client.get_account(&ata).is_err() {
+        Some(create_associated_token_account_idempotent(
+            payer,
+            owner,
+            &mint_info.mint,
+            &mint_info.token_program,
+        ))
+    } else {
+        None
+    };
+    Ok((ata, create_ata_ix))
+}
+
+/// Ask user to confirm sending the transaction
+pub fn confirm(prompt: &str) -> Result<bool> {
+    print!("{prompt} [y/N] ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Keys of program accounts of type T matching the memcmp filters. Only keys are fetched, fetch
+/// account data with [`fetch_accounts`] in pages
+pub fn get_program_account_keys<T: Discriminator>(
+    rpc_client: &RpcClient,
+    memcmp_filters: Vec<Memcmp>,
+) -> Result<Vec<Pubkey>> {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        0,
+        T::DISCRIMINATOR,
+    ))];
+    filters.extend(memcmp_filters.into_iter().map(RpcFilterType::Memcmp));
+    let accounts = rpc_client.get_program_accounts_with_config(
+        &cp_amm::ID,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                }),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+    Ok(accounts.into_iter().map(|(key, _)| key).collect())
+}
+
+/// Maximum number of accounts of getMultipleAccounts
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Fetch and deserialize accounts in pages, missing accounts are skipped
+pub fn fetch_accounts<T: AccountDeserialize>(
+    rpc_client: &RpcClient,
+    keys: &[Pubkey],
+) -> Result<Vec<(Pubkey, T)>> {
+    let mut accounts = vec![];
+    for keys in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for (key, account) in keys.iter().zip(rpc_client.get_multiple_accounts(keys)?) {
+            if let Some(account) = account {
+                accounts.push((*key, T::try_deserialize(&mut account.data.as_slice())?));
+            }
+        }
+    }
+    Ok(accounts)
+}
+
+/// Deserialize a base58 pubkey string of a config file
+pub fn deserialize_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    Pubkey::from_str(&key).map_err(serde::de::Error::custom)
+}
+
+/// Deserialize a list of base58 pubkey strings of a config file
+pub fn deserialize_pubkeys<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Pubkey>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|key| Pubkey::from_str(key).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// With dry run, instructions are printed instead of sent
+pub fn set_dry_run(dry_run: bool) {
+    DRY_RUN.store(dry_run, Ordering::Relaxed);
+}
+
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// Program id, accounts with writable and signer flags, and hex data of an instruction
+pub fn format_instruction(index: usize, instruction: &Instruction) -> String {
+    let mut output = format!(
+        "Instruction #{index}\n  Program: {}\n  Accounts:\n",
+        instruction.program_id
+    );
+    for (i, account) in instruction.accounts.iter().enumerate() {
+        let mut fields = vec![account.pubkey.to_string()];
+        if account.is_writable {
+            fields.push("writable".to_string());
+        }
+        if account.is_signer {
+            fields.push("signer".to_string());
+        }
+        output += &format!("    #{i} {}\n", fields.join(" "));
+    }
+    output += "  Data: ";
+    for byte in instruction.data.iter() {
+        output += &format!("{byte:02x}");
+    }
+    output
+}
+
+pub fn print_instructions(instructions: &[Instruction]) {
+    for (index, instruction) in instructions.iter().enumerate() {
+        println!("{}", format_instruction(index, instruction));
+    }
+}
+
+/// Priority fee of transactions in micro-lamports per compute unit
+#[derive(Debug, Clone, Copy)]
+pub enum PriorityFee {
+    Fixed(u64),
+    /// Percentile of recent prioritization fees of accounts written by the transaction, capped by max
+    Percentile {
+        percentile: u8,
+        max: Option<u64>,
+    },
+}
+
+/// How transactions are built and sent, transactions are v0 when there are lookup tables
+pub struct TransactionOptions {
+    pub payer: Keypair,
+    pub rpc_client: RpcClient,
+    pub priority_fee: Option<PriorityFee>,
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+static TRANSACTION_OPTIONS: OnceLock<TransactionOptions> = OnceLock::new();
+
+pub fn set_transaction_options(options: TransactionOptions) {
+    let _ = TRANSACTION_OPTIONS.set(options);
+}
+
+pub fn fetch_lookup_tables(
+    rpc_client: &RpcClient,
+    lookup_tables: &[Pubkey],
+) -> Result<Vec<AddressLookupTableAccount>> {
+    if lookup_tables.is_empty() {
+        return Ok(vec![]);
+    }
+    let accounts = rpc_client.get_multiple_accounts(lookup_tables)?;
+    lookup_tables
+        .iter()
+        .zip(accounts)
+        .map(|(key, account)| {
+            let account = account.with_context(|| format!("lookup table {key} not found"))?;
+            let lookup_table = AddressLookupTable::deserialize(&account.data)
+                .with_context(|| format!("{key} is not an address lookup table"))?;
+            Ok(AddressLookupTableAccount {
+                key: *key,
+                addresses: lookup_table.addresses.to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// Fee at the percentile of fees, nearest rank rounded down. Zero without fees
+pub fn get_percentile_fee(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[index]
+}
+
+impl TransactionOptions {
+    fn get_priority_fee(&self, instructions: &[Instruction]) -> Result<u64> {
+        match self.priority_fee {
+            None => Ok(0),
+            Some(PriorityFee::Fixed(priority_fee)) => Ok(priority_fee),
+            Some(PriorityFee::Percentile { percentile, max }) => {
+                let writable_accounts: Vec<Pubkey> = instructions
+                    .iter()
+                    .flat_map(|instruction| instruction.accounts.iter())
+                    .filter(|account| account.is_writable)
+                    .map(|account| account.pubkey)
+                    .collect::<BTreeSet<Pubkey>>()
+                    .into_iter()
+                    .collect();
+                let fees = self
+                    .rpc_client
+                    .get_recent_prioritization_fees(&writable_accounts)?
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect();
+                let priority_fee = get_percentile_fee(fees, percentile);
+                let priority_fee = max.map_or(priority_fee, |max| priority_fee.min(max));
+                println!("Priority fee: {priority_fee} micro-lamports per compute unit");
+                Ok(priority_fee)
+            }
+        }
+    }
+
+    fn send(
+        &self,
+        mut instructions: Vec<Instruction>,
+        signers: &[&dyn Signer],
+    ) -> Result<Signature> {
+        let priority_fee = self.get_priority_fee(&instructions)?;
+        if priority_fee > 0 {
+            instructions.insert(
+                0,
+                ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+            );
+        }
+
+        let payer = self.payer.pubkey();
+        let mut all_signers: Vec<&dyn Signer> = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.rpc_client.get_latest_blockhash()?;
+        let signature = if self.lookup_tables.is_empty() {
+            let transaction = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer),
+                &all_signers,
+                blockhash,
+            );
+            self.rpc_client.send_and_confirm_transaction(&transaction)?
+        } else {
+            let message =
+                v0::Message::try_compile(&payer, &instructions, &self.lookup_tables, blockhash)?;
+            let transaction =
+                VersionedTransaction::try_new(VersionedMessage::V0(message), &all_signers)?;
+            self.rpc_client.send_and_confirm_transaction(&transaction)?
+        };
+        Ok(signature)
+    }
+}
+
+/// Send the request signed by wallet, returns no signature in dry run after printing its instructions.
+/// Priority fee and lookup tables of the transaction options are applied
+pub fn send_request<'a, C: Deref<Target = impl Signer> + Clone>(
+    request: RequestBuilder<'a, C, Box<dyn Signer + 'a>>,
+) -> Result<Option<Signature>> {
+    send_request_with_signers(request, &[])
+}
+
+/// Same as `send_request`, the transaction is also signed by the signers, e.g. keypairs of new accounts
+pub fn send_request_with_signers<'a, C: Deref<Target = impl Signer> + Clone>(
+    mut request: RequestBuilder<'a, C, Box<dyn Signer + 'a>>,
+    signers: &[&'a dyn Signer],
+) -> Result<Option<Signature>> {
+    if is_dry_run() {
+        println!("Dry run, transaction is not sent");
+        print_instructions(&request.instructions()?);
+        return Ok(None);
+    }
+    match TRANSACTION_OPTIONS.get() {
+        Some(options) => Ok(Some(options.send(request.instructions()?, signers)?)),
+        None => {
+            for signer in signers {
+                request = request.signer(*signer);
+            }
+            Ok(Some(request.send()?))
+        }
+    }
+}
+
+/// Send instructions of every item, batch_size items per transaction
+pub fn send_in_batches<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    item_instructions: Vec<Vec<Instruction>>,
+    batch_size: usize,
+) -> Result<()> {
+    ensure!(batch_size > 0, "batch size must be positive");
+    for batch in item_instructions.chunks(batch_size) {
+        let mut request = program.request();
+        for instruction in batch.iter().flatten() {
+            request = request.instruction(instruction.clone());
+        }
+        if let Some(signature) = send_request(request)? {
+            println!("Sent {} item(s). Signature: {signature:#?}", batch.len());
+        }
+    }
+    Ok(())
+}
+
+/// Serialize an unsigned transaction of the instructions with multisig vault as fee payer, encoded in
+/// base58 and base64. Blockhash is left default, it is set by the multisig when executing
+pub fn serialize_multisig_transaction(
+    instructions: &[Instruction],
+    multisig: &Pubkey,
+) -> Result<(String, String)> {
+    let transaction = Transaction::new_with_payer(instructions, Some(multisig));
+    let data = bincode::serialize(&transaction)?;
+    Ok((bs58::encode(&data).into_string(), STANDARD.encode(&data)))
+}
+
+/// Send the request signed by wallet. With a multisig vault, the transaction is printed for
+/// proposing to the multisig instead, returns no signature
+pub fn send_or_print_multisig<'a, C: Deref<Target = impl Signer> + Clone>(
+    request: RequestBuilder<'a, C, Box<dyn Signer + 'a>>,
+    multisig: Option<Pubkey>,
+) -> Result<Option<Signature>> {
+    let Some(multisig) = multisig else {
+        return send_request(request);
+    };
+    if is_dry_run() {
+        print_instructions(&request.instructions()?);
+    }
+    let (base58, base64) = serialize_multisig_transaction(&request.instructions()?, &multisig)?;
+    println!("Transaction for multisig vault {multisig}");
+    println!("Base58: {base58}");
+    println!("Base64: {base64}");
+    Ok(None)
+}
+
+/// Print total amount claimed of every mint
+pub fn print_totals(totals: &BTreeMap<Pubkey, u64>) {
+    println!("Total claimed:");
+    for (mint, amount) in totals {
+        println!("  {mint}: {amount}");
+    }
+}
+
+/// Minimum amount out after applying slippage in bps, rounded down
+pub fn get_minimum_amount_out(amount_out: u64, slippage_bps: u16) -> Result<u64> {
+    ensure!(slippage_bps <= 10_000, "slippage bps must be at most 10000");
+    let minimum_amount_out = u128::from(amount_out) * u128::from(10_000 - slippage_bps) / 10_000;
+    Ok(u64::try_from(minimum_amount_out)?)
+}
+
+/// Maximum amount in after applying slippage in bps, rounded up
+pub fn get_maximum_amount_in(amount_in: u64, slippage_bps: u16) -> Result<u64> {
+    ensure!(slippage_bps <= 10_000, "slippage bps must be at most 10000");
+    let maximum_amount_in =
+        (u128::from(amount_in) * u128::from(10_000 + slippage_bps)).div_ceil(10_000);
+    Ok(u64::try_from(maximum_amount_in).unwrap_or(u64::MAX))
+}
+
+/// Liquidity delta of a token amount at current price of pool. The other token amount is
+/// derived from the liquidity delta
+pub fn get_liquidity_delta(
+    pool: &Pool,
+    token_a_amount: Option<u64>,
+    token_b_amount: Option<u64>,
+) -> Result<u128> {
+    let liquidity_delta = match (token_a_amount, token_b_amount) {
+        (Some(token_a_amount), None) => {
+            ensure!(
+                pool.sqrt_price < pool.sqrt_max_price,
+                "pool price is at max price, only token b can be used"
+            );
+            curve::get_liquidity_from_amount_a(
+                pool.sqrt_price,
+                pool.sqrt_max_price,
+                token_a_amount,
+            )?
+        }
+        (None, Some(token_b_amount)) => {
+            ensure!(
+                pool.sqrt_price > pool.sqrt_min_price,
+                "pool price is at min price, only token a can be used"
+            );
+            curve::get_liquidity_from_amount_b(
+                pool.sqrt_min_price,
+                pool.sqrt_price,
+                token_b_amount,
+            )?
+        }
+        (Some(token_a_amount), Some(token_b_amount)) => {
+            pool.get_liquidity_delta_for_amounts(token_a_amount, token_b_amount)?
+        }
+        (None, None) => 0,
+    };
+    ensure!(liquidity_delta > 0, "liquidity delta is zero");
+    Ok(liquidity_delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_percentile_fee() {
+        assert_eq!(get_percentile_fee(vec![], 75), 0);
+        let fees = vec![500, 0, 100, 300, 200];
+        assert_eq!(get_percentile_fee(fees.clone(), 0), 0);
+        assert_eq!(get_percentile_fee(fees.clone(), 50), 200);
+        assert_eq!(get_percentile_fee(fees.clone(), 75), 300);
+        assert_eq!(get_percentile_fee(fees, 100), 500);
+    }
+
+    #[test]
+    fn test_slippage() {
+        assert_eq!(get_minimum_amount_out(1_000_000, 0).unwrap(), 1_000_000);
+        assert_eq!(get_minimum_amount_out(1_000_000, 50).unwrap(), 995_000);
+        assert_eq!(get_minimum_amount_out(999, 100).unwrap(), 989);
+        assert_eq!(get_minimum_amount_out(u64::MAX, 10_000).unwrap(), 0);
+        assert!(get_minimum_amount_out(1_000_000, 10_001).is_err());
+
+        assert_eq!(get_maximum_amount_in(1_000_000, 50).unwrap(), 1_005_000);
+        assert_eq!(get_maximum_amount_in(999, 100).unwrap(), 1_009);
+        assert_eq!(get_maximum_amount_in(u64::MAX, 100).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_serialize_multisig_transaction() {
+        let multisig = Pubkey::new_unique();
+        let instruction = Instruction {
+            program_id: cp_amm::ID,
+            accounts: vec![AccountMeta::new(multisig, true)],
+            data: vec![1, 2, 3],
+        };
+        let (base58, base64) =
+            serialize_multisig_transaction(&[instruction.clone()], &multisig).unwrap();
+
+        let data = bs58::decode(base58).into_vec().unwrap();
+        assert_eq!(data, STANDARD.decode(base64).unwrap());
+        let transaction: Transaction = bincode::deserialize(&data).unwrap();
+        assert_eq!(transaction.message.account_keys[0], multisig);
+        assert_eq!(transaction.message.header.num_required_signatures, 1);
+        assert_eq!(transaction.message.instructions[0].data, instruction.data);
+    }
+
+    #[test]
+    fn test_format_instruction() {
+        let program_id = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new_readonly(readonly, false),
+            ],
+            data: vec![0, 15, 255],
+        };
+        assert_eq!(
+            format_instruction(1, &instruction),
+            format!(
+                "Instruction #1\n  Program: {program_id}\n  Accounts:\n    #0 {signer} writable signer\n    #1 {readonly}\n  Data: 000fff"
+            )
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Fetching and decoding of cp-amm accounts
+use std::mem::size_of;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_lang::{prelude::Pubkey, ZeroCopy};
+use anyhow::{ensure, Context, Result};
+use cp_amm::state::{Config, Pool, Position};
+
+/// Decode account data of a zero-copy account, checking its size and discriminator
+pub fn decode_account<T: ZeroCopy>(data: &[u8]) -> Result<T> {
+    let discriminator_len = T::DISCRIMINATOR.len();
+    ensure!(
+        data.len() >= discriminator_len + size_of::<T>(),
+        "invalid account size"
+    );
+    ensure!(
+        &data[..discriminator_len] == T::DISCRIMINATOR,
+        "invalid account discriminator"
+    );
+    Ok(bytemuck::pod_read_unaligned(
+        &data[discriminator_len..discriminator_len + size_of::<T>()],
+    ))
+}
+
+/// Fetch and decode a zero-copy account owned by cp-amm
+pub fn fetch_account<T: ZeroCopy>(rpc_client: &RpcClient, address: &Pubkey) -> Result<T> {
+    let account = rpc_client
+        .get_account(address)
+        .with_context(|| format!("failed to fetch account {address}"))?;
+    ensure!(
+        account.owner == cp_amm::ID,
+        "account {address} is not owned by cp-amm"
+    );
+    decode_account(&account.data)
+}
+
+/// Fetch and decode many zero-copy accounts owned by cp-amm, missing accounts are `None`
+pub fn fetch_accounts<T: ZeroCopy>(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<Option<T>>> {
+    rpc_client
+        .get_multiple_accounts(addresses)?
+        .into_iter()
+        .zip(addresses)
+        .map(|(account, address)| {
+            account
+                .map(|account| {
+                    ensure!(
+                        account.owner == cp_amm::ID,
+                        "account {address} is not owned by cp-amm"
+                    );
+                    decode_account(&account.data)
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+pub fn fetch_pool(rpc_client: &RpcClient, pool: &Pubkey) -> Result<Pool> {
+    fetch_account(rpc_client, pool)
+}
+
+pub fn fetch_position(rpc_client: &RpcClient, position: &Pubkey) -> Result<Position> {
+    fetch_account(rpc_client, position)
+}
+
+pub fn fetch_config(rpc_client: &RpcClient, config: &Pubkey) -> Result<Config> {
+    fetch_account(rpc_client, config)
+}
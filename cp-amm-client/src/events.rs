@@ -0,0 +1,195 @@
+//! Typed parsing of cp-amm events
+//!
+//! cp-amm emits events with `emit_cpi!`, as a self cpi whose instruction data is
+//! `EVENT_IX_TAG_LE`, the event discriminator and the borsh serialized event. Events emitted
+//! with `emit!` are logged as `Program data: <base64>` instead, both forms are supported.
+use anchor_lang::{
+    event::EVENT_IX_TAG_LE, prelude::*, solana_program::instruction::CompiledInstruction,
+    Discriminator, Event,
+};
+use anyhow::{bail, ensure, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cp_amm::event::*;
+
+macro_rules! define_events {
+    ($($event:ident => $discriminator:ident;)*) => {
+        /// Discriminators of every cp-amm event
+        pub mod discriminator {
+            use super::*;
+
+            $(
+                pub const $discriminator: &[u8] = <$event as Discriminator>::DISCRIMINATOR;
+            )*
+        }
+
+        /// Names of every cp-amm event
+        pub const EVENT_NAMES: &[&str] = &[$(stringify!($event),)*];
+
+        /// Every event emitted by cp-amm
+        pub enum CpAmmEvent {
+            $($event($event),)*
+        }
+
+        impl CpAmmEvent {
+            /// Name of the event, e.g. `EvtSwap`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(CpAmmEvent::$event(_) => stringify!($event),)*
+                }
+            }
+
+            /// Discriminator and borsh serialized data of the event, as emitted by the program
+            pub fn data(&self) -> Vec<u8> {
+                match self {
+                    $(CpAmmEvent::$event(event) => Event::data(event),)*
+                }
+            }
+        }
+
+        /// Decode an event from its discriminator and borsh serialized data
+        pub fn decode_event(data: &[u8]) -> Result<CpAmmEvent> {
+            $(
+                if data.starts_with(discriminator::$discriminator) {
+                    let mut event_data = &data[discriminator::$discriminator.len()..];
+                    return Ok(CpAmmEvent::$event($event::deserialize(&mut event_data)?));
+                }
+            )*
+            bail!("unknown event discriminator")
+        }
+    };
+}
+
+define_events! {
+    EvtCloseConfig => EVT_CLOSE_CONFIG;
+    EvtCreateConfig => EVT_CREATE_CONFIG;
+    EvtUpdateConfig => EVT_UPDATE_CONFIG;
+    EvtCreateDynamicConfig => EVT_CREATE_DYNAMIC_CONFIG;
+    EvtCreateTokenBadge => EVT_CREATE_TOKEN_BADGE;
+    EvtCloseTokenBadge => EVT_CLOSE_TOKEN_BADGE;
+    EvtCreateClaimFeeOperator => EVT_CREATE_CLAIM_FEE_OPERATOR;
+    EvtInitializeProgramAuthority => EVT_INITIALIZE_PROGRAM_AUTHORITY;
+    EvtTransferAuthorityRole => EVT_TRANSFER_AUTHORITY_ROLE;
+    EvtAcceptAuthorityRole => EVT_ACCEPT_AUTHORITY_ROLE;
+    EvtCloseClaimFeeOperator => EVT_CLOSE_CLAIM_FEE_OPERATOR;
+    EvtUpdateClaimFeeOperator => EVT_UPDATE_CLAIM_FEE_OPERATOR;
+    EvtCreateFeeDiscountRegistry => EVT_CREATE_FEE_DISCOUNT_REGISTRY;
+    EvtUpdateFeeDiscountRegistry => EVT_UPDATE_FEE_DISCOUNT_REGISTRY;
+    EvtInitializePool => EVT_INITIALIZE_POOL;
+    EvtAddLiquidity => EVT_ADD_LIQUIDITY;
+    EvtClaimPositionFee => EVT_CLAIM_POSITION_FEE;
+    EvtSetPositionFeeBeneficiary => EVT_SET_POSITION_FEE_BENEFICIARY;
+    EvtSetPositionOperator => EVT_SET_POSITION_OPERATOR;
+    EvtMintFeeReceipt => EVT_MINT_FEE_RECEIPT;
+    EvtCreatePosition => EVT_CREATE_POSITION;
+    EvtClosePosition => EVT_CLOSE_POSITION;
+    EvtMergePositions => EVT_MERGE_POSITIONS;
+    EvtRemoveLiquidity => EVT_REMOVE_LIQUIDITY;
+    EvtRemoveLiquiditySingleSide => EVT_REMOVE_LIQUIDITY_SINGLE_SIDE;
+    EvtSwap => EVT_SWAP;
+    EvtCreateReferral => EVT_CREATE_REFERRAL;
+    EvtClaimReferralFee => EVT_CLAIM_REFERRAL_FEE;
+    EvtLockPosition => EVT_LOCK_POSITION;
+    EvtPermanentLockPosition => EVT_PERMANENT_LOCK_POSITION;
+    EvtClaimProtocolFee => EVT_CLAIM_PROTOCOL_FEE;
+    EvtClaimInsuranceFund => EVT_CLAIM_INSURANCE_FUND;
+    EvtBuybackAndBurn => EVT_BUYBACK_AND_BURN;
+    EvtClaimPartnerFee => EVT_CLAIM_PARTNER_FEE;
+    EvtCreatePartnerFeeReceiver => EVT_CREATE_PARTNER_FEE_RECEIVER;
+    EvtUpdatePartnerFeeReceiver => EVT_UPDATE_PARTNER_FEE_RECEIVER;
+    EvtClosePartnerFeeReceiver => EVT_CLOSE_PARTNER_FEE_RECEIVER;
+    EvtSetPoolStatus => EVT_SET_POOL_STATUS;
+    EvtClosePool => EVT_CLOSE_POOL;
+    EvtSetPoolEmergencyMode => EVT_SET_POOL_EMERGENCY_MODE;
+    EvtMigratePool => EVT_MIGRATE_POOL;
+    EvtSetConfigFeeOverrideBounds => EVT_SET_CONFIG_FEE_OVERRIDE_BOUNDS;
+    EvtSetConfigCpiGuard => EVT_SET_CONFIG_CPI_GUARD;
+    EvtSetConfigRewardBoost => EVT_SET_CONFIG_REWARD_BOOST;
+    EvtInitializeInsuranceFund => EVT_INITIALIZE_INSURANCE_FUND;
+    EvtInitializeBuybackBurn => EVT_INITIALIZE_BUYBACK_BURN;
+    EvtOverridePoolFee => EVT_OVERRIDE_POOL_FEE;
+    EvtTransferPoolCreatorAuthority => EVT_TRANSFER_POOL_CREATOR_AUTHORITY;
+    EvtAcceptPoolCreatorAuthority => EVT_ACCEPT_POOL_CREATOR_AUTHORITY;
+    EvtUpdatePoolDynamicFee => EVT_UPDATE_POOL_DYNAMIC_FEE;
+    EvtCancelPendingUpdate => EVT_CANCEL_PENDING_UPDATE;
+    EvtCreateVaultWhitelist => EVT_CREATE_VAULT_WHITELIST;
+    EvtUpdateVaultWhitelist => EVT_UPDATE_VAULT_WHITELIST;
+    EvtCreatePoolMetadata => EVT_CREATE_POOL_METADATA;
+    EvtUpdatePoolMetadata => EVT_UPDATE_POOL_METADATA;
+    EvtCreatePoolStats => EVT_CREATE_POOL_STATS;
+    EvtSkimVaults => EVT_SKIM_VAULTS;
+    EvtDonate => EVT_DONATE;
+    EvtCreateSwapAllowlistEntry => EVT_CREATE_SWAP_ALLOWLIST_ENTRY;
+    EvtCloseSwapAllowlistEntry => EVT_CLOSE_SWAP_ALLOWLIST_ENTRY;
+    EvtCreateLpAllowlistEntry => EVT_CREATE_LP_ALLOWLIST_ENTRY;
+    EvtCloseLpAllowlistEntry => EVT_CLOSE_LP_ALLOWLIST_ENTRY;
+    EvtCreateLaunchBuyTracker => EVT_CREATE_LAUNCH_BUY_TRACKER;
+    EvtUpdateActivationPoint => EVT_UPDATE_ACTIVATION_POINT;
+    EvtInitializeReward => EVT_INITIALIZE_REWARD;
+    EvtFundReward => EVT_FUND_REWARD;
+    EvtClaimReward => EVT_CLAIM_REWARD;
+    EvtCreateRewardVestingEscrow => EVT_CREATE_REWARD_VESTING_ESCROW;
+    EvtLockReward => EVT_LOCK_REWARD;
+    EvtClaimVestedReward => EVT_CLAIM_VESTED_REWARD;
+    EvtInitializeTradeMining => EVT_INITIALIZE_TRADE_MINING;
+    EvtFundTradeMining => EVT_FUND_TRADE_MINING;
+    EvtWithdrawUnaccruedTradeMining => EVT_WITHDRAW_UNACCRUED_TRADE_MINING;
+    EvtCreateTraderReward => EVT_CREATE_TRADER_REWARD;
+    EvtClaimTradeMiningReward => EVT_CLAIM_TRADE_MINING_REWARD;
+    EvtUpdateRewardDuration => EVT_UPDATE_REWARD_DURATION;
+    EvtUpdateRewardEmissionSchedule => EVT_UPDATE_REWARD_EMISSION_SCHEDULE;
+    EvtUpdateRewardFunder => EVT_UPDATE_REWARD_FUNDER;
+    EvtWithdrawIneligibleReward => EVT_WITHDRAW_INELIGIBLE_REWARD;
+    EvtUpdateRewards => EVT_UPDATE_REWARDS;
+    EvtInitializeRewardExtension => EVT_INITIALIZE_REWARD_EXTENSION;
+    EvtCreatePositionRewardExtension => EVT_CREATE_POSITION_REWARD_EXTENSION;
+}
+
+/// Decode an event from the instruction data of an event cpi, returns `None` if the instruction
+/// is not an event cpi
+pub fn decode_cpi_event(ix_data: &[u8]) -> Result<Option<CpAmmEvent>> {
+    if !ix_data.starts_with(EVENT_IX_TAG_LE) {
+        return Ok(None);
+    }
+    decode_event(&ix_data[EVENT_IX_TAG_LE.len()..]).map(Some)
+}
+
+/// Decode events from the inner instructions of a transaction. `account_keys` are the account
+/// keys of the transaction, including loaded addresses, which `program_id_index` refers to
+pub fn parse_cpi_events(
+    account_keys: &[Pubkey],
+    inner_instructions: &[CompiledInstruction],
+) -> Result<Vec<CpAmmEvent>> {
+    let mut events = vec![];
+    for instruction in inner_instructions {
+        let program_id = account_keys.get(usize::from(instruction.program_id_index));
+        ensure!(program_id.is_some(), "invalid program id index");
+        if program_id != Some(&cp_amm::ID) {
+            continue;
+        }
+        if let Some(event) = decode_cpi_event(&instruction.data)? {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+/// Decode events logged as `Program data: <base64>` while cp-amm is the executing program
+pub fn parse_log_events(logs: &[String]) -> Result<Vec<CpAmmEvent>> {
+    let program_invoke = format!("Program {} invoke", cp_amm::ID);
+    let mut program_stack: Vec<bool> = vec![];
+    let mut events = vec![];
+    for log in logs {
+        if log.starts_with("Program ") && log.contains(" invoke [") {
+            program_stack.push(log.starts_with(&program_invoke));
+        } else if log.starts_with("Program ")
+            && (log.ends_with(" success") || log.contains(" failed"))
+        {
+            program_stack.pop();
+        } else if let Some(data) = log.strip_prefix("Program data: ") {
+            if program_stack.last() == Some(&true) {
+                events.push(decode_event(&STANDARD.decode(data)?)?);
+            }
+        }
+    }
+    Ok(events)
+}
@@ -0,0 +1,133 @@
+//! Instruction builders of every cp-amm instruction
+//!
+//! Every builder takes the accounts and arguments generated by the program, so accounts and
+//! instruction data can't be mismatched. Event cpi accounts are part of the accounts, use
+//! [`crate::pda::derive_event_authority`] and [`cp_amm::ID`] for them. Remaining accounts, eg:
+//! transfer hook or reward extension accounts, are appended to the returned instruction.
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use cp_amm::{accounts, instruction};
+
+pub fn build_instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+    Instruction {
+        program_id: cp_amm::ID,
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
+
+macro_rules! define_instruction_builders {
+    ($($name:ident => $accounts:ident, $data:ident;)*) => {
+        $(
+            pub fn $name(
+                accounts: accounts::$accounts,
+                data: instruction::$data,
+                remaining_accounts: Vec<AccountMeta>,
+            ) -> Instruction {
+                build_instruction(accounts, data, remaining_accounts)
+            }
+        )*
+    };
+}
+
+define_instruction_builders! {
+    initialize_program_authority => InitializeProgramAuthorityCtx, InitializeProgramAuthority;
+    transfer_authority_role => TransferAuthorityRoleCtx, TransferAuthorityRole;
+    accept_authority_role => AcceptAuthorityRoleCtx, AcceptAuthorityRole;
+    create_config => CreateConfigCtx, CreateConfig;
+    create_dynamic_config => CreateConfigCtx, CreateDynamicConfig;
+    create_token_badge => CreateTokenBadgeCtx, CreateTokenBadge;
+    close_token_badge => CloseTokenBadgeCtx, CloseTokenBadge;
+    create_claim_fee_operator => CreateClaimFeeOperatorCtx, CreateClaimFeeOperator;
+    close_claim_fee_operator => CloseClaimFeeOperatorCtx, CloseClaimFeeOperator;
+    update_claim_fee_operator => UpdateClaimFeeOperatorCtx, UpdateClaimFeeOperator;
+    create_fee_discount_registry => CreateFeeDiscountRegistryCtx, CreateFeeDiscountRegistry;
+    update_fee_discount_registry => UpdateFeeDiscountRegistryCtx, UpdateFeeDiscountRegistry;
+    update_config => UpdateConfigCtx, UpdateConfig;
+    close_config => CloseConfigCtx, CloseConfig;
+    initialize_reward => InitializeRewardCtx, InitializeReward;
+    fund_reward => FundRewardCtx, FundReward;
+    withdraw_ineligible_reward => WithdrawIneligibleRewardCtx, WithdrawIneligibleReward;
+    update_reward_funder => UpdateRewardFunderCtx, UpdateRewardFunder;
+    update_reward_emission_schedule => UpdateRewardEmissionScheduleCtx, UpdateRewardEmissionSchedule;
+    update_reward_duration => UpdateRewardDurationCtx, UpdateRewardDuration;
+    initialize_reward_extension => InitializeRewardExtensionCtx, InitializeRewardExtension;
+    set_pool_status => SetPoolStatusCtx, SetPoolStatus;
+    set_pool_emergency_mode => SetPoolEmergencyModeCtx, SetPoolEmergencyMode;
+    close_pool => ClosePoolCtx, ClosePool;
+    migrate_pool => MigratePoolCtx, MigratePool;
+    set_config_fee_override_bounds => SetConfigFeeOverrideBoundsCtx, SetConfigFeeOverrideBounds;
+    set_config_cpi_guard => SetConfigCpiGuardCtx, SetConfigCpiGuard;
+    set_config_reward_boost => SetConfigRewardBoostCtx, SetConfigRewardBoost;
+    initialize_insurance_fund => InitializeInsuranceFundCtx, InitializeInsuranceFund;
+    initialize_buyback_burn => InitializeBuybackBurnCtx, InitializeBuybackBurn;
+    override_pool_fee => OverridePoolFeeCtx, OverridePoolFee;
+    transfer_pool_creator_authority => TransferPoolCreatorAuthorityCtx, TransferPoolCreatorAuthority;
+    accept_pool_creator_authority => AcceptPoolCreatorAuthorityCtx, AcceptPoolCreatorAuthority;
+    update_pool_dynamic_fee => UpdatePoolDynamicFeeCtx, UpdatePoolDynamicFee;
+    cancel_pending_update => CancelPendingUpdateCtx, CancelPendingUpdate;
+    disable_frozen_pool => DisableFrozenPoolCtx, DisableFrozenPool;
+    skim_vaults => SkimVaultsCtx, SkimVaults;
+    donate => DonateCtx, Donate;
+    create_vault_whitelist => CreateVaultWhitelistCtx, CreateVaultWhitelist;
+    update_vault_whitelist => UpdateVaultWhitelistCtx, UpdateVaultWhitelist;
+    create_pool_metadata => CreatePoolMetadataCtx, CreatePoolMetadata;
+    update_pool_metadata => UpdatePoolMetadataCtx, UpdatePoolMetadata;
+    create_swap_allowlist_entry => CreateSwapAllowlistEntryCtx, CreateSwapAllowlistEntry;
+    close_swap_allowlist_entry => CloseSwapAllowlistEntryCtx, CloseSwapAllowlistEntry;
+    create_lp_allowlist_entry => CreateLpAllowlistEntryCtx, CreateLpAllowlistEntry;
+    close_lp_allowlist_entry => CloseLpAllowlistEntryCtx, CloseLpAllowlistEntry;
+    create_launch_buy_tracker => CreateLaunchBuyTrackerCtx, CreateLaunchBuyTracker;
+    create_pool_stats => CreatePoolStatsCtx, CreatePoolStats;
+    update_activation_point => UpdateActivationPointCtx, UpdateActivationPoint;
+    claim_protocol_fee => ClaimProtocolFeesCtx, ClaimProtocolFee;
+    claim_insurance_fund => ClaimInsuranceFundCtx, ClaimInsuranceFund;
+    buyback_and_burn => BuybackAndBurnCtx, BuybackAndBurn;
+    claim_partner_fee => ClaimPartnerFeesCtx, ClaimPartnerFee;
+    create_partner_fee_receiver => CreatePartnerFeeReceiverCtx, CreatePartnerFeeReceiver;
+    update_partner_fee_receiver => UpdatePartnerFeeReceiverCtx, UpdatePartnerFeeReceiver;
+    close_partner_fee_receiver => ClosePartnerFeeReceiverCtx, ClosePartnerFeeReceiver;
+    initialize_pool => InitializePoolCtx, InitializePool;
+    initialize_pool_with_dynamic_config => InitializePoolWithDynamicConfigCtx, InitializePoolWithDynamicConfig;
+    initialize_customizable_pool => InitializeCustomizablePoolCtx, InitializeCustomizablePool;
+    create_position => CreatePositionCtx, CreatePosition;
+    create_position_reward_extension => CreatePositionRewardExtensionCtx, CreatePositionRewardExtension;
+    add_liquidity => AddLiquidityCtx, AddLiquidity;
+    add_liquidity_by_amounts => AddLiquidityCtx, AddLiquidityByAmounts;
+    remove_liquidity => RemoveLiquidityCtx, RemoveLiquidity;
+    remove_liquidity_single_side => RemoveLiquidityCtx, RemoveLiquiditySingleSide;
+    remove_all_liquidity => RemoveLiquidityCtx, RemoveAllLiquidity;
+    close_position => ClosePositionCtx, ClosePosition;
+    merge_positions => MergePositionsCtx, MergePositions;
+    swap => SwapCtx, Swap;
+    create_referral => CreateReferralCtx, CreateReferral;
+    claim_referral_fee => ClaimReferralFeeCtx, ClaimReferralFee;
+    claim_position_fee => ClaimPositionFeeCtx, ClaimPositionFee;
+    set_position_fee_beneficiary => SetPositionFeeBeneficiaryCtx, SetPositionFeeBeneficiary;
+    set_position_operator => SetPositionOperatorCtx, SetPositionOperator;
+    mint_fee_receipt => MintFeeReceiptCtx, MintFeeReceipt;
+    claim_position_fee_with_receipt => ClaimPositionFeeWithReceiptCtx, ClaimPositionFeeWithReceipt;
+    lock_position => LockPositionCtx, LockPosition;
+    refresh_vesting => RefreshVesting, RefreshVesting;
+    batch_refresh_vesting => BatchRefreshVestingCtx, BatchRefreshVesting;
+    permanent_lock_position => PermanentLockPositionCtx, PermanentLockPosition;
+    permanent_lock_vesting => PermanentLockVestingCtx, PermanentLockVesting;
+    claim_reward => ClaimRewardCtx, ClaimReward;
+    create_reward_vesting_escrow => CreateRewardVestingEscrowCtx, CreateRewardVestingEscrow;
+    claim_vested_reward => ClaimVestedRewardCtx, ClaimVestedReward;
+    initialize_trade_mining => InitializeTradeMiningCtx, InitializeTradeMining;
+    fund_trade_mining => FundTradeMiningCtx, FundTradeMining;
+    withdraw_unaccrued_trade_mining => WithdrawUnaccruedTradeMiningCtx, WithdrawUnaccruedTradeMining;
+    create_trader_reward => CreateTraderRewardCtx, CreateTraderReward;
+    claim_trade_mining_reward => ClaimTradeMiningRewardCtx, ClaimTradeMiningReward;
+    claim_all => ClaimAllCtx, ClaimAll;
+    update_rewards => UpdateRewardsCtx, UpdateRewards;
+    get_pool_state => GetPoolStateCtx, GetPoolState;
+    get_position_value => GetPositionValueCtx, GetPositionValue;
+    get_position_pending => GetPositionPendingCtx, GetPositionPending;
+}
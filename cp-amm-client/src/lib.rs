@@ -0,0 +1,15 @@
+//! Rust client of the cp-amm program
+//!
+//! Exposes instruction builders of every instruction, PDA derivations of every account, account
+//! fetching and decoding of the zero-copy accounts, typed event parsing, and quotes from
+//! `rust-sdk`, so integrators don't need to build instructions by hand.
+pub mod account;
+pub mod events;
+pub mod ix;
+pub mod pda;
+
+pub use cp_amm::{accounts, instruction, state, ID};
+pub use rust_sdk::{quote, ui_amount};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,215 @@
+//! PDA derivations of cp-amm accounts
+use std::cmp::{max, min};
+
+use anchor_lang::prelude::Pubkey;
+use cp_amm::constants::seeds::*;
+
+pub fn derive_pool_authority() -> Pubkey {
+    Pubkey::find_program_address(&[POOL_AUTHORITY_PREFIX], &cp_amm::ID).0
+}
+
+pub fn derive_event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &cp_amm::ID).0
+}
+
+pub fn derive_program_authority() -> Pubkey {
+    Pubkey::find_program_address(&[PROGRAM_AUTHORITY_PREFIX], &cp_amm::ID).0
+}
+
+pub fn derive_config(index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_PREFIX, index.to_le_bytes().as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_pool(config: &Pubkey, token_a_mint: &Pubkey, token_b_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            POOL_PREFIX,
+            config.as_ref(),
+            max(token_a_mint, token_b_mint).as_ref(),
+            min(token_a_mint, token_b_mint).as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_customizable_pool(token_a_mint: &Pubkey, token_b_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CUSTOMIZABLE_POOL_PREFIX,
+            max(token_a_mint, token_b_mint).as_ref(),
+            min(token_a_mint, token_b_mint).as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_token_vault(token_mint: &Pubkey, pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TOKEN_VAULT_PREFIX, token_mint.as_ref(), pool.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_position(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POSITION_PREFIX, position_nft_mint.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_position_nft_account(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POSITION_NFT_ACCOUNT_PREFIX, position_nft_mint.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_token_badge(token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TOKEN_BADGE_PREFIX, token_mint.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_reward_vault(pool: &Pubkey, reward_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REWARD_VAULT_PREFIX,
+            pool.as_ref(),
+            reward_index.to_le_bytes().as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_claim_fee_operator(operator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CLAIM_FEE_OPERATOR_PREFIX, operator.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_reward_extension(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[REWARD_EXTENSION_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_position_reward_extension(position: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POSITION_REWARD_EXTENSION_PREFIX, position.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_fee_receipt_mint(position: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[FEE_RECEIPT_MINT_PREFIX, position.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_fee_receipt_account(fee_receipt_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[FEE_RECEIPT_ACCOUNT_PREFIX, fee_receipt_mint.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_partner_fee_receiver(partner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PARTNER_FEE_RECEIVER_PREFIX, partner.as_ref(), mint.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_referral(pool: &Pubkey, referrer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REFERRAL_PREFIX, pool.as_ref(), referrer.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_fee_discount_registry(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[FEE_DISCOUNT_REGISTRY_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_vault_whitelist(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_WHITELIST_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_pool_metadata(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POOL_METADATA_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_pool_stats(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POOL_STATS_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_swap_allowlist_entry(pool: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SWAP_ALLOWLIST_PREFIX, pool.as_ref(), wallet.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_lp_allowlist_entry(pool: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[LP_ALLOWLIST_PREFIX, pool.as_ref(), wallet.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_launch_buy_tracker(pool: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[LAUNCH_BUY_TRACKER_PREFIX, pool.as_ref(), wallet.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_reward_vesting_escrow(pool: &Pubkey, reward_index: u8, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REWARD_VESTING_ESCROW_PREFIX,
+            pool.as_ref(),
+            reward_index.to_le_bytes().as_ref(),
+            owner.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_trade_mining(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TRADE_MINING_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_trade_mining_vault(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TRADE_MINING_VAULT_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_trader_reward(pool: &Pubkey, trader: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TRADER_REWARD_PREFIX, pool.as_ref(), trader.as_ref()],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_insurance_fund(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[INSURANCE_FUND_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
+
+pub fn derive_insurance_vault(insurance_fund: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            INSURANCE_VAULT_PREFIX,
+            insurance_fund.as_ref(),
+            mint.as_ref(),
+        ],
+        &cp_amm::ID,
+    )
+    .0
+}
+
+pub fn derive_buyback_burn(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[BUYBACK_BURN_PREFIX, pool.as_ref()], &cp_amm::ID).0
+}
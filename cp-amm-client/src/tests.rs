@@ -0,0 +1,123 @@
+use anchor_lang::{prelude::*, solana_program::instruction::CompiledInstruction, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytemuck::Zeroable;
+use cp_amm::state::{Pool, Position};
+
+use crate::{
+    account::decode_account,
+    accounts,
+    events::{self, CpAmmEvent},
+    instruction, ix, pda,
+};
+
+#[test]
+fn test_decode_account() {
+    let pool = Pool {
+        liquidity: 100,
+        sqrt_price: 200,
+        ..Pool::zeroed()
+    };
+    let mut data = Pool::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&pool));
+
+    let decoded: Pool = decode_account(&data).unwrap();
+    assert_eq!(decoded.liquidity, 100);
+    assert_eq!(decoded.sqrt_price, 200);
+
+    // wrong discriminator
+    assert!(decode_account::<Position>(&data).is_err());
+    // truncated data
+    assert!(decode_account::<Pool>(&data[..data.len() - 1]).is_err());
+}
+
+#[test]
+fn test_build_instruction() {
+    let pool = Pubkey::new_unique();
+    let position = Pubkey::new_unique();
+    let remaining_account = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+    let instruction = ix::get_position_value(
+        accounts::GetPositionValueCtx { pool, position },
+        instruction::GetPositionValue {},
+        vec![remaining_account.clone()],
+    );
+
+    assert_eq!(instruction.program_id, cp_amm::ID);
+    assert_eq!(
+        instruction.data,
+        instruction::GetPositionValue::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new_readonly(position, false),
+            remaining_account,
+        ]
+    );
+}
+
+#[test]
+fn test_derive_pda() {
+    let token_a_mint = Pubkey::new_unique();
+    let token_b_mint = Pubkey::new_unique();
+    let config = pda::derive_config(0);
+    assert_eq!(
+        pda::derive_pool(&config, &token_a_mint, &token_b_mint),
+        pda::derive_pool(&config, &token_b_mint, &token_a_mint)
+    );
+    assert_eq!(
+        pda::derive_event_authority(),
+        Pubkey::find_program_address(&[b"__event_authority"], &cp_amm::ID).0
+    );
+}
+
+#[test]
+fn test_parse_events() {
+    let event = cp_amm::EvtCreatePosition {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        position: Pubkey::new_unique(),
+        position_nft_mint: Pubkey::new_unique(),
+    };
+    let mut event_data = events::discriminator::EVT_CREATE_POSITION.to_vec();
+    event_data.extend_from_slice(&event.try_to_vec().unwrap());
+
+    // event cpi
+    let mut ix_data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+    ix_data.extend_from_slice(&event_data);
+    let other_program = Pubkey::new_unique();
+    let account_keys = vec![other_program, cp_amm::ID];
+    let inner_instructions = vec![
+        CompiledInstruction::new_from_raw_parts(0, ix_data.clone(), vec![]),
+        CompiledInstruction::new_from_raw_parts(1, ix_data, vec![]),
+    ];
+    let parsed = events::parse_cpi_events(&account_keys, &inner_instructions).unwrap();
+    assert_eq!(parsed.len(), 1);
+    match &parsed[0] {
+        CpAmmEvent::EvtCreatePosition(parsed) => {
+            assert_eq!(parsed.pool, event.pool);
+            assert_eq!(parsed.position_nft_mint, event.position_nft_mint);
+        }
+        _ => panic!("unexpected event"),
+    }
+
+    // logs, data logged by other programs is ignored
+    let encoded = STANDARD.encode(&event_data);
+    let logs = vec![
+        format!("Program {} invoke [1]", cp_amm::ID),
+        format!("Program {other_program} invoke [2]"),
+        format!("Program data: {encoded}"),
+        format!("Program {other_program} success"),
+        format!("Program data: {encoded}"),
+        format!("Program {} success", cp_amm::ID),
+    ];
+    let parsed = events::parse_log_events(&logs).unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert!(matches!(parsed[0], CpAmmEvent::EvtCreatePosition(_)));
+    assert_eq!(parsed[0].name(), "EvtCreatePosition");
+    assert!(events::EVENT_NAMES.contains(&parsed[0].name()));
+    assert_eq!(parsed[0].data(), event_data);
+
+    // unknown discriminator
+    assert!(events::decode_event(&[0; 8]).is_err());
+}
@@ -0,0 +1,8 @@
+pub const ONE_Q64: u128 = 1u128 << 64;
+
+pub const BASIS_POINT_MAX: u64 = 10_000;
+
+/// Default fee denominator. DO NOT simply update it as it will break logic that depends on it as default value.
+pub const FEE_DENOMINATOR: u64 = 1_000_000_000;
+
+pub const MAX_FEE_NUMERATOR: u64 = 500_000_000; // 50%
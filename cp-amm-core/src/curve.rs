@@ -0,0 +1,370 @@
+use ruint::aliases::{U256, U512};
+
+use crate::{
+    safe_math::SafeMath,
+    u128x128_math::{mul_div_u256, Rounding},
+    MathError, Result,
+};
+
+pub const RESOLUTION: u8 = 64;
+
+pub fn get_initialize_amounts(
+    sqrt_min_price: u128,
+    sqrt_max_price: u128,
+    sqrt_price: u128,
+    liquidity: u128,
+) -> Result<(u64, u64)> {
+    // BASE TOKEN
+    let amount_a =
+        get_delta_amount_a_unsigned(sqrt_price, sqrt_max_price, liquidity, Rounding::Up)?;
+    // QUOTE TOKEN
+    let amount_b =
+        get_delta_amount_b_unsigned(sqrt_min_price, sqrt_price, liquidity, Rounding::Up)?;
+    Ok((amount_a, amount_b))
+}
+
+/// Gets the delta amount_a for given liquidity and price range
+///
+/// # Formula
+///
+/// * `Δa = L * (1 / √P_lower - 1 / √P_upper)`
+/// * i.e. `L * (√P_upper - √P_lower) / (√P_upper * √P_lower)`
+pub fn get_delta_amount_a_unsigned(
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    liquidity: u128,
+    round: Rounding,
+) -> Result<u64> {
+    let result = get_delta_amount_a_unsigned_unchecked(
+        lower_sqrt_price,
+        upper_sqrt_price,
+        liquidity,
+        round,
+    )?;
+    if result > U256::from(u64::MAX) {
+        return Err(MathError::MathOverflow);
+    }
+    result.try_into().map_err(|_| MathError::TypeCastFailed)
+}
+
+/// * i.e. `L * (√P_upper - √P_lower) / (√P_upper * √P_lower)`
+pub fn get_delta_amount_a_unsigned_unchecked(
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    liquidity: u128,
+    round: Rounding,
+) -> Result<U256> {
+    if lower_sqrt_price == 0 || lower_sqrt_price > upper_sqrt_price {
+        return Err(MathError::InvalidPriceRange);
+    }
+    let numerator_1 = U256::from(liquidity);
+    let numerator_2 = U256::from(upper_sqrt_price - lower_sqrt_price);
+
+    let denominator = U256::from(lower_sqrt_price).safe_mul(U256::from(upper_sqrt_price))?;
+
+    mul_div_u256(numerator_1, numerator_2, denominator, round).ok_or(MathError::MathOverflow)
+}
+
+/// Gets the delta amount_b for given liquidity and price range
+/// * `Δb = L (√P_upper - √P_lower)`
+pub fn get_delta_amount_b_unsigned(
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    liquidity: u128,
+    round: Rounding,
+) -> Result<u64> {
+    let result = get_delta_amount_b_unsigned_unchecked(
+        lower_sqrt_price,
+        upper_sqrt_price,
+        liquidity,
+        round,
+    )?;
+    if result > U256::from(u64::MAX) {
+        return Err(MathError::MathOverflow);
+    }
+    result.try_into().map_err(|_| MathError::TypeCastFailed)
+}
+
+//Δb = L (√P_upper - √P_lower)
+pub fn get_delta_amount_b_unsigned_unchecked(
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    liquidity: u128,
+    round: Rounding,
+) -> Result<U256> {
+    if lower_sqrt_price > upper_sqrt_price {
+        return Err(MathError::InvalidPriceRange);
+    }
+    let liquidity = U256::from(liquidity);
+    let delta_sqrt_price = U256::from(upper_sqrt_price - lower_sqrt_price);
+    let prod = liquidity.safe_mul(delta_sqrt_price)?;
+
+    match round {
+        Rounding::Up => {
+            let denominator = U256::from(1).safe_shl((RESOLUTION as usize) * 2)?;
+            let result = prod.div_ceil(denominator);
+            Ok(result)
+        }
+        Rounding::Down => {
+            let (result, _) = prod.overflowing_shr((RESOLUTION as usize) * 2);
+            Ok(result)
+        }
+    }
+}
+
+/// Gets the liquidity for given amount_a and price range, rounding down.
+/// Returns u128::MAX if the liquidity doesn't fit in u128 or the price range is empty
+///
+/// # Formula
+///
+/// * `L = Δa * √P_upper * √P_lower / (√P_upper - √P_lower)`
+pub fn get_liquidity_from_amount_a(
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    amount_a: u64,
+) -> Result<u128> {
+    let delta_sqrt_price = upper_sqrt_price.safe_sub(lower_sqrt_price)?;
+    if delta_sqrt_price == 0 {
+        return Ok(u128::MAX);
+    }
+
+    let prod = U512::from(amount_a)
+        .safe_mul(U512::from(lower_sqrt_price))?
+        .safe_mul(U512::from(upper_sqrt_price))?;
+    let liquidity = prod.safe_div(U512::from(delta_sqrt_price))?;
+
+    Ok(u128::try_from(liquidity).unwrap_or(u128::MAX))
+}
+
+/// Gets the liquidity for given amount_b and price range, rounding down.
+/// Returns u128::MAX if the liquidity doesn't fit in u128 or the price range is empty
+///
+/// # Formula
+///
+/// * `L = Δb / (√P_upper - √P_lower)`
+pub fn get_liquidity_from_amount_b(
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    amount_b: u64,
+) -> Result<u128> {
+    let delta_sqrt_price = upper_sqrt_price.safe_sub(lower_sqrt_price)?;
+    if delta_sqrt_price == 0 {
+        return Ok(u128::MAX);
+    }
+
+    let liquidity = U256::from(amount_b)
+        .safe_shl((RESOLUTION as usize) * 2)?
+        .safe_div(U256::from(delta_sqrt_price))?;
+
+    Ok(u128::try_from(liquidity).unwrap_or(u128::MAX))
+}
+
+/// Gets the maximum liquidity that amount_a and amount_b can add at sqrt_price in the price range,
+/// inverse of get_initialize_amounts rounding down
+///
+/// # Formula
+///
+/// * `L = min(L_a(√P, √P_max), L_b(√P_min, √P))`
+pub fn get_liquidity_for_amounts(
+    sqrt_min_price: u128,
+    sqrt_max_price: u128,
+    sqrt_price: u128,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    let liquidity_from_a = get_liquidity_from_amount_a(sqrt_price, sqrt_max_price, amount_a)?;
+    let liquidity_from_b = get_liquidity_from_amount_b(sqrt_min_price, sqrt_price, amount_b)?;
+    Ok(liquidity_from_a.min(liquidity_from_b))
+}
+
+/// Gets the next sqrt price given an input amount of token_a or token_b
+/// Throws if price or liquidity are 0, or if the next price is out of bounds
+pub fn get_next_sqrt_price_from_input(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u64,
+    a_for_b: bool,
+) -> Result<u128> {
+    if sqrt_price == 0 {
+        return Err(MathError::InvalidPriceRange);
+    }
+    if liquidity == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    // round to make sure that we don't pass the target price
+    if a_for_b {
+        get_next_sqrt_price_from_amount_a_rounding_up(sqrt_price, liquidity, amount_in)
+    } else {
+        get_next_sqrt_price_from_amount_b_rounding_down(sqrt_price, liquidity, amount_in)
+    }
+}
+
+/// Gets the next sqrt price √P' given a delta of token_a
+///
+/// Always round up because
+/// 1. In the exact output case, token 0 supply decreases leading to price increase.
+///    Move price up so that exact output is met.
+/// 2. In the exact input case, token 0 supply increases leading to price decrease.
+///    Do not round down to minimize price impact. We only need to meet input
+///    change and not guarantee exact output.
+///
+/// Use function for exact input or exact output swaps for token 0
+///
+/// # Formula
+///
+/// * `√P' = √P * L / (L + Δx * √P)`
+/// * If Δx * √P overflows, use alternate form `√P' = L / (L/√P + Δx)`
+///
+/// # Proof
+///
+/// For constant L,
+///
+///  L = x * √P
+///  x' = x + Δx
+///  x' * √P' = x * √P
+///  (x + Δx) * √P' = x * √P
+///  √P' = (x * √P) / (x + Δx)
+///  x = L/√P
+///  √P' = √P * L / (L + Δx * √P)
+///
+pub fn get_next_sqrt_price_from_amount_a_rounding_up(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+) -> Result<u128> {
+    if amount == 0 {
+        return Ok(sqrt_price);
+    }
+    let sqrt_price = U256::from(sqrt_price);
+    let liquidity = U256::from(liquidity);
+
+    let product = U256::from(amount).safe_mul(sqrt_price)?;
+    let denominator = liquidity.safe_add(product)?;
+    let result = mul_div_u256(liquidity, sqrt_price, denominator, Rounding::Up)
+        .ok_or(MathError::MathOverflow)?;
+    result.try_into().map_err(|_| MathError::TypeCastFailed)
+}
+
+/// Gets the next sqrt price given a delta of token_b
+///
+/// Always round down because
+/// 1. In the exact output case, token 1 supply decreases leading to price decrease.
+///    Move price down by rounding down so that exact output of token 0 is met.
+/// 2. In the exact input case, token 1 supply increases leading to price increase.
+///    Do not round down to minimize price impact. We only need to meet input
+///    change and not gurantee exact output for token 0.
+///
+///
+/// # Formula
+///
+/// * `√P' = √P + Δy / L`
+///
+pub fn get_next_sqrt_price_from_amount_b_rounding_down(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+) -> Result<u128> {
+    let quotient = U256::from(amount)
+        .safe_shl((RESOLUTION * 2) as usize)?
+        .safe_div(U256::from(liquidity))?;
+
+    let result = U256::from(sqrt_price).safe_add(quotient)?;
+    result.try_into().map_err(|_| MathError::TypeCastFailed)
+}
+
+/// Gets the next sqrt price given an output amount of token_b (a_for_b) or token_a
+/// Throws if price or liquidity are 0, or if the output amount isn't less than the reserve of
+/// the curve
+///
+/// Next price is rounded away from the current price, so the swap is over a price range at least
+/// as wide as the exact one and the input amount, rounded up on that range, never favors the trader
+pub fn get_next_sqrt_price_from_output(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_out: u64,
+    a_for_b: bool,
+) -> Result<u128> {
+    if sqrt_price == 0 {
+        return Err(MathError::InvalidPriceRange);
+    }
+    if liquidity == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    if a_for_b {
+        get_next_sqrt_price_from_output_amount_b_rounding_down(sqrt_price, liquidity, amount_out)
+    } else {
+        get_next_sqrt_price_from_output_amount_a_rounding_up(sqrt_price, liquidity, amount_out)
+    }
+}
+
+/// Gets the next sqrt price √P' given an output delta of token_a
+///
+/// Always round up, token_a supply decreases leading to price increase. Move price up so that
+/// exact output is met.
+///
+/// # Formula
+///
+/// * `√P' = √P * L / (L - Δx * √P)`
+///
+/// # Proof
+///
+/// For constant L,
+///
+///  x * √P = L
+///  x' = x - Δx
+///  (x - Δx) * √P' = x * √P
+///  √P' = √P * L / (L - Δx * √P)
+///
+/// Reserve of token_a on the curve is `x = L / √P`, output is only possible when
+/// `Δx * √P < L`
+///
+pub fn get_next_sqrt_price_from_output_amount_a_rounding_up(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+) -> Result<u128> {
+    if amount == 0 {
+        return Ok(sqrt_price);
+    }
+    let sqrt_price = U256::from(sqrt_price);
+    let liquidity = U256::from(liquidity);
+
+    let product = U256::from(amount).safe_mul(sqrt_price)?;
+    if product >= liquidity {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    let denominator = liquidity.safe_sub(product)?;
+    let result = mul_div_u256(liquidity, sqrt_price, denominator, Rounding::Up)
+        .ok_or(MathError::MathOverflow)?;
+    result.try_into().map_err(|_| MathError::TypeCastFailed)
+}
+
+/// Gets the next sqrt price √P' given an output delta of token_b
+///
+/// Always round down, token_b supply decreases leading to price decrease. Move price down by
+/// rounding up the price delta so that exact output is met.
+///
+/// # Formula
+///
+/// * `√P' = √P - Δy / L`
+///
+/// Reserve of token_b on the curve is `y = L * √P`, output is only possible when `Δy / L < √P`
+///
+pub fn get_next_sqrt_price_from_output_amount_b_rounding_down(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+) -> Result<u128> {
+    let quotient = U256::from(amount)
+        .safe_shl((RESOLUTION * 2) as usize)?
+        .div_ceil(U256::from(liquidity));
+
+    let sqrt_price = U256::from(sqrt_price);
+    if quotient >= sqrt_price {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    let result = sqrt_price.safe_sub(quotient)?;
+    result.try_into().map_err(|_| MathError::TypeCastFailed)
+}
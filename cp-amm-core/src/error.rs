@@ -0,0 +1,30 @@
+use core::fmt;
+
+/// Errors of math, curve and fee calculation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    MathOverflow,
+    TypeCastFailed,
+    FeeInverseIsIncorrect,
+    PriceRangeViolation,
+    InvalidPriceRange,
+    InsufficientLiquidity,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            MathError::MathOverflow => "Math operation overflow",
+            MathError::TypeCastFailed => "Type cast error",
+            MathError::FeeInverseIsIncorrect => "Fee inverse is incorrect",
+            MathError::PriceRangeViolation => "Trade is over price range",
+            MathError::InvalidPriceRange => "Invalid Price Range",
+            MathError::InsufficientLiquidity => "Insufficient liquidity",
+        };
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for MathError {}
+
+pub type Result<T> = core::result::Result<T, MathError>;
@@ -0,0 +1,349 @@
+use crate::{
+    constants::{BASIS_POINT_MAX, FEE_DENOMINATOR, MAX_FEE_NUMERATOR, ONE_Q64},
+    safe_math::SafeMath,
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u64,
+    MathError, Result,
+};
+
+const MAX_EXPONENTIAL: u32 = 0x80000; // 1048576
+const SCALE_OFFSET: u32 = 64;
+
+/// Fee scheduler mode of base fee
+pub const FEE_SCHEDULER_MODE_LINEAR: u8 = 0;
+pub const FEE_SCHEDULER_MODE_EXPONENTIAL: u8 = 1;
+
+/// Encodes all results of swapping
+#[derive(Debug, PartialEq)]
+pub struct FeeOnAmountResult {
+    pub amount: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub partner_fee: u64,
+    pub referral_fee: u64,
+}
+
+// cliff_fee_numerator * (1-reduction_factor/10_000)^passed_period
+pub fn get_fee_in_period(
+    cliff_fee_numerator: u64,
+    reduction_factor: u64,
+    passed_period: u16,
+) -> Result<u64> {
+    if reduction_factor == 0 {
+        return Ok(cliff_fee_numerator);
+    }
+    // Make bin_step into Q64x64, and divided by BASIS_POINT_MAX. If bin_step = 1, we get 0.0001 in Q64x64
+    let bps = u128::from(reduction_factor)
+        .safe_shl(SCALE_OFFSET)?
+        .safe_div(BASIS_POINT_MAX.into())?;
+    let base = ONE_Q64.safe_sub(bps)?;
+    let result = pow(base, passed_period.into()).ok_or(MathError::MathOverflow)?;
+
+    let (fee, _) = result
+        .safe_mul(cliff_fee_numerator.into())?
+        .overflowing_shr(SCALE_OFFSET);
+
+    let fee_numerator = u64::try_from(fee).map_err(|_| MathError::TypeCastFailed)?;
+    Ok(fee_numerator)
+}
+
+/// Base fee numerator at current point. Fee scheduler starts from activation point,
+/// before activation point (alpha vault buying) the min fee is used
+pub fn get_base_fee_numerator(
+    cliff_fee_numerator: u64,
+    fee_scheduler_mode: u8,
+    number_of_period: u16,
+    period_frequency: u64,
+    reduction_factor: u64,
+    current_point: u64,
+    activation_point: u64,
+) -> Result<u64> {
+    if period_frequency == 0 {
+        return Ok(cliff_fee_numerator);
+    }
+    let period = if current_point < activation_point {
+        number_of_period.into()
+    } else {
+        let period = current_point
+            .safe_sub(activation_point)?
+            .safe_div(period_frequency)?;
+        period.min(number_of_period.into())
+    };
+
+    match fee_scheduler_mode {
+        FEE_SCHEDULER_MODE_LINEAR => {
+            let fee_numerator = cliff_fee_numerator.safe_sub(period.safe_mul(reduction_factor)?)?;
+            Ok(fee_numerator)
+        }
+        FEE_SCHEDULER_MODE_EXPONENTIAL => {
+            let period = u16::try_from(period).map_err(|_| MathError::MathOverflow)?;
+            get_fee_in_period(cliff_fee_numerator, reduction_factor, period)
+        }
+        _ => Err(MathError::TypeCastFailed),
+    }
+}
+
+/// Variable fee numerator of dynamic fee
+pub fn get_variable_fee(
+    volatility_accumulator: u128,
+    bin_step: u16,
+    variable_fee_control: u32,
+) -> Result<u128> {
+    let square_vfa_bin: u128 = volatility_accumulator
+        .safe_mul(bin_step.into())?
+        .checked_pow(2)
+        .unwrap();
+    // Variable fee control, volatility accumulator, bin step are in basis point unit (10_000)
+    // This is 1e20. Which > 1e9. Scale down it to 1e9 unit and ceiling the remaining.
+    let v_fee = square_vfa_bin.safe_mul(variable_fee_control.into())?;
+
+    let scaled_v_fee = v_fee.safe_add(99_999_999_999)?.safe_div(100_000_000_000)?;
+
+    Ok(scaled_v_fee)
+}
+
+// trade_fee_numerator * (1 - fee_discount_bps / 10_000), discounted fee is rounded up
+pub fn get_discounted_fee_numerator(
+    trade_fee_numerator: u64,
+    fee_discount_bps: u16,
+) -> Result<u64> {
+    if fee_discount_bps == 0 {
+        return Ok(trade_fee_numerator);
+    }
+    safe_mul_div_cast_u64(
+        trade_fee_numerator,
+        BASIS_POINT_MAX.safe_sub(fee_discount_bps.into())?,
+        BASIS_POINT_MAX,
+        Rounding::Up,
+    )
+}
+
+/// Trade fee numerator charged on amount, total trade fee numerator (base fee and variable fee)
+/// is capped at MAX_FEE_NUMERATOR before the discount
+pub fn get_charged_fee_numerator(trade_fee_numerator: u128, fee_discount_bps: u16) -> Result<u64> {
+    let trade_fee_numerator = if trade_fee_numerator > MAX_FEE_NUMERATOR.into() {
+        MAX_FEE_NUMERATOR
+    } else {
+        trade_fee_numerator.try_into().unwrap()
+    };
+    get_discounted_fee_numerator(trade_fee_numerator, fee_discount_bps)
+}
+
+/// Split trade fee of an amount into lp, protocol, partner and referral fee.
+/// Total trade fee numerator (base fee and variable fee) is capped at MAX_FEE_NUMERATOR
+pub fn get_fee_on_amount(
+    amount: u64,
+    trade_fee_numerator: u128,
+    protocol_fee_percent: u8,
+    partner_fee_percent: u8,
+    referral_fee_percent: u8,
+    has_referral: bool,
+    fee_discount_bps: u16,
+) -> Result<FeeOnAmountResult> {
+    let trade_fee_numerator = get_charged_fee_numerator(trade_fee_numerator, fee_discount_bps)?;
+    let lp_fee: u64 =
+        safe_mul_div_cast_u64(amount, trade_fee_numerator, FEE_DENOMINATOR, Rounding::Up)?;
+    // update amount
+    let amount = amount.safe_sub(lp_fee)?;
+
+    let protocol_fee =
+        safe_mul_div_cast_u64(lp_fee, protocol_fee_percent.into(), 100, Rounding::Down)?;
+    // update lp fee
+    let lp_fee = lp_fee.safe_sub(protocol_fee)?;
+
+    let referral_fee = if has_referral {
+        safe_mul_div_cast_u64(
+            protocol_fee,
+            referral_fee_percent.into(),
+            100,
+            Rounding::Down,
+        )?
+    } else {
+        0
+    };
+
+    let protocol_fee_after_referral_fee = protocol_fee.safe_sub(referral_fee)?;
+    let partner_fee = safe_mul_div_cast_u64(
+        protocol_fee_after_referral_fee,
+        partner_fee_percent.into(),
+        100,
+        Rounding::Down,
+    )?;
+
+    let protocol_fee = protocol_fee_after_referral_fee.safe_sub(partner_fee)?;
+
+    Ok(FeeOnAmountResult {
+        amount,
+        lp_fee,
+        protocol_fee,
+        partner_fee,
+        referral_fee,
+    })
+}
+
+pub fn pow(base: u128, exp: i32) -> Option<u128> {
+    // If exponent is negative. We will invert the result later by 1 / base^exp.abs()
+    let mut invert = exp.is_negative();
+
+    // When exponential is 0, result will always be 1
+    if exp == 0 {
+        return Some(1u128 << 64);
+    }
+
+    // Make the exponential positive. Which will compute the result later by 1 / base^exp
+    let exp: u32 = if invert {
+        exp.unsigned_abs()
+    } else {
+        exp as u32
+    };
+
+    // No point to continue the calculation as it will overflow the maximum value Q64.64 can support
+    if exp >= MAX_EXPONENTIAL {
+        return None;
+    }
+
+    let mut squared_base = base;
+    let mut result = ONE_Q64;
+
+    // When multiply the base twice, the number of bits double from 128 -> 256, which overflow.
+    // The trick here is to inverse the calculation, which make the upper 64 bits (number bits) to be 0s.
+    // For example:
+    // let base = 1.001, exp = 5
+    // let neg = 1 / (1.001 ^ 5)
+    // Inverse the neg: 1 / neg
+    // By using a calculator, you will find out that 1.001^5 == 1 / (1 / 1.001^5)
+    if squared_base >= result {
+        // This inverse the base: 1 / base
+        squared_base = u128::MAX.checked_div(squared_base)?;
+        // If exponent is negative, the above already inverted the result. Therefore, at the end of the function, we do not need to invert again.
+        invert = !invert;
+    }
+
+    // The following code is equivalent to looping through each binary value of the exponential.
+    // As explained in MAX_EXPONENTIAL, 19 exponential bits are enough to covert the full bin price.
+    // Therefore, there will be 19 if statements, which similar to the following pseudo code.
+    /*
+        let mut result = 1;
+        while exponential > 0 {
+            if exponential & 1 > 0 {
+                result *= base;
+            }
+            base *= base;
+            exponential >>= 1;
+        }
+    */
+
+    // From right to left
+    // squared_base = 1 * base^1
+    // 1st bit is 1
+    if exp & 0x1 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    // squared_base = base^2
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    // 2nd bit is 1
+    if exp & 0x2 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    // Example:
+    // If the base is 1.001, exponential is 3. Binary form of 3 is ..0011. The last 2 1's bit fulfill the above 2 bitwise condition.
+    // The result will be 1 * base^1 * base^2 == base^3. The process continues until reach the 20th bit
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x4 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x8 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x10 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x20 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x40 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x80 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x100 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x200 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x400 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x800 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x1000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x2000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x4000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x8000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x10000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x20000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    squared_base = (squared_base.checked_mul(squared_base)?) >> SCALE_OFFSET;
+    if exp & 0x40000 > 0 {
+        result = (result.checked_mul(squared_base)?) >> SCALE_OFFSET
+    }
+
+    // Stop here as the next is 20th bit, which > MAX_EXPONENTIAL
+    if result == 0 {
+        return None;
+    }
+
+    if invert {
+        result = u128::MAX.checked_div(result)?;
+    }
+
+    Some(result)
+}
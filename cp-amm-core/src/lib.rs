@@ -0,0 +1,22 @@
+//! Math, curve and fee calculation of cp-amm
+//!
+//! The program uses this crate for its swap and liquidity math, so quotes computed off-chain are
+//! exact. It is `no_std` and doesn't depend on Anchor, so aggregators, simulators and wasm
+//! frontends can use it without the program crate.
+#![no_std]
+
+pub mod constants;
+pub mod curve;
+pub mod error;
+pub mod fee_math;
+pub mod price_math;
+pub mod quote;
+pub mod safe_math;
+pub mod transfer_fee;
+pub mod u128x128_math;
+pub mod utils_math;
+
+pub use error::{MathError, Result};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,93 @@
+//! Conversion between ui price and sqrt price
+//!
+//! Ui price is the price of 1 token a in token b, adjusted by decimals of both tokens. It is a fixed
+//! point number `price / 10^price_scale`, so conversions are exact up to the given rounding.
+
+use ruint::aliases::U512;
+
+use crate::{safe_math::SafeMath, u128x128_math::Rounding, MathError, Result};
+
+fn pow10(exponent: u32) -> Result<U512> {
+    U512::from(10u8)
+        .checked_pow(U512::from(exponent))
+        .ok_or(MathError::MathOverflow)
+}
+
+fn div_rounding(x: U512, y: U512, rounding: Rounding) -> Result<U512> {
+    if y == U512::ZERO {
+        return Err(MathError::MathOverflow);
+    }
+    match rounding {
+        Rounding::Up => Ok(x.div_ceil(y)),
+        Rounding::Down => x.safe_div(y),
+    }
+}
+
+/// Integer square root, rounding down
+fn sqrt_floor(x: U512) -> U512 {
+    if x < U512::from(2u8) {
+        return x;
+    }
+    // initial guess is a power of 2 above the root, newton iterations then decrease monotonically
+    let mut root = U512::from(1u8) << x.bit_len().div_ceil(2);
+    loop {
+        let next_root = (root + x / root) >> 1;
+        if next_root >= root {
+            return root;
+        }
+        root = next_root;
+    }
+}
+
+fn sqrt_rounding(x: U512, rounding: Rounding) -> U512 {
+    let root = sqrt_floor(x);
+    match rounding {
+        Rounding::Up if root * root < x => root + U512::from(1u8),
+        _ => root,
+    }
+}
+
+/// Sqrt price in Q64.64 of ui price `price / 10^price_scale`
+///
+/// # Formula
+///
+/// * `√P = √(price * 10^decimals_b / 10^(price_scale + decimals_a)) * 2^64`
+pub fn get_sqrt_price_from_price(
+    price: u128,
+    price_scale: u8,
+    decimals_a: u8,
+    decimals_b: u8,
+    rounding: Rounding,
+) -> Result<u128> {
+    let numerator = U512::from(price)
+        .safe_mul(pow10(decimals_b.into())?)?
+        .safe_shl(128)?;
+    let denominator = pow10(u32::from(price_scale) + u32::from(decimals_a))?;
+    // rounding the quotient the same way as the root keeps the result rounded correctly
+    let price_x128 = div_rounding(numerator, denominator, rounding)?;
+    sqrt_rounding(price_x128, rounding)
+        .try_into()
+        .map_err(|_| MathError::TypeCastFailed)
+}
+
+/// Ui price of sqrt price in Q64.64, as fixed point number with `price_scale` decimals
+///
+/// # Formula
+///
+/// * `price = (√P / 2^64)^2 * 10^(price_scale + decimals_a) / 10^decimals_b`
+pub fn get_price_from_sqrt_price(
+    sqrt_price: u128,
+    price_scale: u8,
+    decimals_a: u8,
+    decimals_b: u8,
+    rounding: Rounding,
+) -> Result<u128> {
+    let sqrt_price = U512::from(sqrt_price);
+    let numerator = sqrt_price
+        .safe_mul(sqrt_price)?
+        .safe_mul(pow10(u32::from(price_scale) + u32::from(decimals_a))?)?;
+    let denominator = pow10(decimals_b.into())?.safe_shl(128)?;
+    div_rounding(numerator, denominator, rounding)?
+        .try_into()
+        .map_err(|_| MathError::TypeCastFailed)
+}
@@ -0,0 +1,198 @@
+//! Swap quotes of a pool
+//!
+//! Pure functions of pool state and trade fee, the program swaps with `quote_exact_in` so quotes
+//! computed off-chain are exact.
+
+use crate::{
+    constants::FEE_DENOMINATOR,
+    curve::{
+        get_delta_amount_a_unsigned, get_delta_amount_b_unsigned, get_next_sqrt_price_from_input,
+        get_next_sqrt_price_from_output,
+    },
+    fee_math::{get_charged_fee_numerator, get_fee_on_amount, FeeOnAmountResult},
+    safe_math::SafeMath,
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u64,
+    MathError, Result,
+};
+
+/// Pool state and trade fee a swap is quoted with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QuoteParams {
+    pub sqrt_price: u128,
+    pub liquidity: u128,
+    pub sqrt_min_price: u128,
+    pub sqrt_max_price: u128,
+    /// Total trade fee numerator (base fee and variable fee) at the current point
+    pub trade_fee_numerator: u128,
+    pub protocol_fee_percent: u8,
+    pub partner_fee_percent: u8,
+    pub referral_fee_percent: u8,
+    pub has_referral: bool,
+    pub fee_discount_bps: u16,
+    /// Trade fee is charged on input token, otherwise on output token
+    pub fees_on_input: bool,
+    /// Swap token a for token b
+    pub a_to_b: bool,
+}
+
+/// Result of a quote
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Input amount, including trade fee when fee is on input
+    pub amount_in: u64,
+    /// Output amount, excluding trade fee when fee is on output
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub partner_fee: u64,
+    pub referral_fee: u64,
+}
+
+impl QuoteParams {
+    /// Trade fee of an amount, charged on the input or the output depending on fees_on_input
+    fn get_fee_on_amount(&self, amount: u64) -> Result<FeeOnAmountResult> {
+        get_fee_on_amount(
+            amount,
+            self.trade_fee_numerator,
+            self.protocol_fee_percent,
+            self.partner_fee_percent,
+            self.referral_fee_percent,
+            self.has_referral,
+            self.fee_discount_bps,
+        )
+    }
+
+    /// Amount which is at least `amount` after trade fee
+    /// * `amount * FEE_DENOMINATOR / (FEE_DENOMINATOR - fee_numerator)`, rounding up
+    fn get_fee_included_amount(&self, amount: u64) -> Result<u64> {
+        let fee_numerator =
+            get_charged_fee_numerator(self.trade_fee_numerator, self.fee_discount_bps)?;
+        safe_mul_div_cast_u64(
+            amount,
+            FEE_DENOMINATOR,
+            FEE_DENOMINATOR.safe_sub(fee_numerator)?,
+            Rounding::Up,
+        )
+    }
+
+    /// Output amount and next sqrt price of swapping amount in, trade fee excluded
+    fn get_swap_amount_from_input(&self, amount_in: u64) -> Result<(u64, u128)> {
+        let next_sqrt_price = get_next_sqrt_price_from_input(
+            self.sqrt_price,
+            self.liquidity,
+            amount_in,
+            self.a_to_b,
+        )?;
+
+        let output_amount = if self.a_to_b {
+            if next_sqrt_price < self.sqrt_min_price {
+                return Err(MathError::PriceRangeViolation);
+            }
+            get_delta_amount_b_unsigned(
+                next_sqrt_price,
+                self.sqrt_price,
+                self.liquidity,
+                Rounding::Down,
+            )?
+        } else {
+            if next_sqrt_price > self.sqrt_max_price {
+                return Err(MathError::PriceRangeViolation);
+            }
+            get_delta_amount_a_unsigned(
+                self.sqrt_price,
+                next_sqrt_price,
+                self.liquidity,
+                Rounding::Down,
+            )?
+        };
+
+        Ok((output_amount, next_sqrt_price))
+    }
+
+    /// Input amount to swap for at least amount out, trade fee excluded. Next sqrt price is rounded
+    /// away from the current price so that the output is met
+    fn get_swap_amount_from_output(&self, amount_out: u64) -> Result<u64> {
+        let next_sqrt_price = get_next_sqrt_price_from_output(
+            self.sqrt_price,
+            self.liquidity,
+            amount_out,
+            self.a_to_b,
+        )?;
+
+        if self.a_to_b {
+            if next_sqrt_price < self.sqrt_min_price {
+                return Err(MathError::PriceRangeViolation);
+            }
+            get_delta_amount_a_unsigned(
+                next_sqrt_price,
+                self.sqrt_price,
+                self.liquidity,
+                Rounding::Up,
+            )
+        } else {
+            if next_sqrt_price > self.sqrt_max_price {
+                return Err(MathError::PriceRangeViolation);
+            }
+            get_delta_amount_b_unsigned(
+                self.sqrt_price,
+                next_sqrt_price,
+                self.liquidity,
+                Rounding::Up,
+            )
+        }
+    }
+}
+
+/// Quote of swapping amount in
+pub fn quote_exact_in(params: &QuoteParams, amount_in: u64) -> Result<SwapQuote> {
+    let (amount_in_after_fee, fee_on_input) = if params.fees_on_input {
+        let fee = params.get_fee_on_amount(amount_in)?;
+        (fee.amount, Some(fee))
+    } else {
+        (amount_in, None)
+    };
+
+    let (output_amount, next_sqrt_price) =
+        params.get_swap_amount_from_input(amount_in_after_fee)?;
+
+    let fee = match fee_on_input {
+        Some(fee) => FeeOnAmountResult {
+            amount: output_amount,
+            ..fee
+        },
+        None => params.get_fee_on_amount(output_amount)?,
+    };
+
+    Ok(SwapQuote {
+        amount_in,
+        output_amount: fee.amount,
+        next_sqrt_price,
+        lp_fee: fee.lp_fee,
+        protocol_fee: fee.protocol_fee,
+        partner_fee: fee.partner_fee,
+        referral_fee: fee.referral_fee,
+    })
+}
+
+/// Quote of swapping for at least amount out. The input amount is found by inverting the curve and
+/// trade fee, then quoted by `quote_exact_in`, so swapping the returned amount in outputs exactly
+/// the returned output amount
+pub fn quote_exact_out(params: &QuoteParams, amount_out: u64) -> Result<SwapQuote> {
+    let amount_out_before_fee = if params.fees_on_input {
+        amount_out
+    } else {
+        params.get_fee_included_amount(amount_out)?
+    };
+
+    let amount_in_after_fee = params.get_swap_amount_from_output(amount_out_before_fee)?;
+
+    let amount_in = if params.fees_on_input {
+        params.get_fee_included_amount(amount_in_after_fee)?
+    } else {
+        amount_in_after_fee
+    };
+
+    quote_exact_in(params, amount_in)
+}
@@ -0,0 +1,50 @@
+use ruint::aliases::{U256, U512};
+
+use crate::MathError;
+
+/// Checked arithmetic failing with `MathError::MathOverflow`
+pub trait SafeMath<T>: Sized {
+    fn safe_add(self, rhs: Self) -> Result<Self, MathError>;
+    fn safe_mul(self, rhs: Self) -> Result<Self, MathError>;
+    fn safe_div(self, rhs: Self) -> Result<Self, MathError>;
+    fn safe_sub(self, rhs: Self) -> Result<Self, MathError>;
+    fn safe_shl(self, offset: T) -> Result<Self, MathError>;
+}
+
+macro_rules! checked_impl {
+    ($t:ty, $offset:ty) => {
+        impl SafeMath<$offset> for $t {
+            #[inline(always)]
+            fn safe_add(self, v: $t) -> Result<$t, MathError> {
+                self.checked_add(v).ok_or(MathError::MathOverflow)
+            }
+
+            #[inline(always)]
+            fn safe_sub(self, v: $t) -> Result<$t, MathError> {
+                self.checked_sub(v).ok_or(MathError::MathOverflow)
+            }
+
+            #[inline(always)]
+            fn safe_mul(self, v: $t) -> Result<$t, MathError> {
+                self.checked_mul(v).ok_or(MathError::MathOverflow)
+            }
+
+            #[inline(always)]
+            fn safe_div(self, v: $t) -> Result<$t, MathError> {
+                self.checked_div(v).ok_or(MathError::MathOverflow)
+            }
+
+            #[inline(always)]
+            fn safe_shl(self, v: $offset) -> Result<$t, MathError> {
+                self.checked_shl(v).ok_or(MathError::MathOverflow)
+            }
+        }
+    };
+}
+
+checked_impl!(u16, u32);
+checked_impl!(u32, u32);
+checked_impl!(u64, u32);
+checked_impl!(u128, u32);
+checked_impl!(U256, usize);
+checked_impl!(U512, usize);
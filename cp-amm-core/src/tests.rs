@@ -0,0 +1,380 @@
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee as SplTransferFee;
+
+use crate::{
+    constants::MAX_FEE_NUMERATOR,
+    curve::{
+        get_delta_amount_a_unsigned_unchecked, get_delta_amount_b_unsigned_unchecked,
+        get_initialize_amounts, get_liquidity_for_amounts, get_next_sqrt_price_from_input,
+        get_next_sqrt_price_from_output,
+    },
+    fee_math::{get_base_fee_numerator, get_fee_on_amount, get_variable_fee, FeeOnAmountResult},
+    price_math::{get_price_from_sqrt_price, get_sqrt_price_from_price},
+    quote::{quote_exact_out, QuoteParams},
+    transfer_fee::{
+        calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount, TransferFee,
+    },
+    u128x128_math::{mul_div_u256, Rounding},
+    MathError,
+};
+use ruint::aliases::{U256, U512};
+
+fn spl_transfer_fee(transfer_fee: &TransferFee) -> SplTransferFee {
+    SplTransferFee {
+        epoch: 0.into(),
+        maximum_fee: transfer_fee.maximum_fee.into(),
+        transfer_fee_basis_points: transfer_fee.transfer_fee_basis_points.into(),
+    }
+}
+
+#[test]
+fn test_transfer_fee_matches_spl() {
+    for transfer_fee_basis_points in [0u16, 1, 25, 100, 3_333, 9_999] {
+        for maximum_fee in [0u64, 1, 1_000, u64::MAX] {
+            let transfer_fee = TransferFee {
+                transfer_fee_basis_points,
+                maximum_fee,
+            };
+            let spl_transfer_fee = spl_transfer_fee(&transfer_fee);
+            for amount in [0u64, 1, 7, 10_000, 123_456_789, u64::MAX / 10_000] {
+                assert_eq!(
+                    transfer_fee.calculate_fee(amount),
+                    spl_transfer_fee.calculate_fee(amount)
+                );
+                assert_eq!(
+                    transfer_fee.calculate_inverse_fee(amount),
+                    spl_transfer_fee.calculate_inverse_fee(amount)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_transfer_fee_included_amount() {
+    let transfer_fee = TransferFee {
+        transfer_fee_basis_points: 100,
+        maximum_fee: 1_000,
+    };
+
+    let included = calculate_transfer_fee_included_amount(Some(&transfer_fee), 9_900).unwrap();
+    assert_eq!(included.amount, 10_000);
+    assert_eq!(included.transfer_fee, 100);
+
+    let excluded =
+        calculate_transfer_fee_excluded_amount(Some(&transfer_fee), included.amount).unwrap();
+    assert_eq!(excluded.amount, 9_900);
+    assert_eq!(excluded.transfer_fee, 100);
+
+    // maximum fee is charged on large amount
+    let included = calculate_transfer_fee_included_amount(Some(&transfer_fee), 1_000_000).unwrap();
+    assert_eq!(included.amount, 1_001_000);
+    assert_eq!(included.transfer_fee, 1_000);
+
+    // 100% transfer fee
+    let transfer_fee = TransferFee {
+        transfer_fee_basis_points: 10_000,
+        maximum_fee: 1_000,
+    };
+    let included = calculate_transfer_fee_included_amount(Some(&transfer_fee), 500).unwrap();
+    assert_eq!(included.amount, 1_500);
+    assert_eq!(included.transfer_fee, 1_000);
+
+    // mint without transfer fee
+    let excluded = calculate_transfer_fee_excluded_amount(None, 500).unwrap();
+    assert_eq!(excluded.amount, 500);
+    assert_eq!(excluded.transfer_fee, 0);
+}
+
+#[test]
+fn test_base_fee_numerator() {
+    // linear
+    let fee = get_base_fee_numerator(100_000, 0, 10, 10, 5_000, 30, 0).unwrap();
+    assert_eq!(fee, 85_000);
+    // before activation point, min fee is used
+    let fee = get_base_fee_numerator(100_000, 0, 10, 10, 5_000, 0, 10).unwrap();
+    assert_eq!(fee, 50_000);
+    // exponential, fee doesn't go below the last period
+    let fee = get_base_fee_numerator(100_000, 1, 2, 1, 5_000, 100, 0).unwrap();
+    assert!((24_999..=25_000).contains(&fee));
+    // no fee scheduler
+    let fee = get_base_fee_numerator(100_000, 1, 0, 0, 0, 100, 0).unwrap();
+    assert_eq!(fee, 100_000);
+    // invalid fee scheduler mode
+    assert!(get_base_fee_numerator(100_000, 2, 10, 10, 5_000, 30, 0).is_err());
+}
+
+#[test]
+fn test_fee_on_amount() {
+    let variable_fee = get_variable_fee(10_000, 10, 100_000).unwrap();
+    assert_eq!(variable_fee, 10_000);
+
+    let trade_fee_numerator = 10_000_000u128 + variable_fee;
+    let result = get_fee_on_amount(1_000_000, trade_fee_numerator, 20, 50, 20, true, 0).unwrap();
+    assert_eq!(
+        result,
+        FeeOnAmountResult {
+            amount: 989_990,
+            lp_fee: 8_008,
+            protocol_fee: 801,
+            partner_fee: 801,
+            referral_fee: 400,
+        }
+    );
+
+    // trade fee is capped at max fee numerator
+    let result = get_fee_on_amount(1_000_000, u128::MAX, 0, 0, 0, false, 0).unwrap();
+    assert_eq!(
+        result.amount,
+        1_000_000 - 1_000_000 * MAX_FEE_NUMERATOR / 1_000_000_000
+    );
+}
+
+#[test]
+fn test_mul_div_u256() {
+    let x = U256::from(u128::MAX);
+    let y = U256::from(3u8);
+    let denominator = U256::from(2u8);
+    assert!(mul_div_u256(x, y, denominator, Rounding::Down).unwrap() == x * y / denominator);
+    assert!(
+        mul_div_u256(x, y, denominator, Rounding::Up).unwrap()
+            == x * y / denominator + U256::from(1u8)
+    );
+
+    // product overflows U256 but the result fits
+    let result = mul_div_u256(U256::MAX, U256::from(4u8), U256::from(8u8), Rounding::Up).unwrap();
+    assert!(result == U256::MAX / U256::from(2u8) + U256::from(1u8));
+    assert!(mul_div_u256(U256::MAX, U256::from(2u8), U256::from(1u8), Rounding::Down).is_none());
+    assert!(mul_div_u256(x, y, U256::ZERO, Rounding::Down).is_none());
+}
+
+#[test]
+fn test_sqrt_price_from_price() {
+    let one = 1u128 << 64;
+    // price 1 of tokens with the same decimals
+    assert_eq!(
+        get_sqrt_price_from_price(1, 0, 6, 6, Rounding::Down).unwrap(),
+        one
+    );
+    assert_eq!(
+        get_sqrt_price_from_price(1, 0, 6, 6, Rounding::Up).unwrap(),
+        one
+    );
+    // price 4.00 is sqrt price 2
+    assert_eq!(
+        get_sqrt_price_from_price(400, 2, 9, 9, Rounding::Down).unwrap(),
+        2 * one
+    );
+    // 1 token a with 9 decimals for 1000 token b with 6 decimals, raw price is 1
+    assert_eq!(
+        get_sqrt_price_from_price(1_000, 0, 9, 6, Rounding::Down).unwrap(),
+        one
+    );
+
+    // price 2 has no exact sqrt price, rounding brackets the root
+    let sqrt_price_down = get_sqrt_price_from_price(2, 0, 6, 6, Rounding::Down).unwrap();
+    let sqrt_price_up = get_sqrt_price_from_price(2, 0, 6, 6, Rounding::Up).unwrap();
+    assert_eq!(sqrt_price_up, sqrt_price_down + 1);
+    assert!(U256::from(sqrt_price_down).pow(U256::from(2u8)) < U256::from(2u8) << 128);
+    assert!(U256::from(sqrt_price_up).pow(U256::from(2u8)) > U256::from(2u8) << 128);
+
+    assert!(get_sqrt_price_from_price(u128::MAX, 0, 0, 38, Rounding::Down).is_err());
+}
+
+#[test]
+fn test_price_from_sqrt_price() {
+    let one = 1u128 << 64;
+    assert_eq!(
+        get_price_from_sqrt_price(one, 0, 6, 6, Rounding::Down).unwrap(),
+        1
+    );
+    assert_eq!(
+        get_price_from_sqrt_price(2 * one, 2, 9, 9, Rounding::Down).unwrap(),
+        400
+    );
+    assert_eq!(
+        get_price_from_sqrt_price(one, 0, 9, 6, Rounding::Down).unwrap(),
+        1_000
+    );
+    assert_eq!(
+        get_price_from_sqrt_price(one, 3, 6, 9, Rounding::Down).unwrap(),
+        1
+    );
+
+    // price below the scale rounds to 0 or 1
+    assert_eq!(
+        get_price_from_sqrt_price(one, 0, 6, 9, Rounding::Down).unwrap(),
+        0
+    );
+    assert_eq!(
+        get_price_from_sqrt_price(one, 0, 6, 9, Rounding::Up).unwrap(),
+        1
+    );
+
+    // round trip brackets the price within rounding
+    for price in [1u128, 7, 1_234_567, 999_999_999_999] {
+        let sqrt_price = get_sqrt_price_from_price(price, 6, 9, 6, Rounding::Up).unwrap();
+        let price_up = get_price_from_sqrt_price(sqrt_price, 6, 9, 6, Rounding::Up).unwrap();
+        assert!(price_up >= price && price_up <= price + 1);
+
+        let sqrt_price = get_sqrt_price_from_price(price, 6, 9, 6, Rounding::Down).unwrap();
+        let price_down = get_price_from_sqrt_price(sqrt_price, 6, 9, 6, Rounding::Down).unwrap();
+        assert!(price_down <= price && price_down + 1 >= price);
+    }
+}
+
+#[test]
+fn test_liquidity_for_amounts() {
+    let sqrt_min_price = 4295048016;
+    let sqrt_max_price = 79226673521066979257578248091;
+    for sqrt_price in [
+        sqrt_min_price + 1,
+        1u128 << 64,
+        1u128 << 80,
+        sqrt_max_price - 1,
+    ] {
+        for (amount_a, amount_b) in [(1_000_000u64, 1_000_000u64), (1, u64::MAX), (u64::MAX, 1)] {
+            let liquidity = get_liquidity_for_amounts(
+                sqrt_min_price,
+                sqrt_max_price,
+                sqrt_price,
+                amount_a,
+                amount_b,
+            )
+            .unwrap();
+            if liquidity == 0 {
+                continue;
+            }
+            let (used_a, used_b) =
+                get_initialize_amounts(sqrt_min_price, sqrt_max_price, sqrt_price, liquidity)
+                    .unwrap();
+            assert!(used_a <= amount_a && used_b <= amount_b);
+
+            // liquidity is the maximum, one more needs more than one of the amounts
+            let exceeded = match get_initialize_amounts(
+                sqrt_min_price,
+                sqrt_max_price,
+                sqrt_price,
+                liquidity + 1,
+            ) {
+                Ok((used_a, used_b)) => used_a > amount_a || used_b > amount_b,
+                Err(_) => true,
+            };
+            assert!(exceeded);
+        }
+    }
+}
+
+#[test]
+fn test_curve_rejects_malformed_inputs() {
+    let one = 1u128 << 64;
+    assert_eq!(
+        get_delta_amount_a_unsigned_unchecked(2 * one, one, one, Rounding::Up).err(),
+        Some(MathError::InvalidPriceRange)
+    );
+    assert_eq!(
+        get_delta_amount_a_unsigned_unchecked(0, one, one, Rounding::Up).err(),
+        Some(MathError::InvalidPriceRange)
+    );
+    assert_eq!(
+        get_delta_amount_b_unsigned_unchecked(2 * one, one, one, Rounding::Down).err(),
+        Some(MathError::InvalidPriceRange)
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_input(0, one, 1, true),
+        Err(MathError::InvalidPriceRange)
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_input(one, 0, 1, false),
+        Err(MathError::InsufficientLiquidity)
+    );
+
+    let quote_params = QuoteParams {
+        sqrt_price: one,
+        sqrt_max_price: u128::MAX,
+        ..Default::default()
+    };
+    assert_eq!(
+        quote_exact_out(&quote_params, 1),
+        Err(MathError::InsufficientLiquidity)
+    );
+}
+
+#[test]
+fn test_next_sqrt_price_from_output_rounding() {
+    let sqrt_prices = [
+        4295048016u128,
+        1 << 32,
+        1 << 64,
+        3 << 70,
+        79226673521066979257578248091,
+    ];
+    let liquidities = [1u128 << 64, 12345 << 70, 34028236692093846346337460743];
+    let amounts = [1u64, 999, 1_000_000_007, u64::MAX / 3];
+    for sqrt_price in sqrt_prices {
+        for liquidity in liquidities {
+            for amount in amounts {
+                let shifted_amount = U512::from(amount) << 128;
+                let l = U512::from(liquidity);
+
+                // a to b, output token b: (√P - √P') * L >= Δb, tightest √P' rounded down
+                if let Ok(next) =
+                    get_next_sqrt_price_from_output(sqrt_price, liquidity, amount, true)
+                {
+                    assert!(next < sqrt_price);
+                    assert!(U512::from(sqrt_price - next) * l >= shifted_amount);
+                    assert!(U512::from(sqrt_price - next - 1) * l < shifted_amount);
+                }
+
+                // b to a, output token a: √P' * (L - Δa * √P) >= √P * L, tightest √P' rounded up
+                if let Ok(next) =
+                    get_next_sqrt_price_from_output(sqrt_price, liquidity, amount, false)
+                {
+                    assert!(next > sqrt_price);
+                    let remaining = l - U512::from(amount) * U512::from(sqrt_price);
+                    let target = U512::from(sqrt_price) * l;
+                    assert!(U512::from(next) * remaining >= target);
+                    assert!(U512::from(next - 1) * remaining < target);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_next_sqrt_price_from_output_exceeding_reserve() {
+    let sqrt_price = 1u128 << 64;
+    let liquidity = 1_000_000u128 << 64;
+    // reserves of both tokens on the curve are 1_000_000 at price 1
+    assert_eq!(
+        get_next_sqrt_price_from_output(sqrt_price, liquidity, 1_000_000, true),
+        Err(MathError::InsufficientLiquidity)
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_output(sqrt_price, liquidity, 1_000_000, false),
+        Err(MathError::InsufficientLiquidity)
+    );
+    assert!(get_next_sqrt_price_from_output(sqrt_price, liquidity, 999_999, true).is_ok());
+    assert!(get_next_sqrt_price_from_output(sqrt_price, liquidity, 999_999, false).is_ok());
+
+    assert_eq!(
+        get_next_sqrt_price_from_output(sqrt_price, liquidity, 0, true),
+        Ok(sqrt_price)
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_output(sqrt_price, 0, 1, true),
+        Err(MathError::InsufficientLiquidity)
+    );
+
+    let quote_params = QuoteParams {
+        sqrt_price,
+        liquidity,
+        sqrt_max_price: u128::MAX,
+        a_to_b: true,
+        fees_on_input: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        quote_exact_out(&quote_params, 1_000_000),
+        Err(MathError::InsufficientLiquidity)
+    );
+}
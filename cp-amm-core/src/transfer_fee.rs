@@ -0,0 +1,142 @@
+use crate::{constants::BASIS_POINT_MAX, safe_math::SafeMath, MathError, Result};
+
+/// Transfer fee of a token 2022 mint at an epoch, same as `spl_token_2022` `TransferFee`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransferFee {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+impl TransferFee {
+    /// Fee charged on transferring pre_fee_amount, the fee is rounded up
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+        let transfer_fee_basis_points = u128::from(self.transfer_fee_basis_points);
+        if transfer_fee_basis_points == 0 || pre_fee_amount == 0 {
+            return Some(0);
+        }
+        let numerator = u128::from(pre_fee_amount).checked_mul(transfer_fee_basis_points)?;
+        let raw_fee = numerator
+            .checked_add(BASIS_POINT_MAX.into())?
+            .checked_sub(1)?
+            .checked_div(BASIS_POINT_MAX.into())?;
+        let raw_fee = u64::try_from(raw_fee).ok()?;
+        Some(raw_fee.min(self.maximum_fee))
+    }
+
+    /// Amount to transfer, so that post_fee_amount is received
+    pub fn calculate_pre_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        match (self.transfer_fee_basis_points, post_fee_amount) {
+            (0, _) => Some(post_fee_amount),
+            (_, 0) => Some(0),
+            (basis_points, _) if u64::from(basis_points) == BASIS_POINT_MAX => {
+                post_fee_amount.checked_add(self.maximum_fee)
+            }
+            (basis_points, _) => {
+                let numerator = u128::from(post_fee_amount).checked_mul(BASIS_POINT_MAX.into())?;
+                let denominator = u128::from(BASIS_POINT_MAX - u64::from(basis_points));
+                let raw_pre_fee_amount = numerator
+                    .checked_add(denominator)?
+                    .checked_sub(1)?
+                    .checked_div(denominator)?;
+                if raw_pre_fee_amount.checked_sub(post_fee_amount.into())?
+                    >= self.maximum_fee.into()
+                {
+                    post_fee_amount.checked_add(self.maximum_fee)
+                } else {
+                    u64::try_from(raw_pre_fee_amount).ok()
+                }
+            }
+        }
+    }
+
+    /// Fee charged on transferring the pre fee amount of post_fee_amount
+    pub fn calculate_inverse_fee(&self, post_fee_amount: u64) -> Option<u64> {
+        let pre_fee_amount = self.calculate_pre_fee_amount(post_fee_amount)?;
+        self.calculate_fee(pre_fee_amount)
+    }
+}
+
+/// refer code from Orca
+#[derive(Debug, PartialEq)]
+pub struct TransferFeeIncludedAmount {
+    pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TransferFeeExcludedAmount {
+    pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+/// Amount received after transferring transfer_fee_included_amount. None transfer fee means the mint doesn't have transfer fee extension
+pub fn calculate_transfer_fee_excluded_amount(
+    transfer_fee: Option<&TransferFee>,
+    transfer_fee_included_amount: u64,
+) -> Result<TransferFeeExcludedAmount> {
+    if let Some(epoch_transfer_fee) = transfer_fee {
+        let transfer_fee = epoch_transfer_fee
+            .calculate_fee(transfer_fee_included_amount)
+            .ok_or(MathError::MathOverflow)?;
+        let transfer_fee_excluded_amount = transfer_fee_included_amount.safe_sub(transfer_fee)?;
+        return Ok(TransferFeeExcludedAmount {
+            amount: transfer_fee_excluded_amount,
+            transfer_fee,
+        });
+    }
+
+    Ok(TransferFeeExcludedAmount {
+        amount: transfer_fee_included_amount,
+        transfer_fee: 0,
+    })
+}
+
+/// Amount to transfer, so that transfer_fee_excluded_amount is received. None transfer fee means the mint doesn't have transfer fee extension
+pub fn calculate_transfer_fee_included_amount(
+    transfer_fee: Option<&TransferFee>,
+    transfer_fee_excluded_amount: u64,
+) -> Result<TransferFeeIncludedAmount> {
+    if transfer_fee_excluded_amount == 0 {
+        return Ok(TransferFeeIncludedAmount {
+            amount: 0,
+            transfer_fee: 0,
+        });
+    }
+
+    if let Some(epoch_transfer_fee) = transfer_fee {
+        let transfer_fee: u64 =
+            if u64::from(epoch_transfer_fee.transfer_fee_basis_points) == BASIS_POINT_MAX {
+                // edge-case: if transfer fee rate is 100%, current SPL implementation returns 0 as inverse fee.
+                // https://github.com/solana-labs/solana-program-library/blob/fe1ac9a2c4e5d85962b78c3fc6aaf028461e9026/token/program-2022/src/extension/transfer_fee/mod.rs#L95
+
+                // But even if transfer fee is 100%, we can use maximum_fee as transfer fee.
+                // if transfer_fee_excluded_amount + maximum_fee > u64 max, the following checked_add should fail.
+                epoch_transfer_fee.maximum_fee
+            } else {
+                epoch_transfer_fee
+                    .calculate_inverse_fee(transfer_fee_excluded_amount)
+                    .ok_or(MathError::MathOverflow)?
+            };
+
+        let transfer_fee_included_amount = transfer_fee_excluded_amount.safe_add(transfer_fee)?;
+
+        // verify transfer fee calculation for safety
+        let transfer_fee_verification = epoch_transfer_fee
+            .calculate_fee(transfer_fee_included_amount)
+            .ok_or(MathError::MathOverflow)?;
+        if transfer_fee != transfer_fee_verification {
+            // We believe this should never happen
+            return Err(MathError::FeeInverseIsIncorrect);
+        }
+
+        return Ok(TransferFeeIncludedAmount {
+            amount: transfer_fee_included_amount,
+            transfer_fee,
+        });
+    }
+
+    Ok(TransferFeeIncludedAmount {
+        amount: transfer_fee_excluded_amount,
+        transfer_fee: 0,
+    })
+}
@@ -0,0 +1,42 @@
+//! Accounts of the supported instructions, for building instructions off-chain
+use anchor_lang::prelude::*;
+
+use crate::account_meta;
+
+macro_rules! define_client_accounts {
+    ($name:ident {
+        $($field:ident: ($is_writable:expr, $is_signer:expr)),* $(,)?
+    } $(optional {
+        $($optional_field:ident: ($optional_is_writable:expr, $optional_is_signer:expr)),* $(,)?
+    })?) => {
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            $(pub $field: Pubkey,)*
+            $($(pub $optional_field: Option<Pubkey>,)*)?
+            pub event_authority: Pubkey,
+            pub program: Pubkey,
+        }
+
+        impl ToAccountMetas for $name {
+            fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+                let mut account_metas = vec![
+                    $(account_meta(self.$field, $is_writable, $is_signer),)*
+                ];
+                $($(
+                    // optional account is replaced by the program id when it is not provided
+                    account_metas.push(match self.$optional_field {
+                        Some(pubkey) => {
+                            account_meta(pubkey, $optional_is_writable, $optional_is_signer)
+                        }
+                        None => AccountMeta::new_readonly(crate::ID, false),
+                    });
+                )*)?
+                account_metas.push(AccountMeta::new_readonly(self.event_authority, false));
+                account_metas.push(AccountMeta::new_readonly(self.program, false));
+                account_metas
+            }
+        }
+    };
+}
+
+for_each_instruction_accounts!(define_client_accounts);
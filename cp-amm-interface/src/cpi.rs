@@ -0,0 +1,158 @@
+//! Cross program invocation helpers of the supported instructions
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    InstructionData,
+};
+
+use crate::{
+    instruction, AddLiquidityByAmountsParameters, AddLiquidityParameters,
+    RemoveLiquidityParameters, RemoveLiquiditySingleSideParameters, SwapParameters,
+};
+
+pub mod accounts {
+    use anchor_lang::prelude::*;
+
+    use crate::account_meta;
+
+    macro_rules! define_cpi_accounts {
+        ($name:ident {
+            $($field:ident: ($is_writable:expr, $is_signer:expr)),* $(,)?
+        } $(optional {
+            $($optional_field:ident: ($optional_is_writable:expr, $optional_is_signer:expr)),* $(,)?
+        })?) => {
+            pub struct $name<'info> {
+                $(pub $field: AccountInfo<'info>,)*
+                $($(pub $optional_field: Option<AccountInfo<'info>>,)*)?
+                pub event_authority: AccountInfo<'info>,
+                pub program: AccountInfo<'info>,
+            }
+
+            impl ToAccountMetas for $name<'_> {
+                fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+                    let mut account_metas = vec![
+                        $(account_meta(*self.$field.key, $is_writable, $is_signer),)*
+                    ];
+                    $($(
+                        // optional account is replaced by the program id when it is not provided
+                        account_metas.push(match &self.$optional_field {
+                            Some(account) => account_meta(
+                                *account.key,
+                                $optional_is_writable,
+                                $optional_is_signer,
+                            ),
+                            None => AccountMeta::new_readonly(crate::ID, false),
+                        });
+                    )*)?
+                    account_metas.push(AccountMeta::new_readonly(*self.event_authority.key, false));
+                    account_metas.push(AccountMeta::new_readonly(*self.program.key, false));
+                    account_metas
+                }
+            }
+
+            impl<'info> ToAccountInfos<'info> for $name<'info> {
+                fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                    let mut account_infos = vec![$(self.$field.clone(),)*];
+                    $($(
+                        if let Some(account) = &self.$optional_field {
+                            account_infos.push(account.clone());
+                        }
+                    )*)?
+                    account_infos.push(self.event_authority.clone());
+                    account_infos.push(self.program.clone());
+                    account_infos
+                }
+            }
+        };
+    }
+
+    for_each_instruction_accounts!(define_cpi_accounts);
+}
+
+fn invoke_instruction<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    data: impl InstructionData,
+) -> Result<()> {
+    let mut account_metas = ctx.accounts.to_account_metas(None);
+    account_metas.extend(ctx.remaining_accounts.iter().map(|account| AccountMeta {
+        pubkey: *account.key,
+        is_signer: account.is_signer,
+        is_writable: account.is_writable,
+    }));
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: account_metas,
+        data: data.data(),
+    };
+    invoke_signed(&instruction, &ctx.to_account_infos(), ctx.signer_seeds)?;
+    Ok(())
+}
+
+pub fn swap<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::SwapCtx<'info>>,
+    params: SwapParameters,
+) -> Result<()> {
+    invoke_instruction(ctx, instruction::Swap { params })
+}
+
+pub fn add_liquidity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::AddLiquidityCtx<'info>>,
+    params: AddLiquidityParameters,
+) -> Result<()> {
+    invoke_instruction(ctx, instruction::AddLiquidity { params })
+}
+
+pub fn add_liquidity_by_amounts<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::AddLiquidityCtx<'info>>,
+    params: AddLiquidityByAmountsParameters,
+) -> Result<()> {
+    invoke_instruction(ctx, instruction::AddLiquidityByAmounts { params })
+}
+
+pub fn remove_liquidity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::RemoveLiquidityCtx<'info>>,
+    params: RemoveLiquidityParameters,
+) -> Result<()> {
+    invoke_instruction(ctx, instruction::RemoveLiquidity { params })
+}
+
+pub fn remove_liquidity_single_side<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::RemoveLiquidityCtx<'info>>,
+    params: RemoveLiquiditySingleSideParameters,
+) -> Result<()> {
+    invoke_instruction(ctx, instruction::RemoveLiquiditySingleSide { params })
+}
+
+pub fn remove_all_liquidity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::RemoveLiquidityCtx<'info>>,
+    token_a_amount_threshold: u64,
+    token_b_amount_threshold: u64,
+) -> Result<()> {
+    invoke_instruction(
+        ctx,
+        instruction::RemoveAllLiquidity {
+            token_a_amount_threshold,
+            token_b_amount_threshold,
+        },
+    )
+}
+
+pub fn create_position<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::CreatePositionCtx<'info>>,
+) -> Result<()> {
+    invoke_instruction(ctx, instruction::CreatePosition {})
+}
+
+pub fn claim_position_fee<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::ClaimPositionFeeCtx<'info>>,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Result<()> {
+    invoke_instruction(
+        ctx,
+        instruction::ClaimPositionFee {
+            max_amount_a,
+            max_amount_b,
+        },
+    )
+}
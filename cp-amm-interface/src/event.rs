@@ -0,0 +1,58 @@
+//! Events emitted by cp-amm for the supported instructions
+use anchor_lang::prelude::*;
+
+use crate::{AddLiquidityParameters, RemoveLiquidityParameters, SwapParameters, SwapResult};
+
+#[event]
+pub struct EvtAddLiquidity {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub params: AddLiquidityParameters,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub total_amount_a: u64,
+    pub total_amount_b: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct EvtClaimPositionFee {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub fee_a_claimed: u64,
+    pub fee_b_claimed: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct EvtCreatePosition {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+}
+
+#[event]
+pub struct EvtRemoveLiquidity {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub params: RemoveLiquidityParameters,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct EvtSwap {
+    pub pool: Pubkey,
+    pub trade_direction: u8,
+    pub has_referral: bool,
+    pub params: SwapParameters,
+    pub swap_result: SwapResult,
+    pub actual_amount_in: u64,
+    pub current_timestamp: u64,
+    pub sequence: u64,
+}
@@ -0,0 +1,55 @@
+//! Instruction data of the supported instructions
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, InstructionData};
+
+use crate::{
+    AddLiquidityByAmountsParameters, AddLiquidityParameters, RemoveLiquidityParameters,
+    RemoveLiquiditySingleSideParameters, SwapParameters,
+};
+
+macro_rules! instruction_data {
+    ($name:ident, $discriminator:expr, { $($field:ident: $ty:ty),* $(,)? }) => {
+        #[derive(AnchorSerialize, AnchorDeserialize)]
+        pub struct $name {
+            $(pub $field: $ty,)*
+        }
+
+        impl Discriminator for $name {
+            const DISCRIMINATOR: &'static [u8] = &$discriminator;
+        }
+
+        impl InstructionData for $name {}
+    };
+}
+
+instruction_data!(Swap, [248, 198, 158, 145, 225, 117, 135, 200], {
+    params: SwapParameters,
+});
+
+instruction_data!(AddLiquidity, [181, 157, 89, 67, 143, 182, 52, 72], {
+    params: AddLiquidityParameters,
+});
+
+instruction_data!(AddLiquidityByAmounts, [129, 111, 10, 158, 75, 100, 129, 78], {
+    params: AddLiquidityByAmountsParameters,
+});
+
+instruction_data!(RemoveLiquidity, [80, 85, 209, 72, 24, 206, 177, 108], {
+    params: RemoveLiquidityParameters,
+});
+
+instruction_data!(RemoveLiquiditySingleSide, [84, 84, 177, 66, 254, 185, 10, 251], {
+    params: RemoveLiquiditySingleSideParameters,
+});
+
+instruction_data!(RemoveAllLiquidity, [10, 51, 61, 35, 112, 105, 24, 85], {
+    token_a_amount_threshold: u64,
+    token_b_amount_threshold: u64,
+});
+
+instruction_data!(CreatePosition, [48, 215, 197, 153, 96, 203, 180, 133], {});
+
+instruction_data!(ClaimPositionFee, [180, 38, 154, 17, 133, 33, 162, 211], {
+    max_amount_a: u64,
+    max_amount_b: u64,
+});
@@ -0,0 +1,79 @@
+//! Instruction builders of the supported instructions
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+
+use crate::{
+    accounts, instruction, AddLiquidityByAmountsParameters, AddLiquidityParameters,
+    RemoveLiquidityParameters, RemoveLiquiditySingleSideParameters, SwapParameters,
+};
+
+fn build_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn swap(accounts: accounts::SwapCtx, params: SwapParameters) -> Instruction {
+    build_instruction(accounts, instruction::Swap { params })
+}
+
+pub fn add_liquidity(
+    accounts: accounts::AddLiquidityCtx,
+    params: AddLiquidityParameters,
+) -> Instruction {
+    build_instruction(accounts, instruction::AddLiquidity { params })
+}
+
+pub fn add_liquidity_by_amounts(
+    accounts: accounts::AddLiquidityCtx,
+    params: AddLiquidityByAmountsParameters,
+) -> Instruction {
+    build_instruction(accounts, instruction::AddLiquidityByAmounts { params })
+}
+
+pub fn remove_liquidity(
+    accounts: accounts::RemoveLiquidityCtx,
+    params: RemoveLiquidityParameters,
+) -> Instruction {
+    build_instruction(accounts, instruction::RemoveLiquidity { params })
+}
+
+pub fn remove_liquidity_single_side(
+    accounts: accounts::RemoveLiquidityCtx,
+    params: RemoveLiquiditySingleSideParameters,
+) -> Instruction {
+    build_instruction(accounts, instruction::RemoveLiquiditySingleSide { params })
+}
+
+pub fn remove_all_liquidity(
+    accounts: accounts::RemoveLiquidityCtx,
+    token_a_amount_threshold: u64,
+    token_b_amount_threshold: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        instruction::RemoveAllLiquidity {
+            token_a_amount_threshold,
+            token_b_amount_threshold,
+        },
+    )
+}
+
+pub fn create_position(accounts: accounts::CreatePositionCtx) -> Instruction {
+    build_instruction(accounts, instruction::CreatePosition {})
+}
+
+pub fn claim_position_fee(
+    accounts: accounts::ClaimPositionFeeCtx,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        instruction::ClaimPositionFee {
+            max_amount_a,
+            max_amount_b,
+        },
+    )
+}
@@ -0,0 +1,115 @@
+//! Lightweight interface of the cp-amm program
+//!
+//! Exposes the PDA seeds, instruction data, account lists and events of cp-amm, so other
+//! programs and clients can compose with it without depending on the whole program.
+//! Enable the `cpi` feature to get the cross program invocation helpers.
+use anchor_lang::prelude::*;
+
+declare_id!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+/// Invokes `$define` for the accounts of every supported instruction, in the order expected by
+/// the program. Each account is declared as `name: (is_writable, is_signer)`. The event cpi
+/// accounts (`event_authority` and `program`) are appended by `$define`.
+macro_rules! for_each_instruction_accounts {
+    ($define:ident) => {
+        $define!(SwapCtx {
+            pool_authority: (false, false),
+            pool: (true, false),
+            input_token_account: (true, false),
+            output_token_account: (true, false),
+            token_a_vault: (true, false),
+            token_b_vault: (true, false),
+            token_a_mint: (false, false),
+            token_b_mint: (false, false),
+            payer: (false, true),
+            token_a_program: (false, false),
+            token_b_program: (false, false),
+        } optional {
+            referral_token_account: (true, false),
+        });
+        $define!(AddLiquidityCtx {
+            pool: (true, false),
+            position: (true, false),
+            token_a_account: (true, false),
+            token_b_account: (true, false),
+            token_a_vault: (true, false),
+            token_b_vault: (true, false),
+            token_a_mint: (false, false),
+            token_b_mint: (false, false),
+            position_nft_account: (false, false),
+            owner: (false, true),
+            token_a_program: (false, false),
+            token_b_program: (false, false),
+        } optional {
+            lp_allowlist_entry: (false, false),
+        });
+        $define!(RemoveLiquidityCtx {
+            pool_authority: (false, false),
+            pool: (true, false),
+            position: (true, false),
+            token_a_account: (true, false),
+            token_b_account: (true, false),
+            token_a_vault: (true, false),
+            token_b_vault: (true, false),
+            token_a_mint: (false, false),
+            token_b_mint: (false, false),
+            position_nft_account: (false, false),
+            owner: (false, true),
+            token_a_program: (false, false),
+            token_b_program: (false, false),
+        });
+        $define!(CreatePositionCtx {
+            owner: (false, false),
+            position_nft_mint: (true, true),
+            position_nft_account: (true, false),
+            pool: (true, false),
+            position: (true, false),
+            pool_authority: (false, false),
+            payer: (true, true),
+            token_program: (false, false),
+            system_program: (false, false),
+        } optional {
+            lp_allowlist_entry: (false, false),
+        });
+        $define!(ClaimPositionFeeCtx {
+            pool_authority: (false, false),
+            pool: (true, false),
+            position: (true, false),
+            token_a_account: (true, false),
+            token_b_account: (true, false),
+            token_a_vault: (true, false),
+            token_b_vault: (true, false),
+            token_a_mint: (false, false),
+            token_b_mint: (false, false),
+            position_nft_account: (false, false),
+            owner: (false, true),
+            token_a_program: (false, false),
+            token_b_program: (false, false),
+        });
+    };
+}
+
+pub mod accounts;
+pub mod constants;
+pub mod event;
+pub mod instruction;
+pub mod ix;
+pub mod pda;
+pub mod types;
+
+#[cfg(feature = "cpi")]
+pub mod cpi;
+
+pub use constants::seeds;
+pub use types::*;
+
+fn account_meta(pubkey: Pubkey, is_writable: bool, is_signer: bool) -> AccountMeta {
+    if is_writable {
+        AccountMeta::new(pubkey, is_signer)
+    } else {
+        AccountMeta::new_readonly(pubkey, is_signer)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+//! PDA derivations of cp-amm accounts
+use std::cmp::{max, min};
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::seeds::*;
+
+pub fn derive_pool_authority() -> Pubkey {
+    Pubkey::find_program_address(&[POOL_AUTHORITY_PREFIX], &crate::ID).0
+}
+
+pub fn derive_event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_PREFIX], &crate::ID).0
+}
+
+pub fn derive_config(index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_PREFIX, index.to_le_bytes().as_ref()], &crate::ID).0
+}
+
+pub fn derive_pool(config: &Pubkey, token_a_mint: &Pubkey, token_b_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            POOL_PREFIX,
+            config.as_ref(),
+            max(token_a_mint, token_b_mint).as_ref(),
+            min(token_a_mint, token_b_mint).as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn derive_customizable_pool(token_a_mint: &Pubkey, token_b_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CUSTOMIZABLE_POOL_PREFIX,
+            max(token_a_mint, token_b_mint).as_ref(),
+            min(token_a_mint, token_b_mint).as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn derive_token_vault(token_mint: &Pubkey, pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TOKEN_VAULT_PREFIX, token_mint.as_ref(), pool.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn derive_position(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POSITION_PREFIX, position_nft_mint.as_ref()], &crate::ID).0
+}
+
+pub fn derive_position_nft_account(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POSITION_NFT_ACCOUNT_PREFIX, position_nft_mint.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
@@ -0,0 +1,303 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{accounts, event, instruction, pda, seeds};
+
+#[test]
+fn test_program_id_and_seeds_match_program() {
+    assert_eq!(crate::ID, cp_amm::ID);
+    assert_eq!(
+        seeds::POOL_AUTHORITY_PREFIX,
+        cp_amm::constants::seeds::POOL_AUTHORITY_PREFIX
+    );
+    assert_eq!(
+        seeds::CONFIG_PREFIX,
+        cp_amm::constants::seeds::CONFIG_PREFIX
+    );
+    assert_eq!(seeds::POOL_PREFIX, cp_amm::constants::seeds::POOL_PREFIX);
+    assert_eq!(
+        seeds::CUSTOMIZABLE_POOL_PREFIX,
+        cp_amm::constants::seeds::CUSTOMIZABLE_POOL_PREFIX
+    );
+    assert_eq!(
+        seeds::TOKEN_VAULT_PREFIX,
+        cp_amm::constants::seeds::TOKEN_VAULT_PREFIX
+    );
+    assert_eq!(
+        seeds::POSITION_PREFIX,
+        cp_amm::constants::seeds::POSITION_PREFIX
+    );
+    assert_eq!(
+        seeds::POSITION_NFT_ACCOUNT_PREFIX,
+        cp_amm::constants::seeds::POSITION_NFT_ACCOUNT_PREFIX
+    );
+    assert_eq!(
+        seeds::TOKEN_BADGE_PREFIX,
+        cp_amm::constants::seeds::TOKEN_BADGE_PREFIX
+    );
+    assert_eq!(
+        seeds::REWARD_VAULT_PREFIX,
+        cp_amm::constants::seeds::REWARD_VAULT_PREFIX
+    );
+    assert_eq!(
+        seeds::CLAIM_FEE_OPERATOR_PREFIX,
+        cp_amm::constants::seeds::CLAIM_FEE_OPERATOR_PREFIX
+    );
+}
+
+#[test]
+fn test_discriminators_match_program() {
+    assert_eq!(
+        instruction::Swap::DISCRIMINATOR,
+        cp_amm::instruction::Swap::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::AddLiquidity::DISCRIMINATOR,
+        cp_amm::instruction::AddLiquidity::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::AddLiquidityByAmounts::DISCRIMINATOR,
+        cp_amm::instruction::AddLiquidityByAmounts::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::RemoveLiquidity::DISCRIMINATOR,
+        cp_amm::instruction::RemoveLiquidity::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::RemoveLiquiditySingleSide::DISCRIMINATOR,
+        cp_amm::instruction::RemoveLiquiditySingleSide::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::RemoveAllLiquidity::DISCRIMINATOR,
+        cp_amm::instruction::RemoveAllLiquidity::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::CreatePosition::DISCRIMINATOR,
+        cp_amm::instruction::CreatePosition::DISCRIMINATOR
+    );
+    assert_eq!(
+        instruction::ClaimPositionFee::DISCRIMINATOR,
+        cp_amm::instruction::ClaimPositionFee::DISCRIMINATOR
+    );
+
+    assert_eq!(
+        event::EvtSwap::DISCRIMINATOR,
+        cp_amm::EvtSwap::DISCRIMINATOR
+    );
+    assert_eq!(
+        event::EvtAddLiquidity::DISCRIMINATOR,
+        cp_amm::EvtAddLiquidity::DISCRIMINATOR
+    );
+    assert_eq!(
+        event::EvtRemoveLiquidity::DISCRIMINATOR,
+        cp_amm::EvtRemoveLiquidity::DISCRIMINATOR
+    );
+    assert_eq!(
+        event::EvtCreatePosition::DISCRIMINATOR,
+        cp_amm::EvtCreatePosition::DISCRIMINATOR
+    );
+    assert_eq!(
+        event::EvtClaimPositionFee::DISCRIMINATOR,
+        cp_amm::EvtClaimPositionFee::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn test_swap_account_metas_match_program() {
+    for referral_token_account in [None, Some(Pubkey::new_unique())] {
+        let accounts = accounts::SwapCtx {
+            pool_authority: pda::derive_pool_authority(),
+            pool: Pubkey::new_unique(),
+            input_token_account: Pubkey::new_unique(),
+            output_token_account: Pubkey::new_unique(),
+            token_a_vault: Pubkey::new_unique(),
+            token_b_vault: Pubkey::new_unique(),
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            token_a_program: Pubkey::new_unique(),
+            token_b_program: Pubkey::new_unique(),
+            referral_token_account,
+            event_authority: pda::derive_event_authority(),
+            program: crate::ID,
+        };
+        let program_accounts = cp_amm::accounts::SwapCtx {
+            pool_authority: accounts.pool_authority,
+            pool: accounts.pool,
+            input_token_account: accounts.input_token_account,
+            output_token_account: accounts.output_token_account,
+            token_a_vault: accounts.token_a_vault,
+            token_b_vault: accounts.token_b_vault,
+            token_a_mint: accounts.token_a_mint,
+            token_b_mint: accounts.token_b_mint,
+            payer: accounts.payer,
+            token_a_program: accounts.token_a_program,
+            token_b_program: accounts.token_b_program,
+            referral_token_account: accounts.referral_token_account,
+            event_authority: accounts.event_authority,
+            program: accounts.program,
+        };
+        assert_eq!(
+            accounts.to_account_metas(None),
+            program_accounts.to_account_metas(None)
+        );
+        // account list of swap is unchanged since launch, optional accounts are remaining accounts
+        assert_eq!(accounts.to_account_metas(None).len(), 14);
+    }
+}
+
+#[test]
+fn test_liquidity_account_metas_match_program() {
+    let accounts = accounts::RemoveLiquidityCtx {
+        pool_authority: pda::derive_pool_authority(),
+        pool: Pubkey::new_unique(),
+        position: Pubkey::new_unique(),
+        token_a_account: Pubkey::new_unique(),
+        token_b_account: Pubkey::new_unique(),
+        token_a_vault: Pubkey::new_unique(),
+        token_b_vault: Pubkey::new_unique(),
+        token_a_mint: Pubkey::new_unique(),
+        token_b_mint: Pubkey::new_unique(),
+        position_nft_account: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        token_a_program: Pubkey::new_unique(),
+        token_b_program: Pubkey::new_unique(),
+        event_authority: pda::derive_event_authority(),
+        program: crate::ID,
+    };
+    let program_accounts = cp_amm::accounts::RemoveLiquidityCtx {
+        pool_authority: accounts.pool_authority,
+        pool: accounts.pool,
+        position: accounts.position,
+        token_a_account: accounts.token_a_account,
+        token_b_account: accounts.token_b_account,
+        token_a_vault: accounts.token_a_vault,
+        token_b_vault: accounts.token_b_vault,
+        token_a_mint: accounts.token_a_mint,
+        token_b_mint: accounts.token_b_mint,
+        position_nft_account: accounts.position_nft_account,
+        owner: accounts.owner,
+        token_a_program: accounts.token_a_program,
+        token_b_program: accounts.token_b_program,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+    assert_eq!(
+        accounts.to_account_metas(None),
+        program_accounts.to_account_metas(None)
+    );
+
+    let accounts = accounts::AddLiquidityCtx {
+        pool: accounts.pool,
+        position: accounts.position,
+        token_a_account: accounts.token_a_account,
+        token_b_account: accounts.token_b_account,
+        token_a_vault: accounts.token_a_vault,
+        token_b_vault: accounts.token_b_vault,
+        token_a_mint: accounts.token_a_mint,
+        token_b_mint: accounts.token_b_mint,
+        position_nft_account: accounts.position_nft_account,
+        owner: accounts.owner,
+        token_a_program: accounts.token_a_program,
+        token_b_program: accounts.token_b_program,
+        lp_allowlist_entry: None,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+    let program_accounts = cp_amm::accounts::AddLiquidityCtx {
+        pool: accounts.pool,
+        position: accounts.position,
+        token_a_account: accounts.token_a_account,
+        token_b_account: accounts.token_b_account,
+        token_a_vault: accounts.token_a_vault,
+        token_b_vault: accounts.token_b_vault,
+        token_a_mint: accounts.token_a_mint,
+        token_b_mint: accounts.token_b_mint,
+        position_nft_account: accounts.position_nft_account,
+        owner: accounts.owner,
+        token_a_program: accounts.token_a_program,
+        token_b_program: accounts.token_b_program,
+        lp_allowlist_entry: accounts.lp_allowlist_entry,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+    assert_eq!(
+        accounts.to_account_metas(None),
+        program_accounts.to_account_metas(None)
+    );
+
+    let accounts = accounts::ClaimPositionFeeCtx {
+        pool_authority: pda::derive_pool_authority(),
+        pool: accounts.pool,
+        position: accounts.position,
+        token_a_account: accounts.token_a_account,
+        token_b_account: accounts.token_b_account,
+        token_a_vault: accounts.token_a_vault,
+        token_b_vault: accounts.token_b_vault,
+        token_a_mint: accounts.token_a_mint,
+        token_b_mint: accounts.token_b_mint,
+        position_nft_account: accounts.position_nft_account,
+        owner: accounts.owner,
+        token_a_program: accounts.token_a_program,
+        token_b_program: accounts.token_b_program,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+    let program_accounts = cp_amm::accounts::ClaimPositionFeeCtx {
+        pool_authority: accounts.pool_authority,
+        pool: accounts.pool,
+        position: accounts.position,
+        token_a_account: accounts.token_a_account,
+        token_b_account: accounts.token_b_account,
+        token_a_vault: accounts.token_a_vault,
+        token_b_vault: accounts.token_b_vault,
+        token_a_mint: accounts.token_a_mint,
+        token_b_mint: accounts.token_b_mint,
+        position_nft_account: accounts.position_nft_account,
+        owner: accounts.owner,
+        token_a_program: accounts.token_a_program,
+        token_b_program: accounts.token_b_program,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+    assert_eq!(
+        accounts.to_account_metas(None),
+        program_accounts.to_account_metas(None)
+    );
+}
+
+#[test]
+fn test_create_position_account_metas_match_program() {
+    let position_nft_mint = Pubkey::new_unique();
+    let accounts = accounts::CreatePositionCtx {
+        owner: Pubkey::new_unique(),
+        position_nft_mint,
+        position_nft_account: pda::derive_position_nft_account(&position_nft_mint),
+        pool: Pubkey::new_unique(),
+        position: pda::derive_position(&position_nft_mint),
+        pool_authority: pda::derive_pool_authority(),
+        payer: Pubkey::new_unique(),
+        token_program: Pubkey::new_unique(),
+        system_program: Pubkey::new_unique(),
+        lp_allowlist_entry: Some(Pubkey::new_unique()),
+        event_authority: pda::derive_event_authority(),
+        program: crate::ID,
+    };
+    let program_accounts = cp_amm::accounts::CreatePositionCtx {
+        owner: accounts.owner,
+        position_nft_mint: accounts.position_nft_mint,
+        position_nft_account: accounts.position_nft_account,
+        pool: accounts.pool,
+        position: accounts.position,
+        pool_authority: accounts.pool_authority,
+        payer: accounts.payer,
+        token_program: accounts.token_program,
+        system_program: accounts.system_program,
+        lp_allowlist_entry: accounts.lp_allowlist_entry,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+    assert_eq!(
+        accounts.to_account_metas(None),
+        program_accounts.to_account_metas(None)
+    );
+}
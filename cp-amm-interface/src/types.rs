@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SwapParameters {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct AddLiquidityParameters {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// maximum token a amount
+    pub token_a_amount_threshold: u64,
+    /// maximum token b amount
+    pub token_b_amount_threshold: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct AddLiquidityByAmountsParameters {
+    /// maximum token a amount, including transfer fee
+    pub token_a_amount: u64,
+    /// maximum token b amount, including transfer fee
+    pub token_b_amount: u64,
+    /// minimum delta liquidity
+    pub liquidity_delta_threshold: u128,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RemoveLiquidityParameters {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// minimum token a amount, excluding transfer fee
+    pub token_a_amount_threshold: u64,
+    /// minimum token b amount, excluding transfer fee
+    pub token_b_amount_threshold: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RemoveLiquiditySingleSideParameters {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// direction of the swap of withdrawn token, AtoB to receive only token b, BtoA to receive only token a
+    pub trade_direction: u8,
+    /// minimum output token amount, excluding transfer fee
+    pub minimum_amount_out: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SwapResult {
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub partner_fee: u64,
+    pub referral_fee: u64,
+}
@@ -0,0 +1,555 @@
+use std::mem::size_of;
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::program_pack::Pack, Discriminator, ToAccountMetas,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensions,
+        StateWithExtensions,
+    },
+    state::Mint,
+};
+use anyhow::{ensure, Context, Result};
+use cp_amm::{
+    activation_handler::ActivationType,
+    constants::{fee::FEE_DENOMINATOR, seeds::POOL_AUTHORITY_PREFIX},
+    params::swap::TradeDirection,
+    state::{fee::FeeMode, Pool, PoolOperation, POOL_VERSION},
+    token::{TokenProgramFlags, TOKEN_PROGRAM_FLAG_MASK},
+};
+use jupiter_amm_interface::{
+    try_get_account_data, AccountMap, Amm, AmmContext, ClockRef, KeyedAccount, Quote, QuoteParams,
+    Swap, SwapAndAccountMetas, SwapMode, SwapParams,
+};
+use rust_decimal::Decimal;
+use rust_sdk::quote::get_quote;
+
+/// Number of accounts in `SwapCtx`, including the event cpi accounts
+const SWAP_ACCOUNTS_LEN: usize = 14;
+
+/// Transfer fee and transfer hook of a Token-2022 mint, cached at `update`
+#[derive(Clone, Default)]
+struct MintTransferFee {
+    transfer_fee_config: Option<TransferFeeConfig>,
+    /// extra accounts of the hook are not passed by the adapter, so swaps of the mint always fail
+    has_transfer_hook: bool,
+}
+
+impl MintTransferFee {
+    fn from_mint_data(data: &[u8]) -> Result<Self> {
+        // spl token mints have no extension
+        if data.len() <= Mint::LEN {
+            return Ok(Self::default());
+        }
+        let mint = StateWithExtensions::<Mint>::unpack(data)?;
+        let has_transfer_hook = mint
+            .get_extension::<TransferHook>()
+            .is_ok_and(|transfer_hook| Option::<Pubkey>::from(transfer_hook.program_id).is_some());
+        Ok(Self {
+            transfer_fee_config: mint.get_extension::<TransferFeeConfig>().ok().copied(),
+            has_transfer_hook,
+        })
+    }
+
+    fn get_transfer_fee_excluded_amount(&self, amount: u64, epoch: u64) -> Result<u64> {
+        match &self.transfer_fee_config {
+            Some(transfer_fee_config) => {
+                let transfer_fee = transfer_fee_config
+                    .calculate_epoch_fee(epoch, amount)
+                    .context("transfer fee overflow")?;
+                amount
+                    .checked_sub(transfer_fee)
+                    .context("transfer fee overflow")
+            }
+            None => Ok(amount),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CpAmmJupiter {
+    key: Pubkey,
+    pool: Pool,
+    pool_authority: Pubkey,
+    event_authority: Pubkey,
+    token_a_transfer_fee: MintTransferFee,
+    token_b_transfer_fee: MintTransferFee,
+    clock_ref: ClockRef,
+}
+
+/// Pools created before the latest layout version have a shorter account, missing fields are decoded as zero
+/// and the pool is inactive until migrated
+pub fn decode_pool(data: &[u8]) -> Result<Pool> {
+    let discriminator_len = Pool::DISCRIMINATOR.len();
+    ensure!(data.len() >= discriminator_len, "invalid pool account size");
+    ensure!(
+        &data[..discriminator_len] == Pool::DISCRIMINATOR,
+        "invalid pool discriminator"
+    );
+    let pool_data = &data[discriminator_len..];
+    let len = pool_data.len().min(size_of::<Pool>());
+    let mut pool = Pool::default();
+    bytemuck::bytes_of_mut(&mut pool)[..len].copy_from_slice(&pool_data[..len]);
+    Ok(pool)
+}
+
+fn get_token_program(token_flag: u8) -> Result<Pubkey> {
+    let token_flag = TokenProgramFlags::try_from(token_flag & TOKEN_PROGRAM_FLAG_MASK)
+        .context("invalid token program flag")?;
+    let token_program = match token_flag {
+        TokenProgramFlags::TokenProgram => anchor_spl::token::ID,
+        TokenProgramFlags::TokenProgram2022 => anchor_spl::token_2022::ID,
+    };
+    Ok(token_program)
+}
+
+impl CpAmmJupiter {
+    fn get_current_point(&self) -> Result<u64> {
+        let activation_type = ActivationType::try_from(self.pool.activation_type)
+            .context("invalid activation type")?;
+        let current_point = match activation_type {
+            ActivationType::Slot => self.current_slot(),
+            ActivationType::Timestamp => self.current_timestamp(),
+        };
+        Ok(current_point)
+    }
+
+    fn current_slot(&self) -> u64 {
+        self.clock_ref
+            .slot
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn current_timestamp(&self) -> u64 {
+        self.clock_ref
+            .unix_timestamp
+            .load(std::sync::atomic::Ordering::Relaxed) as u64
+    }
+
+    fn current_epoch(&self) -> u64 {
+        self.clock_ref
+            .epoch
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Swaps in the cpi guard window need the instructions sysvar, and buys in the launch guard window need
+    /// a launch buy tracker of the payer, neither of which the adapter passes
+    fn is_swap_guard_active(&self) -> Result<bool> {
+        let current_point = self.get_current_point()?;
+        Ok(self.pool.is_cpi_guard_active(current_point)?
+            || self
+                .pool
+                .launch_guard
+                .is_active(self.pool.activation_point, current_point)?)
+    }
+}
+
+impl Amm for CpAmmJupiter {
+    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self> {
+        let pool = decode_pool(&keyed_account.account.data)?;
+        let (pool_authority, _) =
+            Pubkey::find_program_address(&[POOL_AUTHORITY_PREFIX], &cp_amm::ID);
+        let (event_authority, _) =
+            Pubkey::find_program_address(&[b"__event_authority"], &cp_amm::ID);
+
+        Ok(Self {
+            key: keyed_account.key,
+            pool,
+            pool_authority,
+            event_authority,
+            token_a_transfer_fee: MintTransferFee::default(),
+            token_b_transfer_fee: MintTransferFee::default(),
+            clock_ref: amm_context.clock_ref.clone(),
+        })
+    }
+
+    fn label(&self) -> String {
+        "Meteora DAMM v2".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        cp_amm::ID
+    }
+
+    fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.pool.token_a_mint, self.pool.token_b_mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        vec![self.key, self.pool.token_a_mint, self.pool.token_b_mint]
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        self.pool = decode_pool(try_get_account_data(account_map, &self.key)?)?;
+        self.token_a_transfer_fee = MintTransferFee::from_mint_data(try_get_account_data(
+            account_map,
+            &self.pool.token_a_mint,
+        )?)?;
+        self.token_b_transfer_fee = MintTransferFee::from_mint_data(try_get_account_data(
+            account_map,
+            &self.pool.token_b_mint,
+        )?)?;
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        ensure!(
+            quote_params.swap_mode == SwapMode::ExactIn,
+            "exact out is not supported"
+        );
+        ensure!(self.is_active(), "pool is disabled");
+
+        let current_point = self.get_current_point()?;
+        ensure!(
+            current_point >= self.pool.activation_point,
+            "pool is not activated"
+        );
+
+        let a_to_b = quote_params.input_mint == self.pool.token_a_mint;
+        let (trade_direction, input_transfer_fee, output_transfer_fee) = if a_to_b {
+            (
+                TradeDirection::AtoB,
+                &self.token_a_transfer_fee,
+                &self.token_b_transfer_fee,
+            )
+        } else {
+            (
+                TradeDirection::BtoA,
+                &self.token_b_transfer_fee,
+                &self.token_a_transfer_fee,
+            )
+        };
+
+        let epoch = self.current_epoch();
+        let actual_amount_in =
+            input_transfer_fee.get_transfer_fee_excluded_amount(quote_params.amount, epoch)?;
+
+        let swap_result = get_quote(
+            &self.pool,
+            self.current_timestamp(),
+            self.current_slot(),
+            actual_amount_in,
+            a_to_b,
+            false,
+        )?;
+
+        let out_amount = output_transfer_fee
+            .get_transfer_fee_excluded_amount(swap_result.output_amount, epoch)?;
+
+        let fee_mode = FeeMode::get_fee_mode(self.pool.collect_fee_mode, trade_direction, false)?;
+        let fee_mint = if fee_mode.fees_on_token_a {
+            self.pool.token_a_mint
+        } else {
+            self.pool.token_b_mint
+        };
+        let fee_amount = swap_result
+            .lp_fee
+            .saturating_add(swap_result.protocol_fee)
+            .saturating_add(swap_result.partner_fee)
+            .saturating_add(swap_result.referral_fee);
+
+        let mut pool = self.pool;
+        pool.update_pre_swap(self.current_timestamp())?;
+        let trade_fee_numerator = pool
+            .pool_fees
+            .get_total_trading_fee(current_point, pool.activation_point)?;
+        let fee_pct = Decimal::from(trade_fee_numerator) / Decimal::from(FEE_DENOMINATOR);
+
+        Ok(Quote {
+            in_amount: quote_params.amount,
+            out_amount,
+            fee_amount,
+            fee_mint,
+            fee_pct,
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        let account_metas = cp_amm::accounts::SwapCtx {
+            pool_authority: self.pool_authority,
+            pool: self.key,
+            input_token_account: swap_params.source_token_account,
+            output_token_account: swap_params.destination_token_account,
+            token_a_vault: self.pool.token_a_vault,
+            token_b_vault: self.pool.token_b_vault,
+            token_a_mint: self.pool.token_a_mint,
+            token_b_mint: self.pool.token_b_mint,
+            payer: swap_params.token_transfer_authority,
+            token_a_program: get_token_program(self.pool.token_a_flag)?,
+            token_b_program: get_token_program(self.pool.token_b_flag)?,
+            referral_token_account: None,
+            event_authority: self.event_authority,
+            program: cp_amm::ID,
+        }
+        .to_account_metas(None);
+
+        Ok(SwapAndAccountMetas {
+            swap: Swap::MeteoraDammV2,
+            account_metas,
+        })
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn get_accounts_len(&self) -> usize {
+        SWAP_ACCOUNTS_LEN
+    }
+
+    fn is_active(&self) -> bool {
+        // program can't load pools created before the latest layout version until they are migrated
+        self.pool.version >= POOL_VERSION
+            && !self.token_a_transfer_fee.has_transfer_hook
+            && !self.token_b_transfer_fee.has_transfer_hook
+            && self.pool.is_operation_enabled(PoolOperation::Swap)
+            && !self.pool.is_emergency_mode()
+            && !self.pool.is_permissioned_swap()
+            && !self.is_swap_guard_active().unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::clock::Clock;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+    };
+    use cp_amm::{
+        constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+        state::fee::{BaseFeeStruct, PoolFeesStruct},
+    };
+    use jupiter_amm_interface::{AccountMap, KeyedAccount};
+    use solana_sdk::account::Account;
+
+    fn pool_account_data(pool: &Pool) -> Vec<u8> {
+        let mut data = Pool::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(pool));
+        data
+    }
+
+    fn new_pool() -> Pool {
+        Pool {
+            pool_fees: PoolFeesStruct {
+                base_fee: BaseFeeStruct {
+                    cliff_fee_numerator: 2_500_000,
+                    ..Default::default()
+                },
+                protocol_fee_percent: 20,
+                ..Default::default()
+            },
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            liquidity: 1u128 << 100,
+            sqrt_price: 1u128 << 64,
+            sqrt_min_price: MIN_SQRT_PRICE,
+            sqrt_max_price: MAX_SQRT_PRICE,
+            version: POOL_VERSION,
+            ..Default::default()
+        }
+    }
+
+    fn spl_mint_account() -> Account {
+        Account {
+            data: vec![0; Mint::LEN],
+            owner: anchor_spl::token::ID,
+            ..Default::default()
+        }
+    }
+
+    fn transfer_hook_mint_account() -> Account {
+        let account_len =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferHook])
+                .unwrap();
+        let mut data = vec![0; account_len];
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let transfer_hook = mint.init_extension::<TransferHook>(true).unwrap();
+        transfer_hook.program_id = Some(Pubkey::new_unique()).try_into().unwrap();
+        mint.base.is_initialized = true;
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        Account {
+            data,
+            owner: anchor_spl::token_2022::ID,
+            ..Default::default()
+        }
+    }
+
+    fn new_amm(pool: &Pool) -> CpAmmJupiter {
+        new_amm_with_accounts(
+            pool_account_data(pool),
+            pool,
+            spl_mint_account(),
+            spl_mint_account(),
+        )
+    }
+
+    fn new_amm_with_accounts(
+        pool_data: Vec<u8>,
+        pool: &Pool,
+        token_a_mint_account: Account,
+        token_b_mint_account: Account,
+    ) -> CpAmmJupiter {
+        let key = Pubkey::new_unique();
+        let keyed_account = KeyedAccount {
+            key,
+            account: Account {
+                data: pool_data,
+                owner: cp_amm::ID,
+                ..Default::default()
+            },
+            params: None,
+        };
+        let amm_context = AmmContext {
+            clock_ref: ClockRef::from(Clock::default()),
+        };
+        let mut amm = CpAmmJupiter::from_keyed_account(&keyed_account, &amm_context).unwrap();
+
+        let mut account_map = AccountMap::default();
+        account_map.insert(key, keyed_account.account.clone());
+        account_map.insert(pool.token_a_mint, token_a_mint_account);
+        account_map.insert(pool.token_b_mint, token_b_mint_account);
+        amm.update(&account_map).unwrap();
+        amm
+    }
+
+    #[test]
+    fn test_quote_matches_sdk_quote() {
+        let pool = new_pool();
+        let amm = new_amm(&pool);
+
+        let amount = 1_000_000;
+        let quote = amm
+            .quote(&QuoteParams {
+                amount,
+                input_mint: pool.token_a_mint,
+                output_mint: pool.token_b_mint,
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap();
+        let swap_result = get_quote(&pool, 0, 0, amount, true, false).unwrap();
+
+        assert_eq!(quote.out_amount, swap_result.output_amount);
+        assert_eq!(quote.fee_mint, pool.token_b_mint);
+        assert_eq!(
+            quote.fee_amount,
+            swap_result.lp_fee + swap_result.protocol_fee + swap_result.partner_fee
+        );
+    }
+
+    #[test]
+    fn test_quote_applies_pending_base_fee() {
+        let mut pool = new_pool();
+        pool.pending_base_fee.trade_fee_numerator = 10_000_000;
+        pool.pending_base_fee.activation_point = 100;
+        let mut amm = new_amm(&pool);
+        amm.clock_ref = ClockRef::from(Clock {
+            unix_timestamp: 100,
+            ..Default::default()
+        });
+
+        let amount = 1_000_000;
+        let quote = amm
+            .quote(&QuoteParams {
+                amount,
+                input_mint: pool.token_a_mint,
+                output_mint: pool.token_b_mint,
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap();
+
+        // quote with the override, even though dynamic fee is disabled
+        let mut overridden_pool = new_pool();
+        overridden_pool.pool_fees.base_fee.cliff_fee_numerator = 10_000_000;
+        let swap_result = get_quote(&overridden_pool, 100, 0, amount, true, false).unwrap();
+        assert_eq!(quote.out_amount, swap_result.output_amount);
+        assert_eq!(quote.fee_pct, Decimal::new(1, 2));
+
+        // override is not active yet
+        let swap_result = get_quote(&pool, 99, 0, amount, true, false).unwrap();
+        assert!(quote.out_amount < swap_result.output_amount);
+    }
+
+    #[test]
+    fn test_accounts_len_matches_swap_account_metas() {
+        let pool = new_pool();
+        let amm = new_amm(&pool);
+
+        let swap_and_account_metas = amm
+            .get_swap_and_account_metas(&SwapParams {
+                swap_mode: SwapMode::ExactIn,
+                in_amount: 1_000_000,
+                out_amount: 0,
+                source_mint: pool.token_a_mint,
+                destination_mint: pool.token_b_mint,
+                source_token_account: Pubkey::new_unique(),
+                destination_token_account: Pubkey::new_unique(),
+                token_transfer_authority: Pubkey::new_unique(),
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &Pubkey::new_unique(),
+                missing_dynamic_accounts_as_default: false,
+            })
+            .unwrap();
+
+        assert_eq!(
+            swap_and_account_metas.account_metas.len(),
+            amm.get_accounts_len()
+        );
+    }
+
+    #[test]
+    fn test_inactive_in_guard_windows() {
+        let amm = new_amm(&new_pool());
+        assert!(amm.is_active());
+
+        let mut pool = new_pool();
+        pool.cpi_guard_duration = 10;
+        let amm = new_amm(&pool);
+        assert!(!amm.is_active());
+
+        let mut pool = new_pool();
+        pool.launch_guard.duration = 10;
+        pool.launch_guard.max_buy_amount_per_wallet = 1_000;
+        let amm = new_amm(&pool);
+        assert!(!amm.is_active());
+
+        // both windows are over
+        let mut pool = new_pool();
+        pool.activation_point = 0;
+        pool.cpi_guard_duration = 10;
+        pool.launch_guard.duration = 10;
+        let mut amm = new_amm(&pool);
+        amm.clock_ref = ClockRef::from(Clock {
+            slot: 10,
+            ..Default::default()
+        });
+        assert!(amm.is_active());
+    }
+
+    #[test]
+    fn test_inactive_for_unexecutable_pools() {
+        // pool created before the latest layout version, account is shorter and not migrated
+        let mut pool = new_pool();
+        pool.version = 0;
+        let mut legacy_data = pool_account_data(&pool);
+        legacy_data.truncate(legacy_data.len() - 400);
+        let amm = new_amm_with_accounts(legacy_data, &pool, spl_mint_account(), spl_mint_account());
+        assert!(!amm.is_active());
+
+        // adapter doesn't pass extra accounts of transfer hook
+        let pool = new_pool();
+        let amm = new_amm_with_accounts(
+            pool_account_data(&pool),
+            &pool,
+            spl_mint_account(),
+            transfer_hook_mint_account(),
+        );
+        assert!(!amm.is_active());
+    }
+}
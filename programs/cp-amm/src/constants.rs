@@ -14,6 +14,10 @@ pub const TOTAL_REWARD_SCALE: u8 = 192;
 
 pub const ONE_Q64: u128 = 1u128 << 64;
 
+/// Liquidity permanently locked in the creator position at pool initialization,
+/// so liquidity of a pool never drops to zero and price can't float without liquidity
+pub const MINIMUM_LIQUIDITY: u128 = 1u128 << 32;
+
 pub const BIN_STEP_BPS_DEFAULT: u16 = 1;
 
 //  bin_step << 64 / BASIS_POINT_MAX
@@ -23,6 +27,12 @@ static_assertions::const_assert_eq!(LIQUIDITY_SCALE + REWARD_RATE_SCALE, TOTAL_R
 
 pub const BASIS_POINT_MAX: u64 = 10_000;
 
+// Number of roles in program authority account
+pub const NUM_AUTHORITY_ROLES: usize = 4;
+
+// Max delay in seconds before a proposed role key can accept the role
+pub const MAX_AUTHORITY_TRANSFER_DELAY: u64 = 30 * 24 * 60 * 60; // 30 days
+
 pub const U24_MAX: u32 = 0xffffff;
 
 // Number of bits to scale. This will decide the position of the radix point.
@@ -30,11 +40,49 @@ pub const U24_MAX: u32 = 0xffffff;
 // Number of rewards supported by pool
 pub const NUM_REWARDS: usize = 2;
 
+// Number of additional rewards supported by pool reward extension
+pub const NUM_EXTENSION_REWARDS: usize = 3;
+
 // Minimum reward duration
 pub const MIN_REWARD_DURATION: u64 = 1;
 
 pub const MAX_REWARD_DURATION: u64 = 31536000; // 1 year = 365 * 24 * 3600
 
+// Maximum duration claimed rewards are vested over, for rewards with claim vesting
+pub const MAX_REWARD_CLAIM_VESTING_DURATION: u32 = 31536000; // 1 year
+
+// Maximum extra reward weight of vested and permanently locked liquidity, 10_000 bps doubles reward accrual
+pub const MAX_REWARD_BOOST_BPS: u16 = 10_000;
+
+// Trade mining reward per unit of trade fee is a Q64.64 number
+pub const TRADE_MINING_RATE_SCALE: u8 = 64;
+
+// Fee in lamports paid to treasury when a non-admin initializes a reward
+pub const INITIALIZE_REWARD_FEE: u64 = 1_000_000_000; // 1 SOL
+
+// Maximum number of emission phases of a reward with decaying emission, bound the compute of reward update
+pub const MAX_REWARD_EMISSION_PHASES: u64 = 52;
+
+// Compute units reserved for refreshing a vesting account, vesting refresh stops when less compute units remain
+pub const MIN_COMPUTE_UNITS_PER_VESTING_REFRESH: u64 = 15_000;
+
+// Max number of vaults in a vault whitelist, besides the whitelisted vault of pool
+pub const MAX_WHITELISTED_VAULTS: usize = 4;
+
+// Max length in bytes of fields of pool metadata
+pub const MAX_POOL_METADATA_NAME_LENGTH: usize = 32;
+pub const MAX_POOL_METADATA_SYMBOL_LENGTH: usize = 16;
+pub const MAX_POOL_METADATA_URI_LENGTH: usize = 200;
+
+// Number and duration in seconds of time buckets in pool stats, hourly buckets cover 1 day and daily buckets 30 days
+pub const POOL_STATS_HOURLY_BUCKETS: usize = 24;
+pub const POOL_STATS_DAILY_BUCKETS: usize = 30;
+pub const POOL_STATS_HOUR: u64 = 3600;
+pub const POOL_STATS_DAY: u64 = 86400;
+
+// Duration in seconds of the rolling lp fee window in pool volume metrics
+pub const ROLLING_FEE_WINDOW: u64 = 86400;
+
 pub mod activation {
     #[cfg(not(feature = "local"))]
     pub const SLOT_BUFFER: u64 = 9000; // 1 slot = 400 mls => 1 hour
@@ -68,6 +116,15 @@ pub mod activation {
 
     pub const MAX_HIGH_TAX_TIME_DURATION: u64 = TIME_BUFFER / 6; // 10 minutes
     pub const MAX_HIGH_TAX_SLOT_DURATION: u64 = SLOT_BUFFER / 6; // 10 minutes
+
+    // delay in seconds before a queued dynamic fee update takes effect
+    pub const DYNAMIC_FEE_UPDATE_DELAY: u64 = TIME_BUFFER; // 1 hour
+
+    // delay in seconds before a queued base fee override takes effect
+    pub const BASE_FEE_OVERRIDE_DELAY: u64 = TIME_BUFFER; // 1 hour
+
+    // max duration after activation point that swaps must be top-level instructions, in slots or seconds by activation type
+    pub const MAX_CPI_GUARD_DURATION: u64 = 9000 * 24; // 1 day in slots
 }
 
 /// Store constants related to fees
@@ -101,6 +158,12 @@ pub mod fee {
     pub const CUSTOMIZABLE_HOST_FEE_PERCENT: u8 = 20; // 20%
 
     pub const MEME_MIN_FEE_UPDATE_WINDOW_DURATION: i64 = 60 * 30; // 30 minutes
+
+    /// Max number of tiers in a fee discount registry
+    pub const MAX_FEE_DISCOUNT_TIERS: usize = 4;
+
+    /// Max trade fee discount of a tier
+    pub const MAX_FEE_DISCOUNT_BPS: u16 = 5000; // 50%
 }
 
 pub mod seeds {
@@ -114,6 +177,27 @@ pub mod seeds {
     pub const TOKEN_BADGE_PREFIX: &[u8] = b"token_badge";
     pub const REWARD_VAULT_PREFIX: &[u8] = b"reward_vault";
     pub const CLAIM_FEE_OPERATOR_PREFIX: &[u8] = b"cf_operator";
+    pub const REWARD_EXTENSION_PREFIX: &[u8] = b"reward_extension";
+    pub const POSITION_REWARD_EXTENSION_PREFIX: &[u8] = b"position_reward_extension";
+    pub const FEE_RECEIPT_MINT_PREFIX: &[u8] = b"fee_receipt_mint";
+    pub const FEE_RECEIPT_ACCOUNT_PREFIX: &[u8] = b"fee_receipt_account";
+    pub const PARTNER_FEE_RECEIVER_PREFIX: &[u8] = b"partner_fee_receiver";
+    pub const REFERRAL_PREFIX: &[u8] = b"referral";
+    pub const FEE_DISCOUNT_REGISTRY_PREFIX: &[u8] = b"fee_discount_registry";
+    pub const PROGRAM_AUTHORITY_PREFIX: &[u8] = b"program_authority";
+    pub const VAULT_WHITELIST_PREFIX: &[u8] = b"vault_whitelist";
+    pub const SWAP_ALLOWLIST_PREFIX: &[u8] = b"swap_allowlist";
+    pub const LP_ALLOWLIST_PREFIX: &[u8] = b"lp_allowlist";
+    pub const LAUNCH_BUY_TRACKER_PREFIX: &[u8] = b"launch_buy_tracker";
+    pub const POOL_METADATA_PREFIX: &[u8] = b"pool_metadata";
+    pub const POOL_STATS_PREFIX: &[u8] = b"pool_stats";
+    pub const REWARD_VESTING_ESCROW_PREFIX: &[u8] = b"reward_vesting_escrow";
+    pub const TRADE_MINING_PREFIX: &[u8] = b"trade_mining";
+    pub const TRADE_MINING_VAULT_PREFIX: &[u8] = b"trade_mining_vault";
+    pub const TRADER_REWARD_PREFIX: &[u8] = b"trader_reward";
+    pub const INSURANCE_FUND_PREFIX: &[u8] = b"insurance_fund";
+    pub const INSURANCE_VAULT_PREFIX: &[u8] = b"insurance_vault";
+    pub const BUYBACK_BURN_PREFIX: &[u8] = b"buyback_burn";
 }
 
 pub mod treasury {
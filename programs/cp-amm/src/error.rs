@@ -130,4 +130,191 @@ pub enum PoolError {
 
     #[msg("Invalid config type")]
     InvalidConfigType,
+
+    #[msg("Invalid reward extension")]
+    InvalidRewardExtension,
+
+    #[msg("Invalid reward emission schedule")]
+    InvalidRewardEmissionSchedule,
+
+    #[msg("Identical fee beneficiary")]
+    IdenticalFeeBeneficiary,
+
+    #[msg("Invalid fee beneficiary")]
+    InvalidFeeBeneficiary,
+
+    #[msg("Identical position")]
+    IdenticalPosition,
+
+    #[msg("Unable to merge position with vested liquidity")]
+    UnableToMergeVestedPosition,
+
+    #[msg("Identical position operator")]
+    IdenticalPositionOperator,
+
+    #[msg("Signer is neither owner nor operator of position")]
+    InvalidPositionOperator,
+
+    #[msg("Invalid reward receiver")]
+    InvalidRewardReceiver,
+
+    #[msg("Position is not fully permanently locked")]
+    PositionNotPermanentlyLocked,
+
+    #[msg("Position fee is claimable by fee receipt holder only")]
+    PositionHasFeeReceipt,
+
+    #[msg("Dynamic fee is not initialized")]
+    DynamicFeeNotInitialized,
+
+    #[msg("Invalid authority to update dynamic fee")]
+    InvalidDynamicFeeAuthority,
+
+    #[msg("Pool fee override is disabled for the config")]
+    PoolFeeOverrideDisabled,
+
+    #[msg("Invalid partner fee receiver")]
+    InvalidPartnerFeeReceiver,
+
+    #[msg("Signer is neither partner nor operator of partner fee receiver")]
+    InvalidPartnerFeeOperator,
+
+    #[msg("Invalid fee discount tiers")]
+    InvalidFeeDiscountTiers,
+
+    #[msg("Invalid fee discount token account")]
+    InvalidFeeDiscountAccount,
+
+    #[msg("Invalid authority to set pool status")]
+    InvalidPoolStatusAuthority,
+
+    #[msg("Pool is not empty")]
+    PoolIsNotEmpty,
+
+    #[msg("Invalid authority role")]
+    InvalidAuthorityRole,
+
+    #[msg("Authority role transfer is still locked")]
+    AuthorityTransferLocked,
+
+    #[msg("No pending update")]
+    NoPendingUpdate,
+
+    #[msg("Pool vault is frozen")]
+    VaultFrozen,
+
+    #[msg("Invalid vault whitelist")]
+    InvalidVaultWhitelist,
+
+    #[msg("Invalid authority to update vault whitelist")]
+    InvalidVaultWhitelistAuthority,
+
+    #[msg("Invalid authority to update swap allowlist")]
+    InvalidSwapAllowlistAuthority,
+
+    #[msg("Swap rate limit exceeded")]
+    SwapRateLimitExceeded,
+
+    #[msg("Invalid swap rate limiter parameters")]
+    InvalidSwapRateLimiter,
+
+    #[msg("Max buy amount per wallet exceeded")]
+    MaxBuyAmountExceeded,
+
+    #[msg("Invalid launch buy tracker")]
+    InvalidLaunchBuyTracker,
+
+    #[msg("Invalid launch guard parameters")]
+    InvalidLaunchGuard,
+
+    #[msg("Invalid authority to update activation point")]
+    InvalidActivationPointAuthority,
+
+    #[msg("Oracle is not initialized")]
+    OracleNotInitialized,
+
+    #[msg("Pool is already migrated to the latest version")]
+    PoolAlreadyMigrated,
+
+    #[msg("Invalid pool metadata")]
+    InvalidPoolMetadata,
+
+    #[msg("Invalid authority to update pool metadata")]
+    InvalidPoolMetadataAuthority,
+
+    #[msg("Invalid pool stats")]
+    InvalidPoolStats,
+
+    #[msg("Vault balance change does not match swap result")]
+    VaultBalanceMismatch,
+
+    #[msg("Pool does not track fees owed since initialization")]
+    PoolFeeNotTracked,
+
+    #[msg("Price change within the slot exceeds the circuit breaker limit")]
+    PriceCircuitBreakerTriggered,
+
+    #[msg("Invalid price circuit breaker parameters")]
+    InvalidPriceCircuitBreaker,
+
+    #[msg("Invalid cpi guard duration")]
+    InvalidCpiGuardDuration,
+
+    #[msg("Swap must be a top-level instruction while cpi guard of pool is active")]
+    SwapCpiGuardActive,
+
+    #[msg("Invalid reward claim vesting duration")]
+    InvalidRewardClaimVestingDuration,
+
+    #[msg("Invalid reward vesting escrow")]
+    InvalidRewardVestingEscrow,
+
+    #[msg("Invalid reward boost")]
+    InvalidRewardBoost,
+
+    #[msg("Invalid trade mining authority")]
+    InvalidTradeMiningAuthority,
+
+    #[msg("Invalid trader reward")]
+    InvalidTraderReward,
+
+    #[msg("Invalid insurance fund")]
+    InvalidInsuranceFund,
+
+    #[msg("Buyback is not ready")]
+    BuybackNotReady,
+
+    #[msg("Invalid authority to update lp allowlist")]
+    InvalidLpAllowlistAuthority,
+
+    #[msg("Buyback and burn and insurance fund can't both take protocol fee of a pool")]
+    ConflictingProtocolFeeRouting,
+
+    #[msg("Unsupported pausable mint")]
+    UnsupportedPausableMint,
+
+    #[msg("Invalid referral")]
+    InvalidReferral,
+
+    #[msg("Invalid swap allowlist entry")]
+    InvalidSwapAllowlistEntry,
+
+    #[msg("Transfer hook program is not approved by token badge")]
+    UnapprovedTransferHookProgram,
+
+    #[msg("Invalid position nft")]
+    InvalidPositionNft,
+}
+
+impl From<cp_amm_core::MathError> for PoolError {
+    fn from(error: cp_amm_core::MathError) -> Self {
+        match error {
+            cp_amm_core::MathError::MathOverflow => PoolError::MathOverflow,
+            cp_amm_core::MathError::TypeCastFailed => PoolError::TypeCastFailed,
+            cp_amm_core::MathError::FeeInverseIsIncorrect => PoolError::FeeInverseIsIncorrect,
+            cp_amm_core::MathError::PriceRangeViolation => PoolError::PriceRangeViolation,
+            cp_amm_core::MathError::InvalidPriceRange => PoolError::InvalidPriceRange,
+            cp_amm_core::MathError::InsufficientLiquidity => PoolError::InsufficientLiquidity,
+        }
+    }
 }
@@ -2,8 +2,9 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    params::fee_parameters::PoolFeeParameters, state::SwapResult, AddLiquidityParameters,
-    RemoveLiquidityParameters, SwapParameters,
+    constants::NUM_AUTHORITY_ROLES, params::fee_parameters::PoolFeeParameters, state::SwapResult,
+    AddLiquidityParameters, FeeDiscountTierParameters, RemoveLiquidityParameters,
+    RemoveLiquiditySingleSideParameters, SwapParameters, UpdateDynamicFeeParameters,
 };
 
 /// Close config
@@ -29,6 +30,16 @@ pub struct EvtCreateConfig {
     pub config: Pubkey,
 }
 
+/// Update config
+#[event]
+pub struct EvtUpdateConfig {
+    pub config: Pubkey,
+    pub pool_fees: PoolFeeParameters,
+    pub vault_config_key: Pubkey,
+    pub pool_creator_authority: Pubkey,
+    pub activation_type: u8,
+}
+
 /// Create dynamic config
 #[event]
 pub struct EvtCreateDynamicConfig {
@@ -41,6 +52,15 @@ pub struct EvtCreateDynamicConfig {
 #[event]
 pub struct EvtCreateTokenBadge {
     pub token_mint: Pubkey,
+    pub policy: u8,
+    pub transfer_hook_program: Pubkey,
+}
+
+/// Close token badge
+#[event]
+pub struct EvtCloseTokenBadge {
+    pub token_mint: Pubkey,
+    pub admin: Pubkey,
 }
 
 /// Create claim fee operator
@@ -49,6 +69,30 @@ pub struct EvtCreateClaimFeeOperator {
     pub operator: Pubkey,
 }
 
+/// Initialize program authority
+#[event]
+pub struct EvtInitializeProgramAuthority {
+    pub roles: [Pubkey; NUM_AUTHORITY_ROLES],
+    pub transfer_delay: u64,
+}
+
+/// Propose a new key for a role of program authority
+#[event]
+pub struct EvtTransferAuthorityRole {
+    pub role: u8,
+    pub current_key: Pubkey,
+    pub pending_key: Pubkey,
+    pub activation_point: u64,
+}
+
+/// Accept a role of program authority
+#[event]
+pub struct EvtAcceptAuthorityRole {
+    pub role: u8,
+    pub old_key: Pubkey,
+    pub new_key: Pubkey,
+}
+
 /// Close claim fee operator
 #[event]
 pub struct EvtCloseClaimFeeOperator {
@@ -56,6 +100,33 @@ pub struct EvtCloseClaimFeeOperator {
     pub operator: Pubkey,
 }
 
+/// Update claim fee operator
+#[event]
+pub struct EvtUpdateClaimFeeOperator {
+    pub claim_fee_operator: Pubkey,
+    pub old_operator: Pubkey,
+    pub operator: Pubkey,
+    pub treasury: Pubkey,
+}
+
+/// Create fee discount registry
+#[event]
+pub struct EvtCreateFeeDiscountRegistry {
+    pub pool: Pubkey,
+    pub fee_discount_registry: Pubkey,
+    pub mint: Pubkey,
+    pub tiers: Vec<FeeDiscountTierParameters>,
+}
+
+/// Update fee discount registry
+#[event]
+pub struct EvtUpdateFeeDiscountRegistry {
+    pub pool: Pubkey,
+    pub fee_discount_registry: Pubkey,
+    pub mint: Pubkey,
+    pub tiers: Vec<FeeDiscountTierParameters>,
+}
+
 #[event]
 pub struct EvtInitializePool {
     pub pool: Pubkey,
@@ -91,6 +162,7 @@ pub struct EvtAddLiquidity {
     pub token_b_amount: u64,
     pub total_amount_a: u64,
     pub total_amount_b: u64,
+    pub sequence: u64,
 }
 
 #[event]
@@ -100,6 +172,34 @@ pub struct EvtClaimPositionFee {
     pub owner: Pubkey,
     pub fee_a_claimed: u64,
     pub fee_b_claimed: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct EvtSetPositionFeeBeneficiary {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub old_fee_beneficiary: Pubkey,
+    pub new_fee_beneficiary: Pubkey,
+}
+
+#[event]
+pub struct EvtSetPositionOperator {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+}
+
+#[event]
+pub struct EvtMintFeeReceipt {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub receiver: Pubkey,
+    pub fee_receipt_mint: Pubkey,
 }
 
 #[event]
@@ -118,6 +218,15 @@ pub struct EvtClosePosition {
     pub position_nft_mint: Pubkey,
 }
 
+#[event]
+pub struct EvtMergePositions {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub position: Pubkey,
+    pub source_position: Pubkey,
+    pub source_position_nft_mint: Pubkey,
+}
+
 #[event]
 pub struct EvtRemoveLiquidity {
     pub pool: Pubkey,
@@ -126,6 +235,21 @@ pub struct EvtRemoveLiquidity {
     pub params: RemoveLiquidityParameters,
     pub token_a_amount: u64,
     pub token_b_amount: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct EvtRemoveLiquiditySingleSide {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub params: RemoveLiquiditySingleSideParameters,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub swap_result: Option<SwapResult>,
+    pub amount_out: u64,
+    pub current_timestamp: u64,
+    pub sequence: u64,
 }
 
 #[event]
@@ -137,6 +261,24 @@ pub struct EvtSwap {
     pub swap_result: SwapResult,
     pub actual_amount_in: u64,
     pub current_timestamp: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct EvtCreateReferral {
+    pub pool: Pubkey,
+    pub referral: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct EvtClaimReferralFee {
+    pub pool: Pubkey,
+    pub referral: Pubkey,
+    pub referrer: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub sequence: u64,
 }
 
 #[event]
@@ -164,6 +306,11 @@ pub struct EvtClaimProtocolFee {
     pub pool: Pubkey,
     pub token_a_amount: u64,
     pub token_b_amount: u64,
+    pub settlement_mode: u8,
+    pub swap_result: Option<SwapResult>,
+    pub insurance_a_amount: u64,
+    pub insurance_b_amount: u64,
+    pub sequence: u64,
 }
 
 #[event]
@@ -171,14 +318,241 @@ pub struct EvtClaimPartnerFee {
     pub pool: Pubkey,
     pub token_a_amount: u64,
     pub token_b_amount: u64,
+    pub sequence: u64,
+}
+
+/// Create partner fee receiver
+#[event]
+pub struct EvtCreatePartnerFeeReceiver {
+    pub partner_fee_receiver: Pubkey,
+    pub partner: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub operator: Pubkey,
+}
+
+/// Update partner fee receiver
+#[event]
+pub struct EvtUpdatePartnerFeeReceiver {
+    pub partner_fee_receiver: Pubkey,
+    pub partner: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub operator: Pubkey,
+}
+
+/// Close partner fee receiver
+#[event]
+pub struct EvtClosePartnerFeeReceiver {
+    pub partner_fee_receiver: Pubkey,
+    pub partner: Pubkey,
+    pub mint: Pubkey,
 }
 
 #[event]
 pub struct EvtSetPoolStatus {
     pub pool: Pubkey,
+    pub signer: Pubkey,
     pub status: u8,
 }
 
+#[event]
+pub struct EvtClosePool {
+    pub pool: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub rent_receiver: Pubkey,
+}
+
+#[event]
+pub struct EvtSetPoolEmergencyMode {
+    pub pool: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct EvtMigratePool {
+    pub pool: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct EvtSetConfigFeeOverrideBounds {
+    pub config: Pubkey,
+    pub min_fee_numerator: u64,
+    pub max_fee_numerator: u64,
+}
+
+#[event]
+pub struct EvtSetConfigCpiGuard {
+    pub config: Pubkey,
+    pub cpi_guard_duration: u64,
+}
+
+#[event]
+pub struct EvtSetConfigRewardBoost {
+    pub config: Pubkey,
+    pub vesting_reward_boost_bps: u16,
+    pub permanent_lock_reward_boost_bps: u16,
+}
+
+#[event]
+pub struct EvtOverridePoolFee {
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    pub pool_creator_authority: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub activation_point: u64,
+}
+
+#[event]
+pub struct EvtTransferPoolCreatorAuthority {
+    pub config: Pubkey,
+    pub pool_creator_authority: Pubkey,
+    pub pending_pool_creator_authority: Pubkey,
+}
+
+#[event]
+pub struct EvtAcceptPoolCreatorAuthority {
+    pub config: Pubkey,
+    pub old_pool_creator_authority: Pubkey,
+    pub new_pool_creator_authority: Pubkey,
+}
+
+#[event]
+pub struct EvtUpdatePoolDynamicFee {
+    pub pool: Pubkey,
+    pub signer: Pubkey,
+    pub params: UpdateDynamicFeeParameters,
+    pub activation_point: u64,
+}
+
+// Cancel pending update
+#[event]
+pub struct EvtCancelPendingUpdate {
+    pub pool: Pubkey,
+    pub signer: Pubkey,
+}
+
+// Create vault whitelist
+#[event]
+pub struct EvtCreateVaultWhitelist {
+    pub pool: Pubkey,
+    pub vault_whitelist: Pubkey,
+    pub signer: Pubkey,
+    pub vaults: Vec<Pubkey>,
+}
+
+// Update vault whitelist
+#[event]
+pub struct EvtUpdateVaultWhitelist {
+    pub pool: Pubkey,
+    pub vault_whitelist: Pubkey,
+    pub signer: Pubkey,
+    pub vaults: Vec<Pubkey>,
+}
+
+// Create pool metadata
+#[event]
+pub struct EvtCreatePoolMetadata {
+    pub pool: Pubkey,
+    pub pool_metadata: Pubkey,
+    pub signer: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub project_url: String,
+}
+
+// Update pool metadata
+#[event]
+pub struct EvtUpdatePoolMetadata {
+    pub pool: Pubkey,
+    pub pool_metadata: Pubkey,
+    pub signer: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub project_url: String,
+}
+
+// Create pool stats
+#[event]
+pub struct EvtCreatePoolStats {
+    pub pool: Pubkey,
+    pub pool_stats: Pubkey,
+}
+
+// Skim vaults
+#[event]
+pub struct EvtSkimVaults {
+    pub pool: Pubkey,
+    pub signer: Pubkey,
+    pub excess_a_amount: u64,
+    pub excess_b_amount: u64,
+}
+
+// Donate to liquidity providers
+#[event]
+pub struct EvtDonate {
+    pub pool: Pubkey,
+    pub donor: Pubkey,
+    /// amount of token a received by vault
+    pub token_a_amount: u64,
+    /// amount of token b received by vault
+    pub token_b_amount: u64,
+}
+
+// Create swap allowlist entry
+#[event]
+pub struct EvtCreateSwapAllowlistEntry {
+    pub pool: Pubkey,
+    pub swap_allowlist_entry: Pubkey,
+    pub wallet: Pubkey,
+}
+
+// Close swap allowlist entry
+#[event]
+pub struct EvtCloseSwapAllowlistEntry {
+    pub pool: Pubkey,
+    pub swap_allowlist_entry: Pubkey,
+    pub wallet: Pubkey,
+}
+
+// Create lp allowlist entry
+#[event]
+pub struct EvtCreateLpAllowlistEntry {
+    pub pool: Pubkey,
+    pub lp_allowlist_entry: Pubkey,
+    pub wallet: Pubkey,
+}
+
+// Close lp allowlist entry
+#[event]
+pub struct EvtCloseLpAllowlistEntry {
+    pub pool: Pubkey,
+    pub lp_allowlist_entry: Pubkey,
+    pub wallet: Pubkey,
+}
+
+// Create launch buy tracker
+#[event]
+pub struct EvtCreateLaunchBuyTracker {
+    pub pool: Pubkey,
+    pub launch_buy_tracker: Pubkey,
+    pub wallet: Pubkey,
+}
+
+// Update activation point
+#[event]
+pub struct EvtUpdateActivationPoint {
+    pub pool: Pubkey,
+    pub signer: Pubkey,
+    pub old_activation_point: u64,
+    pub new_activation_point: u64,
+}
+
 // Initialize reward
 #[event]
 pub struct EvtInitializeReward {
@@ -192,6 +566,10 @@ pub struct EvtInitializeReward {
     pub reward_index: u8,
     // Duration of the farm reward in seconds
     pub reward_duration: u64,
+    // Fee in lamports paid to treasury, zero if initialized by admin
+    pub creation_fee: u64,
+    // Duration claimed rewards are vested over in seconds, zero if rewards are claimed directly
+    pub claim_vesting_duration: u32,
 }
 
 #[event]
@@ -226,6 +604,48 @@ pub struct EvtClaimReward {
     pub total_reward: u64,
 }
 
+#[event]
+pub struct EvtCreateRewardVestingEscrow {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Reward vesting escrow address
+    pub reward_vesting_escrow: Pubkey,
+    // Owner receiving the vested rewards
+    pub owner: Pubkey,
+    // Index of the farm reward
+    pub reward_index: u8,
+}
+
+#[event]
+pub struct EvtLockReward {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Position address
+    pub position: Pubkey,
+    // Reward vesting escrow address
+    pub reward_vesting_escrow: Pubkey,
+    // Index of the farm reward
+    pub reward_index: u8,
+    // Amount of reward locked
+    pub amount: u64,
+    // Time all locked rewards of the escrow are released
+    pub end_time: u64,
+}
+
+#[event]
+pub struct EvtClaimVestedReward {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Reward vesting escrow address
+    pub reward_vesting_escrow: Pubkey,
+    // Owner of the escrow
+    pub owner: Pubkey,
+    // Index of the farm reward
+    pub reward_index: u8,
+    // Amount of released reward claimed
+    pub amount: u64,
+}
+
 #[event]
 pub struct EvtUpdateRewardDuration {
     // Liquidity pool
@@ -238,6 +658,18 @@ pub struct EvtUpdateRewardDuration {
     pub new_reward_duration: u64,
 }
 
+#[event]
+pub struct EvtUpdateRewardEmissionSchedule {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Index of the farm reward being updated
+    pub reward_index: u8,
+    // Duration of every emission phase in seconds
+    pub emission_period: u32,
+    // Decay of reward rate after every emission phase in basis point
+    pub emission_decay_bps: u16,
+}
+
 #[event]
 pub struct EvtUpdateRewardFunder {
     // Liquidity pool
@@ -259,3 +691,139 @@ pub struct EvtWithdrawIneligibleReward {
     // Amount of ineligible reward withdrawn
     pub amount: u64,
 }
+
+#[event]
+pub struct EvtUpdateRewards {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Timestamp the rewards are updated to
+    pub current_time: u64,
+}
+
+#[event]
+pub struct EvtInitializeRewardExtension {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Reward extension of the pool
+    pub reward_extension: Pubkey,
+}
+
+#[event]
+pub struct EvtCreatePositionRewardExtension {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Position address
+    pub position: Pubkey,
+    // Reward extension of the position
+    pub position_reward_extension: Pubkey,
+}
+
+#[event]
+pub struct EvtInitializeTradeMining {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Trade mining address
+    pub trade_mining: Pubkey,
+    // Reward mint
+    pub reward_mint: Pubkey,
+    // Funder of trade mining budget
+    pub funder: Pubkey,
+    // Reward per unit of trade fee paid in token a, Q64.64
+    pub reward_per_fee_a: u128,
+    // Reward per unit of trade fee paid in token b, Q64.64
+    pub reward_per_fee_b: u128,
+    // Timestamp trade mining ends at
+    pub end_time: u64,
+}
+
+#[event]
+pub struct EvtFundTradeMining {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Trade mining address
+    pub trade_mining: Pubkey,
+    // Funder
+    pub funder: Pubkey,
+    // Amount transferred by funder
+    pub amount: u64,
+    // Amount added to the budget, excluding transfer fee
+    pub transfer_fee_excluded_amount_in: u64,
+}
+
+#[event]
+pub struct EvtWithdrawUnaccruedTradeMining {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Trade mining address
+    pub trade_mining: Pubkey,
+    // Remaining budget withdrawn to funder
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtCreateTraderReward {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Trader reward address
+    pub trader_reward: Pubkey,
+    // Trader
+    pub trader: Pubkey,
+}
+
+#[event]
+pub struct EvtClaimTradeMiningReward {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Trader reward address
+    pub trader_reward: Pubkey,
+    // Trader
+    pub trader: Pubkey,
+    // Amount of reward claimed
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtInitializeInsuranceFund {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Insurance fund address
+    pub insurance_fund: Pubkey,
+    // Claim authority of insurance fund
+    pub authority: Pubkey,
+    // Share of claimed protocol fee routed to insurance fund, in percent
+    pub insurance_fee_percent: u8,
+}
+
+#[event]
+pub struct EvtClaimInsuranceFund {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Insurance fund address
+    pub insurance_fund: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[event]
+pub struct EvtInitializeBuybackBurn {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Buyback burn address
+    pub buyback_burn: Pubkey,
+    pub max_amount_per_buyback: u64,
+    pub min_buyback_interval: u64,
+}
+
+#[event]
+pub struct EvtBuybackAndBurn {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Buyback burn address
+    pub buyback_burn: Pubkey,
+    // Protocol fee in token b spent
+    pub spent_amount: u64,
+    // Token a bought and burnt
+    pub burnt_amount: u64,
+    pub swap_result: SwapResult,
+    pub current_timestamp: u64,
+}
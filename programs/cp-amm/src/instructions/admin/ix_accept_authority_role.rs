@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, ProgramAuthority},
+    EvtAcceptAuthorityRole, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthorityRoleCtx<'info> {
+    #[account(mut, seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// pending key of the role
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_accept_authority_role(ctx: Context<AcceptAuthorityRoleCtx>, role: u8) -> Result<()> {
+    let authority_role =
+        AuthorityRole::try_from(role).map_err(|_| PoolError::InvalidAuthorityRole)?;
+
+    let mut program_authority = ctx.accounts.program_authority.load_mut()?;
+    let old_key = program_authority.roles[usize::from(role)];
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    program_authority.accept_role(authority_role, ctx.accounts.signer.key(), current_timestamp)?;
+
+    emit_cpi!(EvtAcceptAuthorityRole {
+        role,
+        old_key,
+        new_key: ctx.accounts.signer.key(),
+    });
+
+    Ok(())
+}
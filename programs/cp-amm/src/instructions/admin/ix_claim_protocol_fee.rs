@@ -1,13 +1,43 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::{
-    constants::{seeds::POOL_AUTHORITY_PREFIX, treasury},
-    state::{ClaimFeeOperator, Pool},
-    token::transfer_from_pool,
-    EvtClaimProtocolFee,
+    activation_handler::ActivationHandler,
+    constants::seeds::POOL_AUTHORITY_PREFIX,
+    get_pool_access_validator,
+    math::safe_math::SafeMath,
+    params::swap::TradeDirection,
+    state::{fee::FeeMode, ClaimFeeOperator, InsuranceFund, Pool},
+    token::{
+        calculate_transfer_fee_excluded_amount_cached, require_vaults_not_frozen,
+        transfer_from_pool,
+    },
+    EvtClaimProtocolFee, PoolActionAccess, PoolError,
 };
 
+/// Token the treasury receives claimed protocol fee in
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum ProtocolFeeSettlementMode {
+    /// Claim token a and b as they are
+    None,
+    /// Swap claimed token b into token a through the pool
+    TokenA,
+    /// Swap claimed token a into token b through the pool
+    TokenB,
+}
+
+impl ProtocolFeeSettlementMode {
+    pub fn get_trade_direction(&self) -> Option<TradeDirection> {
+        match self {
+            Self::None => None,
+            Self::TokenA => Some(TradeDirection::BtoA),
+            Self::TokenB => Some(TradeDirection::AtoB),
+        }
+    }
+}
+
 /// Accounts for withdraw protocol fees
 #[event_cpi]
 #[derive(Accounts)]
@@ -33,19 +63,19 @@ pub struct ClaimProtocolFeesCtx<'info> {
     /// The mint of token b
     pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// The treasury token a account
+    /// The treasury token a account, owned by treasury of claim fee operator
     #[account(
         mut,
-        associated_token::authority = treasury::ID,
+        associated_token::authority = claim_fee_operator.load()?.get_treasury(),
         associated_token::mint = token_a_mint,
         associated_token::token_program = token_a_program,
     )]
     pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The treasury token b account
+    /// The treasury token b account, owned by treasury of claim fee operator
     #[account(
         mut,
-        associated_token::authority = treasury::ID,
+        associated_token::authority = claim_fee_operator.load()?.get_treasury(),
         associated_token::mint = token_b_mint,
         associated_token::token_program = token_b_program,
     )]
@@ -63,13 +93,162 @@ pub struct ClaimProtocolFeesCtx<'info> {
 
     /// Token b program
     pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// Insurance fund of pool, required when insurance fee percent of pool is set
+    #[account(mut)]
+    pub insurance_fund: Option<AccountLoader<'info, InsuranceFund>>,
+
+    /// Insurance vault of token a
+    #[account(mut)]
+    pub insurance_token_a_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Insurance vault of token b
+    #[account(mut)]
+    pub insurance_token_b_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 /// Withdraw protocol fees. Permissionless.
-pub fn handle_claim_protocol_fee(ctx: Context<ClaimProtocolFeesCtx>) -> Result<()> {
+/// With a settlement mode, claimed fee of the other token stays in its vault and is swapped through the pool into the
+/// settlement token, so the treasury only receives the settlement token
+pub fn handle_claim_protocol_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimProtocolFeesCtx<'info>>,
+    max_amount_a: u64,
+    max_amount_b: u64,
+    settlement_mode: u8,
+    minimum_settlement_amount: u64,
+) -> Result<()> {
+    let settlement_mode = ProtocolFeeSettlementMode::try_from(settlement_mode)
+        .map_err(|_| PoolError::TypeCastFailed)?;
+
     let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
+
+    // protocol fee in token b is reserved for buyback and burn
+    let max_amount_b = if pool.is_buyback_burn_enabled() {
+        0
+    } else {
+        max_amount_b
+    };
+    let (mut token_a_amount, mut token_b_amount) =
+        pool.claim_protocol_fee(max_amount_a, max_amount_b)?;
+
+    // insurance share is routed in the claimed tokens, only the rest is settled
+    let insurance_a_amount = pool.pool_fees.get_insurance_fee(token_a_amount)?;
+    let insurance_b_amount = pool.pool_fees.get_insurance_fee(token_b_amount)?;
+    token_a_amount = token_a_amount.safe_sub(insurance_a_amount)?;
+    token_b_amount = token_b_amount.safe_sub(insurance_b_amount)?;
 
-    let (token_a_amount, token_b_amount) = pool.claim_protocol_fee();
+    let mut swap_result = None;
+    if let Some(trade_direction) = settlement_mode.get_trade_direction() {
+        let clock = Clock::get()?;
+        let amount_in = match trade_direction {
+            TradeDirection::AtoB => token_a_amount,
+            TradeDirection::BtoA => token_b_amount,
+        };
+
+        if amount_in > 0 {
+            let access_validator = get_pool_access_validator(&pool, None, None, None)?;
+            require!(
+                access_validator.can_swap(&ctx.accounts.operator.key()),
+                PoolError::PoolDisabled
+            );
+            require_vaults_not_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_b_vault)?;
+
+            let current_time = clock.unix_timestamp as u64;
+            pool.update_pre_swap(current_time)?;
+
+            let current_point =
+                ActivationHandler::get_current_point_from_clock(pool.activation_type, &clock)?;
+            let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, false)?;
+            let result =
+                pool.get_swap_result(amount_in, fee_mode, trade_direction, current_point)?;
+            let sqrt_price = pool.sqrt_price;
+            pool.price_circuit_breaker.check_price_change(
+                clock.slot,
+                sqrt_price,
+                result.next_sqrt_price,
+            )?;
+            pool.apply_swap_result(amount_in, &result, fee_mode, trade_direction, current_time)?;
+
+            match trade_direction {
+                TradeDirection::AtoB => {
+                    token_a_amount = 0;
+                    token_b_amount = token_b_amount.safe_add(result.output_amount)?;
+                }
+                TradeDirection::BtoA => {
+                    token_b_amount = 0;
+                    token_a_amount = token_a_amount.safe_add(result.output_amount)?;
+                }
+            }
+            swap_result = Some(result);
+        }
+
+        let (settlement_mint, settlement_amount, settlement_transfer_fee_cache) =
+            match trade_direction {
+                TradeDirection::AtoB => (
+                    &ctx.accounts.token_b_mint,
+                    token_b_amount,
+                    &mut pool.transfer_fee_cache_b,
+                ),
+                TradeDirection::BtoA => (
+                    &ctx.accounts.token_a_mint,
+                    token_a_amount,
+                    &mut pool.transfer_fee_cache_a,
+                ),
+            };
+        let transfer_fee_excluded_settlement_amount =
+            calculate_transfer_fee_excluded_amount_cached(
+                settlement_mint,
+                settlement_transfer_fee_cache,
+                settlement_amount,
+                clock.epoch,
+            )?
+            .amount;
+        require!(
+            transfer_fee_excluded_settlement_amount >= minimum_settlement_amount,
+            PoolError::ExceededSlippage
+        );
+    }
+
+    if pool.pool_fees.insurance_fee_percent > 0 {
+        let (Some(insurance_fund), Some(insurance_token_a_vault), Some(insurance_token_b_vault)) = (
+            &ctx.accounts.insurance_fund,
+            &ctx.accounts.insurance_token_a_vault,
+            &ctx.accounts.insurance_token_b_vault,
+        ) else {
+            return Err(PoolError::InvalidInsuranceFund.into());
+        };
+        let mut insurance_fund_state = insurance_fund.load_mut()?;
+        require!(
+            insurance_fund_state.pool == ctx.accounts.pool.key()
+                && insurance_fund_state.token_a_vault == insurance_token_a_vault.key()
+                && insurance_fund_state.token_b_vault == insurance_token_b_vault.key(),
+            PoolError::InvalidInsuranceFund
+        );
+        insurance_fund_state.accumulate_routed_amount(insurance_a_amount, insurance_b_amount)?;
+
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            insurance_token_a_vault,
+            &ctx.accounts.token_a_program,
+            insurance_a_amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            insurance_token_b_vault,
+            &ctx.accounts.token_b_program,
+            insurance_b_amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
 
     transfer_from_pool(
         ctx.accounts.pool_authority.to_account_info(),
@@ -79,6 +258,7 @@ pub fn handle_claim_protocol_fee(ctx: Context<ClaimProtocolFeesCtx>) -> Result<(
         &ctx.accounts.token_a_program,
         token_a_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
 
     transfer_from_pool(
@@ -89,12 +269,18 @@ pub fn handle_claim_protocol_fee(ctx: Context<ClaimProtocolFeesCtx>) -> Result<(
         &ctx.accounts.token_b_program,
         token_b_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtClaimProtocolFee {
         pool: ctx.accounts.pool.key(),
         token_a_amount,
-        token_b_amount
+        token_b_amount,
+        settlement_mode: settlement_mode.into(),
+        swap_result,
+        insurance_a_amount,
+        insurance_b_amount,
+        sequence,
     });
 
     Ok(())
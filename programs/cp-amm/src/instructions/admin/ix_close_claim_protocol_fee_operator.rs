@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{assert_eq_admin, state::ClaimFeeOperator, EvtCloseClaimFeeOperator, PoolError};
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, ClaimFeeOperator, ProgramAuthority},
+    EvtCloseClaimFeeOperator, PoolError,
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -15,8 +19,11 @@ pub struct CloseClaimFeeOperatorCtx<'info> {
     #[account(mut)]
     pub rent_receiver: UncheckedAccount<'info>,
 
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
     #[account(
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = program_authority.load()?.has_role(AuthorityRole::FeeClaimer, admin.key()) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 }
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{assert_eq_admin, event, state::Config, PoolError};
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    state::{AuthorityRole, Config, ProgramAuthority},
+    PoolError,
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -12,7 +17,10 @@ pub struct CloseConfigCtx<'info> {
     )]
     pub config: AccountLoader<'info, Config>,
 
-    #[account(mut, constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin)]
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(mut, constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
     pub admin: Signer<'info>,
 
     /// CHECK: Account to receive closed account rental SOL
@@ -0,0 +1,424 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Token2022},
+    token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface},
+};
+use std::collections::BTreeSet;
+
+use crate::{
+    constants::{
+        seeds::{
+            INSURANCE_FUND_PREFIX, POOL_AUTHORITY_PREFIX, PROGRAM_AUTHORITY_PREFIX,
+            TRADE_MINING_PREFIX,
+        },
+        MINIMUM_LIQUIDITY,
+    },
+    event, load_reward_extension,
+    safe_math::SafeMath,
+    state::{
+        AuthorityRole, InsuranceFund, Pool, Position, ProgramAuthority, RewardInfo, TradeMining,
+    },
+    token::close_pool_token_account,
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePoolCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        close = rent_receiver
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault token account for token a
+    #[account(mut, token::token_program = token_a_program)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token b
+    #[account(mut, token::token_program = token_b_program)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a, dust left in token a vault is burnt
+    #[account(mut)]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b, dust left in token b vault is burnt
+    #[account(mut)]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Position holding the minimum liquidity locked at pool initialization, required if it is left in pool
+    #[account(mut, has_one = pool)]
+    pub position: Option<AccountLoader<'info, Position>>,
+
+    /// Nft mint of the position, required with position
+    #[account(mut)]
+    pub position_nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// The token account for nft of the position, required with position
+    #[account(mut)]
+    pub position_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Owner of the position, burns the position nft and receives rent of the position accounts
+    #[account(mut)]
+    pub position_owner: Option<Signer<'info>>,
+
+    /// Program of the position nft, required with position
+    pub token_program: Option<Program<'info, Token2022>>,
+
+    /// CHECK: trade mining of pool, budget must be withdrawn if it is initialized
+    #[account(seeds = [TRADE_MINING_PREFIX, pool.key().as_ref()], bump)]
+    pub trade_mining: UncheckedAccount<'info>,
+
+    /// CHECK: insurance fund of pool, closed with its vaults if it is initialized
+    #[account(mut, seeds = [INSURANCE_FUND_PREFIX, pool.key().as_ref()], bump)]
+    pub insurance_fund: UncheckedAccount<'info>,
+
+    /// Insurance vault of token a, required if insurance fund is initialized
+    #[account(mut, token::token_program = token_a_program)]
+    pub insurance_token_a_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Insurance vault of token b, required if insurance fund is initialized
+    #[account(mut, token::token_program = token_b_program)]
+    pub insurance_token_b_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RewardVaultRemainingAccounts<'info> {
+    #[account(mut, token::token_program = token_program)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close an empty reward vault of an initialized reward, remaining accounts contain the reward vault followed by its token program
+fn close_reward_vault<'c: 'info, 'info>(
+    ctx: &Context<'_, '_, 'c, 'info, ClosePoolCtx<'info>>,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    reward_info: &RewardInfo,
+) -> Result<()> {
+    let accounts = RewardVaultRemainingAccounts::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut RewardVaultRemainingAccountsBumps {},
+        &mut BTreeSet::new(),
+    )?;
+    require!(
+        accounts.reward_vault.key() == reward_info.vault,
+        PoolError::InvalidRewardVault
+    );
+    require!(accounts.reward_vault.amount == 0, PoolError::PoolIsNotEmpty);
+
+    close_pool_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        accounts.reward_vault.to_account_info(),
+        ctx.accounts.rent_receiver.to_account_info(),
+        accounts.token_program.to_account_info(),
+        ctx.bumps.pool_authority,
+    )
+}
+
+/// Data of a zero-copy account owned by the program, passed as unchecked account because it may not be initialized
+fn load_program_account<T: anchor_lang::ZeroCopy + Space>(data: &[u8]) -> Result<&T> {
+    data.strip_prefix(T::DISCRIMINATOR)
+        .and_then(|data| data.get(..T::INIT_SPACE))
+        .and_then(|data| bytemuck::try_from_bytes(data).ok())
+        .ok_or_else(|| ErrorCode::AccountDiscriminatorMismatch.into())
+}
+
+/// Close the insurance fund of pool and its vaults if it is initialized. The fund authority must claim
+/// the insurance vaults before, so funds routed to the insurance fund are never burnt with the pool
+fn close_insurance_fund<'info>(
+    ctx: &Context<'_, '_, '_, 'info, ClosePoolCtx<'info>>,
+) -> Result<()> {
+    let insurance_fund_info = ctx.accounts.insurance_fund.to_account_info();
+    if insurance_fund_info.owner != &crate::ID {
+        return Ok(());
+    }
+    let (token_a_vault, token_b_vault) = {
+        let data = insurance_fund_info.try_borrow_data()?;
+        let insurance_fund = load_program_account::<InsuranceFund>(&data)?;
+        (insurance_fund.token_a_vault, insurance_fund.token_b_vault)
+    };
+
+    let (Some(insurance_token_a_vault), Some(insurance_token_b_vault)) = (
+        ctx.accounts.insurance_token_a_vault.as_ref(),
+        ctx.accounts.insurance_token_b_vault.as_ref(),
+    ) else {
+        return Err(PoolError::InvalidInsuranceFund.into());
+    };
+    require!(
+        insurance_token_a_vault.key() == token_a_vault
+            && insurance_token_b_vault.key() == token_b_vault,
+        PoolError::InvalidInsuranceFund
+    );
+    require!(
+        insurance_token_a_vault.amount == 0 && insurance_token_b_vault.amount == 0,
+        PoolError::PoolIsNotEmpty
+    );
+
+    close_pool_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        insurance_token_a_vault.to_account_info(),
+        ctx.accounts.rent_receiver.to_account_info(),
+        ctx.accounts.token_a_program.to_account_info(),
+        ctx.bumps.pool_authority,
+    )?;
+    close_pool_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        insurance_token_b_vault.to_account_info(),
+        ctx.accounts.rent_receiver.to_account_info(),
+        ctx.accounts.token_b_program.to_account_info(),
+        ctx.bumps.pool_authority,
+    )?;
+
+    // same as closing an anchor account: refund lamports and give the account back to system program
+    let rent_receiver_info = ctx.accounts.rent_receiver.to_account_info();
+    let lamports = insurance_fund_info.lamports();
+    **rent_receiver_info.try_borrow_mut_lamports()? =
+        rent_receiver_info.lamports().safe_add(lamports)?;
+    **insurance_fund_info.try_borrow_mut_lamports()? = 0;
+    insurance_fund_info.assign(&System::id());
+    insurance_fund_info.realloc(0, false)?;
+    Ok(())
+}
+
+/// Burn the whole amount of a pool vault
+fn burn_vault_dust<'info>(
+    ctx: &Context<'_, '_, '_, 'info, ClosePoolCtx<'info>>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if token_vault.amount == 0 {
+        return Ok(());
+    }
+    let signer_seeds = pool_authority_seeds!(ctx.bumps.pool_authority);
+    burn(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Burn {
+                mint: token_mint.to_account_info(),
+                from: token_vault.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&signer_seeds[..]],
+        ),
+        token_vault.amount,
+    )
+}
+
+/// Burn the position nft, then close the nft token account, nft mint and position with rent refunded to the position owner
+fn close_position_with_nft<'info>(
+    ctx: &Context<'_, '_, '_, 'info, ClosePoolCtx<'info>>,
+    position: &AccountLoader<'info, Position>,
+) -> Result<()> {
+    let (
+        Some(position_nft_mint),
+        Some(position_nft_account),
+        Some(position_owner),
+        Some(token_program),
+    ) = (
+        ctx.accounts.position_nft_mint.as_ref(),
+        ctx.accounts.position_nft_account.as_ref(),
+        ctx.accounts.position_owner.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    )
+    else {
+        return Err(PoolError::InvalidPositionNft.into());
+    };
+    let nft_mint = position.load()?.nft_mint;
+    require!(
+        position_nft_mint.key() == nft_mint
+            && position_nft_account.mint == nft_mint
+            && position_nft_account.amount == 1
+            && position_nft_account.owner == position_owner.key(),
+        PoolError::InvalidPositionNft
+    );
+
+    token_2022::burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token_2022::Burn {
+                mint: position_nft_mint.to_account_info(),
+                from: position_nft_account.to_account_info(),
+                authority: position_owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    token_2022::close_account(CpiContext::new(
+        token_program.to_account_info(),
+        token_2022::CloseAccount {
+            account: position_nft_account.to_account_info(),
+            destination: position_owner.to_account_info(),
+            authority: position_owner.to_account_info(),
+        },
+    ))?;
+
+    close_pool_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        position_nft_mint.to_account_info(),
+        position_owner.to_account_info(),
+        token_program.to_account_info(),
+        ctx.bumps.pool_authority,
+    )?;
+
+    position.close(position_owner.to_account_info())
+}
+
+/// Close an empty pool, its vaults, reward vaults and insurance fund. Remaining accounts contain reward extension if pool
+/// has one, followed by the reward vault and token program of every initialized reward.
+/// Trade mining of pool must have no remaining budget and insurance vaults must be claimed by the fund authority.
+/// Pools only holding the minimum liquidity locked at initialization are closed with the position holding it, signed by
+/// the position owner. Position nft is burnt and position rent goes to its owner, vault dust of that liquidity is burnt
+pub fn handle_close_pool<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClosePoolCtx<'info>>,
+) -> Result<()> {
+    let pool = ctx.accounts.pool.load()?;
+    require!(
+        pool.protocol_a_fee == 0
+            && pool.protocol_b_fee == 0
+            && pool.partner_a_fee == 0
+            && pool.partner_b_fee == 0,
+        PoolError::PoolIsNotEmpty
+    );
+
+    // budget never accrued to traders must be withdrawn by the funder before
+    if ctx.accounts.trade_mining.owner == &crate::ID {
+        let data = ctx.accounts.trade_mining.try_borrow_data()?;
+        require!(
+            load_program_account::<TradeMining>(&data)?.remaining_budget == 0,
+            PoolError::PoolIsNotEmpty
+        );
+    }
+    close_insurance_fund(&ctx)?;
+
+    if pool.liquidity == 0 {
+        require!(pool.metrics.total_position == 0, PoolError::PoolIsNotEmpty);
+        // vaults also hold fee of referrals, so they must be empty
+        require!(
+            ctx.accounts.token_a_vault.amount == 0 && ctx.accounts.token_b_vault.amount == 0,
+            PoolError::PoolIsNotEmpty
+        );
+    } else {
+        // only the minimum liquidity locked at initialization is left, it can never be removed
+        require!(
+            pool.liquidity == MINIMUM_LIQUIDITY
+                && pool.permanent_lock_liquidity == MINIMUM_LIQUIDITY
+                && pool.metrics.total_position == 1
+                && pool.is_fee_tracked()
+                && pool.unclaimed_referral_a_fee == 0
+                && pool.unclaimed_referral_b_fee == 0,
+            PoolError::PoolIsNotEmpty
+        );
+        let position = ctx
+            .accounts
+            .position
+            .as_ref()
+            .ok_or(PoolError::PoolIsNotEmpty)?;
+        {
+            // fee owed to the position must be claimed by its owner before
+            let mut position = *position.load()?;
+            position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+            require!(
+                position.permanent_locked_liquidity == MINIMUM_LIQUIDITY
+                    && position.fee_a_pending == 0
+                    && position.fee_b_pending == 0,
+                PoolError::PoolIsNotEmpty
+            );
+        }
+        close_position_with_nft(&ctx, position)?;
+
+        burn_vault_dust(
+            &ctx,
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_program,
+        )?;
+        burn_vault_dust(
+            &ctx,
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_program,
+        )?;
+    }
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let reward_extension = if pool.has_reward_extension() {
+        Some(load_reward_extension(
+            &mut remaining_accounts,
+            ctx.accounts.pool.key(),
+        )?)
+    } else {
+        None
+    };
+
+    for reward_info in pool.reward_infos.iter() {
+        if reward_info.initialized() {
+            close_reward_vault(&ctx, &mut remaining_accounts, reward_info)?;
+        }
+    }
+
+    if let Some(reward_extension) = reward_extension {
+        {
+            let reward_extension = reward_extension.load()?;
+            for reward_info in reward_extension.reward_infos.iter() {
+                if reward_info.initialized() {
+                    close_reward_vault(&ctx, &mut remaining_accounts, reward_info)?;
+                }
+            }
+        }
+        reward_extension.close(ctx.accounts.rent_receiver.to_account_info())?;
+    }
+
+    close_pool_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.token_a_vault.to_account_info(),
+        ctx.accounts.rent_receiver.to_account_info(),
+        ctx.accounts.token_a_program.to_account_info(),
+        ctx.bumps.pool_authority,
+    )?;
+    close_pool_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.token_b_vault.to_account_info(),
+        ctx.accounts.rent_receiver.to_account_info(),
+        ctx.accounts.token_b_program.to_account_info(),
+        ctx.bumps.pool_authority,
+    )?;
+
+    emit_cpi!(event::EvtClosePool {
+        pool: ctx.accounts.pool.key(),
+        token_a_mint: pool.token_a_mint,
+        token_b_mint: pool.token_b_mint,
+        rent_receiver: ctx.accounts.rent_receiver.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, ProgramAuthority, TokenBadge},
+    EvtCloseTokenBadge, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseTokenBadgeCtx<'info> {
+    #[account(
+        mut,
+        close = rent_receiver
+    )]
+    pub token_badge: AccountLoader<'info, TokenBadge>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Account to receive closed account rental SOL
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Revoke token badge of a mint, new pools and rewards of the mint can't be initialized until the badge is created again
+pub fn handle_close_token_badge(ctx: Context<CloseTokenBadgeCtx>) -> Result<()> {
+    let token_badge = ctx.accounts.token_badge.load()?;
+
+    emit_cpi!(EvtCloseTokenBadge {
+        token_mint: token_badge.token_mint,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
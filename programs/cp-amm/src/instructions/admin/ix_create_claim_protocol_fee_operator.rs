@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    assert_eq_admin, constants::seeds::CLAIM_FEE_OPERATOR_PREFIX, state::ClaimFeeOperator,
+    constants::seeds::{CLAIM_FEE_OPERATOR_PREFIX, PROGRAM_AUTHORITY_PREFIX},
+    state::{AuthorityRole, ClaimFeeOperator, ProgramAuthority},
     EvtCreateClaimFeeOperator, PoolError,
 };
 
@@ -23,9 +24,12 @@ pub struct CreateClaimFeeOperatorCtx<'info> {
     /// CHECK: operator
     pub operator: UncheckedAccount<'info>,
 
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
     #[account(
         mut,
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = program_authority.load()?.has_role(AuthorityRole::FeeClaimer, admin.key()) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 
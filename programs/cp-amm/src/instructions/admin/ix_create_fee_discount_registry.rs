@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::seeds::{FEE_DISCOUNT_REGISTRY_PREFIX, PROGRAM_AUTHORITY_PREFIX},
+    state::{AuthorityRole, FeeDiscountRegistry, FeeDiscountTier, Pool, ProgramAuthority},
+    EvtCreateFeeDiscountRegistry, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct FeeDiscountTierParameters {
+    /// minimum amount of discount token held by trader
+    pub min_amount: u64,
+    /// discount on trade fee numerator
+    pub discount_bps: u16,
+}
+
+impl FeeDiscountTierParameters {
+    pub fn to_fee_discount_tiers(tiers: &[FeeDiscountTierParameters]) -> Vec<FeeDiscountTier> {
+        tiers
+            .iter()
+            .map(|tier| FeeDiscountTier {
+                min_amount: tier.min_amount,
+                discount_bps: tier.discount_bps,
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateFeeDiscountRegistryCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [
+            FEE_DISCOUNT_REGISTRY_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + FeeDiscountRegistry::INIT_SPACE
+    )]
+    pub fee_discount_registry: AccountLoader<'info, FeeDiscountRegistry>,
+
+    /// discount token mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_fee_discount_registry(
+    ctx: Context<CreateFeeDiscountRegistryCtx>,
+    tiers: Vec<FeeDiscountTierParameters>,
+) -> Result<()> {
+    let mut fee_discount_registry = ctx.accounts.fee_discount_registry.load_init()?;
+    fee_discount_registry.initialize(
+        ctx.accounts.pool.key(),
+        ctx.accounts.mint.key(),
+        &FeeDiscountTierParameters::to_fee_discount_tiers(&tiers),
+    )?;
+
+    emit_cpi!(EvtCreateFeeDiscountRegistry {
+        pool: ctx.accounts.pool.key(),
+        fee_discount_registry: ctx.accounts.fee_discount_registry.key(),
+        mint: ctx.accounts.mint.key(),
+        tiers,
+    });
+
+    Ok(())
+}
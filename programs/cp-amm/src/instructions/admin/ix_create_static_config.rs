@@ -1,15 +1,17 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    activation_handler::ActivationHandler,
-    assert_eq_admin,
-    constants::{seeds::CONFIG_PREFIX, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    activation_handler::{ActivationHandler, ActivationType},
+    constants::{
+        seeds::{CONFIG_PREFIX, PROGRAM_AUTHORITY_PREFIX},
+        MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+    },
     event,
     params::{
         activation::ActivationParams,
         fee_parameters::{PartnerInfo, PoolFeeParameters},
     },
-    state::{CollectFeeMode, Config},
+    state::{AuthorityRole, CollectFeeMode, Config, ProgramAuthority},
     PoolError,
 };
 
@@ -24,6 +26,45 @@ pub struct StaticConfigParameters {
     pub collect_fee_mode: u8,
 }
 
+impl StaticConfigParameters {
+    /// Validate parameters not depending on current time, so it can also be checked off-chain
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.sqrt_min_price >= MIN_SQRT_PRICE && self.sqrt_max_price <= MAX_SQRT_PRICE,
+            PoolError::InvalidPriceRange
+        );
+        // TODO do we need more buffer here?
+        require!(
+            self.sqrt_min_price < self.sqrt_max_price,
+            PoolError::InvalidPriceRange
+        );
+
+        // validate collect fee mode
+        require!(
+            CollectFeeMode::try_from(self.collect_fee_mode).is_ok(),
+            PoolError::InvalidCollectFeeMode
+        );
+
+        require!(
+            ActivationType::try_from(self.activation_type).is_ok(),
+            PoolError::InvalidActivationType
+        );
+
+        // validate fee
+        self.pool_fees.validate()?;
+
+        let partner_info = PartnerInfo {
+            partner_authority: self.pool_creator_authority,
+            fee_percent: self.pool_fees.partner_fee_percent,
+            ..Default::default()
+        };
+
+        partner_info.validate()?;
+
+        Ok(())
+    }
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(index: u64)]
@@ -37,7 +78,10 @@ pub struct CreateConfigCtx<'info> {
     )]
     pub config: AccountLoader<'info, Config>,
 
-    #[account(mut, constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin)]
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(mut, constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
     pub admin: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -48,6 +92,8 @@ pub fn handle_create_static_config(
     index: u64,
     config_parameters: StaticConfigParameters,
 ) -> Result<()> {
+    config_parameters.validate()?;
+
     let StaticConfigParameters {
         pool_fees,
         vault_config_key,
@@ -58,25 +104,6 @@ pub fn handle_create_static_config(
         collect_fee_mode,
     } = config_parameters;
 
-    require!(
-        sqrt_min_price >= MIN_SQRT_PRICE && sqrt_max_price <= MAX_SQRT_PRICE,
-        PoolError::InvalidPriceRange
-    );
-    // TODO do we need more buffer here?
-    require!(
-        sqrt_min_price < sqrt_max_price,
-        PoolError::InvalidPriceRange
-    );
-
-    // validate collect fee mode
-    require!(
-        CollectFeeMode::try_from(collect_fee_mode).is_ok(),
-        PoolError::InvalidCollectFeeMode
-    );
-
-    // validate fee
-    pool_fees.validate()?;
-
     let has_alpha_vault = vault_config_key.ne(&Pubkey::default());
 
     let activation_point = Some(ActivationHandler::get_max_activation_point(
@@ -90,14 +117,6 @@ pub fn handle_create_static_config(
     };
     activation_params.validate()?;
 
-    let partner_info = PartnerInfo {
-        partner_authority: pool_creator_authority,
-        fee_percent: pool_fees.partner_fee_percent,
-        ..Default::default()
-    };
-
-    partner_info.validate()?;
-
     let mut config = ctx.accounts.config.load_init()?;
     config.init_static_config(
         index,
@@ -2,8 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
 use crate::{
-    assert_eq_admin, constants::seeds::TOKEN_BADGE_PREFIX, state::TokenBadge,
-    token::is_supported_mint, EvtCreateTokenBadge, PoolError,
+    constants::seeds::{PROGRAM_AUTHORITY_PREFIX, TOKEN_BADGE_PREFIX},
+    state::{AuthorityRole, ProgramAuthority, TokenBadge, TOKEN_BADGE_POLICY_MASK},
+    token::{get_transfer_hook_program, is_supported_mint},
+    EvtCreateTokenBadge, PoolError,
 };
 
 #[event_cpi]
@@ -23,25 +25,38 @@ pub struct CreateTokenBadgeCtx<'info> {
 
     pub token_mint: InterfaceAccount<'info, Mint>,
 
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
     #[account(
         mut,
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_create_token_badge(ctx: Context<CreateTokenBadgeCtx>) -> Result<()> {
+/// Create token badge, `policy` is the bits of TokenBadgePolicy allowed for the mint.
+/// Current transfer hook program of the mint is recorded as the approved one
+pub fn handle_create_token_badge(ctx: Context<CreateTokenBadgeCtx>, policy: u8) -> Result<()> {
     require!(
         !is_supported_mint(&ctx.accounts.token_mint)?,
         PoolError::CannotCreateTokenBadgeOnSupportedMint
     );
+    require!(
+        policy & !TOKEN_BADGE_POLICY_MASK == 0,
+        PoolError::InvalidInput
+    );
+    let transfer_hook_program =
+        get_transfer_hook_program(&ctx.accounts.token_mint)?.unwrap_or_default();
     let mut token_badge = ctx.accounts.token_badge.load_init()?;
-    token_badge.initialize(ctx.accounts.token_mint.key())?;
+    token_badge.initialize(ctx.accounts.token_mint.key(), policy, transfer_hook_program)?;
 
     emit_cpi!(EvtCreateTokenBadge {
         token_mint: ctx.accounts.token_mint.key(),
+        policy,
+        transfer_hook_program,
     });
 
     Ok(())
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::{BUYBACK_BURN_PREFIX, PROGRAM_AUTHORITY_PREFIX},
+    state::{AuthorityRole, BuybackBurn, Pool, ProgramAuthority},
+    EvtInitializeBuybackBurn, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeBuybackBurnCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [
+            BUYBACK_BURN_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + BuybackBurn::INIT_SPACE
+    )]
+    pub buyback_burn: AccountLoader<'info, BuybackBurn>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Reserve protocol fee in token b of a pool for buyback and burn of token a, it can't be claimed afterwards.
+/// Pools routing protocol fee to an insurance fund can't enable it, buybacks would spend the insurance share
+pub fn handle_initialize_buyback_burn(
+    ctx: Context<InitializeBuybackBurnCtx>,
+    max_amount_per_buyback: u64,
+    min_buyback_interval: u64,
+) -> Result<()> {
+    require!(max_amount_per_buyback > 0, PoolError::InvalidParameters);
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.pool_fees.insurance_fee_percent == 0,
+        PoolError::ConflictingProtocolFeeRouting
+    );
+    pool.increment_sequence();
+    pool.buyback_burn_flag = 1;
+
+    let mut buyback_burn = ctx.accounts.buyback_burn.load_init()?;
+    buyback_burn.initialize(
+        ctx.accounts.pool.key(),
+        max_amount_per_buyback,
+        min_buyback_interval,
+    );
+
+    emit_cpi!(EvtInitializeBuybackBurn {
+        pool: ctx.accounts.pool.key(),
+        buyback_burn: ctx.accounts.buyback_burn.key(),
+        max_amount_per_buyback,
+        min_buyback_interval,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::{
+        INSURANCE_FUND_PREFIX, INSURANCE_VAULT_PREFIX, POOL_AUTHORITY_PREFIX,
+        PROGRAM_AUTHORITY_PREFIX,
+    },
+    params::fee_parameters::validate_fee_fraction,
+    state::{AuthorityRole, InsuranceFund, Pool, ProgramAuthority},
+    EvtInitializeInsuranceFund, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeInsuranceFundCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = token_a_mint, has_one = token_b_mint)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [
+            INSURANCE_FUND_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + InsuranceFund::INIT_SPACE
+    )]
+    pub insurance_fund: AccountLoader<'info, InsuranceFund>,
+
+    #[account(
+        init,
+        seeds = [
+            INSURANCE_VAULT_PREFIX.as_ref(),
+            insurance_fund.key().as_ref(),
+            token_a_mint.key().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        token::token_program = token_a_program,
+    )]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds = [
+            INSURANCE_VAULT_PREFIX.as_ref(),
+            insurance_fund.key().as_ref(),
+            token_b_mint.key().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        token::token_program = token_b_program,
+    )]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the insurance fund of a pool, `insurance_fee_percent` of protocol fee claimed afterwards is routed
+/// to its vaults instead of the treasury. Pools with buyback and burn can't route it, their protocol fee in token b
+/// is never claimed
+pub fn handle_initialize_insurance_fund(
+    ctx: Context<InitializeInsuranceFundCtx>,
+    authority: Pubkey,
+    insurance_fee_percent: u8,
+) -> Result<()> {
+    validate_fee_fraction(insurance_fee_percent.into(), 100)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        !pool.is_buyback_burn_enabled(),
+        PoolError::ConflictingProtocolFeeRouting
+    );
+    pool.increment_sequence();
+    pool.pool_fees.insurance_fee_percent = insurance_fee_percent;
+
+    let mut insurance_fund = ctx.accounts.insurance_fund.load_init()?;
+    insurance_fund.initialize(
+        ctx.accounts.pool.key(),
+        authority,
+        ctx.accounts.token_a_vault.key(),
+        ctx.accounts.token_b_vault.key(),
+    );
+
+    emit_cpi!(EvtInitializeInsuranceFund {
+        pool: ctx.accounts.pool.key(),
+        insurance_fund: ctx.accounts.insurance_fund.key(),
+        authority,
+        insurance_fee_percent,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_eq_admin,
+    constants::{
+        seeds::PROGRAM_AUTHORITY_PREFIX, MAX_AUTHORITY_TRANSFER_DELAY, NUM_AUTHORITY_ROLES,
+    },
+    state::ProgramAuthority,
+    EvtInitializeProgramAuthority, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct InitializeProgramAuthorityParameters {
+    pub config_admin: Pubkey,
+    pub fee_claimer: Pubkey,
+    pub reward_admin: Pubkey,
+    pub pauser: Pubkey,
+    /// delay in seconds between proposing and accepting a role
+    pub transfer_delay: u64,
+}
+
+impl InitializeProgramAuthorityParameters {
+    /// keys indexed by `AuthorityRole`
+    pub fn to_roles(&self) -> [Pubkey; NUM_AUTHORITY_ROLES] {
+        [
+            self.config_admin,
+            self.fee_claimer,
+            self.reward_admin,
+            self.pauser,
+        ]
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeProgramAuthorityCtx<'info> {
+    #[account(
+        init,
+        payer = admin,
+        seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()],
+        bump,
+        space = 8 + ProgramAuthority::INIT_SPACE
+    )]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// one of the hardcoded admins, only used to bootstrap program authority
+    #[account(
+        mut,
+        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_program_authority(
+    ctx: Context<InitializeProgramAuthorityCtx>,
+    params: InitializeProgramAuthorityParameters,
+) -> Result<()> {
+    let roles = params.to_roles();
+    require!(
+        roles.iter().all(|role| *role != Pubkey::default()),
+        PoolError::InvalidAuthorityRole
+    );
+    require!(
+        params.transfer_delay <= MAX_AUTHORITY_TRANSFER_DELAY,
+        PoolError::InvalidInput
+    );
+
+    let mut program_authority = ctx.accounts.program_authority.load_init()?;
+    program_authority.initialize(roles, params.transfer_delay);
+
+    emit_cpi!(EvtInitializeProgramAuthority {
+        roles,
+        transfer_delay: params.transfer_delay,
+    });
+
+    Ok(())
+}
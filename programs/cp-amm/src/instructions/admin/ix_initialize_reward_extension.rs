@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::{PROGRAM_AUTHORITY_PREFIX, REWARD_EXTENSION_PREFIX},
+    error::PoolError,
+    event::EvtInitializeRewardExtension,
+    state::{AuthorityRole, Pool, ProgramAuthority, RewardExtension},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeRewardExtensionCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [REWARD_EXTENSION_PREFIX.as_ref(), pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + RewardExtension::INIT_SPACE
+    )]
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::RewardAdmin, admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_reward_extension(
+    ctx: Context<InitializeRewardExtensionCtx>,
+) -> Result<()> {
+    let mut reward_extension = ctx.accounts.reward_extension.load_init()?;
+    reward_extension.initialize(ctx.accounts.pool.key());
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    pool.reward_extension_flag = 1;
+
+    emit_cpi!(EvtInitializeRewardExtension {
+        pool: ctx.accounts.pool.key(),
+        reward_extension: ctx.accounts.reward_extension.key(),
+    });
+
+    Ok(())
+}
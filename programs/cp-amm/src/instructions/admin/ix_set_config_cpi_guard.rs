@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    state::{AuthorityRole, Config, ProgramAuthority},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetConfigCpiGuardCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+/// Set duration after activation point that swaps of pools created from the config must be top-level instructions,
+/// existing pools of the config keep their duration. 0 disables the guard, so aggregators can swap by CPI
+pub fn handle_set_config_cpi_guard(
+    ctx: Context<SetConfigCpiGuardCtx>,
+    cpi_guard_duration: u64,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.set_cpi_guard_duration(cpi_guard_duration)?;
+
+    emit_cpi!(event::EvtSetConfigCpiGuard {
+        config: ctx.accounts.config.key(),
+        cpi_guard_duration,
+    });
+
+    Ok(())
+}
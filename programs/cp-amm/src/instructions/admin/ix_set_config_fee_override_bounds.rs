@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    state::{AuthorityRole, Config, ProgramAuthority},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetConfigFeeOverrideBoundsCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_set_config_fee_override_bounds(
+    ctx: Context<SetConfigFeeOverrideBoundsCtx>,
+    min_fee_numerator: u64,
+    max_fee_numerator: u64,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.set_pool_fee_override_bounds(min_fee_numerator, max_fee_numerator)?;
+
+    emit_cpi!(event::EvtSetConfigFeeOverrideBounds {
+        config: ctx.accounts.config.key(),
+        min_fee_numerator,
+        max_fee_numerator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    state::{AuthorityRole, Config, ProgramAuthority},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetConfigRewardBoostCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+/// Set extra reward weight of vested and permanently locked liquidity for pools created from the config,
+/// existing pools of the config keep their boost
+pub fn handle_set_config_reward_boost(
+    ctx: Context<SetConfigRewardBoostCtx>,
+    vesting_reward_boost_bps: u16,
+    permanent_lock_reward_boost_bps: u16,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.set_reward_boost(vesting_reward_boost_bps, permanent_lock_reward_boost_bps)?;
+
+    emit_cpi!(event::EvtSetConfigRewardBoost {
+        config: ctx.accounts.config.key(),
+        vesting_reward_boost_bps,
+        permanent_lock_reward_boost_bps,
+    });
+
+    Ok(())
+}
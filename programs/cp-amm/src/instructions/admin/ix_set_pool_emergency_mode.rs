@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    state::{AuthorityRole, Pool, ProgramAuthority},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolEmergencyModeCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+/// In emergency mode, swaps and deposits are blocked, so no fee is accrued,
+/// and liquidity providers can withdraw regardless of activation point and vesting locks.
+/// Vestings refreshed in emergency mode are released and closed, disabling emergency mode doesn't lock them again,
+/// so only the config admin can toggle it
+pub fn handle_set_pool_emergency_mode(
+    ctx: Context<SetPoolEmergencyModeCtx>,
+    enabled: bool,
+) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    require!(
+        pool.is_emergency_mode() != enabled,
+        PoolError::InvalidPoolStatus
+    );
+    pool.emergency_mode = enabled.into();
+
+    emit_cpi!(event::EvtSetPoolEmergencyMode {
+        pool: ctx.accounts.pool.key(),
+        enabled,
+    });
+
+    Ok(())
+}
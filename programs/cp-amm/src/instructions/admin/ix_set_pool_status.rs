@@ -1,36 +1,41 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    assert_eq_admin, event,
-    state::{Pool, PoolStatus},
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    state::{AuthorityRole, Pool, ProgramAuthority, POOL_STATUS_MASK},
     PoolError,
 };
 
 #[event_cpi]
 #[derive(Accounts)]
-
 pub struct SetPoolStatusCtx<'info> {
     #[account(mut)]
     pub pool: AccountLoader<'info, Pool>,
 
-    #[account(constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin)]
-    pub admin: Signer<'info>,
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::Pauser, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidPoolStatusAuthority
+    )]
+    pub signer: Signer<'info>,
 }
 
 pub fn handle_set_pool_status(ctx: Context<SetPoolStatusCtx>, status: u8) -> Result<()> {
     let mut pool = ctx.accounts.pool.load_mut()?;
-    let new_pool_status = PoolStatus::try_from(status).map_err(|_| PoolError::TypeCastFailed)?;
-    let current_pool_status =
-        PoolStatus::try_from(pool.pool_status).map_err(|_| PoolError::TypeCastFailed)?;
-
+    pool.increment_sequence();
+    // status is a bitfield of disabled operations
     require!(
-        new_pool_status != current_pool_status,
+        status & !POOL_STATUS_MASK == 0 && status != pool.pool_status,
         PoolError::InvalidPoolStatus
     );
-    pool.pool_status = new_pool_status.into();
+    pool.pool_status = status;
 
     emit_cpi!(event::EvtSetPoolStatus {
         pool: ctx.accounts.pool.key(),
+        signer: ctx.accounts.signer.key(),
         status,
     });
 
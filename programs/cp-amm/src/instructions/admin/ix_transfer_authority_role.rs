@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, ProgramAuthority},
+    EvtTransferAuthorityRole, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferAuthorityRoleCtx<'info> {
+    #[account(mut, seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// current key of the role
+    pub signer: Signer<'info>,
+}
+
+/// Propose a new key for a role, the new key must accept the role after the transfer delay to take it over
+pub fn handle_transfer_authority_role(
+    ctx: Context<TransferAuthorityRoleCtx>,
+    role: u8,
+    new_key: Pubkey,
+) -> Result<()> {
+    let authority_role =
+        AuthorityRole::try_from(role).map_err(|_| PoolError::InvalidAuthorityRole)?;
+
+    let mut program_authority = ctx.accounts.program_authority.load_mut()?;
+    require!(
+        program_authority.has_role(authority_role, ctx.accounts.signer.key()),
+        PoolError::InvalidAdmin
+    );
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let activation_point =
+        program_authority.transfer_role(authority_role, new_key, current_timestamp)?;
+
+    emit_cpi!(EvtTransferAuthorityRole {
+        role,
+        current_key: ctx.accounts.signer.key(),
+        pending_key: new_key,
+        activation_point,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, ClaimFeeOperator, ProgramAuthority},
+    EvtUpdateClaimFeeOperator, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateClaimFeeOperatorCtx<'info> {
+    #[account(mut)]
+    pub claim_fee_operator: AccountLoader<'info, ClaimFeeOperator>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::FeeClaimer, admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Rotate operator key and set treasury receiving protocol fee of a claim fee operator
+pub fn handle_update_claim_fee_operator(
+    ctx: Context<UpdateClaimFeeOperatorCtx>,
+    operator: Pubkey,
+    treasury: Pubkey,
+) -> Result<()> {
+    let mut claim_fee_operator = ctx.accounts.claim_fee_operator.load_mut()?;
+    let old_operator = claim_fee_operator.operator;
+    claim_fee_operator.update(operator, treasury);
+
+    emit_cpi!(EvtUpdateClaimFeeOperator {
+        claim_fee_operator: ctx.accounts.claim_fee_operator.key(),
+        old_operator,
+        operator,
+        treasury: claim_fee_operator.get_treasury(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    event,
+    params::{
+        activation::ActivationParams,
+        fee_parameters::{PartnerInfo, PoolFeeParameters},
+    },
+    state::{AuthorityRole, Config, ConfigType, ProgramAuthority},
+    PoolError,
+};
+
+/// Fields of config to update, `None` keeps the current value
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default)]
+pub struct UpdateConfigParameters {
+    pub pool_fees: Option<PoolFeeParameters>,
+    pub vault_config_key: Option<Pubkey>,
+    pub pool_creator_authority: Option<Pubkey>,
+    pub activation_type: Option<u8>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfigCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+/// Update config, pools created before keep their parameters
+pub fn handle_update_config(
+    ctx: Context<UpdateConfigCtx>,
+    params: UpdateConfigParameters,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let config_type = config.get_config_type()?;
+
+    if config_type == ConfigType::Dynamic {
+        // dynamic config only defines pool creator authority
+        require!(
+            params.pool_fees.is_none()
+                && params.vault_config_key.is_none()
+                && params.activation_type.is_none(),
+            PoolError::InvalidConfigType
+        );
+    }
+
+    let UpdateConfigParameters {
+        pool_fees,
+        vault_config_key,
+        pool_creator_authority,
+        activation_type,
+    } = params;
+    let pool_fees = pool_fees.unwrap_or_else(|| config.pool_fees.to_pool_fee_parameters());
+    let vault_config_key = vault_config_key.unwrap_or(config.vault_config_key);
+    let pool_creator_authority = pool_creator_authority.unwrap_or(config.pool_creator_authority);
+    let activation_type = activation_type.unwrap_or(config.activation_type);
+
+    if config_type == ConfigType::Static {
+        // same validations as create config
+        pool_fees.validate()?;
+
+        let activation_params = ActivationParams {
+            activation_point: Some(ActivationHandler::get_max_activation_point(
+                activation_type,
+            )?),
+            activation_type,
+            has_alpha_vault: vault_config_key.ne(&Pubkey::default()),
+        };
+        activation_params.validate()?;
+
+        let partner_info = PartnerInfo {
+            partner_authority: pool_creator_authority,
+            fee_percent: pool_fees.partner_fee_percent,
+            ..Default::default()
+        };
+        partner_info.validate()?;
+
+        config.update_static_config(
+            &pool_fees,
+            vault_config_key,
+            pool_creator_authority,
+            activation_type,
+        );
+    } else {
+        config.set_pool_creator_authority(pool_creator_authority);
+    }
+
+    emit_cpi!(event::EvtUpdateConfig {
+        config: ctx.accounts.config.key(),
+        pool_fees,
+        vault_config_key,
+        pool_creator_authority,
+        activation_type,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, FeeDiscountRegistry, ProgramAuthority},
+    EvtUpdateFeeDiscountRegistry, FeeDiscountTierParameters, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeeDiscountRegistryCtx<'info> {
+    #[account(mut)]
+    pub fee_discount_registry: AccountLoader<'info, FeeDiscountRegistry>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Replace tiers of fee discount registry, empty tiers disable the discount
+pub fn handle_update_fee_discount_registry(
+    ctx: Context<UpdateFeeDiscountRegistryCtx>,
+    tiers: Vec<FeeDiscountTierParameters>,
+) -> Result<()> {
+    let mut fee_discount_registry = ctx.accounts.fee_discount_registry.load_mut()?;
+    fee_discount_registry
+        .update_tiers(&FeeDiscountTierParameters::to_fee_discount_tiers(&tiers))?;
+
+    emit_cpi!(EvtUpdateFeeDiscountRegistry {
+        pool: fee_discount_registry.pool,
+        fee_discount_registry: ctx.accounts.fee_discount_registry.key(),
+        mint: fee_discount_registry.mint,
+        tiers,
+    });
+
+    Ok(())
+}
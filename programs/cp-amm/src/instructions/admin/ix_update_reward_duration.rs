@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    assert_eq_admin,
-    constants::{MAX_REWARD_DURATION, MIN_REWARD_DURATION, NUM_REWARDS},
-    state::Pool,
+    constants::{seeds::PROGRAM_AUTHORITY_PREFIX, MAX_REWARD_DURATION, MIN_REWARD_DURATION},
+    load_reward_extension_for_index,
+    state::{
+        get_reward_info_mut, validate_emission_schedule, AuthorityRole, Pool, ProgramAuthority,
+        RewardInfo,
+    },
     EvtUpdateRewardDuration, PoolError,
 };
 
@@ -13,26 +16,31 @@ pub struct UpdateRewardDurationCtx<'info> {
     #[account(mut)]
     pub pool: AccountLoader<'info, Pool>,
 
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
     #[account(
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = program_authority.load()?.has_role(AuthorityRole::RewardAdmin, admin.key()) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 }
 
 impl<'info> UpdateRewardDurationCtx<'info> {
-    fn validate(&self, reward_index: usize, new_reward_duration: u64) -> Result<()> {
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
-
+    fn validate(&self, reward_info: &RewardInfo, new_reward_duration: u64) -> Result<()> {
         require!(
             new_reward_duration >= MIN_REWARD_DURATION
                 && new_reward_duration <= MAX_REWARD_DURATION,
             PoolError::InvalidRewardDuration
         );
 
-        let pool = self.pool.load()?;
-        let reward_info = &pool.reward_infos[reward_index];
         require!(reward_info.initialized(), PoolError::RewardInitialized);
 
+        validate_emission_schedule(
+            new_reward_duration,
+            reward_info.emission_period,
+            reward_info.emission_decay_bps,
+        )?;
+
         require!(
             reward_info.reward_duration != new_reward_duration,
             PoolError::IdenticalRewardDuration
@@ -49,8 +57,8 @@ impl<'info> UpdateRewardDurationCtx<'info> {
     }
 }
 
-pub fn handle_update_reward_duration(
-    ctx: Context<UpdateRewardDurationCtx>,
+pub fn handle_update_reward_duration<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateRewardDurationCtx<'info>>,
     reward_index: u8,
     new_reward_duration: u64,
 ) -> Result<()> {
@@ -58,10 +66,25 @@ pub fn handle_update_reward_duration(
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
 
-    ctx.accounts.validate(index, new_reward_duration)?;
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let reward_extension =
+        load_reward_extension_for_index(&mut remaining_accounts, ctx.accounts.pool.key(), index)?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
-    let reward_info = &mut pool.reward_infos[index];
+    pool.increment_sequence();
+    let mut reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| reward_extension.load_mut())
+        .transpose()?;
+    // accrue rewards until now, so emission before the update is not priced by the new schedule
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    pool.update_rewards(current_time)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+    }
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
+
+    ctx.accounts.validate(reward_info, new_reward_duration)?;
 
     let old_reward_duration = reward_info.reward_duration;
     reward_info.reward_duration = new_reward_duration;
@@ -8,17 +8,45 @@ pub mod auth;
 pub use auth::*;
 pub mod ix_create_token_badge;
 pub use ix_create_token_badge::*;
+pub mod ix_close_token_badge;
+pub use ix_close_token_badge::*;
 pub mod ix_claim_protocol_fee;
 pub use ix_claim_protocol_fee::*;
 pub mod ix_set_pool_status;
 pub use ix_set_pool_status::*;
+pub mod ix_set_pool_emergency_mode;
+pub use ix_set_pool_emergency_mode::*;
+pub mod ix_set_config_fee_override_bounds;
+pub use ix_set_config_fee_override_bounds::*;
+pub mod ix_set_config_cpi_guard;
+pub use ix_set_config_cpi_guard::*;
+pub mod ix_set_config_reward_boost;
+pub use ix_set_config_reward_boost::*;
 pub mod ix_create_claim_protocol_fee_operator;
 pub use ix_create_claim_protocol_fee_operator::*;
 pub mod ix_close_claim_protocol_fee_operator;
 pub use ix_close_claim_protocol_fee_operator::*;
-pub mod ix_initialize_reward;
-pub use ix_initialize_reward::*;
-pub mod ix_update_reward_funder;
-pub use ix_update_reward_funder::*;
+pub mod ix_update_claim_fee_operator;
+pub use ix_update_claim_fee_operator::*;
 pub mod ix_update_reward_duration;
 pub use ix_update_reward_duration::*;
+pub mod ix_initialize_reward_extension;
+pub use ix_initialize_reward_extension::*;
+pub mod ix_create_fee_discount_registry;
+pub use ix_create_fee_discount_registry::*;
+pub mod ix_update_fee_discount_registry;
+pub use ix_update_fee_discount_registry::*;
+pub mod ix_initialize_program_authority;
+pub use ix_initialize_program_authority::*;
+pub mod ix_transfer_authority_role;
+pub use ix_transfer_authority_role::*;
+pub mod ix_accept_authority_role;
+pub use ix_accept_authority_role::*;
+pub mod ix_close_pool;
+pub use ix_close_pool::*;
+pub mod ix_update_config;
+pub use ix_update_config::*;
+pub mod ix_initialize_insurance_fund;
+pub use ix_initialize_insurance_fund::*;
+pub mod ix_initialize_buyback_burn;
+pub use ix_initialize_buyback_burn::*;
@@ -12,15 +12,19 @@ use crate::{
             CUSTOMIZABLE_POOL_PREFIX, POOL_AUTHORITY_PREFIX, POSITION_NFT_ACCOUNT_PREFIX,
             POSITION_PREFIX, TOKEN_VAULT_PREFIX,
         },
-        DEFAULT_QUOTE_MINTS, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+        DEFAULT_QUOTE_MINTS, MAX_SQRT_PRICE, MINIMUM_LIQUIDITY, MIN_SQRT_PRICE,
     },
     create_position_nft,
     curve::get_initialize_amounts,
-    params::{activation::ActivationParams, fee_parameters::PoolFeeParameters},
+    params::{
+        activation::ActivationParams,
+        fee_parameters::PoolFeeParameters,
+        swap::{LaunchGuardParameters, PriceCircuitBreakerParameters, SwapRateLimiterParameters},
+    },
     state::{CollectFeeMode, Pool, PoolType, Position},
     token::{
-        calculate_transfer_fee_included_amount, get_token_program_flags, is_supported_mint,
-        is_token_badge_initialized, transfer_from_user,
+        calculate_transfer_fee_included_amount, get_pool_token_flag,
+        is_mint_allowed_by_token_badge, is_supported_mint, transfer_from_user,
     },
     EvtCreatePosition, EvtInitializePool, PoolError,
 };
@@ -47,6 +51,16 @@ pub struct InitializeCustomizablePoolParameters {
     pub collect_fee_mode: u8,
     /// activation point
     pub activation_point: Option<u64>,
+    /// swap is only allowed for wallets in the swap allowlist managed by creator
+    pub permissioned_swap: bool,
+    /// adding liquidity and creating position are only allowed for wallets in the lp allowlist managed by creator
+    pub permissioned_lp: bool,
+    /// cap of buy amount per window after activation point
+    pub swap_rate_limiter: Option<SwapRateLimiterParameters>,
+    /// cap of cumulative buy amount per wallet after activation point
+    pub launch_guard: Option<LaunchGuardParameters>,
+    /// cap of sqrt price change of swaps within a slot
+    pub price_circuit_breaker: Option<PriceCircuitBreakerParameters>,
 }
 
 impl InitializeCustomizablePoolParameters {
@@ -65,7 +79,10 @@ impl InitializeCustomizablePoolParameters {
             PoolError::InvalidPriceRange
         );
 
-        require!(self.liquidity > 0, PoolError::InvalidMinimumLiquidity);
+        require!(
+            self.liquidity > MINIMUM_LIQUIDITY,
+            PoolError::InvalidMinimumLiquidity
+        );
 
         // validate fee
         self.pool_fees.validate()?;
@@ -82,6 +99,16 @@ impl InitializeCustomizablePoolParameters {
             has_alpha_vault: self.has_alpha_vault,
         };
         activation_params.validate()?;
+
+        if let Some(swap_rate_limiter) = &self.swap_rate_limiter {
+            swap_rate_limiter.validate()?;
+        }
+        if let Some(launch_guard) = &self.launch_guard {
+            launch_guard.validate()?;
+        }
+        if let Some(price_circuit_breaker) = &self.price_circuit_breaker {
+            price_circuit_breaker.validate()?;
+        }
         Ok(())
     }
 }
@@ -230,8 +257,8 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
     params.validate()?;
     if !is_supported_mint(&ctx.accounts.token_a_mint)? {
         require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_a_mint.key(),
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.token_a_mint,
                 ctx.remaining_accounts
                     .get(0)
                     .ok_or(PoolError::InvalidTokenBadge)?,
@@ -242,8 +269,8 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
 
     if !is_supported_mint(&ctx.accounts.token_b_mint)? {
         require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_b_mint.key(),
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.token_b_mint,
                 ctx.remaining_accounts
                     .get(1)
                     .ok_or(PoolError::InvalidTokenBadge)?,
@@ -262,6 +289,11 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         activation_type,
         collect_fee_mode,
         has_alpha_vault,
+        permissioned_swap,
+        permissioned_lp,
+        swap_rate_limiter,
+        launch_guard,
+        price_circuit_breaker,
     } = params;
 
     // validate quote token
@@ -281,10 +313,12 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
 
     let mut pool = ctx.accounts.pool.load_init()?;
 
-    let token_a_flag: u8 = get_token_program_flags(&ctx.accounts.token_a_mint).into();
-    let token_b_flag: u8 = get_token_program_flags(&ctx.accounts.token_b_mint).into();
-    let activation_point =
-        activation_point.unwrap_or(ActivationHandler::get_current_point(activation_type)?);
+    let token_a_flag = get_pool_token_flag(&ctx.accounts.token_a_mint)?;
+    let token_b_flag = get_pool_token_flag(&ctx.accounts.token_b_mint)?;
+    let clock = Clock::get()?;
+    let activation_point = activation_point.unwrap_or(
+        ActivationHandler::get_current_point_from_clock(activation_type, &clock)?,
+    );
     let alpha_vault = get_whitelisted_alpha_vault(
         ctx.accounts.payer.key(),
         ctx.accounts.pool.key(),
@@ -310,6 +344,22 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         collect_fee_mode,
         pool_type,
     );
+    pool.creator = ctx.accounts.creator.key();
+    if permissioned_swap {
+        pool.swap_allowlist_authority = ctx.accounts.creator.key();
+    }
+    if permissioned_lp {
+        pool.lp_allowlist_authority = ctx.accounts.creator.key();
+    }
+    if let Some(swap_rate_limiter) = swap_rate_limiter {
+        pool.swap_rate_limiter = swap_rate_limiter.to_swap_rate_limiter();
+    }
+    if let Some(launch_guard) = launch_guard {
+        pool.launch_guard = launch_guard.to_launch_guard();
+    }
+    if let Some(price_circuit_breaker) = price_circuit_breaker {
+        pool.price_circuit_breaker = price_circuit_breaker.to_price_circuit_breaker();
+    }
 
     let mut position = ctx.accounts.position.load_init()?;
     position.initialize(
@@ -318,6 +368,9 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         ctx.accounts.position_nft_mint.key(),
         liquidity,
     )?;
+    // lock minimum liquidity forever
+    position.permanent_lock_liquidity(MINIMUM_LIQUIDITY)?;
+    pool.accumulate_permanent_locked_liquidity(MINIMUM_LIQUIDITY)?;
 
     // create position nft
     drop(position);
@@ -339,10 +392,18 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
     });
 
     // transfer token
-    let mut total_amount_a =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_a_mint, token_a_amount)?.amount;
-    let mut total_amount_b =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_b_mint, token_b_amount)?.amount;
+    let mut total_amount_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_a_mint,
+        token_a_amount,
+        clock.epoch,
+    )?
+    .amount;
+    let mut total_amount_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_b_mint,
+        token_b_amount,
+        clock.epoch,
+    )?
+    .amount;
 
     // require at least 1 lamport to prove onwership of token mints
     total_amount_a = total_amount_a.max(1);
@@ -355,6 +416,7 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        ctx.remaining_accounts,
     )?;
     transfer_from_user(
         &ctx.accounts.payer,
@@ -363,6 +425,7 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtInitializePool {
@@ -7,17 +7,20 @@ use std::cmp::{max, min};
 
 use crate::{
     activation_handler::ActivationHandler,
-    constants::seeds::{
-        POOL_AUTHORITY_PREFIX, POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX,
-        TOKEN_VAULT_PREFIX,
+    constants::{
+        seeds::{
+            POOL_AUTHORITY_PREFIX, POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX,
+            TOKEN_VAULT_PREFIX,
+        },
+        MINIMUM_LIQUIDITY,
     },
     create_position_nft,
     curve::get_initialize_amounts,
     params::activation::ActivationParams,
     state::{Config, ConfigType, Pool, PoolType, Position},
     token::{
-        calculate_transfer_fee_included_amount, get_token_program_flags, is_supported_mint,
-        is_token_badge_initialized, transfer_from_user,
+        calculate_transfer_fee_included_amount, get_pool_token_flag,
+        is_mint_allowed_by_token_badge, is_supported_mint, transfer_from_user,
     },
     EvtCreatePosition, EvtInitializePool, PoolError,
 };
@@ -188,8 +191,8 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
 ) -> Result<()> {
     if !is_supported_mint(&ctx.accounts.token_a_mint)? {
         require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_a_mint.key(),
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.token_a_mint,
                 ctx.remaining_accounts
                     .get(0)
                     .ok_or(PoolError::InvalidTokenBadge)?,
@@ -200,8 +203,8 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
 
     if !is_supported_mint(&ctx.accounts.token_b_mint)? {
         require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_b_mint.key(),
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.token_b_mint,
                 ctx.remaining_accounts
                     .get(1)
                     .ok_or(PoolError::InvalidTokenBadge)?,
@@ -216,7 +219,10 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         activation_point,
     } = params;
 
-    require!(liquidity > 0, PoolError::InvalidMinimumLiquidity);
+    require!(
+        liquidity > MINIMUM_LIQUIDITY,
+        PoolError::InvalidMinimumLiquidity
+    );
 
     // init pool
     let config = ctx.accounts.config.load()?;
@@ -239,9 +245,10 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
     };
     activation_params.validate()?;
 
-    let activation_point = activation_point.unwrap_or(ActivationHandler::get_current_point(
-        config.activation_type,
-    )?);
+    let clock = Clock::get()?;
+    let activation_point = activation_point.unwrap_or(
+        ActivationHandler::get_current_point_from_clock(config.activation_type, &clock)?,
+    );
 
     require!(
         sqrt_price >= config.sqrt_min_price && sqrt_price <= config.sqrt_max_price,
@@ -261,8 +268,8 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
     );
     let mut pool = ctx.accounts.pool.load_init()?;
 
-    let token_a_flag: u8 = get_token_program_flags(&ctx.accounts.token_a_mint).into();
-    let token_b_flag: u8 = get_token_program_flags(&ctx.accounts.token_b_mint).into();
+    let token_a_flag = get_pool_token_flag(&ctx.accounts.token_a_mint)?;
+    let token_b_flag = get_pool_token_flag(&ctx.accounts.token_b_mint)?;
     let pool_type: u8 = PoolType::Permissionless.into();
 
     let alpha_vault = config.get_whitelisted_alpha_vault(ctx.accounts.pool.key());
@@ -285,6 +292,10 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         config.collect_fee_mode,
         pool_type,
     );
+    pool.creator = ctx.accounts.creator.key();
+    pool.cpi_guard_duration = config.cpi_guard_duration;
+    pool.vesting_reward_boost_bps = config.vesting_reward_boost_bps;
+    pool.permanent_lock_reward_boost_bps = config.permanent_lock_reward_boost_bps;
 
     // init position
     let mut position = ctx.accounts.position.load_init()?;
@@ -295,6 +306,9 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         ctx.accounts.position_nft_mint.key(),
         liquidity,
     )?;
+    // lock minimum liquidity forever
+    position.permanent_lock_liquidity(MINIMUM_LIQUIDITY)?;
+    pool.accumulate_permanent_locked_liquidity(MINIMUM_LIQUIDITY)?;
 
     // create position nft
     drop(position);
@@ -316,10 +330,18 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
     });
 
     // transfer token
-    let total_amount_a =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_a_mint, token_a_amount)?.amount;
-    let total_amount_b =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_b_mint, token_b_amount)?.amount;
+    let total_amount_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_a_mint,
+        token_a_amount,
+        clock.epoch,
+    )?
+    .amount;
+    let total_amount_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_b_mint,
+        token_b_amount,
+        clock.epoch,
+    )?
+    .amount;
 
     transfer_from_user(
         &ctx.accounts.payer,
@@ -328,6 +350,7 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        ctx.remaining_accounts,
     )?;
     transfer_from_user(
         &ctx.accounts.payer,
@@ -336,6 +359,7 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtInitializePool {
@@ -6,17 +6,20 @@ use anchor_spl::{
 
 use crate::{
     activation_handler::ActivationHandler,
-    constants::seeds::{
-        POOL_AUTHORITY_PREFIX, POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX,
-        TOKEN_VAULT_PREFIX,
+    constants::{
+        seeds::{
+            POOL_AUTHORITY_PREFIX, POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX,
+            TOKEN_VAULT_PREFIX,
+        },
+        MINIMUM_LIQUIDITY,
     },
     create_position_nft,
     curve::get_initialize_amounts,
     get_whitelisted_alpha_vault,
     state::{Config, ConfigType, Pool, PoolType, Position},
     token::{
-        calculate_transfer_fee_included_amount, get_token_program_flags, is_supported_mint,
-        is_token_badge_initialized, transfer_from_user,
+        calculate_transfer_fee_included_amount, get_pool_token_flag,
+        is_mint_allowed_by_token_badge, is_supported_mint, transfer_from_user,
     },
     validate_quote_token, EvtCreatePosition, EvtInitializePool, PoolError,
 };
@@ -174,8 +177,8 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
     params.validate()?;
     if !is_supported_mint(&ctx.accounts.token_a_mint)? {
         require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_a_mint.key(),
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.token_a_mint,
                 ctx.remaining_accounts
                     .get(0)
                     .ok_or(PoolError::InvalidTokenBadge)?,
@@ -186,8 +189,8 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
 
     if !is_supported_mint(&ctx.accounts.token_b_mint)? {
         require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_b_mint.key(),
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.token_b_mint,
                 ctx.remaining_accounts
                     .get(1)
                     .ok_or(PoolError::InvalidTokenBadge)?,
@@ -206,6 +209,11 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         activation_type,
         collect_fee_mode,
         has_alpha_vault,
+        permissioned_swap,
+        permissioned_lp,
+        swap_rate_limiter,
+        launch_guard,
+        price_circuit_breaker,
     } = params;
 
     // init pool
@@ -233,10 +241,12 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
 
     let mut pool = ctx.accounts.pool.load_init()?;
 
-    let token_a_flag: u8 = get_token_program_flags(&ctx.accounts.token_a_mint).into();
-    let token_b_flag: u8 = get_token_program_flags(&ctx.accounts.token_b_mint).into();
-    let activation_point =
-        activation_point.unwrap_or(ActivationHandler::get_current_point(activation_type)?);
+    let token_a_flag = get_pool_token_flag(&ctx.accounts.token_a_mint)?;
+    let token_b_flag = get_pool_token_flag(&ctx.accounts.token_b_mint)?;
+    let clock = Clock::get()?;
+    let activation_point = activation_point.unwrap_or(
+        ActivationHandler::get_current_point_from_clock(activation_type, &clock)?,
+    );
     let alpha_vault = get_whitelisted_alpha_vault(
         ctx.accounts.payer.key(),
         ctx.accounts.pool.key(),
@@ -262,6 +272,25 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         collect_fee_mode,
         pool_type,
     );
+    pool.creator = ctx.accounts.creator.key();
+    if permissioned_swap {
+        pool.swap_allowlist_authority = ctx.accounts.creator.key();
+    }
+    if permissioned_lp {
+        pool.lp_allowlist_authority = ctx.accounts.creator.key();
+    }
+    if let Some(swap_rate_limiter) = swap_rate_limiter {
+        pool.swap_rate_limiter = swap_rate_limiter.to_swap_rate_limiter();
+    }
+    if let Some(launch_guard) = launch_guard {
+        pool.launch_guard = launch_guard.to_launch_guard();
+    }
+    if let Some(price_circuit_breaker) = price_circuit_breaker {
+        pool.price_circuit_breaker = price_circuit_breaker.to_price_circuit_breaker();
+    }
+    pool.cpi_guard_duration = config.cpi_guard_duration;
+    pool.vesting_reward_boost_bps = config.vesting_reward_boost_bps;
+    pool.permanent_lock_reward_boost_bps = config.permanent_lock_reward_boost_bps;
 
     let mut position = ctx.accounts.position.load_init()?;
     position.initialize(
@@ -270,6 +299,9 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         ctx.accounts.position_nft_mint.key(),
         liquidity,
     )?;
+    // lock minimum liquidity forever
+    position.permanent_lock_liquidity(MINIMUM_LIQUIDITY)?;
+    pool.accumulate_permanent_locked_liquidity(MINIMUM_LIQUIDITY)?;
 
     // create position nft
     drop(position);
@@ -291,10 +323,18 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
     });
 
     // transfer token
-    let total_amount_a =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_a_mint, token_a_amount)?.amount;
-    let total_amount_b =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_b_mint, token_b_amount)?.amount;
+    let total_amount_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_a_mint,
+        token_a_amount,
+        clock.epoch,
+    )?
+    .amount;
+    let total_amount_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_b_mint,
+        token_b_amount,
+        clock.epoch,
+    )?
+    .amount;
 
     transfer_from_user(
         &ctx.accounts.payer,
@@ -303,6 +343,7 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        ctx.remaining_accounts,
     )?;
     transfer_from_user(
         &ctx.accounts.payer,
@@ -311,6 +352,7 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtInitializePool {
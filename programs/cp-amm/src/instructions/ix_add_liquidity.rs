@@ -3,10 +3,13 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     get_pool_access_validator,
-    state::{ModifyLiquidityResult, Pool, Position},
-    token::{calculate_transfer_fee_included_amount, transfer_from_user},
+    state::{LpAllowlistEntry, ModifyLiquidityResult, Pool, Position},
+    token::{
+        calculate_transfer_fee_included_amount_cached, require_vaults_not_frozen,
+        transfer_from_user,
+    },
     u128x128_math::Rounding,
-    EvtAddLiquidity, PoolError,
+    update_position_extension_rewards, EvtAddLiquidity, PoolActionAccess, PoolError,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -69,10 +72,14 @@ pub struct AddLiquidityCtx<'info> {
 
     /// Token b program
     pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// lp allowlist entry of owner, required for permissioned lp pool
+    #[account(has_one = pool)]
+    pub lp_allowlist_entry: Option<AccountLoader<'info, LpAllowlistEntry>>,
 }
 
-pub fn handle_add_liquidity(
-    ctx: Context<AddLiquidityCtx>,
+pub fn handle_add_liquidity<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
     params: AddLiquidityParameters,
 ) -> Result<()> {
     let AddLiquidityParameters {
@@ -84,20 +91,37 @@ pub fn handle_add_liquidity(
 
     {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let lp_allowlist_entry = match &ctx.accounts.lp_allowlist_entry {
+            Some(lp_allowlist_entry) => Some(lp_allowlist_entry.load()?),
+            None => None,
+        };
+        let access_validator =
+            get_pool_access_validator(&pool, None, None, lp_allowlist_entry.as_deref())?;
         require!(
-            access_validator.can_add_liquidity(),
+            access_validator.can_add_liquidity(&ctx.accounts.owner.key()),
             PoolError::PoolDisabled
         );
     }
+    require_vaults_not_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_b_vault)?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
 
     let mut position = ctx.accounts.position.load_mut()?;
 
     // update current pool reward & postion reward before any logic
-    let current_time = Clock::get()?.unix_timestamp as u64;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
     position.update_rewards(&mut pool, current_time)?;
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    update_position_extension_rewards(
+        &mut remaining_accounts,
+        ctx.accounts.pool.key(),
+        &pool,
+        ctx.accounts.position.key(),
+        &position,
+        current_time,
+    )?;
 
     let ModifyLiquidityResult {
         token_a_amount,
@@ -111,10 +135,20 @@ pub fn handle_add_liquidity(
 
     pool.apply_add_liquidity(&mut position, liquidity_delta)?;
 
-    let total_amount_a =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_a_mint, token_a_amount)?.amount;
-    let total_amount_b =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_b_mint, token_b_amount)?.amount;
+    let total_amount_a = calculate_transfer_fee_included_amount_cached(
+        &ctx.accounts.token_a_mint,
+        &mut pool.transfer_fee_cache_a,
+        token_a_amount,
+        clock.epoch,
+    )?
+    .amount;
+    let total_amount_b = calculate_transfer_fee_included_amount_cached(
+        &ctx.accounts.token_b_mint,
+        &mut pool.transfer_fee_cache_b,
+        token_b_amount,
+        clock.epoch,
+    )?
+    .amount;
 
     require!(
         total_amount_a <= token_a_amount_threshold,
@@ -132,6 +166,7 @@ pub fn handle_add_liquidity(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        ctx.remaining_accounts,
     )?;
 
     transfer_from_user(
@@ -141,6 +176,7 @@ pub fn handle_add_liquidity(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtAddLiquidity {
@@ -152,6 +188,7 @@ pub fn handle_add_liquidity(
         token_b_amount,
         total_amount_a,
         total_amount_b,
+        sequence,
     });
 
     Ok(())
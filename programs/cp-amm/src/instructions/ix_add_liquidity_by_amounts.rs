@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    token::calculate_transfer_fee_excluded_amount_cached, AddLiquidityCtx, AddLiquidityParameters,
+    PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddLiquidityByAmountsParameters {
+    /// maximum token a amount, including transfer fee
+    pub token_a_amount: u64,
+    /// maximum token b amount, including transfer fee
+    pub token_b_amount: u64,
+    /// minimum delta liquidity
+    pub liquidity_delta_threshold: u128,
+}
+
+pub fn handle_add_liquidity_by_amounts<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
+    params: AddLiquidityByAmountsParameters,
+) -> Result<()> {
+    let AddLiquidityByAmountsParameters {
+        token_a_amount,
+        token_b_amount,
+        liquidity_delta_threshold,
+    } = params;
+
+    let current_epoch = Clock::get()?.epoch;
+    let liquidity_delta = {
+        let mut pool = ctx.accounts.pool.load_mut()?;
+        let excluded_amount_a = calculate_transfer_fee_excluded_amount_cached(
+            &ctx.accounts.token_a_mint,
+            &mut pool.transfer_fee_cache_a,
+            token_a_amount,
+            current_epoch,
+        )?
+        .amount;
+        let excluded_amount_b = calculate_transfer_fee_excluded_amount_cached(
+            &ctx.accounts.token_b_mint,
+            &mut pool.transfer_fee_cache_b,
+            token_b_amount,
+            current_epoch,
+        )?
+        .amount;
+        pool.get_liquidity_delta_for_amounts(excluded_amount_a, excluded_amount_b)?
+    };
+
+    require!(liquidity_delta > 0, PoolError::AmountIsZero);
+    require!(
+        liquidity_delta >= liquidity_delta_threshold,
+        PoolError::ExceededSlippage
+    );
+
+    crate::handle_add_liquidity(
+        ctx,
+        AddLiquidityParameters {
+            liquidity_delta,
+            token_a_amount_threshold: token_a_amount,
+            token_b_amount_threshold: token_b_amount,
+        },
+    )
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    activation_handler::ActivationHandler,
+    constants::seeds::{BUYBACK_BURN_PREFIX, POOL_AUTHORITY_PREFIX},
+    get_pool_access_validator,
+    params::swap::TradeDirection,
+    state::{fee::FeeMode, BuybackBurn, Pool},
+    token::require_vaults_not_frozen,
+    EvtBuybackAndBurn, PoolActionAccess, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuybackAndBurnCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = token_a_vault, has_one = token_b_vault, has_one = token_a_mint)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_BURN_PREFIX.as_ref(), pool.key().as_ref()],
+        bump,
+    )]
+    pub buyback_burn: AccountLoader<'info, BuybackBurn>,
+
+    /// The vault token account for token a, bought token a is burnt from it
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token b
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    #[account(mut)]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+}
+
+/// Spend protocol fee in token b to buy token a through the pool and burn it. Permissionless.
+/// Spent token b stays in its vault as pool reserve, bought token a is burnt from its vault
+pub fn handle_buyback_and_burn(ctx: Context<BuybackAndBurnCtx>) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    require!(pool.is_buyback_burn_enabled(), PoolError::InvalidParameters);
+    {
+        let access_validator = get_pool_access_validator(&pool, None, None, None)?;
+        require!(
+            access_validator.can_swap(&ctx.accounts.pool_authority.key()),
+            PoolError::PoolDisabled
+        );
+    }
+    require_vaults_not_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_b_vault)?;
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let mut buyback_burn = ctx.accounts.buyback_burn.load_mut()?;
+    buyback_burn.validate_buyback(current_time)?;
+
+    let amount_in = pool.take_buyback_amount(buyback_burn.max_amount_per_buyback)?;
+    require!(amount_in > 0, PoolError::AmountIsZero);
+
+    pool.update_pre_swap(current_time)?;
+
+    let trade_direction = TradeDirection::BtoA;
+    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
+    let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, false)?;
+    let swap_result = pool.get_swap_result(amount_in, fee_mode, trade_direction, current_point)?;
+    let sqrt_price = pool.sqrt_price;
+    pool.price_circuit_breaker.check_price_change(
+        Clock::get()?.slot,
+        sqrt_price,
+        swap_result.next_sqrt_price,
+    )?;
+    pool.apply_swap_result(
+        amount_in,
+        &swap_result,
+        fee_mode,
+        trade_direction,
+        current_time,
+    )?;
+
+    let burnt_amount = swap_result.output_amount;
+    buyback_burn.record_buyback(amount_in, burnt_amount, current_time)?;
+
+    let signer_seeds = pool_authority_seeds!(ctx.bumps.pool_authority);
+    burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_a_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_a_mint.to_account_info(),
+                from: ctx.accounts.token_a_vault.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&signer_seeds[..]],
+        ),
+        burnt_amount,
+    )?;
+
+    emit_cpi!(EvtBuybackAndBurn {
+        pool: ctx.accounts.pool.key(),
+        buyback_burn: ctx.accounts.buyback_burn.key(),
+        spent_amount: amount_in,
+        burnt_amount,
+        swap_result,
+        current_timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, Pool, ProgramAuthority},
+    EvtCancelPendingUpdate, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelPendingUpdateCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidDynamicFeeAuthority
+    )]
+    pub signer: Signer<'info>,
+}
+
+/// Cancel the queued dynamic fee update and base fee override of a pool before they take effect
+pub fn handle_cancel_pending_update(ctx: Context<CancelPendingUpdateCtx>) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    // matured updates already took effect
+    pool.apply_pending_fee_updates(current_timestamp)?;
+    pool.cancel_pending_fee_updates()?;
+
+    emit_cpi!(EvtCancelPendingUpdate {
+        pool: ctx.accounts.pool.key(),
+        signer: ctx.accounts.signer.key(),
+    });
+
+    Ok(())
+}
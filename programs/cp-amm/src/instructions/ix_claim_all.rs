@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use std::collections::BTreeSet;
+
+use crate::{
+    constants::{seeds::POOL_AUTHORITY_PREFIX, NUM_REWARDS},
+    state::{Pool, Position},
+    token::transfer_from_pool,
+    EvtClaimPositionFee, EvtClaimReward, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimAllCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        seeds = [
+            POOL_AUTHORITY_PREFIX.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut, has_one = pool
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The user token a account
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token b account
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            constraint = position.load()?.is_owner_or_operator(position_nft_account.owner, owner.key()) @ PoolError::InvalidPositionOperator
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner or operator of position
+    pub owner: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Accounts to claim a pool reward, passed in remaining accounts for every initialized reward
+#[derive(Accounts)]
+pub struct ClaimRewardRemainingAccount<'info> {
+    /// The vault token account for reward token
+    #[account(mut)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Reward mint
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_all<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimAllCtx<'info>>,
+) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
+
+    // claim position fee, skipped when fee is claimable by fee receipt holder only
+    if !position.has_fee_receipt() {
+        position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+        require!(
+            position.is_valid_fee_receiver(
+                ctx.accounts.token_a_account.owner,
+                ctx.accounts.position_nft_account.owner,
+                ctx.accounts.owner.key(),
+            ) && position.is_valid_fee_receiver(
+                ctx.accounts.token_b_account.owner,
+                ctx.accounts.position_nft_account.owner,
+                ctx.accounts.owner.key(),
+            ),
+            PoolError::InvalidFeeBeneficiary
+        );
+
+        let (fee_a_claimed, fee_b_claimed) = position.claim_fee(u64::MAX, u64::MAX)?;
+        pool.accumulate_lp_fee_claimed(fee_a_claimed, fee_b_claimed)?;
+
+        if fee_a_claimed > 0 {
+            transfer_from_pool(
+                ctx.accounts.pool_authority.to_account_info(),
+                &ctx.accounts.token_a_mint,
+                &ctx.accounts.token_a_vault,
+                &ctx.accounts.token_a_account,
+                &ctx.accounts.token_a_program,
+                fee_a_claimed,
+                ctx.bumps.pool_authority,
+                ctx.remaining_accounts,
+            )?;
+        }
+
+        if fee_b_claimed > 0 {
+            transfer_from_pool(
+                ctx.accounts.pool_authority.to_account_info(),
+                &ctx.accounts.token_b_mint,
+                &ctx.accounts.token_b_vault,
+                &ctx.accounts.token_b_account,
+                &ctx.accounts.token_b_program,
+                fee_b_claimed,
+                ctx.bumps.pool_authority,
+                ctx.remaining_accounts,
+            )?;
+        }
+
+        emit_cpi!(EvtClaimPositionFee {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            owner: ctx.accounts.position_nft_account.owner,
+            fee_a_claimed,
+            fee_b_claimed,
+            sequence,
+        });
+    }
+
+    // claim all initialized rewards
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    for reward_index in 0..NUM_REWARDS {
+        let reward_info = &pool.reward_infos[reward_index];
+        if !reward_info.initialized() {
+            continue;
+        }
+
+        let ClaimRewardRemainingAccount {
+            reward_vault,
+            reward_mint,
+            user_token_account,
+            token_program,
+        } = ClaimRewardRemainingAccount::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &[],
+            &mut ClaimRewardRemainingAccountBumps {},
+            &mut BTreeSet::new(),
+        )?;
+
+        require!(
+            reward_info.vault.eq(&reward_vault.key()),
+            PoolError::InvalidRewardVault
+        );
+        require!(
+            position.is_valid_reward_receiver(
+                user_token_account.owner,
+                ctx.accounts.position_nft_account.owner,
+                ctx.accounts.owner.key(),
+            ),
+            PoolError::InvalidRewardReceiver
+        );
+
+        // rewards with claim vesting stay pending in position, they are claimed into escrow by `claim_reward`
+        if reward_info.has_claim_vesting() {
+            continue;
+        }
+
+        let total_reward = position.claim_reward(reward_index)?;
+
+        if total_reward > 0 {
+            transfer_from_pool(
+                ctx.accounts.pool_authority.to_account_info(),
+                &reward_mint,
+                &reward_vault,
+                &user_token_account,
+                &token_program,
+                total_reward,
+                ctx.bumps.pool_authority,
+                ctx.remaining_accounts,
+            )?;
+        }
+
+        emit_cpi!(EvtClaimReward {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            mint_reward: reward_mint.key(),
+            owner: ctx.accounts.position_nft_account.owner,
+            reward_index: reward_index as u8,
+            total_reward,
+        });
+    }
+
+    Ok(())
+}
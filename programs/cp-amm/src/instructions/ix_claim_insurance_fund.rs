@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::POOL_AUTHORITY_PREFIX,
+    state::{InsuranceFund, Pool},
+    token::transfer_from_pool,
+    EvtClaimInsuranceFund, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimInsuranceFundCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(has_one = token_a_mint, has_one = token_b_mint)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = pool @ PoolError::InvalidInsuranceFund,
+        has_one = token_a_vault @ PoolError::InvalidInsuranceFund,
+        has_one = token_b_vault @ PoolError::InvalidInsuranceFund,
+        has_one = authority @ PoolError::InvalidAdmin,
+    )]
+    pub insurance_fund: AccountLoader<'info, InsuranceFund>,
+
+    /// The insurance vault of token a
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The insurance vault of token b
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The receiver token a account
+    #[account(mut, token::mint = token_a_mint)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The receiver token b account
+    #[account(mut, token::mint = token_b_mint)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Claim authority of insurance fund
+    pub authority: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw from insurance vaults, eg: to cover losses of the pool
+pub fn handle_claim_insurance_fund<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimInsuranceFundCtx<'info>>,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Result<()> {
+    let token_a_amount = ctx.accounts.token_a_vault.amount.min(max_amount_a);
+    let token_b_amount = ctx.accounts.token_b_vault.amount.min(max_amount_b);
+
+    let mut insurance_fund = ctx.accounts.insurance_fund.load_mut()?;
+    insurance_fund.accumulate_claimed_amount(token_a_amount, token_b_amount)?;
+
+    transfer_from_pool(
+        ctx.accounts.pool_authority.to_account_info(),
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.token_a_vault,
+        &ctx.accounts.token_a_account,
+        &ctx.accounts.token_a_program,
+        token_a_amount,
+        ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
+    )?;
+
+    transfer_from_pool(
+        ctx.accounts.pool_authority.to_account_info(),
+        &ctx.accounts.token_b_mint,
+        &ctx.accounts.token_b_vault,
+        &ctx.accounts.token_b_account,
+        &ctx.accounts.token_b_program,
+        token_b_amount,
+        ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
+    )?;
+
+    emit_cpi!(EvtClaimInsuranceFund {
+        pool: ctx.accounts.pool.key(),
+        insurance_fund: ctx.accounts.insurance_fund.key(),
+        token_a_amount,
+        token_b_amount,
+    });
+
+    Ok(())
+}
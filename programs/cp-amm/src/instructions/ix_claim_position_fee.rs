@@ -5,7 +5,7 @@ use crate::{
     constants::seeds::POOL_AUTHORITY_PREFIX,
     state::{Pool, Position},
     token::transfer_from_pool,
-    EvtClaimPositionFee,
+    EvtClaimPositionFee, PoolError,
 };
 
 #[event_cpi]
@@ -21,6 +21,7 @@ pub struct ClaimPositionFeeCtx<'info> {
     pub pool_authority: UncheckedAccount<'info>,
 
     #[account(
+        mut,
         has_one = token_a_mint,
         has_one = token_b_mint,
         has_one = token_a_vault,
@@ -59,11 +60,11 @@ pub struct ClaimPositionFeeCtx<'info> {
     #[account(
             constraint = position_nft_account.mint == position.load()?.nft_mint,
             constraint = position_nft_account.amount == 1,
-            token::authority = owner
+            constraint = position.load()?.is_owner_or_operator(position_nft_account.owner, owner.key()) @ PoolError::InvalidPositionOperator
     )]
     pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// owner of position
+    /// owner or operator of position
     pub owner: Signer<'info>,
 
     /// Token a program
@@ -73,20 +74,40 @@ pub struct ClaimPositionFeeCtx<'info> {
     pub token_b_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handle_claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()> {
+pub fn handle_claim_position_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimPositionFeeCtx<'info>>,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Result<()> {
     let mut position = ctx.accounts.position.load_mut()?;
 
-    let pool = ctx.accounts.pool.load()?;
+    require!(
+        !position.has_fee_receipt(),
+        PoolError::PositionHasFeeReceipt
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
     position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
     // update metrics
 
-    let fee_a_pending = position.fee_a_pending;
-    let fee_b_pending = position.fee_b_pending;
-    position
-        .metrics
-        .accumulate_claimed_fee(fee_a_pending, fee_b_pending)?;
-
-    if fee_a_pending > 0 {
+    require!(
+        position.is_valid_fee_receiver(
+            ctx.accounts.token_a_account.owner,
+            ctx.accounts.position_nft_account.owner,
+            ctx.accounts.owner.key(),
+        ) && position.is_valid_fee_receiver(
+            ctx.accounts.token_b_account.owner,
+            ctx.accounts.position_nft_account.owner,
+            ctx.accounts.owner.key(),
+        ),
+        PoolError::InvalidFeeBeneficiary
+    );
+
+    let (fee_a_claimed, fee_b_claimed) = position.claim_fee(max_amount_a, max_amount_b)?;
+    pool.accumulate_lp_fee_claimed(fee_a_claimed, fee_b_claimed)?;
+
+    if fee_a_claimed > 0 {
         // send to user
         transfer_from_pool(
             ctx.accounts.pool_authority.to_account_info(),
@@ -94,31 +115,32 @@ pub fn handle_claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()
             &ctx.accounts.token_a_vault,
             &ctx.accounts.token_a_account,
             &ctx.accounts.token_a_program,
-            fee_a_pending,
+            fee_a_claimed,
             ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
         )?;
     }
 
-    if fee_b_pending > 0 {
+    if fee_b_claimed > 0 {
         transfer_from_pool(
             ctx.accounts.pool_authority.to_account_info(),
             &ctx.accounts.token_b_mint,
             &ctx.accounts.token_b_vault,
             &ctx.accounts.token_b_account,
             &ctx.accounts.token_b_program,
-            fee_b_pending,
+            fee_b_claimed,
             ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
         )?;
     }
 
-    position.reset_pending_fee();
-
     emit_cpi!(EvtClaimPositionFee {
         pool: ctx.accounts.pool.key(),
         position: ctx.accounts.position.key(),
-        owner: ctx.accounts.owner.key(),
-        fee_a_claimed: fee_a_pending,
-        fee_b_claimed: fee_b_pending,
+        owner: ctx.accounts.position_nft_account.owner,
+        fee_a_claimed,
+        fee_b_claimed,
+        sequence,
     });
 
     Ok(())
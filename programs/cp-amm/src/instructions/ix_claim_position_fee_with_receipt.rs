@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::{FEE_RECEIPT_MINT_PREFIX, POOL_AUTHORITY_PREFIX},
+    state::{Pool, Position},
+    token::transfer_from_pool,
+    EvtClaimPositionFee,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimPositionFeeWithReceiptCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        seeds = [
+            POOL_AUTHORITY_PREFIX.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut, has_one = pool
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The user token a account
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token b account
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// fee receipt mint of position
+    #[account(
+        seeds = [FEE_RECEIPT_MINT_PREFIX.as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub fee_receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account holding fee receipt
+    #[account(
+        token::mint = fee_receipt_mint,
+        token::authority = holder,
+        constraint = fee_receipt_account.amount == 1,
+    )]
+    pub fee_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// holder of fee receipt
+    pub holder: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_position_fee_with_receipt<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimPositionFeeWithReceiptCtx<'info>>,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    let (fee_a_claimed, fee_b_claimed) = position.claim_fee(max_amount_a, max_amount_b)?;
+    pool.accumulate_lp_fee_claimed(fee_a_claimed, fee_b_claimed)?;
+
+    if fee_a_claimed > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            fee_a_claimed,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    if fee_b_claimed > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            fee_b_claimed,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtClaimPositionFee {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.holder.key(),
+        fee_a_claimed,
+        fee_b_claimed,
+        sequence,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::POOL_AUTHORITY_PREFIX,
+    state::{Pool, Referral},
+    token::transfer_from_pool,
+    EvtClaimReferralFee,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralFeeCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool, has_one = referrer)]
+    pub referral: AccountLoader<'info, Referral>,
+
+    /// The referrer token a account
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The referrer token b account
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub referrer: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Referrer claims referral fee accrued in swaps.
+pub fn handle_claim_referral_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimReferralFeeCtx<'info>>,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Result<()> {
+    let mut referral = ctx.accounts.referral.load_mut()?;
+    let (token_a_amount, token_b_amount) = referral.claim_fee(max_amount_a, max_amount_b)?;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
+    pool.claim_referral_fee(token_a_amount, token_b_amount);
+
+    if token_a_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            token_a_amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    if token_b_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            token_b_amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtClaimReferralFee {
+        pool: ctx.accounts.pool.key(),
+        referral: ctx.accounts.referral.key(),
+        referrer: ctx.accounts.referrer.key(),
+        token_a_amount,
+        token_b_amount,
+        sequence,
+    });
+
+    Ok(())
+}
@@ -4,8 +4,11 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
     constants::{seeds::POOL_AUTHORITY_PREFIX, NUM_REWARDS},
     error::PoolError,
-    event::EvtClaimReward,
-    state::{pool::Pool, position::Position},
+    event::{EvtClaimReward, EvtLockReward},
+    load_position_reward_extension, load_reward_extension,
+    state::{
+        get_extension_reward_index, pool::Pool, position::Position, RewardInfo, RewardVestingEscrow,
+    },
     token::transfer_from_pool,
 };
 
@@ -39,22 +42,22 @@ pub struct ClaimRewardCtx<'info> {
     #[account(
             constraint = position_nft_account.mint == position.load()?.nft_mint,
             constraint = position_nft_account.amount == 1,
-            token::authority = owner
+            constraint = position.load()?.is_owner_or_operator(position_nft_account.owner, owner.key()) @ PoolError::InvalidPositionOperator
     )]
     pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// owner of position
+    /// owner or operator of position
     pub owner: Signer<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// Reward vesting escrow of the reward receiver, required by rewards with claim vesting
+    #[account(mut, has_one = pool)]
+    pub reward_vesting_escrow: Option<AccountLoader<'info, RewardVestingEscrow>>,
 }
 
 impl<'info> ClaimRewardCtx<'info> {
-    fn validate(&self, reward_index: usize) -> Result<()> {
-        let pool = self.pool.load()?;
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
-
-        let reward_info = &pool.reward_infos[reward_index];
+    fn validate(&self, reward_info: &RewardInfo) -> Result<()> {
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
         require!(
             reward_info.vault.eq(&self.reward_vault.key()),
@@ -63,27 +66,111 @@ impl<'info> ClaimRewardCtx<'info> {
 
         Ok(())
     }
+
+    /// Lock claimed reward in the reward vesting escrow of the reward receiver,
+    /// returns the escrow and its end time
+    fn lock_reward(
+        &self,
+        reward_index: u8,
+        amount: u64,
+        claim_vesting_duration: u32,
+        current_time: u64,
+    ) -> Result<(Pubkey, u64)> {
+        let reward_vesting_escrow_loader = self
+            .reward_vesting_escrow
+            .as_ref()
+            .ok_or(PoolError::InvalidRewardVestingEscrow)?;
+        let mut reward_vesting_escrow = reward_vesting_escrow_loader.load_mut()?;
+        require!(
+            reward_vesting_escrow.reward_index == reward_index
+                && reward_vesting_escrow.owner == self.user_token_account.owner,
+            PoolError::InvalidRewardVestingEscrow
+        );
+        reward_vesting_escrow.lock(amount, claim_vesting_duration.into(), current_time)?;
+        Ok((
+            reward_vesting_escrow_loader.key(),
+            reward_vesting_escrow.end_time,
+        ))
+    }
 }
 
-pub fn handle_claim_reward(ctx: Context<ClaimRewardCtx>, reward_index: u8) -> Result<()> {
+pub fn handle_claim_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimRewardCtx<'info>>,
+    reward_index: u8,
+) -> Result<()> {
     let index: usize = reward_index
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index)?;
 
     let mut position = ctx.accounts.position.load_mut()?;
 
+    require!(
+        position.is_valid_reward_receiver(
+            ctx.accounts.user_token_account.owner,
+            ctx.accounts.position_nft_account.owner,
+            ctx.accounts.owner.key(),
+        ),
+        PoolError::InvalidRewardReceiver
+    );
+
     let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
     let current_time = Clock::get()?.unix_timestamp as u64;
 
-    // update pool reward & position reward
-    position.update_rewards(&mut pool, current_time)?;
-
-    // get all pending reward
-    let total_reward = position.claim_reward(index)?;
-
-    // transfer rewards to user
-    if total_reward > 0 {
+    let (total_reward, claim_vesting_duration) = if index < NUM_REWARDS {
+        ctx.accounts.validate(&pool.reward_infos[index])?;
+
+        // update pool reward & position reward
+        position.update_rewards(&mut pool, current_time)?;
+
+        // get all pending reward
+        (
+            position.claim_reward(index)?,
+            pool.reward_infos[index].claim_vesting_duration,
+        )
+    } else {
+        let extension_index = get_extension_reward_index(index)?;
+        let mut remaining_accounts = &ctx.remaining_accounts[..];
+        let reward_extension =
+            load_reward_extension(&mut remaining_accounts, ctx.accounts.pool.key())?;
+        let position_reward_extension =
+            load_position_reward_extension(&mut remaining_accounts, ctx.accounts.position.key())?;
+
+        let mut reward_extension = reward_extension.load_mut()?;
+        ctx.accounts
+            .validate(&reward_extension.reward_infos[extension_index])?;
+
+        // update extension reward & position extension reward
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+        let mut position_reward_extension = position_reward_extension.load_mut()?;
+        position_reward_extension
+            .update_rewards(&reward_extension, position.get_reward_liquidity(&pool)?)?;
+
+        // get all pending reward
+        (
+            position_reward_extension.claim_reward(extension_index)?,
+            reward_extension.reward_infos[extension_index].claim_vesting_duration,
+        )
+    };
+
+    if claim_vesting_duration > 0 {
+        // rewards with claim vesting are released linearly by the escrow
+        let (reward_vesting_escrow, end_time) = ctx.accounts.lock_reward(
+            reward_index,
+            total_reward,
+            claim_vesting_duration,
+            current_time,
+        )?;
+        emit_cpi!(EvtLockReward {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            reward_vesting_escrow,
+            reward_index,
+            amount: total_reward,
+            end_time,
+        });
+    } else if total_reward > 0 {
+        // transfer rewards to user
         transfer_from_pool(
             ctx.accounts.pool_authority.to_account_info(),
             &ctx.accounts.reward_mint,
@@ -92,6 +179,7 @@ pub fn handle_claim_reward(ctx: Context<ClaimRewardCtx>, reward_index: u8) -> Re
             &ctx.accounts.token_program,
             total_reward,
             ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
         )?;
     }
 
@@ -99,7 +187,7 @@ pub fn handle_claim_reward(ctx: Context<ClaimRewardCtx>, reward_index: u8) -> Re
         pool: ctx.accounts.pool.key(),
         position: ctx.accounts.position.key(),
         mint_reward: ctx.accounts.reward_mint.key(),
-        owner: ctx.accounts.owner.key(),
+        owner: ctx.accounts.position_nft_account.owner,
         reward_index,
         total_reward,
     });
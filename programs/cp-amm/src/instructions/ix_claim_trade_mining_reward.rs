@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::{POOL_AUTHORITY_PREFIX, TRADER_REWARD_PREFIX},
+    state::{Pool, TradeMining, TraderReward},
+    token::transfer_from_pool,
+    EvtClaimTradeMiningReward, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTradeMiningRewardCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool, has_one = vault @ PoolError::InvalidRewardVault)]
+    pub trade_mining: AccountLoader<'info, TradeMining>,
+
+    #[account(
+        mut,
+        seeds = [
+            TRADER_REWARD_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            trader.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub trader_reward: AccountLoader<'info, TraderReward>,
+
+    /// The vault token account for trade mining reward
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = vault.mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub trader: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim trade mining rewards accrued by swaps of trader
+pub fn handle_claim_trade_mining_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimTradeMiningRewardCtx<'info>>,
+) -> Result<()> {
+    let amount = ctx.accounts.trader_reward.load_mut()?.claim();
+    ctx.accounts
+        .trade_mining
+        .load_mut()?
+        .accumulate_claimed_amount(amount);
+
+    if amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtClaimTradeMiningReward {
+        pool: ctx.accounts.pool.key(),
+        trader_reward: ctx.accounts.trader_reward.key(),
+        trader: ctx.accounts.trader.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::{POOL_AUTHORITY_PREFIX, REWARD_VAULT_PREFIX, REWARD_VESTING_ESCROW_PREFIX},
+    state::{Pool, RewardVestingEscrow},
+    token::transfer_from_pool,
+    EvtClaimVestedReward,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct ClaimVestedRewardCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            REWARD_VESTING_ESCROW_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            reward_index.to_le_bytes().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_vesting_escrow: AccountLoader<'info, RewardVestingEscrow>,
+
+    /// The vault token account for reward token
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_PREFIX.as_ref(), pool.key().as_ref(), reward_index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = reward_vault.mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of reward vesting escrow
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim rewards released by the reward vesting escrow of owner
+pub fn handle_claim_vested_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimVestedRewardCtx<'info>>,
+    reward_index: u8,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let amount = ctx
+        .accounts
+        .reward_vesting_escrow
+        .load_mut()?
+        .claim(current_time)?;
+
+    if amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtClaimVestedReward {
+        pool: ctx.accounts.pool.key(),
+        reward_vesting_escrow: ctx.accounts.reward_vesting_escrow.key(),
+        owner: ctx.accounts.owner.key(),
+        reward_index,
+        amount,
+    });
+
+    Ok(())
+}
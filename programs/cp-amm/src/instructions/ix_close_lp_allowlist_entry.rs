@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{LpAllowlistEntry, Pool},
+    EvtCloseLpAllowlistEntry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseLpAllowlistEntryCtx<'info> {
+    #[account(has_one = lp_allowlist_authority @ PoolError::InvalidLpAllowlistAuthority)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        close = rent_receiver
+    )]
+    pub lp_allowlist_entry: AccountLoader<'info, LpAllowlistEntry>,
+
+    pub lp_allowlist_authority: Signer<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Revoke a wallet from adding liquidity in a permissioned lp pool, existing positions can still be withdrawn
+pub fn handle_close_lp_allowlist_entry(ctx: Context<CloseLpAllowlistEntryCtx>) -> Result<()> {
+    let lp_allowlist_entry = ctx.accounts.lp_allowlist_entry.load()?;
+
+    emit_cpi!(EvtCloseLpAllowlistEntry {
+        pool: ctx.accounts.pool.key(),
+        lp_allowlist_entry: ctx.accounts.lp_allowlist_entry.key(),
+        wallet: lp_allowlist_entry.wallet,
+    });
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use anchor_spl::{
 
 use crate::{
     constants::seeds::POOL_AUTHORITY_PREFIX,
+    load_position_reward_extension,
     state::{Pool, Position},
     EvtClosePosition, PoolError,
 };
@@ -51,10 +52,28 @@ pub struct ClosePositionCtx<'info> {
     pub token_program: Program<'info, Token2022>,
 }
 
-pub fn handle_close_position(ctx: Context<ClosePositionCtx>) -> Result<()> {
+pub fn handle_close_position<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClosePositionCtx<'info>>,
+) -> Result<()> {
     let position = ctx.accounts.position.load()?;
     require!(position.is_empty()?, PoolError::PositionIsNotEmpty);
 
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    pool.metrics.rec_position()?;
+
+    // close position reward extension if pool has reward extension
+    if pool.has_reward_extension() {
+        let mut remaining_accounts = &ctx.remaining_accounts[..];
+        let position_reward_extension =
+            load_position_reward_extension(&mut remaining_accounts, ctx.accounts.position.key())?;
+        require!(
+            position_reward_extension.load()?.is_empty(),
+            PoolError::PositionIsNotEmpty
+        );
+        position_reward_extension.close(ctx.accounts.rent_receiver.to_account_info())?;
+    }
+
     // burn
     token_2022::burn(
         CpiContext::new(
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Pool, SwapAllowlistEntry},
+    EvtCloseSwapAllowlistEntry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseSwapAllowlistEntryCtx<'info> {
+    #[account(has_one = swap_allowlist_authority @ PoolError::InvalidSwapAllowlistAuthority)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        close = rent_receiver
+    )]
+    pub swap_allowlist_entry: AccountLoader<'info, SwapAllowlistEntry>,
+
+    pub swap_allowlist_authority: Signer<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Revoke a wallet from swapping in a permissioned pool
+pub fn handle_close_swap_allowlist_entry(ctx: Context<CloseSwapAllowlistEntryCtx>) -> Result<()> {
+    let swap_allowlist_entry = ctx.accounts.swap_allowlist_entry.load()?;
+
+    emit_cpi!(EvtCloseSwapAllowlistEntry {
+        pool: ctx.accounts.pool.key(),
+        swap_allowlist_entry: ctx.accounts.swap_allowlist_entry.key(),
+        wallet: swap_allowlist_entry.wallet,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::LAUNCH_BUY_TRACKER_PREFIX,
+    state::{LaunchBuyTracker, Pool},
+    EvtCreateLaunchBuyTracker,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateLaunchBuyTrackerCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            LAUNCH_BUY_TRACKER_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump,
+        space = 8 + LaunchBuyTracker::INIT_SPACE
+    )]
+    pub launch_buy_tracker: AccountLoader<'info, LaunchBuyTracker>,
+
+    /// CHECK: wallet buying in the pool
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_launch_buy_tracker(ctx: Context<CreateLaunchBuyTrackerCtx>) -> Result<()> {
+    let mut launch_buy_tracker = ctx.accounts.launch_buy_tracker.load_init()?;
+    launch_buy_tracker.initialize(ctx.accounts.pool.key(), ctx.accounts.wallet.key());
+
+    emit_cpi!(EvtCreateLaunchBuyTracker {
+        pool: ctx.accounts.pool.key(),
+        launch_buy_tracker: ctx.accounts.launch_buy_tracker.key(),
+        wallet: ctx.accounts.wallet.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::LP_ALLOWLIST_PREFIX,
+    state::{LpAllowlistEntry, Pool},
+    EvtCreateLpAllowlistEntry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateLpAllowlistEntryCtx<'info> {
+    #[account(has_one = lp_allowlist_authority @ PoolError::InvalidLpAllowlistAuthority)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            LP_ALLOWLIST_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump,
+        space = 8 + LpAllowlistEntry::INIT_SPACE
+    )]
+    pub lp_allowlist_entry: AccountLoader<'info, LpAllowlistEntry>,
+
+    /// CHECK: wallet allowed to add liquidity
+    pub wallet: UncheckedAccount<'info>,
+
+    pub lp_allowlist_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow a wallet to add liquidity and create position in a permissioned lp pool
+pub fn handle_create_lp_allowlist_entry(ctx: Context<CreateLpAllowlistEntryCtx>) -> Result<()> {
+    let mut lp_allowlist_entry = ctx.accounts.lp_allowlist_entry.load_init()?;
+    lp_allowlist_entry.initialize(ctx.accounts.pool.key(), ctx.accounts.wallet.key());
+
+    emit_cpi!(EvtCreateLpAllowlistEntry {
+        pool: ctx.accounts.pool.key(),
+        lp_allowlist_entry: ctx.accounts.lp_allowlist_entry.key(),
+        wallet: ctx.accounts.wallet.key(),
+    });
+
+    Ok(())
+}
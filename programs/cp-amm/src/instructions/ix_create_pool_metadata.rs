@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::{POOL_METADATA_PREFIX, PROGRAM_AUTHORITY_PREFIX},
+    state::{AuthorityRole, Pool, PoolMetadata, ProgramAuthority},
+    EvtCreatePoolMetadata, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PoolMetadataParameters {
+    /// display name, required
+    pub name: String,
+    /// display symbol
+    pub symbol: String,
+    /// logo uri
+    pub uri: String,
+    /// project url
+    pub project_url: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePoolMetadataCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [
+            POOL_METADATA_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + PoolMetadata::INIT_SPACE
+    )]
+    pub pool_metadata: AccountLoader<'info, PoolMetadata>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidPoolMetadataAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create display metadata of a pool for explorers and frontends
+pub fn handle_create_pool_metadata(
+    ctx: Context<CreatePoolMetadataCtx>,
+    params: PoolMetadataParameters,
+) -> Result<()> {
+    let PoolMetadataParameters {
+        name,
+        symbol,
+        uri,
+        project_url,
+    } = params;
+
+    let mut pool_metadata = ctx.accounts.pool_metadata.load_init()?;
+    pool_metadata.initialize(ctx.accounts.pool.key(), &name, &symbol, &uri, &project_url)?;
+
+    emit_cpi!(EvtCreatePoolMetadata {
+        pool: ctx.accounts.pool.key(),
+        pool_metadata: ctx.accounts.pool_metadata.key(),
+        signer: ctx.accounts.signer.key(),
+        name,
+        symbol,
+        uri,
+        project_url,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::POOL_STATS_PREFIX,
+    state::{Pool, PoolStats},
+    EvtCreatePoolStats, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePoolStatsCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            POOL_STATS_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + PoolStats::INIT_SPACE
+    )]
+    pub pool_stats: AccountLoader<'info, PoolStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_pool_stats(ctx: Context<CreatePoolStatsCtx>) -> Result<()> {
+    let mut pool_stats = ctx.accounts.pool_stats.load_init()?;
+    pool_stats.initialize(ctx.accounts.pool.key());
+
+    emit_cpi!(EvtCreatePoolStats {
+        pool: ctx.accounts.pool.key(),
+        pool_stats: ctx.accounts.pool_stats.key(),
+    });
+
+    Ok(())
+}
+
+/// Find pool stats of the pool in remaining accounts, other remaining accounts such as
+/// extra accounts of transfer hooks are skipped
+pub fn load_pool_stats<'c: 'info, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    pool: Pubkey,
+) -> Result<Option<AccountLoader<'info, PoolStats>>> {
+    for account in remaining_accounts {
+        if account.owner != &crate::ID
+            || !account
+                .try_borrow_data()?
+                .starts_with(PoolStats::DISCRIMINATOR)
+        {
+            continue;
+        }
+        let pool_stats = AccountLoader::<PoolStats>::try_from(account)?;
+        require!(pool_stats.load()?.pool == pool, PoolError::InvalidPoolStats);
+        return Ok(Some(pool_stats));
+    }
+    Ok(None)
+}
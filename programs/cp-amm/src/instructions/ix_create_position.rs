@@ -7,9 +7,9 @@ use anchor_spl::{
 use crate::{
     constants::seeds::{POOL_AUTHORITY_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX},
     get_pool_access_validator,
-    state::{Pool, Position},
+    state::{LpAllowlistEntry, Pool, Position},
     token::update_account_lamports_to_minimum_balance,
-    EvtCreatePosition, PoolError,
+    EvtCreatePosition, PoolActionAccess, PoolError,
 };
 
 #[event_cpi]
@@ -72,14 +72,23 @@ pub struct CreatePositionCtx<'info> {
     pub token_program: Program<'info, Token2022>,
 
     pub system_program: Program<'info, System>,
+
+    /// lp allowlist entry of payer, required for permissioned lp pool
+    #[account(has_one = pool)]
+    pub lp_allowlist_entry: Option<AccountLoader<'info, LpAllowlistEntry>>,
 }
 
 pub fn handle_create_position(ctx: Context<CreatePositionCtx>) -> Result<()> {
     {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let lp_allowlist_entry = match &ctx.accounts.lp_allowlist_entry {
+            Some(lp_allowlist_entry) => Some(lp_allowlist_entry.load()?),
+            None => None,
+        };
+        let access_validator =
+            get_pool_access_validator(&pool, None, None, lp_allowlist_entry.as_deref())?;
         require!(
-            access_validator.can_create_position(),
+            access_validator.can_create_position(&ctx.accounts.payer.key()),
             PoolError::PoolDisabled
         );
     }
@@ -87,6 +96,7 @@ pub fn handle_create_position(ctx: Context<CreatePositionCtx>) -> Result<()> {
     // init position
     let mut position = ctx.accounts.position.load_init()?;
     let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
 
     let liquidity = 0;
 
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::POSITION_REWARD_EXTENSION_PREFIX,
+    event::EvtCreatePositionRewardExtension,
+    state::{Position, PositionRewardExtension},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePositionRewardExtensionCtx<'info> {
+    pub position: AccountLoader<'info, Position>,
+
+    #[account(
+        init,
+        seeds = [POSITION_REWARD_EXTENSION_PREFIX.as_ref(), position.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + PositionRewardExtension::INIT_SPACE
+    )]
+    pub position_reward_extension: AccountLoader<'info, PositionRewardExtension>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_position_reward_extension(
+    ctx: Context<CreatePositionRewardExtensionCtx>,
+) -> Result<()> {
+    let mut position_reward_extension = ctx.accounts.position_reward_extension.load_init()?;
+    position_reward_extension.initialize(ctx.accounts.position.key());
+
+    emit_cpi!(EvtCreatePositionRewardExtension {
+        pool: ctx.accounts.position.load()?.pool,
+        position: ctx.accounts.position.key(),
+        position_reward_extension: ctx.accounts.position_reward_extension.key(),
+    });
+
+    Ok(())
+}
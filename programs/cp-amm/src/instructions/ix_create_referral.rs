@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::REFERRAL_PREFIX,
+    state::{Pool, Referral},
+    EvtCreateReferral,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferralCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            REFERRAL_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump,
+        space = 8 + Referral::INIT_SPACE
+    )]
+    pub referral: AccountLoader<'info, Referral>,
+
+    /// CHECK: referrer
+    pub referrer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_referral(ctx: Context<CreateReferralCtx>) -> Result<()> {
+    let mut referral = ctx.accounts.referral.load_init()?;
+    referral.initialize(ctx.accounts.pool.key(), ctx.accounts.referrer.key());
+
+    emit_cpi!(EvtCreateReferral {
+        pool: ctx.accounts.pool.key(),
+        referral: ctx.accounts.referral.key(),
+        referrer: ctx.accounts.referrer.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{seeds::REWARD_VESTING_ESCROW_PREFIX, NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    state::{Pool, RewardVestingEscrow},
+    EvtCreateRewardVestingEscrow, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct CreateRewardVestingEscrowCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            REWARD_VESTING_ESCROW_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            reward_index.to_le_bytes().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        space = 8 + RewardVestingEscrow::INIT_SPACE
+    )]
+    pub reward_vesting_escrow: AccountLoader<'info, RewardVestingEscrow>,
+
+    /// CHECK: owner receiving the vested rewards
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_reward_vesting_escrow(
+    ctx: Context<CreateRewardVestingEscrowCtx>,
+    reward_index: u8,
+) -> Result<()> {
+    require!(
+        usize::from(reward_index) < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+        PoolError::InvalidRewardIndex
+    );
+
+    let mut reward_vesting_escrow = ctx.accounts.reward_vesting_escrow.load_init()?;
+    reward_vesting_escrow.initialize(
+        ctx.accounts.pool.key(),
+        ctx.accounts.owner.key(),
+        reward_index,
+    );
+
+    emit_cpi!(EvtCreateRewardVestingEscrow {
+        pool: ctx.accounts.pool.key(),
+        reward_vesting_escrow: ctx.accounts.reward_vesting_escrow.key(),
+        owner: ctx.accounts.owner.key(),
+        reward_index,
+    });
+
+    Ok(())
+}
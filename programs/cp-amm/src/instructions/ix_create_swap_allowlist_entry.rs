@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::SWAP_ALLOWLIST_PREFIX,
+    state::{Pool, SwapAllowlistEntry},
+    EvtCreateSwapAllowlistEntry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSwapAllowlistEntryCtx<'info> {
+    #[account(has_one = swap_allowlist_authority @ PoolError::InvalidSwapAllowlistAuthority)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            SWAP_ALLOWLIST_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump,
+        space = 8 + SwapAllowlistEntry::INIT_SPACE
+    )]
+    pub swap_allowlist_entry: AccountLoader<'info, SwapAllowlistEntry>,
+
+    /// CHECK: wallet allowed to swap
+    pub wallet: UncheckedAccount<'info>,
+
+    pub swap_allowlist_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow a wallet to swap in a permissioned pool
+pub fn handle_create_swap_allowlist_entry(ctx: Context<CreateSwapAllowlistEntryCtx>) -> Result<()> {
+    let mut swap_allowlist_entry = ctx.accounts.swap_allowlist_entry.load_init()?;
+    swap_allowlist_entry.initialize(ctx.accounts.pool.key(), ctx.accounts.wallet.key());
+
+    emit_cpi!(EvtCreateSwapAllowlistEntry {
+        pool: ctx.accounts.pool.key(),
+        swap_allowlist_entry: ctx.accounts.swap_allowlist_entry.key(),
+        wallet: ctx.accounts.wallet.key(),
+    });
+
+    Ok(())
+}
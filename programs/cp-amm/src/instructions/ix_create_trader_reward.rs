@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::TRADER_REWARD_PREFIX,
+    state::{Pool, TradeMining, TraderReward},
+    EvtCreateTraderReward, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateTraderRewardCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            TRADER_REWARD_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            trader.key().as_ref(),
+        ],
+        bump,
+        space = 8 + TraderReward::INIT_SPACE
+    )]
+    pub trader_reward: AccountLoader<'info, TraderReward>,
+
+    /// CHECK: trader accruing trade mining rewards
+    pub trader: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_trader_reward(ctx: Context<CreateTraderRewardCtx>) -> Result<()> {
+    let mut trader_reward = ctx.accounts.trader_reward.load_init()?;
+    trader_reward.initialize(ctx.accounts.pool.key(), ctx.accounts.trader.key());
+
+    emit_cpi!(EvtCreateTraderReward {
+        pool: ctx.accounts.pool.key(),
+        trader_reward: ctx.accounts.trader_reward.key(),
+        trader: ctx.accounts.trader.key(),
+    });
+
+    Ok(())
+}
+
+/// Find trade mining of the pool and trader reward of the trader in remaining accounts, both are required
+/// to accrue trade mining rewards. Other remaining accounts such as extra accounts of transfer hooks are skipped
+pub fn load_trade_mining<'c: 'info, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    pool: Pubkey,
+    trader: Pubkey,
+) -> Result<
+    Option<(
+        AccountLoader<'info, TradeMining>,
+        AccountLoader<'info, TraderReward>,
+    )>,
+> {
+    let mut trade_mining = None;
+    let mut trader_reward = None;
+    for account in remaining_accounts {
+        if account.owner != &crate::ID {
+            continue;
+        }
+        let (is_trade_mining, is_trader_reward) = {
+            let data = account.try_borrow_data()?;
+            (
+                data.starts_with(TradeMining::DISCRIMINATOR),
+                data.starts_with(TraderReward::DISCRIMINATOR),
+            )
+        };
+        if trade_mining.is_none() && is_trade_mining {
+            let loader = AccountLoader::<TradeMining>::try_from(account)?;
+            require!(loader.load()?.pool == pool, PoolError::InvalidTraderReward);
+            trade_mining = Some(loader);
+        } else if trader_reward.is_none() && is_trader_reward {
+            let loader = AccountLoader::<TraderReward>::try_from(account)?;
+            {
+                let trader_reward = loader.load()?;
+                require!(
+                    trader_reward.pool == pool && trader_reward.trader == trader,
+                    PoolError::InvalidTraderReward
+                );
+            }
+            trader_reward = Some(loader);
+        }
+    }
+    Ok(trade_mining.zip(trader_reward))
+}
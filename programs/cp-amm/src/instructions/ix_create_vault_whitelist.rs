@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::{PROGRAM_AUTHORITY_PREFIX, VAULT_WHITELIST_PREFIX},
+    state::{AuthorityRole, Pool, ProgramAuthority, VaultWhitelist},
+    EvtCreateVaultWhitelist, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateVaultWhitelistCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [
+            VAULT_WHITELIST_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + VaultWhitelist::INIT_SPACE
+    )]
+    pub vault_whitelist: AccountLoader<'info, VaultWhitelist>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidVaultWhitelistAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a vault whitelist for a pool, whitelisted vaults can swap from the pre-activation point
+pub fn handle_create_vault_whitelist(
+    ctx: Context<CreateVaultWhitelistCtx>,
+    vaults: Vec<Pubkey>,
+) -> Result<()> {
+    let mut vault_whitelist = ctx.accounts.vault_whitelist.load_init()?;
+    vault_whitelist.initialize(ctx.accounts.pool.key(), &vaults)?;
+
+    emit_cpi!(EvtCreateVaultWhitelist {
+        pool: ctx.accounts.pool.key(),
+        vault_whitelist: ctx.accounts.vault_whitelist.key(),
+        signer: ctx.accounts.signer.key(),
+        vaults,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    event,
+    state::{Pool, PoolOperation},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DisableFrozenPoolCtx<'info> {
+    #[account(mut, has_one = token_a_vault, has_one = token_b_vault)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault token account for token a
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token b
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Permissionless, disable swap and adding liquidity of a pool having a frozen vault so routers stop quoting it.
+/// Pauser or partner of pool enables them again with `set_pool_status` once the vault is thawed
+pub fn handle_disable_frozen_pool(ctx: Context<DisableFrozenPoolCtx>) -> Result<()> {
+    require!(
+        ctx.accounts.token_a_vault.is_frozen() || ctx.accounts.token_b_vault.is_frozen(),
+        PoolError::InvalidPoolStatus
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let status = pool.pool_status | PoolOperation::Swap.mask() | PoolOperation::AddLiquidity.mask();
+    require!(status != pool.pool_status, PoolError::InvalidPoolStatus);
+    pool.pool_status = status;
+
+    emit_cpi!(event::EvtSetPoolStatus {
+        pool: ctx.accounts.pool.key(),
+        signer: ctx.accounts.signer.key(),
+        status,
+    });
+
+    Ok(())
+}
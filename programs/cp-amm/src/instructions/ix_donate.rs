@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    safe_math::SafeMath,
+    state::Pool,
+    token::{get_token_account_amount, transfer_from_user},
+    EvtDonate, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DonateParameters {
+    /// amount of token a to donate, including transfer fee
+    pub token_a_amount: u64,
+    /// amount of token b to donate, including transfer fee
+    pub token_b_amount: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DonateCtx<'info> {
+    #[account(mut, has_one = token_a_vault, has_one = token_b_vault, has_one = token_a_mint, has_one = token_b_mint)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The donor token a account
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The donor token b account
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token a
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token b
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub donor: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Transfer amount to vault, returns the amount received by the vault
+fn transfer_to_vault<'c: 'info, 'info>(
+    donor: &Signer<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<u64> {
+    if amount == 0 {
+        return Ok(0);
+    }
+    let vault_amount = token_vault.amount;
+    transfer_from_user(
+        donor,
+        token_mint,
+        token_account,
+        token_vault,
+        token_program,
+        amount,
+        remaining_accounts,
+    )?;
+    Ok(get_token_account_amount(token_vault)?.safe_sub(vault_amount)?)
+}
+
+/// Anyone can donate tokens to liquidity providers of a pool, donation is distributed as lp fee and doesn't move the price
+pub fn handle_donate<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, DonateCtx<'info>>,
+    params: DonateParameters,
+) -> Result<()> {
+    let DonateParameters {
+        token_a_amount,
+        token_b_amount,
+    } = params;
+
+    // pool is credited with the amount received by vaults, excluding transfer fee
+    let donated_a_amount = transfer_to_vault(
+        &ctx.accounts.donor,
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.token_a_account,
+        &ctx.accounts.token_a_vault,
+        &ctx.accounts.token_a_program,
+        token_a_amount,
+        ctx.remaining_accounts,
+    )?;
+    let donated_b_amount = transfer_to_vault(
+        &ctx.accounts.donor,
+        &ctx.accounts.token_b_mint,
+        &ctx.accounts.token_b_account,
+        &ctx.accounts.token_b_vault,
+        &ctx.accounts.token_b_program,
+        token_b_amount,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        donated_a_amount > 0 || donated_b_amount > 0,
+        PoolError::AmountIsZero
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    pool.distribute_to_lp(donated_a_amount, donated_b_amount)?;
+
+    emit_cpi!(EvtDonate {
+        pool: ctx.accounts.pool.key(),
+        donor: ctx.accounts.donor.key(),
+        token_a_amount: donated_a_amount,
+        token_b_amount: donated_b_amount,
+    });
+
+    Ok(())
+}
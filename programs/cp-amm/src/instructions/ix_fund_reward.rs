@@ -2,10 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constants::{NUM_REWARDS, REWARD_RATE_SCALE},
+    constants::{seeds::PROGRAM_AUTHORITY_PREFIX, REWARD_RATE_SCALE},
     event::EvtFundReward,
+    load_reward_extension_for_index,
     math::safe_math::SafeMath,
-    state::Pool,
+    state::{get_reward_info_mut, Pool, ProgramAuthority, RewardInfo},
     token::{calculate_transfer_fee_excluded_amount, transfer_from_user},
     utils_math::safe_mul_shr_cast,
     PoolError,
@@ -25,25 +26,24 @@ pub struct FundRewardCtx<'info> {
     #[account(mut)]
     pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
     pub funder: Signer<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> FundRewardCtx<'info> {
-    fn validate(&self, reward_index: usize) -> Result<()> {
-        let pool = self.pool.load()?;
-
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
-
-        let reward_info = &pool.reward_infos[reward_index];
+    fn validate(&self, reward_info: &RewardInfo) -> Result<()> {
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
         require!(
             reward_info.vault.eq(&self.reward_vault.key()),
             PoolError::InvalidRewardVault
         );
+        let program_authority = self.program_authority.load()?;
         require!(
-            reward_info.is_valid_funder(self.funder.key()),
+            reward_info.is_valid_funder(self.funder.key(), &program_authority),
             PoolError::InvalidAdmin
         );
 
@@ -51,8 +51,8 @@ impl<'info> FundRewardCtx<'info> {
     }
 }
 
-pub fn handle_fund_reward(
-    ctx: Context<FundRewardCtx>,
+pub fn handle_fund_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
     reward_index: u8,
     amount: u64,
     carry_forward: bool,
@@ -60,21 +60,35 @@ pub fn handle_fund_reward(
     let index: usize = reward_index
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index)?;
 
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let reward_extension =
+        load_reward_extension_for_index(&mut remaining_accounts, ctx.accounts.pool.key(), index)?;
+
+    let clock = Clock::get()?;
     // actual amount need to transfer
     let transfer_fee_excluded_amount_in =
-        calculate_transfer_fee_excluded_amount(&ctx.accounts.reward_mint, amount)?.amount;
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.reward_mint, amount, clock.epoch)?
+            .amount;
 
     require!(transfer_fee_excluded_amount_in > 0, PoolError::AmountIsZero);
 
     let mut pool = ctx.accounts.pool.load_mut()?;
-    let current_time = Clock::get()?.unix_timestamp;
+    pool.increment_sequence();
+    let mut reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| reward_extension.load_mut())
+        .transpose()?;
+    let current_time = clock.unix_timestamp;
     // 1. update pool rewards
     pool.update_rewards(current_time as u64)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time as u64)?;
+    }
 
     // 2. set new farming rate
-    let reward_info = &mut pool.reward_infos[index];
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
+    ctx.accounts.validate(reward_info)?;
 
     let total_amount = if carry_forward {
         let carry_forward_ineligible_reward: u64 = safe_mul_shr_cast(
@@ -113,6 +127,7 @@ pub fn handle_fund_reward(
         &ctx.accounts.reward_vault,
         &ctx.accounts.token_program,
         amount,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtFundReward {
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, Pool, ProgramAuthority, TradeMining},
+    token::{calculate_transfer_fee_excluded_amount, transfer_from_user},
+    EvtFundTradeMining, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundTradeMiningCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool, has_one = vault @ PoolError::InvalidRewardVault)]
+    pub trade_mining: AccountLoader<'info, TradeMining>,
+
+    /// The vault token account for trade mining reward
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = vault.mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// reward admin or funder of trade mining
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::RewardAdmin, funder.key()) || trade_mining.load()?.funder == funder.key() @ PoolError::InvalidAdmin
+    )]
+    pub funder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_fund_trade_mining<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FundTradeMiningCtx<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let transfer_fee_excluded_amount_in = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.reward_mint,
+        amount,
+        Clock::get()?.epoch,
+    )?
+    .amount;
+    require!(transfer_fee_excluded_amount_in > 0, PoolError::AmountIsZero);
+
+    ctx.accounts
+        .trade_mining
+        .load_mut()?
+        .fund(transfer_fee_excluded_amount_in)?;
+
+    transfer_from_user(
+        &ctx.accounts.funder,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.funder_token_account,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+        amount,
+        ctx.remaining_accounts,
+    )?;
+
+    emit_cpi!(EvtFundTradeMining {
+        pool: ctx.accounts.pool.key(),
+        trade_mining: ctx.accounts.trade_mining.key(),
+        funder: ctx.accounts.funder.key(),
+        amount,
+        transfer_fee_excluded_amount_in,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::ActivationHandler, constants::fee::MAX_FEE_NUMERATOR, state::Pool,
+    u128x128_math::Rounding,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PoolStateView {
+    pub sqrt_price: u128,
+    pub sqrt_min_price: u128,
+    pub sqrt_max_price: u128,
+    pub liquidity: u128,
+    /// base fee numerator at current point, after fee scheduler
+    pub base_fee_numerator: u64,
+    /// variable fee numerator of dynamic fee
+    pub variable_fee_numerator: u128,
+    /// total trade fee numerator applied to a swap at current point
+    pub trade_fee_numerator: u64,
+    pub protocol_fee_percent: u8,
+    pub partner_fee_percent: u8,
+    pub referral_fee_percent: u8,
+    pub collect_fee_mode: u8,
+    pub pool_status: u8,
+    pub activation_type: u8,
+    pub activation_point: u64,
+    pub current_point: u64,
+    /// token a amount of liquidity delta, rounded down
+    pub token_a_amount: u64,
+    /// token b amount of liquidity delta, rounded down
+    pub token_b_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct GetPoolStateCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+}
+
+/// Read-only snapshot of pool state for simulation, returned via return data. Pool state is not modified
+pub fn handle_get_pool_state(
+    ctx: Context<GetPoolStateCtx>,
+    liquidity_delta: u128,
+) -> Result<PoolStateView> {
+    // copy, so pending dynamic fee update and volatility references are applied like a swap without being stored
+    let mut pool = *ctx.accounts.pool.load()?;
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    pool.update_pre_swap(current_timestamp)?;
+
+    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
+    let base_fee_numerator = pool
+        .pool_fees
+        .base_fee
+        .get_current_base_fee_numerator(current_point, pool.activation_point)?;
+    let variable_fee_numerator = pool.pool_fees.dynamic_fee.get_variable_fee()?;
+    let trade_fee_numerator = pool
+        .pool_fees
+        .get_total_trading_fee(current_point, pool.activation_point)?
+        .min(MAX_FEE_NUMERATOR.into())
+        .try_into()
+        .unwrap();
+
+    let modify_liquidity_result =
+        pool.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Down)?;
+
+    Ok(PoolStateView {
+        sqrt_price: pool.sqrt_price,
+        sqrt_min_price: pool.sqrt_min_price,
+        sqrt_max_price: pool.sqrt_max_price,
+        liquidity: pool.liquidity,
+        base_fee_numerator,
+        variable_fee_numerator,
+        trade_fee_numerator,
+        protocol_fee_percent: pool.pool_fees.protocol_fee_percent,
+        partner_fee_percent: pool.pool_fees.partner_fee_percent,
+        referral_fee_percent: pool.pool_fees.referral_fee_percent,
+        collect_fee_mode: pool.collect_fee_mode,
+        pool_status: pool.pool_status,
+        activation_type: pool.activation_type,
+        activation_point: pool.activation_point,
+        current_point,
+        token_a_amount: modify_liquidity_result.token_a_amount,
+        token_b_amount: modify_liquidity_result.token_b_amount,
+    })
+}
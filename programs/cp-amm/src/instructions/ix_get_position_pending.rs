@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    state::{Pool, Position, PositionRewardExtension, RewardExtension},
+    PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PositionPendingView {
+    /// claimable fee a, including fee accrued since last position update
+    pub fee_a_pending: u64,
+    /// claimable fee b, including fee accrued since last position update
+    pub fee_b_pending: u64,
+    /// claimable rewards by reward index, rewards of reward extension follow pool rewards and are zero if pool has no reward extension
+    pub reward_pendings: [u64; NUM_REWARDS + NUM_EXTENSION_REWARDS],
+}
+
+#[derive(Accounts)]
+pub struct GetPositionPendingCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// reward extension of pool, required if pool has reward extension
+    #[account(has_one = pool @ PoolError::InvalidRewardExtension)]
+    pub reward_extension: Option<AccountLoader<'info, RewardExtension>>,
+
+    /// reward extension of position, required if pool has reward extension
+    #[account(has_one = position @ PoolError::InvalidRewardExtension)]
+    pub position_reward_extension: Option<AccountLoader<'info, PositionRewardExtension>>,
+}
+
+/// Read-only pending fees and rewards of position for simulation, returned via return data. No account is modified
+pub fn handle_get_position_pending(
+    ctx: Context<GetPositionPendingCtx>,
+) -> Result<PositionPendingView> {
+    let mut pool = *ctx.accounts.pool.load()?;
+    let mut position = *ctx.accounts.position.load()?;
+    let mut reward_extensions = if pool.has_reward_extension() {
+        match (
+            &ctx.accounts.reward_extension,
+            &ctx.accounts.position_reward_extension,
+        ) {
+            (Some(reward_extension), Some(position_reward_extension)) => Some((
+                *reward_extension.load()?,
+                *position_reward_extension.load()?,
+            )),
+            _ => return Err(PoolError::InvalidRewardExtension.into()),
+        }
+    } else {
+        None
+    };
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    calculate_position_pending(
+        &mut pool,
+        &mut position,
+        reward_extensions
+            .as_mut()
+            .map(|(reward_extension, position_reward_extension)| {
+                (reward_extension, position_reward_extension)
+            }),
+        current_time,
+    )
+}
+
+/// Accrue fees and rewards of position to current time, the same way as `update_rewards` before a claim.
+/// Accounts are updated in place, callers not persisting the accrual must pass copies
+pub fn calculate_position_pending(
+    pool: &mut Pool,
+    position: &mut Position,
+    reward_extensions: Option<(&mut RewardExtension, &mut PositionRewardExtension)>,
+    current_time: u64,
+) -> Result<PositionPendingView> {
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+    position.update_rewards(pool, current_time)?;
+
+    let mut reward_pendings = [0u64; NUM_REWARDS + NUM_EXTENSION_REWARDS];
+    for (reward_pending, reward_info) in reward_pendings.iter_mut().zip(position.reward_infos) {
+        *reward_pending = reward_info.reward_pendings;
+    }
+
+    if let Some((reward_extension, position_reward_extension)) = reward_extensions {
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+        position_reward_extension
+            .update_rewards(reward_extension, position.get_reward_liquidity(pool)?)?;
+        for (reward_pending, reward_info) in reward_pendings[NUM_REWARDS..]
+            .iter_mut()
+            .zip(position_reward_extension.reward_infos)
+        {
+            *reward_pending = reward_info.reward_pendings;
+        }
+    }
+
+    Ok(PositionPendingView {
+        fee_a_pending: position.fee_a_pending,
+        fee_b_pending: position.fee_b_pending,
+        reward_pendings,
+    })
+}
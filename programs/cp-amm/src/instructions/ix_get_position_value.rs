@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::NUM_REWARDS,
+    state::{Pool, Position},
+    u128x128_math::Rounding,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PositionValueView {
+    pub unlocked_liquidity: u128,
+    pub vested_liquidity: u128,
+    pub permanent_locked_liquidity: u128,
+    /// token a amount of total position liquidity, rounded down
+    pub token_a_amount: u64,
+    /// token b amount of total position liquidity, rounded down
+    pub token_b_amount: u64,
+    /// claimable fee a, including fee accrued since last position update
+    pub fee_a_pending: u64,
+    /// claimable fee b, including fee accrued since last position update
+    pub fee_b_pending: u64,
+    /// claimable rewards of rewards stored in pool account, rewards of reward extension are excluded
+    pub reward_pendings: [u64; NUM_REWARDS],
+}
+
+#[derive(Accounts)]
+pub struct GetPositionValueCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+}
+
+/// Read-only snapshot of position value for simulation, returned via return data. Pool and position are not modified
+pub fn handle_get_position_value(ctx: Context<GetPositionValueCtx>) -> Result<PositionValueView> {
+    let mut pool = *ctx.accounts.pool.load()?;
+    let mut position = *ctx.accounts.position.load()?;
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+    position.update_rewards(&mut pool, current_time)?;
+
+    let modify_liquidity_result =
+        pool.get_amounts_for_modify_liquidity(position.get_total_liquidity()?, Rounding::Down)?;
+
+    Ok(PositionValueView {
+        unlocked_liquidity: position.unlocked_liquidity,
+        vested_liquidity: position.vested_liquidity,
+        permanent_locked_liquidity: position.permanent_locked_liquidity,
+        token_a_amount: modify_liquidity_result.token_a_amount,
+        token_b_amount: modify_liquidity_result.token_b_amount,
+        fee_a_pending: position.fee_a_pending,
+        fee_b_pending: position.fee_b_pending,
+        reward_pendings: position
+            .reward_infos
+            .map(|reward_info| reward_info.reward_pendings),
+    })
+}
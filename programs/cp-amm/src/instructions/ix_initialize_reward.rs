@@ -0,0 +1,163 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::{
+        seeds::{POOL_AUTHORITY_PREFIX, PROGRAM_AUTHORITY_PREFIX, REWARD_VAULT_PREFIX},
+        treasury, INITIALIZE_REWARD_FEE, MAX_REWARD_CLAIM_VESTING_DURATION, MAX_REWARD_DURATION,
+        MIN_REWARD_DURATION,
+    },
+    error::PoolError,
+    event::EvtInitializeReward,
+    load_reward_extension_for_index,
+    state::{get_reward_info_mut, AuthorityRole, Pool, ProgramAuthority, RewardInfo},
+    token::{get_token_program_flags, is_mint_allowed_by_token_badge, is_supported_mint},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct InitializeRewardCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [REWARD_VAULT_PREFIX.as_ref(), pool.key().as_ref(), reward_index.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        token::mint = reward_mint,
+        token::authority = pool_authority
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// Admin, or anyone who pays the reward initialization fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: treasury, receives the reward initialization fee
+    #[account(mut, address = treasury::ID)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeRewardCtx<'info> {
+    fn validate(
+        &self,
+        reward_info: &RewardInfo,
+        reward_duration: u64,
+        claim_vesting_duration: u32,
+    ) -> Result<()> {
+        require!(
+            reward_duration >= MIN_REWARD_DURATION && reward_duration <= MAX_REWARD_DURATION,
+            PoolError::InvalidRewardDuration
+        );
+
+        require!(
+            claim_vesting_duration <= MAX_REWARD_CLAIM_VESTING_DURATION,
+            PoolError::InvalidRewardClaimVestingDuration
+        );
+
+        require!(!reward_info.initialized(), PoolError::RewardInitialized);
+
+        Ok(())
+    }
+
+    fn charge_initialize_reward_fee(&self) -> Result<u64> {
+        if self
+            .program_authority
+            .load()?
+            .has_role(AuthorityRole::RewardAdmin, self.payer.key())
+        {
+            return Ok(0);
+        }
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.payer.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                },
+            ),
+            INITIALIZE_REWARD_FEE,
+        )?;
+
+        Ok(INITIALIZE_REWARD_FEE)
+    }
+}
+
+pub fn handle_initialize_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeRewardCtx<'info>>,
+    reward_index: u8,
+    reward_duration: u64,
+    funder: Pubkey,
+    claim_vesting_duration: u32,
+) -> Result<()> {
+    let index: usize = reward_index
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    // reward extension is the first remaining account for extension rewards
+    let reward_extension =
+        load_reward_extension_for_index(&mut remaining_accounts, ctx.accounts.pool.key(), index)?;
+
+    if !is_supported_mint(&ctx.accounts.reward_mint)? {
+        require!(
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.reward_mint,
+                remaining_accounts
+                    .get(0)
+                    .ok_or(PoolError::InvalidTokenBadge)?
+            )?,
+            PoolError::InvalidTokenBadge
+        );
+    }
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let mut reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| reward_extension.load_mut())
+        .transpose()?;
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
+
+    ctx.accounts
+        .validate(reward_info, reward_duration, claim_vesting_duration)?;
+
+    let creation_fee = ctx.accounts.charge_initialize_reward_fee()?;
+
+    reward_info.init_reward(
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_vault.key(),
+        funder,
+        reward_duration,
+        get_token_program_flags(&ctx.accounts.reward_mint).into(),
+        claim_vesting_duration,
+    );
+
+    emit_cpi!(EvtInitializeReward {
+        pool: ctx.accounts.pool.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        funder,
+        reward_duration,
+        reward_index,
+        creation_fee,
+        claim_vesting_duration,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::{
+        POOL_AUTHORITY_PREFIX, PROGRAM_AUTHORITY_PREFIX, TRADE_MINING_PREFIX,
+        TRADE_MINING_VAULT_PREFIX,
+    },
+    state::{AuthorityRole, Pool, ProgramAuthority, TradeMining},
+    token::{get_token_program_flags, is_mint_allowed_by_token_badge, is_supported_mint},
+    EvtInitializeTradeMining, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeTradeMiningCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [
+            TRADE_MINING_PREFIX.as_ref(),
+            pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + TradeMining::INIT_SPACE
+    )]
+    pub trade_mining: AccountLoader<'info, TradeMining>,
+
+    #[account(
+        init,
+        seeds = [TRADE_MINING_VAULT_PREFIX.as_ref(), pool.key().as_ref()],
+        bump,
+        payer = signer,
+        token::mint = reward_mint,
+        token::authority = pool_authority
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// reward admin or partner of pool
+    #[account(
+        mut,
+        constraint = program_authority.load()?.has_role(AuthorityRole::RewardAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidTradeMiningAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Start trade mining of a pool, swaps accrue `reward_per_fee_a` or `reward_per_fee_b` (Q64.64) of reward
+/// per unit of trade fee paid to trader reward of the swap payer, until the funded budget is used up or `end_time`
+pub fn handle_initialize_trade_mining<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeTradeMiningCtx<'info>>,
+    funder: Pubkey,
+    reward_per_fee_a: u128,
+    reward_per_fee_b: u128,
+    end_time: u64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(end_time > current_time, PoolError::InvalidRewardDuration);
+
+    if !is_supported_mint(&ctx.accounts.reward_mint)? {
+        require!(
+            is_mint_allowed_by_token_badge(
+                &ctx.accounts.reward_mint,
+                ctx.remaining_accounts
+                    .get(0)
+                    .ok_or(PoolError::InvalidTokenBadge)?
+            )?,
+            PoolError::InvalidTokenBadge
+        );
+    }
+
+    let mut trade_mining = ctx.accounts.trade_mining.load_init()?;
+    trade_mining.initialize(
+        ctx.accounts.pool.key(),
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_vault.key(),
+        funder,
+        reward_per_fee_a,
+        reward_per_fee_b,
+        get_token_program_flags(&ctx.accounts.reward_mint).into(),
+        end_time,
+    );
+
+    emit_cpi!(EvtInitializeTradeMining {
+        pool: ctx.accounts.pool.key(),
+        trade_mining: ctx.accounts.trade_mining.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        funder,
+        reward_per_fee_a,
+        reward_per_fee_b,
+        end_time,
+    });
+
+    Ok(())
+}
@@ -4,9 +4,10 @@ use anchor_spl::token_interface::TokenAccount;
 use crate::{
     activation_handler::ActivationHandler,
     error::PoolError,
+    instructions::update_position_rewards_before_lock,
     safe_math::SafeMath,
     state::{Pool, Position, Vesting},
-    {get_pool_access_validator, EvtLockPosition},
+    {get_pool_access_validator, EvtLockPosition, PoolActionAccess},
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
@@ -67,6 +68,7 @@ impl VestingParameters {
 #[derive(Accounts)]
 #[instruction(params: VestingParameters)]
 pub struct LockPositionCtx<'info> {
+    #[account(mut)]
     pub pool: AccountLoader<'info, Pool>,
 
     #[account(mut, has_one = pool)]
@@ -96,12 +98,13 @@ pub struct LockPositionCtx<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_lock_position(
-    ctx: Context<LockPositionCtx>,
+pub fn handle_lock_position<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, LockPositionCtx<'info>>,
     params: VestingParameters,
 ) -> Result<()> {
-    let pool = ctx.accounts.pool.load()?;
-    let access_validator = get_pool_access_validator(&pool)?;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let access_validator = get_pool_access_validator(&pool, None, None, None)?;
     require!(
         access_validator.can_lock_position(),
         PoolError::PoolDisabled
@@ -134,7 +137,17 @@ pub fn handle_lock_position(
     );
 
     let mut position = ctx.accounts.position.load_mut()?;
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    update_position_rewards_before_lock(
+        &mut remaining_accounts,
+        ctx.accounts.pool.key(),
+        &mut pool,
+        ctx.accounts.position.key(),
+        &mut position,
+    )?;
+
     position.lock(total_lock_liquidity)?;
+    pool.accumulate_vested_liquidity(total_lock_liquidity)?;
 
     emit_cpi!(EvtLockPosition {
         position: ctx.accounts.position.key(),
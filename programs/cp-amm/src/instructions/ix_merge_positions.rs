@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constants::seeds::POOL_AUTHORITY_PREFIX,
+    load_position_reward_extension, load_reward_extension,
+    state::{Pool, Position},
+    EvtMergePositions, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MergePositionsCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// position receiving liquidity, fees and rewards of source position
+    #[account(
+        mut,
+        has_one = pool,
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The token account for nft
+    #[account(
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// position to be merged, closed after merging
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = source_position.key() != position.key() @ PoolError::IdenticalPosition,
+        close = rent_receiver
+    )]
+    pub source_position: AccountLoader<'info, Position>,
+
+    /// source position nft mint
+    #[account(mut, address = source_position.load()?.nft_mint)]
+    pub source_position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account for source position nft
+    #[account(
+        mut,
+        constraint = source_position_nft_account.mint == source_position.load()?.nft_mint,
+        constraint = source_position_nft_account.amount == 1,
+        token::authority = owner
+    )]
+    pub source_position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// Owner of both positions
+    pub owner: Signer<'info>,
+
+    /// Program to close NFT mint/token account for token22 account
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn handle_merge_positions<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MergePositionsCtx<'info>>,
+) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let mut position = ctx.accounts.position.load_mut()?;
+    let mut source_position = ctx.accounts.source_position.load_mut()?;
+
+    // update fees and rewards of both positions to current checkpoint before merging
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+    source_position.update_rewards(&mut pool, current_time)?;
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+    source_position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    // merge and close position reward extension if pool has reward extension
+    if pool.has_reward_extension() {
+        let mut remaining_accounts = &ctx.remaining_accounts[..];
+        let reward_extension =
+            load_reward_extension(&mut remaining_accounts, ctx.accounts.pool.key())?;
+        let position_reward_extension =
+            load_position_reward_extension(&mut remaining_accounts, ctx.accounts.position.key())?;
+        let source_position_reward_extension = load_position_reward_extension(
+            &mut remaining_accounts,
+            ctx.accounts.source_position.key(),
+        )?;
+
+        {
+            let mut reward_extension = reward_extension.load_mut()?;
+            reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+
+            let mut position_reward_extension = position_reward_extension.load_mut()?;
+            let mut source_position_reward_extension =
+                source_position_reward_extension.load_mut()?;
+            position_reward_extension
+                .update_rewards(&reward_extension, position.get_reward_liquidity(&pool)?)?;
+            source_position_reward_extension.update_rewards(
+                &reward_extension,
+                source_position.get_reward_liquidity(&pool)?,
+            )?;
+
+            position_reward_extension.merge(&mut source_position_reward_extension)?;
+        }
+
+        source_position_reward_extension.close(ctx.accounts.rent_receiver.to_account_info())?;
+    }
+
+    position.merge(&mut source_position)?;
+    pool.metrics.rec_position()?;
+
+    // burn
+    token_2022::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::Burn {
+                mint: ctx.accounts.source_position_nft_mint.to_account_info(),
+                from: ctx.accounts.source_position_nft_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    // close source_position_nft_account
+    token_2022::close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::CloseAccount {
+            account: ctx.accounts.source_position_nft_account.to_account_info(),
+            destination: ctx.accounts.rent_receiver.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    ))?;
+
+    // close source_position_nft_mint
+    let signer_seeds = pool_authority_seeds!(ctx.bumps.pool_authority);
+    token_2022::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::CloseAccount {
+            account: ctx.accounts.source_position_nft_mint.to_account_info(),
+            destination: ctx.accounts.rent_receiver.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[&signer_seeds[..]],
+    ))?;
+
+    emit_cpi!(EvtMergePositions {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        position: ctx.accounts.position.key(),
+        source_position: ctx.accounts.source_position.key(),
+        source_position_nft_mint: ctx.accounts.source_position_nft_mint.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{event, state::Pool};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigratePoolCtx<'info> {
+    #[account(
+        mut,
+        realloc = 8 + Pool::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = true,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Pays rent of the grown pool account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow pool account to the current layout and upgrade its version, permissionless as the payer funds the rent.
+/// Pools created before the latest version can't be loaded until migrated
+pub fn handle_migrate_pool(ctx: Context<MigratePoolCtx>) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let from_version = pool.migrate()?;
+
+    emit_cpi!(event::EvtMigratePool {
+        pool: ctx.accounts.pool.key(),
+        from_version,
+        to_version: pool.version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constants::seeds::{
+        FEE_RECEIPT_ACCOUNT_PREFIX, FEE_RECEIPT_MINT_PREFIX, POOL_AUTHORITY_PREFIX,
+    },
+    state::{Pool, Position},
+    EvtMintFeeReceipt, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintFeeReceiptCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The token account for nft
+    #[account(
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+
+    /// fee receipt mint, one per position
+    #[account(
+        init,
+        seeds = [FEE_RECEIPT_MINT_PREFIX.as_ref(), position.key().as_ref()],
+        bump,
+        payer = payer,
+        mint::token_program = token_program,
+        mint::decimals = 0,
+        mint::authority = pool_authority,
+    )]
+    pub fee_receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Receives the fee receipt
+    pub receiver: UncheckedAccount<'info>,
+
+    /// fee receipt token account of receiver
+    #[account(
+        init,
+        seeds = [FEE_RECEIPT_ACCOUNT_PREFIX.as_ref(), fee_receipt_mint.key().as_ref()],
+        bump,
+        payer = payer,
+        token::mint = fee_receipt_mint,
+        token::authority = receiver,
+        token::token_program = token_program,
+    )]
+    pub fee_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Program to create fee receipt mint/token account
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_mint_fee_receipt(ctx: Context<MintFeeReceiptCtx>) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    require!(
+        position.is_fully_permanent_locked(),
+        PoolError::PositionNotPermanentlyLocked
+    );
+
+    // pending fee are claimable by fee receipt holder from now on
+    position.fee_receipt_minted = 1;
+
+    let seeds = pool_authority_seeds!(ctx.bumps.pool_authority);
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::MintTo {
+                mint: ctx.accounts.fee_receipt_mint.to_account_info(),
+                to: ctx.accounts.fee_receipt_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        1,
+    )?;
+
+    emit_cpi!(EvtMintFeeReceipt {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        receiver: ctx.accounts.receiver.key(),
+        fee_receipt_mint: ctx.accounts.fee_receipt_mint.key(),
+    });
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use anchor_spl::token_interface::TokenAccount;
 
 use crate::{
     get_pool_access_validator,
+    instructions::update_position_rewards_before_lock,
     state::{Pool, Position},
-    EvtPermanentLockPosition, PoolError,
+    EvtPermanentLockPosition, PoolActionAccess, PoolError,
 };
 
 #[event_cpi]
@@ -28,13 +29,13 @@ pub struct PermanentLockPositionCtx<'info> {
     pub owner: Signer<'info>,
 }
 
-pub fn handle_permanent_lock_position(
-    ctx: Context<PermanentLockPositionCtx>,
+pub fn handle_permanent_lock_position<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PermanentLockPositionCtx<'info>>,
     permanent_lock_liquidity: u128,
 ) -> Result<()> {
     {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let access_validator = get_pool_access_validator(&pool, None, None, None)?;
         require!(
             access_validator.can_lock_position(),
             PoolError::PoolDisabled
@@ -42,8 +43,18 @@ pub fn handle_permanent_lock_position(
     }
 
     let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
     let mut position = ctx.accounts.position.load_mut()?;
 
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    update_position_rewards_before_lock(
+        &mut remaining_accounts,
+        ctx.accounts.pool.key(),
+        &mut pool,
+        ctx.accounts.position.key(),
+        &mut position,
+    )?;
+
     position.permanent_lock_liquidity(permanent_lock_liquidity)?;
     pool.accumulate_permanent_locked_liquidity(permanent_lock_liquidity)?;
 
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    get_pool_access_validator,
+    instructions::update_position_rewards_before_lock,
+    state::{Pool, Position, Vesting},
+    EvtPermanentLockPosition, PoolActionAccess, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PermanentLockVestingCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// vesting of position, closed after its remaining liquidity is permanently locked
+    #[account(
+        mut,
+        has_one = position @ PoolError::InvalidVestingAccount,
+        close = owner
+    )]
+    pub vesting: AccountLoader<'info, Vesting>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn handle_permanent_lock_vesting<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PermanentLockVestingCtx<'info>>,
+) -> Result<()> {
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let access_validator = get_pool_access_validator(&pool, None, None, None)?;
+        require!(
+            access_validator.can_lock_position(),
+            PoolError::PoolDisabled
+        );
+    }
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let mut position = ctx.accounts.position.load_mut()?;
+    let vesting = ctx.accounts.vesting.load()?;
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    update_position_rewards_before_lock(
+        &mut remaining_accounts,
+        ctx.accounts.pool.key(),
+        &mut pool,
+        ctx.accounts.position.key(),
+        &mut position,
+    )?;
+
+    // all liquidity that has not been released yet, including liquidity due for the next refresh
+    let permanent_lock_liquidity = vesting.get_remaining_locked_liquidity()?;
+
+    position.permanent_lock_vested_liquidity(permanent_lock_liquidity)?;
+    pool.release_vested_liquidity(permanent_lock_liquidity)?;
+    pool.accumulate_permanent_locked_liquidity(permanent_lock_liquidity)?;
+
+    emit_cpi!(EvtPermanentLockPosition {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        lock_liquidity_amount: permanent_lock_liquidity,
+        total_permanent_locked_liquidity: pool.permanent_lock_liquidity
+    });
+
+    Ok(())
+}
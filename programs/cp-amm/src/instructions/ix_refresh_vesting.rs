@@ -1,16 +1,20 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
 use anchor_spl::token_interface::TokenAccount;
 use std::cell::RefMut;
 use std::collections::BTreeSet;
 
 use crate::{
     activation_handler::ActivationHandler,
+    constants::MIN_COMPUTE_UNITS_PER_VESTING_REFRESH,
+    instructions::update_position_rewards_before_lock,
     state::{Pool, Position, Vesting},
     PoolError,
 };
 
 #[derive(Accounts)]
 pub struct RefreshVesting<'info> {
+    #[account(mut)]
     pub pool: AccountLoader<'info, Pool>,
 
     #[account(
@@ -31,6 +35,12 @@ pub struct RefreshVesting<'info> {
     pub owner: UncheckedAccount<'info>,
 }
 
+#[derive(Accounts)]
+pub struct BatchRefreshVestingCtx<'info> {
+    /// Crank signer, can be anyone
+    pub signer: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct VestingRemainingAccount<'info> {
     #[account(mut)]
@@ -51,49 +61,117 @@ impl<'info> VestingRemainingAccount<'info> {
 pub fn handle_refresh_vesting<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, RefreshVesting<'info>>,
 ) -> Result<()> {
-    let pool = ctx.accounts.pool.load()?;
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    refresh_position_vestings(ctx.accounts, &mut remaining_accounts, None)?;
 
-    let (current_point, _) =
-        ActivationHandler::get_current_point_and_buffer_duration(pool.activation_type)?;
+    Ok(())
+}
 
-    let mut position: RefMut<'_, Position> = ctx.accounts.position.load_mut()?;
+/// Refresh vestings of many positions. Remaining accounts contain, for every position, the accounts of
+/// `RefreshVesting`, reward extension and position reward extension if pool has reward boost and reward extension,
+/// followed by `vesting_counts[i]` vesting accounts of the position.
+/// Stops without error when compute units are not enough to refresh the next vesting
+pub fn handle_batch_refresh_vesting<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, BatchRefreshVestingCtx<'info>>,
+    vesting_counts: Vec<u8>,
+) -> Result<()> {
     let mut remaining_accounts = &ctx.remaining_accounts[..];
 
-    loop {
-        if remaining_accounts.is_empty() {
+    for number_of_vestings in vesting_counts {
+        let accounts = RefreshVesting::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &[],
+            &mut RefreshVestingBumps {},
+            &mut BTreeSet::new(),
+        )?;
+
+        let completed = refresh_position_vestings(
+            &accounts,
+            &mut remaining_accounts,
+            Some(number_of_vestings.into()),
+        )?;
+        if !completed {
             break;
         }
+    }
+
+    Ok(())
+}
+
+/// Release liquidity of the next `number_of_vestings` vesting accounts in remaining accounts to the position,
+/// or all remaining accounts if it is None. Reward extension accounts of the position precede the vesting accounts
+/// if pool has reward boost and reward extension.
+/// All locked liquidity is released when pool is in emergency mode.
+/// Returns false if it stops early because compute units are not enough
+fn refresh_position_vestings<'c: 'info, 'info>(
+    accounts: &RefreshVesting<'info>,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    number_of_vestings: Option<usize>,
+) -> Result<bool> {
+    let mut pool = accounts.pool.load_mut()?;
+    pool.increment_sequence();
+
+    let (current_point, _) =
+        ActivationHandler::get_current_point_and_buffer_duration(pool.activation_type)?;
+
+    let mut position: RefMut<'_, Position> = accounts.position.load_mut()?;
+    update_position_rewards_before_lock(
+        remaining_accounts,
+        accounts.pool.key(),
+        &mut pool,
+        accounts.position.key(),
+        &mut position,
+    )?;
+
+    let number_of_vestings = number_of_vestings.unwrap_or(remaining_accounts.len());
+
+    for _ in 0..number_of_vestings {
+        if sol_remaining_compute_units() < MIN_COMPUTE_UNITS_PER_VESTING_REFRESH {
+            return Ok(false);
+        }
 
         let vesting_account = VestingRemainingAccount::try_accounts(
             &crate::ID,
-            &mut remaining_accounts,
+            remaining_accounts,
             &[],
             &mut VestingRemainingAccountBumps {},
             &mut BTreeSet::new(),
         )?;
 
-        let mut vesting = vesting_account.load_and_validate(ctx.accounts.position.key())?;
-        release_vesting_liquidity_to_position(&mut vesting, &mut position, current_point)?;
+        let mut vesting = vesting_account.load_and_validate(accounts.position.key())?;
+        release_vesting_liquidity_to_position(
+            &mut vesting,
+            &mut position,
+            &mut pool,
+            current_point,
+        )?;
 
         if vesting.done()? {
             drop(vesting);
             vesting_account
                 .vesting
-                .close(ctx.accounts.owner.to_account_info())?;
+                .close(accounts.owner.to_account_info())?;
         }
     }
 
-    Ok(())
+    Ok(true)
 }
 
 fn release_vesting_liquidity_to_position(
     vesting: &mut RefMut<'_, Vesting>,
     position: &mut RefMut<'_, Position>,
+    pool: &mut RefMut<'_, Pool>,
     current_point: u64,
 ) -> Result<()> {
-    let released_liquidity = vesting.get_new_release_liquidity(current_point)?;
+    let released_liquidity = if pool.is_emergency_mode() {
+        vesting.get_remaining_locked_liquidity()?
+    } else {
+        vesting.get_new_release_liquidity(current_point)?
+    };
     if released_liquidity > 0 {
         position.release_vested_liquidity(released_liquidity)?;
+        pool.release_vested_liquidity(released_liquidity)?;
         vesting.accumulate_released_liquidity(released_liquidity)?;
     }
 
@@ -7,18 +7,21 @@ use crate::{
     constants::seeds::POOL_AUTHORITY_PREFIX,
     get_pool_access_validator,
     state::{ModifyLiquidityResult, Pool, Position},
-    token::transfer_from_pool,
+    token::{
+        calculate_transfer_fee_excluded_amount_cached, require_vaults_not_frozen,
+        transfer_from_pool,
+    },
     u128x128_math::Rounding,
-    EvtRemoveLiquidity, PoolError,
+    update_position_extension_rewards, EvtRemoveLiquidity, PoolActionAccess, PoolError,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RemoveLiquidityParameters {
     /// delta liquidity
     pub liquidity_delta: u128,
-    /// minimum token a amount
+    /// minimum token a amount, excluding transfer fee
     pub token_a_amount_threshold: u64,
-    /// minimum token b amount
+    /// minimum token b amount, excluding transfer fee
     pub token_b_amount_threshold: u64,
 }
 
@@ -78,22 +81,24 @@ pub struct RemoveLiquidityCtx<'info> {
     pub token_b_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handle_remove_liquidity(
-    ctx: Context<RemoveLiquidityCtx>,
+pub fn handle_remove_liquidity<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
     liquidity_delta: Option<u128>,
     token_a_amount_threshold: u64,
     token_b_amount_threshold: u64,
 ) -> Result<()> {
     {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let access_validator = get_pool_access_validator(&pool, None, None, None)?;
         require!(
             access_validator.can_remove_liquidity(),
             PoolError::PoolDisabled
         );
     }
+    require_vaults_not_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_b_vault)?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
     let mut position = ctx.accounts.position.load_mut()?;
 
     let liquidity_delta = liquidity_delta.unwrap_or(position.unlocked_liquidity);
@@ -103,8 +108,18 @@ pub fn handle_remove_liquidity(
     );
 
     // update current pool reward & postion reward before any logic
-    let current_time = Clock::get()?.unix_timestamp as u64;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
     position.update_rewards(&mut pool, current_time)?;
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    update_position_extension_rewards(
+        &mut remaining_accounts,
+        ctx.accounts.pool.key(),
+        &pool,
+        ctx.accounts.position.key(),
+        &position,
+        current_time,
+    )?;
 
     let ModifyLiquidityResult {
         token_a_amount,
@@ -115,13 +130,27 @@ pub fn handle_remove_liquidity(
         token_a_amount > 0 || token_b_amount > 0,
         PoolError::AmountIsZero
     );
-    // Slippage check
+    // Slippage check against the amounts user actually receives
+    let transfer_fee_excluded_amount_a = calculate_transfer_fee_excluded_amount_cached(
+        &ctx.accounts.token_a_mint,
+        &mut pool.transfer_fee_cache_a,
+        token_a_amount,
+        clock.epoch,
+    )?
+    .amount;
+    let transfer_fee_excluded_amount_b = calculate_transfer_fee_excluded_amount_cached(
+        &ctx.accounts.token_b_mint,
+        &mut pool.transfer_fee_cache_b,
+        token_b_amount,
+        clock.epoch,
+    )?
+    .amount;
     require!(
-        token_a_amount >= token_a_amount_threshold,
+        transfer_fee_excluded_amount_a >= token_a_amount_threshold,
         PoolError::ExceededSlippage
     );
     require!(
-        token_b_amount >= token_b_amount_threshold,
+        transfer_fee_excluded_amount_b >= token_b_amount_threshold,
         PoolError::ExceededSlippage
     );
 
@@ -136,6 +165,7 @@ pub fn handle_remove_liquidity(
         &ctx.accounts.token_a_program,
         token_a_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
     transfer_from_pool(
         ctx.accounts.pool_authority.to_account_info(),
@@ -145,6 +175,7 @@ pub fn handle_remove_liquidity(
         &ctx.accounts.token_b_program,
         token_b_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtRemoveLiquidity {
@@ -158,6 +189,7 @@ pub fn handle_remove_liquidity(
         },
         token_a_amount,
         token_b_amount,
+        sequence,
     });
 
     Ok(())
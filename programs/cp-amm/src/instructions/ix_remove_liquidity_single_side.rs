@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    get_pool_access_validator,
+    math::safe_math::SafeMath,
+    params::swap::TradeDirection,
+    state::{fee::FeeMode, ModifyLiquidityResult},
+    token::{
+        calculate_transfer_fee_excluded_amount_cached, require_vaults_not_frozen,
+        transfer_from_pool,
+    },
+    u128x128_math::Rounding,
+    update_position_extension_rewards, EvtRemoveLiquiditySingleSide, PoolActionAccess, PoolError,
+    RemoveLiquidityCtx,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveLiquiditySingleSideParameters {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// direction of the swap of withdrawn token, AtoB to receive only token b, BtoA to receive only token a
+    pub trade_direction: u8,
+    /// minimum output token amount, excluding transfer fee
+    pub minimum_amount_out: u64,
+}
+
+pub fn handle_remove_liquidity_single_side<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
+    params: RemoveLiquiditySingleSideParameters,
+) -> Result<()> {
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let access_validator = get_pool_access_validator(&pool, None, None, None)?;
+        require!(
+            access_validator.can_remove_liquidity(),
+            PoolError::PoolDisabled
+        );
+        require!(
+            access_validator.can_swap(&ctx.accounts.owner.key()),
+            PoolError::PoolDisabled
+        );
+    }
+    require_vaults_not_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_b_vault)?;
+
+    let RemoveLiquiditySingleSideParameters {
+        liquidity_delta,
+        trade_direction,
+        minimum_amount_out,
+    } = params;
+
+    let trade_direction =
+        TradeDirection::try_from(trade_direction).map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    require!(
+        liquidity_delta <= position.unlocked_liquidity && liquidity_delta > 0,
+        PoolError::InsufficientLiquidity
+    );
+
+    // update current pool reward & postion reward before any logic
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    update_position_extension_rewards(
+        &mut remaining_accounts,
+        ctx.accounts.pool.key(),
+        &pool,
+        ctx.accounts.position.key(),
+        &position,
+        current_time,
+    )?;
+
+    let ModifyLiquidityResult {
+        token_a_amount,
+        token_b_amount,
+    } = pool.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Down)?;
+
+    require!(
+        token_a_amount > 0 || token_b_amount > 0,
+        PoolError::AmountIsZero
+    );
+
+    pool.apply_remove_liquidity(&mut position, liquidity_delta)?;
+
+    // withdrawn input token stays in vault and is swapped into output token
+    let (amount_in, withdrawn_amount_out) = match trade_direction {
+        TradeDirection::AtoB => (token_a_amount, token_b_amount),
+        TradeDirection::BtoA => (token_b_amount, token_a_amount),
+    };
+
+    pool.update_pre_swap(current_time)?;
+
+    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
+    let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, false)?;
+
+    let swap_result = if amount_in > 0 {
+        let swap_result =
+            pool.get_swap_result(amount_in, fee_mode, trade_direction, current_point)?;
+        let sqrt_price = pool.sqrt_price;
+        pool.price_circuit_breaker.check_price_change(
+            clock.slot,
+            sqrt_price,
+            swap_result.next_sqrt_price,
+        )?;
+        pool.apply_swap_result(
+            amount_in,
+            &swap_result,
+            fee_mode,
+            trade_direction,
+            current_time,
+        )?;
+        Some(swap_result)
+    } else {
+        None
+    };
+
+    let amount_out = withdrawn_amount_out.safe_add(
+        swap_result
+            .as_ref()
+            .map(|swap_result| swap_result.output_amount)
+            .unwrap_or_default(),
+    )?;
+
+    let (
+        token_out_mint,
+        output_vault_account,
+        output_token_account,
+        output_program,
+        output_transfer_fee_cache,
+    ) = match trade_direction {
+        TradeDirection::AtoB => (
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            &mut pool.transfer_fee_cache_b,
+        ),
+        TradeDirection::BtoA => (
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            &mut pool.transfer_fee_cache_a,
+        ),
+    };
+
+    let transfer_fee_excluded_amount_out = calculate_transfer_fee_excluded_amount_cached(
+        token_out_mint,
+        output_transfer_fee_cache,
+        amount_out,
+        clock.epoch,
+    )?
+    .amount;
+    require!(
+        transfer_fee_excluded_amount_out >= minimum_amount_out,
+        PoolError::ExceededSlippage
+    );
+
+    // send to user
+    transfer_from_pool(
+        ctx.accounts.pool_authority.to_account_info(),
+        token_out_mint,
+        output_vault_account,
+        output_token_account,
+        output_program,
+        amount_out,
+        ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
+    )?;
+
+    emit_cpi!(EvtRemoveLiquiditySingleSide {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        params,
+        token_a_amount,
+        token_b_amount,
+        swap_result,
+        amount_out,
+        current_timestamp: current_time,
+        sequence,
+    });
+
+    Ok(())
+}
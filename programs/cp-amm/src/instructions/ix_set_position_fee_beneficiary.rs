@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{state::Position, EvtSetPositionFeeBeneficiary, PoolError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPositionFeeBeneficiaryCtx<'info> {
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+}
+
+pub fn handle_set_position_fee_beneficiary(
+    ctx: Context<SetPositionFeeBeneficiaryCtx>,
+    fee_beneficiary: Pubkey,
+) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    require!(
+        position.fee_beneficiary != fee_beneficiary,
+        PoolError::IdenticalFeeBeneficiary
+    );
+
+    let old_fee_beneficiary = position.fee_beneficiary;
+    position.fee_beneficiary = fee_beneficiary;
+
+    emit_cpi!(EvtSetPositionFeeBeneficiary {
+        pool: position.pool,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        old_fee_beneficiary,
+        new_fee_beneficiary: fee_beneficiary,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{state::Position, EvtSetPositionOperator, PoolError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPositionOperatorCtx<'info> {
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+}
+
+pub fn handle_set_position_operator(
+    ctx: Context<SetPositionOperatorCtx>,
+    operator: Pubkey,
+) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    require!(
+        position.operator != operator,
+        PoolError::IdenticalPositionOperator
+    );
+
+    let old_operator = position.operator;
+    position.operator = operator;
+
+    emit_cpi!(EvtSetPositionOperator {
+        pool: position.pool,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        old_operator,
+        new_operator: operator,
+    });
+
+    Ok(())
+}
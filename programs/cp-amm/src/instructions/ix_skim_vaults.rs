@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{event, state::Pool, token::get_token_account_amount};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SkimVaultsCtx<'info> {
+    #[account(mut, has_one = token_a_vault, has_one = token_b_vault)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault token account for token a
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token b
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Permissionless, distribute tokens transferred to vaults directly, eg: airdrops and mistaken transfers,
+/// to liquidity providers as lp fee. Liquidity of pools tracking fees is never zero as `MINIMUM_LIQUIDITY` is locked
+pub fn handle_skim_vaults(ctx: Context<SkimVaultsCtx>) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+
+    let (excess_a_amount, excess_b_amount) = pool.get_excess_vault_amounts(
+        get_token_account_amount(&ctx.accounts.token_a_vault)?,
+        get_token_account_amount(&ctx.accounts.token_b_vault)?,
+    )?;
+
+    pool.distribute_to_lp(excess_a_amount, excess_b_amount)?;
+
+    emit_cpi!(event::EvtSkimVaults {
+        pool: ctx.accounts.pool.key(),
+        signer: ctx.accounts.signer.key(),
+        excess_a_amount,
+        excess_b_amount,
+    });
+
+    Ok(())
+}
@@ -1,20 +1,36 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+        sysvar::instructions as sysvar_instructions,
+    },
+    ZeroCopy,
+};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     activation_handler::ActivationHandler,
     constants::seeds::POOL_AUTHORITY_PREFIX,
-    get_pool_access_validator,
+    get_pool_access_validator_at_point, load_pool_stats, load_trade_mining,
     params::swap::TradeDirection,
-    state::{fee::FeeMode, Pool},
-    token::{calculate_transfer_fee_excluded_amount, transfer_from_pool, transfer_from_user},
-    EvtSwap, PoolError,
+    safe_math::SafeMath,
+    state::{
+        fee::FeeMode, FeeDiscountRegistry, LaunchBuyTracker, Pool, Referral, SwapAllowlistEntry,
+        SwapVolume, VaultWhitelist,
+    },
+    token::{
+        calculate_transfer_fee_excluded_amount_cached, get_token_account_amount,
+        require_vaults_not_frozen, transfer_from_pool, transfer_from_user,
+    },
+    EvtSwap, PoolActionAccess, PoolError,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SwapParameters {
-    amount_in: u64,
-    minimum_amount_out: u64,
+    /// amount of input token, including transfer fee
+    pub amount_in: u64,
+    /// minimum amount of output token, excluding transfer fee
+    pub minimum_amount_out: u64,
 }
 
 #[event_cpi]
@@ -64,7 +80,8 @@ pub struct SwapCtx<'info> {
     /// Token b program
     pub token_b_program: Interface<'info, TokenInterface>,
 
-    /// referral token account
+    /// referral token account receiving referral fee in the swap, referrers with a referral account pass it
+    /// in remaining accounts instead to accrue referral fee
     #[account(mut)]
     pub referral_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
@@ -79,16 +96,192 @@ impl<'info> SwapCtx<'info> {
     }
 }
 
+/// Find the first account of the program of type `T` in remaining accounts by its discriminator.
+/// Other remaining accounts, such as extra accounts of transfer hooks, are skipped
+pub fn find_remaining_program_account<'c: 'info, 'info, T: ZeroCopy + Owner>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, T>>> {
+    for account in remaining_accounts {
+        if account.owner != &crate::ID || !account.try_borrow_data()?.starts_with(T::DISCRIMINATOR)
+        {
+            continue;
+        }
+        return Ok(Some(AccountLoader::try_from(account)?));
+    }
+    Ok(None)
+}
+
+/// Optional accounts of swap, passed in remaining accounts so the account list of `swap` stays the same
+/// for existing callers. Each account is looked up by its discriminator, or by address for the instructions sysvar
+pub struct SwapRemainingAccounts<'info> {
+    /// referral account accruing referral fee of the referrer
+    pub referral: Option<AccountLoader<'info, Referral>>,
+    /// fee discount registry of pool
+    pub fee_discount_registry: Option<AccountLoader<'info, FeeDiscountRegistry>>,
+    /// vault whitelist of pool, required for whitelisted vaults swapping before activation point
+    pub vault_whitelist: Option<AccountLoader<'info, VaultWhitelist>>,
+    /// swap allowlist entry of payer, required for permissioned pool
+    pub swap_allowlist_entry: Option<AccountLoader<'info, SwapAllowlistEntry>>,
+    /// launch buy tracker of payer, required for buys while launch guard of pool is active
+    pub launch_buy_tracker: Option<AccountLoader<'info, LaunchBuyTracker>>,
+    /// instructions sysvar, required while cpi guard of pool is active
+    pub instructions_sysvar: Option<&'info AccountInfo<'info>>,
+}
+
+impl<'info> SwapRemainingAccounts<'info> {
+    pub fn load<'c: 'info>(
+        remaining_accounts: &'c [AccountInfo<'info>],
+        pool: Pubkey,
+    ) -> Result<Self> {
+        let referral = find_remaining_program_account::<Referral>(remaining_accounts)?;
+        if let Some(referral) = &referral {
+            require!(referral.load()?.pool == pool, PoolError::InvalidReferral);
+        }
+        let fee_discount_registry =
+            find_remaining_program_account::<FeeDiscountRegistry>(remaining_accounts)?;
+        if let Some(fee_discount_registry) = &fee_discount_registry {
+            require!(
+                fee_discount_registry.load()?.pool == pool,
+                PoolError::InvalidFeeDiscountAccount
+            );
+        }
+        let vault_whitelist = find_remaining_program_account::<VaultWhitelist>(remaining_accounts)?;
+        if let Some(vault_whitelist) = &vault_whitelist {
+            require!(
+                vault_whitelist.load()?.pool == pool,
+                PoolError::InvalidVaultWhitelist
+            );
+        }
+        let swap_allowlist_entry =
+            find_remaining_program_account::<SwapAllowlistEntry>(remaining_accounts)?;
+        if let Some(swap_allowlist_entry) = &swap_allowlist_entry {
+            require!(
+                swap_allowlist_entry.load()?.pool == pool,
+                PoolError::InvalidSwapAllowlistEntry
+            );
+        }
+        let launch_buy_tracker =
+            find_remaining_program_account::<LaunchBuyTracker>(remaining_accounts)?;
+        if let Some(launch_buy_tracker) = &launch_buy_tracker {
+            require!(
+                launch_buy_tracker.load()?.pool == pool,
+                PoolError::InvalidLaunchBuyTracker
+            );
+        }
+        let instructions_sysvar = remaining_accounts
+            .iter()
+            .find(|account| account.key == &sysvar_instructions::ID);
+
+        Ok(Self {
+            referral,
+            fee_discount_registry,
+            vault_whitelist,
+            swap_allowlist_entry,
+            launch_buy_tracker,
+            instructions_sysvar,
+        })
+    }
+
+    /// Accumulate buy amount of payer in the launch buy tracker, fails over max buy amount per wallet
+    pub fn accumulate_launch_buy_amount(
+        &self,
+        payer: Pubkey,
+        amount: u64,
+        max_buy_amount: u64,
+    ) -> Result<()> {
+        let launch_buy_tracker = self
+            .launch_buy_tracker
+            .as_ref()
+            .ok_or(PoolError::InvalidLaunchBuyTracker)?;
+        let mut launch_buy_tracker = launch_buy_tracker.load_mut()?;
+        require!(
+            launch_buy_tracker.wallet == payer,
+            PoolError::InvalidLaunchBuyTracker
+        );
+        launch_buy_tracker.accumulate_buy_amount(amount, max_buy_amount)
+    }
+
+    /// Require the swap to be a top-level instruction of cp-amm, not invoked by another program
+    pub fn require_top_level_swap(&self) -> Result<()> {
+        let instructions_sysvar = self
+            .instructions_sysvar
+            .ok_or(PoolError::SwapCpiGuardActive)?;
+        let current_index = sysvar_instructions::load_current_index_checked(instructions_sysvar)?;
+        let current_instruction = sysvar_instructions::load_instruction_at_checked(
+            current_index.into(),
+            instructions_sysvar,
+        )?;
+        require!(
+            current_instruction.program_id == crate::ID
+                && get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            PoolError::SwapCpiGuardActive
+        );
+        Ok(())
+    }
+
+    /// Get the trade fee discount of payer from the fee discount registry, if any. Token account of payer
+    /// holding the discount token is looked up in remaining accounts by its mint and owner
+    pub fn get_fee_discount_bps<'c: 'info>(
+        &self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        payer: Pubkey,
+    ) -> Result<u16> {
+        let Some(fee_discount_registry) = &self.fee_discount_registry else {
+            return Ok(0);
+        };
+        let fee_discount_registry = fee_discount_registry.load()?;
+        let fee_discount_token_account = remaining_accounts
+            .iter()
+            .filter(|account| {
+                account.owner == &anchor_spl::token::ID
+                    || account.owner == &anchor_spl::token_2022::ID
+            })
+            .filter_map(|account| InterfaceAccount::<TokenAccount>::try_from(account).ok())
+            .find(|token_account| {
+                token_account.mint == fee_discount_registry.mint && token_account.owner == payer
+            })
+            .ok_or(PoolError::InvalidFeeDiscountAccount)?;
+        Ok(fee_discount_registry.get_fee_discount_bps(fee_discount_token_account.amount))
+    }
+}
+
 // TODO impl swap exact out
-pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()> {
-    {
+pub fn handle_swap<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+    params: SwapParameters,
+) -> Result<()> {
+    // read the clock once for the whole swap
+    let clock = Clock::get()?;
+    let remaining = SwapRemainingAccounts::load(ctx.remaining_accounts, ctx.accounts.pool.key())?;
+    let current_point = {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let current_point =
+            ActivationHandler::get_current_point_from_clock(pool.activation_type, &clock)?;
+        let vault_whitelist = match &remaining.vault_whitelist {
+            Some(vault_whitelist) => Some(vault_whitelist.load()?),
+            None => None,
+        };
+        let swap_allowlist_entry = match &remaining.swap_allowlist_entry {
+            Some(swap_allowlist_entry) => Some(swap_allowlist_entry.load()?),
+            None => None,
+        };
+        let access_validator = get_pool_access_validator_at_point(
+            &pool,
+            vault_whitelist.as_deref(),
+            swap_allowlist_entry.as_deref(),
+            None,
+            current_point,
+        )?;
         require!(
             access_validator.can_swap(&ctx.accounts.payer.key()),
             PoolError::PoolDisabled
         );
-    }
+        if pool.is_cpi_guard_active(current_point)? {
+            remaining.require_top_level_swap()?;
+        }
+        current_point
+    };
+    require_vaults_not_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_b_vault)?;
 
     let SwapParameters {
         amount_in,
@@ -122,21 +315,42 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         ),
     };
 
+    // send to reserve first, pool is credited with the amount received by the vault,
+    // so transfer fee and transfer hook of token 2022 can't make accounting drift from reserves
+    let input_vault_amount = input_vault_account.amount;
+    transfer_from_user(
+        &ctx.accounts.payer,
+        token_in_mint,
+        &ctx.accounts.input_token_account,
+        &input_vault_account,
+        input_program,
+        amount_in,
+        ctx.remaining_accounts,
+    )?;
     let transfer_fee_excluded_amount_in =
-        calculate_transfer_fee_excluded_amount(&token_in_mint, amount_in)?.amount;
+        get_token_account_amount(input_vault_account)?.safe_sub(input_vault_amount)?;
 
     require!(transfer_fee_excluded_amount_in > 0, PoolError::AmountIsZero);
 
-    let has_referral = ctx.accounts.referral_token_account.is_some();
+    // referral fee either goes to the referral token account or accrues to the referral account
+    require!(
+        ctx.accounts.referral_token_account.is_none() || remaining.referral.is_none(),
+        PoolError::InvalidReferral
+    );
+    let has_referral =
+        ctx.accounts.referral_token_account.is_some() || remaining.referral.is_some();
+    let fee_discount_bps =
+        remaining.get_fee_discount_bps(ctx.remaining_accounts, ctx.accounts.payer.key())?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
 
     // update for dynamic fee reference
-    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let current_timestamp = clock.unix_timestamp as u64;
     pool.update_pre_swap(current_timestamp)?;
 
-    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
-    let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, has_referral)?;
+    let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, has_referral)?
+        .with_fee_discount(fee_discount_bps);
 
     let swap_result = pool.get_swap_result(
         transfer_fee_excluded_amount_in,
@@ -145,25 +359,107 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         current_point,
     )?;
 
-    let transfer_fee_excluded_amount_out =
-        calculate_transfer_fee_excluded_amount(&token_out_mint, swap_result.output_amount)?.amount;
+    if trade_direction == TradeDirection::BtoA {
+        let activation_point = pool.activation_point;
+        pool.swap_rate_limiter.accumulate(
+            activation_point,
+            current_point,
+            transfer_fee_excluded_amount_in,
+        )?;
+
+        if pool
+            .launch_guard
+            .is_active(activation_point, current_point)?
+        {
+            remaining.accumulate_launch_buy_amount(
+                ctx.accounts.payer.key(),
+                transfer_fee_excluded_amount_in,
+                pool.launch_guard.max_buy_amount_per_wallet,
+            )?;
+        }
+    }
+
+    let output_transfer_fee_cache = match trade_direction {
+        TradeDirection::AtoB => &mut pool.transfer_fee_cache_b,
+        TradeDirection::BtoA => &mut pool.transfer_fee_cache_a,
+    };
+    let transfer_fee_excluded_amount_out = calculate_transfer_fee_excluded_amount_cached(
+        &token_out_mint,
+        output_transfer_fee_cache,
+        swap_result.output_amount,
+        clock.epoch,
+    )?
+    .amount;
     require!(
         transfer_fee_excluded_amount_out >= minimum_amount_out,
         PoolError::ExceededSlippage
     );
 
-    pool.apply_swap_result(&swap_result, fee_mode, current_timestamp)?;
+    let sqrt_price = pool.sqrt_price;
+    pool.price_circuit_breaker.check_price_change(
+        clock.slot,
+        sqrt_price,
+        swap_result.next_sqrt_price,
+    )?;
 
-    // send to reserve
-    transfer_from_user(
-        &ctx.accounts.payer,
-        token_in_mint,
-        &ctx.accounts.input_token_account,
-        &input_vault_account,
-        input_program,
-        amount_in,
+    pool.apply_swap_result(
+        transfer_fee_excluded_amount_in,
+        &swap_result,
+        fee_mode,
+        trade_direction,
+        current_timestamp,
     )?;
+
+    // accrue to referral, claimable by referrer later
+    if let Some(referral) = &remaining.referral {
+        referral
+            .load_mut()?
+            .accumulate_fee(swap_result.referral_fee, fee_mode.fees_on_token_a)?;
+        pool.accumulate_referral_fee(swap_result.referral_fee, fee_mode.fees_on_token_a)?;
+    }
+
+    let total_fee = swap_result
+        .lp_fee
+        .saturating_add(swap_result.protocol_fee)
+        .saturating_add(swap_result.partner_fee)
+        .saturating_add(swap_result.referral_fee);
+
+    // record volume and fee in pool stats if it is passed in remaining accounts
+    if let Some(pool_stats) = load_pool_stats(ctx.remaining_accounts, ctx.accounts.pool.key())? {
+        let (amount_a, amount_b) = match trade_direction {
+            TradeDirection::AtoB => (transfer_fee_excluded_amount_in, swap_result.output_amount),
+            TradeDirection::BtoA => (swap_result.output_amount, transfer_fee_excluded_amount_in),
+        };
+        pool_stats.load_mut()?.record_swap(
+            current_timestamp,
+            &SwapVolume {
+                amount_a,
+                amount_b,
+                lp_fee: swap_result.lp_fee,
+                total_fee,
+                fees_on_token_a: fee_mode.fees_on_token_a,
+            },
+            pool.sqrt_price,
+        );
+    }
+
+    // accrue trade mining rewards to payer if trade mining and trader reward of payer are passed in remaining accounts
+    if let Some((trade_mining, trader_reward)) = load_trade_mining(
+        ctx.remaining_accounts,
+        ctx.accounts.pool.key(),
+        ctx.accounts.payer.key(),
+    )? {
+        let accrued_amount =
+            trade_mining
+                .load_mut()?
+                .accrue(total_fee, fee_mode.fees_on_token_a, current_timestamp);
+        trader_reward
+            .load_mut()?
+            .accumulate(accrued_amount, total_fee, fee_mode.fees_on_token_a);
+    }
+
     // send to user
+    let output_vault_amount = output_vault_account.amount;
     transfer_from_pool(
         ctx.accounts.pool_authority.to_account_info(),
         &token_out_mint,
@@ -172,30 +468,39 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         output_program,
         swap_result.output_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
-    // send to referral
-    if has_referral {
-        if fee_mode.fees_on_token_a {
-            transfer_from_pool(
-                ctx.accounts.pool_authority.to_account_info(),
+    require!(
+        output_vault_amount.safe_sub(get_token_account_amount(output_vault_account)?)?
+            == swap_result.output_amount,
+        PoolError::VaultBalanceMismatch
+    );
+
+    // send to referral token account
+    if let Some(referral_token_account) = &ctx.accounts.referral_token_account {
+        let (token_mint, token_vault, token_program) = if fee_mode.fees_on_token_a {
+            (
                 &ctx.accounts.token_a_mint,
                 &ctx.accounts.token_a_vault,
-                &ctx.accounts.referral_token_account.clone().unwrap(),
                 &ctx.accounts.token_a_program,
-                swap_result.referral_fee,
-                ctx.bumps.pool_authority,
-            )?;
+            )
         } else {
-            transfer_from_pool(
-                ctx.accounts.pool_authority.to_account_info(),
+            (
                 &ctx.accounts.token_b_mint,
                 &ctx.accounts.token_b_vault,
-                &ctx.accounts.referral_token_account.clone().unwrap(),
                 &ctx.accounts.token_b_program,
-                swap_result.referral_fee,
-                ctx.bumps.pool_authority,
-            )?;
-        }
+            )
+        };
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            token_mint,
+            token_vault,
+            referral_token_account,
+            token_program,
+            swap_result.referral_fee,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
     }
 
     emit_cpi!(EvtSwap {
@@ -206,6 +511,7 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         has_referral,
         actual_amount_in: transfer_fee_excluded_amount_in,
         current_timestamp,
+        sequence,
     });
 
     Ok(())
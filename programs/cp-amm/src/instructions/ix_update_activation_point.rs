@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, Pool, ProgramAuthority},
+    EvtUpdateActivationPoint, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateActivationPointCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin, partner of pool or creator of customizable pool
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.is_activation_point_authority(signer.key()) @ PoolError::InvalidActivationPointAuthority
+    )]
+    pub signer: Signer<'info>,
+}
+
+/// Delay activation point of a pool that is not activated yet, activation point can't be moved earlier
+pub fn handle_update_activation_point(
+    ctx: Context<UpdateActivationPointCtx>,
+    new_activation_point: u64,
+) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let old_activation_point = pool.activation_point;
+    require!(
+        new_activation_point > old_activation_point
+            && new_activation_point
+                <= ActivationHandler::get_max_activation_point(pool.activation_type)?,
+        PoolError::UnableToModifyActivationPoint
+    );
+
+    let (curr_point, buffer_duration) =
+        ActivationHandler::get_current_point_and_buffer_duration(pool.activation_type)?;
+    let activation_handler = ActivationHandler {
+        curr_point,
+        activation_point: old_activation_point,
+        buffer_duration,
+        whitelisted_vault: pool.whitelisted_vault,
+    };
+    activation_handler.validate_update_activation_point(new_activation_point)?;
+
+    pool.activation_point = new_activation_point;
+
+    emit_cpi!(EvtUpdateActivationPoint {
+        pool: ctx.accounts.pool.key(),
+        signer: ctx.accounts.signer.key(),
+        old_activation_point,
+        new_activation_point,
+    });
+
+    Ok(())
+}
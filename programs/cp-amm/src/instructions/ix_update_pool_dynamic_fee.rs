@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{activation::DYNAMIC_FEE_UPDATE_DELAY, seeds::PROGRAM_AUTHORITY_PREFIX},
+    safe_math::SafeMath,
+    state::{AuthorityRole, Pool, ProgramAuthority},
+    EvtUpdatePoolDynamicFee, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct UpdateDynamicFeeParameters {
+    /// period after which volatility reference is reset
+    pub decay_period: u16,
+    /// decay rate of volatility reference in bps
+    pub reduction_factor: u16,
+    /// scaling factor of variable fee
+    pub variable_fee_control: u32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePoolDynamicFeeCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidDynamicFeeAuthority
+    )]
+    pub signer: Signer<'info>,
+}
+
+/// Queue an update of dynamic fee parameters, it takes effect after `DYNAMIC_FEE_UPDATE_DELAY`
+pub fn handle_update_pool_dynamic_fee(
+    ctx: Context<UpdatePoolDynamicFeeCtx>,
+    params: UpdateDynamicFeeParameters,
+) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    // matured update must not be replaced
+    pool.apply_pending_fee_updates(current_timestamp)?;
+
+    let activation_point = current_timestamp.safe_add(DYNAMIC_FEE_UPDATE_DELAY)?;
+    pool.pool_fees.queue_dynamic_fee_update(
+        params.decay_period,
+        params.reduction_factor,
+        params.variable_fee_control,
+        activation_point,
+    )?;
+
+    emit_cpi!(EvtUpdatePoolDynamicFee {
+        pool: ctx.accounts.pool.key(),
+        signer: ctx.accounts.signer.key(),
+        params,
+        activation_point,
+    });
+
+    Ok(())
+}
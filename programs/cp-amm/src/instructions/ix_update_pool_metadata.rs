@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, Pool, PoolMetadata, ProgramAuthority},
+    EvtUpdatePoolMetadata, PoolError, PoolMetadataParameters,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePoolMetadataCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub pool_metadata: AccountLoader<'info, PoolMetadata>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidPoolMetadataAuthority
+    )]
+    pub signer: Signer<'info>,
+}
+
+/// Replace all fields of pool metadata
+pub fn handle_update_pool_metadata(
+    ctx: Context<UpdatePoolMetadataCtx>,
+    params: PoolMetadataParameters,
+) -> Result<()> {
+    let PoolMetadataParameters {
+        name,
+        symbol,
+        uri,
+        project_url,
+    } = params;
+
+    let mut pool_metadata = ctx.accounts.pool_metadata.load_mut()?;
+    pool_metadata.update(&name, &symbol, &uri, &project_url)?;
+
+    emit_cpi!(EvtUpdatePoolMetadata {
+        pool: ctx.accounts.pool.key(),
+        pool_metadata: ctx.accounts.pool_metadata.key(),
+        signer: ctx.accounts.signer.key(),
+        name,
+        symbol,
+        uri,
+        project_url,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    load_reward_extension_for_index,
+    state::{get_reward_info_mut, validate_emission_schedule, Pool, ProgramAuthority, RewardInfo},
+    EvtUpdateRewardEmissionSchedule, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRewardEmissionScheduleCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or current funder of the reward
+    pub signer: Signer<'info>,
+}
+
+impl<'info> UpdateRewardEmissionScheduleCtx<'info> {
+    fn validate(
+        &self,
+        reward_info: &RewardInfo,
+        emission_period: u32,
+        emission_decay_bps: u16,
+    ) -> Result<()> {
+        require!(reward_info.initialized(), PoolError::RewardUninitialized);
+
+        let program_authority = self.program_authority.load()?;
+        require!(
+            reward_info.is_valid_funder(self.signer.key(), &program_authority),
+            PoolError::InvalidFunder
+        );
+
+        validate_emission_schedule(
+            reward_info.reward_duration,
+            emission_period,
+            emission_decay_bps,
+        )?;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        // only allow update emission schedule if previous reward has been finished
+        require!(
+            reward_info.reward_duration_end < (current_time as u64),
+            PoolError::RewardCampaignInProgress
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handle_update_reward_emission_schedule<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateRewardEmissionScheduleCtx<'info>>,
+    reward_index: u8,
+    emission_period: u32,
+    emission_decay_bps: u16,
+) -> Result<()> {
+    let index: usize = reward_index
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let reward_extension =
+        load_reward_extension_for_index(&mut remaining_accounts, ctx.accounts.pool.key(), index)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let mut reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| reward_extension.load_mut())
+        .transpose()?;
+    // accrue rewards until now, so emission before the update is not priced by the new schedule
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    pool.update_rewards(current_time)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+    }
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
+
+    ctx.accounts
+        .validate(reward_info, emission_period, emission_decay_bps)?;
+
+    reward_info.emission_period = emission_period;
+    reward_info.emission_decay_bps = emission_decay_bps;
+
+    emit_cpi!(EvtUpdateRewardEmissionSchedule {
+        pool: ctx.accounts.pool.key(),
+        reward_index,
+        emission_period,
+        emission_decay_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    load_reward_extension_for_index,
+    state::{get_reward_info_mut, Pool, ProgramAuthority, RewardInfo},
+    EvtUpdateRewardFunder, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRewardFunderCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or current funder of the reward
+    pub signer: Signer<'info>,
+}
+
+impl<'info> UpdateRewardFunderCtx<'info> {
+    fn validate(&self, reward_info: &RewardInfo, new_funder: Pubkey) -> Result<()> {
+        require!(reward_info.initialized(), PoolError::RewardUninitialized);
+
+        let program_authority = self.program_authority.load()?;
+        require!(
+            reward_info.is_valid_funder(self.signer.key(), &program_authority),
+            PoolError::InvalidFunder
+        );
+
+        require!(reward_info.funder != new_funder, PoolError::IdenticalFunder);
+
+        Ok(())
+    }
+}
+
+pub fn handle_update_reward_funder<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateRewardFunderCtx<'info>>,
+    reward_index: u8,
+    new_funder: Pubkey,
+) -> Result<()> {
+    let index: usize = reward_index
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let reward_extension =
+        load_reward_extension_for_index(&mut remaining_accounts, ctx.accounts.pool.key(), index)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let mut reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| reward_extension.load_mut())
+        .transpose()?;
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
+
+    ctx.accounts.validate(reward_info, new_funder)?;
+
+    let old_funder = reward_info.funder;
+    reward_info.funder = new_funder;
+
+    emit_cpi!(EvtUpdateRewardFunder {
+        pool: ctx.accounts.pool.key(),
+        reward_index,
+        old_funder,
+        new_funder,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{event::EvtUpdateRewards, load_reward_extension, state::pool::Pool};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRewardsCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+}
+
+pub fn handle_update_rewards<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateRewardsCtx<'info>>,
+) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    // update pool reward
+    pool.update_rewards(current_time)?;
+
+    // update extension reward
+    if pool.has_reward_extension() {
+        let mut remaining_accounts = &ctx.remaining_accounts[..];
+        let reward_extension =
+            load_reward_extension(&mut remaining_accounts, ctx.accounts.pool.key())?;
+        reward_extension
+            .load_mut()?
+            .update_rewards(pool.get_reward_liquidity()?, current_time)?;
+    }
+
+    emit_cpi!(EvtUpdateRewards {
+        pool: ctx.accounts.pool.key(),
+        current_time,
+    });
+
+    Ok(())
+}
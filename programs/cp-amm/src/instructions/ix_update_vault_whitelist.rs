@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::PROGRAM_AUTHORITY_PREFIX,
+    state::{AuthorityRole, Pool, ProgramAuthority, VaultWhitelist},
+    EvtUpdateVaultWhitelist, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateVaultWhitelistCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub vault_whitelist: AccountLoader<'info, VaultWhitelist>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// admin or partner of pool
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::ConfigAdmin, signer.key()) || pool.load()?.partner == signer.key() @ PoolError::InvalidVaultWhitelistAuthority
+    )]
+    pub signer: Signer<'info>,
+}
+
+/// Replace vaults of vault whitelist, empty vaults leave only the whitelisted vault of pool
+pub fn handle_update_vault_whitelist(
+    ctx: Context<UpdateVaultWhitelistCtx>,
+    vaults: Vec<Pubkey>,
+) -> Result<()> {
+    let mut vault_whitelist = ctx.accounts.vault_whitelist.load_mut()?;
+    vault_whitelist.update_vaults(&vaults)?;
+
+    emit_cpi!(EvtUpdateVaultWhitelist {
+        pool: ctx.accounts.pool.key(),
+        vault_whitelist: ctx.accounts.vault_whitelist.key(),
+        signer: ctx.accounts.signer.key(),
+        vaults,
+    });
+
+    Ok(())
+}
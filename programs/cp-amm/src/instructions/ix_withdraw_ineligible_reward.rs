@@ -2,10 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constants::{seeds::POOL_AUTHORITY_PREFIX, NUM_REWARDS},
+    constants::seeds::{POOL_AUTHORITY_PREFIX, PROGRAM_AUTHORITY_PREFIX},
     error::PoolError,
     event::EvtWithdrawIneligibleReward,
-    state::pool::Pool,
+    load_reward_extension_for_index,
+    state::{get_reward_info_mut, Pool, ProgramAuthority, RewardInfo},
     token::transfer_from_pool,
 };
 
@@ -27,18 +28,16 @@ pub struct WithdrawIneligibleRewardCtx<'info> {
     #[account(mut)]
     pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX.as_ref()], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
     pub funder: Signer<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> WithdrawIneligibleRewardCtx<'info> {
-    fn validate(&self, reward_index: usize) -> Result<()> {
-        let pool = self.pool.load()?;
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
-
-        let reward_info = &pool.reward_infos[reward_index];
-
+    fn validate(&self, reward_info: &RewardInfo) -> Result<()> {
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
 
         require!(
@@ -46,8 +45,9 @@ impl<'info> WithdrawIneligibleRewardCtx<'info> {
             PoolError::InvalidRewardVault
         );
 
+        let program_authority = self.program_authority.load()?;
         require!(
-            reward_info.is_valid_funder(self.funder.key()),
+            reward_info.is_valid_funder(self.funder.key(), &program_authority),
             PoolError::InvalidFunder
         );
 
@@ -61,23 +61,37 @@ impl<'info> WithdrawIneligibleRewardCtx<'info> {
     }
 }
 
-pub fn handle_withdraw_ineligible_reward(
-    ctx: Context<WithdrawIneligibleRewardCtx>,
+pub fn handle_withdraw_ineligible_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, WithdrawIneligibleRewardCtx<'info>>,
     reward_index: u8,
 ) -> Result<()> {
     let index: usize = reward_index
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index)?;
+
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let reward_extension =
+        load_reward_extension_for_index(&mut remaining_accounts, ctx.accounts.pool.key(), index)?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let mut reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| reward_extension.load_mut())
+        .transpose()?;
 
     let current_time = Clock::get()?.unix_timestamp as u64;
 
     // update pool reward
     pool.update_rewards(current_time)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+    }
+
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
+    ctx.accounts.validate(reward_info)?;
 
-    let ineligible_reward = pool.claim_ineligible_reward(index)?;
+    let ineligible_reward = reward_info.claim_ineligible_reward()?;
 
     // transfer rewards to funder
     if ineligible_reward > 0 {
@@ -89,6 +103,7 @@ pub fn handle_withdraw_ineligible_reward(
             &ctx.accounts.token_program,
             ineligible_reward,
             ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
         )?;
     }
 
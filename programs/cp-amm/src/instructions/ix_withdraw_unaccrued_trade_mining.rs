@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::seeds::{POOL_AUTHORITY_PREFIX, PROGRAM_AUTHORITY_PREFIX},
+    state::{AuthorityRole, Pool, ProgramAuthority, TradeMining},
+    token::transfer_from_pool,
+    EvtWithdrawUnaccruedTradeMining, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawUnaccruedTradeMiningCtx<'info> {
+    /// CHECK: pool authority
+    #[account(seeds = [POOL_AUTHORITY_PREFIX], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool, has_one = vault @ PoolError::InvalidRewardVault)]
+    pub trade_mining: AccountLoader<'info, TradeMining>,
+
+    /// The vault token account for trade mining reward
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = vault.mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(seeds = [PROGRAM_AUTHORITY_PREFIX], bump)]
+    pub program_authority: AccountLoader<'info, ProgramAuthority>,
+
+    /// reward admin or funder of trade mining
+    #[account(
+        constraint = program_authority.load()?.has_role(AuthorityRole::RewardAdmin, funder.key()) || trade_mining.load()?.funder == funder.key() @ PoolError::InvalidAdmin
+    )]
+    pub funder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw the budget never accrued to traders once trade mining ended. Accrued rewards stay in the vault
+/// for traders to claim
+pub fn handle_withdraw_unaccrued_trade_mining<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, WithdrawUnaccruedTradeMiningCtx<'info>>,
+) -> Result<()> {
+    let amount = {
+        let mut trade_mining = ctx.accounts.trade_mining.load_mut()?;
+        let current_time = Clock::get()?.unix_timestamp as u64;
+        require!(
+            trade_mining.is_ended(current_time),
+            PoolError::RewardNotEnded
+        );
+        trade_mining.withdraw_remaining_budget()
+    };
+
+    if amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.funder_token_account,
+            &ctx.accounts.token_program,
+            amount,
+            ctx.bumps.pool_authority,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtWithdrawUnaccruedTradeMining {
+        pool: ctx.accounts.pool.key(),
+        trade_mining: ctx.accounts.trade_mining.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -2,14 +2,30 @@ pub mod admin;
 pub use admin::*;
 pub mod ix_swap;
 pub use ix_swap::*;
+pub mod ix_create_referral;
+pub use ix_create_referral::*;
+pub mod ix_claim_referral_fee;
+pub use ix_claim_referral_fee::*;
 pub mod ix_add_liquidity;
 pub use ix_add_liquidity::*;
+pub mod ix_add_liquidity_by_amounts;
+pub use ix_add_liquidity_by_amounts::*;
 pub mod ix_create_position;
 pub use ix_create_position::*;
 pub mod ix_remove_liquidity;
 pub use ix_remove_liquidity::*;
+pub mod ix_remove_liquidity_single_side;
+pub use ix_remove_liquidity_single_side::*;
 pub mod ix_claim_position_fee;
 pub use ix_claim_position_fee::*;
+pub mod ix_claim_position_fee_with_receipt;
+pub use ix_claim_position_fee_with_receipt::*;
+pub mod ix_mint_fee_receipt;
+pub use ix_mint_fee_receipt::*;
+pub mod ix_set_position_fee_beneficiary;
+pub use ix_set_position_fee_beneficiary::*;
+pub mod ix_set_position_operator;
+pub use ix_set_position_operator::*;
 pub mod initialize_pool;
 pub use initialize_pool::*;
 pub mod ix_lock_position;
@@ -18,13 +34,89 @@ pub mod ix_refresh_vesting;
 pub use ix_refresh_vesting::*;
 pub mod ix_permanent_lock_position;
 pub use ix_permanent_lock_position::*;
+pub mod ix_permanent_lock_vesting;
+pub use ix_permanent_lock_vesting::*;
 pub mod ix_claim_reward;
 pub use ix_claim_reward::*;
+pub mod ix_create_reward_vesting_escrow;
+pub use ix_create_reward_vesting_escrow::*;
+pub mod ix_claim_vested_reward;
+pub use ix_claim_vested_reward::*;
+pub mod ix_claim_all;
+pub use ix_claim_all::*;
 pub mod partner;
 pub use partner::*;
+pub mod ix_initialize_reward;
+pub use ix_initialize_reward::*;
 pub mod ix_fund_reward;
 pub use ix_fund_reward::*;
 pub mod ix_withdraw_ineligible_reward;
 pub use ix_withdraw_ineligible_reward::*;
+pub mod ix_update_reward_funder;
+pub use ix_update_reward_funder::*;
+pub mod ix_update_reward_emission_schedule;
+pub use ix_update_reward_emission_schedule::*;
 pub mod ix_close_position;
 pub use ix_close_position::*;
+pub mod ix_merge_positions;
+pub use ix_merge_positions::*;
+pub mod ix_update_pool_dynamic_fee;
+pub use ix_update_pool_dynamic_fee::*;
+pub mod ix_cancel_pending_update;
+pub use ix_cancel_pending_update::*;
+pub mod ix_disable_frozen_pool;
+pub use ix_disable_frozen_pool::*;
+pub mod ix_skim_vaults;
+pub use ix_skim_vaults::*;
+pub mod ix_donate;
+pub use ix_donate::*;
+pub mod ix_create_vault_whitelist;
+pub use ix_create_vault_whitelist::*;
+pub mod ix_update_vault_whitelist;
+pub use ix_update_vault_whitelist::*;
+pub mod ix_create_pool_metadata;
+pub use ix_create_pool_metadata::*;
+pub mod ix_update_pool_metadata;
+pub use ix_update_pool_metadata::*;
+pub mod ix_create_swap_allowlist_entry;
+pub use ix_create_swap_allowlist_entry::*;
+pub mod ix_close_swap_allowlist_entry;
+pub use ix_close_swap_allowlist_entry::*;
+pub mod ix_create_lp_allowlist_entry;
+pub use ix_create_lp_allowlist_entry::*;
+pub mod ix_close_lp_allowlist_entry;
+pub use ix_close_lp_allowlist_entry::*;
+pub mod ix_create_launch_buy_tracker;
+pub use ix_create_launch_buy_tracker::*;
+pub mod ix_create_pool_stats;
+pub use ix_create_pool_stats::*;
+pub mod ix_initialize_trade_mining;
+pub use ix_initialize_trade_mining::*;
+pub mod ix_fund_trade_mining;
+pub use ix_fund_trade_mining::*;
+pub mod ix_withdraw_unaccrued_trade_mining;
+pub use ix_withdraw_unaccrued_trade_mining::*;
+pub mod ix_create_trader_reward;
+pub use ix_create_trader_reward::*;
+pub mod ix_claim_trade_mining_reward;
+pub use ix_claim_trade_mining_reward::*;
+pub mod ix_claim_insurance_fund;
+pub use ix_claim_insurance_fund::*;
+pub mod ix_buyback_and_burn;
+pub use ix_buyback_and_burn::*;
+pub mod ix_update_activation_point;
+pub use ix_update_activation_point::*;
+pub mod ix_update_rewards;
+pub use ix_update_rewards::*;
+pub mod reward_extension_accounts;
+pub use reward_extension_accounts::*;
+pub mod ix_create_position_reward_extension;
+pub use ix_create_position_reward_extension::*;
+pub mod ix_get_pool_state;
+pub use ix_get_pool_state::*;
+pub mod ix_get_position_value;
+pub use ix_get_position_value::*;
+pub mod ix_get_position_pending;
+pub use ix_get_position_pending::*;
+pub mod ix_migrate_pool;
+pub use ix_migrate_pool::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::Config, EvtAcceptPoolCreatorAuthority};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptPoolCreatorAuthorityCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    /// pending pool_creator_authority of config
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_accept_pool_creator_authority(
+    ctx: Context<AcceptPoolCreatorAuthorityCtx>,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let old_pool_creator_authority = config.pool_creator_authority;
+    config.accept_pool_creator_authority(ctx.accounts.signer.key())?;
+
+    emit_cpi!(EvtAcceptPoolCreatorAuthority {
+        config: ctx.accounts.config.key(),
+        old_pool_creator_authority,
+        new_pool_creator_authority: ctx.accounts.signer.key(),
+    });
+
+    Ok(())
+}
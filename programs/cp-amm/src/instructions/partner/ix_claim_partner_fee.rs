@@ -2,8 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constants::seeds::POOL_AUTHORITY_PREFIX, state::Pool, token::transfer_from_pool,
-    EvtClaimPartnerFee,
+    constants::seeds::POOL_AUTHORITY_PREFIX,
+    state::{PartnerFeeReceiver, Pool},
+    token::transfer_from_pool,
+    EvtClaimPartnerFee, PoolError,
 };
 
 /// Accounts for partner to claim fees
@@ -20,7 +22,6 @@ pub struct ClaimPartnerFeesCtx<'info> {
         has_one = token_b_vault,
         has_one = token_a_mint,
         has_one = token_b_mint,
-        has_one = partner,
     )]
     pub pool: AccountLoader<'info, Pool>,
 
@@ -46,7 +47,14 @@ pub struct ClaimPartnerFeesCtx<'info> {
     /// The mint of token b
     pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub partner: Signer<'info>,
+    /// partner, or operator of partner fee receivers
+    pub signer: Signer<'info>,
+
+    /// Partner fee receiver of token a, token_a_account must be its registered token account
+    pub partner_fee_receiver_a: Option<AccountLoader<'info, PartnerFeeReceiver>>,
+
+    /// Partner fee receiver of token b, token_b_account must be its registered token account
+    pub partner_fee_receiver_b: Option<AccountLoader<'info, PartnerFeeReceiver>>,
 
     /// Token a program
     pub token_a_program: Interface<'info, TokenInterface>,
@@ -56,12 +64,30 @@ pub struct ClaimPartnerFeesCtx<'info> {
 }
 
 /// Partner claim fees.
-pub fn handle_claim_partner_fee(
-    ctx: Context<ClaimPartnerFeesCtx>,
+pub fn handle_claim_partner_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimPartnerFeesCtx<'info>>,
     max_amount_a: u64,
     max_amount_b: u64,
 ) -> Result<()> {
     let mut pool = ctx.accounts.pool.load_mut()?;
+    let sequence = pool.increment_sequence();
+
+    let signer = ctx.accounts.signer.key();
+    validate_partner_fee_receiver(
+        &ctx.accounts.partner_fee_receiver_a,
+        pool.partner,
+        signer,
+        ctx.accounts.token_a_mint.key(),
+        ctx.accounts.token_a_account.key(),
+    )?;
+    validate_partner_fee_receiver(
+        &ctx.accounts.partner_fee_receiver_b,
+        pool.partner,
+        signer,
+        ctx.accounts.token_b_mint.key(),
+        ctx.accounts.token_b_account.key(),
+    )?;
+
     let (token_a_amount, token_b_amount) = pool.claim_partner_fee(max_amount_a, max_amount_b)?;
 
     transfer_from_pool(
@@ -72,6 +98,7 @@ pub fn handle_claim_partner_fee(
         &ctx.accounts.token_a_program,
         token_a_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
 
     transfer_from_pool(
@@ -82,12 +109,42 @@ pub fn handle_claim_partner_fee(
         &ctx.accounts.token_b_program,
         token_b_amount,
         ctx.bumps.pool_authority,
+        ctx.remaining_accounts,
     )?;
 
     emit_cpi!(EvtClaimPartnerFee {
         pool: ctx.accounts.pool.key(),
         token_a_amount,
-        token_b_amount
+        token_b_amount,
+        sequence,
     });
     Ok(())
 }
+
+/// Without a fee receiver, only partner can claim to any token account.
+/// With a fee receiver, partner or its operator can claim to the registered token account only.
+fn validate_partner_fee_receiver(
+    partner_fee_receiver: &Option<AccountLoader<PartnerFeeReceiver>>,
+    partner: Pubkey,
+    signer: Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+) -> Result<()> {
+    if let Some(partner_fee_receiver) = partner_fee_receiver {
+        let partner_fee_receiver = partner_fee_receiver.load()?;
+        require!(
+            partner_fee_receiver.partner == partner
+                && partner_fee_receiver.mint == mint
+                && partner_fee_receiver.token_account == token_account,
+            PoolError::InvalidPartnerFeeReceiver
+        );
+        require!(
+            signer == partner || partner_fee_receiver.is_operator(signer),
+            PoolError::InvalidPartnerFeeOperator
+        );
+    } else {
+        require!(signer == partner, PoolError::InvalidPartnerFeeOperator);
+    }
+
+    Ok(())
+}
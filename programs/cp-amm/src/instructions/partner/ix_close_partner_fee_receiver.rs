@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::PartnerFeeReceiver, EvtClosePartnerFeeReceiver};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePartnerFeeReceiverCtx<'info> {
+    #[account(
+        mut,
+        has_one = partner,
+        close = rent_receiver,
+    )]
+    pub partner_fee_receiver: AccountLoader<'info, PartnerFeeReceiver>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    pub partner: Signer<'info>,
+}
+
+pub fn handle_close_partner_fee_receiver(ctx: Context<ClosePartnerFeeReceiverCtx>) -> Result<()> {
+    let partner_fee_receiver = ctx.accounts.partner_fee_receiver.load()?;
+    emit_cpi!(EvtClosePartnerFeeReceiver {
+        partner_fee_receiver: ctx.accounts.partner_fee_receiver.key(),
+        partner: partner_fee_receiver.partner,
+        mint: partner_fee_receiver.mint,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    constants::seeds::PARTNER_FEE_RECEIVER_PREFIX, state::PartnerFeeReceiver,
+    EvtCreatePartnerFeeReceiver,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePartnerFeeReceiverCtx<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            PARTNER_FEE_RECEIVER_PREFIX.as_ref(),
+            partner.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+        space = 8 + PartnerFeeReceiver::INIT_SPACE
+    )]
+    pub partner_fee_receiver: AccountLoader<'info, PartnerFeeReceiver>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// token account receiving partner fee
+    #[account(token::mint = mint)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub partner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_partner_fee_receiver(
+    ctx: Context<CreatePartnerFeeReceiverCtx>,
+    operator: Pubkey,
+) -> Result<()> {
+    let mut partner_fee_receiver = ctx.accounts.partner_fee_receiver.load_init()?;
+    partner_fee_receiver.initialize(
+        ctx.accounts.partner.key(),
+        ctx.accounts.mint.key(),
+        ctx.accounts.token_account.key(),
+        operator,
+    );
+
+    emit_cpi!(EvtCreatePartnerFeeReceiver {
+        partner_fee_receiver: ctx.accounts.partner_fee_receiver.key(),
+        partner: ctx.accounts.partner.key(),
+        mint: ctx.accounts.mint.key(),
+        token_account: ctx.accounts.token_account.key(),
+        operator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{activation::BASE_FEE_OVERRIDE_DELAY, seeds::POOL_PREFIX},
+    max_key, min_key,
+    safe_math::SafeMath,
+    state::{Config, Pool},
+    EvtOverridePoolFee, PoolError,
+};
+
+/// Accounts for pool creator authority of config to override trade fee of a pool
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OverridePoolFeeCtx<'info> {
+    #[account(
+        mut,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        seeds = [
+            POOL_PREFIX.as_ref(),
+            config.key().as_ref(),
+            &max_key(&token_a_mint.key(), &token_b_mint.key()),
+            &min_key(&token_a_mint.key(), &token_b_mint.key()),
+        ],
+        bump,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(has_one = pool_creator_authority @ PoolError::InvalidPoolCreatorAuthority)]
+    pub config: AccountLoader<'info, Config>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub pool_creator_authority: Signer<'info>,
+}
+
+/// Queue an override of base fee of a pool by a constant trade fee, it takes effect after `BASE_FEE_OVERRIDE_DELAY`
+/// and can be cancelled by `cancel_pending_update` until then
+pub fn handle_override_pool_fee(
+    ctx: Context<OverridePoolFeeCtx>,
+    trade_fee_numerator: u64,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    config.validate_pool_fee_override(trade_fee_numerator)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.increment_sequence();
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    // matured override must not be replaced
+    pool.apply_pending_fee_updates(current_timestamp)?;
+
+    let activation_point = current_timestamp.safe_add(BASE_FEE_OVERRIDE_DELAY)?;
+    pool.queue_base_fee_override(trade_fee_numerator, activation_point);
+
+    emit_cpi!(EvtOverridePoolFee {
+        pool: ctx.accounts.pool.key(),
+        config: ctx.accounts.config.key(),
+        pool_creator_authority: ctx.accounts.pool_creator_authority.key(),
+        trade_fee_numerator,
+        activation_point,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::Config, EvtTransferPoolCreatorAuthority, PoolError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferPoolCreatorAuthorityCtx<'info> {
+    #[account(mut, has_one = pool_creator_authority @ PoolError::InvalidPoolCreatorAuthority)]
+    pub config: AccountLoader<'info, Config>,
+
+    pub pool_creator_authority: Signer<'info>,
+}
+
+/// Propose a new pool_creator_authority of a config, the new key must accept to take it over
+pub fn handle_transfer_pool_creator_authority(
+    ctx: Context<TransferPoolCreatorAuthorityCtx>,
+    new_pool_creator_authority: Pubkey,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.transfer_pool_creator_authority(new_pool_creator_authority);
+
+    emit_cpi!(EvtTransferPoolCreatorAuthority {
+        config: ctx.accounts.config.key(),
+        pool_creator_authority: ctx.accounts.pool_creator_authority.key(),
+        pending_pool_creator_authority: new_pool_creator_authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{state::PartnerFeeReceiver, EvtUpdatePartnerFeeReceiver};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePartnerFeeReceiverCtx<'info> {
+    #[account(mut, has_one = partner, has_one = mint)]
+    pub partner_fee_receiver: AccountLoader<'info, PartnerFeeReceiver>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// new token account receiving partner fee
+    #[account(token::mint = mint)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub partner: Signer<'info>,
+}
+
+pub fn handle_update_partner_fee_receiver(
+    ctx: Context<UpdatePartnerFeeReceiverCtx>,
+    operator: Pubkey,
+) -> Result<()> {
+    let mut partner_fee_receiver = ctx.accounts.partner_fee_receiver.load_mut()?;
+    partner_fee_receiver.update(ctx.accounts.token_account.key(), operator);
+
+    emit_cpi!(EvtUpdatePartnerFeeReceiver {
+        partner_fee_receiver: ctx.accounts.partner_fee_receiver.key(),
+        partner: ctx.accounts.partner.key(),
+        mint: ctx.accounts.mint.key(),
+        token_account: ctx.accounts.token_account.key(),
+        operator,
+    });
+
+    Ok(())
+}
@@ -1,2 +1,14 @@
 pub mod ix_claim_partner_fee;
 pub use ix_claim_partner_fee::*;
+pub mod ix_override_pool_fee;
+pub use ix_override_pool_fee::*;
+pub mod ix_create_partner_fee_receiver;
+pub use ix_create_partner_fee_receiver::*;
+pub mod ix_update_partner_fee_receiver;
+pub use ix_update_partner_fee_receiver::*;
+pub mod ix_close_partner_fee_receiver;
+pub use ix_close_partner_fee_receiver::*;
+pub mod ix_transfer_pool_creator_authority;
+pub use ix_transfer_pool_creator_authority::*;
+pub mod ix_accept_pool_creator_authority;
+pub use ix_accept_pool_creator_authority::*;
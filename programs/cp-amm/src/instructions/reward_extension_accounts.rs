@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::{
+    constants::NUM_REWARDS,
+    state::{get_extension_reward_index, Pool, Position, PositionRewardExtension, RewardExtension},
+    PoolError,
+};
+
+#[derive(Accounts)]
+pub struct RewardExtensionRemainingAccount<'info> {
+    #[account(mut)]
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+}
+
+#[derive(Accounts)]
+pub struct PositionRewardExtensionRemainingAccount<'info> {
+    #[account(mut)]
+    pub position_reward_extension: AccountLoader<'info, PositionRewardExtension>,
+}
+
+/// Load reward extension of the pool from remaining accounts
+pub fn load_reward_extension<'c: 'info, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    pool: Pubkey,
+) -> Result<AccountLoader<'info, RewardExtension>> {
+    let account = RewardExtensionRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut RewardExtensionRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )?;
+    require!(
+        account.reward_extension.load()?.pool == pool,
+        PoolError::InvalidRewardExtension
+    );
+    Ok(account.reward_extension)
+}
+
+/// Load reward extension of the pool from remaining accounts if reward index belongs to reward extension
+pub fn load_reward_extension_for_index<'c: 'info, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    pool: Pubkey,
+    reward_index: usize,
+) -> Result<Option<AccountLoader<'info, RewardExtension>>> {
+    if reward_index < NUM_REWARDS {
+        return Ok(None);
+    }
+    get_extension_reward_index(reward_index)?;
+    Ok(Some(load_reward_extension(remaining_accounts, pool)?))
+}
+
+/// Load reward extension of the position from remaining accounts
+pub fn load_position_reward_extension<'c: 'info, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    position: Pubkey,
+) -> Result<AccountLoader<'info, PositionRewardExtension>> {
+    let account = PositionRewardExtensionRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut PositionRewardExtensionRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )?;
+    require!(
+        account.position_reward_extension.load()?.position == position,
+        PoolError::InvalidRewardExtension
+    );
+    Ok(account.position_reward_extension)
+}
+
+/// Update extension rewards of pool and position, must be called before any change of pool or position liquidity.
+/// Remaining accounts must contain reward extension and position reward extension if pool has reward extension
+pub fn update_position_extension_rewards<'c: 'info, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    pool_key: Pubkey,
+    pool: &Pool,
+    position_key: Pubkey,
+    position: &Position,
+    current_time: u64,
+) -> Result<()> {
+    if pool.has_reward_extension() {
+        let reward_extension = load_reward_extension(remaining_accounts, pool_key)?;
+        let position_reward_extension =
+            load_position_reward_extension(remaining_accounts, position_key)?;
+
+        let mut reward_extension = reward_extension.load_mut()?;
+        reward_extension.update_rewards(pool.get_reward_liquidity()?, current_time)?;
+
+        position_reward_extension
+            .load_mut()?
+            .update_rewards(&reward_extension, position.get_reward_liquidity(pool)?)?;
+    }
+
+    Ok(())
+}
+
+/// Update rewards of pool and position before vested or permanently locked liquidity of the position changes.
+/// Reward liquidity only changes when pool has reward boost, so remaining accounts must contain reward extension
+/// and position reward extension only if pool has both reward boost and reward extension
+pub fn update_position_rewards_before_lock<'c: 'info, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    pool_key: Pubkey,
+    pool: &mut Pool,
+    position_key: Pubkey,
+    position: &mut Position,
+) -> Result<()> {
+    if pool.has_reward_boost() {
+        let current_time = Clock::get()?.unix_timestamp as u64;
+        position.update_rewards(pool, current_time)?;
+        update_position_extension_rewards(
+            remaining_accounts,
+            pool_key,
+            pool,
+            position_key,
+            position,
+            current_time,
+        )?;
+    }
+
+    Ok(())
+}
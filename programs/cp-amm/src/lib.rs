@@ -33,6 +33,25 @@ pub mod cp_amm {
 
     /// ADMIN FUNCTIONS /////
 
+    pub fn initialize_program_authority(
+        ctx: Context<InitializeProgramAuthorityCtx>,
+        params: InitializeProgramAuthorityParameters,
+    ) -> Result<()> {
+        instructions::handle_initialize_program_authority(ctx, params)
+    }
+
+    pub fn transfer_authority_role(
+        ctx: Context<TransferAuthorityRoleCtx>,
+        role: u8,
+        new_key: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_transfer_authority_role(ctx, role, new_key)
+    }
+
+    pub fn accept_authority_role(ctx: Context<AcceptAuthorityRoleCtx>, role: u8) -> Result<()> {
+        instructions::handle_accept_authority_role(ctx, role)
+    }
+
     // create static config
     pub fn create_config(
         ctx: Context<CreateConfigCtx>,
@@ -51,8 +70,12 @@ pub mod cp_amm {
         instructions::handle_create_dynamic_config(ctx, index, config_parameters)
     }
 
-    pub fn create_token_badge(ctx: Context<CreateTokenBadgeCtx>) -> Result<()> {
-        instructions::handle_create_token_badge(ctx)
+    pub fn create_token_badge(ctx: Context<CreateTokenBadgeCtx>, policy: u8) -> Result<()> {
+        instructions::handle_create_token_badge(ctx, policy)
+    }
+
+    pub fn close_token_badge(ctx: Context<CloseTokenBadgeCtx>) -> Result<()> {
+        instructions::handle_close_token_badge(ctx)
     }
 
     pub fn create_claim_fee_operator(ctx: Context<CreateClaimFeeOperatorCtx>) -> Result<()> {
@@ -63,6 +86,35 @@ pub mod cp_amm {
         instructions::handle_close_claim_fee_operator(ctx)
     }
 
+    pub fn update_claim_fee_operator(
+        ctx: Context<UpdateClaimFeeOperatorCtx>,
+        operator: Pubkey,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_update_claim_fee_operator(ctx, operator, treasury)
+    }
+
+    pub fn create_fee_discount_registry(
+        ctx: Context<CreateFeeDiscountRegistryCtx>,
+        tiers: Vec<FeeDiscountTierParameters>,
+    ) -> Result<()> {
+        instructions::handle_create_fee_discount_registry(ctx, tiers)
+    }
+
+    pub fn update_fee_discount_registry(
+        ctx: Context<UpdateFeeDiscountRegistryCtx>,
+        tiers: Vec<FeeDiscountTierParameters>,
+    ) -> Result<()> {
+        instructions::handle_update_fee_discount_registry(ctx, tiers)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfigCtx>,
+        params: UpdateConfigParameters,
+    ) -> Result<()> {
+        instructions::handle_update_config(ctx, params)
+    }
+
     pub fn close_config(ctx: Context<CloseConfigCtx>) -> Result<()> {
         instructions::handle_close_config(ctx)
     }
@@ -72,12 +124,19 @@ pub mod cp_amm {
         reward_index: u8,
         reward_duration: u64,
         funder: Pubkey,
+        claim_vesting_duration: u32,
     ) -> Result<()> {
-        instructions::handle_initialize_reward(ctx, reward_index, reward_duration, funder)
+        instructions::handle_initialize_reward(
+            ctx,
+            reward_index,
+            reward_duration,
+            funder,
+            claim_vesting_duration,
+        )
     }
 
-    pub fn fund_reward(
-        ctx: Context<FundRewardCtx>,
+    pub fn fund_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
         reward_index: u8,
         amount: u64,
         carry_forward: bool,
@@ -85,45 +144,317 @@ pub mod cp_amm {
         instructions::handle_fund_reward(ctx, reward_index, amount, carry_forward)
     }
 
-    pub fn withdraw_ineligible_reward(
-        ctx: Context<WithdrawIneligibleRewardCtx>,
+    pub fn withdraw_ineligible_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawIneligibleRewardCtx<'info>>,
         reward_index: u8,
     ) -> Result<()> {
         instructions::handle_withdraw_ineligible_reward(ctx, reward_index)
     }
 
-    pub fn update_reward_funder(
-        ctx: Context<UpdateRewardFunderCtx>,
+    pub fn update_reward_funder<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateRewardFunderCtx<'info>>,
         reward_index: u8,
         new_funder: Pubkey,
     ) -> Result<()> {
         instructions::handle_update_reward_funder(ctx, reward_index, new_funder)
     }
 
-    pub fn update_reward_duration(
-        ctx: Context<UpdateRewardDurationCtx>,
+    pub fn update_reward_emission_schedule<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateRewardEmissionScheduleCtx<'info>>,
+        reward_index: u8,
+        emission_period: u32,
+        emission_decay_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_update_reward_emission_schedule(
+            ctx,
+            reward_index,
+            emission_period,
+            emission_decay_bps,
+        )
+    }
+
+    pub fn update_reward_duration<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateRewardDurationCtx<'info>>,
         reward_index: u8,
         new_duration: u64,
     ) -> Result<()> {
         instructions::handle_update_reward_duration(ctx, reward_index, new_duration)
     }
 
+    pub fn initialize_reward_extension(ctx: Context<InitializeRewardExtensionCtx>) -> Result<()> {
+        instructions::handle_initialize_reward_extension(ctx)
+    }
+
     pub fn set_pool_status(ctx: Context<SetPoolStatusCtx>, status: u8) -> Result<()> {
         instructions::handle_set_pool_status(ctx, status)
     }
 
-    pub fn claim_protocol_fee(ctx: Context<ClaimProtocolFeesCtx>) -> Result<()> {
-        instructions::handle_claim_protocol_fee(ctx)
+    pub fn set_pool_emergency_mode(
+        ctx: Context<SetPoolEmergencyModeCtx>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::handle_set_pool_emergency_mode(ctx, enabled)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePoolCtx>) -> Result<()> {
+        instructions::handle_migrate_pool(ctx)
+    }
+
+    pub fn close_pool<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClosePoolCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_close_pool(ctx)
+    }
+
+    pub fn set_config_fee_override_bounds(
+        ctx: Context<SetConfigFeeOverrideBoundsCtx>,
+        min_fee_numerator: u64,
+        max_fee_numerator: u64,
+    ) -> Result<()> {
+        instructions::handle_set_config_fee_override_bounds(
+            ctx,
+            min_fee_numerator,
+            max_fee_numerator,
+        )
+    }
+
+    pub fn set_config_cpi_guard(
+        ctx: Context<SetConfigCpiGuardCtx>,
+        cpi_guard_duration: u64,
+    ) -> Result<()> {
+        instructions::handle_set_config_cpi_guard(ctx, cpi_guard_duration)
+    }
+
+    pub fn set_config_reward_boost(
+        ctx: Context<SetConfigRewardBoostCtx>,
+        vesting_reward_boost_bps: u16,
+        permanent_lock_reward_boost_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_set_config_reward_boost(
+            ctx,
+            vesting_reward_boost_bps,
+            permanent_lock_reward_boost_bps,
+        )
+    }
+
+    pub fn initialize_insurance_fund(
+        ctx: Context<InitializeInsuranceFundCtx>,
+        authority: Pubkey,
+        insurance_fee_percent: u8,
+    ) -> Result<()> {
+        instructions::handle_initialize_insurance_fund(ctx, authority, insurance_fee_percent)
     }
 
-    pub fn claim_partner_fee(
-        ctx: Context<ClaimPartnerFeesCtx>,
+    pub fn initialize_buyback_burn(
+        ctx: Context<InitializeBuybackBurnCtx>,
+        max_amount_per_buyback: u64,
+        min_buyback_interval: u64,
+    ) -> Result<()> {
+        instructions::handle_initialize_buyback_burn(
+            ctx,
+            max_amount_per_buyback,
+            min_buyback_interval,
+        )
+    }
+
+    pub fn override_pool_fee(
+        ctx: Context<OverridePoolFeeCtx>,
+        trade_fee_numerator: u64,
+    ) -> Result<()> {
+        instructions::handle_override_pool_fee(ctx, trade_fee_numerator)
+    }
+
+    pub fn transfer_pool_creator_authority(
+        ctx: Context<TransferPoolCreatorAuthorityCtx>,
+        new_pool_creator_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_transfer_pool_creator_authority(ctx, new_pool_creator_authority)
+    }
+
+    pub fn accept_pool_creator_authority(
+        ctx: Context<AcceptPoolCreatorAuthorityCtx>,
+    ) -> Result<()> {
+        instructions::handle_accept_pool_creator_authority(ctx)
+    }
+
+    pub fn update_pool_dynamic_fee(
+        ctx: Context<UpdatePoolDynamicFeeCtx>,
+        params: UpdateDynamicFeeParameters,
+    ) -> Result<()> {
+        instructions::handle_update_pool_dynamic_fee(ctx, params)
+    }
+
+    pub fn cancel_pending_update(ctx: Context<CancelPendingUpdateCtx>) -> Result<()> {
+        instructions::handle_cancel_pending_update(ctx)
+    }
+
+    pub fn disable_frozen_pool(ctx: Context<DisableFrozenPoolCtx>) -> Result<()> {
+        instructions::handle_disable_frozen_pool(ctx)
+    }
+
+    pub fn skim_vaults(ctx: Context<SkimVaultsCtx>) -> Result<()> {
+        instructions::handle_skim_vaults(ctx)
+    }
+
+    pub fn donate<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DonateCtx<'info>>,
+        params: DonateParameters,
+    ) -> Result<()> {
+        instructions::handle_donate(ctx, params)
+    }
+
+    pub fn create_vault_whitelist(
+        ctx: Context<CreateVaultWhitelistCtx>,
+        vaults: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::handle_create_vault_whitelist(ctx, vaults)
+    }
+
+    pub fn update_vault_whitelist(
+        ctx: Context<UpdateVaultWhitelistCtx>,
+        vaults: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::handle_update_vault_whitelist(ctx, vaults)
+    }
+
+    pub fn create_pool_metadata(
+        ctx: Context<CreatePoolMetadataCtx>,
+        params: PoolMetadataParameters,
+    ) -> Result<()> {
+        instructions::handle_create_pool_metadata(ctx, params)
+    }
+
+    pub fn update_pool_metadata(
+        ctx: Context<UpdatePoolMetadataCtx>,
+        params: PoolMetadataParameters,
+    ) -> Result<()> {
+        instructions::handle_update_pool_metadata(ctx, params)
+    }
+
+    pub fn create_swap_allowlist_entry(ctx: Context<CreateSwapAllowlistEntryCtx>) -> Result<()> {
+        instructions::handle_create_swap_allowlist_entry(ctx)
+    }
+
+    pub fn close_swap_allowlist_entry(ctx: Context<CloseSwapAllowlistEntryCtx>) -> Result<()> {
+        instructions::handle_close_swap_allowlist_entry(ctx)
+    }
+
+    pub fn create_lp_allowlist_entry(ctx: Context<CreateLpAllowlistEntryCtx>) -> Result<()> {
+        instructions::handle_create_lp_allowlist_entry(ctx)
+    }
+
+    pub fn close_lp_allowlist_entry(ctx: Context<CloseLpAllowlistEntryCtx>) -> Result<()> {
+        instructions::handle_close_lp_allowlist_entry(ctx)
+    }
+
+    pub fn create_launch_buy_tracker(ctx: Context<CreateLaunchBuyTrackerCtx>) -> Result<()> {
+        instructions::handle_create_launch_buy_tracker(ctx)
+    }
+
+    pub fn create_pool_stats(ctx: Context<CreatePoolStatsCtx>) -> Result<()> {
+        instructions::handle_create_pool_stats(ctx)
+    }
+
+    pub fn initialize_trade_mining<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeTradeMiningCtx<'info>>,
+        funder: Pubkey,
+        reward_per_fee_a: u128,
+        reward_per_fee_b: u128,
+        end_time: u64,
+    ) -> Result<()> {
+        instructions::handle_initialize_trade_mining(
+            ctx,
+            funder,
+            reward_per_fee_a,
+            reward_per_fee_b,
+            end_time,
+        )
+    }
+
+    pub fn fund_trade_mining<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FundTradeMiningCtx<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::handle_fund_trade_mining(ctx, amount)
+    }
+
+    pub fn withdraw_unaccrued_trade_mining<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawUnaccruedTradeMiningCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_withdraw_unaccrued_trade_mining(ctx)
+    }
+
+    pub fn create_trader_reward(ctx: Context<CreateTraderRewardCtx>) -> Result<()> {
+        instructions::handle_create_trader_reward(ctx)
+    }
+
+    pub fn claim_trade_mining_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimTradeMiningRewardCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_claim_trade_mining_reward(ctx)
+    }
+
+    pub fn update_activation_point(
+        ctx: Context<UpdateActivationPointCtx>,
+        new_activation_point: u64,
+    ) -> Result<()> {
+        instructions::handle_update_activation_point(ctx, new_activation_point)
+    }
+
+    pub fn claim_protocol_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimProtocolFeesCtx<'info>>,
+        max_amount_a: u64,
+        max_amount_b: u64,
+        settlement_mode: u8,
+        minimum_settlement_amount: u64,
+    ) -> Result<()> {
+        instructions::handle_claim_protocol_fee(
+            ctx,
+            max_amount_a,
+            max_amount_b,
+            settlement_mode,
+            minimum_settlement_amount,
+        )
+    }
+
+    pub fn claim_insurance_fund<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimInsuranceFundCtx<'info>>,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<()> {
+        instructions::handle_claim_insurance_fund(ctx, max_amount_a, max_amount_b)
+    }
+
+    pub fn buyback_and_burn(ctx: Context<BuybackAndBurnCtx>) -> Result<()> {
+        instructions::handle_buyback_and_burn(ctx)
+    }
+
+    pub fn claim_partner_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimPartnerFeesCtx<'info>>,
         max_amount_a: u64,
         max_amount_b: u64,
     ) -> Result<()> {
         instructions::handle_claim_partner_fee(ctx, max_amount_a, max_amount_b)
     }
 
+    pub fn create_partner_fee_receiver(
+        ctx: Context<CreatePartnerFeeReceiverCtx>,
+        operator: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_create_partner_fee_receiver(ctx, operator)
+    }
+
+    pub fn update_partner_fee_receiver(
+        ctx: Context<UpdatePartnerFeeReceiverCtx>,
+        operator: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_update_partner_fee_receiver(ctx, operator)
+    }
+
+    pub fn close_partner_fee_receiver(ctx: Context<ClosePartnerFeeReceiverCtx>) -> Result<()> {
+        instructions::handle_close_partner_fee_receiver(ctx)
+    }
+
     /// USER FUNCTIONS ////
 
     pub fn initialize_pool<'c: 'info, 'info>(
@@ -151,15 +482,28 @@ pub mod cp_amm {
         instructions::handle_create_position(ctx)
     }
 
-    pub fn add_liquidity(
-        ctx: Context<AddLiquidityCtx>,
+    pub fn create_position_reward_extension(
+        ctx: Context<CreatePositionRewardExtensionCtx>,
+    ) -> Result<()> {
+        instructions::handle_create_position_reward_extension(ctx)
+    }
+
+    pub fn add_liquidity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
         params: AddLiquidityParameters,
     ) -> Result<()> {
         instructions::handle_add_liquidity(ctx, params)
     }
 
-    pub fn remove_liquidity(
-        ctx: Context<RemoveLiquidityCtx>,
+    pub fn add_liquidity_by_amounts<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
+        params: AddLiquidityByAmountsParameters,
+    ) -> Result<()> {
+        instructions::handle_add_liquidity_by_amounts(ctx, params)
+    }
+
+    pub fn remove_liquidity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
         params: RemoveLiquidityParameters,
     ) -> Result<()> {
         instructions::handle_remove_liquidity(
@@ -170,8 +514,15 @@ pub mod cp_amm {
         )
     }
 
-    pub fn remove_all_liquidity(
-        ctx: Context<RemoveLiquidityCtx>,
+    pub fn remove_liquidity_single_side<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
+        params: RemoveLiquiditySingleSideParameters,
+    ) -> Result<()> {
+        instructions::handle_remove_liquidity_single_side(ctx, params)
+    }
+
+    pub fn remove_all_liquidity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
         token_a_amount_threshold: u64,
         token_b_amount_threshold: u64,
     ) -> Result<()> {
@@ -183,19 +534,75 @@ pub mod cp_amm {
         )
     }
 
-    pub fn close_position(ctx: Context<ClosePositionCtx>) -> Result<()> {
+    pub fn close_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClosePositionCtx<'info>>,
+    ) -> Result<()> {
         instructions::handle_close_position(ctx)
     }
 
-    pub fn swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()> {
+    pub fn merge_positions<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MergePositionsCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_merge_positions(ctx)
+    }
+
+    pub fn swap<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters,
+    ) -> Result<()> {
         instructions::handle_swap(ctx, params)
     }
 
-    pub fn claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()> {
-        instructions::handle_claim_position_fee(ctx)
+    pub fn create_referral(ctx: Context<CreateReferralCtx>) -> Result<()> {
+        instructions::handle_create_referral(ctx)
+    }
+
+    pub fn claim_referral_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimReferralFeeCtx<'info>>,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<()> {
+        instructions::handle_claim_referral_fee(ctx, max_amount_a, max_amount_b)
+    }
+
+    pub fn claim_position_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimPositionFeeCtx<'info>>,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<()> {
+        instructions::handle_claim_position_fee(ctx, max_amount_a, max_amount_b)
+    }
+
+    pub fn set_position_fee_beneficiary(
+        ctx: Context<SetPositionFeeBeneficiaryCtx>,
+        fee_beneficiary: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_set_position_fee_beneficiary(ctx, fee_beneficiary)
     }
 
-    pub fn lock_position(ctx: Context<LockPositionCtx>, params: VestingParameters) -> Result<()> {
+    pub fn set_position_operator(
+        ctx: Context<SetPositionOperatorCtx>,
+        operator: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_set_position_operator(ctx, operator)
+    }
+
+    pub fn mint_fee_receipt(ctx: Context<MintFeeReceiptCtx>) -> Result<()> {
+        instructions::handle_mint_fee_receipt(ctx)
+    }
+
+    pub fn claim_position_fee_with_receipt<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimPositionFeeWithReceiptCtx<'info>>,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<()> {
+        instructions::handle_claim_position_fee_with_receipt(ctx, max_amount_a, max_amount_b)
+    }
+
+    pub fn lock_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, LockPositionCtx<'info>>,
+        params: VestingParameters,
+    ) -> Result<()> {
         instructions::handle_lock_position(ctx, params)
     }
 
@@ -205,14 +612,73 @@ pub mod cp_amm {
         instructions::handle_refresh_vesting(ctx)
     }
 
-    pub fn permanent_lock_position(
-        ctx: Context<PermanentLockPositionCtx>,
+    pub fn batch_refresh_vesting<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, BatchRefreshVestingCtx<'info>>,
+        vesting_counts: Vec<u8>,
+    ) -> Result<()> {
+        instructions::handle_batch_refresh_vesting(ctx, vesting_counts)
+    }
+
+    pub fn permanent_lock_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PermanentLockPositionCtx<'info>>,
         permanent_lock_liquidity: u128,
     ) -> Result<()> {
         instructions::handle_permanent_lock_position(ctx, permanent_lock_liquidity)
     }
 
-    pub fn claim_reward(ctx: Context<ClaimRewardCtx>, reward_index: u8) -> Result<()> {
+    pub fn permanent_lock_vesting<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PermanentLockVestingCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_permanent_lock_vesting(ctx)
+    }
+
+    pub fn claim_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimRewardCtx<'info>>,
+        reward_index: u8,
+    ) -> Result<()> {
         instructions::handle_claim_reward(ctx, reward_index)
     }
+
+    pub fn create_reward_vesting_escrow(
+        ctx: Context<CreateRewardVestingEscrowCtx>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::handle_create_reward_vesting_escrow(ctx, reward_index)
+    }
+
+    pub fn claim_vested_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimVestedRewardCtx<'info>>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::handle_claim_vested_reward(ctx, reward_index)
+    }
+
+    pub fn claim_all<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimAllCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_claim_all(ctx)
+    }
+
+    pub fn update_rewards<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateRewardsCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_update_rewards(ctx)
+    }
+
+    pub fn get_pool_state(
+        ctx: Context<GetPoolStateCtx>,
+        liquidity_delta: u128,
+    ) -> Result<PoolStateView> {
+        instructions::handle_get_pool_state(ctx, liquidity_delta)
+    }
+
+    pub fn get_position_value(ctx: Context<GetPositionValueCtx>) -> Result<PositionValueView> {
+        instructions::handle_get_position_value(ctx)
+    }
+
+    pub fn get_position_pending(
+        ctx: Context<GetPositionPendingCtx>,
+    ) -> Result<PositionPendingView> {
+        instructions::handle_get_position_pending(ctx)
+    }
 }
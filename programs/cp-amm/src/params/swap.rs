@@ -1,5 +1,12 @@
+use anchor_lang::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
+use crate::{
+    constants::BASIS_POINT_MAX,
+    state::{LaunchGuard, PriceCircuitBreaker, SwapRateLimiter},
+    PoolError,
+};
+
 /// Trade (swap) direction
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
@@ -9,3 +16,81 @@ pub enum TradeDirection {
     /// Input token B, output token A
     BtoA,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct SwapRateLimiterParameters {
+    /// max token b input amount of swaps buying token a in a window
+    pub max_amount_per_window: u64,
+    /// window duration, in slots or seconds by activation type
+    pub window_duration: u32,
+    /// number of windows after activation point that the rate limiter applies to
+    pub window_count: u32,
+}
+
+impl SwapRateLimiterParameters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_amount_per_window > 0 && self.window_duration > 0 && self.window_count > 0,
+            PoolError::InvalidSwapRateLimiter
+        );
+        Ok(())
+    }
+
+    pub fn to_swap_rate_limiter(&self) -> SwapRateLimiter {
+        SwapRateLimiter {
+            max_amount_per_window: self.max_amount_per_window,
+            window_duration: self.window_duration,
+            window_count: self.window_count,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct LaunchGuardParameters {
+    /// max cumulative token b input amount of swaps buying token a per wallet
+    pub max_buy_amount_per_wallet: u64,
+    /// duration after activation point, in slots or seconds by activation type
+    pub duration: u64,
+}
+
+impl LaunchGuardParameters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_buy_amount_per_wallet > 0 && self.duration > 0,
+            PoolError::InvalidLaunchGuard
+        );
+        Ok(())
+    }
+
+    pub fn to_launch_guard(&self) -> LaunchGuard {
+        LaunchGuard {
+            max_buy_amount_per_wallet: self.max_buy_amount_per_wallet,
+            duration: self.duration,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct PriceCircuitBreakerParameters {
+    /// max sqrt price change of swaps within a slot, in bps of sqrt price at the start of the slot
+    pub max_sqrt_price_change_bps: u16,
+}
+
+impl PriceCircuitBreakerParameters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_sqrt_price_change_bps > 0
+                && u64::from(self.max_sqrt_price_change_bps) < BASIS_POINT_MAX,
+            PoolError::InvalidPriceCircuitBreaker
+        );
+        Ok(())
+    }
+
+    pub fn to_price_circuit_breaker(&self) -> PriceCircuitBreaker {
+        PriceCircuitBreaker {
+            max_sqrt_price_change_bps: self.max_sqrt_price_change_bps,
+            ..Default::default()
+        }
+    }
+}
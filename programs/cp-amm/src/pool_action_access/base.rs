@@ -2,7 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::pubkey::Pubkey;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use crate::{state::Pool, PermissionlessActionAccess};
+use crate::{
+    activation_handler::ActivationHandler,
+    state::{LpAllowlistEntry, Pool, SwapAllowlistEntry, VaultWhitelist},
+    PermissionedActionAccess, PermissionlessActionAccess,
+};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
@@ -13,14 +17,94 @@ pub enum ActivationType {
 }
 
 pub trait PoolActionAccess {
-    fn can_add_liquidity(&self) -> bool;
+    fn can_add_liquidity(&self, sender: &Pubkey) -> bool;
     fn can_remove_liquidity(&self) -> bool;
     fn can_swap(&self, sender: &Pubkey) -> bool;
-    fn can_create_position(&self) -> bool;
+    fn can_create_position(&self, sender: &Pubkey) -> bool;
     fn can_lock_position(&self) -> bool;
 }
 
-pub fn get_pool_access_validator<'a>(pool: &'a Pool) -> Result<Box<dyn PoolActionAccess + 'a>> {
-    let access_validator = PermissionlessActionAccess::new(pool)?;
-    Ok(Box::new(access_validator))
+/// Access validator of a pool, dispatched statically by pool type to avoid heap allocation
+pub enum PoolAccessValidator {
+    Permissionless(PermissionlessActionAccess),
+    Permissioned(PermissionedActionAccess),
+}
+
+impl PoolActionAccess for PoolAccessValidator {
+    fn can_add_liquidity(&self, sender: &Pubkey) -> bool {
+        match self {
+            Self::Permissionless(access) => access.can_add_liquidity(sender),
+            Self::Permissioned(access) => access.can_add_liquidity(sender),
+        }
+    }
+
+    fn can_remove_liquidity(&self) -> bool {
+        match self {
+            Self::Permissionless(access) => access.can_remove_liquidity(),
+            Self::Permissioned(access) => access.can_remove_liquidity(),
+        }
+    }
+
+    fn can_swap(&self, sender: &Pubkey) -> bool {
+        match self {
+            Self::Permissionless(access) => access.can_swap(sender),
+            Self::Permissioned(access) => access.can_swap(sender),
+        }
+    }
+
+    fn can_create_position(&self, sender: &Pubkey) -> bool {
+        match self {
+            Self::Permissionless(access) => access.can_create_position(sender),
+            Self::Permissioned(access) => access.can_create_position(sender),
+        }
+    }
+
+    fn can_lock_position(&self) -> bool {
+        match self {
+            Self::Permissionless(access) => access.can_lock_position(),
+            Self::Permissioned(access) => access.can_lock_position(),
+        }
+    }
+}
+
+/// Vault whitelist and swap allowlist entry are only needed to validate swaps,
+/// lp allowlist entry is only needed to validate adding liquidity and creating position
+pub fn get_pool_access_validator(
+    pool: &Pool,
+    vault_whitelist: Option<&VaultWhitelist>,
+    swap_allowlist_entry: Option<&SwapAllowlistEntry>,
+    lp_allowlist_entry: Option<&LpAllowlistEntry>,
+) -> Result<PoolAccessValidator> {
+    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
+    get_pool_access_validator_at_point(
+        pool,
+        vault_whitelist,
+        swap_allowlist_entry,
+        lp_allowlist_entry,
+        current_point,
+    )
+}
+
+/// Same as get_pool_access_validator, with current point of the caller to avoid reading the clock again
+pub fn get_pool_access_validator_at_point(
+    pool: &Pool,
+    vault_whitelist: Option<&VaultWhitelist>,
+    swap_allowlist_entry: Option<&SwapAllowlistEntry>,
+    lp_allowlist_entry: Option<&LpAllowlistEntry>,
+    current_point: u64,
+) -> Result<PoolAccessValidator> {
+    if pool.is_permissioned_swap() {
+        return Ok(PoolAccessValidator::Permissioned(
+            PermissionedActionAccess::new(
+                pool,
+                vault_whitelist,
+                swap_allowlist_entry,
+                lp_allowlist_entry,
+                current_point,
+            )?,
+        ));
+    }
+    Ok(PoolAccessValidator::Permissionless(
+        PermissionlessActionAccess::new(pool, vault_whitelist, lp_allowlist_entry, current_point)?,
+    ))
 }
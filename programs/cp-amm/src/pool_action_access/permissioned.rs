@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{LpAllowlistEntry, Pool, SwapAllowlistEntry, VaultWhitelist},
+    PermissionlessActionAccess, PoolActionAccess,
+};
+
+/// Access of a permissioned pool, swap is only allowed for wallets in the swap allowlist of pool
+pub struct PermissionedActionAccess {
+    permissionless_action_access: PermissionlessActionAccess,
+    allowlisted_wallet: Option<Pubkey>,
+}
+
+impl PermissionedActionAccess {
+    pub fn new(
+        pool: &Pool,
+        vault_whitelist: Option<&VaultWhitelist>,
+        swap_allowlist_entry: Option<&SwapAllowlistEntry>,
+        lp_allowlist_entry: Option<&LpAllowlistEntry>,
+        current_point: u64,
+    ) -> Result<Self> {
+        Ok(Self {
+            permissionless_action_access: PermissionlessActionAccess::new(
+                pool,
+                vault_whitelist,
+                lp_allowlist_entry,
+                current_point,
+            )?,
+            allowlisted_wallet: swap_allowlist_entry.map(|entry| entry.wallet),
+        })
+    }
+}
+
+impl PoolActionAccess for PermissionedActionAccess {
+    fn can_add_liquidity(&self, sender: &Pubkey) -> bool {
+        self.permissionless_action_access.can_add_liquidity(sender)
+    }
+
+    fn can_remove_liquidity(&self) -> bool {
+        self.permissionless_action_access.can_remove_liquidity()
+    }
+
+    fn can_swap(&self, sender: &Pubkey) -> bool {
+        self.allowlisted_wallet == Some(*sender)
+            && self.permissionless_action_access.can_swap(sender)
+    }
+
+    fn can_create_position(&self, sender: &Pubkey) -> bool {
+        self.permissionless_action_access
+            .can_create_position(sender)
+    }
+
+    fn can_lock_position(&self) -> bool {
+        self.permissionless_action_access.can_lock_position()
+    }
+}
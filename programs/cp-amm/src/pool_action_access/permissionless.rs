@@ -3,53 +3,90 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::activation::{SLOT_BUFFER, TIME_BUFFER},
     safe_math::SafeMath,
-    state::{Pool, PoolStatus},
+    state::{LpAllowlistEntry, Pool, PoolOperation, VaultWhitelist},
     PoolError, {ActivationType, PoolActionAccess},
 };
 
 pub struct PermissionlessActionAccess {
-    is_enabled: bool,
+    is_swap_enabled: bool,
+    is_add_liquidity_enabled: bool,
+    is_remove_liquidity_enabled: bool,
+    is_create_position_enabled: bool,
+    is_emergency_mode: bool,
+    is_permissioned_lp: bool,
+    lp_allowlisted_wallet: Option<Pubkey>,
     activation_point: u64,
     pre_activation_point: u64,
     current_point: u64,
-    whitelisted_vault: Pubkey,
+    whitelisted_vaults: Vec<Pubkey>,
 }
 
 impl PermissionlessActionAccess {
-    pub fn new(pool: &Pool) -> Result<Self> {
+    pub fn new(
+        pool: &Pool,
+        vault_whitelist: Option<&VaultWhitelist>,
+        lp_allowlist_entry: Option<&LpAllowlistEntry>,
+        current_point: u64,
+    ) -> Result<Self> {
         let activation_type = ActivationType::try_from(pool.activation_type)
             .map_err(|_| PoolError::InvalidActivationType)?;
-        let (current_point, buffer_time) = match activation_type {
-            ActivationType::Slot => (Clock::get()?.slot, SLOT_BUFFER),
-            ActivationType::Timestamp => (Clock::get()?.unix_timestamp as u64, TIME_BUFFER),
+        let buffer_time = match activation_type {
+            ActivationType::Slot => SLOT_BUFFER,
+            ActivationType::Timestamp => TIME_BUFFER,
         };
         let pre_activation_point = if pool.activation_point >= buffer_time {
             pool.activation_point.safe_sub(buffer_time)?
         } else {
             0
         };
+        // whitelisted vault of pool and vaults of vault whitelist can buy from pre-activation point
+        let mut whitelisted_vaults = vec![pool.whitelisted_vault];
+        if let Some(vault_whitelist) = vault_whitelist {
+            whitelisted_vaults.extend(
+                vault_whitelist
+                    .vaults
+                    .iter()
+                    .filter(|vault| **vault != Pubkey::default()),
+            );
+        }
         Ok(Self {
-            is_enabled: pool.pool_status == Into::<u8>::into(PoolStatus::Enable),
+            is_swap_enabled: pool.is_operation_enabled(PoolOperation::Swap),
+            is_add_liquidity_enabled: pool.is_operation_enabled(PoolOperation::AddLiquidity),
+            is_remove_liquidity_enabled: pool.is_operation_enabled(PoolOperation::RemoveLiquidity),
+            is_create_position_enabled: pool.is_operation_enabled(PoolOperation::CreatePosition),
+            is_emergency_mode: pool.is_emergency_mode(),
+            is_permissioned_lp: pool.is_permissioned_lp(),
+            lp_allowlisted_wallet: lp_allowlist_entry.map(|entry| entry.wallet),
             current_point,
             activation_point: pool.activation_point,
-            whitelisted_vault: pool.whitelisted_vault,
+            whitelisted_vaults,
             pre_activation_point,
         })
     }
 }
 
+impl PermissionlessActionAccess {
+    // permissioned lp pool only accepts new liquidity from wallets in its lp allowlist
+    fn is_lp_allowed(&self, sender: &Pubkey) -> bool {
+        !self.is_permissioned_lp || self.lp_allowlisted_wallet == Some(*sender)
+    }
+}
+
 impl PoolActionAccess for PermissionlessActionAccess {
-    fn can_add_liquidity(&self) -> bool {
-        self.is_enabled
+    fn can_add_liquidity(&self, sender: &Pubkey) -> bool {
+        self.is_add_liquidity_enabled && !self.is_emergency_mode && self.is_lp_allowed(sender)
     }
 
     fn can_remove_liquidity(&self) -> bool {
-        self.current_point >= self.activation_point
+        if self.is_emergency_mode {
+            return true;
+        }
+        self.is_remove_liquidity_enabled && self.current_point >= self.activation_point
     }
 
     fn can_swap(&self, sender: &Pubkey) -> bool {
-        if self.is_enabled {
-            if sender.eq(&self.whitelisted_vault) {
+        if self.is_swap_enabled && !self.is_emergency_mode {
+            if self.whitelisted_vaults.contains(sender) {
                 self.current_point >= self.pre_activation_point
             } else {
                 self.current_point >= self.activation_point
@@ -59,10 +96,11 @@ impl PoolActionAccess for PermissionlessActionAccess {
         }
     }
 
-    fn can_create_position(&self) -> bool {
-        self.is_enabled
+    fn can_create_position(&self, sender: &Pubkey) -> bool {
+        self.is_create_position_enabled && !self.is_emergency_mode && self.is_lp_allowed(sender)
     }
+    // locking commits liquidity to the pool, so it is halted with deposits
     fn can_lock_position(&self) -> bool {
-        self.is_enabled
+        self.is_add_liquidity_enabled && !self.is_emergency_mode
     }
 }
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{safe_math::SafeMath, PoolError};
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Buyback and burn of a pool, protocol fee in token b is spent to buy token a through the pool
+/// and the bought token a is burnt, by a permissionless crank
+pub struct BuybackBurn {
+    /// pool
+    pub pool: Pubkey,
+    /// max protocol fee in token b spent by a buyback
+    pub max_amount_per_buyback: u64,
+    /// min seconds between buybacks
+    pub min_buyback_interval: u64,
+    /// timestamp of last buyback
+    pub last_buyback_timestamp: u64,
+    /// total token b spent
+    pub total_spent_amount: u64,
+    /// total token a burnt
+    pub total_burnt_amount: u64,
+    /// Reserve
+    pub _padding: [u8; 56],
+}
+
+const_assert_eq!(BuybackBurn::INIT_SPACE, 128);
+
+impl BuybackBurn {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        max_amount_per_buyback: u64,
+        min_buyback_interval: u64,
+    ) {
+        self.pool = pool;
+        self.max_amount_per_buyback = max_amount_per_buyback;
+        self.min_buyback_interval = min_buyback_interval;
+    }
+
+    /// Buybacks are spaced by `min_buyback_interval` and capped by `max_amount_per_buyback`, so a sandwich
+    /// around the crank only moves a bounded amount per interval
+    pub fn validate_buyback(&self, current_time: u64) -> Result<()> {
+        require!(
+            self.last_buyback_timestamp == 0
+                || current_time
+                    >= self
+                        .last_buyback_timestamp
+                        .safe_add(self.min_buyback_interval)?,
+            PoolError::BuybackNotReady
+        );
+        Ok(())
+    }
+
+    pub fn record_buyback(
+        &mut self,
+        spent_amount: u64,
+        burnt_amount: u64,
+        current_time: u64,
+    ) -> Result<()> {
+        self.last_buyback_timestamp = current_time;
+        self.total_spent_amount = self.total_spent_amount.safe_add(spent_amount)?;
+        self.total_burnt_amount = self.total_burnt_amount.safe_add(burnt_amount)?;
+        Ok(())
+    }
+}
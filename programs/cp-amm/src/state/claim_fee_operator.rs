@@ -1,14 +1,18 @@
 use anchor_lang::prelude::*;
 use static_assertions::const_assert_eq;
 
+use crate::constants::treasury;
+
 #[account(zero_copy)]
 #[derive(InitSpace, Debug)]
 /// Parameter that set by the protocol
 pub struct ClaimFeeOperator {
     /// operator
     pub operator: Pubkey,
+    /// owner of token accounts receiving protocol fee, default pubkey means the hardcoded treasury
+    pub treasury: Pubkey,
     /// Reserve
-    pub _padding: [u8; 128],
+    pub _padding: [u8; 96],
 }
 
 const_assert_eq!(ClaimFeeOperator::INIT_SPACE, 160);
@@ -18,4 +22,17 @@ impl ClaimFeeOperator {
         self.operator = operator;
         Ok(())
     }
+
+    pub fn update(&mut self, operator: Pubkey, treasury: Pubkey) {
+        self.operator = operator;
+        self.treasury = treasury;
+    }
+
+    pub fn get_treasury(&self) -> Pubkey {
+        if self.treasury == Pubkey::default() {
+            treasury::ID
+        } else {
+            self.treasury
+        }
+    }
 }
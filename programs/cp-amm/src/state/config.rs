@@ -5,7 +5,11 @@ use static_assertions::const_assert_eq;
 use crate::{
     activation_handler::ActivationType,
     alpha_vault::alpha_vault,
-    constants::activation::*,
+    constants::{
+        activation::*,
+        fee::{MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR},
+        MAX_REWARD_BOOST_BPS,
+    },
     error::PoolError,
     params::fee_parameters::{
         BaseFeeParameters, DynamicFeeParameters, PartnerInfo, PoolFeeParameters,
@@ -213,9 +217,22 @@ pub struct Config {
     pub sqrt_min_price: u128,
     /// sqrt max price
     pub sqrt_max_price: u128,
-    /// Fee curve point
+    /// Min trade fee numerator that pool_creator_authority can override for a pool, 0 means override is disabled
+    pub pool_fee_override_min_numerator: u64,
+    /// Max trade fee numerator that pool_creator_authority can override for a pool
+    pub pool_fee_override_max_numerator: u64,
+    /// Proposed pool_creator_authority, takes over after accepting
+    pub pending_pool_creator_authority: Pubkey,
+    /// Duration after activation point that swaps of new pools of the config must be top-level instructions, 0 means swaps can be CPI-invoked
+    pub cpi_guard_duration: u64,
+    /// Extra reward weight of vested liquidity of new pools of the config, in bps of liquidity
+    pub vesting_reward_boost_bps: u16,
+    /// Extra reward weight of permanently locked liquidity of new pools of the config, in bps of liquidity
+    pub permanent_lock_reward_boost_bps: u16,
+    /// padding 2
+    pub _padding_2: [u8; 4],
     /// Padding for further use
-    pub _padding_1: [u64; 10],
+    pub _padding_1: [u64; 2],
 }
 
 const_assert_eq!(Config::INIT_SPACE, 320);
@@ -291,6 +308,42 @@ impl Config {
         self.config_type = ConfigType::Static.into();
     }
 
+    pub fn update_static_config(
+        &mut self,
+        pool_fees: &PoolFeeParameters,
+        vault_config_key: Pubkey,
+        pool_creator_authority: Pubkey,
+        activation_type: u8,
+    ) {
+        self.pool_fees = pool_fees.to_pool_fees_config();
+        self.vault_config_key = vault_config_key;
+        self.set_pool_creator_authority(pool_creator_authority);
+        self.activation_type = activation_type;
+    }
+
+    /// Set pool_creator_authority, a pending transfer to another key is cancelled
+    pub fn set_pool_creator_authority(&mut self, pool_creator_authority: Pubkey) {
+        if self.pool_creator_authority != pool_creator_authority {
+            self.pool_creator_authority = pool_creator_authority;
+            self.pending_pool_creator_authority = Pubkey::default();
+        }
+    }
+
+    /// Propose a new pool_creator_authority, proposing the default pubkey cancels a pending transfer
+    pub fn transfer_pool_creator_authority(&mut self, new_pool_creator_authority: Pubkey) {
+        self.pending_pool_creator_authority = new_pool_creator_authority;
+    }
+
+    pub fn accept_pool_creator_authority(&mut self, key: Pubkey) -> Result<()> {
+        require!(
+            self.pending_pool_creator_authority == key && key != Pubkey::default(),
+            PoolError::InvalidPoolCreatorAuthority
+        );
+        self.pool_creator_authority = key;
+        self.pending_pool_creator_authority = Pubkey::default();
+        Ok(())
+    }
+
     pub fn get_config_type(&self) -> Result<ConfigType> {
         let config_type =
             ConfigType::try_from(self.config_type).map_err(|_| PoolError::TypeCastFailed)?;
@@ -303,6 +356,62 @@ impl Config {
         self.config_type = ConfigType::Dynamic.into();
     }
 
+    pub fn set_pool_fee_override_bounds(
+        &mut self,
+        min_fee_numerator: u64,
+        max_fee_numerator: u64,
+    ) -> Result<()> {
+        // zero bounds disable the override
+        if min_fee_numerator != 0 || max_fee_numerator != 0 {
+            require!(
+                min_fee_numerator >= MIN_FEE_NUMERATOR
+                    && min_fee_numerator <= max_fee_numerator
+                    && max_fee_numerator <= MAX_FEE_NUMERATOR,
+                PoolError::InvalidFee
+            );
+        }
+        self.pool_fee_override_min_numerator = min_fee_numerator;
+        self.pool_fee_override_max_numerator = max_fee_numerator;
+        Ok(())
+    }
+
+    pub fn set_cpi_guard_duration(&mut self, cpi_guard_duration: u64) -> Result<()> {
+        require!(
+            cpi_guard_duration <= MAX_CPI_GUARD_DURATION,
+            PoolError::InvalidCpiGuardDuration
+        );
+        self.cpi_guard_duration = cpi_guard_duration;
+        Ok(())
+    }
+
+    pub fn set_reward_boost(
+        &mut self,
+        vesting_reward_boost_bps: u16,
+        permanent_lock_reward_boost_bps: u16,
+    ) -> Result<()> {
+        require!(
+            vesting_reward_boost_bps <= MAX_REWARD_BOOST_BPS
+                && permanent_lock_reward_boost_bps <= MAX_REWARD_BOOST_BPS,
+            PoolError::InvalidRewardBoost
+        );
+        self.vesting_reward_boost_bps = vesting_reward_boost_bps;
+        self.permanent_lock_reward_boost_bps = permanent_lock_reward_boost_bps;
+        Ok(())
+    }
+
+    pub fn validate_pool_fee_override(&self, trade_fee_numerator: u64) -> Result<()> {
+        require!(
+            self.pool_fee_override_min_numerator != 0,
+            PoolError::PoolFeeOverrideDisabled
+        );
+        require!(
+            trade_fee_numerator >= self.pool_fee_override_min_numerator
+                && trade_fee_numerator <= self.pool_fee_override_max_numerator,
+            PoolError::ExceedMaxFeeBps
+        );
+        Ok(())
+    }
+
     pub fn to_bootstrapping_config(&self, activation_point: u64) -> BootstrappingConfig {
         BootstrappingConfig {
             activation_point,
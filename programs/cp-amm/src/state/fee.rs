@@ -4,30 +4,20 @@ use anchor_lang::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
 
+use cp_amm_core::fee_math;
+
 use crate::{
-    constants::{
-        fee::{FEE_DENOMINATOR, MAX_FEE_NUMERATOR},
-        BASIS_POINT_MAX, ONE_Q64,
-    },
-    fee_math::get_fee_in_period,
+    constants::{BASIS_POINT_MAX, ONE_Q64, U24_MAX},
     params::swap::TradeDirection,
     safe_math::SafeMath,
     u128x128_math::Rounding,
-    utils_math::{safe_mul_div_cast_u64, safe_shl_div_cast},
+    utils_math::safe_shl_div_cast,
     PoolError,
 };
 
 use super::CollectFeeMode;
 
-/// Encodes all results of swapping
-#[derive(Debug, PartialEq)]
-pub struct FeeOnAmountResult {
-    pub amount: u64,
-    pub lp_fee: u64,
-    pub protocol_fee: u64,
-    pub partner_fee: u64,
-    pub referral_fee: u64,
-}
+pub use cp_amm_core::fee_math::FeeOnAmountResult;
 
 /// collect fee mode
 #[repr(u8)]
@@ -56,6 +46,7 @@ pub enum FeeSchedulerMode {
 /// protocol_fee = trading_fee * protocol_fee_percentage / 100
 /// referral_fee = protocol_fee * referral_percentage / 100
 /// partner_fee = (protocol_fee - referral_fee) * partner_fee_percentage / denominator
+/// insurance_fee = claimed_protocol_fee * insurance_fee_percentage / 100
 #[derive(Debug, InitSpace, Default)]
 pub struct PoolFeesStruct {
     /// Trade fees are extra token amounts that are held inside the token
@@ -72,18 +63,56 @@ pub struct PoolFeesStruct {
     pub partner_fee_percent: u8,
     /// referral fee
     pub referral_fee_percent: u8,
+    /// share of claimed protocol fee routed to insurance fund of pool, in percent
+    pub insurance_fee_percent: u8,
     /// padding
-    pub padding_0: [u8; 5],
+    pub padding_0: [u8; 4],
 
     /// dynamic fee
     pub dynamic_fee: DynamicFeeStruct,
 
-    /// padding
-    pub padding_1: [u64; 2],
+    /// queued dynamic fee update
+    pub pending_dynamic_fee: PendingDynamicFeeStruct,
 }
 
 const_assert_eq!(PoolFeesStruct::INIT_SPACE, 160);
 
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct PendingDynamicFeeStruct {
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub variable_fee_control: u32,
+    /// timestamp from which the update takes effect, 0 means no pending update
+    pub activation_point: u64,
+}
+
+const_assert_eq!(PendingDynamicFeeStruct::INIT_SPACE, 16);
+
+impl PendingDynamicFeeStruct {
+    pub fn is_pending(&self) -> bool {
+        self.activation_point != 0
+    }
+}
+
+/// Base fee override queued by pool creator authority of config
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct PendingBaseFeeStruct {
+    /// constant trade fee numerator replacing base fee
+    pub trade_fee_numerator: u64,
+    /// timestamp from which the override takes effect, 0 means no pending override
+    pub activation_point: u64,
+}
+
+const_assert_eq!(PendingBaseFeeStruct::INIT_SPACE, 16);
+
+impl PendingBaseFeeStruct {
+    pub fn is_pending(&self) -> bool {
+        self.activation_point != 0
+    }
+}
+
 #[zero_copy]
 #[derive(Debug, InitSpace, Default)]
 pub struct BaseFeeStruct {
@@ -99,6 +128,14 @@ pub struct BaseFeeStruct {
 const_assert_eq!(BaseFeeStruct::INIT_SPACE, 40);
 
 impl BaseFeeStruct {
+    /// Replace base fee by a constant trade fee, fee scheduler is removed
+    pub fn override_trade_fee(&mut self, trade_fee_numerator: u64) {
+        self.cliff_fee_numerator = trade_fee_numerator;
+        self.number_of_period = 0;
+        self.period_frequency = 0;
+        self.reduction_factor = 0;
+    }
+
     pub fn get_max_base_fee_numerator(&self) -> u64 {
         self.cliff_fee_numerator
     }
@@ -111,39 +148,74 @@ impl BaseFeeStruct {
         current_point: u64,
         activation_point: u64,
     ) -> Result<u64> {
-        if self.period_frequency == 0 {
-            return Ok(self.cliff_fee_numerator);
-        }
         // can trade before activation point, so it is alpha-vault, we use min fee
-        let period = if current_point < activation_point {
-            self.number_of_period.into()
-        } else {
-            let period = current_point
-                .safe_sub(activation_point)?
-                .safe_div(self.period_frequency)?;
-            period.min(self.number_of_period.into())
-        };
-        let fee_scheduler_mode = FeeSchedulerMode::try_from(self.fee_scheduler_mode)
-            .map_err(|_| PoolError::TypeCastFailed)?;
-
-        match fee_scheduler_mode {
-            FeeSchedulerMode::Linear => {
-                let fee_numerator = self
-                    .cliff_fee_numerator
-                    .safe_sub(period.safe_mul(self.reduction_factor.into())?)?;
-                Ok(fee_numerator)
-            }
-            FeeSchedulerMode::Exponential => {
-                let period = u16::try_from(period).map_err(|_| PoolError::MathOverflow)?;
-                let fee_numerator =
-                    get_fee_in_period(self.cliff_fee_numerator, self.reduction_factor, period)?;
-                Ok(fee_numerator)
-            }
-        }
+        Ok(fee_math::get_base_fee_numerator(
+            self.cliff_fee_numerator,
+            self.fee_scheduler_mode,
+            self.number_of_period,
+            self.period_frequency,
+            self.reduction_factor,
+            current_point,
+            activation_point,
+        )
+        .map_err(PoolError::from)?)
     }
 }
 
 impl PoolFeesStruct {
+    /// Queue an update of dynamic fee parameters, replacing the pending one
+    pub fn queue_dynamic_fee_update(
+        &mut self,
+        decay_period: u16,
+        reduction_factor: u16,
+        variable_fee_control: u32,
+        activation_point: u64,
+    ) -> Result<()> {
+        self.dynamic_fee.validate_parameters(
+            decay_period,
+            reduction_factor,
+            variable_fee_control,
+        )?;
+        self.pending_dynamic_fee = PendingDynamicFeeStruct {
+            decay_period,
+            reduction_factor,
+            variable_fee_control,
+            activation_point,
+        };
+        Ok(())
+    }
+
+    /// Apply pending dynamic fee update once its activation point is reached
+    pub fn apply_pending_dynamic_fee_update(&mut self, current_timestamp: u64) -> Result<()> {
+        let pending = self.pending_dynamic_fee;
+        if pending.is_pending() && current_timestamp >= pending.activation_point {
+            self.dynamic_fee.update_parameters(
+                pending.decay_period,
+                pending.reduction_factor,
+                pending.variable_fee_control,
+            )?;
+            self.pending_dynamic_fee = PendingDynamicFeeStruct::default();
+        }
+        Ok(())
+    }
+
+    pub fn cancel_pending_dynamic_fee_update(&mut self) -> Result<()> {
+        require!(
+            self.pending_dynamic_fee.is_pending(),
+            PoolError::NoPendingUpdate
+        );
+        self.pending_dynamic_fee = PendingDynamicFeeStruct::default();
+        Ok(())
+    }
+
+    /// Share of claimed protocol fee routed to insurance fund, rounded down
+    pub fn get_insurance_fee(&self, protocol_fee: u64) -> Result<u64> {
+        let insurance_fee = u128::from(protocol_fee)
+            .safe_mul(self.insurance_fee_percent.into())?
+            .safe_div(100)?;
+        Ok(u64::try_from(insurance_fee).map_err(|_| PoolError::TypeCastFailed)?)
+    }
+
     // in numerator
     pub fn get_total_trading_fee(&self, current_point: u64, activation_point: u64) -> Result<u128> {
         let base_fee_numerator = self
@@ -160,57 +232,21 @@ impl PoolFeesStruct {
         &self,
         amount: u64,
         has_referral: bool,
+        fee_discount_bps: u16,
         current_point: u64,
         activation_point: u64,
     ) -> Result<FeeOnAmountResult> {
         let trade_fee_numerator = self.get_total_trading_fee(current_point, activation_point)?;
-        let trade_fee_numerator = if trade_fee_numerator > MAX_FEE_NUMERATOR.into() {
-            MAX_FEE_NUMERATOR
-        } else {
-            trade_fee_numerator.try_into().unwrap()
-        };
-        let lp_fee: u64 =
-            safe_mul_div_cast_u64(amount, trade_fee_numerator, FEE_DENOMINATOR, Rounding::Up)?;
-        // update amount
-        let amount = amount.safe_sub(lp_fee)?;
-
-        let protocol_fee = safe_mul_div_cast_u64(
-            lp_fee,
-            self.protocol_fee_percent.into(),
-            100,
-            Rounding::Down,
-        )?;
-        // update lp fee
-        let lp_fee = lp_fee.safe_sub(protocol_fee)?;
-
-        let referral_fee = if has_referral {
-            safe_mul_div_cast_u64(
-                protocol_fee,
-                self.referral_fee_percent.into(),
-                100,
-                Rounding::Down,
-            )?
-        } else {
-            0
-        };
-
-        let protocol_fee_after_referral_fee = protocol_fee.safe_sub(referral_fee)?;
-        let partner_fee = safe_mul_div_cast_u64(
-            protocol_fee_after_referral_fee,
-            self.partner_fee_percent.into(),
-            100,
-            Rounding::Down,
-        )?;
-
-        let protocol_fee = protocol_fee_after_referral_fee.safe_sub(partner_fee)?;
-
-        Ok(FeeOnAmountResult {
+        Ok(fee_math::get_fee_on_amount(
             amount,
-            lp_fee,
-            protocol_fee,
-            partner_fee,
-            referral_fee,
-        })
+            trade_fee_numerator,
+            self.protocol_fee_percent,
+            self.partner_fee_percent,
+            self.referral_fee_percent,
+            has_referral,
+            fee_discount_bps,
+        )
+        .map_err(PoolError::from)?)
     }
 }
 
@@ -235,6 +271,46 @@ pub struct DynamicFeeStruct {
 const_assert_eq!(DynamicFeeStruct::INIT_SPACE, 96);
 
 impl DynamicFeeStruct {
+    /// Validate decay period, reduction factor and variable fee control for an update of an initialized dynamic fee
+    pub fn validate_parameters(
+        &self,
+        decay_period: u16,
+        reduction_factor: u16,
+        variable_fee_control: u32,
+    ) -> Result<()> {
+        require!(
+            self.is_dynamic_fee_enable(),
+            PoolError::DynamicFeeNotInitialized
+        );
+
+        // same bounds as DynamicFeeParameters::validate
+        require!(self.filter_period < decay_period, PoolError::InvalidInput);
+        require!(
+            reduction_factor <= BASIS_POINT_MAX as u16,
+            PoolError::InvalidInput
+        );
+        require!(variable_fee_control <= U24_MAX, PoolError::InvalidInput);
+
+        Ok(())
+    }
+
+    /// Update decay period, reduction factor and variable fee control of an initialized dynamic fee.
+    /// Volatility states are kept, so the variable fee is continuous after the update
+    pub fn update_parameters(
+        &mut self,
+        decay_period: u16,
+        reduction_factor: u16,
+        variable_fee_control: u32,
+    ) -> Result<()> {
+        self.validate_parameters(decay_period, reduction_factor, variable_fee_control)?;
+
+        self.decay_period = decay_period;
+        self.reduction_factor = reduction_factor;
+        self.variable_fee_control = variable_fee_control;
+
+        Ok(())
+    }
+
     // we approximate Px / Py = (1 + b) ^ delta_bin  = 1 + b * delta_bin (if b is too small)
     // Ex: (1+1/10000)^ 5000 / (1+5000 * 1/10000) = 1.1 (10% diff if sqrt_price diff is (1+1/10000)^ 5000 = 1.64 times)
     pub fn get_delta_bin_id(
@@ -303,18 +379,12 @@ impl DynamicFeeStruct {
 
     pub fn get_variable_fee(&self) -> Result<u128> {
         if self.is_dynamic_fee_enable() {
-            let square_vfa_bin: u128 = self
-                .volatility_accumulator
-                .safe_mul(self.bin_step.into())?
-                .checked_pow(2)
-                .unwrap();
-            // Variable fee control, volatility accumulator, bin step are in basis point unit (10_000)
-            // This is 1e20. Which > 1e9. Scale down it to 1e9 unit and ceiling the remaining.
-            let v_fee = square_vfa_bin.safe_mul(self.variable_fee_control.into())?;
-
-            let scaled_v_fee = v_fee.safe_add(99_999_999_999)?.safe_div(100_000_000_000)?;
-
-            Ok(scaled_v_fee)
+            Ok(fee_math::get_variable_fee(
+                self.volatility_accumulator,
+                self.bin_step,
+                self.variable_fee_control,
+            )
+            .map_err(PoolError::from)?)
         } else {
             Ok(0)
         }
@@ -326,6 +396,8 @@ pub struct FeeMode {
     pub fees_on_input: bool,
     pub fees_on_token_a: bool,
     pub has_referral: bool,
+    /// discount on trade fee numerator from fee discount registry
+    pub fee_discount_bps: u16,
 }
 
 impl FeeMode {
@@ -345,14 +417,26 @@ impl FeeMode {
             // When collecting fees on tokenB
             (CollectFeeMode::OnlyB, TradeDirection::AtoB) => (false, false),
             (CollectFeeMode::OnlyB, TradeDirection::BtoA) => (true, false),
+
+            // When collecting fees on tokenA
+            (CollectFeeMode::OnlyA, TradeDirection::AtoB) => (true, true),
+            (CollectFeeMode::OnlyA, TradeDirection::BtoA) => (false, true),
         };
 
         Ok(FeeMode {
             fees_on_input,
             fees_on_token_a,
             has_referral,
+            fee_discount_bps: 0,
         })
     }
+
+    pub fn with_fee_discount(self, fee_discount_bps: u16) -> FeeMode {
+        FeeMode {
+            fee_discount_bps,
+            ..self
+        }
+    }
 }
 
 #[cfg(test)]
@@ -404,10 +488,31 @@ mod tests {
         assert_eq!(fee_mode.has_referral, true);
     }
 
+    #[test]
+    fn test_fee_mode_only_a_token_a_to_b() {
+        let fee_mode =
+            FeeMode::get_fee_mode(CollectFeeMode::OnlyA as u8, TradeDirection::AtoB, true).unwrap();
+
+        assert_eq!(fee_mode.fees_on_input, true);
+        assert_eq!(fee_mode.fees_on_token_a, true);
+        assert_eq!(fee_mode.has_referral, true);
+    }
+
+    #[test]
+    fn test_fee_mode_only_a_token_b_to_a() {
+        let fee_mode =
+            FeeMode::get_fee_mode(CollectFeeMode::OnlyA as u8, TradeDirection::BtoA, false)
+                .unwrap();
+
+        assert_eq!(fee_mode.fees_on_input, false);
+        assert_eq!(fee_mode.fees_on_token_a, true);
+        assert_eq!(fee_mode.has_referral, false);
+    }
+
     #[test]
     fn test_invalid_collect_fee_mode() {
         let result = FeeMode::get_fee_mode(
-            2, // Invalid mode
+            3, // Invalid mode
             TradeDirection::BtoA,
             false,
         );
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::fee::{MAX_FEE_DISCOUNT_BPS, MAX_FEE_DISCOUNT_TIERS},
+    PoolError,
+};
+
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct FeeDiscountTier {
+    /// minimum amount of discount token held by trader
+    pub min_amount: u64,
+    /// discount on trade fee numerator
+    pub discount_bps: u16,
+    pub padding: [u8; 6],
+}
+
+const_assert_eq!(FeeDiscountTier::INIT_SPACE, 16);
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Trade fee discount tiers for traders holding discount token of a pool
+pub struct FeeDiscountRegistry {
+    /// pool
+    pub pool: Pubkey,
+    /// discount token mint
+    pub mint: Pubkey,
+    /// tiers sorted by min_amount, unused tiers are zero
+    pub tiers: [FeeDiscountTier; MAX_FEE_DISCOUNT_TIERS],
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(FeeDiscountRegistry::INIT_SPACE, 160);
+
+impl FeeDiscountRegistry {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        mint: Pubkey,
+        tiers: &[FeeDiscountTier],
+    ) -> Result<()> {
+        self.pool = pool;
+        self.mint = mint;
+        self.update_tiers(tiers)
+    }
+
+    pub fn update_tiers(&mut self, tiers: &[FeeDiscountTier]) -> Result<()> {
+        validate_fee_discount_tiers(tiers)?;
+
+        self.tiers = [FeeDiscountTier::default(); MAX_FEE_DISCOUNT_TIERS];
+        for (i, tier) in tiers.iter().enumerate() {
+            self.tiers[i] = *tier;
+        }
+        Ok(())
+    }
+
+    /// Discount of the highest tier reached by amount
+    pub fn get_fee_discount_bps(&self, amount: u64) -> u16 {
+        self.tiers
+            .iter()
+            .filter(|tier| tier.discount_bps > 0 && amount >= tier.min_amount)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Tiers must be strictly increasing in both min_amount and discount_bps
+fn validate_fee_discount_tiers(tiers: &[FeeDiscountTier]) -> Result<()> {
+    require!(
+        tiers.len() <= MAX_FEE_DISCOUNT_TIERS,
+        PoolError::InvalidFeeDiscountTiers
+    );
+
+    for (i, tier) in tiers.iter().enumerate() {
+        require!(
+            tier.discount_bps > 0 && tier.discount_bps <= MAX_FEE_DISCOUNT_BPS,
+            PoolError::InvalidFeeDiscountTiers
+        );
+        if i > 0 {
+            let prev_tier = &tiers[i - 1];
+            require!(
+                tier.min_amount > prev_tier.min_amount
+                    && tier.discount_bps > prev_tier.discount_bps,
+                PoolError::InvalidFeeDiscountTiers
+            );
+        }
+    }
+    Ok(())
+}
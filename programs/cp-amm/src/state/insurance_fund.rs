@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::safe_math::SafeMath;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Insurance fund of a pool, receives `insurance_fee_percent` of protocol fee claimed from the pool,
+/// its authority withdraws from the insurance vaults to cover losses
+pub struct InsuranceFund {
+    /// pool
+    pub pool: Pubkey,
+    /// authority allowed to claim from insurance vaults
+    pub authority: Pubkey,
+    /// insurance vault of token a
+    pub token_a_vault: Pubkey,
+    /// insurance vault of token b
+    pub token_b_vault: Pubkey,
+    /// total token a routed from protocol fee
+    pub total_routed_a_amount: u64,
+    /// total token b routed from protocol fee
+    pub total_routed_b_amount: u64,
+    /// total token a claimed by authority
+    pub total_claimed_a_amount: u64,
+    /// total token b claimed by authority
+    pub total_claimed_b_amount: u64,
+    /// Reserve
+    pub _padding: [u8; 64],
+}
+
+const_assert_eq!(InsuranceFund::INIT_SPACE, 224);
+
+impl InsuranceFund {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        authority: Pubkey,
+        token_a_vault: Pubkey,
+        token_b_vault: Pubkey,
+    ) {
+        self.pool = pool;
+        self.authority = authority;
+        self.token_a_vault = token_a_vault;
+        self.token_b_vault = token_b_vault;
+    }
+
+    pub fn accumulate_routed_amount(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.total_routed_a_amount = self.total_routed_a_amount.safe_add(amount_a)?;
+        self.total_routed_b_amount = self.total_routed_b_amount.safe_add(amount_b)?;
+        Ok(())
+    }
+
+    pub fn accumulate_claimed_amount(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.total_claimed_a_amount = self.total_claimed_a_amount.safe_add(amount_a)?;
+        self.total_claimed_b_amount = self.total_claimed_b_amount.safe_add(amount_b)?;
+        Ok(())
+    }
+}
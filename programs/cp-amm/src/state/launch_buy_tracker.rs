@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{safe_math::SafeMath, PoolError};
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Cumulative buy amount of a wallet in a pool while launch guard of the pool is active
+pub struct LaunchBuyTracker {
+    /// pool
+    pub pool: Pubkey,
+    /// wallet, swap payer
+    pub wallet: Pubkey,
+    /// cumulative token b input amount of buys
+    pub total_buy_amount: u64,
+    /// Reserve
+    pub _padding: [u8; 24],
+}
+
+const_assert_eq!(LaunchBuyTracker::INIT_SPACE, 96);
+
+impl LaunchBuyTracker {
+    pub fn initialize(&mut self, pool: Pubkey, wallet: Pubkey) {
+        self.pool = pool;
+        self.wallet = wallet;
+    }
+
+    pub fn accumulate_buy_amount(&mut self, amount: u64, max_buy_amount: u64) -> Result<()> {
+        self.total_buy_amount = self.total_buy_amount.safe_add(amount)?;
+        require!(
+            self.total_buy_amount <= max_buy_amount,
+            PoolError::MaxBuyAmountExceeded
+        );
+        Ok(())
+    }
+}
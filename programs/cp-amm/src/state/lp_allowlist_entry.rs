@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Wallet allowed to add liquidity and create position in a permissioned lp pool
+pub struct LpAllowlistEntry {
+    /// pool
+    pub pool: Pubkey,
+    /// allowlisted wallet, position owner adding liquidity or payer creating position
+    pub wallet: Pubkey,
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(LpAllowlistEntry::INIT_SPACE, 96);
+
+impl LpAllowlistEntry {
+    pub fn initialize(&mut self, pool: Pubkey, wallet: Pubkey) {
+        self.pool = pool;
+        self.wallet = wallet;
+    }
+}
@@ -11,3 +11,33 @@ pub mod vesting;
 pub use vesting::*;
 pub mod claim_fee_operator;
 pub use claim_fee_operator::*;
+pub mod reward_extension;
+pub use reward_extension::*;
+pub mod partner_fee_receiver;
+pub use partner_fee_receiver::*;
+pub mod referral;
+pub use referral::*;
+pub mod fee_discount_registry;
+pub use fee_discount_registry::*;
+pub mod program_authority;
+pub use program_authority::*;
+pub mod vault_whitelist;
+pub use vault_whitelist::*;
+pub mod swap_allowlist_entry;
+pub use swap_allowlist_entry::*;
+pub mod lp_allowlist_entry;
+pub use lp_allowlist_entry::*;
+pub mod launch_buy_tracker;
+pub use launch_buy_tracker::*;
+pub mod pool_metadata;
+pub use pool_metadata::*;
+pub mod pool_stats;
+pub use pool_stats::*;
+pub mod reward_vesting_escrow;
+pub use reward_vesting_escrow::*;
+pub mod trade_mining;
+pub use trade_mining::*;
+pub mod insurance_fund;
+pub use insurance_fund::*;
+pub mod buyback_burn;
+pub use buyback_burn::*;
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Token account registered by partner to receive partner fee of a mint
+pub struct PartnerFeeReceiver {
+    /// partner
+    pub partner: Pubkey,
+    /// token mint
+    pub mint: Pubkey,
+    /// token account receiving partner fee
+    pub token_account: Pubkey,
+    /// key allowed to claim partner fee to token account besides partner, default pubkey means none
+    pub operator: Pubkey,
+    /// Reserve
+    pub _padding: [u8; 64],
+}
+
+const_assert_eq!(PartnerFeeReceiver::INIT_SPACE, 192);
+
+impl PartnerFeeReceiver {
+    pub fn initialize(
+        &mut self,
+        partner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        operator: Pubkey,
+    ) {
+        self.partner = partner;
+        self.mint = mint;
+        self.update(token_account, operator);
+    }
+
+    pub fn update(&mut self, token_account: Pubkey, operator: Pubkey) {
+        self.token_account = token_account;
+        self.operator = operator;
+    }
+
+    pub fn is_operator(&self, signer: Pubkey) -> bool {
+        self.operator != Pubkey::default() && self.operator == signer
+    }
+}
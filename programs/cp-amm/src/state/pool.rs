@@ -1,23 +1,29 @@
 use ruint::aliases::U256;
 use static_assertions::const_assert_eq;
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::u64;
 
 use anchor_lang::prelude::*;
+use cp_amm_core::{
+    quote::{quote_exact_in, QuoteParams, SwapQuote},
+    transfer_fee::TransferFee,
+};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::{
-    assert_eq_admin,
-    constants::{LIQUIDITY_SCALE, NUM_REWARDS, REWARD_RATE_SCALE},
+    constants::{
+        BASIS_POINT_MAX, LIQUIDITY_SCALE, MAX_REWARD_EMISSION_PHASES, NUM_REWARDS, ONE_Q64,
+        REWARD_RATE_SCALE, ROLLING_FEE_WINDOW,
+    },
     curve::{
         get_delta_amount_a_unsigned, get_delta_amount_a_unsigned_unchecked,
-        get_delta_amount_b_unsigned, get_next_sqrt_price_from_input,
+        get_delta_amount_b_unsigned, get_liquidity_for_amounts,
     },
     params::swap::TradeDirection,
     safe_math::SafeMath,
     state::{
-        fee::{DynamicFeeStruct, FeeOnAmountResult, PoolFeesStruct},
-        Position,
+        fee::{DynamicFeeStruct, PendingBaseFeeStruct, PendingDynamicFeeStruct, PoolFeesStruct},
+        AuthorityRole, Position, ProgramAuthority,
     },
     u128x128_math::{shl_div_256, Rounding},
     utils_math::{safe_mul_shr_cast, safe_shl_div_cast},
@@ -41,11 +47,13 @@ use super::fee::FeeMode;
 pub enum CollectFeeMode {
     /// Both token, in this mode only out token is collected
     BothToken,
-    /// Only token B, we just need token B, because if user want to collect fee in token A, they just need to flip order of tokens
+    /// Only token B, fee is collected on input when swapping B to A and on output when swapping A to B
     OnlyB,
+    /// Only token A, fee is collected on input when swapping A to B and on output when swapping B to A
+    OnlyA,
 }
 
-/// pool status
+/// pool operation, bit `1 << operation` of pool status disables the operation
 #[repr(u8)]
 #[derive(
     Clone,
@@ -57,11 +65,25 @@ pub enum CollectFeeMode {
     AnchorDeserialize,
     AnchorSerialize,
 )]
-pub enum PoolStatus {
-    Enable,
-    Disable,
+pub enum PoolOperation {
+    Swap,
+    AddLiquidity,
+    RemoveLiquidity,
+    CreatePosition,
+}
+
+impl PoolOperation {
+    pub fn mask(&self) -> u8 {
+        1 << u8::from(*self)
+    }
 }
 
+/// pool status bits that can be set
+pub const POOL_STATUS_MASK: u8 = 0b1111;
+
+/// Layout version of new pools. Pools created before versioning have version 0 and a shorter account
+pub const POOL_VERSION: u8 = 1;
+
 #[repr(u8)]
 #[derive(
     Clone,
@@ -97,8 +119,9 @@ pub struct Pool {
     pub partner: Pubkey,
     /// liquidity share
     pub liquidity: u128,
-    /// padding, previous reserve amount, be careful to use that field
-    pub _padding: u128,
+    /// Sum of sqrt price weighted by seconds elapsed at last oracle update, wraps on overflow.
+    /// Previous reserve amount of old pools, it is reset on first oracle update
+    pub last_cumulative_sqrt_price: u128,
     /// protocol a fee
     pub protocol_a_fee: u64,
     /// protocol b fee
@@ -117,18 +140,20 @@ pub struct Pool {
     pub activation_point: u64,
     /// Activation type, 0 means by slot, 1 means by timestamp
     pub activation_type: u8,
-    /// pool status, 0: enable, 1 disable
+    /// pool status, bitfield of disabled operations, bit 0: swap, bit 1: add liquidity, bit 2: remove liquidity, bit 3: create position
     pub pool_status: u8,
-    /// token a flag
+    /// token a flag, bit 0 is token program, higher bits are mint extension findings
     pub token_a_flag: u8,
-    /// token b flag
+    /// token b flag, bit 0 is token program, higher bits are mint extension findings
     pub token_b_flag: u8,
-    /// 0 is collect fee in both token, 1 only collect fee in token a, 2 only collect fee in token b
+    /// 0 is collect fee in both token, 1 only collect fee in token b, 2 only collect fee in token a
     pub collect_fee_mode: u8,
     /// pool type
     pub pool_type: u8,
-    /// padding
-    pub _padding_0: [u8; 2],
+    /// 1 if pool has a reward extension, additional rewards are tracked in reward extension account
+    pub reward_extension_flag: u8,
+    /// 1 if pool is in emergency mode, only withdrawals are allowed and vesting locks are ignored
+    pub emergency_mode: u8,
     /// cumulative
     pub fee_a_per_liquidity: [u8; 32], // U256
     /// cumulative
@@ -137,13 +162,63 @@ pub struct Pool {
     pub permanent_lock_liquidity: u128,
     /// metrics
     pub metrics: PoolMetrics,
-    /// Padding for further use
-    pub _padding_1: [u64; 10],
+    /// Authority managing swap allowlist of a permissioned pool, default pubkey means swap is permissionless
+    pub swap_allowlist_authority: Pubkey,
+    /// Cap of buy amount per window after activation point
+    pub swap_rate_limiter: SwapRateLimiter,
+    /// Cap of cumulative buy amount per wallet for a duration after activation point
+    pub launch_guard: LaunchGuard,
     /// Farming reward information
     pub reward_infos: [RewardInfo; NUM_REWARDS],
+    /// Layout version of pool account, pools created before `POOL_VERSION` are upgraded by `migrate_pool`
+    pub version: u8,
+    /// 1 if claimed lp fee and unclaimed referral fee are tracked since pool initialization, required by `skim_vaults`.
+    /// Pools upgraded by `migrate_pool` don't track them
+    pub fee_tracking_flag: u8,
+    /// 1 if protocol fee in token b is reserved for `buyback_and_burn`, it can't be claimed by `claim_protocol_fee`
+    pub buyback_burn_flag: u8,
+    /// padding for future fields
+    pub _padding_0: [u8; 5],
+    /// Duration after activation point that swaps must be top-level instructions, copied from config at initialization
+    pub cpi_guard_duration: u64,
+    /// total lp fee in token a claimed by positions
+    pub total_claimed_lp_a_fee: u128,
+    /// total lp fee in token b claimed by positions
+    pub total_claimed_lp_b_fee: u128,
+    /// referral fee in token a accrued to referral accounts and not claimed yet
+    pub unclaimed_referral_a_fee: u64,
+    /// referral fee in token b accrued to referral accounts and not claimed yet
+    pub unclaimed_referral_b_fee: u64,
+    /// Cap of sqrt price change of swaps within a slot
+    pub price_circuit_breaker: PriceCircuitBreaker,
+    /// Epoch transfer fee of token a mint
+    pub transfer_fee_cache_a: TransferFeeCache,
+    /// Epoch transfer fee of token b mint
+    pub transfer_fee_cache_b: TransferFeeCache,
+    /// Extra reward weight of vested liquidity in bps of liquidity, copied from config at initialization
+    pub vesting_reward_boost_bps: u16,
+    /// Extra reward weight of permanently locked liquidity in bps of liquidity, copied from config at initialization
+    pub permanent_lock_reward_boost_bps: u16,
+    /// padding for future fields
+    pub _padding_1: [u8; 4],
+    /// Incremented by every instruction changing pool state and included in swap, liquidity and fee claim events,
+    /// so indexers can order events of a pool
+    pub sequence: u64,
+    /// Vested liquidity of all positions, only tracked when vesting reward boost is set
+    pub total_vested_liquidity: u128,
+    /// Authority managing lp allowlist of a permissioned lp pool, default pubkey means adding liquidity is permissionless
+    pub lp_allowlist_authority: Pubkey,
+    /// Lifetime swap volume and lp fee of the last 24 hours
+    pub volume_metrics: PoolVolumeMetrics,
+    /// queued base fee override
+    pub pending_base_fee: PendingBaseFeeStruct,
+    /// Creator of pool, default pubkey for pools created before it is recorded
+    pub creator: Pubkey,
+    /// Reserved for future fields, so they don't need another layout version
+    pub _padding_2: [u64; 8],
 }
 
-const_assert_eq!(Pool::INIT_SPACE, 1104);
+const_assert_eq!(Pool::INIT_SPACE, 1504);
 
 #[zero_copy]
 #[derive(Debug, InitSpace, Default)]
@@ -155,7 +230,8 @@ pub struct PoolMetrics {
     pub total_partner_a_fee: u64,
     pub total_partner_b_fee: u64,
     pub total_position: u64,
-    pub padding: u64,
+    /// Timestamp of last oracle update, 0 means oracle is not initialized
+    pub last_oracle_timestamp: u64,
 }
 
 const_assert_eq!(PoolMetrics::INIT_SPACE, 80);
@@ -191,6 +267,277 @@ impl PoolMetrics {
     }
 }
 
+/// Lifetime swap volume and lp fee of the last 24 hours, so frontends can show apr without replaying swap events.
+/// Lp fee is accumulated in fixed windows of `ROLLING_FEE_WINDOW` seconds, fee of the last 24 hours is estimated
+/// from the current and previous windows by `get_rolling_lp_fee`
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct PoolVolumeMetrics {
+    /// traded amount of token a, input or output of swaps
+    pub total_volume_a: u128,
+    /// traded amount of token b, input or output of swaps
+    pub total_volume_b: u128,
+    /// start timestamp of the current fee window, a multiple of the window duration
+    pub fee_window_start_timestamp: u64,
+    /// lp fee in token a of the current window
+    pub current_window_lp_a_fee: u64,
+    /// lp fee in token b of the current window
+    pub current_window_lp_b_fee: u64,
+    /// lp fee in token a of the previous window
+    pub previous_window_lp_a_fee: u64,
+    /// lp fee in token b of the previous window
+    pub previous_window_lp_b_fee: u64,
+    /// padding for future fields
+    pub _padding: [u8; 8],
+}
+
+const_assert_eq!(PoolVolumeMetrics::INIT_SPACE, 80);
+
+impl PoolVolumeMetrics {
+    // metrics saturate instead of failing, so they never block a swap
+    pub fn record_swap(
+        &mut self,
+        current_timestamp: u64,
+        amount_a: u64,
+        amount_b: u64,
+        lp_fee: u64,
+        fees_on_token_a: bool,
+    ) {
+        self.total_volume_a = self.total_volume_a.saturating_add(amount_a.into());
+        self.total_volume_b = self.total_volume_b.saturating_add(amount_b.into());
+
+        let ([current_a, current_b], [previous_a, previous_b]) =
+            self.get_window_lp_fees(current_timestamp);
+        self.fee_window_start_timestamp = get_fee_window_start(current_timestamp);
+        self.previous_window_lp_a_fee = previous_a;
+        self.previous_window_lp_b_fee = previous_b;
+        if fees_on_token_a {
+            self.current_window_lp_a_fee = current_a.saturating_add(lp_fee);
+            self.current_window_lp_b_fee = current_b;
+        } else {
+            self.current_window_lp_a_fee = current_a;
+            self.current_window_lp_b_fee = current_b.saturating_add(lp_fee);
+        }
+    }
+
+    /// Lp fee in token a and b of the last `ROLLING_FEE_WINDOW` seconds, fee of the previous window is weighted
+    /// by the part of it that is still within the rolling window
+    pub fn get_rolling_lp_fee(&self, current_timestamp: u64) -> (u64, u64) {
+        let (current, previous) = self.get_window_lp_fees(current_timestamp);
+        let remaining = ROLLING_FEE_WINDOW - current_timestamp % ROLLING_FEE_WINDOW;
+        let rolling_fee = |current: u64, previous: u64| {
+            let weighted_previous =
+                u128::from(previous) * u128::from(remaining) / u128::from(ROLLING_FEE_WINDOW);
+            // weighted previous fee is at most previous fee, so it fits in u64
+            current.saturating_add(weighted_previous as u64)
+        };
+        (
+            rolling_fee(current[0], previous[0]),
+            rolling_fee(current[1], previous[1]),
+        )
+    }
+
+    /// Lp fee in token a and b of the window of a timestamp and the window before it
+    fn get_window_lp_fees(&self, current_timestamp: u64) -> ([u64; 2], [u64; 2]) {
+        let window_start = get_fee_window_start(current_timestamp);
+        let current = [self.current_window_lp_a_fee, self.current_window_lp_b_fee];
+        if window_start == self.fee_window_start_timestamp {
+            (
+                current,
+                [self.previous_window_lp_a_fee, self.previous_window_lp_b_fee],
+            )
+        } else if window_start
+            == self
+                .fee_window_start_timestamp
+                .saturating_add(ROLLING_FEE_WINDOW)
+        {
+            ([0, 0], current)
+        } else {
+            ([0, 0], [0, 0])
+        }
+    }
+}
+
+fn get_fee_window_start(timestamp: u64) -> u64 {
+    timestamp - timestamp % ROLLING_FEE_WINDOW
+}
+
+/// Caps token b input amount of swaps buying token a in every window of the first
+/// `window_count` windows after activation point. Window duration is in slots or seconds,
+/// by activation type of pool
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct SwapRateLimiter {
+    /// max token b input amount in a window
+    pub max_amount_per_window: u64,
+    /// token b input amount in current window
+    pub window_amount: u64,
+    /// start point of current window
+    pub window_start_point: u64,
+    /// window duration, 0 means the rate limiter is disabled
+    pub window_duration: u32,
+    /// number of windows after activation point that the rate limiter applies to
+    pub window_count: u32,
+}
+
+const_assert_eq!(SwapRateLimiter::INIT_SPACE, 32);
+
+impl SwapRateLimiter {
+    pub fn is_enabled(&self) -> bool {
+        self.window_duration != 0
+    }
+
+    /// Accumulate buy amount into window of current point, fails when cap of the window is exceeded
+    pub fn accumulate(
+        &mut self,
+        activation_point: u64,
+        current_point: u64,
+        amount: u64,
+    ) -> Result<()> {
+        if !self.is_enabled() || current_point < activation_point {
+            return Ok(());
+        }
+        let window_duration: u64 = self.window_duration.into();
+        let window_index = current_point
+            .safe_sub(activation_point)?
+            .safe_div(window_duration)?;
+        if window_index >= self.window_count.into() {
+            return Ok(());
+        }
+
+        let window_start_point =
+            activation_point.safe_add(window_index.safe_mul(window_duration)?)?;
+        if window_start_point != self.window_start_point {
+            self.window_start_point = window_start_point;
+            self.window_amount = 0;
+        }
+        self.window_amount = self.window_amount.safe_add(amount)?;
+        require!(
+            self.window_amount <= self.max_amount_per_window,
+            PoolError::SwapRateLimitExceeded
+        );
+        Ok(())
+    }
+}
+
+/// Caps cumulative token b input amount of swaps buying token a per wallet, from activation point
+/// for `duration` slots or seconds by activation type of pool. Buys of a wallet are tracked in its launch buy tracker
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct LaunchGuard {
+    /// max cumulative token b input amount of a wallet
+    pub max_buy_amount_per_wallet: u64,
+    /// duration after activation point, 0 means the launch guard is disabled
+    pub duration: u64,
+}
+
+const_assert_eq!(LaunchGuard::INIT_SPACE, 16);
+
+impl LaunchGuard {
+    pub fn is_active(&self, activation_point: u64, current_point: u64) -> Result<bool> {
+        Ok(self.duration != 0
+            && current_point >= activation_point
+            && current_point < activation_point.safe_add(self.duration)?)
+    }
+}
+
+/// Caps sqrt price change of swaps within a slot at `max_sqrt_price_change_bps` of sqrt price at the start of the slot,
+/// so a single transaction can't move price of a thin pool far
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct PriceCircuitBreaker {
+    /// sqrt price before the first swap of `last_slot`
+    pub slot_start_sqrt_price: u128,
+    /// slot of the last swap
+    pub last_slot: u64,
+    /// max sqrt price change in a slot in bps of sqrt price at the start of the slot, 0 means the circuit breaker is disabled
+    pub max_sqrt_price_change_bps: u16,
+    /// padding
+    pub _padding: [u8; 6],
+}
+
+const_assert_eq!(PriceCircuitBreaker::INIT_SPACE, 32);
+
+impl PriceCircuitBreaker {
+    pub fn is_enabled(&self) -> bool {
+        self.max_sqrt_price_change_bps != 0
+    }
+
+    /// Check sqrt price after a swap against sqrt price at the start of current slot, fails when the change exceeds the cap
+    pub fn check_price_change(
+        &mut self,
+        current_slot: u64,
+        sqrt_price: u128,
+        next_sqrt_price: u128,
+    ) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        if current_slot != self.last_slot {
+            self.last_slot = current_slot;
+            self.slot_start_sqrt_price = sqrt_price;
+        }
+        let max_sqrt_price_change = self
+            .slot_start_sqrt_price
+            .safe_mul(self.max_sqrt_price_change_bps.into())?
+            .safe_div(BASIS_POINT_MAX.into())?;
+        require!(
+            next_sqrt_price.abs_diff(self.slot_start_sqrt_price) <= max_sqrt_price_change,
+            PoolError::PriceCircuitBreakerTriggered
+        );
+        Ok(())
+    }
+}
+
+/// Transfer fee of a token 2022 mint at an epoch, cached so swaps don't unpack mint extensions every time.
+/// Transfer fee authority can only change the fee from 2 epochs later, so the fee is fixed within an epoch
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct TransferFeeCache {
+    /// epoch of the cached transfer fee
+    pub epoch: u64,
+    /// maximum fee at the epoch
+    pub maximum_fee: u64,
+    /// transfer fee basis points at the epoch
+    pub transfer_fee_basis_points: u16,
+    /// 1 if the transfer fee of epoch is cached
+    pub initialized: u8,
+    /// 1 if the mint has transfer fee extension
+    pub has_transfer_fee: u8,
+    /// padding
+    pub _padding: [u8; 4],
+}
+
+const_assert_eq!(TransferFeeCache::INIT_SPACE, 24);
+
+impl TransferFeeCache {
+    /// Cached transfer fee of the epoch, None if it isn't cached. Inner None means the mint doesn't have transfer fee extension
+    pub fn get(&self, epoch: u64) -> Option<Option<TransferFee>> {
+        if self.initialized == 0 || self.epoch != epoch {
+            return None;
+        }
+        Some((self.has_transfer_fee != 0).then_some(TransferFee {
+            transfer_fee_basis_points: self.transfer_fee_basis_points,
+            maximum_fee: self.maximum_fee,
+        }))
+    }
+
+    pub fn update(&mut self, epoch: u64, transfer_fee: Option<TransferFee>) {
+        let TransferFee {
+            transfer_fee_basis_points,
+            maximum_fee,
+        } = transfer_fee.unwrap_or_default();
+        *self = TransferFeeCache {
+            epoch,
+            maximum_fee,
+            transfer_fee_basis_points,
+            initialized: 1,
+            has_transfer_fee: transfer_fee.is_some().into(),
+            ..Default::default()
+        };
+    }
+}
+
 /// Stores the state relevant for tracking liquidity mining rewards
 #[zero_copy]
 #[derive(InitSpace, Default, Debug, PartialEq)]
@@ -199,10 +546,14 @@ pub struct RewardInfo {
     pub initialized: u8,
     /// reward token flag
     pub reward_token_flag: u8,
-    /// padding
-    pub _padding_0: [u8; 6],
+    /// decay of reward rate after every emission phase, in basis point. 0 means flat emission
+    pub emission_decay_bps: u16,
+    /// duration of every emission phase, in seconds. 0 means flat emission
+    pub emission_period: u32,
+    /// duration claimed rewards are vested over in a reward vesting escrow, in seconds. 0 means claimed rewards are transferred directly
+    pub claim_vesting_duration: u32,
     /// Padding to ensure `reward_rate: u128` is 16-byte aligned
-    pub _padding_1: [u8; 8], // 8 bytes
+    pub _padding_1: [u8; 4], // 4 bytes
     /// Reward token mint.
     pub mint: Pubkey,
     /// Reward vault token account.
@@ -213,7 +564,7 @@ pub struct RewardInfo {
     pub reward_duration: u64,
     /// reward duration end
     pub reward_duration_end: u64,
-    /// reward rate
+    /// reward rate, that is the rate of the first emission phase if emission decays
     pub reward_rate: u128,
     /// Reward per token stored
     pub reward_per_token_stored: [u8; 32], // U256
@@ -226,6 +577,32 @@ pub struct RewardInfo {
 
 const_assert_eq!(RewardInfo::INIT_SPACE, 192);
 
+/// Validate emission schedule of a reward, both emission period and decay are zero for flat emission
+pub fn validate_emission_schedule(
+    reward_duration: u64,
+    emission_period: u32,
+    emission_decay_bps: u16,
+) -> Result<()> {
+    if emission_period == 0 && emission_decay_bps == 0 {
+        return Ok(());
+    }
+
+    require!(
+        emission_period > 0
+            && emission_decay_bps > 0
+            && u64::from(emission_decay_bps) < BASIS_POINT_MAX,
+        PoolError::InvalidRewardEmissionSchedule
+    );
+
+    let num_phases = reward_duration.div_ceil(emission_period.into());
+    require!(
+        num_phases <= MAX_REWARD_EMISSION_PHASES,
+        PoolError::InvalidRewardEmissionSchedule
+    );
+
+    Ok(())
+}
+
 impl RewardInfo {
     /// Returns true if this reward is initialized.
     /// Once initialized, a reward cannot transition back to uninitialized.
@@ -233,8 +610,13 @@ impl RewardInfo {
         self.initialized != 0
     }
 
-    pub fn is_valid_funder(&self, funder: Pubkey) -> bool {
-        assert_eq_admin(funder) || funder.eq(&self.funder)
+    pub fn has_claim_vesting(&self) -> bool {
+        self.claim_vesting_duration != 0
+    }
+
+    /// Reward admin of program authority or funder of the reward
+    pub fn is_valid_funder(&self, funder: Pubkey, program_authority: &ProgramAuthority) -> bool {
+        program_authority.has_role(AuthorityRole::RewardAdmin, funder) || funder.eq(&self.funder)
     }
 
     pub fn init_reward(
@@ -244,8 +626,10 @@ impl RewardInfo {
         funder: Pubkey,
         reward_duration: u64,
         reward_token_flag: u8,
+        claim_vesting_duration: u32,
     ) {
         self.initialized = 1;
+        self.claim_vesting_duration = claim_vesting_duration;
         self.mint = mint;
         self.vault = vault;
         self.funder = funder;
@@ -266,7 +650,7 @@ impl RewardInfo {
                 self.accumulate_reward_per_token_stored(reward_per_token_stored_delta)?;
             } else {
                 // Time period which the reward was distributed to empty
-                let time_period = self.get_seconds_elapsed_since_last_update(current_time)?;
+                let time_period = self.get_seconds_with_empty_liquidity(current_time)?;
 
                 // Save the time window of empty reward, and reward it in the next time window
                 self.cumulative_seconds_with_empty_liquidity_reward = self
@@ -291,16 +675,36 @@ impl RewardInfo {
         Ok(time_period)
     }
 
+    /// Seconds of reward distributed to empty liquidity since last update.
+    /// For decaying emission, it is converted to seconds at the reward rate, so ineligible reward can be computed from it
+    pub fn get_seconds_with_empty_liquidity(&self, current_time: u64) -> Result<u64> {
+        if !self.is_emission_decaying() || self.reward_rate == 0 {
+            return self.get_seconds_elapsed_since_last_update(current_time);
+        }
+
+        let last_time_reward_applicable = min(current_time, self.reward_duration_end);
+        let total_reward =
+            self.get_emission_between(self.last_update_time, last_time_reward_applicable)?;
+
+        u64::try_from(total_reward.safe_div(self.reward_rate)?)
+            .map_err(|_| PoolError::TypeCastFailed.into())
+    }
+
     // To make it simple we truncate decimals of liquidity_supply for the calculation
     pub fn calculate_reward_per_token_stored_since_last_update(
         &self,
         current_time: u64,
         liquidity_supply: u128,
     ) -> Result<U256> {
-        let time_period: u128 = self
-            .get_seconds_elapsed_since_last_update(current_time)?
-            .into();
-        let total_reward = time_period.safe_mul(self.reward_rate.into())?;
+        let total_reward = if self.is_emission_decaying() {
+            let last_time_reward_applicable = min(current_time, self.reward_duration_end);
+            self.get_emission_between(self.last_update_time, last_time_reward_applicable)?
+        } else {
+            let time_period: u128 = self
+                .get_seconds_elapsed_since_last_update(current_time)?
+                .into();
+            time_period.safe_mul(self.reward_rate.into())?
+        };
 
         let reward_per_token_stored = shl_div_256(total_reward, liquidity_supply, LIQUIDITY_SCALE)
             .ok_or_else(|| PoolError::MathOverflow)?;
@@ -329,6 +733,13 @@ impl RewardInfo {
 
         let total_amount = if current_time >= reward_duration_end {
             funding_amount
+        } else if self.is_emission_decaying() {
+            let leftover = self
+                .get_emission_between(current_time, reward_duration_end)?
+                .safe_shr(REWARD_RATE_SCALE.into())?;
+            let leftover = u64::try_from(leftover).map_err(|_| PoolError::TypeCastFailed)?;
+
+            funding_amount.safe_add(leftover)?
         } else {
             let remaining_seconds = reward_duration_end.safe_sub(current_time)?;
             let leftover: u64 = safe_mul_shr_cast(
@@ -340,20 +751,107 @@ impl RewardInfo {
             funding_amount.safe_add(leftover)?
         };
 
-        self.reward_rate = safe_shl_div_cast(
-            total_amount.into(),
-            self.reward_duration.into(),
-            REWARD_RATE_SCALE,
-            Rounding::Down,
-        )?;
+        self.reward_rate = if self.is_emission_decaying() {
+            // reward rate of the first phase, so that sum of emission over all phases equals to total amount
+            let mut weighted_duration = U256::ZERO;
+            self.for_each_emission_phase(
+                current_time,
+                current_time,
+                current_time.safe_add(self.reward_duration)?,
+                |seconds, factor| {
+                    weighted_duration =
+                        weighted_duration.safe_add(U256::from(seconds).safe_mul(factor)?)?;
+                    Ok(())
+                },
+            )?;
+
+            let reward_rate = U256::from(total_amount)
+                .safe_shl((REWARD_RATE_SCALE * 2).into())?
+                .safe_div(weighted_duration)?;
+            u128::try_from(reward_rate).map_err(|_| PoolError::TypeCastFailed)?
+        } else {
+            safe_shl_div_cast(
+                total_amount.into(),
+                self.reward_duration.into(),
+                REWARD_RATE_SCALE,
+                Rounding::Down,
+            )?
+        };
         self.last_update_time = current_time;
         self.reward_duration_end = current_time.safe_add(self.reward_duration)?;
 
         Ok(())
     }
+
+    /// Returns true if reward rate decays after every emission phase
+    pub fn is_emission_decaying(&self) -> bool {
+        self.emission_period > 0 && self.emission_decay_bps > 0
+    }
+
+    /// Invokes `f(seconds, factor)` for every emission phase overlapping `[from, to)` of the reward window starting from `start`.
+    /// `factor` is the Q64.64 multiplier of the reward rate in the phase
+    fn for_each_emission_phase(
+        &self,
+        start: u64,
+        from: u64,
+        to: u64,
+        mut f: impl FnMut(u64, U256) -> Result<()>,
+    ) -> Result<()> {
+        let decayed_bps = U256::from(BASIS_POINT_MAX.safe_sub(self.emission_decay_bps.into())?);
+        let mut factor = U256::from(ONE_Q64);
+        let mut phase_start = start;
+        while phase_start < to {
+            let phase_end = phase_start.safe_add(self.emission_period.into())?;
+            let seconds = min(to, phase_end).saturating_sub(max(from, phase_start));
+            if seconds > 0 {
+                f(seconds, factor)?;
+            }
+            factor = factor
+                .safe_mul(decayed_bps)?
+                .safe_div(U256::from(BASIS_POINT_MAX))?;
+            phase_start = phase_end;
+        }
+
+        Ok(())
+    }
+
+    /// Emission of decaying reward in `[from, to)` of current reward window, scaled by REWARD_RATE_SCALE
+    pub fn get_emission_between(&self, from: u64, to: u64) -> Result<u128> {
+        let start = self
+            .reward_duration_end
+            .saturating_sub(self.reward_duration);
+        let reward_rate = U256::from(self.reward_rate);
+        let mut total_reward = U256::ZERO;
+        self.for_each_emission_phase(start, from, to, |seconds, factor| {
+            let phase_rate = reward_rate
+                .safe_mul(factor)?
+                .safe_shr(REWARD_RATE_SCALE.into())?;
+            total_reward = total_reward.safe_add(phase_rate.safe_mul(U256::from(seconds))?)?;
+            Ok(())
+        })?;
+
+        u128::try_from(total_reward).map_err(|_| PoolError::TypeCastFailed.into())
+    }
+
+    pub fn claim_ineligible_reward(&mut self) -> Result<u64> {
+        // calculate ineligible reward
+        let ineligible_reward: u64 = safe_mul_shr_cast(
+            self.cumulative_seconds_with_empty_liquidity_reward.into(),
+            self.reward_rate,
+            REWARD_RATE_SCALE,
+        )?;
+
+        self.cumulative_seconds_with_empty_liquidity_reward = 0;
+
+        Ok(ineligible_reward)
+    }
 }
 
 impl Pool {
+    pub fn is_operation_enabled(&self, operation: PoolOperation) -> bool {
+        self.pool_status & operation.mask() == 0
+    }
+
     pub fn initialize(
         &mut self,
         pool_fees: PoolFeesStruct,
@@ -391,12 +889,48 @@ impl Pool {
         self.sqrt_price = sqrt_price;
         self.collect_fee_mode = collect_fee_mode;
         self.pool_type = pool_type;
+        self.version = POOL_VERSION;
+        self.fee_tracking_flag = 1;
+    }
+
+    /// Upgrade layout of a pool whose account was reallocated to the current size,
+    /// new fields are zeroed by realloc, so only fields with non-zero defaults are set here
+    pub fn migrate(&mut self) -> Result<u8> {
+        let from_version = self.version;
+        require!(from_version < POOL_VERSION, PoolError::PoolAlreadyMigrated);
+        self.version = POOL_VERSION;
+        Ok(from_version)
     }
 
     pub fn pool_reward_initialized(&self) -> bool {
         self.reward_infos[0].initialized() || self.reward_infos[1].initialized()
     }
 
+    /// Pool state and trade fee at current point, to quote a swap with cp_amm_core::quote
+    pub fn get_quote_params(
+        &self,
+        fee_mode: &FeeMode,
+        trade_direction: TradeDirection,
+        current_point: u64,
+    ) -> Result<QuoteParams> {
+        Ok(QuoteParams {
+            sqrt_price: self.sqrt_price,
+            liquidity: self.liquidity,
+            sqrt_min_price: self.sqrt_min_price,
+            sqrt_max_price: self.sqrt_max_price,
+            trade_fee_numerator: self
+                .pool_fees
+                .get_total_trading_fee(current_point, self.activation_point)?,
+            protocol_fee_percent: self.pool_fees.protocol_fee_percent,
+            partner_fee_percent: self.pool_fees.partner_fee_percent,
+            referral_fee_percent: self.pool_fees.referral_fee_percent,
+            has_referral: fee_mode.has_referral,
+            fee_discount_bps: fee_mode.fee_discount_bps,
+            fees_on_input: fee_mode.fees_on_input,
+            a_to_b: trade_direction == TradeDirection::AtoB,
+        })
+    }
+
     pub fn get_swap_result(
         &self,
         amount_in: u64,
@@ -404,127 +938,37 @@ impl Pool {
         trade_direction: TradeDirection,
         current_point: u64,
     ) -> Result<SwapResult> {
-        let mut actual_protocol_fee = 0;
-        let mut actual_lp_fee = 0;
-        let mut actual_referral_fee = 0;
-        let mut actual_partner_fee = 0;
-
-        let actual_amount_in = if fee_mode.fees_on_input {
-            let FeeOnAmountResult {
-                amount,
-                lp_fee,
-                protocol_fee,
-                partner_fee,
-                referral_fee,
-            } = self.pool_fees.get_fee_on_amount(
-                amount_in,
-                fee_mode.has_referral,
-                current_point,
-                self.activation_point,
-            )?;
-
-            actual_protocol_fee = protocol_fee;
-            actual_lp_fee = lp_fee;
-            actual_referral_fee = referral_fee;
-            actual_partner_fee = partner_fee;
-
-            amount
-        } else {
-            amount_in
-        };
-
-        let SwapAmount {
+        let quote_params = self.get_quote_params(fee_mode, trade_direction, current_point)?;
+        let SwapQuote {
             output_amount,
             next_sqrt_price,
-        } = match trade_direction {
-            TradeDirection::AtoB => self.get_swap_result_from_a_to_b(actual_amount_in),
-            TradeDirection::BtoA => self.get_swap_result_from_b_to_a(actual_amount_in),
-        }?;
-
-        let actual_amount_out = if fee_mode.fees_on_input {
-            output_amount
-        } else {
-            let FeeOnAmountResult {
-                amount,
-                lp_fee,
-                protocol_fee,
-                partner_fee,
-                referral_fee,
-            } = self.pool_fees.get_fee_on_amount(
-                output_amount,
-                fee_mode.has_referral,
-                current_point,
-                self.activation_point,
-            )?;
-            actual_protocol_fee = protocol_fee;
-            actual_lp_fee = lp_fee;
-            actual_referral_fee = referral_fee;
-            actual_partner_fee = partner_fee;
-            amount
-        };
+            lp_fee,
+            protocol_fee,
+            partner_fee,
+            referral_fee,
+            ..
+        } = quote_exact_in(&quote_params, amount_in).map_err(PoolError::from)?;
 
         Ok(SwapResult {
-            output_amount: actual_amount_out,
-            next_sqrt_price,
-            lp_fee: actual_lp_fee,
-            protocol_fee: actual_protocol_fee,
-            partner_fee: actual_partner_fee,
-            referral_fee: actual_referral_fee,
-        })
-    }
-    fn get_swap_result_from_a_to_b(&self, amount_in: u64) -> Result<SwapAmount> {
-        // finding new target price
-        let next_sqrt_price =
-            get_next_sqrt_price_from_input(self.sqrt_price, self.liquidity, amount_in, true)?;
-
-        if next_sqrt_price < self.sqrt_min_price {
-            return Err(PoolError::PriceRangeViolation.into());
-        }
-
-        // finding output amount
-        let output_amount = get_delta_amount_b_unsigned(
-            next_sqrt_price,
-            self.sqrt_price,
-            self.liquidity,
-            Rounding::Down,
-        )?;
-
-        Ok(SwapAmount {
-            output_amount,
-            next_sqrt_price,
-        })
-    }
-
-    fn get_swap_result_from_b_to_a(&self, amount_in: u64) -> Result<SwapAmount> {
-        // finding new target price
-        let next_sqrt_price =
-            get_next_sqrt_price_from_input(self.sqrt_price, self.liquidity, amount_in, false)?;
-
-        if next_sqrt_price > self.sqrt_max_price {
-            return Err(PoolError::PriceRangeViolation.into());
-        }
-        // finding output amount
-        let output_amount = get_delta_amount_a_unsigned(
-            self.sqrt_price,
-            next_sqrt_price,
-            self.liquidity,
-            Rounding::Down,
-        )?;
-
-        Ok(SwapAmount {
             output_amount,
             next_sqrt_price,
+            lp_fee,
+            protocol_fee,
+            partner_fee,
+            referral_fee,
         })
     }
 
     pub fn apply_swap_result(
         &mut self,
+        amount_in: u64,
         swap_result: &SwapResult,
         fee_mode: &FeeMode,
+        trade_direction: TradeDirection,
         current_timestamp: u64,
     ) -> Result<()> {
         let &SwapResult {
-            output_amount: _output_amount,
+            output_amount,
             lp_fee,
             next_sqrt_price,
             protocol_fee,
@@ -558,6 +1002,18 @@ impl Pool {
                 .accumulate_fee(lp_fee, protocol_fee, partner_fee, false)?;
         }
 
+        let (amount_a, amount_b) = match trade_direction {
+            TradeDirection::AtoB => (amount_in, output_amount),
+            TradeDirection::BtoA => (output_amount, amount_in),
+        };
+        self.volume_metrics.record_swap(
+            current_timestamp,
+            amount_a,
+            amount_b,
+            lp_fee,
+            fee_mode.fees_on_token_a,
+        );
+
         self.update_post_swap(old_sqrt_price, current_timestamp)?;
 
         Ok(())
@@ -589,6 +1045,21 @@ impl Pool {
         })
     }
 
+    /// Maximum liquidity delta that can be added with the given token amounts at current price
+    pub fn get_liquidity_delta_for_amounts(
+        &self,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<u128> {
+        get_liquidity_for_amounts(
+            self.sqrt_min_price,
+            self.sqrt_max_price,
+            self.sqrt_price,
+            token_a_amount,
+            token_b_amount,
+        )
+    }
+
     pub fn apply_add_liquidity(
         &mut self,
         position: &mut Position,
@@ -643,7 +1114,41 @@ impl Pool {
         }
     }
 
+    /// Queue an override of base fee by a constant trade fee, replacing the pending one
+    pub fn queue_base_fee_override(&mut self, trade_fee_numerator: u64, activation_point: u64) {
+        self.pending_base_fee = PendingBaseFeeStruct {
+            trade_fee_numerator,
+            activation_point,
+        };
+    }
+
+    /// Apply pending dynamic fee update and base fee override once their activation points are reached
+    pub fn apply_pending_fee_updates(&mut self, current_timestamp: u64) -> Result<()> {
+        self.pool_fees
+            .apply_pending_dynamic_fee_update(current_timestamp)?;
+        let pending = self.pending_base_fee;
+        if pending.is_pending() && current_timestamp >= pending.activation_point {
+            self.pool_fees
+                .base_fee
+                .override_trade_fee(pending.trade_fee_numerator);
+            self.pending_base_fee = PendingBaseFeeStruct::default();
+        }
+        Ok(())
+    }
+
+    /// Cancel pending dynamic fee update and base fee override
+    pub fn cancel_pending_fee_updates(&mut self) -> Result<()> {
+        require!(
+            self.pool_fees.pending_dynamic_fee.is_pending() || self.pending_base_fee.is_pending(),
+            PoolError::NoPendingUpdate
+        );
+        self.pool_fees.pending_dynamic_fee = PendingDynamicFeeStruct::default();
+        self.pending_base_fee = PendingBaseFeeStruct::default();
+        Ok(())
+    }
+
     pub fn update_pre_swap(&mut self, current_timestamp: u64) -> Result<()> {
+        self.apply_pending_fee_updates(current_timestamp)?;
         if self.pool_fees.dynamic_fee.is_dynamic_fee_enable() {
             self.pool_fees
                 .dynamic_fee
@@ -653,6 +1158,8 @@ impl Pool {
     }
 
     pub fn update_post_swap(&mut self, old_sqrt_price: u128, current_timestamp: u64) -> Result<()> {
+        self.update_oracle(old_sqrt_price, current_timestamp);
+
         if self.pool_fees.dynamic_fee.is_dynamic_fee_enable() {
             self.pool_fees
                 .dynamic_fee
@@ -671,6 +1178,50 @@ impl Pool {
         Ok(())
     }
 
+    /// Accumulate sqrt price held since last oracle update, must be called with the sqrt price before swap
+    pub fn update_oracle(&mut self, old_sqrt_price: u128, current_timestamp: u64) {
+        let last_oracle_timestamp = self.metrics.last_oracle_timestamp;
+        if last_oracle_timestamp == 0 {
+            self.last_cumulative_sqrt_price = 0;
+        } else if current_timestamp > last_oracle_timestamp {
+            self.last_cumulative_sqrt_price = self.last_cumulative_sqrt_price.wrapping_add(
+                old_sqrt_price.wrapping_mul((current_timestamp - last_oracle_timestamp).into()),
+            );
+        } else {
+            return;
+        }
+        self.metrics.last_oracle_timestamp = current_timestamp;
+    }
+
+    /// Cumulative sqrt price at current timestamp. Time weighted average sqrt price between 2 timestamps is
+    /// the wrapping difference of their cumulative sqrt prices divided by seconds elapsed
+    pub fn get_cumulative_sqrt_price(&self, current_timestamp: u64) -> Result<u128> {
+        let last_oracle_timestamp = self.metrics.last_oracle_timestamp;
+        require!(
+            last_oracle_timestamp != 0 && current_timestamp >= last_oracle_timestamp,
+            PoolError::OracleNotInitialized
+        );
+        Ok(self.last_cumulative_sqrt_price.wrapping_add(
+            self.sqrt_price
+                .wrapping_mul((current_timestamp - last_oracle_timestamp).into()),
+        ))
+    }
+
+    pub fn accumulate_vested_liquidity(&mut self, vested_liquidity: u128) -> Result<()> {
+        if self.vesting_reward_boost_bps != 0 {
+            self.total_vested_liquidity = self.total_vested_liquidity.safe_add(vested_liquidity)?;
+        }
+        Ok(())
+    }
+
+    pub fn release_vested_liquidity(&mut self, released_liquidity: u128) -> Result<()> {
+        if self.vesting_reward_boost_bps != 0 {
+            self.total_vested_liquidity =
+                self.total_vested_liquidity.safe_sub(released_liquidity)?;
+        }
+        Ok(())
+    }
+
     pub fn accumulate_permanent_locked_liquidity(
         &mut self,
         permanent_locked_liquidity: u128,
@@ -682,12 +1233,16 @@ impl Pool {
         Ok(())
     }
 
-    pub fn claim_protocol_fee(&mut self) -> (u64, u64) {
-        let token_a_amount = self.protocol_a_fee;
-        let token_b_amount = self.protocol_b_fee;
-        self.protocol_a_fee = 0;
-        self.protocol_b_fee = 0;
-        (token_a_amount, token_b_amount)
+    pub fn claim_protocol_fee(
+        &mut self,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<(u64, u64)> {
+        let token_a_amount = self.protocol_a_fee.min(max_amount_a);
+        let token_b_amount = self.protocol_b_fee.min(max_amount_b);
+        self.protocol_a_fee = self.protocol_a_fee.safe_sub(token_a_amount)?;
+        self.protocol_b_fee = self.protocol_b_fee.safe_sub(token_b_amount)?;
+        Ok((token_a_amount, token_b_amount))
     }
 
     pub fn claim_partner_fee(
@@ -702,30 +1257,192 @@ impl Pool {
         Ok((token_a_amount, token_b_amount))
     }
 
+    /// Swaps must be top-level instructions from activation point for `cpi_guard_duration`, so bundler and sniper
+    /// programs can't wrap launch swaps. Swaps before activation point are only allowed for whitelisted vaults,
+    /// eg: alpha vault swapping by CPI, so they are not guarded
+    pub fn is_cpi_guard_active(&self, current_point: u64) -> Result<bool> {
+        Ok(self.cpi_guard_duration != 0
+            && current_point >= self.activation_point
+            && current_point < self.activation_point.safe_add(self.cpi_guard_duration)?)
+    }
+
+    /// Partner, or creator of a customizable pool, can delay activation point besides admin
+    pub fn is_activation_point_authority(&self, signer: Pubkey) -> bool {
+        self.partner == signer
+            || (self.pool_type == u8::from(PoolType::Customizable) && self.creator == signer)
+    }
+
+    /// Increment sequence of pool state changes, returns the new sequence
+    pub fn increment_sequence(&mut self) -> u64 {
+        self.sequence = self.sequence.wrapping_add(1);
+        self.sequence
+    }
+
+    pub fn is_fee_tracked(&self) -> bool {
+        self.fee_tracking_flag != 0
+    }
+
+    pub fn accumulate_lp_fee_claimed(
+        &mut self,
+        fee_a_claimed: u64,
+        fee_b_claimed: u64,
+    ) -> Result<()> {
+        self.total_claimed_lp_a_fee = self.total_claimed_lp_a_fee.safe_add(fee_a_claimed.into())?;
+        self.total_claimed_lp_b_fee = self.total_claimed_lp_b_fee.safe_add(fee_b_claimed.into())?;
+        Ok(())
+    }
+
+    pub fn accumulate_referral_fee(&mut self, referral_fee: u64, is_token_a: bool) -> Result<()> {
+        if is_token_a {
+            self.unclaimed_referral_a_fee = self.unclaimed_referral_a_fee.safe_add(referral_fee)?;
+        } else {
+            self.unclaimed_referral_b_fee = self.unclaimed_referral_b_fee.safe_add(referral_fee)?;
+        }
+        Ok(())
+    }
+
+    pub fn claim_referral_fee(&mut self, token_a_amount: u64, token_b_amount: u64) {
+        // referral fee accrued before the pool was migrated is not tracked
+        self.unclaimed_referral_a_fee =
+            self.unclaimed_referral_a_fee.saturating_sub(token_a_amount);
+        self.unclaimed_referral_b_fee =
+            self.unclaimed_referral_b_fee.saturating_sub(token_b_amount);
+    }
+
+    /// Amounts in vaults exceeding reserves of liquidity and fees owed to positions, protocol, partner and referrers,
+    /// eg: tokens transferred to vaults directly
+    pub fn get_excess_vault_amounts(
+        &self,
+        token_a_vault_amount: u64,
+        token_b_vault_amount: u64,
+    ) -> Result<(u64, u64)> {
+        require!(self.is_fee_tracked(), PoolError::PoolFeeNotTracked);
+
+        // rounding up covers withdrawals of all positions
+        let ModifyLiquidityResult {
+            token_a_amount,
+            token_b_amount,
+        } = self.get_amounts_for_modify_liquidity(self.liquidity, Rounding::Up)?;
+
+        let owed_a_amount = u128::from(token_a_amount)
+            .safe_add(self.protocol_a_fee.into())?
+            .safe_add(self.partner_a_fee.into())?
+            .safe_add(self.unclaimed_referral_a_fee.into())?
+            .safe_add(
+                self.metrics
+                    .total_lp_a_fee
+                    .safe_sub(self.total_claimed_lp_a_fee)?,
+            )?;
+        let owed_b_amount = u128::from(token_b_amount)
+            .safe_add(self.protocol_b_fee.into())?
+            .safe_add(self.partner_b_fee.into())?
+            .safe_add(self.unclaimed_referral_b_fee.into())?
+            .safe_add(
+                self.metrics
+                    .total_lp_b_fee
+                    .safe_sub(self.total_claimed_lp_b_fee)?,
+            )?;
+
+        // excess is not larger than vault amount, so it fits in u64
+        let excess_a_amount = u128::from(token_a_vault_amount).saturating_sub(owed_a_amount) as u64;
+        let excess_b_amount = u128::from(token_b_vault_amount).saturating_sub(owed_b_amount) as u64;
+
+        Ok((excess_a_amount, excess_b_amount))
+    }
+
+    /// Distribute token amounts in vaults to liquidity providers as lp fee, price is unchanged
+    pub fn distribute_to_lp(&mut self, token_a_amount: u64, token_b_amount: u64) -> Result<()> {
+        require!(self.liquidity > 0, PoolError::InsufficientLiquidity);
+        if token_a_amount > 0 {
+            let fee_per_token_stored =
+                shl_div_256(token_a_amount.into(), self.liquidity, LIQUIDITY_SCALE)
+                    .ok_or_else(|| PoolError::MathOverflow)?;
+            self.fee_a_per_liquidity = self
+                .fee_a_per_liquidity()
+                .safe_add(fee_per_token_stored)?
+                .to_le_bytes();
+            self.metrics.total_lp_a_fee = self
+                .metrics
+                .total_lp_a_fee
+                .safe_add(token_a_amount.into())?;
+        }
+        if token_b_amount > 0 {
+            let fee_per_token_stored =
+                shl_div_256(token_b_amount.into(), self.liquidity, LIQUIDITY_SCALE)
+                    .ok_or_else(|| PoolError::MathOverflow)?;
+            self.fee_b_per_liquidity = self
+                .fee_b_per_liquidity()
+                .safe_add(fee_per_token_stored)?
+                .to_le_bytes();
+            self.metrics.total_lp_b_fee = self
+                .metrics
+                .total_lp_b_fee
+                .safe_add(token_b_amount.into())?;
+        }
+        Ok(())
+    }
+
+    pub fn has_reward_boost(&self) -> bool {
+        self.vesting_reward_boost_bps != 0 || self.permanent_lock_reward_boost_bps != 0
+    }
+
+    /// Liquidity weighted for reward accrual, vested and permanently locked liquidity are boosted
+    pub fn get_reward_liquidity(&self) -> Result<u128> {
+        if !self.has_reward_boost() {
+            return Ok(self.liquidity);
+        }
+        Ok(self
+            .liquidity
+            .safe_add(get_boosted_liquidity(
+                self.total_vested_liquidity,
+                self.vesting_reward_boost_bps,
+            )?)?
+            .safe_add(get_boosted_liquidity(
+                self.permanent_lock_liquidity,
+                self.permanent_lock_reward_boost_bps,
+            )?)?)
+    }
+
     /// Update the rewards per token stored.
     pub fn update_rewards(&mut self, current_time: u64) -> Result<()> {
+        let reward_liquidity = self.get_reward_liquidity()?;
         for reward_idx in 0..NUM_REWARDS {
             let reward_info = &mut self.reward_infos[reward_idx];
-            reward_info.update_rewards(self.liquidity, current_time)?;
+            reward_info.update_rewards(reward_liquidity, current_time)?;
         }
 
         Ok(())
     }
 
     pub fn claim_ineligible_reward(&mut self, reward_index: usize) -> Result<u64> {
-        // calculate ineligible reward
-        let reward_info = &mut self.reward_infos[reward_index];
-        let ineligible_reward: u64 = safe_mul_shr_cast(
-            reward_info
-                .cumulative_seconds_with_empty_liquidity_reward
-                .into(),
-            reward_info.reward_rate,
-            REWARD_RATE_SCALE,
-        )?;
+        self.reward_infos[reward_index].claim_ineligible_reward()
+    }
 
-        reward_info.cumulative_seconds_with_empty_liquidity_reward = 0;
+    pub fn has_reward_extension(&self) -> bool {
+        self.reward_extension_flag != 0
+    }
 
-        Ok(ineligible_reward)
+    pub fn is_buyback_burn_enabled(&self) -> bool {
+        self.buyback_burn_flag != 0
+    }
+
+    /// Take protocol fee in token b to spend in a buyback, up to `max_amount`
+    pub fn take_buyback_amount(&mut self, max_amount: u64) -> Result<u64> {
+        let amount = self.protocol_b_fee.min(max_amount);
+        self.protocol_b_fee = self.protocol_b_fee.safe_sub(amount)?;
+        Ok(amount)
+    }
+
+    pub fn is_emergency_mode(&self) -> bool {
+        self.emergency_mode != 0
+    }
+
+    pub fn is_permissioned_swap(&self) -> bool {
+        self.swap_allowlist_authority.ne(&Pubkey::default())
+    }
+
+    pub fn is_permissioned_lp(&self) -> bool {
+        self.lp_allowlist_authority.ne(&Pubkey::default())
     }
 
     pub fn fee_a_per_liquidity(&self) -> U256 {
@@ -748,13 +1465,19 @@ pub struct SwapResult {
     pub referral_fee: u64,
 }
 
-pub struct SwapAmount {
-    output_amount: u64,
-    next_sqrt_price: u128,
-}
-
 #[derive(Debug, PartialEq)]
 pub struct ModifyLiquidityResult {
     pub token_a_amount: u64,
     pub token_b_amount: u64,
 }
+
+/// Extra reward weight of liquidity, rounded down so that boosted liquidity of positions never exceeds the pool's
+pub fn get_boosted_liquidity(liquidity: u128, boost_bps: u16) -> Result<u128> {
+    if boost_bps == 0 {
+        return Ok(0);
+    }
+    let boosted_liquidity = U256::from(liquidity)
+        .safe_mul(U256::from(boost_bps))?
+        .safe_div(U256::from(BASIS_POINT_MAX))?;
+    u128::try_from(boosted_liquidity).map_err(|_| PoolError::TypeCastFailed.into())
+}
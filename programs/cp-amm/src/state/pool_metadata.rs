@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{
+        MAX_POOL_METADATA_NAME_LENGTH, MAX_POOL_METADATA_SYMBOL_LENGTH,
+        MAX_POOL_METADATA_URI_LENGTH,
+    },
+    PoolError,
+};
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Display information of a pool for explorers and frontends, strings are utf8 padded with zero bytes
+pub struct PoolMetadata {
+    /// pool
+    pub pool: Pubkey,
+    /// display name
+    pub name: [u8; MAX_POOL_METADATA_NAME_LENGTH],
+    /// display symbol
+    pub symbol: [u8; MAX_POOL_METADATA_SYMBOL_LENGTH],
+    /// logo uri
+    pub uri: [u8; MAX_POOL_METADATA_URI_LENGTH],
+    /// project url
+    pub project_url: [u8; MAX_POOL_METADATA_URI_LENGTH],
+    /// Reserve
+    pub _padding: [u8; 64],
+}
+
+const_assert_eq!(PoolMetadata::INIT_SPACE, 544);
+
+impl PoolMetadata {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+        project_url: &str,
+    ) -> Result<()> {
+        self.pool = pool;
+        self.update(name, symbol, uri, project_url)
+    }
+
+    /// Name is required, other fields can be empty
+    pub fn update(&mut self, name: &str, symbol: &str, uri: &str, project_url: &str) -> Result<()> {
+        require!(!name.is_empty(), PoolError::InvalidPoolMetadata);
+        self.name = to_padded_bytes(name)?;
+        self.symbol = to_padded_bytes(symbol)?;
+        self.uri = to_padded_bytes(uri)?;
+        self.project_url = to_padded_bytes(project_url)?;
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {
+        from_padded_bytes(&self.name)
+    }
+
+    pub fn symbol(&self) -> &str {
+        from_padded_bytes(&self.symbol)
+    }
+
+    pub fn uri(&self) -> &str {
+        from_padded_bytes(&self.uri)
+    }
+
+    pub fn project_url(&self) -> &str {
+        from_padded_bytes(&self.project_url)
+    }
+}
+
+/// Zero bytes are rejected, so trailing zero bytes always mark the end of the string
+fn to_padded_bytes<const N: usize>(value: &str) -> Result<[u8; N]> {
+    require!(
+        value.len() <= N && !value.as_bytes().contains(&0),
+        PoolError::InvalidPoolMetadata
+    );
+    let mut bytes = [0u8; N];
+    bytes[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(bytes)
+}
+
+fn from_padded_bytes(bytes: &[u8]) -> &str {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    // only valid utf8 is written by `to_padded_bytes`
+    std::str::from_utf8(&bytes[..len]).unwrap_or_default()
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::constants::{
+    POOL_STATS_DAILY_BUCKETS, POOL_STATS_DAY, POOL_STATS_HOUR, POOL_STATS_HOURLY_BUCKETS,
+};
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+/// Swap statistics of a pool in a time bucket
+pub struct StatsBucket {
+    /// start timestamp of the bucket, a multiple of the bucket duration
+    pub start_timestamp: u64,
+    /// number of swaps
+    pub swap_count: u64,
+    /// traded amount of token a, input or output of swaps
+    pub volume_a: u128,
+    /// traded amount of token b, input or output of swaps
+    pub volume_b: u128,
+    /// lp fee in token a
+    pub lp_fee_a: u64,
+    /// lp fee in token b
+    pub lp_fee_b: u64,
+    /// total trade fee in token a, including protocol, partner and referral fee
+    pub total_fee_a: u64,
+    /// total trade fee in token b, including protocol, partner and referral fee
+    pub total_fee_b: u64,
+    /// sqrt price after the last swap of the bucket
+    pub sqrt_price: u128,
+}
+
+const_assert_eq!(StatsBucket::INIT_SPACE, 96);
+
+/// Volume and fee of a swap recorded in pool stats
+pub struct SwapVolume {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub lp_fee: u64,
+    pub total_fee: u64,
+    pub fees_on_token_a: bool,
+}
+
+impl StatsBucket {
+    // stats saturate instead of failing, so they never block a swap
+    fn record_swap(&mut self, start_timestamp: u64, volume: &SwapVolume, sqrt_price: u128) {
+        if self.start_timestamp != start_timestamp {
+            *self = StatsBucket {
+                start_timestamp,
+                ..Default::default()
+            };
+        }
+        self.swap_count = self.swap_count.saturating_add(1);
+        self.volume_a = self.volume_a.saturating_add(volume.amount_a.into());
+        self.volume_b = self.volume_b.saturating_add(volume.amount_b.into());
+        if volume.fees_on_token_a {
+            self.lp_fee_a = self.lp_fee_a.saturating_add(volume.lp_fee);
+            self.total_fee_a = self.total_fee_a.saturating_add(volume.total_fee);
+        } else {
+            self.lp_fee_b = self.lp_fee_b.saturating_add(volume.lp_fee);
+            self.total_fee_b = self.total_fee_b.saturating_add(volume.total_fee);
+        }
+        self.sqrt_price = sqrt_price;
+    }
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Hourly and daily swap statistics of a pool, updated by swaps passing it in remaining accounts.
+/// Buckets are ring buffers indexed by bucket start timestamp, buckets with an older start timestamp are stale
+pub struct PoolStats {
+    /// pool
+    pub pool: Pubkey,
+    /// hourly buckets
+    pub hourly_buckets: [StatsBucket; POOL_STATS_HOURLY_BUCKETS],
+    /// daily buckets
+    pub daily_buckets: [StatsBucket; POOL_STATS_DAILY_BUCKETS],
+    /// Reserve
+    pub _padding: [u8; 64],
+}
+
+const_assert_eq!(PoolStats::INIT_SPACE, 5280);
+
+impl PoolStats {
+    pub fn initialize(&mut self, pool: Pubkey) {
+        self.pool = pool;
+    }
+
+    pub fn record_swap(&mut self, current_timestamp: u64, volume: &SwapVolume, sqrt_price: u128) {
+        record_swap_in_buckets(
+            &mut self.hourly_buckets,
+            POOL_STATS_HOUR,
+            current_timestamp,
+            volume,
+            sqrt_price,
+        );
+        record_swap_in_buckets(
+            &mut self.daily_buckets,
+            POOL_STATS_DAY,
+            current_timestamp,
+            volume,
+            sqrt_price,
+        );
+    }
+
+    /// Bucket of a timestamp, if it is recorded and not overwritten yet
+    pub fn get_hourly_bucket(&self, timestamp: u64) -> Option<&StatsBucket> {
+        get_bucket(&self.hourly_buckets, POOL_STATS_HOUR, timestamp)
+    }
+
+    /// Bucket of a timestamp, if it is recorded and not overwritten yet
+    pub fn get_daily_bucket(&self, timestamp: u64) -> Option<&StatsBucket> {
+        get_bucket(&self.daily_buckets, POOL_STATS_DAY, timestamp)
+    }
+}
+
+fn get_bucket_index(buckets_len: usize, duration: u64, timestamp: u64) -> usize {
+    // bucket count fits in u64, so the remainder fits in usize
+    ((timestamp / duration) % buckets_len as u64) as usize
+}
+
+fn record_swap_in_buckets(
+    buckets: &mut [StatsBucket],
+    duration: u64,
+    current_timestamp: u64,
+    volume: &SwapVolume,
+    sqrt_price: u128,
+) {
+    let index = get_bucket_index(buckets.len(), duration, current_timestamp);
+    let start_timestamp = current_timestamp - current_timestamp % duration;
+    buckets[index].record_swap(start_timestamp, volume, sqrt_price);
+}
+
+fn get_bucket(buckets: &[StatsBucket], duration: u64, timestamp: u64) -> Option<&StatsBucket> {
+    let bucket = &buckets[get_bucket_index(buckets.len(), duration, timestamp)];
+    (bucket.swap_count > 0 && bucket.start_timestamp == timestamp - timestamp % duration)
+        .then_some(bucket)
+}
@@ -1,12 +1,12 @@
 use anchor_lang::prelude::*;
 use ruint::aliases::U256;
 use static_assertions::const_assert_eq;
-use std::{cell::RefMut, u64};
+use std::u64;
 
 use crate::{
     constants::{LIQUIDITY_SCALE, NUM_REWARDS, TOTAL_REWARD_SCALE},
     safe_math::SafeMath,
-    state::Pool,
+    state::{get_boosted_liquidity, Pool},
     utils_math::safe_mul_shr_256_cast,
     PoolError,
 };
@@ -46,6 +46,18 @@ impl UserRewardInfo {
     pub fn reward_per_token_checkpoint(&self) -> U256 {
         U256::from_le_bytes(self.reward_per_token_checkpoint)
     }
+
+    /// Move pending and claimed rewards of other into self, both must be updated to the same checkpoint
+    pub fn merge(&mut self, other: &mut UserRewardInfo) -> Result<()> {
+        self.reward_pendings = self.reward_pendings.safe_add(other.reward_pendings)?;
+        self.total_claimed_rewards = self
+            .total_claimed_rewards
+            .wrapping_add(other.total_claimed_rewards);
+
+        other.reward_pendings = 0;
+        other.total_claimed_rewards = 0;
+        Ok(())
+    }
 }
 
 #[account(zero_copy)]
@@ -72,8 +84,16 @@ pub struct Position {
     pub metrics: PositionMetrics,
     /// Farming reward information
     pub reward_infos: [UserRewardInfo; NUM_REWARDS],
+    /// owner of token accounts receiving position fee, default pubkey means any token account
+    pub fee_beneficiary: Pubkey,
+    /// operator allowed to claim fee and rewards to position owner, default pubkey means no operator
+    pub operator: Pubkey,
+    /// 1 if fee receipt has been minted, position fee is only claimable by holder of the fee receipt
+    pub fee_receipt_minted: u8,
+    /// padding
+    pub padding_0: [u8; 15],
     /// padding for future usage
-    pub padding: [u128; 6],
+    pub padding: [u128; 1],
 }
 
 const_assert_eq!(Position::INIT_SPACE, 400);
@@ -114,6 +134,55 @@ impl Position {
         Ok(())
     }
 
+    pub fn has_fee_beneficiary(&self) -> bool {
+        self.fee_beneficiary != Pubkey::default()
+    }
+
+    pub fn has_fee_receipt(&self) -> bool {
+        self.fee_receipt_minted == 1
+    }
+
+    /// Returns true if all liquidity of position is permanently locked
+    pub fn is_fully_permanent_locked(&self) -> bool {
+        self.permanent_locked_liquidity > 0
+            && self.unlocked_liquidity == 0
+            && self.vested_liquidity == 0
+    }
+
+    pub fn has_operator(&self) -> bool {
+        self.operator != Pubkey::default()
+    }
+
+    /// Returns true if signer is the owner (holder of position nft) or the operator of position
+    pub fn is_owner_or_operator(&self, position_owner: Pubkey, signer: Pubkey) -> bool {
+        signer == position_owner || (self.has_operator() && self.operator == signer)
+    }
+
+    /// Returns true if the token account owner is allowed to receive position fee claimed by signer.
+    /// Without fee beneficiary, operator can only claim to token accounts of position owner
+    pub fn is_valid_fee_receiver(
+        &self,
+        token_account_owner: Pubkey,
+        position_owner: Pubkey,
+        signer: Pubkey,
+    ) -> bool {
+        if self.has_fee_beneficiary() {
+            return self.fee_beneficiary == token_account_owner;
+        }
+        self.is_valid_reward_receiver(token_account_owner, position_owner, signer)
+    }
+
+    /// Returns true if the token account owner is allowed to receive rewards claimed by signer.
+    /// Operator can only claim to token accounts of position owner
+    pub fn is_valid_reward_receiver(
+        &self,
+        token_account_owner: Pubkey,
+        position_owner: Pubkey,
+        signer: Pubkey,
+    ) -> bool {
+        signer == position_owner || token_account_owner == position_owner
+    }
+
     pub fn has_sufficient_liquidity(&self, liquidity: u128) -> bool {
         self.unlocked_liquidity >= liquidity
     }
@@ -151,6 +220,18 @@ impl Position {
         Ok(())
     }
 
+    pub fn permanent_lock_vested_liquidity(
+        &mut self,
+        permanent_lock_liquidity: u128,
+    ) -> Result<()> {
+        self.vested_liquidity = self.vested_liquidity.safe_sub(permanent_lock_liquidity)?;
+        self.permanent_locked_liquidity = self
+            .permanent_locked_liquidity
+            .safe_add(permanent_lock_liquidity)?;
+
+        Ok(())
+    }
+
     pub fn update_fee(
         &mut self,
         fee_a_per_token_stored: U256,
@@ -200,13 +281,24 @@ impl Position {
         self.fee_b_pending = 0;
     }
 
-    pub fn update_rewards(&mut self, pool: &mut RefMut<'_, Pool>, current_time: u64) -> Result<()> {
+    /// Claim pending fee, bounded by max amounts
+    pub fn claim_fee(&mut self, max_amount_a: u64, max_amount_b: u64) -> Result<(u64, u64)> {
+        let fee_a_claimed = self.fee_a_pending.min(max_amount_a);
+        let fee_b_claimed = self.fee_b_pending.min(max_amount_b);
+        self.fee_a_pending = self.fee_a_pending.safe_sub(fee_a_claimed)?;
+        self.fee_b_pending = self.fee_b_pending.safe_sub(fee_b_claimed)?;
+        self.metrics
+            .accumulate_claimed_fee(fee_a_claimed, fee_b_claimed)?;
+        Ok((fee_a_claimed, fee_b_claimed))
+    }
+
+    pub fn update_rewards(&mut self, pool: &mut Pool, current_time: u64) -> Result<()> {
         // update if reward has been initialized
         if pool.pool_reward_initialized() {
             // update pool reward before any update about position reward
             pool.update_rewards(current_time)?;
 
-            let position_liquidity = self.get_total_liquidity()?;
+            let position_liquidity = self.get_reward_liquidity(pool)?;
             let position_reward_infos = &mut self.reward_infos;
             for reward_idx in 0..NUM_REWARDS {
                 let pool_reward_info = pool.reward_infos[reward_idx];
@@ -223,6 +315,23 @@ impl Position {
         Ok(())
     }
 
+    /// Liquidity weighted for reward accrual with reward boost of pool
+    pub fn get_reward_liquidity(&self, pool: &Pool) -> Result<u128> {
+        let liquidity = self.get_total_liquidity()?;
+        if !pool.has_reward_boost() {
+            return Ok(liquidity);
+        }
+        Ok(liquidity
+            .safe_add(get_boosted_liquidity(
+                self.vested_liquidity,
+                pool.vesting_reward_boost_bps,
+            )?)?
+            .safe_add(get_boosted_liquidity(
+                self.permanent_locked_liquidity,
+                pool.permanent_lock_reward_boost_bps,
+            )?)?)
+    }
+
     fn get_total_reward(&self, reward_index: usize) -> Result<u64> {
         Ok(self.reward_infos[reward_index].reward_pendings)
     }
@@ -254,6 +363,48 @@ impl Position {
         U256::from_le_bytes(self.fee_b_per_token_checkpoint)
     }
 
+    /// Move liquidity, pending fees and rewards of source into self.
+    /// Fees and rewards of both positions must be updated before merging
+    pub fn merge(&mut self, source: &mut Position) -> Result<()> {
+        require!(
+            source.vested_liquidity == 0,
+            PoolError::UnableToMergeVestedPosition
+        );
+        require!(
+            !self.has_fee_receipt() && !source.has_fee_receipt(),
+            PoolError::PositionHasFeeReceipt
+        );
+
+        self.unlocked_liquidity = self
+            .unlocked_liquidity
+            .safe_add(source.unlocked_liquidity)?;
+        self.permanent_locked_liquidity = self
+            .permanent_locked_liquidity
+            .safe_add(source.permanent_locked_liquidity)?;
+        source.unlocked_liquidity = 0;
+        source.permanent_locked_liquidity = 0;
+
+        self.fee_a_pending = self.fee_a_pending.safe_add(source.fee_a_pending)?;
+        self.fee_b_pending = self.fee_b_pending.safe_add(source.fee_b_pending)?;
+        source.reset_pending_fee();
+
+        self.metrics.accumulate_claimed_fee(
+            source.metrics.total_claimed_a_fee,
+            source.metrics.total_claimed_b_fee,
+        )?;
+        source.metrics = PositionMetrics::default();
+
+        for (reward_info, source_reward_info) in self
+            .reward_infos
+            .iter_mut()
+            .zip(source.reward_infos.iter_mut())
+        {
+            reward_info.merge(source_reward_info)?;
+        }
+
+        Ok(())
+    }
+
     pub fn is_empty(&self) -> Result<bool> {
         // check reward
         for i in 0..NUM_REWARDS {
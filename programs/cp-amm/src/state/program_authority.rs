@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use static_assertions::const_assert_eq;
+
+use crate::{constants::NUM_AUTHORITY_ROLES, safe_math::SafeMath, PoolError};
+
+/// Role of a key in program authority account
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum AuthorityRole {
+    /// manage configs, token badges, fee discount registries, emergency mode and close pools
+    ConfigAdmin,
+    /// manage claim fee operators
+    FeeClaimer,
+    /// manage rewards of pools
+    RewardAdmin,
+    /// set pool status
+    Pauser,
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Keys allowed to call admin endpoints, by role
+pub struct ProgramAuthority {
+    /// key of every role, indexed by `AuthorityRole`
+    pub roles: [Pubkey; NUM_AUTHORITY_ROLES],
+    /// pending key of every role, it must accept the role to replace the current key
+    pub pending_roles: [Pubkey; NUM_AUTHORITY_ROLES],
+    /// timestamp from which pending key of every role can accept the role
+    pub pending_role_activation_points: [u64; NUM_AUTHORITY_ROLES],
+    /// delay in seconds between proposing and accepting a role
+    pub transfer_delay: u64,
+    /// Reserve
+    pub _padding: [u8; 24],
+}
+
+const_assert_eq!(ProgramAuthority::INIT_SPACE, 320);
+
+impl ProgramAuthority {
+    pub fn initialize(&mut self, roles: [Pubkey; NUM_AUTHORITY_ROLES], transfer_delay: u64) {
+        self.roles = roles;
+        self.transfer_delay = transfer_delay;
+    }
+
+    pub fn has_role(&self, role: AuthorityRole, key: Pubkey) -> bool {
+        self.roles[usize::from(u8::from(role))] == key
+    }
+
+    /// First step of a role transfer, called by the current key of the role.
+    /// Proposing default pubkey cancels the pending transfer. Returns activation point of the new key
+    pub fn transfer_role(
+        &mut self,
+        role: AuthorityRole,
+        new_key: Pubkey,
+        current_timestamp: u64,
+    ) -> Result<u64> {
+        let index = usize::from(u8::from(role));
+        let activation_point = current_timestamp.safe_add(self.transfer_delay)?;
+        self.pending_roles[index] = new_key;
+        self.pending_role_activation_points[index] = activation_point;
+        Ok(activation_point)
+    }
+
+    /// Second step of a role transfer, called by the pending key of the role after the transfer delay
+    pub fn accept_role(
+        &mut self,
+        role: AuthorityRole,
+        key: Pubkey,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        let index = usize::from(u8::from(role));
+        require!(
+            self.pending_roles[index] == key && key != Pubkey::default(),
+            PoolError::InvalidAuthorityRole
+        );
+        require!(
+            current_timestamp >= self.pending_role_activation_points[index],
+            PoolError::AuthorityTransferLocked
+        );
+        self.roles[index] = key;
+        self.pending_roles[index] = Pubkey::default();
+        self.pending_role_activation_points[index] = 0;
+        Ok(())
+    }
+}
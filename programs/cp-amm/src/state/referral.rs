@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::safe_math::SafeMath;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Referral fee accrued by a referrer in a pool
+pub struct Referral {
+    /// pool
+    pub pool: Pubkey,
+    /// referrer, can claim referral fee
+    pub referrer: Pubkey,
+    /// referral fee in token a not claimed yet
+    pub fee_a_pending: u64,
+    /// referral fee in token b not claimed yet
+    pub fee_b_pending: u64,
+    /// total referral fee in token a
+    pub total_fee_a: u64,
+    /// total referral fee in token b
+    pub total_fee_b: u64,
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(Referral::INIT_SPACE, 128);
+
+impl Referral {
+    pub fn initialize(&mut self, pool: Pubkey, referrer: Pubkey) {
+        self.pool = pool;
+        self.referrer = referrer;
+    }
+
+    pub fn accumulate_fee(&mut self, referral_fee: u64, is_token_a: bool) -> Result<()> {
+        if is_token_a {
+            self.fee_a_pending = self.fee_a_pending.safe_add(referral_fee)?;
+            self.total_fee_a = self.total_fee_a.safe_add(referral_fee)?;
+        } else {
+            self.fee_b_pending = self.fee_b_pending.safe_add(referral_fee)?;
+            self.total_fee_b = self.total_fee_b.safe_add(referral_fee)?;
+        }
+        Ok(())
+    }
+
+    pub fn claim_fee(&mut self, max_amount_a: u64, max_amount_b: u64) -> Result<(u64, u64)> {
+        let fee_a_claimed = self.fee_a_pending.min(max_amount_a);
+        let fee_b_claimed = self.fee_b_pending.min(max_amount_b);
+        self.fee_a_pending = self.fee_a_pending.safe_sub(fee_a_claimed)?;
+        self.fee_b_pending = self.fee_b_pending.safe_sub(fee_b_claimed)?;
+        Ok((fee_a_claimed, fee_b_claimed))
+    }
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    state::{Pool, RewardInfo, UserRewardInfo},
+    PoolError,
+};
+
+/// Additional rewards of a pool, on top of the rewards stored in pool account.
+/// Reward index of extension rewards starts from NUM_REWARDS
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct RewardExtension {
+    /// pool
+    pub pool: Pubkey,
+    /// Farming reward information
+    pub reward_infos: [RewardInfo; NUM_EXTENSION_REWARDS],
+    /// padding for future usage
+    pub padding: [u64; 8],
+}
+
+const_assert_eq!(RewardExtension::INIT_SPACE, 672);
+
+/// Position reward information of extension rewards
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct PositionRewardExtension {
+    /// position
+    pub position: Pubkey,
+    /// Farming reward information
+    pub reward_infos: [UserRewardInfo; NUM_EXTENSION_REWARDS],
+    /// padding for future usage
+    pub padding: [u64; 8],
+}
+
+const_assert_eq!(PositionRewardExtension::INIT_SPACE, 240);
+
+/// Map reward index of pool to index in reward extension
+pub fn get_extension_reward_index(reward_index: usize) -> Result<usize> {
+    let index = reward_index
+        .checked_sub(NUM_REWARDS)
+        .ok_or(PoolError::InvalidRewardIndex)?;
+    require!(index < NUM_EXTENSION_REWARDS, PoolError::InvalidRewardIndex);
+    Ok(index)
+}
+
+/// Get reward info of pool by reward index, reward extension is required for extension rewards
+pub fn get_reward_info_mut<'a>(
+    pool: &'a mut Pool,
+    reward_extension: Option<&'a mut RewardExtension>,
+    reward_index: usize,
+) -> Result<&'a mut RewardInfo> {
+    if reward_index < NUM_REWARDS {
+        return Ok(&mut pool.reward_infos[reward_index]);
+    }
+    let index = get_extension_reward_index(reward_index)?;
+    let reward_extension = reward_extension.ok_or(PoolError::InvalidRewardExtension)?;
+    Ok(&mut reward_extension.reward_infos[index])
+}
+
+impl RewardExtension {
+    pub fn initialize(&mut self, pool: Pubkey) {
+        self.pool = pool;
+    }
+
+    /// Update the rewards per token stored.
+    pub fn update_rewards(&mut self, liquidity_supply: u128, current_time: u64) -> Result<()> {
+        for reward_info in self.reward_infos.iter_mut() {
+            reward_info.update_rewards(liquidity_supply, current_time)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PositionRewardExtension {
+    pub fn initialize(&mut self, position: Pubkey) {
+        self.position = position;
+    }
+
+    /// Update extension rewards of position, reward extension must be updated before
+    pub fn update_rewards(
+        &mut self,
+        reward_extension: &RewardExtension,
+        position_liquidity: u128,
+    ) -> Result<()> {
+        for (position_reward_info, reward_info) in self
+            .reward_infos
+            .iter_mut()
+            .zip(reward_extension.reward_infos.iter())
+        {
+            if reward_info.initialized() {
+                position_reward_info
+                    .update_rewards(position_liquidity, reward_info.reward_per_token_stored())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn claim_reward(&mut self, index: usize) -> Result<u64> {
+        let reward_info = &mut self.reward_infos[index];
+        let total_reward = reward_info.reward_pendings;
+        reward_info.total_claimed_rewards =
+            reward_info.total_claimed_rewards.wrapping_add(total_reward);
+        reward_info.reward_pendings = 0;
+
+        Ok(total_reward)
+    }
+
+    /// Move pending and claimed extension rewards of source into self
+    pub fn merge(&mut self, source: &mut PositionRewardExtension) -> Result<()> {
+        for (reward_info, source_reward_info) in self
+            .reward_infos
+            .iter_mut()
+            .zip(source.reward_infos.iter_mut())
+        {
+            reward_info.merge(source_reward_info)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reward_infos
+            .iter()
+            .all(|reward_info| reward_info.reward_pendings == 0)
+    }
+}
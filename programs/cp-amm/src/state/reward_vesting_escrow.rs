@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    safe_math::SafeMath, u128x128_math::Rounding, utils_math::safe_mul_div_cast_u64, PoolError,
+};
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Rewards claimed by an owner from a reward with claim vesting, released linearly until `end_time`
+pub struct RewardVestingEscrow {
+    /// pool
+    pub pool: Pubkey,
+    /// owner receiving the vested rewards
+    pub owner: Pubkey,
+    /// reward index
+    pub reward_index: u8,
+    /// padding
+    pub _padding_0: [u8; 7],
+    /// rewards still locked at last update time
+    pub locked_amount: u64,
+    /// rewards released and not claimed yet
+    pub unlocked_amount: u64,
+    /// last time locked rewards were released
+    pub last_update_time: u64,
+    /// time all locked rewards are released
+    pub end_time: u64,
+    /// total rewards claimed from the escrow
+    pub total_claimed_amount: u64,
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(RewardVestingEscrow::INIT_SPACE, 144);
+
+impl RewardVestingEscrow {
+    pub fn initialize(&mut self, pool: Pubkey, owner: Pubkey, reward_index: u8) {
+        self.pool = pool;
+        self.owner = owner;
+        self.reward_index = reward_index;
+    }
+
+    /// Release locked rewards linearly from last update time to end time
+    pub fn release(&mut self, current_time: u64) -> Result<()> {
+        if current_time <= self.last_update_time {
+            return Ok(());
+        }
+        let released_amount = if current_time >= self.end_time {
+            self.locked_amount
+        } else {
+            safe_mul_div_cast_u64(
+                self.locked_amount,
+                current_time.safe_sub(self.last_update_time)?,
+                self.end_time.safe_sub(self.last_update_time)?,
+                Rounding::Down,
+            )?
+        };
+        self.locked_amount = self.locked_amount.safe_sub(released_amount)?;
+        self.unlocked_amount = self.unlocked_amount.safe_add(released_amount)?;
+        self.last_update_time = current_time;
+        Ok(())
+    }
+
+    /// Lock claimed rewards for vesting duration. Remaining duration of rewards locked before
+    /// is averaged with vesting duration, weighted by amount
+    pub fn lock(&mut self, amount: u64, vesting_duration: u64, current_time: u64) -> Result<()> {
+        self.release(current_time)?;
+        if amount == 0 {
+            return Ok(());
+        }
+        let remaining_duration = self.end_time.saturating_sub(current_time);
+        let locked_amount = self.locked_amount.safe_add(amount)?;
+        let weighted_duration = u128::from(self.locked_amount)
+            .safe_mul(remaining_duration.into())?
+            .safe_add(u128::from(amount).safe_mul(vesting_duration.into())?)?;
+        // rounded up, so rewards locked before are never released faster
+        let duration: u64 = weighted_duration
+            .div_ceil(locked_amount.into())
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed)?;
+        self.locked_amount = locked_amount;
+        self.last_update_time = current_time;
+        self.end_time = current_time.safe_add(duration)?;
+        Ok(())
+    }
+
+    /// Claim all released rewards
+    pub fn claim(&mut self, current_time: u64) -> Result<u64> {
+        self.release(current_time)?;
+        let amount = self.unlocked_amount;
+        self.unlocked_amount = 0;
+        self.total_claimed_amount = self.total_claimed_amount.safe_add(amount)?;
+        Ok(amount)
+    }
+}
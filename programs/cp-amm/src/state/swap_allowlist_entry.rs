@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Wallet allowed to swap in a permissioned pool
+pub struct SwapAllowlistEntry {
+    /// pool
+    pub pool: Pubkey,
+    /// allowlisted wallet, swap payer
+    pub wallet: Pubkey,
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(SwapAllowlistEntry::INIT_SPACE, 96);
+
+impl SwapAllowlistEntry {
+    pub fn initialize(&mut self, pool: Pubkey, wallet: Pubkey) {
+        self.pool = pool;
+        self.wallet = wallet;
+    }
+}
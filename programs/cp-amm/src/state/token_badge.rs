@@ -1,21 +1,76 @@
 use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
 
+/// Token-2022 mint features that a token badge allows, bit index of token badge policy
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum TokenBadgePolicy {
+    AllowTransferHook,
+    AllowPermanentDelegate,
+    AllowFreezeAuthority,
+    AllowInterestBearing,
+}
+
+impl TokenBadgePolicy {
+    pub fn mask(&self) -> u8 {
+        1 << u8::from(*self)
+    }
+}
+
+/// token badge policy bits that can be set
+pub const TOKEN_BADGE_POLICY_MASK: u8 = 0b1111;
+
 #[account(zero_copy)]
 #[derive(InitSpace, Debug)]
 /// Parameter that set by the protocol
 pub struct TokenBadge {
     /// token mint
     pub token_mint: Pubkey,
+    /// bits of allowed mint features, see TokenBadgePolicy
+    pub policy: u8,
+    /// 0 for badges created before policy, they allow every mint feature
+    pub has_policy: u8,
+    /// transfer hook program of the mint when the badge was created, the only hook program approved for transfers
+    pub transfer_hook_program: Pubkey,
     /// Reserve
-    pub _padding: [u8; 128],
+    pub _padding: [u8; 94],
 }
 
 const_assert_eq!(TokenBadge::INIT_SPACE, 160);
 
 impl TokenBadge {
-    pub fn initialize(&mut self, token_mint: Pubkey) -> Result<()> {
+    pub fn initialize(
+        &mut self,
+        token_mint: Pubkey,
+        policy: u8,
+        transfer_hook_program: Pubkey,
+    ) -> Result<()> {
         self.token_mint = token_mint;
+        self.policy = policy;
+        self.has_policy = 1;
+        self.transfer_hook_program = transfer_hook_program;
         Ok(())
     }
+
+    pub fn is_allowed(&self, policy: TokenBadgePolicy) -> bool {
+        self.has_policy == 0 || self.policy & policy.mask() != 0
+    }
+
+    /// Whether transfers of the mint can invoke `transfer_hook_program`. Badges created before the hook program
+    /// was recorded approve no hook program
+    pub fn is_transfer_hook_program_approved(&self, transfer_hook_program: Pubkey) -> bool {
+        self.is_allowed(TokenBadgePolicy::AllowTransferHook)
+            && transfer_hook_program != Pubkey::default()
+            && self.transfer_hook_program == transfer_hook_program
+    }
 }
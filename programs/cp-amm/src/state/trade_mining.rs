@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+use static_assertions::const_assert_eq;
+
+use crate::{constants::TRADE_MINING_RATE_SCALE, safe_math::SafeMath};
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Trade mining program of a pool, swappers passing it with their trader reward accrue rewards
+/// proportional to trade fee paid, until the funded budget is used up or trade mining ends
+pub struct TradeMining {
+    /// pool
+    pub pool: Pubkey,
+    /// reward mint
+    pub mint: Pubkey,
+    /// reward vault
+    pub vault: Pubkey,
+    /// funder of trade mining budget
+    pub funder: Pubkey,
+    /// reward per unit of trade fee paid in token a, Q64.64
+    pub reward_per_fee_a: u128,
+    /// reward per unit of trade fee paid in token b, Q64.64
+    pub reward_per_fee_b: u128,
+    /// funded rewards not accrued to traders yet
+    pub remaining_budget: u64,
+    /// total rewards funded, excluding transfer fee
+    pub total_funded_amount: u64,
+    /// total rewards accrued to traders
+    pub total_accrued_amount: u64,
+    /// total rewards claimed by traders
+    pub total_claimed_amount: u64,
+    /// reward token flag
+    pub reward_token_flag: u8,
+    /// padding
+    pub _padding_0: [u8; 7],
+    /// timestamp trade mining ends at, swaps stop accruing rewards and unaccrued budget can be withdrawn
+    pub end_time: u64,
+    /// Reserve
+    pub _padding: [u8; 48],
+}
+
+const_assert_eq!(TradeMining::INIT_SPACE, 256);
+
+impl TradeMining {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        mint: Pubkey,
+        vault: Pubkey,
+        funder: Pubkey,
+        reward_per_fee_a: u128,
+        reward_per_fee_b: u128,
+        reward_token_flag: u8,
+        end_time: u64,
+    ) {
+        self.pool = pool;
+        self.mint = mint;
+        self.vault = vault;
+        self.funder = funder;
+        self.reward_per_fee_a = reward_per_fee_a;
+        self.reward_per_fee_b = reward_per_fee_b;
+        self.reward_token_flag = reward_token_flag;
+        self.end_time = end_time;
+    }
+
+    pub fn is_ended(&self, current_time: u64) -> bool {
+        current_time >= self.end_time
+    }
+
+    pub fn fund(&mut self, amount: u64) -> Result<()> {
+        self.remaining_budget = self.remaining_budget.safe_add(amount)?;
+        self.total_funded_amount = self.total_funded_amount.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// Accrue rewards of a trade fee from the remaining budget, returns the accrued amount.
+    /// Never fails, so trade mining can't block a swap
+    pub fn accrue(&mut self, trade_fee: u64, fees_on_token_a: bool, current_time: u64) -> u64 {
+        if self.is_ended(current_time) {
+            return 0;
+        }
+        let reward_per_fee = if fees_on_token_a {
+            self.reward_per_fee_a
+        } else {
+            self.reward_per_fee_b
+        };
+        // u64 * u128 fits in U256
+        let reward = (U256::from(trade_fee) * U256::from(reward_per_fee))
+            >> usize::from(TRADE_MINING_RATE_SCALE);
+        // capped by remaining budget, so it fits in u64
+        let accrued_amount = reward.min(U256::from(self.remaining_budget)).to::<u64>();
+
+        self.remaining_budget -= accrued_amount;
+        self.total_accrued_amount = self.total_accrued_amount.saturating_add(accrued_amount);
+        accrued_amount
+    }
+
+    pub fn accumulate_claimed_amount(&mut self, amount: u64) {
+        self.total_claimed_amount = self.total_claimed_amount.wrapping_add(amount);
+    }
+
+    /// Take the budget never accrued to traders, returns the withdrawn amount
+    pub fn withdraw_remaining_budget(&mut self) -> u64 {
+        let amount = self.remaining_budget;
+        self.remaining_budget = 0;
+        amount
+    }
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Trade mining rewards of a trader in a pool
+pub struct TraderReward {
+    /// pool
+    pub pool: Pubkey,
+    /// trader, swaps signed by the trader accrue rewards to this account
+    pub trader: Pubkey,
+    /// rewards accrued and not claimed yet
+    pub pending_amount: u64,
+    /// total rewards claimed
+    pub total_claimed_amount: u64,
+    /// total trade fee paid in token a by swaps accruing rewards
+    pub total_fee_a: u64,
+    /// total trade fee paid in token b by swaps accruing rewards
+    pub total_fee_b: u64,
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(TraderReward::INIT_SPACE, 128);
+
+impl TraderReward {
+    pub fn initialize(&mut self, pool: Pubkey, trader: Pubkey) {
+        self.pool = pool;
+        self.trader = trader;
+    }
+
+    // saturate instead of failing, so trade mining can't block a swap
+    pub fn accumulate(&mut self, accrued_amount: u64, trade_fee: u64, fees_on_token_a: bool) {
+        self.pending_amount = self.pending_amount.saturating_add(accrued_amount);
+        if fees_on_token_a {
+            self.total_fee_a = self.total_fee_a.saturating_add(trade_fee);
+        } else {
+            self.total_fee_b = self.total_fee_b.saturating_add(trade_fee);
+        }
+    }
+
+    pub fn claim(&mut self) -> u64 {
+        let amount = self.pending_amount;
+        self.pending_amount = 0;
+        self.total_claimed_amount = self.total_claimed_amount.wrapping_add(amount);
+        amount
+    }
+}
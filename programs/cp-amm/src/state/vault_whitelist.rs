@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{constants::MAX_WHITELISTED_VAULTS, PoolError};
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Buyers of a pool allowed to swap from the pre-activation point, eg: alpha vault, market maker, treasury
+pub struct VaultWhitelist {
+    /// pool
+    pub pool: Pubkey,
+    /// whitelisted vaults, unused slots are default pubkey
+    pub vaults: [Pubkey; MAX_WHITELISTED_VAULTS],
+    /// Reserve
+    pub _padding: [u8; 64],
+}
+
+const_assert_eq!(VaultWhitelist::INIT_SPACE, 224);
+
+impl VaultWhitelist {
+    pub fn initialize(&mut self, pool: Pubkey, vaults: &[Pubkey]) -> Result<()> {
+        self.pool = pool;
+        self.update_vaults(vaults)
+    }
+
+    pub fn update_vaults(&mut self, vaults: &[Pubkey]) -> Result<()> {
+        validate_whitelisted_vaults(vaults)?;
+
+        self.vaults = [Pubkey::default(); MAX_WHITELISTED_VAULTS];
+        for (i, vault) in vaults.iter().enumerate() {
+            self.vaults[i] = *vault;
+        }
+        Ok(())
+    }
+
+    pub fn is_whitelisted(&self, sender: &Pubkey) -> bool {
+        *sender != Pubkey::default() && self.vaults.contains(sender)
+    }
+}
+
+/// Vaults must be non default and unique
+fn validate_whitelisted_vaults(vaults: &[Pubkey]) -> Result<()> {
+    require!(
+        vaults.len() <= MAX_WHITELISTED_VAULTS,
+        PoolError::InvalidVaultWhitelist
+    );
+
+    for (i, vault) in vaults.iter().enumerate() {
+        require!(
+            *vault != Pubkey::default() && !vaults[..i].contains(vault),
+            PoolError::InvalidVaultWhitelist
+        );
+    }
+    Ok(())
+}
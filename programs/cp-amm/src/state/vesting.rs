@@ -46,6 +46,13 @@ impl Vesting {
         Ok(total_amount)
     }
 
+    pub fn get_remaining_locked_liquidity(&self) -> Result<u128> {
+        let remaining_liquidity = self
+            .get_total_lock_amount()?
+            .safe_sub(self.total_released_liquidity)?;
+        Ok(remaining_liquidity)
+    }
+
     pub fn get_max_unlocked_liquidity(&self, current_point: u64) -> Result<u128> {
         if current_point < self.cliff_point {
             return Ok(0);
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::state::{BuybackBurn, Pool};
+
+#[test]
+fn test_buyback_burn() {
+    let mut pool = Pool::zeroed();
+    pool.protocol_b_fee = 1_500;
+
+    let mut buyback_burn = BuybackBurn::zeroed();
+    buyback_burn.initialize(Pubkey::new_unique(), 1_000, 60);
+
+    // first buyback is allowed any time
+    buyback_burn.validate_buyback(10).unwrap();
+    let amount = pool
+        .take_buyback_amount(buyback_burn.max_amount_per_buyback)
+        .unwrap();
+    assert_eq!(amount, 1_000);
+    assert_eq!(pool.protocol_b_fee, 500);
+    buyback_burn.record_buyback(amount, 90, 10).unwrap();
+
+    // next buyback waits for min buyback interval
+    assert!(buyback_burn.validate_buyback(69).is_err());
+    buyback_burn.validate_buyback(70).unwrap();
+    let amount = pool
+        .take_buyback_amount(buyback_burn.max_amount_per_buyback)
+        .unwrap();
+    assert_eq!(amount, 500);
+    assert_eq!(pool.protocol_b_fee, 0);
+    buyback_burn.record_buyback(amount, 45, 70).unwrap();
+
+    assert_eq!(buyback_burn.last_buyback_timestamp, 70);
+    assert_eq!(buyback_burn.total_spent_amount, 1_500);
+    assert_eq!(buyback_burn.total_burnt_amount, 135);
+}
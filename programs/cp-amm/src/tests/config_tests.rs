@@ -0,0 +1,84 @@
+use bytemuck::Zeroable;
+
+use crate::{
+    constants::{
+        activation::MAX_CPI_GUARD_DURATION,
+        fee::{MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR},
+    },
+    state::{fee::BaseFeeStruct, Config, Pool},
+};
+
+#[test]
+fn test_pool_fee_override_bounds() {
+    let mut config = Config::zeroed();
+
+    // override is disabled by default
+    assert!(config
+        .validate_pool_fee_override(MIN_FEE_NUMERATOR)
+        .is_err());
+
+    // bounds must be within min and max fee
+    assert!(config
+        .set_pool_fee_override_bounds(MIN_FEE_NUMERATOR - 1, MAX_FEE_NUMERATOR)
+        .is_err());
+    assert!(config
+        .set_pool_fee_override_bounds(MIN_FEE_NUMERATOR, MAX_FEE_NUMERATOR + 1)
+        .is_err());
+    assert!(config
+        .set_pool_fee_override_bounds(10_000_000, 5_000_000)
+        .is_err());
+
+    config
+        .set_pool_fee_override_bounds(5_000_000, 10_000_000)
+        .unwrap();
+    assert!(config.validate_pool_fee_override(4_999_999).is_err());
+    assert!(config.validate_pool_fee_override(10_000_001).is_err());
+    config.validate_pool_fee_override(5_000_000).unwrap();
+    config.validate_pool_fee_override(10_000_000).unwrap();
+
+    // disable override
+    config.set_pool_fee_override_bounds(0, 0).unwrap();
+    assert!(config.validate_pool_fee_override(5_000_000).is_err());
+}
+
+#[test]
+fn test_override_trade_fee_remove_fee_scheduler() {
+    let mut base_fee = BaseFeeStruct {
+        cliff_fee_numerator: 500_000_000,
+        number_of_period: 10,
+        period_frequency: 60,
+        reduction_factor: 1_000,
+        ..Default::default()
+    };
+    base_fee.override_trade_fee(5_000_000);
+
+    assert_eq!(
+        base_fee.get_current_base_fee_numerator(0, 0).unwrap(),
+        5_000_000
+    );
+    assert_eq!(
+        base_fee.get_current_base_fee_numerator(1_000, 0).unwrap(),
+        5_000_000
+    );
+}
+
+#[test]
+fn test_cpi_guard_duration() {
+    let mut config = Config::zeroed();
+    assert!(config
+        .set_cpi_guard_duration(MAX_CPI_GUARD_DURATION + 1)
+        .is_err());
+    config.set_cpi_guard_duration(100).unwrap();
+
+    let mut pool = Pool::zeroed();
+    pool.activation_point = 1000;
+    // disabled by default
+    assert!(!pool.is_cpi_guard_active(1000).unwrap());
+
+    pool.cpi_guard_duration = config.cpi_guard_duration;
+    // whitelisted vaults swap before activation point without the guard
+    assert!(!pool.is_cpi_guard_active(999).unwrap());
+    assert!(pool.is_cpi_guard_active(1000).unwrap());
+    assert!(pool.is_cpi_guard_active(1099).unwrap());
+    assert!(!pool.is_cpi_guard_active(1100).unwrap());
+}
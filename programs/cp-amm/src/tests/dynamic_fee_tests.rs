@@ -175,3 +175,107 @@ fn test_from_price_to_bin_id() {
 
     println!("{:?}", model);
 }
+
+#[test]
+fn test_update_dynamic_fee_parameters() {
+    use crate::constants::U24_MAX;
+    use crate::state::fee::DynamicFeeStruct;
+
+    let mut dynamic_fee = DynamicFeeStruct::default();
+    // dynamic fee is not initialized
+    assert!(dynamic_fee.update_parameters(120, 5000, 100_000).is_err());
+
+    dynamic_fee.initialized = 1;
+    dynamic_fee.filter_period = 10;
+    dynamic_fee.volatility_accumulator = 1_000;
+
+    // decay period must be greater than filter period
+    assert!(dynamic_fee.update_parameters(10, 5000, 100_000).is_err());
+    // reduction factor can't exceed 100%
+    assert!(dynamic_fee
+        .update_parameters(120, BASIS_POINT_MAX as u16 + 1, 100_000)
+        .is_err());
+    assert!(dynamic_fee
+        .update_parameters(120, 5000, U24_MAX + 1)
+        .is_err());
+
+    dynamic_fee.update_parameters(120, 5000, 100_000).unwrap();
+    assert_eq!(dynamic_fee.decay_period, 120);
+    assert_eq!(dynamic_fee.reduction_factor, 5000);
+    assert_eq!(dynamic_fee.variable_fee_control, 100_000);
+    // volatility states are kept
+    assert_eq!(dynamic_fee.volatility_accumulator, 1_000);
+}
+
+#[test]
+fn test_pending_dynamic_fee_update() {
+    use crate::state::fee::PoolFeesStruct;
+
+    let mut pool_fees = PoolFeesStruct::default();
+    pool_fees.dynamic_fee.initialized = 1;
+    pool_fees.dynamic_fee.filter_period = 10;
+    pool_fees.dynamic_fee.decay_period = 60;
+
+    // nothing to cancel
+    assert!(pool_fees.cancel_pending_dynamic_fee_update().is_err());
+    // queued parameters are validated
+    assert!(pool_fees
+        .queue_dynamic_fee_update(10, 5000, 100_000, 1_000)
+        .is_err());
+
+    pool_fees
+        .queue_dynamic_fee_update(120, 5000, 100_000, 1_000)
+        .unwrap();
+    pool_fees.apply_pending_dynamic_fee_update(999).unwrap();
+    assert_eq!(pool_fees.dynamic_fee.decay_period, 60);
+
+    pool_fees.apply_pending_dynamic_fee_update(1_000).unwrap();
+    assert_eq!(pool_fees.dynamic_fee.decay_period, 120);
+    assert_eq!(pool_fees.dynamic_fee.reduction_factor, 5000);
+    assert_eq!(pool_fees.dynamic_fee.variable_fee_control, 100_000);
+    assert!(!pool_fees.pending_dynamic_fee.is_pending());
+
+    // cancelled update never takes effect
+    pool_fees
+        .queue_dynamic_fee_update(200, 5000, 100_000, 2_000)
+        .unwrap();
+    pool_fees.cancel_pending_dynamic_fee_update().unwrap();
+    pool_fees.apply_pending_dynamic_fee_update(2_000).unwrap();
+    assert_eq!(pool_fees.dynamic_fee.decay_period, 120);
+}
+
+#[test]
+fn test_pending_base_fee_override() {
+    use crate::state::Pool;
+
+    let mut pool = Pool::default();
+    pool.pool_fees.base_fee.cliff_fee_numerator = 2_500_000;
+    pool.pool_fees.base_fee.number_of_period = 10;
+    pool.pool_fees.base_fee.period_frequency = 60;
+
+    // nothing to cancel
+    assert!(pool.cancel_pending_fee_updates().is_err());
+
+    // override only takes effect at its activation point
+    pool.queue_base_fee_override(5_000_000, 1_000);
+    pool.apply_pending_fee_updates(999).unwrap();
+    assert_eq!(pool.pool_fees.base_fee.cliff_fee_numerator, 2_500_000);
+
+    pool.apply_pending_fee_updates(1_000).unwrap();
+    assert_eq!(pool.pool_fees.base_fee.cliff_fee_numerator, 5_000_000);
+    assert_eq!(pool.pool_fees.base_fee.number_of_period, 0);
+    assert!(!pool.pending_base_fee.is_pending());
+
+    // cancelled override never takes effect, pending dynamic fee update is cancelled with it
+    pool.pool_fees.dynamic_fee.initialized = 1;
+    pool.pool_fees.dynamic_fee.filter_period = 10;
+    pool.pool_fees
+        .queue_dynamic_fee_update(120, 5000, 100_000, 2_000)
+        .unwrap();
+    pool.queue_base_fee_override(1_000_000, 2_000);
+    pool.cancel_pending_fee_updates().unwrap();
+    assert!(!pool.pool_fees.pending_dynamic_fee.is_pending());
+    pool.apply_pending_fee_updates(2_000).unwrap();
+    assert_eq!(pool.pool_fees.base_fee.cliff_fee_numerator, 5_000_000);
+    assert_eq!(pool.pool_fees.dynamic_fee.decay_period, 0);
+}
@@ -0,0 +1,69 @@
+use bytemuck::Zeroable;
+
+use crate::state::{
+    fee::{BaseFeeStruct, PoolFeesStruct},
+    FeeDiscountRegistry, FeeDiscountTier,
+};
+
+fn tier(min_amount: u64, discount_bps: u16) -> FeeDiscountTier {
+    FeeDiscountTier {
+        min_amount,
+        discount_bps,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_fee_discount_tiers() {
+    let mut registry = FeeDiscountRegistry::zeroed();
+
+    // tiers must be increasing in both amount and discount
+    assert!(registry
+        .update_tiers(&[tier(1_000, 500), tier(1_000, 1_000)])
+        .is_err());
+    assert!(registry
+        .update_tiers(&[tier(1_000, 500), tier(10_000, 500)])
+        .is_err());
+    // discount is capped
+    assert!(registry.update_tiers(&[tier(1_000, 5_001)]).is_err());
+    assert!(registry.update_tiers(&[tier(1_000, 0)]).is_err());
+    // too many tiers
+    assert!(registry
+        .update_tiers(&[tier(1, 1), tier(2, 2), tier(3, 3), tier(4, 4), tier(5, 5)])
+        .is_err());
+
+    registry
+        .update_tiers(&[tier(1_000, 500), tier(10_000, 1_000), tier(100_000, 2_000)])
+        .unwrap();
+    assert_eq!(registry.get_fee_discount_bps(999), 0);
+    assert_eq!(registry.get_fee_discount_bps(1_000), 500);
+    assert_eq!(registry.get_fee_discount_bps(99_999), 1_000);
+    assert_eq!(registry.get_fee_discount_bps(u64::MAX), 2_000);
+
+    // empty tiers disable discount
+    registry.update_tiers(&[]).unwrap();
+    assert_eq!(registry.get_fee_discount_bps(u64::MAX), 0);
+}
+
+#[test]
+fn test_fee_on_amount_with_discount() {
+    let pool_fees = PoolFeesStruct {
+        base_fee: BaseFeeStruct {
+            cliff_fee_numerator: 10_000_000, // 1%
+            ..Default::default()
+        },
+        protocol_fee_percent: 20,
+        ..Default::default()
+    };
+
+    let full_fee = pool_fees
+        .get_fee_on_amount(1_000_000, false, 0, 0, 0)
+        .unwrap();
+    let discounted_fee = pool_fees
+        .get_fee_on_amount(1_000_000, false, 2_500, 0, 0)
+        .unwrap();
+
+    assert_eq!(full_fee.lp_fee + full_fee.protocol_fee, 10_000);
+    assert_eq!(discounted_fee.lp_fee + discounted_fee.protocol_fee, 7_500);
+    assert_eq!(discounted_fee.amount, 1_000_000 - 7_500);
+}
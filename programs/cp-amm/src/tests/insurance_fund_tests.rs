@@ -0,0 +1,20 @@
+use crate::state::fee::PoolFeesStruct;
+
+#[test]
+fn test_insurance_fee() {
+    let mut pool_fees = PoolFeesStruct::default();
+    // nothing is routed without insurance fee percent
+    assert_eq!(pool_fees.get_insurance_fee(1_000).unwrap(), 0);
+
+    pool_fees.insurance_fee_percent = 15;
+    assert_eq!(pool_fees.get_insurance_fee(1_000).unwrap(), 150);
+    // rounded down
+    assert_eq!(pool_fees.get_insurance_fee(99).unwrap(), 14);
+    assert_eq!(
+        pool_fees.get_insurance_fee(u64::MAX).unwrap(),
+        u64::MAX / 100 * 15 + u64::MAX % 100 * 15 / 100
+    );
+
+    pool_fees.insurance_fee_percent = 100;
+    assert_eq!(pool_fees.get_insurance_fee(u64::MAX).unwrap(), u64::MAX);
+}
@@ -0,0 +1,209 @@
+use crate::{
+    constants::{fee::MAX_FEE_NUMERATOR, LIQUIDITY_SCALE, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    params::swap::TradeDirection,
+    state::{
+        fee::{BaseFeeStruct, FeeMode, FeeOnAmountResult, PoolFeesStruct},
+        Pool, Position, SwapResult,
+    },
+    tests::LIQUIDITY_MAX,
+    u128x128_math::{shl_div_256, Rounding},
+};
+use cp_amm_core::{constants::FEE_DENOMINATOR, fee_math::get_charged_fee_numerator};
+use proptest::prelude::*;
+use ruint::aliases::U256;
+
+fn get_pool_fees(
+    cliff_fee_numerator: u64,
+    protocol_fee_percent: u8,
+    partner_fee_percent: u8,
+    referral_fee_percent: u8,
+) -> PoolFeesStruct {
+    PoolFeesStruct {
+        base_fee: BaseFeeStruct {
+            cliff_fee_numerator,
+            ..Default::default()
+        },
+        protocol_fee_percent,
+        partner_fee_percent,
+        referral_fee_percent,
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 10000, .. ProptestConfig::default()
+    })]
+    #[test]
+    fn test_fee_split_is_conserved(
+        amount in 0..=u64::MAX,
+        cliff_fee_numerator in 0..=MAX_FEE_NUMERATOR,
+        protocol_fee_percent in 0..=100u8,
+        partner_fee_percent in 0..=100u8,
+        referral_fee_percent in 0..=100u8,
+        has_referral: bool,
+        fee_discount_bps in 0..=10_000u16,
+    ) {
+        let pool_fees = get_pool_fees(
+            cliff_fee_numerator,
+            protocol_fee_percent,
+            partner_fee_percent,
+            referral_fee_percent,
+        );
+        let FeeOnAmountResult {
+            amount: amount_after_fee,
+            lp_fee,
+            protocol_fee,
+            partner_fee,
+            referral_fee,
+        } = pool_fees
+            .get_fee_on_amount(amount, has_referral, fee_discount_bps, 0, 0)
+            .unwrap();
+
+        let total_fee = u128::from(lp_fee)
+            + u128::from(protocol_fee)
+            + u128::from(partner_fee)
+            + u128::from(referral_fee);
+        assert_eq!(total_fee + u128::from(amount_after_fee), u128::from(amount));
+
+        // total fee taken is the charged fee numerator on amount, rounding up
+        let fee_numerator =
+            get_charged_fee_numerator(cliff_fee_numerator.into(), fee_discount_bps).unwrap();
+        let expected_total_fee = (u128::from(amount) * u128::from(fee_numerator))
+            .div_ceil(u128::from(FEE_DENOMINATOR));
+        assert_eq!(total_fee, expected_total_fee);
+        if !has_referral {
+            assert_eq!(referral_fee, 0);
+        }
+    }
+
+    #[test]
+    fn test_vault_amounts_cover_liquidity_after_swap(
+        sqrt_price in MIN_SQRT_PRICE..=MAX_SQRT_PRICE,
+        token_a_amount in 1..=u64::MAX,
+        token_b_amount in 1..=u64::MAX,
+        amount_in in 1..=u64::MAX,
+        cliff_fee_numerator in 0..=MAX_FEE_NUMERATOR,
+        collect_fee_mode in 0..=2u8,
+        a_to_b: bool,
+        has_referral: bool,
+    ) {
+        let mut pool = Pool {
+            sqrt_price,
+            sqrt_min_price: MIN_SQRT_PRICE,
+            sqrt_max_price: MAX_SQRT_PRICE,
+            collect_fee_mode,
+            pool_fees: get_pool_fees(cliff_fee_numerator, 20, 50, 20),
+            ..Default::default()
+        };
+        let mut position = Position::default();
+
+        let liquidity = pool
+            .get_liquidity_delta_for_amounts(token_a_amount, token_b_amount)
+            .unwrap();
+        if liquidity == 0 {
+            return Ok(());
+        }
+        let deposit = pool
+            .get_amounts_for_modify_liquidity(liquidity, Rounding::Up)
+            .unwrap();
+        pool.apply_add_liquidity(&mut position, liquidity).unwrap();
+
+        let trade_direction = if a_to_b { TradeDirection::AtoB } else { TradeDirection::BtoA };
+        let fee_mode = &FeeMode::get_fee_mode(collect_fee_mode, trade_direction, has_referral).unwrap();
+        let amount_in = amount_in.min(pool.get_max_amount_in(trade_direction).unwrap());
+        if amount_in == 0 {
+            return Ok(());
+        }
+        let swap_result = pool
+            .get_swap_result(amount_in, fee_mode, trade_direction, 0)
+            .unwrap();
+        pool.apply_swap_result(amount_in, &swap_result, fee_mode, trade_direction, 0).unwrap();
+
+        let mut vault_a = u128::from(deposit.token_a_amount);
+        let mut vault_b = u128::from(deposit.token_b_amount);
+        if a_to_b {
+            vault_a += u128::from(amount_in);
+            vault_b -= u128::from(swap_result.output_amount);
+        } else {
+            vault_b += u128::from(amount_in);
+            vault_a -= u128::from(swap_result.output_amount);
+        }
+
+        // every fee stays in the vault until claimed
+        let total_fee = u128::from(swap_result.lp_fee)
+            + u128::from(swap_result.protocol_fee)
+            + u128::from(swap_result.partner_fee)
+            + u128::from(swap_result.referral_fee);
+        if fee_mode.fees_on_token_a {
+            vault_a -= total_fee;
+        } else {
+            vault_b -= total_fee;
+        }
+
+        let withdrawal = pool
+            .get_amounts_for_modify_liquidity(liquidity, Rounding::Down)
+            .unwrap();
+        assert!(u128::from(withdrawal.token_a_amount) <= vault_a);
+        assert!(u128::from(withdrawal.token_b_amount) <= vault_b);
+    }
+
+    #[test]
+    fn test_fee_per_liquidity_wont_overflow_for_max_liquidity(
+        lp_fee in 0..=u64::MAX,
+        fees_on_token_a: bool,
+    ) {
+        // fee per liquidity after u64::MAX swaps charging u64::MAX lp fee
+        let max_fee_per_liquidity =
+            shl_div_256(u64::MAX.into(), LIQUIDITY_MAX, LIQUIDITY_SCALE).unwrap();
+        let accumulated_fee_per_liquidity = max_fee_per_liquidity
+            .checked_mul(U256::from(u64::MAX))
+            .unwrap();
+
+        let mut pool = Pool {
+            liquidity: LIQUIDITY_MAX,
+            sqrt_price: MAX_SQRT_PRICE,
+            sqrt_min_price: MIN_SQRT_PRICE,
+            sqrt_max_price: MAX_SQRT_PRICE,
+            fee_a_per_liquidity: accumulated_fee_per_liquidity.to_le_bytes(),
+            fee_b_per_liquidity: accumulated_fee_per_liquidity.to_le_bytes(),
+            ..Default::default()
+        };
+        let mut position = Position {
+            unlocked_liquidity: LIQUIDITY_MAX,
+            fee_a_per_token_checkpoint: accumulated_fee_per_liquidity.to_le_bytes(),
+            fee_b_per_token_checkpoint: accumulated_fee_per_liquidity.to_le_bytes(),
+            ..Default::default()
+        };
+
+        let fee_mode = &FeeMode {
+            fees_on_input: false,
+            fees_on_token_a,
+            has_referral: false,
+            fee_discount_bps: 0,
+        };
+        let swap_result = SwapResult {
+            output_amount: 0,
+            next_sqrt_price: MAX_SQRT_PRICE,
+            lp_fee,
+            protocol_fee: 0,
+            partner_fee: 0,
+            referral_fee: 0,
+        };
+        pool.apply_swap_result(0, &swap_result, fee_mode, TradeDirection::AtoB, 0).unwrap();
+
+        // the only position never claims more than the lp fee
+        position
+            .update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())
+            .unwrap();
+        let claimed_fee = if fees_on_token_a {
+            assert_eq!(position.fee_b_pending, 0);
+            position.fee_a_pending
+        } else {
+            assert_eq!(position.fee_a_pending, 0);
+            position.fee_b_pending
+        };
+        assert!(claimed_fee <= lp_fee);
+        assert!(claimed_fee + 1 >= lp_fee);
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::{
+    get_pool_access_validator_at_point,
+    state::{LpAllowlistEntry, Pool},
+    PoolActionAccess,
+};
+
+#[test]
+fn test_permissioned_lp_access() {
+    let mut pool = Pool::zeroed();
+    let wallet = Pubkey::new_unique();
+    let other_wallet = Pubkey::new_unique();
+
+    // permissionless lp pool doesn't need an allowlist entry
+    let access_validator = get_pool_access_validator_at_point(&pool, None, None, None, 0).unwrap();
+    assert!(access_validator.can_add_liquidity(&wallet));
+    assert!(access_validator.can_create_position(&wallet));
+
+    pool.lp_allowlist_authority = Pubkey::new_unique();
+    assert!(pool.is_permissioned_lp());
+
+    let access_validator = get_pool_access_validator_at_point(&pool, None, None, None, 0).unwrap();
+    assert!(!access_validator.can_add_liquidity(&wallet));
+    assert!(!access_validator.can_create_position(&wallet));
+    // swap and withdrawal stay permissionless
+    assert!(access_validator.can_swap(&wallet));
+    assert!(access_validator.can_remove_liquidity());
+
+    let mut lp_allowlist_entry = LpAllowlistEntry::zeroed();
+    lp_allowlist_entry.initialize(Pubkey::new_unique(), wallet);
+    let access_validator =
+        get_pool_access_validator_at_point(&pool, None, None, Some(&lp_allowlist_entry), 0)
+            .unwrap();
+    assert!(access_validator.can_add_liquidity(&wallet));
+    assert!(access_validator.can_create_position(&wallet));
+    // entry of another wallet doesn't grant access
+    assert!(!access_validator.can_add_liquidity(&other_wallet));
+    assert!(!access_validator.can_create_position(&other_wallet));
+
+    // emergency mode halts deposits of allowlisted wallets
+    pool.emergency_mode = 1;
+    let access_validator =
+        get_pool_access_validator_at_point(&pool, None, None, Some(&lp_allowlist_entry), 0)
+            .unwrap();
+    assert!(!access_validator.can_add_liquidity(&wallet));
+    assert!(!access_validator.can_create_position(&wallet));
+}
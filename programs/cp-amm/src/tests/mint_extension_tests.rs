@@ -0,0 +1,32 @@
+use crate::token::{has_tlv_extension_type, PAUSABLE_EXTENSION_TYPE};
+
+fn tlv_entry(extension_type: u16, value: &[u8]) -> Vec<u8> {
+    let mut entry = extension_type.to_le_bytes().to_vec();
+    entry.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    entry.extend_from_slice(value);
+    entry
+}
+
+#[test]
+fn test_has_tlv_extension_type() {
+    // metadata pointer followed by pausable config
+    let mut tlv_data = tlv_entry(18, &[1; 64]);
+    tlv_data.extend(tlv_entry(PAUSABLE_EXTENSION_TYPE, &[2; 33]));
+    assert!(has_tlv_extension_type(&tlv_data, 18));
+    assert!(has_tlv_extension_type(&tlv_data, PAUSABLE_EXTENSION_TYPE));
+    assert!(!has_tlv_extension_type(&tlv_data, 1));
+
+    // value bytes are not read as extension types
+    let tlv_data = tlv_entry(18, &PAUSABLE_EXTENSION_TYPE.to_le_bytes());
+    assert!(!has_tlv_extension_type(&tlv_data, PAUSABLE_EXTENSION_TYPE));
+
+    // walk stops at uninitialized padding and truncated entries
+    let mut tlv_data = vec![0; 4];
+    tlv_data.extend(tlv_entry(PAUSABLE_EXTENSION_TYPE, &[]));
+    assert!(!has_tlv_extension_type(&tlv_data, PAUSABLE_EXTENSION_TYPE));
+    assert!(!has_tlv_extension_type(
+        &PAUSABLE_EXTENSION_TYPE.to_le_bytes(),
+        PAUSABLE_EXTENSION_TYPE
+    ));
+    assert!(!has_tlv_extension_type(&[], PAUSABLE_EXTENSION_TYPE));
+}
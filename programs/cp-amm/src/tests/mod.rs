@@ -22,8 +22,56 @@ mod price_math;
 #[cfg(test)]
 mod reward_tests;
 
+#[cfg(test)]
+mod invariant_tests;
+
 #[cfg(test)]
 mod fee_scheduler_tests;
 
 #[cfg(test)]
 mod test_volatility_accumulate;
+
+#[cfg(test)]
+mod vesting_tests;
+
+#[cfg(test)]
+mod config_tests;
+
+#[cfg(test)]
+mod fee_discount_tests;
+
+#[cfg(test)]
+mod program_authority_tests;
+
+#[cfg(test)]
+mod token_badge_tests;
+
+#[cfg(test)]
+mod vault_whitelist_tests;
+
+#[cfg(test)]
+mod pool_metadata_tests;
+
+#[cfg(test)]
+mod pool_stats_tests;
+
+#[cfg(test)]
+mod skim_vaults_tests;
+
+#[cfg(test)]
+mod trade_mining_tests;
+
+#[cfg(test)]
+mod insurance_fund_tests;
+
+#[cfg(test)]
+mod buyback_burn_tests;
+
+#[cfg(test)]
+mod lp_allowlist_tests;
+
+#[cfg(test)]
+mod mint_extension_tests;
+
+#[cfg(test)]
+mod pool_access_tests;
@@ -1,10 +1,11 @@
 use crate::{
-    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    constants::{LIQUIDITY_SCALE, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
     state::{Pool, Position},
     tests::LIQUIDITY_MAX,
     u128x128_math::Rounding,
 };
 use proptest::prelude::*;
+use ruint::aliases::U256;
 
 proptest! {
     #![proptest_config(ProptestConfig {
@@ -44,3 +45,90 @@ proptest! {
         assert!(result_0.token_b_amount >= result_1.token_b_amount);
     }
 }
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 10000, .. ProptestConfig::default()
+    })]
+    #[test]
+    fn test_liquidity_for_amounts_wont_exceed_amounts(
+        sqrt_price in MIN_SQRT_PRICE..=MAX_SQRT_PRICE,
+        token_a_amount in 1..=u64::MAX,
+        token_b_amount in 1..=u64::MAX,
+    ) {
+        let pool = Pool {
+            sqrt_price,
+            sqrt_min_price: MIN_SQRT_PRICE,
+            sqrt_max_price: MAX_SQRT_PRICE,
+            ..Default::default()
+        };
+
+        let liquidity_delta = pool
+            .get_liquidity_delta_for_amounts(token_a_amount, token_b_amount)
+            .unwrap();
+
+        if liquidity_delta > 0 && liquidity_delta <= LIQUIDITY_MAX {
+            let result = pool
+                .get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)
+                .unwrap();
+
+            assert!(result.token_a_amount <= token_a_amount);
+            assert!(result.token_b_amount <= token_b_amount);
+        }
+    }
+}
+
+#[test]
+fn test_merge_positions() {
+    let mut pool = Pool {
+        sqrt_price: MIN_SQRT_PRICE,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        ..Default::default()
+    };
+
+    let mut position = Position::default();
+    let mut source_position = Position::default();
+    pool.apply_add_liquidity(&mut position, 1_000).unwrap();
+    pool.apply_add_liquidity(&mut source_position, 3_000)
+        .unwrap();
+    source_position.permanent_lock_liquidity(1_000).unwrap();
+
+    // 1 token a and 2 token b fee per liquidity
+    pool.fee_a_per_liquidity = (U256::from(1) << LIQUIDITY_SCALE).to_le_bytes();
+    pool.fee_b_per_liquidity = (U256::from(2) << LIQUIDITY_SCALE).to_le_bytes();
+    position
+        .update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())
+        .unwrap();
+    source_position
+        .update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())
+        .unwrap();
+    source_position.reward_infos[0].reward_pendings = 10;
+
+    position.merge(&mut source_position).unwrap();
+
+    assert_eq!(position.unlocked_liquidity, 3_000);
+    assert_eq!(position.permanent_locked_liquidity, 1_000);
+    assert_eq!(position.fee_a_pending, 4_000);
+    assert_eq!(position.fee_b_pending, 8_000);
+    assert_eq!(position.reward_infos[0].reward_pendings, 10);
+    assert!(source_position.is_empty().unwrap());
+    assert_eq!(pool.liquidity, 4_000);
+
+    // position with vested liquidity can't be merged
+    let mut vested_position = Position::default();
+    pool.apply_add_liquidity(&mut vested_position, 1_000)
+        .unwrap();
+    vested_position.lock(1_000).unwrap();
+    assert!(position.merge(&mut vested_position).is_err());
+
+    // position with fee receipt can't be merged
+    let mut receipt_position = Position::default();
+    pool.apply_add_liquidity(&mut receipt_position, 1_000)
+        .unwrap();
+    assert!(!receipt_position.is_fully_permanent_locked());
+    receipt_position.permanent_lock_liquidity(1_000).unwrap();
+    assert!(receipt_position.is_fully_permanent_locked());
+    receipt_position.fee_receipt_minted = 1;
+    assert!(position.merge(&mut receipt_position).is_err());
+}
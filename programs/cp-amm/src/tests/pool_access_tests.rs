@@ -0,0 +1,98 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::{
+    get_pool_access_validator_at_point,
+    state::{LpAllowlistEntry, Pool, PoolOperation, SwapAllowlistEntry},
+    PoolAccessValidator, PoolActionAccess,
+};
+
+#[test]
+fn test_permissionless_pool_access() {
+    let mut pool = Pool::zeroed();
+    pool.activation_point = 100;
+    pool.whitelisted_vault = Pubkey::new_unique();
+    let wallet = Pubkey::new_unique();
+
+    let access_validator = get_pool_access_validator_at_point(&pool, None, None, None, 0).unwrap();
+    assert!(matches!(
+        access_validator,
+        PoolAccessValidator::Permissionless(_)
+    ));
+    // only whitelisted vault swaps before activation, from pre-activation point
+    assert!(!access_validator.can_swap(&wallet));
+    assert!(!access_validator.can_remove_liquidity());
+    assert!(access_validator.can_add_liquidity(&wallet));
+    assert!(access_validator.can_create_position(&wallet));
+    assert!(access_validator.can_lock_position());
+    let access_validator =
+        get_pool_access_validator_at_point(&pool, None, None, None, pool.activation_point - 1)
+            .unwrap();
+    assert!(access_validator.can_swap(&pool.whitelisted_vault));
+    assert!(!access_validator.can_swap(&wallet));
+
+    let access_validator =
+        get_pool_access_validator_at_point(&pool, None, None, None, pool.activation_point).unwrap();
+    assert!(access_validator.can_swap(&wallet));
+    assert!(access_validator.can_remove_liquidity());
+
+    // disabled operations are rejected for every wallet
+    pool.pool_status = PoolOperation::Swap.mask() | PoolOperation::RemoveLiquidity.mask();
+    let access_validator =
+        get_pool_access_validator_at_point(&pool, None, None, None, pool.activation_point).unwrap();
+    assert!(!access_validator.can_swap(&wallet));
+    assert!(!access_validator.can_remove_liquidity());
+    assert!(access_validator.can_add_liquidity(&wallet));
+}
+
+#[test]
+fn test_permissioned_pool_access() {
+    let mut pool = Pool::zeroed();
+    pool.swap_allowlist_authority = Pubkey::new_unique();
+    pool.lp_allowlist_authority = Pubkey::new_unique();
+    let wallet = Pubkey::new_unique();
+    let other_wallet = Pubkey::new_unique();
+
+    let access_validator = get_pool_access_validator_at_point(&pool, None, None, None, 0).unwrap();
+    assert!(matches!(
+        access_validator,
+        PoolAccessValidator::Permissioned(_)
+    ));
+    assert!(!access_validator.can_swap(&wallet));
+    assert!(!access_validator.can_add_liquidity(&wallet));
+    assert!(!access_validator.can_create_position(&wallet));
+    // withdrawal stays permissionless, so liquidity is never locked in the pool
+    assert!(access_validator.can_remove_liquidity());
+
+    let mut swap_allowlist_entry = SwapAllowlistEntry::zeroed();
+    swap_allowlist_entry.initialize(Pubkey::new_unique(), wallet);
+    let mut lp_allowlist_entry = LpAllowlistEntry::zeroed();
+    lp_allowlist_entry.initialize(Pubkey::new_unique(), wallet);
+    let access_validator = get_pool_access_validator_at_point(
+        &pool,
+        None,
+        Some(&swap_allowlist_entry),
+        Some(&lp_allowlist_entry),
+        0,
+    )
+    .unwrap();
+    assert!(access_validator.can_swap(&wallet));
+    assert!(access_validator.can_add_liquidity(&wallet));
+    assert!(access_validator.can_create_position(&wallet));
+    // entries of another wallet don't grant access
+    assert!(!access_validator.can_swap(&other_wallet));
+    assert!(!access_validator.can_add_liquidity(&other_wallet));
+    assert!(!access_validator.can_create_position(&other_wallet));
+
+    // allowlisted wallet is still subject to pool status
+    pool.pool_status = PoolOperation::Swap.mask();
+    let access_validator = get_pool_access_validator_at_point(
+        &pool,
+        None,
+        Some(&swap_allowlist_entry),
+        Some(&lp_allowlist_entry),
+        0,
+    )
+    .unwrap();
+    assert!(!access_validator.can_swap(&wallet));
+}
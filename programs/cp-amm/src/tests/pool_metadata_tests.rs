@@ -0,0 +1,42 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::{
+    constants::{MAX_POOL_METADATA_NAME_LENGTH, MAX_POOL_METADATA_URI_LENGTH},
+    state::PoolMetadata,
+};
+
+#[test]
+fn test_pool_metadata() {
+    let mut pool_metadata = PoolMetadata::zeroed();
+    let pool = Pubkey::new_unique();
+
+    // name is required
+    assert!(pool_metadata.initialize(pool, "", "", "", "").is_err());
+    // too long
+    let long_name = "a".repeat(MAX_POOL_METADATA_NAME_LENGTH + 1);
+    assert!(pool_metadata
+        .initialize(pool, &long_name, "", "", "")
+        .is_err());
+    // zero byte
+    assert!(pool_metadata
+        .initialize(pool, "Pool\0", "", "", "")
+        .is_err());
+
+    let uri = format!("https://{}", "a".repeat(MAX_POOL_METADATA_URI_LENGTH - 8));
+    pool_metadata
+        .initialize(pool, "SOL-USDC ✓", "SOL-USDC", &uri, "https://meteora.ag")
+        .unwrap();
+    assert_eq!(pool_metadata.pool, pool);
+    assert_eq!(pool_metadata.name(), "SOL-USDC ✓");
+    assert_eq!(pool_metadata.symbol(), "SOL-USDC");
+    assert_eq!(pool_metadata.uri(), uri);
+    assert_eq!(pool_metadata.project_url(), "https://meteora.ag");
+
+    // shorter values clear the previous bytes
+    pool_metadata.update("SOL", "", "", "").unwrap();
+    assert_eq!(pool_metadata.name(), "SOL");
+    assert_eq!(pool_metadata.symbol(), "");
+    assert_eq!(pool_metadata.uri(), "");
+    assert_eq!(pool_metadata.project_url(), "");
+}
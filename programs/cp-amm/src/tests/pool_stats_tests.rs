@@ -0,0 +1,120 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::{
+    constants::{
+        POOL_STATS_DAILY_BUCKETS, POOL_STATS_DAY, POOL_STATS_HOUR, POOL_STATS_HOURLY_BUCKETS,
+        ROLLING_FEE_WINDOW,
+    },
+    state::{PoolStats, PoolVolumeMetrics, SwapVolume},
+};
+
+fn swap_volume(amount: u64, fees_on_token_a: bool) -> SwapVolume {
+    SwapVolume {
+        amount_a: amount,
+        amount_b: amount * 2,
+        lp_fee: amount / 10,
+        total_fee: amount / 5,
+        fees_on_token_a,
+    }
+}
+
+#[test]
+fn test_pool_stats_buckets() {
+    let mut pool_stats = PoolStats::zeroed();
+    pool_stats.initialize(Pubkey::new_unique());
+
+    let start = 1_700_000_000 / POOL_STATS_DAY * POOL_STATS_DAY;
+    assert!(pool_stats.get_hourly_bucket(start).is_none());
+
+    pool_stats.record_swap(start + 10, &swap_volume(100, true), 1);
+    pool_stats.record_swap(start + POOL_STATS_HOUR - 1, &swap_volume(200, false), 2);
+
+    let bucket = pool_stats.get_hourly_bucket(start + 5).unwrap();
+    assert_eq!(bucket.start_timestamp, start);
+    assert_eq!(bucket.swap_count, 2);
+    assert_eq!(bucket.volume_a, 300);
+    assert_eq!(bucket.volume_b, 600);
+    assert_eq!(bucket.lp_fee_a, 10);
+    assert_eq!(bucket.total_fee_a, 20);
+    assert_eq!(bucket.lp_fee_b, 20);
+    assert_eq!(bucket.total_fee_b, 40);
+    // price at the end of bucket
+    assert_eq!(bucket.sqrt_price, 2);
+
+    // next hour starts a new bucket, same day accumulates
+    pool_stats.record_swap(start + POOL_STATS_HOUR, &swap_volume(100, true), 3);
+    assert_eq!(
+        pool_stats
+            .get_hourly_bucket(start + POOL_STATS_HOUR)
+            .unwrap()
+            .swap_count,
+        1
+    );
+    let daily_bucket = pool_stats.get_daily_bucket(start).unwrap();
+    assert_eq!(daily_bucket.swap_count, 3);
+    assert_eq!(daily_bucket.volume_a, 400);
+    assert_eq!(daily_bucket.sqrt_price, 3);
+
+    // buckets are overwritten after a full ring
+    let next_hourly_ring = start + POOL_STATS_HOUR * POOL_STATS_HOURLY_BUCKETS as u64;
+    pool_stats.record_swap(next_hourly_ring, &swap_volume(50, true), 4);
+    assert!(pool_stats.get_hourly_bucket(start).is_none());
+    let bucket = pool_stats.get_hourly_bucket(next_hourly_ring).unwrap();
+    assert_eq!(bucket.swap_count, 1);
+    assert_eq!(bucket.volume_a, 50);
+
+    let next_daily_ring = start + POOL_STATS_DAY * POOL_STATS_DAILY_BUCKETS as u64;
+    pool_stats.record_swap(next_daily_ring, &swap_volume(50, true), 5);
+    assert!(pool_stats.get_daily_bucket(start).is_none());
+    assert_eq!(
+        pool_stats
+            .get_daily_bucket(next_daily_ring)
+            .unwrap()
+            .swap_count,
+        1
+    );
+}
+
+#[test]
+fn test_pool_volume_metrics() {
+    let mut volume_metrics = PoolVolumeMetrics::default();
+    let start = 1_700_000_000 / ROLLING_FEE_WINDOW * ROLLING_FEE_WINDOW;
+
+    volume_metrics.record_swap(start + 10, 100, 200, 10, true);
+    volume_metrics.record_swap(start + 20, 300, 150, 5, false);
+    assert_eq!(volume_metrics.total_volume_a, 400);
+    assert_eq!(volume_metrics.total_volume_b, 350);
+    assert_eq!(volume_metrics.get_rolling_lp_fee(start + 30), (10, 5));
+
+    // fee of the previous window is weighted by its part still in the rolling window
+    volume_metrics.record_swap(start + ROLLING_FEE_WINDOW, 100, 100, 4, true);
+    assert_eq!(
+        volume_metrics.get_rolling_lp_fee(start + ROLLING_FEE_WINDOW),
+        (14, 5)
+    );
+    assert_eq!(
+        volume_metrics.get_rolling_lp_fee(start + ROLLING_FEE_WINDOW * 3 / 2),
+        (9, 2)
+    );
+
+    // previous window is dropped after a full window without swaps
+    assert_eq!(
+        volume_metrics.get_rolling_lp_fee(start + ROLLING_FEE_WINDOW * 2),
+        (4, 0)
+    );
+    assert_eq!(
+        volume_metrics.get_rolling_lp_fee(start + ROLLING_FEE_WINDOW * 3),
+        (0, 0)
+    );
+    volume_metrics.record_swap(start + ROLLING_FEE_WINDOW * 3, 100, 100, 1, false);
+    assert_eq!(volume_metrics.previous_window_lp_a_fee, 0);
+    assert_eq!(
+        volume_metrics.get_rolling_lp_fee(start + ROLLING_FEE_WINDOW * 3),
+        (0, 1)
+    );
+
+    // lifetime volume is never reset
+    assert_eq!(volume_metrics.total_volume_a, 600);
+    assert_eq!(volume_metrics.total_volume_b, 550);
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::state::{AuthorityRole, ProgramAuthority};
+
+#[test]
+fn test_transfer_authority_role() {
+    let admin = Pubkey::new_unique();
+    let new_admin = Pubkey::new_unique();
+    let transfer_delay = 3600;
+    let mut program_authority = ProgramAuthority::zeroed();
+    program_authority.initialize([admin; 4], transfer_delay);
+
+    let current_timestamp = 1_000;
+    let activation_point = program_authority
+        .transfer_role(AuthorityRole::Pauser, new_admin, current_timestamp)
+        .unwrap();
+    assert_eq!(activation_point, current_timestamp + transfer_delay);
+    // current key keeps the role until the pending key accepts it
+    assert!(program_authority.has_role(AuthorityRole::Pauser, admin));
+    assert!(program_authority
+        .accept_role(AuthorityRole::Pauser, admin, activation_point)
+        .is_err());
+    assert!(program_authority
+        .accept_role(AuthorityRole::ConfigAdmin, new_admin, activation_point)
+        .is_err());
+    // new key can't accept before transfer delay
+    assert!(program_authority
+        .accept_role(AuthorityRole::Pauser, new_admin, activation_point - 1)
+        .is_err());
+
+    program_authority
+        .accept_role(AuthorityRole::Pauser, new_admin, activation_point)
+        .unwrap();
+    assert!(program_authority.has_role(AuthorityRole::Pauser, new_admin));
+    assert!(!program_authority.has_role(AuthorityRole::Pauser, admin));
+    assert!(program_authority.has_role(AuthorityRole::ConfigAdmin, admin));
+    // pending key is cleared
+    assert!(program_authority
+        .accept_role(AuthorityRole::Pauser, new_admin, activation_point)
+        .is_err());
+}
@@ -2,8 +2,18 @@ use std::u128;
 
 use proptest::proptest;
 
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
 use crate::{
-    constants::REWARD_RATE_SCALE, state::Pool, u128x128_math::Rounding,
+    calculate_position_pending,
+    constants::{MAX_REWARD_BOOST_BPS, NUM_EXTENSION_REWARDS, NUM_REWARDS, REWARD_RATE_SCALE},
+    state::{
+        get_extension_reward_index, get_reward_info_mut, validate_emission_schedule, Config, Pool,
+        Position, PositionRewardExtension, RewardExtension, RewardInfo, RewardVestingEscrow,
+        UserRewardInfo,
+    },
+    u128x128_math::Rounding,
     utils_math::safe_shl_div_cast,
 };
 use proptest::prelude::*;
@@ -25,3 +35,346 @@ proptest! {
         assert!(expect_rate == reward_info.reward_rate)
     }
 }
+
+#[test]
+fn test_extension_reward_index() {
+    for reward_index in 0..NUM_REWARDS {
+        assert!(get_extension_reward_index(reward_index).is_err());
+    }
+    for index in 0..NUM_EXTENSION_REWARDS {
+        assert_eq!(
+            get_extension_reward_index(NUM_REWARDS + index).unwrap(),
+            index
+        );
+    }
+    assert!(get_extension_reward_index(NUM_REWARDS + NUM_EXTENSION_REWARDS).is_err());
+
+    let mut pool = Pool::default();
+    assert!(get_reward_info_mut(&mut pool, None, NUM_REWARDS).is_err());
+}
+
+#[test]
+fn test_extension_reward_accrual() {
+    let liquidity: u128 = 1 << 64;
+    let funding_amount = 1_000_000;
+    let reward_index = NUM_REWARDS;
+
+    let mut pool = Pool {
+        liquidity,
+        ..Default::default()
+    };
+    let mut reward_extension = RewardExtension::default();
+    let mut position_reward_extension = PositionRewardExtension::default();
+
+    let reward_info =
+        get_reward_info_mut(&mut pool, Some(&mut reward_extension), reward_index).unwrap();
+    reward_info.init_reward(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        PER_DAY,
+        0,
+        0,
+    );
+    reward_info
+        .update_rate_after_funding(0, funding_amount)
+        .unwrap();
+
+    // position owns the whole liquidity, so it earns all rewards once duration is over
+    reward_extension.update_rewards(liquidity, PER_DAY).unwrap();
+    position_reward_extension
+        .update_rewards(&reward_extension, liquidity)
+        .unwrap();
+    assert!(!position_reward_extension.is_empty());
+
+    let index = get_extension_reward_index(reward_index).unwrap();
+    let total_reward = position_reward_extension.claim_reward(index).unwrap();
+    assert!(total_reward <= funding_amount && total_reward >= funding_amount - 1);
+    assert!(position_reward_extension.is_empty());
+    // pool rewards are untouched
+    assert!(!pool.reward_infos[0].initialized());
+}
+
+fn decaying_reward_info(emission_period: u32, emission_decay_bps: u16) -> RewardInfo {
+    let mut reward_info = RewardInfo::default();
+    reward_info.init_reward(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        PER_DAY,
+        0,
+        0,
+    );
+    reward_info.emission_period = emission_period;
+    reward_info.emission_decay_bps = emission_decay_bps;
+    reward_info
+}
+
+#[test]
+fn test_validate_emission_schedule() {
+    assert!(validate_emission_schedule(PER_DAY, 0, 0).is_ok());
+    assert!(validate_emission_schedule(PER_DAY, 3600, 1000).is_ok());
+    assert!(validate_emission_schedule(PER_DAY, 0, 1000).is_err());
+    assert!(validate_emission_schedule(PER_DAY, 3600, 0).is_err());
+    assert!(validate_emission_schedule(PER_DAY, 3600, 10_000).is_err());
+    // too many phases
+    assert!(validate_emission_schedule(PER_DAY, 60, 1000).is_err());
+}
+
+#[test]
+fn test_decaying_emission_phases() {
+    let funding_amount = 1_000_000_000;
+    let mut reward_info = decaying_reward_info(3600, 5000);
+    reward_info
+        .update_rate_after_funding(0, funding_amount)
+        .unwrap();
+
+    // emission halves every phase
+    let first_phase = reward_info.get_emission_between(0, 3600).unwrap();
+    let second_phase = reward_info.get_emission_between(3600, 7200).unwrap();
+    assert!(second_phase <= first_phase / 2);
+    assert!(second_phase + 3600 >= first_phase / 2);
+
+    // whole reward window distributes up to funding amount
+    let total_reward = reward_info.get_emission_between(0, PER_DAY).unwrap() >> REWARD_RATE_SCALE;
+    assert!(total_reward <= funding_amount.into());
+    assert!(total_reward + 12 >= funding_amount.into());
+}
+
+#[test]
+fn test_emission_schedule_update_after_unaccrued_tail() {
+    let liquidity: u128 = 1 << 64;
+    let funding_amount = 1_000_000_000;
+    let mut reward_info = decaying_reward_info(3600, 5000);
+    reward_info
+        .update_rate_after_funding(0, funding_amount)
+        .unwrap();
+    // tail of the reward window after the last update is not accrued yet
+    reward_info.update_rewards(liquidity, PER_DAY / 2).unwrap();
+
+    let distributed = |reward_info: &RewardInfo| {
+        let mut user_reward_info = UserRewardInfo::default();
+        user_reward_info
+            .update_rewards(liquidity, reward_info.reward_per_token_stored())
+            .unwrap();
+        user_reward_info.reward_pendings
+    };
+
+    // switching decaying to flat prices the tail at reward rate of the first phase
+    let mut unaccrued_reward_info = reward_info;
+    unaccrued_reward_info.emission_period = 0;
+    unaccrued_reward_info.emission_decay_bps = 0;
+    unaccrued_reward_info
+        .update_rewards(liquidity, PER_DAY + 1)
+        .unwrap();
+    assert!(distributed(&unaccrued_reward_info) > funding_amount);
+
+    // accruing before the update distributes the tail by the old schedule
+    reward_info.update_rewards(liquidity, PER_DAY + 1).unwrap();
+    reward_info.emission_period = 0;
+    reward_info.emission_decay_bps = 0;
+    reward_info.update_rewards(liquidity, PER_DAY + 2).unwrap();
+    let distributed = distributed(&reward_info);
+    assert!(distributed <= funding_amount);
+    assert!(distributed + 12 >= funding_amount);
+}
+
+proptest! {
+    #[test]
+    fn test_decaying_emission_never_exceeds_funding(
+        funding_amount in 1..=U64_MAX,
+        emission_decay_bps in 1..10_000u16,
+        second_funding_amount in 0..=U64_MAX / 2,
+        second_funding_time in 0..PER_DAY,
+    ) {
+        let mut reward_info = decaying_reward_info(3600, emission_decay_bps);
+        reward_info.update_rate_after_funding(0, funding_amount / 2)?;
+        let distributed = reward_info.get_emission_between(0, second_funding_time)? >> REWARD_RATE_SCALE;
+
+        reward_info.update_rate_after_funding(second_funding_time, second_funding_amount)?;
+        let end = reward_info.reward_duration_end;
+        let distributed = distributed
+            + (reward_info.get_emission_between(second_funding_time, end)? >> REWARD_RATE_SCALE);
+
+        assert!(distributed <= u128::from(funding_amount / 2) + u128::from(second_funding_amount));
+    }
+}
+
+#[test]
+fn test_reward_vesting_escrow() {
+    let mut escrow = RewardVestingEscrow::zeroed();
+
+    escrow.lock(1_000, 100, 1_000).unwrap();
+    assert_eq!(escrow.end_time, 1_100);
+    assert_eq!(escrow.claim(1_000).unwrap(), 0);
+
+    // released linearly
+    assert_eq!(escrow.claim(1_025).unwrap(), 250);
+    assert_eq!(escrow.claim(1_050).unwrap(), 250);
+
+    // new rewards vest over the duration averaged with remaining locked rewards
+    escrow.lock(500, 100, 1_050).unwrap();
+    assert_eq!(escrow.locked_amount, 1_000);
+    assert_eq!(escrow.end_time, 1_050 + 75);
+
+    // everything released after end time
+    assert_eq!(escrow.claim(2_000).unwrap(), 1_000);
+    assert_eq!(escrow.locked_amount, 0);
+    assert_eq!(escrow.total_claimed_amount, 1_500);
+    assert_eq!(escrow.claim(3_000).unwrap(), 0);
+
+    // remaining duration of released escrow doesn't shorten vesting of new rewards
+    escrow.lock(100, 100, 3_000).unwrap();
+    assert_eq!(escrow.end_time, 3_100);
+}
+
+#[test]
+fn test_reward_boost() {
+    let mut config = Config::zeroed();
+    assert!(config
+        .set_reward_boost(MAX_REWARD_BOOST_BPS + 1, 0)
+        .is_err());
+    assert!(config
+        .set_reward_boost(0, MAX_REWARD_BOOST_BPS + 1)
+        .is_err());
+    config
+        .set_reward_boost(5_000, MAX_REWARD_BOOST_BPS)
+        .unwrap();
+
+    let liquidity: u128 = 1 << 64;
+    let funding_amount = 900_000;
+    let mut pool = Pool {
+        liquidity: liquidity * 3,
+        vesting_reward_boost_bps: config.vesting_reward_boost_bps,
+        permanent_lock_reward_boost_bps: config.permanent_lock_reward_boost_bps,
+        ..Default::default()
+    };
+    pool.reward_infos[0].init_reward(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        PER_DAY,
+        0,
+        0,
+    );
+    pool.reward_infos[0]
+        .update_rate_after_funding(0, funding_amount)
+        .unwrap();
+
+    let mut unlocked_position = Position::default();
+    let mut permanent_lock_position = Position::default();
+    let mut vesting_position = Position::default();
+    for position in [
+        &mut unlocked_position,
+        &mut permanent_lock_position,
+        &mut vesting_position,
+    ] {
+        position.add_liquidity(liquidity).unwrap();
+    }
+    permanent_lock_position
+        .permanent_lock_liquidity(liquidity)
+        .unwrap();
+    pool.accumulate_permanent_locked_liquidity(liquidity)
+        .unwrap();
+    vesting_position.lock(liquidity).unwrap();
+    pool.accumulate_vested_liquidity(liquidity).unwrap();
+
+    // 1x, 2x and 1.5x weight
+    assert_eq!(pool.get_reward_liquidity().unwrap(), liquidity * 9 / 2);
+    assert_eq!(
+        permanent_lock_position.get_reward_liquidity(&pool).unwrap(),
+        liquidity * 2
+    );
+
+    for position in [
+        &mut unlocked_position,
+        &mut permanent_lock_position,
+        &mut vesting_position,
+    ] {
+        position.update_rewards(&mut pool, PER_DAY).unwrap();
+    }
+    let unlocked_reward = unlocked_position.claim_reward(0).unwrap();
+    let permanent_lock_reward = permanent_lock_position.claim_reward(0).unwrap();
+    let vesting_reward = vesting_position.claim_reward(0).unwrap();
+    assert!((200_000 - 1..=200_000).contains(&unlocked_reward));
+    assert!((400_000 - 1..=400_000).contains(&permanent_lock_reward));
+    assert!((300_000 - 1..=300_000).contains(&vesting_reward));
+
+    // released liquidity loses the boost
+    vesting_position
+        .release_vested_liquidity(liquidity)
+        .unwrap();
+    pool.release_vested_liquidity(liquidity).unwrap();
+    assert_eq!(pool.get_reward_liquidity().unwrap(), liquidity * 4);
+    assert_eq!(
+        vesting_position.get_reward_liquidity(&pool).unwrap(),
+        liquidity
+    );
+
+    // vested liquidity is not tracked without vesting boost
+    pool.vesting_reward_boost_bps = 0;
+    pool.accumulate_vested_liquidity(liquidity).unwrap();
+    assert_eq!(pool.total_vested_liquidity, 0);
+}
+
+#[test]
+fn test_position_pending() {
+    let liquidity: u128 = 1 << 64;
+    let funding_amount = 1_000_000;
+
+    let mut pool = Pool {
+        liquidity,
+        ..Default::default()
+    };
+    let mut reward_extension = RewardExtension::default();
+    for reward_index in [0, NUM_REWARDS] {
+        let reward_info =
+            get_reward_info_mut(&mut pool, Some(&mut reward_extension), reward_index).unwrap();
+        reward_info.init_reward(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            PER_DAY,
+            0,
+            0,
+        );
+        reward_info
+            .update_rate_after_funding(0, funding_amount)
+            .unwrap();
+    }
+    let mut position = Position::default();
+    position.add_liquidity(liquidity).unwrap();
+    let position_reward_extension = PositionRewardExtension::default();
+
+    // pending amounts are computed on copies, so the same view is returned until accounts are updated
+    for _ in 0..2 {
+        let view = calculate_position_pending(
+            &mut pool.clone(),
+            &mut position.clone(),
+            Some((
+                &mut reward_extension.clone(),
+                &mut position_reward_extension.clone(),
+            )),
+            PER_DAY,
+        )
+        .unwrap();
+        assert_eq!(view.fee_a_pending, 0);
+        assert_eq!(view.fee_b_pending, 0);
+        for (reward_index, reward_pending) in view.reward_pendings.into_iter().enumerate() {
+            if reward_index == 0 || reward_index == NUM_REWARDS {
+                assert!((funding_amount - 1..=funding_amount).contains(&reward_pending));
+            } else {
+                assert_eq!(reward_pending, 0);
+            }
+        }
+    }
+    assert_eq!(position.reward_infos[0].reward_pendings, 0);
+
+    // pending amounts match the claimable rewards after accrual
+    let view = calculate_position_pending(&mut pool.clone(), &mut position.clone(), None, PER_DAY)
+        .unwrap();
+    position.update_rewards(&mut pool, PER_DAY).unwrap();
+    assert_eq!(view.reward_pendings[0], position.claim_reward(0).unwrap());
+    // extension rewards are not computed without reward extension accounts
+    assert_eq!(view.reward_pendings[NUM_REWARDS], 0);
+}
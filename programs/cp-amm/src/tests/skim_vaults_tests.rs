@@ -0,0 +1,73 @@
+use crate::{
+    constants::{MAX_SQRT_PRICE, MINIMUM_LIQUIDITY, MIN_SQRT_PRICE},
+    state::{ModifyLiquidityResult, Pool, Position},
+    u128x128_math::Rounding,
+};
+
+#[test]
+fn test_skim_vaults() {
+    let mut pool = Pool {
+        sqrt_price: 1u128 << 64,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        ..Default::default()
+    };
+
+    // pool upgraded by migration doesn't track owed fees
+    assert!(pool.get_excess_vault_amounts(0, 0).is_err());
+    pool.fee_tracking_flag = 1;
+
+    let mut position = Position::default();
+    let liquidity = MINIMUM_LIQUIDITY * 1_000;
+    pool.apply_add_liquidity(&mut position, liquidity).unwrap();
+    let ModifyLiquidityResult {
+        token_a_amount,
+        token_b_amount,
+    } = pool
+        .get_amounts_for_modify_liquidity(liquidity, Rounding::Up)
+        .unwrap();
+
+    // owed fees
+    pool.protocol_a_fee = 10;
+    pool.partner_b_fee = 20;
+    pool.accumulate_referral_fee(30, true).unwrap();
+    pool.metrics.total_lp_b_fee = 100;
+    pool.accumulate_lp_fee_claimed(0, 60).unwrap();
+
+    let owed_a_amount = token_a_amount + 10 + 30;
+    let owed_b_amount = token_b_amount + 20 + 40;
+    assert_eq!(
+        pool.get_excess_vault_amounts(owed_a_amount, owed_b_amount)
+            .unwrap(),
+        (0, 0)
+    );
+    // vault short of owed amounts has no excess
+    assert_eq!(
+        pool.get_excess_vault_amounts(owed_a_amount - 1, 0).unwrap(),
+        (0, 0)
+    );
+
+    // claimed referral fee leaves the vault
+    pool.claim_referral_fee(30, 0);
+    let (excess_a_amount, excess_b_amount) = pool
+        .get_excess_vault_amounts(owed_a_amount - 30 + 5_000, owed_b_amount + 7)
+        .unwrap();
+    assert_eq!((excess_a_amount, excess_b_amount), (5_000, 7));
+
+    pool.distribute_to_lp(excess_a_amount, excess_b_amount)
+        .unwrap();
+    assert_eq!(pool.metrics.total_lp_a_fee, 5_000);
+    assert_eq!(pool.metrics.total_lp_b_fee, 107);
+
+    // skimmed excess is owed to the position now
+    position
+        .update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())
+        .unwrap();
+    assert!(position.fee_a_pending <= 5_000 && position.fee_a_pending >= 4_999);
+    assert!(position.fee_b_pending <= 7 && position.fee_b_pending >= 6);
+    assert_eq!(
+        pool.get_excess_vault_amounts(owed_a_amount - 30 + 5_000, owed_b_amount + 7)
+            .unwrap(),
+        (0, 0)
+    );
+}
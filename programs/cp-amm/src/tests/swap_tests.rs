@@ -1,14 +1,30 @@
 use std::{u128, u64};
 
+use cp_amm_core::transfer_fee::TransferFee;
+
 use crate::{
-    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
-    curve::get_initialize_amounts,
-    params::swap::TradeDirection,
+    constants::{fee::MAX_FEE_NUMERATOR, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    curve::{
+        get_delta_amount_a_unsigned, get_delta_amount_b_unsigned, get_initialize_amounts,
+        get_next_sqrt_price_from_input,
+    },
+    params::swap::{
+        LaunchGuardParameters, PriceCircuitBreakerParameters, SwapRateLimiterParameters,
+        TradeDirection,
+    },
     safe_math::SafeMath,
-    state::{fee::FeeMode, Pool},
+    state::{
+        fee::{BaseFeeStruct, FeeMode, FeeOnAmountResult, PoolFeesStruct},
+        CollectFeeMode, LaunchBuyTracker, Pool, PriceCircuitBreaker, SwapRateLimiter, SwapResult,
+        TransferFeeCache,
+    },
     tests::LIQUIDITY_MAX,
+    u128x128_math::Rounding,
 };
+use bytemuck::Zeroable;
+use cp_amm_core::quote::{quote_exact_in, quote_exact_out};
 use proptest::prelude::*;
+use ruint::aliases::U256;
 
 proptest! {
     #![proptest_config(ProptestConfig {
@@ -37,7 +53,7 @@ proptest! {
             .get_swap_result(amount_in, fee_mode, trade_direction, 0)
             .unwrap();
 
-            pool.apply_swap_result(&swap_result_0, fee_mode, 0).unwrap();
+            pool.apply_swap_result(amount_in, &swap_result_0, fee_mode, trade_direction, 0).unwrap();
             // swap back
 
             let swap_result_1 = pool
@@ -73,7 +89,7 @@ proptest! {
             .get_swap_result(amount_in, fee_mode, trade_direction, 0)
             .unwrap();
 
-            pool.apply_swap_result(&swap_result_0, fee_mode, 0).unwrap();
+            pool.apply_swap_result(amount_in, &swap_result_0, fee_mode, trade_direction, 0).unwrap();
             // swap back
 
             let swap_result_1 = pool
@@ -84,6 +100,150 @@ proptest! {
         }
     }
 
+    #[test]
+    fn test_quote_exact_in_matches_reference_swap(
+        sqrt_price in MIN_SQRT_PRICE..=MAX_SQRT_PRICE,
+        amount_in in 1..=u64::MAX,
+        liquidity in 1..=LIQUIDITY_MAX,
+        cliff_fee_numerator in 0..=MAX_FEE_NUMERATOR,
+        collect_fee_mode in 0..=2u8,
+        a_to_b: bool,
+        has_referral: bool,
+    ) {
+        let pool = quote_test_pool(sqrt_price, liquidity, cliff_fee_numerator, collect_fee_mode);
+        let trade_direction = if a_to_b { TradeDirection::AtoB } else { TradeDirection::BtoA };
+        let fee_mode = &FeeMode::get_fee_mode(collect_fee_mode, trade_direction, has_referral).unwrap();
+
+        let swap_result = pool.get_swap_result(amount_in, fee_mode, trade_direction, 0);
+        let reference = get_reference_swap_result(&pool, amount_in, fee_mode, trade_direction);
+        match (swap_result, reference) {
+            (Ok(swap_result), Some(reference)) => assert_eq!(swap_result, reference),
+            (Err(_), None) => {}
+            (swap_result, reference) => panic!("{:?} {:?}", swap_result, reference),
+        }
+    }
+
+    #[test]
+    fn test_quote_exact_out_meets_amount_out(
+        sqrt_price in MIN_SQRT_PRICE..=MAX_SQRT_PRICE,
+        amount_in in 1..=u64::MAX,
+        liquidity in 1..=LIQUIDITY_MAX,
+        cliff_fee_numerator in 0..=MAX_FEE_NUMERATOR,
+        collect_fee_mode in 0..=2u8,
+        a_to_b: bool,
+    ) {
+        let pool = quote_test_pool(sqrt_price, liquidity, cliff_fee_numerator, collect_fee_mode);
+        let trade_direction = if a_to_b { TradeDirection::AtoB } else { TradeDirection::BtoA };
+        let fee_mode = &FeeMode::get_fee_mode(collect_fee_mode, trade_direction, false).unwrap();
+        let quote_params = pool.get_quote_params(fee_mode, trade_direction, 0).unwrap();
+
+        // amount out reachable by swapping amount in, quoting it back mustn't need more input
+        if let Ok(exact_in_quote) = quote_exact_in(&quote_params, amount_in) {
+            let amount_out = exact_in_quote.output_amount;
+            if amount_out > 0 {
+                let quote = quote_exact_out(&quote_params, amount_out).unwrap();
+                assert!(quote.output_amount >= amount_out);
+                assert!(quote.amount_in <= amount_in);
+                assert_eq!(quote, quote_exact_in(&quote_params, quote.amount_in).unwrap());
+            }
+        }
+    }
+}
+
+fn quote_test_pool(
+    sqrt_price: u128,
+    liquidity: u128,
+    cliff_fee_numerator: u64,
+    collect_fee_mode: u8,
+) -> Pool {
+    Pool {
+        liquidity,
+        sqrt_price,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        collect_fee_mode,
+        pool_fees: PoolFeesStruct {
+            base_fee: BaseFeeStruct {
+                cliff_fee_numerator,
+                ..Default::default()
+            },
+            protocol_fee_percent: 20,
+            partner_fee_percent: 50,
+            referral_fee_percent: 20,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Swap result computed step by step, fee on input or output then the curve
+fn get_reference_swap_result(
+    pool: &Pool,
+    amount_in: u64,
+    fee_mode: &FeeMode,
+    trade_direction: TradeDirection,
+) -> Option<SwapResult> {
+    let get_fee_on_amount = |amount| {
+        pool.pool_fees
+            .get_fee_on_amount(
+                amount,
+                fee_mode.has_referral,
+                fee_mode.fee_discount_bps,
+                0,
+                pool.activation_point,
+            )
+            .ok()
+    };
+    let input_fee = if fee_mode.fees_on_input {
+        Some(get_fee_on_amount(amount_in)?)
+    } else {
+        None
+    };
+    let actual_amount_in = input_fee.as_ref().map_or(amount_in, |fee| fee.amount);
+
+    let a_to_b = trade_direction == TradeDirection::AtoB;
+    let next_sqrt_price =
+        get_next_sqrt_price_from_input(pool.sqrt_price, pool.liquidity, actual_amount_in, a_to_b)
+            .ok()?;
+    let output_amount = if a_to_b {
+        if next_sqrt_price < pool.sqrt_min_price {
+            return None;
+        }
+        get_delta_amount_b_unsigned(
+            next_sqrt_price,
+            pool.sqrt_price,
+            pool.liquidity,
+            Rounding::Down,
+        )
+        .ok()?
+    } else {
+        if next_sqrt_price > pool.sqrt_max_price {
+            return None;
+        }
+        get_delta_amount_a_unsigned(
+            pool.sqrt_price,
+            next_sqrt_price,
+            pool.liquidity,
+            Rounding::Down,
+        )
+        .ok()?
+    };
+
+    let fee = match input_fee {
+        Some(fee) => FeeOnAmountResult {
+            amount: output_amount,
+            ..fee
+        },
+        None => get_fee_on_amount(output_amount)?,
+    };
+    Some(SwapResult {
+        output_amount: fee.amount,
+        next_sqrt_price,
+        lp_fee: fee.lp_fee,
+        protocol_fee: fee.protocol_fee,
+        partner_fee: fee.partner_fee,
+        referral_fee: fee.referral_fee,
+    })
 }
 
 // #[test]
@@ -138,7 +298,8 @@ fn test_reserve_wont_lost_when_swap_from_b_to_a_single() {
 
     println!("{:?}", swap_result_0);
 
-    pool.apply_swap_result(&swap_result_0, fee_mode, 0).unwrap();
+    pool.apply_swap_result(amount_in, &swap_result_0, fee_mode, trade_direction, 0)
+        .unwrap();
 
     let swap_result_1 = pool
         .get_swap_result(
@@ -202,7 +363,8 @@ fn test_swap_basic() {
 
     // return;
 
-    pool.apply_swap_result(&swap_result, fee_mode, 0).unwrap();
+    pool.apply_swap_result(amount_in, &swap_result, fee_mode, trade_direction, 0)
+        .unwrap();
 
     let swap_result_referse = pool
         .get_swap_result(swap_result.output_amount, fee_mode, TradeDirection::BtoA, 0)
@@ -212,6 +374,41 @@ fn test_swap_basic() {
     assert!(swap_result_referse.output_amount <= amount_in);
 }
 
+#[test]
+fn test_swap_only_a_collect_fee_in_token_a() {
+    let mut pool = Pool {
+        liquidity: LIQUIDITY_MAX,
+        sqrt_price: u64::MAX as u128,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        collect_fee_mode: CollectFeeMode::OnlyA.into(),
+        pool_fees: PoolFeesStruct {
+            base_fee: BaseFeeStruct {
+                cliff_fee_numerator: 10_000_000, // 1%
+                ..Default::default()
+            },
+            protocol_fee_percent: 20,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for trade_direction in [TradeDirection::AtoB, TradeDirection::BtoA] {
+        let fee_mode =
+            &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, false).unwrap();
+        let swap_result = pool
+            .get_swap_result(100_000_000, fee_mode, trade_direction, 0)
+            .unwrap();
+        assert!(swap_result.lp_fee > 0);
+        pool.apply_swap_result(100_000_000, &swap_result, fee_mode, trade_direction, 0)
+            .unwrap();
+    }
+
+    assert!(pool.protocol_a_fee > 0);
+    assert_eq!(pool.protocol_b_fee, 0);
+    assert_eq!(pool.fee_b_per_liquidity(), U256::ZERO);
+}
+
 #[test]
 fn test_basic_math() {
     let liquidity = LIQUIDITY_MAX;
@@ -219,3 +416,193 @@ fn test_basic_math() {
     let quote_2 = liquidity.safe_div(1.safe_shl(64).unwrap()).unwrap();
     assert_eq!(quote_1, quote_2);
 }
+
+#[test]
+fn test_swap_rate_limiter() {
+    let params = SwapRateLimiterParameters {
+        max_amount_per_window: 1_000,
+        window_duration: 10,
+        window_count: 3,
+    };
+    params.validate().unwrap();
+    let mut swap_rate_limiter = params.to_swap_rate_limiter();
+    let activation_point = 100;
+
+    // no cap before activation point, eg: whitelisted vault
+    swap_rate_limiter
+        .accumulate(activation_point, 99, 10_000)
+        .unwrap();
+
+    swap_rate_limiter
+        .accumulate(activation_point, 100, 600)
+        .unwrap();
+    swap_rate_limiter
+        .accumulate(activation_point, 109, 400)
+        .unwrap();
+    assert!(swap_rate_limiter
+        .accumulate(activation_point, 109, 1)
+        .is_err());
+
+    // cap resets in next window
+    swap_rate_limiter
+        .accumulate(activation_point, 110, 1_000)
+        .unwrap();
+    assert_eq!(swap_rate_limiter.window_start_point, 110);
+
+    // no cap after window count
+    swap_rate_limiter
+        .accumulate(activation_point, 130, 10_000)
+        .unwrap();
+
+    // disabled rate limiter never caps
+    let mut swap_rate_limiter = SwapRateLimiter::default();
+    swap_rate_limiter
+        .accumulate(activation_point, 100, u64::MAX)
+        .unwrap();
+
+    assert!(SwapRateLimiterParameters {
+        window_duration: 0,
+        ..params
+    }
+    .validate()
+    .is_err());
+}
+
+#[test]
+fn test_price_circuit_breaker() {
+    let params = PriceCircuitBreakerParameters {
+        max_sqrt_price_change_bps: 100,
+    };
+    params.validate().unwrap();
+    let mut price_circuit_breaker = params.to_price_circuit_breaker();
+    let sqrt_price = 1_000_000;
+
+    // swaps within a slot add up against the sqrt price at the start of the slot
+    price_circuit_breaker
+        .check_price_change(10, sqrt_price, 1_006_000)
+        .unwrap();
+    price_circuit_breaker
+        .check_price_change(10, 1_006_000, 1_010_000)
+        .unwrap();
+    assert!(price_circuit_breaker
+        .check_price_change(10, 1_010_000, 1_010_001)
+        .is_err());
+    // moving back within the limit is allowed
+    price_circuit_breaker
+        .check_price_change(10, 1_010_000, 990_000)
+        .unwrap();
+
+    // limit resets in next slot
+    price_circuit_breaker
+        .check_price_change(11, 1_010_000, 1_020_000)
+        .unwrap();
+    assert_eq!(price_circuit_breaker.slot_start_sqrt_price, 1_010_000);
+
+    // disabled circuit breaker never trips
+    let mut price_circuit_breaker = PriceCircuitBreaker::default();
+    price_circuit_breaker
+        .check_price_change(10, sqrt_price, MAX_SQRT_PRICE)
+        .unwrap();
+
+    assert!(PriceCircuitBreakerParameters {
+        max_sqrt_price_change_bps: 0,
+    }
+    .validate()
+    .is_err());
+    assert!(PriceCircuitBreakerParameters {
+        max_sqrt_price_change_bps: 10_000,
+    }
+    .validate()
+    .is_err());
+}
+
+#[test]
+fn test_launch_guard() {
+    let params = LaunchGuardParameters {
+        max_buy_amount_per_wallet: 1_000,
+        duration: 10,
+    };
+    params.validate().unwrap();
+    let launch_guard = params.to_launch_guard();
+    let activation_point = 100;
+
+    assert!(!launch_guard.is_active(activation_point, 99).unwrap());
+    assert!(launch_guard.is_active(activation_point, 100).unwrap());
+    assert!(launch_guard.is_active(activation_point, 109).unwrap());
+    assert!(!launch_guard.is_active(activation_point, 110).unwrap());
+
+    let mut launch_buy_tracker = LaunchBuyTracker::zeroed();
+    launch_buy_tracker
+        .accumulate_buy_amount(600, launch_guard.max_buy_amount_per_wallet)
+        .unwrap();
+    launch_buy_tracker
+        .accumulate_buy_amount(400, launch_guard.max_buy_amount_per_wallet)
+        .unwrap();
+    assert!(launch_buy_tracker
+        .accumulate_buy_amount(1, launch_guard.max_buy_amount_per_wallet)
+        .is_err());
+
+    assert!(LaunchGuardParameters {
+        duration: 0,
+        ..params
+    }
+    .validate()
+    .is_err());
+}
+
+#[test]
+fn test_oracle_cumulative_sqrt_price() {
+    let mut pool = Pool {
+        sqrt_price: 200,
+        // previous reserve amount of old pools
+        last_cumulative_sqrt_price: 12345,
+        ..Pool::zeroed()
+    };
+    assert!(pool.get_cumulative_sqrt_price(100).is_err());
+
+    // first update resets the accumulator
+    pool.update_oracle(100, 100);
+    assert_eq!(pool.last_cumulative_sqrt_price, 0);
+    assert_eq!(pool.metrics.last_oracle_timestamp, 100);
+
+    // price before swap is held for elapsed seconds
+    pool.update_oracle(200, 110);
+    assert_eq!(pool.last_cumulative_sqrt_price, 2_000);
+    // no accumulation within the same second
+    pool.update_oracle(300, 110);
+    assert_eq!(pool.last_cumulative_sqrt_price, 2_000);
+
+    assert_eq!(pool.get_cumulative_sqrt_price(110).unwrap(), 2_000);
+    assert_eq!(pool.get_cumulative_sqrt_price(120).unwrap(), 4_000);
+    assert!(pool.get_cumulative_sqrt_price(109).is_err());
+
+    // accumulator wraps on overflow, differences stay correct
+    pool.last_cumulative_sqrt_price = u128::MAX;
+    pool.update_oracle(MAX_SQRT_PRICE, 120);
+    assert_eq!(
+        pool.last_cumulative_sqrt_price.wrapping_sub(u128::MAX) / 10,
+        MAX_SQRT_PRICE
+    );
+}
+
+#[test]
+fn test_transfer_fee_cache() {
+    let mut cache = TransferFeeCache::default();
+    // nothing cached yet, including epoch 0
+    assert_eq!(cache.get(0), None);
+
+    let transfer_fee = TransferFee {
+        transfer_fee_basis_points: 100,
+        maximum_fee: 1_000,
+    };
+    cache.update(5, Some(transfer_fee));
+    assert_eq!(cache.get(5), Some(Some(transfer_fee)));
+    // refreshed when epoch advances
+    assert_eq!(cache.get(6), None);
+
+    // mint without transfer fee extension
+    cache.update(6, None);
+    assert_eq!(cache.get(6), Some(None));
+    assert_eq!(cache.maximum_fee, 0);
+    assert_eq!(cache.transfer_fee_basis_points, 0);
+}
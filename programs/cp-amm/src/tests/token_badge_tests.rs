@@ -0,0 +1,49 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::state::{TokenBadge, TokenBadgePolicy};
+
+#[test]
+fn test_token_badge_policy() {
+    // badge created before policy allows everything
+    let token_badge = TokenBadge::zeroed();
+    assert!(token_badge.is_allowed(TokenBadgePolicy::AllowTransferHook));
+    assert!(token_badge.is_allowed(TokenBadgePolicy::AllowFreezeAuthority));
+
+    let mut token_badge = TokenBadge::zeroed();
+    let policy = TokenBadgePolicy::AllowPermanentDelegate.mask()
+        | TokenBadgePolicy::AllowInterestBearing.mask();
+    token_badge
+        .initialize(Pubkey::new_unique(), policy, Pubkey::default())
+        .unwrap();
+    assert!(token_badge.is_allowed(TokenBadgePolicy::AllowPermanentDelegate));
+    assert!(token_badge.is_allowed(TokenBadgePolicy::AllowInterestBearing));
+    assert!(!token_badge.is_allowed(TokenBadgePolicy::AllowTransferHook));
+    assert!(!token_badge.is_allowed(TokenBadgePolicy::AllowFreezeAuthority));
+}
+
+#[test]
+fn test_token_badge_transfer_hook_program() {
+    let transfer_hook_program = Pubkey::new_unique();
+    let mut token_badge = TokenBadge::zeroed();
+    token_badge
+        .initialize(
+            Pubkey::new_unique(),
+            TokenBadgePolicy::AllowTransferHook.mask(),
+            transfer_hook_program,
+        )
+        .unwrap();
+    assert!(token_badge.is_transfer_hook_program_approved(transfer_hook_program));
+    // hook program swapped by the mint authority
+    assert!(!token_badge.is_transfer_hook_program_approved(Pubkey::new_unique()));
+
+    // badge created before the hook program was recorded allows every mint feature but no hook program
+    let token_badge = TokenBadge::zeroed();
+    assert!(!token_badge.is_transfer_hook_program_approved(transfer_hook_program));
+
+    let mut token_badge = TokenBadge::zeroed();
+    token_badge
+        .initialize(Pubkey::new_unique(), 0, transfer_hook_program)
+        .unwrap();
+    assert!(!token_badge.is_transfer_hook_program_approved(transfer_hook_program));
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::{
+    constants::ONE_Q64,
+    state::{TradeMining, TraderReward},
+};
+
+#[test]
+fn test_trade_mining_accrual() {
+    let mut trade_mining = TradeMining::zeroed();
+    trade_mining.initialize(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        // 2 rewards per fee in token a, 0.5 per fee in token b
+        ONE_Q64 * 2,
+        ONE_Q64 / 2,
+        0,
+        100,
+    );
+    let mut trader_reward = TraderReward::zeroed();
+
+    // nothing accrues without budget
+    assert_eq!(trade_mining.accrue(100, true, 0), 0);
+
+    trade_mining.fund(1_000).unwrap();
+    let accrued_amount = trade_mining.accrue(100, true, 0);
+    assert_eq!(accrued_amount, 200);
+    trader_reward.accumulate(accrued_amount, 100, true);
+    let accrued_amount = trade_mining.accrue(101, false, 0);
+    assert_eq!(accrued_amount, 50);
+    trader_reward.accumulate(accrued_amount, 101, false);
+    assert_eq!(trade_mining.remaining_budget, 750);
+    assert_eq!(trader_reward.total_fee_a, 100);
+    assert_eq!(trader_reward.total_fee_b, 101);
+
+    // capped by remaining budget, huge fees don't fail
+    assert_eq!(trade_mining.accrue(u64::MAX, true, 0), 750);
+    assert_eq!(trade_mining.remaining_budget, 0);
+    assert_eq!(trade_mining.total_accrued_amount, 1_000);
+
+    assert_eq!(trader_reward.claim(), 250);
+    assert_eq!(trader_reward.claim(), 0);
+    assert_eq!(trader_reward.total_claimed_amount, 250);
+}
+
+#[test]
+fn test_trade_mining_end() {
+    let mut trade_mining = TradeMining::zeroed();
+    trade_mining.initialize(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        ONE_Q64,
+        ONE_Q64,
+        0,
+        100,
+    );
+    trade_mining.fund(1_000).unwrap();
+
+    assert!(!trade_mining.is_ended(99));
+    assert_eq!(trade_mining.accrue(100, true, 99), 100);
+
+    // nothing accrues once ended, unaccrued budget is withdrawn once
+    assert!(trade_mining.is_ended(100));
+    assert_eq!(trade_mining.accrue(100, true, 100), 0);
+    assert_eq!(trade_mining.withdraw_remaining_budget(), 900);
+    assert_eq!(trade_mining.remaining_budget, 0);
+    assert_eq!(trade_mining.withdraw_remaining_budget(), 0);
+    assert_eq!(trade_mining.total_accrued_amount, 100);
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+
+use crate::state::VaultWhitelist;
+
+#[test]
+fn test_vault_whitelist() {
+    let mut vault_whitelist = VaultWhitelist::zeroed();
+    let vaults: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+    // too many vaults
+    let mut too_many_vaults = vaults.clone();
+    too_many_vaults.push(Pubkey::new_unique());
+    assert!(vault_whitelist.update_vaults(&too_many_vaults).is_err());
+    // duplicated vault
+    assert!(vault_whitelist
+        .update_vaults(&[vaults[0], vaults[1], vaults[0]])
+        .is_err());
+    // default vault
+    assert!(vault_whitelist
+        .update_vaults(&[vaults[0], Pubkey::default()])
+        .is_err());
+
+    vault_whitelist
+        .initialize(Pubkey::new_unique(), &vaults)
+        .unwrap();
+    assert!(vaults
+        .iter()
+        .all(|vault| vault_whitelist.is_whitelisted(vault)));
+    assert!(!vault_whitelist.is_whitelisted(&Pubkey::new_unique()));
+
+    // replaced vaults are no longer whitelisted, unused slots do not whitelist default pubkey
+    vault_whitelist.update_vaults(&vaults[2..]).unwrap();
+    assert!(!vault_whitelist.is_whitelisted(&vaults[0]));
+    assert!(vault_whitelist.is_whitelisted(&vaults[3]));
+    assert!(!vault_whitelist.is_whitelisted(&Pubkey::default()));
+}
@@ -0,0 +1,102 @@
+use crate::{
+    state::{Position, Vesting},
+    VestingParameters,
+};
+
+#[test]
+fn test_cliff_then_linear_vesting() {
+    let params = VestingParameters {
+        cliff_point: Some(1_000),
+        period_frequency: 100,
+        cliff_unlock_liquidity: 500,
+        liquidity_per_period: 50,
+        number_of_period: 10,
+    };
+    params.validate(0, 10_000).unwrap();
+
+    let mut vesting = Vesting::default();
+    vesting.initialize(
+        Default::default(),
+        params.get_cliff_point(0).unwrap(),
+        params.period_frequency,
+        params.cliff_unlock_liquidity,
+        params.liquidity_per_period,
+        params.number_of_period,
+    );
+    assert_eq!(vesting.get_total_lock_amount().unwrap(), 1_000);
+
+    // nothing is unlocked before cliff
+    assert_eq!(vesting.get_new_release_liquidity(999).unwrap(), 0);
+    // cliff amount is unlocked at cliff point
+    assert_eq!(vesting.get_new_release_liquidity(1_000).unwrap(), 500);
+    // then unlocked per period
+    assert_eq!(vesting.get_new_release_liquidity(1_199).unwrap(), 550);
+
+    vesting.accumulate_released_liquidity(550).unwrap();
+    assert_eq!(vesting.get_new_release_liquidity(1_250).unwrap(), 50);
+    // never unlocks more than number of periods
+    assert_eq!(vesting.get_new_release_liquidity(u64::MAX).unwrap(), 450);
+
+    vesting.accumulate_released_liquidity(450).unwrap();
+    assert!(vesting.done().unwrap());
+}
+
+#[test]
+fn test_validate_vesting_parameters() {
+    let params = VestingParameters {
+        cliff_point: None,
+        period_frequency: 100,
+        cliff_unlock_liquidity: 0,
+        liquidity_per_period: 50,
+        number_of_period: 10,
+    };
+    params.validate(0, 1_000).unwrap();
+    // exceeds max vesting duration
+    assert!(params.validate(0, 999).is_err());
+
+    // cliff point in the past
+    let params = VestingParameters {
+        cliff_point: Some(10),
+        ..params
+    };
+    assert!(params.validate(11, 10_000).is_err());
+
+    // periods without frequency
+    let params = VestingParameters {
+        cliff_point: None,
+        period_frequency: 0,
+        ..params
+    };
+    assert!(params.validate(0, 10_000).is_err());
+
+    // nothing to lock
+    let params = VestingParameters {
+        cliff_point: None,
+        period_frequency: 0,
+        cliff_unlock_liquidity: 0,
+        liquidity_per_period: 0,
+        number_of_period: 0,
+    };
+    assert!(params.validate(0, 10_000).is_err());
+}
+
+#[test]
+fn test_permanent_lock_vested_liquidity() {
+    let mut vesting = Vesting::default();
+    vesting.initialize(Default::default(), 0, 100, 500, 50, 10);
+    vesting.accumulate_released_liquidity(600).unwrap();
+    assert_eq!(vesting.get_remaining_locked_liquidity().unwrap(), 400);
+
+    let mut position = Position {
+        vested_liquidity: 400,
+        ..Default::default()
+    };
+    position
+        .permanent_lock_vested_liquidity(vesting.get_remaining_locked_liquidity().unwrap())
+        .unwrap();
+    assert_eq!(position.vested_liquidity, 0);
+    assert_eq!(position.permanent_locked_liquidity, 400);
+
+    // can't lock more than vested liquidity
+    assert!(position.permanent_lock_vested_liquidity(1).is_err());
+}
@@ -35,11 +35,15 @@ pub struct ActivationHandler {
 
 impl ActivationHandler {
     pub fn get_current_point(activation_type: u8) -> Result<u64> {
+        Self::get_current_point_from_clock(activation_type, &Clock::get()?)
+    }
+
+    pub fn get_current_point_from_clock(activation_type: u8, clock: &Clock) -> Result<u64> {
         let activation_type = ActivationType::try_from(activation_type)
             .map_err(|_| PoolError::InvalidActivationType)?;
         let current_point = match activation_type {
-            ActivationType::Slot => Clock::get()?.slot,
-            ActivationType::Timestamp => Clock::get()?.unix_timestamp as u64,
+            ActivationType::Slot => clock.slot,
+            ActivationType::Timestamp => clock.unix_timestamp as u64,
         };
         Ok(current_point)
     }
@@ -2,25 +2,26 @@ use crate::math::safe_math::SafeMath;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_instruction::transfer;
 
-use anchor_lang::{
-    prelude::InterfaceAccount,
-    solana_program::program::{invoke, invoke_signed},
-};
+use anchor_lang::{prelude::InterfaceAccount, solana_program::program::invoke};
 use anchor_spl::{
     token::Token,
     token_2022::spl_token_2022::{
         self,
         extension::{
-            self,
-            transfer_fee::{TransferFee, MAX_FEE_BASIS_POINTS},
-            BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+            self, transfer_fee::TransferFee, BaseStateWithExtensions, ExtensionType,
+            StateWithExtensions,
         },
+        onchain::invoke_transfer_checked,
     },
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
+use cp_amm_core::transfer_fee;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use crate::{state::TokenBadge, PoolError};
+use crate::{
+    state::{TokenBadge, TokenBadgePolicy, TransferFeeCache},
+    PoolError,
+};
 
 #[derive(
     AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive,
@@ -31,6 +32,66 @@ pub enum TokenProgramFlags {
     TokenProgram2022,
 }
 
+/// bit of token program in token flag of pool
+pub const TOKEN_PROGRAM_FLAG_MASK: u8 = 0b1;
+
+/// Rug-capable mint extensions found at pool initialization, bit index in token flag of pool.
+/// Mints with these extensions are not supported permissionlessly, pool initialization requires a token badge
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum MintExtensionFinding {
+    PermanentDelegate = 1,
+    DefaultAccountStateFrozen,
+    MintCloseAuthority,
+}
+
+impl MintExtensionFinding {
+    pub fn mask(&self) -> u8 {
+        1 << u8::from(*self)
+    }
+}
+
+/// Audit extensions of a Token-2022 mint, returns bits of MintExtensionFinding.
+/// Pausable mints have no finding, they are rejected by `is_supported_mint`
+pub fn get_mint_extension_findings(token_mint: &InterfaceAccount<Mint>) -> Result<u8> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(0);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    let mut findings = 0;
+    if let Ok(permanent_delegate) =
+        mint.get_extension::<extension::permanent_delegate::PermanentDelegate>()
+    {
+        if Option::<Pubkey>::from(permanent_delegate.delegate).is_some() {
+            findings |= MintExtensionFinding::PermanentDelegate.mask();
+        }
+    }
+    if let Ok(default_account_state) =
+        mint.get_extension::<extension::default_account_state::DefaultAccountState>()
+    {
+        if default_account_state.state == spl_token_2022::state::AccountState::Frozen as u8 {
+            findings |= MintExtensionFinding::DefaultAccountStateFrozen.mask();
+        }
+    }
+    if let Ok(mint_close_authority) =
+        mint.get_extension::<extension::mint_close_authority::MintCloseAuthority>()
+    {
+        if Option::<Pubkey>::from(mint_close_authority.close_authority).is_some() {
+            findings |= MintExtensionFinding::MintCloseAuthority.mask();
+        }
+    }
+    Ok(findings)
+}
+
+/// Token flag of pool, token program bit with mint extension findings
+pub fn get_pool_token_flag(token_mint: &InterfaceAccount<Mint>) -> Result<u8> {
+    let token_program_flag: u8 = get_token_program_flags(token_mint).into();
+    Ok(token_program_flag | get_mint_extension_findings(token_mint)?)
+}
+
 pub fn get_token_program_flags<'a, 'info>(
     token_mint: &'a InterfaceAccount<'info, Mint>,
 ) -> TokenProgramFlags {
@@ -43,45 +104,26 @@ pub fn get_token_program_flags<'a, 'info>(
     }
 }
 
-/// refer code from Orca
-#[derive(Debug)]
-pub struct TransferFeeIncludedAmount {
-    pub amount: u64,
-    pub transfer_fee: u64,
-}
-
-#[derive(Debug)]
-pub struct TransferFeeExcludedAmount {
-    pub amount: u64,
-    pub transfer_fee: u64,
-}
+pub use cp_amm_core::transfer_fee::{TransferFeeExcludedAmount, TransferFeeIncludedAmount};
 
 pub fn calculate_transfer_fee_excluded_amount<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
     transfer_fee_included_amount: u64,
+    current_epoch: u64,
 ) -> Result<TransferFeeExcludedAmount> {
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        let transfer_fee = epoch_transfer_fee
-            .calculate_fee(transfer_fee_included_amount)
-            .ok_or_else(|| PoolError::MathOverflow)?;
-        let transfer_fee_excluded_amount = transfer_fee_included_amount
-            .checked_sub(transfer_fee)
-            .ok_or_else(|| PoolError::MathOverflow)?;
-        return Ok(TransferFeeExcludedAmount {
-            amount: transfer_fee_excluded_amount,
-            transfer_fee,
-        });
-    }
-
-    Ok(TransferFeeExcludedAmount {
-        amount: transfer_fee_included_amount,
-        transfer_fee: 0,
-    })
+    let epoch_transfer_fee =
+        get_epoch_transfer_fee(token_mint, current_epoch)?.map(to_core_transfer_fee);
+    Ok(transfer_fee::calculate_transfer_fee_excluded_amount(
+        epoch_transfer_fee.as_ref(),
+        transfer_fee_included_amount,
+    )
+    .map_err(PoolError::from)?)
 }
 
 pub fn calculate_transfer_fee_included_amount<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
     transfer_fee_excluded_amount: u64,
+    current_epoch: u64,
 ) -> Result<TransferFeeIncludedAmount> {
     if transfer_fee_excluded_amount == 0 {
         return Ok(TransferFeeIncludedAmount {
@@ -90,48 +132,84 @@ pub fn calculate_transfer_fee_included_amount<'info>(
         });
     }
 
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        let transfer_fee: u64 =
-            if u16::from(epoch_transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
-                // edge-case: if transfer fee rate is 100%, current SPL implementation returns 0 as inverse fee.
-                // https://github.com/solana-labs/solana-program-library/blob/fe1ac9a2c4e5d85962b78c3fc6aaf028461e9026/token/program-2022/src/extension/transfer_fee/mod.rs#L95
-
-                // But even if transfer fee is 100%, we can use maximum_fee as transfer fee.
-                // if transfer_fee_excluded_amount + maximum_fee > u64 max, the following checked_add should fail.
-                u64::from(epoch_transfer_fee.maximum_fee)
-            } else {
-                epoch_transfer_fee
-                    .calculate_inverse_fee(transfer_fee_excluded_amount)
-                    .ok_or(PoolError::MathOverflow)?
-            };
-
-        let transfer_fee_included_amount = transfer_fee_excluded_amount
-            .checked_add(transfer_fee)
-            .ok_or(PoolError::MathOverflow)?;
-
-        // verify transfer fee calculation for safety
-        let transfer_fee_verification = epoch_transfer_fee
-            .calculate_fee(transfer_fee_included_amount)
-            .unwrap();
-        if transfer_fee != transfer_fee_verification {
-            // We believe this should never happen
-            return Err(PoolError::FeeInverseIsIncorrect.into());
-        }
+    let epoch_transfer_fee =
+        get_epoch_transfer_fee(token_mint, current_epoch)?.map(to_core_transfer_fee);
+    Ok(transfer_fee::calculate_transfer_fee_included_amount(
+        epoch_transfer_fee.as_ref(),
+        transfer_fee_excluded_amount,
+    )
+    .map_err(PoolError::from)?)
+}
+
+/// Same as `calculate_transfer_fee_excluded_amount`, with epoch transfer fee read from the transfer fee cache of pool
+pub fn calculate_transfer_fee_excluded_amount_cached<'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    transfer_fee_cache: &mut TransferFeeCache,
+    transfer_fee_included_amount: u64,
+    current_epoch: u64,
+) -> Result<TransferFeeExcludedAmount> {
+    let epoch_transfer_fee =
+        get_cached_epoch_transfer_fee(token_mint, transfer_fee_cache, current_epoch)?;
+    Ok(transfer_fee::calculate_transfer_fee_excluded_amount(
+        epoch_transfer_fee.as_ref(),
+        transfer_fee_included_amount,
+    )
+    .map_err(PoolError::from)?)
+}
 
+/// Same as `calculate_transfer_fee_included_amount`, with epoch transfer fee read from the transfer fee cache of pool
+pub fn calculate_transfer_fee_included_amount_cached<'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    transfer_fee_cache: &mut TransferFeeCache,
+    transfer_fee_excluded_amount: u64,
+    current_epoch: u64,
+) -> Result<TransferFeeIncludedAmount> {
+    if transfer_fee_excluded_amount == 0 {
         return Ok(TransferFeeIncludedAmount {
-            amount: transfer_fee_included_amount,
-            transfer_fee,
+            amount: 0,
+            transfer_fee: 0,
         });
     }
 
-    Ok(TransferFeeIncludedAmount {
-        amount: transfer_fee_excluded_amount,
-        transfer_fee: 0,
-    })
+    let epoch_transfer_fee =
+        get_cached_epoch_transfer_fee(token_mint, transfer_fee_cache, current_epoch)?;
+    Ok(transfer_fee::calculate_transfer_fee_included_amount(
+        epoch_transfer_fee.as_ref(),
+        transfer_fee_excluded_amount,
+    )
+    .map_err(PoolError::from)?)
+}
+
+/// Epoch transfer fee of a pool token mint, token 2022 mint extensions are only unpacked
+/// to refresh the cache when the epoch advanced
+fn get_cached_epoch_transfer_fee<'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    transfer_fee_cache: &mut TransferFeeCache,
+    current_epoch: u64,
+) -> Result<Option<transfer_fee::TransferFee>> {
+    if *token_mint.to_account_info().owner == Token::id() {
+        return Ok(None);
+    }
+
+    if let Some(epoch_transfer_fee) = transfer_fee_cache.get(current_epoch) {
+        return Ok(epoch_transfer_fee);
+    }
+    let epoch_transfer_fee =
+        get_epoch_transfer_fee(token_mint, current_epoch)?.map(to_core_transfer_fee);
+    transfer_fee_cache.update(current_epoch, epoch_transfer_fee);
+    Ok(epoch_transfer_fee)
+}
+
+fn to_core_transfer_fee(epoch_transfer_fee: TransferFee) -> transfer_fee::TransferFee {
+    transfer_fee::TransferFee {
+        transfer_fee_basis_points: epoch_transfer_fee.transfer_fee_basis_points.into(),
+        maximum_fee: epoch_transfer_fee.maximum_fee.into(),
+    }
 }
 
 pub fn get_epoch_transfer_fee<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
+    current_epoch: u64,
 ) -> Result<Option<TransferFee>> {
     let token_mint_info = token_mint.to_account_info();
     if *token_mint_info.owner == Token::id() {
@@ -144,13 +222,58 @@ pub fn get_epoch_transfer_fee<'info>(
     if let Ok(transfer_fee_config) =
         token_mint_unpacked.get_extension::<extension::transfer_fee::TransferFeeConfig>()
     {
-        let epoch = Clock::get()?.epoch;
-        return Ok(Some(transfer_fee_config.get_epoch_fee(epoch).clone()));
+        return Ok(Some(
+            transfer_fee_config.get_epoch_fee(current_epoch).clone(),
+        ));
     }
 
     Ok(None)
 }
 
+/// Transfer hook program of a Token-2022 mint, if any
+pub fn get_transfer_hook_program(token_mint: &InterfaceAccount<Mint>) -> Result<Option<Pubkey>> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(None);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    Ok(extension::transfer_hook::get_program_id(&mint))
+}
+
+/// Transfer hook program of the mint must be approved by the token badge of the mint, passed in `remaining_accounts`
+/// with extra accounts of the hook, so a hook program swapped by the mint authority is never invoked
+fn require_approved_transfer_hook_program(
+    token_mint: &InterfaceAccount<Mint>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let Some(transfer_hook_program) = get_transfer_hook_program(token_mint)? else {
+        return Ok(());
+    };
+    for account in remaining_accounts {
+        if account.owner != &crate::ID {
+            continue;
+        }
+        let data = account.try_borrow_data()?;
+        if !data.starts_with(TokenBadge::DISCRIMINATOR) {
+            continue;
+        }
+        let token_badge: &TokenBadge = bytemuck::try_from_bytes(
+            data.get(8..8 + TokenBadge::INIT_SPACE)
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )
+        .map_err(|_| PoolError::InvalidTokenBadge)?;
+        if token_badge.token_mint == token_mint.key()
+            && token_badge.is_transfer_hook_program_approved(transfer_hook_program)
+        {
+            return Ok(());
+        }
+    }
+    Err(PoolError::UnapprovedTransferHookProgram.into())
+}
+
+/// Extra accounts required by transfer hook of the mint are looked up in `remaining_accounts`
 pub fn transfer_from_user<'a, 'c: 'info, 'info>(
     authority: &'a Signer<'info>,
     token_mint: &'a InterfaceAccount<'info, Mint>,
@@ -158,32 +281,26 @@ pub fn transfer_from_user<'a, 'c: 'info, 'info>(
     destination_token_account: &'a InterfaceAccount<'info, TokenAccount>,
     token_program: &'a Interface<'info, TokenInterface>,
     amount: u64,
+    remaining_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
-    let destination_account = destination_token_account.to_account_info();
+    require_approved_transfer_hook_program(token_mint, remaining_accounts)?;
 
-    let instruction = spl_token_2022::instruction::transfer_checked(
+    invoke_transfer_checked(
         token_program.key,
-        &token_owner_account.key(),
-        &token_mint.key(),
-        destination_account.key,
-        authority.key,
-        &[],
-        amount,
-        token_mint.decimals,
-    )?;
-
-    let account_infos = vec![
         token_owner_account.to_account_info(),
         token_mint.to_account_info(),
-        destination_account.to_account_info(),
+        destination_token_account.to_account_info(),
         authority.to_account_info(),
-    ];
-
-    invoke_signed(&instruction, &account_infos, &[])?;
+        remaining_accounts,
+        amount,
+        token_mint.decimals,
+        &[],
+    )?;
 
     Ok(())
 }
 
+/// Extra accounts required by transfer hook of the mint are looked up in `remaining_accounts`
 pub fn transfer_from_pool<'c: 'info, 'info>(
     pool_authority: AccountInfo<'info>,
     token_mint: &InterfaceAccount<'info, Mint>,
@@ -192,32 +309,87 @@ pub fn transfer_from_pool<'c: 'info, 'info>(
     token_program: &Interface<'info, TokenInterface>,
     amount: u64,
     bump: u8,
+    remaining_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
+    require_approved_transfer_hook_program(token_mint, remaining_accounts)?;
+
     let signer_seeds = pool_authority_seeds!(bump);
 
-    let instruction = spl_token_2022::instruction::transfer_checked(
+    invoke_transfer_checked(
         token_program.key,
-        &token_vault.key(),
-        &token_mint.key(),
-        &token_owner_account.key(),
-        &pool_authority.key(),
-        &[],
+        token_vault.to_account_info(),
+        token_mint.to_account_info(),
+        token_owner_account.to_account_info(),
+        pool_authority,
+        remaining_accounts,
         amount,
         token_mint.decimals,
+        &[&signer_seeds[..]],
     )?;
 
-    let account_infos = vec![
-        token_vault.to_account_info(),
-        token_mint.to_account_info(),
-        token_owner_account.to_account_info(),
-        pool_authority.to_account_info(),
-    ];
+    Ok(())
+}
 
-    invoke_signed(&instruction, &account_infos, &[&signer_seeds[..]])?;
+/// Amount of token account read from account data, so it includes transfers made earlier in the instruction
+pub fn get_token_account_amount(token_account: &InterfaceAccount<TokenAccount>) -> Result<u64> {
+    anchor_spl::token::accessor::amount(&token_account.to_account_info())
+}
 
+/// Token creator with freeze authority can freeze pool vaults, every transfer of a frozen vault fails
+pub fn require_vaults_not_frozen(
+    token_a_vault: &InterfaceAccount<TokenAccount>,
+    token_b_vault: &InterfaceAccount<TokenAccount>,
+) -> Result<()> {
+    require!(
+        !token_a_vault.is_frozen() && !token_b_vault.is_frozen(),
+        PoolError::VaultFrozen
+    );
     Ok(())
 }
 
+/// Close an empty token account owned by pool authority
+pub fn close_pool_token_account<'info>(
+    pool_authority: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    bump: u8,
+) -> Result<()> {
+    let signer_seeds = pool_authority_seeds!(bump);
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program,
+        token_interface::CloseAccount {
+            account: token_account,
+            destination,
+            authority: pool_authority,
+        },
+        &[&signer_seeds[..]],
+    ))
+}
+
+/// Extension type number of the Token-2022 Pausable mint extension, not known by the spl-token-2022 version in use
+pub const PAUSABLE_EXTENSION_TYPE: u16 = 26;
+
+/// Whether raw TLV data of a Token-2022 account has an extension type, walked by hand so extension types
+/// unknown to spl-token-2022 are found too
+pub fn has_tlv_extension_type(tlv_data: &[u8], extension_type: u16) -> bool {
+    let mut start_index: usize = 0;
+    while let Some(entry) = tlv_data.get(start_index..start_index.saturating_add(4)) {
+        let entry_type = u16::from_le_bytes([entry[0], entry[1]]);
+        if entry_type == 0 {
+            return false;
+        }
+        if entry_type == extension_type {
+            return true;
+        }
+        let length = u16::from_le_bytes([entry[2], entry[3]]);
+        start_index = start_index.saturating_add(4).saturating_add(length.into());
+    }
+    false
+}
+
+/// Pausable mints are rejected even with a token badge, pausing transfers would freeze pool and reward vaults
 pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
@@ -230,25 +402,72 @@ pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool>
 
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    require!(
+        !has_tlv_extension_type(mint.get_tlv_data(), PAUSABLE_EXTENSION_TYPE),
+        PoolError::UnsupportedPausableMint
+    );
     let extensions = mint.get_extension_types()?;
     for e in extensions {
-        if e != ExtensionType::TransferFeeConfig
-            && e != ExtensionType::MetadataPointer
-            && e != ExtensionType::TokenMetadata
-        {
-            return Ok(false);
+        match e {
+            ExtensionType::TransferFeeConfig
+            | ExtensionType::MetadataPointer
+            | ExtensionType::TokenMetadata => {}
+            // interest only scales ui amount, pool works on raw amount. Mint that can change its rate needs a token badge
+            ExtensionType::InterestBearingConfig => {
+                let interest_bearing_config =
+                    mint.get_extension::<extension::interest_bearing_mint::InterestBearingConfig>(
+                    )?;
+                if Option::<Pubkey>::from(interest_bearing_config.rate_authority).is_some() {
+                    return Ok(false);
+                }
+            }
+            _ => return Ok(false),
         }
     }
     Ok(true)
 }
 
-pub fn is_token_badge_initialized<'c: 'info, 'info>(
-    mint: Pubkey,
+/// Token badge is checked at the time of use, so a revoked (closed) badge is never accepted.
+/// Freeze authority and extensions gated by token badge policy must be allowed by the badge
+pub fn is_mint_allowed_by_token_badge<'c: 'info, 'info>(
+    mint_account: &InterfaceAccount<Mint>,
     token_badge: &'c AccountInfo<'info>,
 ) -> Result<bool> {
+    if token_badge.owner != &crate::ID || token_badge.data_is_empty() {
+        return Ok(false);
+    }
     let token_badge: AccountLoader<'_, TokenBadge> = AccountLoader::try_from(token_badge)?;
     let token_badge = token_badge.load()?;
-    Ok(token_badge.token_mint == mint)
+    if token_badge.token_mint != mint_account.key() {
+        return Ok(false);
+    }
+
+    let mint_info = mint_account.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    if mint.base.freeze_authority.is_some()
+        && !token_badge.is_allowed(TokenBadgePolicy::AllowFreezeAuthority)
+    {
+        return Ok(false);
+    }
+    if let Some(transfer_hook_program) = extension::transfer_hook::get_program_id(&mint) {
+        if !token_badge.is_transfer_hook_program_approved(transfer_hook_program) {
+            return Ok(false);
+        }
+    }
+    let extensions = mint.get_extension_types()?;
+    for e in extensions {
+        let policy = match e {
+            ExtensionType::TransferHook => TokenBadgePolicy::AllowTransferHook,
+            ExtensionType::PermanentDelegate => TokenBadgePolicy::AllowPermanentDelegate,
+            ExtensionType::InterestBearingConfig => TokenBadgePolicy::AllowInterestBearing,
+            _ => continue,
+        };
+        if !token_badge.is_allowed(policy) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
 pub fn update_account_lamports_to_minimum_balance<'info>(
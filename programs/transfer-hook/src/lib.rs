@@ -0,0 +1,30 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("EBZDYx7599krFc4m2govwBdZcicr4GgepqC78m71nsHS");
+
+/// Transfer hook without extra accounts, for testing transfers of transfer hook mints
+#[program]
+pub mod transfer_hook {
+    use super::*;
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(_ctx: Context<TransferHookCtx>, _amount: u64) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TransferHookCtx<'info> {
+    /// CHECK: source token account
+    pub source_token: UncheckedAccount<'info>,
+    /// CHECK: token mint
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: destination token account
+    pub destination_token: UncheckedAccount<'info>,
+    /// CHECK: owner or delegate of source token account
+    pub owner: UncheckedAccount<'info>,
+}
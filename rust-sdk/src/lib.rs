@@ -1 +1,3 @@
+pub mod price;
 pub mod quote;
+pub mod ui_amount;
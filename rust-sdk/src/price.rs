@@ -0,0 +1,60 @@
+use anyhow::{ensure, Context, Result};
+use cp_amm::u128x128_math::Rounding;
+use cp_amm_core::price_math::{get_price_from_sqrt_price, get_sqrt_price_from_price};
+
+/// Sqrt price in Q64.64 of a ui price of 1 token a in token b, e.g. "1.5". Pool initialization
+/// with this sqrt price quotes the ui price after adjusting for decimals of both mints
+pub fn ui_price_to_sqrt_price(
+    ui_price: &str,
+    decimals_a: u8,
+    decimals_b: u8,
+    rounding: Rounding,
+) -> Result<u128> {
+    let (integer, fraction) = ui_price.split_once('.').unwrap_or((ui_price, ""));
+    ensure!(
+        !(integer.is_empty() && fraction.is_empty())
+            && integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit()),
+        "invalid ui price {ui_price}"
+    );
+    let price_scale = u8::try_from(fraction.len()).context("too many decimals in ui price")?;
+    let price: u128 = format!("{integer}{fraction}")
+        .parse()
+        .context("ui price overflow")?;
+    ensure!(price > 0, "ui price is zero");
+
+    Ok(get_sqrt_price_from_price(
+        price,
+        price_scale,
+        decimals_a,
+        decimals_b,
+        rounding,
+    )?)
+}
+
+/// Ui price of 1 token a in token b of a sqrt price in Q64.64, rounded down to `price_scale`
+/// decimals with trailing zeros trimmed
+pub fn sqrt_price_to_ui_price(
+    sqrt_price: u128,
+    decimals_a: u8,
+    decimals_b: u8,
+    price_scale: u8,
+) -> Result<String> {
+    let price = get_price_from_sqrt_price(
+        sqrt_price,
+        price_scale,
+        decimals_a,
+        decimals_b,
+        Rounding::Down,
+    )?;
+    let price = format!("{price:0>width$}", width = usize::from(price_scale) + 1);
+    let (integer, fraction) = price.split_at(price.len() - usize::from(price_scale));
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        Ok(integer.to_string())
+    } else {
+        Ok(format!("{integer}.{fraction}"))
+    }
+}
@@ -13,31 +13,41 @@ pub fn get_quote(
     a_to_b: bool,
     has_referral: bool,
 ) -> Result<SwapResult> {
-    ensure!(actual_amount_in > 0, "amount is zero");
+    get_quote_with_fee_discount(
+        pool,
+        current_timestamp,
+        current_slot,
+        actual_amount_in,
+        a_to_b,
+        has_referral,
+        0,
+    )
+}
 
-    let result = if pool.pool_fees.dynamic_fee.is_dynamic_fee_enable() {
-        let mut pool = *pool;
-        pool.update_pre_swap(current_timestamp)?;
-        get_internal_quote(
-            &pool,
-            current_timestamp,
-            current_slot,
-            actual_amount_in,
-            a_to_b,
-            has_referral,
-        )
-    } else {
-        get_internal_quote(
-            pool,
-            current_timestamp,
-            current_slot,
-            actual_amount_in,
-            a_to_b,
-            has_referral,
-        )
-    };
+/// Quote for a trader getting `fee_discount_bps` trade fee discount from the fee discount registry of pool
+pub fn get_quote_with_fee_discount(
+    pool: &Pool,
+    current_timestamp: u64,
+    current_slot: u64,
+    actual_amount_in: u64,
+    a_to_b: bool,
+    has_referral: bool,
+    fee_discount_bps: u16,
+) -> Result<SwapResult> {
+    ensure!(actual_amount_in > 0, "amount is zero");
 
-    result
+    // pending fee updates take effect like in the swap, volatility is only tracked with dynamic fee enabled
+    let mut pool = *pool;
+    pool.update_pre_swap(current_timestamp)?;
+    get_internal_quote(
+        &pool,
+        current_timestamp,
+        current_slot,
+        actual_amount_in,
+        a_to_b,
+        has_referral,
+        fee_discount_bps,
+    )
 }
 
 fn get_internal_quote(
@@ -47,6 +57,7 @@ fn get_internal_quote(
     actual_amount_in: u64,
     a_to_b: bool,
     has_referral: bool,
+    fee_discount_bps: u16,
 ) -> Result<SwapResult> {
     let activation_type =
         ActivationType::try_from(pool.activation_type).context("invalid activation type")?;
@@ -62,10 +73,25 @@ fn get_internal_quote(
         TradeDirection::BtoA
     };
 
-    let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, has_referral)?;
+    // buys over the cap of current window fail on-chain
+    if trade_direction == TradeDirection::BtoA {
+        let mut swap_rate_limiter = pool.swap_rate_limiter;
+        swap_rate_limiter.accumulate(pool.activation_point, current_point, actual_amount_in)?;
+    }
+
+    let fee_mode = &FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, has_referral)?
+        .with_fee_discount(fee_discount_bps);
 
     let swap_result =
         pool.get_swap_result(actual_amount_in, fee_mode, trade_direction, current_point)?;
 
+    // swaps moving price over the cap of current slot fail on-chain
+    let mut price_circuit_breaker = pool.price_circuit_breaker;
+    price_circuit_breaker.check_price_change(
+        current_slot,
+        pool.sqrt_price,
+        swap_result.next_sqrt_price,
+    )?;
+
     Ok(swap_result)
 }
@@ -0,0 +1,50 @@
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+use anyhow::{Context, Result};
+
+/// Converts between raw and ui amounts of a mint, pool and quotes work on raw amounts.
+/// Ui amount of an interest-bearing mint includes the interest accrued at the given timestamp
+#[derive(Clone, Copy, Debug)]
+pub struct MintUiAmount {
+    decimals: u8,
+    interest_bearing_config: Option<InterestBearingConfig>,
+}
+
+impl MintUiAmount {
+    pub fn from_mint_data(data: &[u8]) -> Result<Self> {
+        let mint = StateWithExtensions::<Mint>::unpack(data)?;
+        Ok(Self {
+            decimals: mint.base.decimals,
+            interest_bearing_config: mint.get_extension::<InterestBearingConfig>().ok().copied(),
+        })
+    }
+
+    pub fn amount_to_ui_amount(&self, amount: u64, unix_timestamp: i64) -> Result<String> {
+        match &self.interest_bearing_config {
+            Some(interest_bearing_config) => interest_bearing_config
+                .amount_to_ui_amount(amount, self.decimals, unix_timestamp)
+                .context("interest overflow"),
+            None => Ok(spl_token_2022::amount_to_ui_amount_string_trimmed(
+                amount,
+                self.decimals,
+            )),
+        }
+    }
+
+    pub fn ui_amount_to_amount(&self, ui_amount: &str, unix_timestamp: i64) -> Result<u64> {
+        let amount = match &self.interest_bearing_config {
+            Some(interest_bearing_config) => interest_bearing_config.try_ui_amount_into_amount(
+                ui_amount,
+                self.decimals,
+                unix_timestamp,
+            ),
+            None => spl_token_2022::try_ui_amount_into_amount(ui_amount.to_string(), self.decimals),
+        };
+        amount.context("invalid ui amount")
+    }
+}
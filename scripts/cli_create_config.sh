@@ -1,4 +1,5 @@
 cluster=localnet
+index=0
 sqrt_max_price=79226673521066979257578248091
 sqrt_min_price=4295048016
 vault_config_key=11111111111111111111111111111111
@@ -9,5 +10,11 @@ trade_fee_numerator=2500000
 protocol_fee_percent=0
 partner_fee_percent=0
 referral_fee_percent=0
+# dynamic fee, remove --filter-period and following flags to create config without dynamic fee
+filter_period=10
+decay_period=120
+reduction_factor=5000
+variable_fee_control=2000000
+max_volatility_accumulator=100000
 
-target/debug/cli --provider.cluster $cluster create-config  --sqrt-min-price $sqrt_min_price --sqrt-max-price $sqrt_max_price --vault-config-key $vault_config_key --pool-creator-authority $pool_creator_authority --activation-type $activation_type --collect-fee-mode $collect_fee_node --trade-fee-numerator $trade_fee_numerator --protocol-fee-percent $protocol_fee_percent --partner-fee-percent $partner_fee_percent --referral-fee-percent $referral_fee_percent
\ No newline at end of file
+target/debug/cli --provider.cluster $cluster create-config --index $index --sqrt-min-price $sqrt_min_price --sqrt-max-price $sqrt_max_price --vault-config-key $vault_config_key --pool-creator-authority $pool_creator_authority --activation-type $activation_type --collect-fee-mode $collect_fee_node --trade-fee-numerator $trade_fee_numerator --protocol-fee-percent $protocol_fee_percent --partner-fee-percent $partner_fee_percent --referral-fee-percent $referral_fee_percent --filter-period $filter_period --decay-period $decay_period --reduction-factor $reduction_factor --variable-fee-control $variable_fee_control --max-volatility-accumulator $max_volatility_accumulator
\ No newline at end of file
@@ -4,6 +4,7 @@ import { generateKpAndFund, randomID, startTest } from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   addLiquidity,
+  addLiquidityByAmounts,
   AddLiquidityParams,
   createConfigIx,
   CreateConfigParams,
@@ -14,9 +15,11 @@ import {
   MAX_SQRT_PRICE,
   MIN_SQRT_PRICE,
   getPool,
+  getPosition,
   U64_MAX,
   createToken,
   mintSplTokenTo,
+  initializeProgramAuthority,
 } from "./bankrun-utils";
 import BN from "bn.js";
 import { AccountLayout, ExtensionType } from "@solana/spl-token";
@@ -40,6 +43,7 @@ describe("Add liquidity", () => {
 
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken(
@@ -250,6 +254,72 @@ describe("Add liquidity", () => {
 
       expect(preTokenAVaultBalance).eq(postTokenAVaultBalance);
     });
+
+    it("Add liquidity by token amounts", async () => {
+      const initPoolParams: InitializePoolParams = {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint: tokenAMint,
+        tokenBMint: tokenBMint,
+        liquidity: MIN_LP_AMOUNT,
+        sqrtPrice: MIN_SQRT_PRICE.muln(2),
+        activationPoint: null,
+      };
+
+      const result = await initializePool(context.banksClient, initPoolParams);
+
+      pool = result.pool;
+      position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+
+      const poolState = await getPool(context.banksClient, pool);
+
+      const preTokenAVaultBalance = new BN(
+        AccountLayout.decode(
+          (await context.banksClient.getAccount(poolState.tokenAVault)).data
+        ).amount.toString()
+      );
+      const preTokenBVaultBalance = new BN(
+        AccountLayout.decode(
+          (await context.banksClient.getAccount(poolState.tokenBVault)).data
+        ).amount.toString()
+      );
+
+      const tokenAAmount = new BN(1_000_000);
+      const tokenBAmount = new BN(1_000_000);
+      await addLiquidityByAmounts(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        tokenAAmount,
+        tokenBAmount,
+        liquidityDeltaThreshold: new BN(1),
+      });
+
+      const postTokenAVaultBalance = new BN(
+        AccountLayout.decode(
+          (await context.banksClient.getAccount(poolState.tokenAVault)).data
+        ).amount.toString()
+      );
+      const postTokenBVaultBalance = new BN(
+        AccountLayout.decode(
+          (await context.banksClient.getAccount(poolState.tokenBVault)).data
+        ).amount.toString()
+      );
+
+      const depositedA = postTokenAVaultBalance.sub(preTokenAVaultBalance);
+      const depositedB = postTokenBVaultBalance.sub(preTokenBVaultBalance);
+      expect(depositedA.lte(tokenAAmount)).to.be.true;
+      expect(depositedB.lte(tokenBAmount)).to.be.true;
+
+      const positionState = await getPosition(context.banksClient, position);
+      expect(positionState.unlockedLiquidity.gtn(0)).to.be.true;
+    });
   });
 
   describe("Token 2022", () => {
@@ -269,6 +339,7 @@ describe("Add liquidity", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken2022(
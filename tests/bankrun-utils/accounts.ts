@@ -60,6 +60,22 @@ export function deriveRewardVaultAddress(
   )[0];
 }
 
+export function deriveRewardExtensionAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_extension"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function derivePositionRewardExtensionAddress(
+  position: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("position_reward_extension"), position.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
 export function deriveCustomizablePoolAddress(
   tokenAMint: PublicKey,
   tokenBMint: PublicKey
@@ -81,6 +97,13 @@ export function deriveTokenBadgeAddress(tokenMint: PublicKey): PublicKey {
   )[0];
 }
 
+export function deriveProgramAuthorityAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("program_authority")],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
 export function deriveClaimFeeOperatorAddress(operator: PublicKey): PublicKey {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("cf_operator"), operator.toBuffer()],
@@ -88,6 +111,162 @@ export function deriveClaimFeeOperatorAddress(operator: PublicKey): PublicKey {
   )[0];
 }
 
+export function derivePartnerFeeReceiverAddress(
+  partner: PublicKey,
+  mint: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("partner_fee_receiver"), partner.toBuffer(), mint.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveReferralAddress(
+  pool: PublicKey,
+  referrer: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referral"), pool.toBuffer(), referrer.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFeeDiscountRegistryAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_discount_registry"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveVaultWhitelistAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_whitelist"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function derivePoolMetadataAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_metadata"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function derivePoolStatsAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_stats"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveSwapAllowlistEntryAddress(
+  pool: PublicKey,
+  wallet: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("swap_allowlist"), pool.toBuffer(), wallet.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveLpAllowlistEntryAddress(
+  pool: PublicKey,
+  wallet: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("lp_allowlist"), pool.toBuffer(), wallet.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveLaunchBuyTrackerAddress(
+  pool: PublicKey,
+  wallet: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("launch_buy_tracker"), pool.toBuffer(), wallet.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveRewardVestingEscrowAddress(
+  pool: PublicKey,
+  rewardIndex: number,
+  owner: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("reward_vesting_escrow"),
+      pool.toBuffer(),
+      Buffer.from([rewardIndex]),
+      owner.toBuffer(),
+    ],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveTradeMiningAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("trade_mining"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveTradeMiningVaultAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("trade_mining_vault"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveTraderRewardAddress(
+  pool: PublicKey,
+  trader: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("trader_reward"), pool.toBuffer(), trader.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveInsuranceFundAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("insurance_fund"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveInsuranceVaultAddress(
+  insuranceFund: PublicKey,
+  mint: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("insurance_vault"), insuranceFund.toBuffer(), mint.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveBuybackBurnAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("buyback_burn"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFeeReceiptMint(position: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_receipt_mint"), position.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFeeReceiptAccount(feeReceiptMint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_receipt_account"), feeReceiptMint.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
 export function derivePositionNftAccount(
   positionNftMint: PublicKey
 ): PublicKey {
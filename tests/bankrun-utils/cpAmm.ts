@@ -27,9 +27,11 @@ import {
   clusterApiUrl,
   ComputeBudgetProgram,
   Connection,
+  AccountMeta,
   Keypair,
   PublicKey,
   SystemProgram,
+  Transaction,
 } from "@solana/web3.js";
 import { BanksClient } from "solana-bankrun";
 import CpAmmIDL from "../../target/idl/cp_amm.json";
@@ -39,16 +41,37 @@ import {
   deriveClaimFeeOperatorAddress,
   deriveConfigAddress,
   deriveCustomizablePoolAddress,
+  deriveFeeDiscountRegistryAddress,
+  deriveFeeReceiptAccount,
+  deriveFeeReceiptMint,
+  derivePartnerFeeReceiverAddress,
   derivePoolAddress,
   derivePoolAuthority,
   derivePositionAddress,
   derivePositionNftAccount,
+  derivePositionRewardExtensionAddress,
+  deriveProgramAuthorityAddress,
+  deriveReferralAddress,
+  deriveRewardExtensionAddress,
   deriveRewardVaultAddress,
   deriveTokenBadgeAddress,
   deriveTokenVaultAddress,
+  deriveVaultWhitelistAddress,
+  derivePoolMetadataAddress,
+  derivePoolStatsAddress,
+  deriveSwapAllowlistEntryAddress,
+  deriveLpAllowlistEntryAddress,
+  deriveLaunchBuyTrackerAddress,
+  deriveRewardVestingEscrowAddress,
+  deriveTradeMiningAddress,
+  deriveTradeMiningVaultAddress,
+  deriveTraderRewardAddress,
+  deriveInsuranceFundAddress,
+  deriveInsuranceVaultAddress,
+  deriveBuybackBurnAddress,
 } from "./accounts";
 import { processTransactionMaybeThrow } from "./common";
-import { CP_AMM_PROGRAM_ID } from "./constants";
+import { CP_AMM_PROGRAM_ID, TREASURY, U64_MAX } from "./constants";
 import { assert, expect } from "chai";
 
 export type Pool = IdlAccounts<CpAmm>["pool"];
@@ -57,6 +80,26 @@ export type Vesting = IdlAccounts<CpAmm>["vesting"];
 export type Config = IdlAccounts<CpAmm>["config"];
 export type LockPositionParams = IdlTypes<CpAmm>["vestingParameters"];
 export type TokenBadge = IdlAccounts<CpAmm>["tokenBadge"];
+export type PartnerFeeReceiver = IdlAccounts<CpAmm>["partnerFeeReceiver"];
+export type Referral = IdlAccounts<CpAmm>["referral"];
+export type FeeDiscountRegistry = IdlAccounts<CpAmm>["feeDiscountRegistry"];
+export type ProgramAuthority = IdlAccounts<CpAmm>["programAuthority"];
+export type VaultWhitelist = IdlAccounts<CpAmm>["vaultWhitelist"];
+export type PoolMetadata = IdlAccounts<CpAmm>["poolMetadata"];
+export type PoolStats = IdlAccounts<CpAmm>["poolStats"];
+export type RewardVestingEscrow = IdlAccounts<CpAmm>["rewardVestingEscrow"];
+export type TradeMining = IdlAccounts<CpAmm>["tradeMining"];
+export type TraderReward = IdlAccounts<CpAmm>["traderReward"];
+export type InsuranceFund = IdlAccounts<CpAmm>["insuranceFund"];
+export type BuybackBurn = IdlAccounts<CpAmm>["buybackBurn"];
+export type PoolMetadataParameters = IdlTypes<CpAmm>["poolMetadataParameters"];
+export type FeeDiscountTierParameters =
+  IdlTypes<CpAmm>["feeDiscountTierParameters"];
+export type SwapRateLimiterParameters =
+  IdlTypes<CpAmm>["swapRateLimiterParameters"];
+export type LaunchGuardParameters = IdlTypes<CpAmm>["launchGuardParameters"];
+export type PriceCircuitBreakerParameters =
+  IdlTypes<CpAmm>["priceCircuitBreakerParameters"];
 
 export function getSecondKey(key1: PublicKey, key2: PublicKey) {
   const buf1 = key1.toBuffer();
@@ -254,20 +297,127 @@ export async function closeConfigIx(
   expect(configState).to.be.null;
 }
 
+// index of roles in program authority account
+export const AUTHORITY_ROLE_CONFIG_ADMIN = 0;
+export const AUTHORITY_ROLE_FEE_CLAIMER = 1;
+export const AUTHORITY_ROLE_REWARD_ADMIN = 2;
+export const AUTHORITY_ROLE_PAUSER = 3;
+
+// admin gets every role of program authority
+export async function initializeProgramAuthority(
+  banksClient: BanksClient,
+  admin: Keypair,
+  transferDelay = new BN(0)
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .initializeProgramAuthority({
+      configAdmin: admin.publicKey,
+      feeClaimer: admin.publicKey,
+      rewardAdmin: admin.publicKey,
+      pauser: admin.publicKey,
+      transferDelay,
+    })
+    .accountsPartial({
+      programAuthority: deriveProgramAuthorityAddress(),
+      admin: admin.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function transferAuthorityRole(
+  banksClient: BanksClient,
+  signer: Keypair,
+  role: number,
+  newKey: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .transferAuthorityRole(role, newKey)
+    .accountsPartial({
+      programAuthority: deriveProgramAuthorityAddress(),
+      signer: signer.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function acceptAuthorityRole(
+  banksClient: BanksClient,
+  signer: Keypair,
+  role: number
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .acceptAuthorityRole(role)
+    .accountsPartial({
+      programAuthority: deriveProgramAuthorityAddress(),
+      signer: signer.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type UpdateConfigParams = {
+  poolFees: PoolFees | null;
+  vaultConfigKey: PublicKey | null;
+  poolCreatorAuthority: PublicKey | null;
+  activationType: number | null;
+};
+
+export async function updateConfigIx(
+  banksClient: BanksClient,
+  admin: Keypair,
+  config: PublicKey,
+  params: UpdateConfigParams
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateConfig(params)
+    .accountsPartial({
+      config,
+      admin: admin.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+// bit index of token badge policy
+export const TOKEN_BADGE_ALLOW_TRANSFER_HOOK = 0;
+export const TOKEN_BADGE_ALLOW_PERMANENT_DELEGATE = 1;
+export const TOKEN_BADGE_ALLOW_FREEZE_AUTHORITY = 2;
+export const TOKEN_BADGE_ALLOW_INTEREST_BEARING = 3;
+
 export type CreateTokenBadgeParams = {
   tokenMint: PublicKey;
   admin: Keypair;
+  // bits of allowed mint features, nothing allowed by default
+  policy?: number;
 };
 
 export async function createTokenBadge(
   banksClient: BanksClient,
   params: CreateTokenBadgeParams
 ) {
-  const { tokenMint, admin } = params;
+  const { tokenMint, admin, policy = 0 } = params;
   const program = createCpAmmProgram();
   const tokenBadge = deriveTokenBadgeAddress(tokenMint);
   const transaction = await program.methods
-    .createTokenBadge()
+    .createTokenBadge(policy)
     .accountsPartial({
       tokenBadge,
       tokenMint,
@@ -283,6 +433,31 @@ export async function createTokenBadge(
   const tokenBadgeState = await getTokenBadge(banksClient, tokenBadge);
 
   expect(tokenBadgeState.tokenMint.toString()).eq(tokenMint.toString());
+  expect(tokenBadgeState.policy).eq(policy);
+}
+
+export async function closeTokenBadge(
+  banksClient: BanksClient,
+  params: CreateTokenBadgeParams
+) {
+  const { tokenMint, admin } = params;
+  const program = createCpAmmProgram();
+  const tokenBadge = deriveTokenBadgeAddress(tokenMint);
+  const transaction = await program.methods
+    .closeTokenBadge()
+    .accountsPartial({
+      tokenBadge,
+      admin: admin.publicKey,
+      rentReceiver: admin.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const tokenBadgeState = await banksClient.getAccount(tokenBadge);
+  expect(tokenBadgeState).to.be.null;
 }
 
 export type ClaimFeeOperatorParams = {
@@ -345,19 +520,63 @@ export async function closeClaimFeeOperator(
   expect(account).to.be.null;
 }
 
+export type UpdateClaimFeeOperatorParams = {
+  admin: Keypair;
+  claimFeeOperator: PublicKey;
+  operator: PublicKey;
+  treasury: PublicKey;
+};
+export async function updateClaimFeeOperator(
+  banksClient: BanksClient,
+  params: UpdateClaimFeeOperatorParams
+) {
+  const program = createCpAmmProgram();
+  const { admin, claimFeeOperator, operator, treasury } = params;
+
+  const transaction = await program.methods
+    .updateClaimFeeOperator(operator, treasury)
+    .accountsPartial({
+      claimFeeOperator,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
 export type ClaimProtocolFeeParams = {
   operator: Keypair;
   pool: PublicKey;
   treasury: PublicKey;
+  // claim fee operator account, derived from operator by default
+  claimFeeOperator?: PublicKey;
+  maxAmountA?: BN;
+  maxAmountB?: BN;
+  // 0: claim both tokens, 1: settle in token a, 2: settle in token b
+  settlementMode?: number;
+  minimumSettlementAmount?: BN;
 };
 export async function claimProtocolFee(
   banksClient: BanksClient,
   params: ClaimProtocolFeeParams
 ) {
   const program = createCpAmmProgram();
-  const { operator, pool, treasury } = params;
+  const {
+    operator,
+    pool,
+    treasury,
+    maxAmountA,
+    maxAmountB,
+    settlementMode,
+    minimumSettlementAmount,
+  } = params;
   const poolAuthority = derivePoolAuthority();
-  const claimFeeOperator = deriveClaimFeeOperatorAddress(operator.publicKey);
+  const claimFeeOperator =
+    params.claimFeeOperator ??
+    deriveClaimFeeOperatorAddress(operator.publicKey);
   const poolState = await getPool(banksClient, pool);
 
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
@@ -381,8 +600,19 @@ export async function claimProtocolFee(
     tokenBProgram
   );
 
+  // insurance share of protocol fee is routed to insurance vaults of pool
+  const insuranceFund =
+    poolState.poolFees.insuranceFeePercent > 0
+      ? deriveInsuranceFundAddress(pool)
+      : null;
+
   const transaction = await program.methods
-    .claimProtocolFee()
+    .claimProtocolFee(
+      maxAmountA ?? U64_MAX,
+      maxAmountB ?? U64_MAX,
+      settlementMode ?? 0,
+      minimumSettlementAmount ?? new BN(0)
+    )
     .accountsPartial({
       poolAuthority,
       pool,
@@ -396,6 +626,13 @@ export async function claimProtocolFee(
       operator: operator.publicKey,
       tokenAProgram,
       tokenBProgram,
+      insuranceFund,
+      insuranceTokenAVault: insuranceFund
+        ? deriveInsuranceVaultAddress(insuranceFund, poolState.tokenAMint)
+        : null,
+      insuranceTokenBVault: insuranceFund
+        ? deriveInsuranceVaultAddress(insuranceFund, poolState.tokenBMint)
+        : null,
     })
     .transaction();
 
@@ -405,226 +642,416 @@ export async function claimProtocolFee(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type ClaimPartnerFeeParams = {
-  partner: Keypair;
-  pool: PublicKey;
-  maxAmountA: BN;
-  maxAmountB: BN;
-};
-export async function claimPartnerFee(
+export async function initializeInsuranceFund(
   banksClient: BanksClient,
-  params: ClaimPartnerFeeParams
-) {
+  admin: Keypair,
+  pool: PublicKey,
+  authority: PublicKey,
+  insuranceFeePercent: number
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const insuranceFund = deriveInsuranceFundAddress(pool);
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const transaction = await program.methods
+    .initializeInsuranceFund(authority, insuranceFeePercent)
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      insuranceFund,
+      tokenAVault: deriveInsuranceVaultAddress(
+        insuranceFund,
+        poolState.tokenAMint
+      ),
+      tokenBVault: deriveInsuranceVaultAddress(
+        insuranceFund,
+        poolState.tokenBMint
+      ),
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      programAuthority: deriveProgramAuthorityAddress(),
+      admin: admin.publicKey,
+      tokenAProgram,
+      tokenBProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return insuranceFund;
+}
+
+export async function claimInsuranceFund(
+  banksClient: BanksClient,
+  authority: Keypair,
+  pool: PublicKey,
+  maxAmountA?: BN,
+  maxAmountB?: BN
+): Promise<void> {
   const program = createCpAmmProgram();
-  const { partner, pool, maxAmountA, maxAmountB } = params;
-  const poolAuthority = derivePoolAuthority();
   const poolState = await getPool(banksClient, pool);
+  const insuranceFund = deriveInsuranceFundAddress(pool);
+  const { tokenAVault, tokenBVault } = await getInsuranceFund(
+    banksClient,
+    insuranceFund
+  );
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
     .owner;
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
+
   const tokenAAccount = await getOrCreateAssociatedTokenAccount(
     banksClient,
-    partner,
+    authority,
     poolState.tokenAMint,
-    partner.publicKey,
+    authority.publicKey,
     tokenAProgram
   );
-
   const tokenBAccount = await getOrCreateAssociatedTokenAccount(
     banksClient,
-    partner,
+    authority,
     poolState.tokenBMint,
-    partner.publicKey,
+    authority.publicKey,
     tokenBProgram
   );
+
   const transaction = await program.methods
-    .claimPartnerFee(maxAmountA, maxAmountB)
+    .claimInsuranceFund(maxAmountA ?? U64_MAX, maxAmountB ?? U64_MAX)
     .accountsPartial({
-      poolAuthority,
+      poolAuthority: derivePoolAuthority(),
       pool,
-      tokenAVault: poolState.tokenAVault,
-      tokenBVault: poolState.tokenBVault,
+      insuranceFund,
+      tokenAVault,
+      tokenBVault,
       tokenAMint: poolState.tokenAMint,
       tokenBMint: poolState.tokenBMint,
       tokenAAccount,
       tokenBAccount,
-      partner: partner.publicKey,
+      authority: authority.publicKey,
       tokenAProgram,
       tokenBProgram,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(partner);
+  transaction.sign(authority);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type InitializePoolParams = {
-  payer: Keypair;
-  creator: PublicKey;
-  config: PublicKey;
-  tokenAMint: PublicKey;
-  tokenBMint: PublicKey;
-  liquidity: BN;
-  sqrtPrice: BN;
-  activationPoint: BN | null;
-};
+export async function getInsuranceFund(
+  banksClient: BanksClient,
+  insuranceFund: PublicKey
+): Promise<InsuranceFund> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(insuranceFund);
+  return program.coder.accounts.decode(
+    "insuranceFund",
+    Buffer.from(account.data)
+  );
+}
 
-export async function initializePool(
+export async function initializeBuybackBurn(
   banksClient: BanksClient,
-  params: InitializePoolParams
-): Promise<{ pool: PublicKey; position: PublicKey }> {
-  const {
-    config,
-    tokenAMint,
-    tokenBMint,
-    payer,
-    creator,
-    liquidity,
-    sqrtPrice,
-    activationPoint,
-  } = params;
+  admin: Keypair,
+  pool: PublicKey,
+  maxAmountPerBuyback: BN,
+  minBuybackInterval: BN
+): Promise<PublicKey> {
   const program = createCpAmmProgram();
+  const buybackBurn = deriveBuybackBurnAddress(pool);
 
-  const poolAuthority = derivePoolAuthority();
-  const pool = derivePoolAddress(config, tokenAMint, tokenBMint);
+  const transaction = await program.methods
+    .initializeBuybackBurn(maxAmountPerBuyback, minBuybackInterval)
+    .accountsPartial({
+      pool,
+      buybackBurn,
+      programAuthority: deriveProgramAuthorityAddress(),
+      admin: admin.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
 
-  const positionNftKP = Keypair.generate();
-  const position = derivePositionAddress(positionNftKP.publicKey);
-  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
 
-  const tokenAVault = deriveTokenVaultAddress(tokenAMint, pool);
-  const tokenBVault = deriveTokenVaultAddress(tokenBMint, pool);
+  await processTransactionMaybeThrow(banksClient, transaction);
 
-  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
-  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+  return buybackBurn;
+}
 
-  const payerTokenA = getAssociatedTokenAddressSync(
-    tokenAMint,
-    payer.publicKey,
-    true,
-    tokenAProgram
-  );
-  const payerTokenB = getAssociatedTokenAddressSync(
-    tokenBMint,
-    payer.publicKey,
-    true,
-    tokenBProgram
-  );
+export async function buybackAndBurn(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
 
-  let transaction = await program.methods
-    .initializePool({
-      liquidity: liquidity,
-      sqrtPrice: sqrtPrice,
-      activationPoint: activationPoint,
-    })
+  const transaction = await program.methods
+    .buybackAndBurn()
     .accountsPartial({
-      creator,
-      positionNftAccount,
-      positionNftMint: positionNftKP.publicKey,
-      payer: payer.publicKey,
-      config,
-      poolAuthority,
+      poolAuthority: derivePoolAuthority(),
       pool,
-      position,
-      tokenAMint,
-      tokenBMint,
-      tokenAVault,
-      tokenBVault,
-      payerTokenA,
-      payerTokenB,
-      token2022Program: TOKEN_2022_PROGRAM_ID,
+      buybackBurn: deriveBuybackBurnAddress(pool),
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenAProgram,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function getBuybackBurn(
+  banksClient: BanksClient,
+  buybackBurn: PublicKey
+): Promise<BuybackBurn> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(buybackBurn);
+  return program.coder.accounts.decode(
+    "buybackBurn",
+    Buffer.from(account.data)
+  );
+}
+
+export type ClaimPartnerFeeParams = {
+  partner: Keypair;
+  pool: PublicKey;
+  maxAmountA: BN;
+  maxAmountB: BN;
+  // sign instead of partner, must be operator of the partner fee receivers
+  operator?: Keypair;
+  partnerFeeReceiverA?: PublicKey;
+  partnerFeeReceiverB?: PublicKey;
+};
+export async function claimPartnerFee(
+  banksClient: BanksClient,
+  params: ClaimPartnerFeeParams
+) {
+  const program = createCpAmmProgram();
+  const {
+    partner,
+    pool,
+    maxAmountA,
+    maxAmountB,
+    operator,
+    partnerFeeReceiverA,
+    partnerFeeReceiverB,
+  } = params;
+  const signer = operator ?? partner;
+  const poolAuthority = derivePoolAuthority();
+  const poolState = await getPool(banksClient, pool);
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+  const tokenAAccount = partnerFeeReceiverA
+    ? (await getPartnerFeeReceiver(banksClient, partnerFeeReceiverA))
+        .tokenAccount
+    : await getOrCreateAssociatedTokenAccount(
+        banksClient,
+        partner,
+        poolState.tokenAMint,
+        partner.publicKey,
+        tokenAProgram
+      );
+
+  const tokenBAccount = partnerFeeReceiverB
+    ? (await getPartnerFeeReceiver(banksClient, partnerFeeReceiverB))
+        .tokenAccount
+    : await getOrCreateAssociatedTokenAccount(
+        banksClient,
+        partner,
+        poolState.tokenBMint,
+        partner.publicKey,
+        tokenBProgram
+      );
+  const transaction = await program.methods
+    .claimPartnerFee(maxAmountA, maxAmountB)
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      tokenAAccount,
+      tokenBAccount,
+      signer: signer.publicKey,
+      partnerFeeReceiverA: partnerFeeReceiverA ?? null,
+      partnerFeeReceiverB: partnerFeeReceiverB ?? null,
       tokenAProgram,
       tokenBProgram,
-      systemProgram: SystemProgram.programId,
     })
     .transaction();
-  // requires more compute budget than usual
-  transaction.add(
-    ComputeBudgetProgram.setComputeUnitLimit({
-      units: 350_000,
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type CreatePartnerFeeReceiverParams = {
+  partner: Keypair;
+  mint: PublicKey;
+  tokenAccount: PublicKey;
+  operator: PublicKey;
+};
+export async function createPartnerFeeReceiver(
+  banksClient: BanksClient,
+  params: CreatePartnerFeeReceiverParams
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const { partner, mint, tokenAccount, operator } = params;
+
+  const partnerFeeReceiver = derivePartnerFeeReceiverAddress(
+    partner.publicKey,
+    mint
+  );
+  const transaction = await program.methods
+    .createPartnerFeeReceiver(operator)
+    .accountsPartial({
+      partnerFeeReceiver,
+      mint,
+      tokenAccount,
+      partner: partner.publicKey,
+      payer: partner.publicKey,
+      systemProgram: SystemProgram.programId,
     })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(partner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const partnerFeeReceiverState = await getPartnerFeeReceiver(
+    banksClient,
+    partnerFeeReceiver
+  );
+  expect(partnerFeeReceiverState.tokenAccount.toString()).eq(
+    tokenAccount.toString()
   );
+  expect(partnerFeeReceiverState.operator.toString()).eq(operator.toString());
+
+  return partnerFeeReceiver;
+}
+
+export type UpdatePartnerFeeReceiverParams = {
+  partner: Keypair;
+  partnerFeeReceiver: PublicKey;
+  tokenAccount: PublicKey;
+  operator: PublicKey;
+};
+export async function updatePartnerFeeReceiver(
+  banksClient: BanksClient,
+  params: UpdatePartnerFeeReceiverParams
+) {
+  const program = createCpAmmProgram();
+  const { partner, partnerFeeReceiver, tokenAccount, operator } = params;
+  const { mint } = await getPartnerFeeReceiver(banksClient, partnerFeeReceiver);
+
+  const transaction = await program.methods
+    .updatePartnerFeeReceiver(operator)
+    .accountsPartial({
+      partnerFeeReceiver,
+      mint,
+      tokenAccount,
+      partner: partner.publicKey,
+    })
+    .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, positionNftKP);
+  transaction.sign(partner);
 
   await processTransactionMaybeThrow(banksClient, transaction);
+}
 
-  // validate pool data
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.tokenAMint.toString()).eq(tokenAMint.toString());
-  expect(poolState.tokenBMint.toString()).eq(tokenBMint.toString());
-  expect(poolState.tokenAVault.toString()).eq(tokenAVault.toString());
-  expect(poolState.tokenBVault.toString()).eq(tokenBVault.toString());
-  expect(poolState.liquidity.toString()).eq(liquidity.toString());
-  expect(poolState.sqrtPrice.toString()).eq(sqrtPrice.toString());
+export type ClosePartnerFeeReceiverParams = {
+  partner: Keypair;
+  partnerFeeReceiver: PublicKey;
+  rentReceiver: PublicKey;
+};
+export async function closePartnerFeeReceiver(
+  banksClient: BanksClient,
+  params: ClosePartnerFeeReceiverParams
+) {
+  const program = createCpAmmProgram();
+  const { partner, partnerFeeReceiver, rentReceiver } = params;
 
-  expect(poolState.rewardInfos[0].initialized).eq(0);
-  expect(poolState.rewardInfos[1].initialized).eq(0);
+  const transaction = await program.methods
+    .closePartnerFeeReceiver()
+    .accountsPartial({
+      partnerFeeReceiver,
+      rentReceiver,
+      partner: partner.publicKey,
+    })
+    .transaction();
 
-  return { pool, position: position };
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(partner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type InitializePoolWithCustomizeConfigParams = {
+export type InitializePoolParams = {
   payer: Keypair;
-  poolCreatorAuthority: Keypair;
   creator: PublicKey;
-  customizeConfigAddress: PublicKey;
+  config: PublicKey;
   tokenAMint: PublicKey;
   tokenBMint: PublicKey;
-  poolFees: PoolFeesParams;
-  sqrtMinPrice: BN;
-  sqrtMaxPrice: BN;
-  hasAlphaVault: boolean;
   liquidity: BN;
   sqrtPrice: BN;
-  activationType: number;
-  collectFeeMode: number;
   activationPoint: BN | null;
+  // token badges of unsupported mints, followed by extra accounts of transfer hooks
+  remainingAccounts?: AccountMeta[];
 };
 
-export async function initializePoolWithCustomizeConfig(
+export async function initializePool(
   banksClient: BanksClient,
-  params: InitializePoolWithCustomizeConfigParams
+  params: InitializePoolParams
 ): Promise<{ pool: PublicKey; position: PublicKey }> {
   const {
+    config,
     tokenAMint,
     tokenBMint,
     payer,
     creator,
-    poolCreatorAuthority,
-    customizeConfigAddress,
-    poolFees,
-    hasAlphaVault,
     liquidity,
-    sqrtMaxPrice,
-    sqrtMinPrice,
     sqrtPrice,
-    collectFeeMode,
     activationPoint,
-    activationType,
+    remainingAccounts = [],
   } = params;
   const program = createCpAmmProgram();
 
   const poolAuthority = derivePoolAuthority();
-  const pool = derivePoolAddress(
-    customizeConfigAddress,
-    tokenAMint,
-    tokenBMint
-  );
+  const pool = derivePoolAddress(config, tokenAMint, tokenBMint);
 
   const positionNftKP = Keypair.generate();
   const position = derivePositionAddress(positionNftKP.publicKey);
   const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
 
-  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
-  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
-
   const tokenAVault = deriveTokenVaultAddress(tokenAMint, pool);
   const tokenBVault = deriveTokenVaultAddress(tokenBMint, pool);
 
+  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
+  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+
   const payerTokenA = getAssociatedTokenAddressSync(
     tokenAMint,
     payer.publicKey,
@@ -638,25 +1065,18 @@ export async function initializePoolWithCustomizeConfig(
     tokenBProgram
   );
 
-  const transaction = await program.methods
-    .initializePoolWithDynamicConfig({
-      poolFees,
-      sqrtMinPrice,
-      sqrtMaxPrice,
-      hasAlphaVault,
-      liquidity,
-      sqrtPrice,
-      activationType,
-      collectFeeMode,
-      activationPoint,
+  let transaction = await program.methods
+    .initializePool({
+      liquidity: liquidity,
+      sqrtPrice: sqrtPrice,
+      activationPoint: activationPoint,
     })
     .accountsPartial({
       creator,
       positionNftAccount,
       positionNftMint: positionNftKP.publicKey,
       payer: payer.publicKey,
-      poolCreatorAuthority: poolCreatorAuthority.publicKey,
-      config: customizeConfigAddress,
+      config,
       poolAuthority,
       pool,
       position,
@@ -666,10 +1086,12 @@ export async function initializePoolWithCustomizeConfig(
       tokenBVault,
       payerTokenA,
       payerTokenB,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
       tokenAProgram,
       tokenBProgram,
-      token2022Program: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
     })
+    .remainingAccounts(remainingAccounts)
     .transaction();
   // requires more compute budget than usual
   transaction.add(
@@ -678,7 +1100,7 @@ export async function initializePoolWithCustomizeConfig(
     })
   );
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, positionNftKP, poolCreatorAuthority);
+  transaction.sign(payer, positionNftKP);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
@@ -690,7 +1112,6 @@ export async function initializePoolWithCustomizeConfig(
   expect(poolState.tokenBVault.toString()).eq(tokenBVault.toString());
   expect(poolState.liquidity.toString()).eq(liquidity.toString());
   expect(poolState.sqrtPrice.toString()).eq(sqrtPrice.toString());
-  expect(poolState.poolType).eq(1); // Pool type: customize
 
   expect(poolState.rewardInfos[0].initialized).eq(0);
   expect(poolState.rewardInfos[1].initialized).eq(0);
@@ -698,43 +1119,11 @@ export async function initializePoolWithCustomizeConfig(
   return { pool, position: position };
 }
 
-export type SetPoolStatusParams = {
-  admin: Keypair;
-  pool: PublicKey;
-  status: number;
-};
-
-export async function setPoolStatus(
-  banksClient: BanksClient,
-  params: SetPoolStatusParams
-) {
-  const { admin, pool, status } = params;
-  const program = createCpAmmProgram();
-  const transaction = await program.methods
-    .setPoolStatus(status)
-    .accountsPartial({
-      pool,
-      admin: admin.publicKey,
-    })
-    .transaction();
-
-  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(admin);
-
-  await processTransactionMaybeThrow(banksClient, transaction);
-}
-
-export type PoolFeesParams = {
-  baseFee: BaseFee;
-  protocolFeePercent: number;
-  partnerFeePercent: number;
-  referralFeePercent: number;
-  dynamicFee: DynamicFee | null;
-};
-
-export type InitializeCustomizeablePoolParams = {
+export type InitializePoolWithCustomizeConfigParams = {
   payer: Keypair;
+  poolCreatorAuthority: Keypair;
   creator: PublicKey;
+  customizeConfigAddress: PublicKey;
   tokenAMint: PublicKey;
   tokenBMint: PublicKey;
   poolFees: PoolFeesParams;
@@ -746,17 +1135,26 @@ export type InitializeCustomizeablePoolParams = {
   activationType: number;
   collectFeeMode: number;
   activationPoint: BN | null;
+  // swap is only allowed for wallets in the swap allowlist managed by creator
+  permissionedSwap?: boolean;
+  // only wallets in the lp allowlist managed by creator can add liquidity
+  permissionedLp?: boolean;
+  swapRateLimiter?: SwapRateLimiterParameters;
+  launchGuard?: LaunchGuardParameters;
+  priceCircuitBreaker?: PriceCircuitBreakerParameters;
 };
 
-export async function initializeCustomizeablePool(
+export async function initializePoolWithCustomizeConfig(
   banksClient: BanksClient,
-  params: InitializeCustomizeablePoolParams
+  params: InitializePoolWithCustomizeConfigParams
 ): Promise<{ pool: PublicKey; position: PublicKey }> {
   const {
     tokenAMint,
     tokenBMint,
     payer,
     creator,
+    poolCreatorAuthority,
+    customizeConfigAddress,
     poolFees,
     hasAlphaVault,
     liquidity,
@@ -766,11 +1164,20 @@ export async function initializeCustomizeablePool(
     collectFeeMode,
     activationPoint,
     activationType,
+    permissionedSwap,
+    permissionedLp,
+    swapRateLimiter,
+    launchGuard,
+    priceCircuitBreaker,
   } = params;
   const program = createCpAmmProgram();
 
   const poolAuthority = derivePoolAuthority();
-  const pool = deriveCustomizablePoolAddress(tokenAMint, tokenBMint);
+  const pool = derivePoolAddress(
+    customizeConfigAddress,
+    tokenAMint,
+    tokenBMint
+  );
 
   const positionNftKP = Keypair.generate();
   const position = derivePositionAddress(positionNftKP.publicKey);
@@ -796,7 +1203,7 @@ export async function initializeCustomizeablePool(
   );
 
   const transaction = await program.methods
-    .initializeCustomizablePool({
+    .initializePoolWithDynamicConfig({
       poolFees,
       sqrtMinPrice,
       sqrtMaxPrice,
@@ -806,12 +1213,19 @@ export async function initializeCustomizeablePool(
       activationType,
       collectFeeMode,
       activationPoint,
+      permissionedSwap: permissionedSwap ?? false,
+      permissionedLp: permissionedLp ?? false,
+      swapRateLimiter: swapRateLimiter ?? null,
+      launchGuard: launchGuard ?? null,
+      priceCircuitBreaker: priceCircuitBreaker ?? null,
     })
     .accountsPartial({
       creator,
       positionNftAccount,
       positionNftMint: positionNftKP.publicKey,
       payer: payer.publicKey,
+      poolCreatorAuthority: poolCreatorAuthority.publicKey,
+      config: customizeConfigAddress,
       poolAuthority,
       pool,
       position,
@@ -821,10 +1235,9 @@ export async function initializeCustomizeablePool(
       tokenBVault,
       payerTokenA,
       payerTokenB,
-      token2022Program: TOKEN_2022_PROGRAM_ID,
       tokenAProgram,
       tokenBProgram,
-      systemProgram: SystemProgram.programId,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
     })
     .transaction();
   // requires more compute budget than usual
@@ -834,7 +1247,7 @@ export async function initializeCustomizeablePool(
     })
   );
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, positionNftKP);
+  transaction.sign(payer, positionNftKP, poolCreatorAuthority);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
@@ -846,6 +1259,7 @@ export async function initializeCustomizeablePool(
   expect(poolState.tokenBVault.toString()).eq(tokenBVault.toString());
   expect(poolState.liquidity.toString()).eq(liquidity.toString());
   expect(poolState.sqrtPrice.toString()).eq(sqrtPrice.toString());
+  expect(poolState.poolType).eq(1); // Pool type: customize
 
   expect(poolState.rewardInfos[0].initialized).eq(0);
   expect(poolState.rewardInfos[1].initialized).eq(0);
@@ -853,270 +1267,2427 @@ export async function initializeCustomizeablePool(
   return { pool, position: position };
 }
 
-export type InitializeRewardParams = {
-  payer: Keypair;
+export type SetPoolStatusParams = {
+  signer: Keypair;
+  pool: PublicKey;
+  status: number;
+};
+
+export async function setPoolStatus(
+  banksClient: BanksClient,
+  params: SetPoolStatusParams
+) {
+  const { signer, pool, status } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .setPoolStatus(status)
+    .accountsPartial({
+      pool,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function disableFrozenPool(
+  banksClient: BanksClient,
+  signer: Keypair,
+  pool: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const transaction = await program.methods
+    .disableFrozenPool()
+    .accountsPartial({
+      pool,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function skimVaults(
+  banksClient: BanksClient,
+  signer: Keypair,
+  pool: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const transaction = await program.methods
+    .skimVaults()
+    .accountsPartial({
+      pool,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function setPoolEmergencyMode(
+  banksClient: BanksClient,
+  admin: Keypair,
+  pool: PublicKey,
+  enabled: boolean
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .setPoolEmergencyMode(enabled)
+    .accountsPartial({
+      pool,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function migratePool(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .migratePool()
+    .accountsPartial({
+      pool,
+      payer: payer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function closePool(
+  banksClient: BanksClient,
+  admin: Keypair,
+  pool: PublicKey,
+  rentReceiver: PublicKey,
+  // position holding the locked minimum liquidity and its owner, if it is
+  // left in pool
+  position?: PublicKey,
+  positionOwner?: Keypair
+) {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionNftMint = position
+    ? (await getPosition(banksClient, position)).nftMint
+    : null;
+  // insurance fund is closed with its vaults if pool has one
+  const insuranceFund = deriveInsuranceFundAddress(pool);
+  const hasInsuranceFund = !!(await banksClient.getAccount(insuranceFund));
+
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const transaction = await program.methods
+    .closePool()
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      position: position ?? null,
+      positionNftMint,
+      positionNftAccount: positionNftMint
+        ? derivePositionNftAccount(positionNftMint)
+        : null,
+      positionOwner: positionOwner?.publicKey ?? null,
+      tokenProgram: position ? TOKEN_2022_PROGRAM_ID : null,
+      tradeMining: deriveTradeMiningAddress(pool),
+      insuranceFund,
+      insuranceTokenAVault: hasInsuranceFund
+        ? deriveInsuranceVaultAddress(insuranceFund, poolState.tokenAMint)
+        : null,
+      insuranceTokenBVault: hasInsuranceFund
+        ? deriveInsuranceVaultAddress(insuranceFund, poolState.tokenBMint)
+        : null,
+      rentReceiver,
+      admin: admin.publicKey,
+      tokenAProgram,
+      tokenBProgram,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin, ...(positionOwner ? [positionOwner] : []));
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function setConfigFeeOverrideBounds(
+  banksClient: BanksClient,
+  admin: Keypair,
+  config: PublicKey,
+  minFeeNumerator: BN,
+  maxFeeNumerator: BN
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .setConfigFeeOverrideBounds(minFeeNumerator, maxFeeNumerator)
+    .accountsPartial({
+      config,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function setConfigCpiGuard(
+  banksClient: BanksClient,
+  admin: Keypair,
+  config: PublicKey,
+  cpiGuardDuration: BN
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .setConfigCpiGuard(cpiGuardDuration)
+    .accountsPartial({
+      config,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function setConfigRewardBoost(
+  banksClient: BanksClient,
+  admin: Keypair,
+  config: PublicKey,
+  vestingRewardBoostBps: number,
+  permanentLockRewardBoostBps: number
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .setConfigRewardBoost(vestingRewardBoostBps, permanentLockRewardBoostBps)
+    .accountsPartial({
+      config,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type OverridePoolFeeParams = {
+  poolCreatorAuthority: Keypair;
+  config: PublicKey;
+  pool: PublicKey;
+  tradeFeeNumerator: BN;
+};
+
+export async function overridePoolFee(
+  banksClient: BanksClient,
+  params: OverridePoolFeeParams
+) {
+  const { poolCreatorAuthority, config, pool, tradeFeeNumerator } = params;
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const transaction = await program.methods
+    .overridePoolFee(tradeFeeNumerator)
+    .accountsPartial({
+      pool,
+      config,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      poolCreatorAuthority: poolCreatorAuthority.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(poolCreatorAuthority);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // override is queued until its activation point
+  const { pendingBaseFee } = await getPool(banksClient, pool);
+  expect(pendingBaseFee.tradeFeeNumerator.toString()).eq(
+    tradeFeeNumerator.toString()
+  );
+  expect(pendingBaseFee.activationPoint.isZero()).to.be.false;
+}
+
+export async function transferPoolCreatorAuthority(
+  banksClient: BanksClient,
+  poolCreatorAuthority: Keypair,
+  config: PublicKey,
+  newPoolCreatorAuthority: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .transferPoolCreatorAuthority(newPoolCreatorAuthority)
+    .accountsPartial({
+      config,
+      poolCreatorAuthority: poolCreatorAuthority.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(poolCreatorAuthority);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const configState = await getConfig(banksClient, config);
+  expect(configState.pendingPoolCreatorAuthority.toString()).eq(
+    newPoolCreatorAuthority.toString()
+  );
+}
+
+export async function acceptPoolCreatorAuthority(
+  banksClient: BanksClient,
+  signer: Keypair,
+  config: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .acceptPoolCreatorAuthority()
+    .accountsPartial({
+      config,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const configState = await getConfig(banksClient, config);
+  expect(configState.poolCreatorAuthority.toString()).eq(
+    signer.publicKey.toString()
+  );
+  expect(configState.pendingPoolCreatorAuthority.toString()).eq(
+    PublicKey.default.toString()
+  );
+}
+
+export type UpdatePoolDynamicFeeParams = {
+  signer: Keypair;
+  pool: PublicKey;
+  decayPeriod: number;
+  reductionFactor: number;
+  variableFeeControl: number;
+};
+
+export async function updatePoolDynamicFee(
+  banksClient: BanksClient,
+  params: UpdatePoolDynamicFeeParams
+) {
+  const { signer, pool, decayPeriod, reductionFactor, variableFeeControl } =
+    params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updatePoolDynamicFee({
+      decayPeriod,
+      reductionFactor,
+      variableFeeControl,
+    })
+    .accountsPartial({
+      pool,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // update is queued until its activation point
+  const poolState = await getPool(banksClient, pool);
+  const pendingDynamicFee = poolState.poolFees.pendingDynamicFee;
+  expect(pendingDynamicFee.decayPeriod).eq(decayPeriod);
+  expect(pendingDynamicFee.reductionFactor).eq(reductionFactor);
+  expect(pendingDynamicFee.variableFeeControl).eq(variableFeeControl);
+  expect(pendingDynamicFee.activationPoint.isZero()).to.be.false;
+}
+
+export async function cancelPendingUpdate(
+  banksClient: BanksClient,
+  signer: Keypair,
+  pool: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .cancelPendingUpdate()
+    .accountsPartial({
+      pool,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.poolFees.pendingDynamicFee.activationPoint.isZero()).to.be
+    .true;
+  expect(poolState.pendingBaseFee.activationPoint.isZero()).to.be.true;
+}
+
+export async function updateActivationPoint(
+  banksClient: BanksClient,
+  signer: Keypair,
+  pool: PublicKey,
+  newActivationPoint: BN
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateActivationPoint(newActivationPoint)
+    .accountsPartial({
+      pool,
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.activationPoint.toString()).eq(
+    newActivationPoint.toString()
+  );
+}
+
+export type PoolFeesParams = {
+  baseFee: BaseFee;
+  protocolFeePercent: number;
+  partnerFeePercent: number;
+  referralFeePercent: number;
+  dynamicFee: DynamicFee | null;
+};
+
+export type InitializeCustomizeablePoolParams = {
+  payer: Keypair;
+  creator: PublicKey;
+  tokenAMint: PublicKey;
+  tokenBMint: PublicKey;
+  poolFees: PoolFeesParams;
+  sqrtMinPrice: BN;
+  sqrtMaxPrice: BN;
+  hasAlphaVault: boolean;
+  liquidity: BN;
+  sqrtPrice: BN;
+  activationType: number;
+  collectFeeMode: number;
+  activationPoint: BN | null;
+  // swap is only allowed for wallets in the swap allowlist managed by creator
+  permissionedSwap?: boolean;
+  // only wallets in the lp allowlist managed by creator can add liquidity
+  permissionedLp?: boolean;
+  swapRateLimiter?: SwapRateLimiterParameters;
+  launchGuard?: LaunchGuardParameters;
+  priceCircuitBreaker?: PriceCircuitBreakerParameters;
+};
+
+export async function initializeCustomizeablePool(
+  banksClient: BanksClient,
+  params: InitializeCustomizeablePoolParams
+): Promise<{ pool: PublicKey; position: PublicKey }> {
+  const {
+    tokenAMint,
+    tokenBMint,
+    payer,
+    creator,
+    poolFees,
+    hasAlphaVault,
+    liquidity,
+    sqrtMaxPrice,
+    sqrtMinPrice,
+    sqrtPrice,
+    collectFeeMode,
+    activationPoint,
+    activationType,
+    permissionedSwap,
+    permissionedLp,
+    swapRateLimiter,
+    launchGuard,
+    priceCircuitBreaker,
+  } = params;
+  const program = createCpAmmProgram();
+
+  const poolAuthority = derivePoolAuthority();
+  const pool = deriveCustomizablePoolAddress(tokenAMint, tokenBMint);
+
+  const positionNftKP = Keypair.generate();
+  const position = derivePositionAddress(positionNftKP.publicKey);
+  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+
+  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
+  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+
+  const tokenAVault = deriveTokenVaultAddress(tokenAMint, pool);
+  const tokenBVault = deriveTokenVaultAddress(tokenBMint, pool);
+
+  const payerTokenA = getAssociatedTokenAddressSync(
+    tokenAMint,
+    payer.publicKey,
+    true,
+    tokenAProgram
+  );
+  const payerTokenB = getAssociatedTokenAddressSync(
+    tokenBMint,
+    payer.publicKey,
+    true,
+    tokenBProgram
+  );
+
+  const transaction = await program.methods
+    .initializeCustomizablePool({
+      poolFees,
+      sqrtMinPrice,
+      sqrtMaxPrice,
+      hasAlphaVault,
+      liquidity,
+      sqrtPrice,
+      activationType,
+      collectFeeMode,
+      activationPoint,
+      permissionedSwap: permissionedSwap ?? false,
+      permissionedLp: permissionedLp ?? false,
+      swapRateLimiter: swapRateLimiter ?? null,
+      launchGuard: launchGuard ?? null,
+      priceCircuitBreaker: priceCircuitBreaker ?? null,
+    })
+    .accountsPartial({
+      creator,
+      positionNftAccount,
+      positionNftMint: positionNftKP.publicKey,
+      payer: payer.publicKey,
+      poolAuthority,
+      pool,
+      position,
+      tokenAMint,
+      tokenBMint,
+      tokenAVault,
+      tokenBVault,
+      payerTokenA,
+      payerTokenB,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+      tokenAProgram,
+      tokenBProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  // requires more compute budget than usual
+  transaction.add(
+    ComputeBudgetProgram.setComputeUnitLimit({
+      units: 350_000,
+    })
+  );
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, positionNftKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // validate pool data
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.tokenAMint.toString()).eq(tokenAMint.toString());
+  expect(poolState.tokenBMint.toString()).eq(tokenBMint.toString());
+  expect(poolState.tokenAVault.toString()).eq(tokenAVault.toString());
+  expect(poolState.tokenBVault.toString()).eq(tokenBVault.toString());
+  expect(poolState.liquidity.toString()).eq(liquidity.toString());
+  expect(poolState.sqrtPrice.toString()).eq(sqrtPrice.toString());
+
+  expect(poolState.rewardInfos[0].initialized).eq(0);
+  expect(poolState.rewardInfos[1].initialized).eq(0);
+
+  return { pool, position: position };
+}
+
+export type InitializeRewardParams = {
+  payer: Keypair;
+  index: number;
+  rewardDuration: BN;
+  pool: PublicKey;
+  rewardMint: PublicKey;
+  // seconds claimed rewards are vested over, 0 by default
+  claimVestingDuration?: number;
+};
+
+export async function initializeReward(
+  banksClient: BanksClient,
+  params: InitializeRewardParams
+): Promise<void> {
+  const { index, rewardDuration, pool, rewardMint, payer } = params;
+  const program = createCpAmmProgram();
+
+  const poolAuthority = derivePoolAuthority();
+  const rewardVault = deriveRewardVaultAddress(pool, index);
+
+  const tokenProgram = (await banksClient.getAccount(rewardMint)).owner;
+
+  const transaction = await program.methods
+    .initializeReward(
+      index,
+      rewardDuration,
+      payer.publicKey,
+      params.claimVestingDuration ?? 0
+    )
+    .accountsPartial({
+      pool,
+      poolAuthority,
+      rewardVault,
+      rewardMint,
+      payer: payer.publicKey,
+      treasury: TREASURY,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // validate reward data
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.rewardInfos[index].initialized).eq(1);
+  expect(poolState.rewardInfos[index].vault.toString()).eq(
+    rewardVault.toString()
+  );
+  expect(poolState.rewardInfos[index].mint.toString()).eq(
+    rewardMint.toString()
+  );
+}
+
+export type UpdateRewardDurationParams = {
+  index: number;
+  admin: Keypair;
+  pool: PublicKey;
+  newDuration: BN;
+};
+
+export async function updateRewardDuration(
+  banksClient: BanksClient,
+  params: UpdateRewardDurationParams
+): Promise<void> {
+  const { pool, admin, index, newDuration } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewardDuration(index, newDuration)
+    .accountsPartial({
+      pool,
+      admin: admin.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.rewardInfos[index].rewardDuration.toNumber()).eq(
+    newDuration.toNumber()
+  );
+}
+
+export type UpdateRewardsParams = {
+  payer: Keypair;
+  pool: PublicKey;
+};
+
+export async function updateRewards(
+  banksClient: BanksClient,
+  params: UpdateRewardsParams
+): Promise<void> {
+  const { pool, payer } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewards()
+    .accountsPartial({
+      pool,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  const currentTime = (await banksClient.getClock()).unixTimestamp;
+  for (const rewardInfo of poolState.rewardInfos) {
+    if (rewardInfo.initialized == 0) {
+      continue;
+    }
+    const expectedLastUpdateTime = BN.min(
+      new BN(currentTime.toString()),
+      rewardInfo.rewardDurationEnd
+    );
+    expect(rewardInfo.lastUpdateTime.toString()).eq(
+      expectedLastUpdateTime.toString()
+    );
+  }
+}
+
+export type InitializeRewardExtensionParams = {
+  admin: Keypair;
+  pool: PublicKey;
+};
+
+export async function initializeRewardExtension(
+  banksClient: BanksClient,
+  params: InitializeRewardExtensionParams
+): Promise<PublicKey> {
+  const { pool, admin } = params;
+  const program = createCpAmmProgram();
+  const rewardExtension = deriveRewardExtensionAddress(pool);
+  const transaction = await program.methods
+    .initializeRewardExtension()
+    .accountsPartial({
+      pool,
+      rewardExtension,
+      admin: admin.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.rewardExtensionFlag).eq(1);
+
+  return rewardExtension;
+}
+
+export type CreatePositionRewardExtensionParams = {
+  payer: Keypair;
+  position: PublicKey;
+};
+
+export async function createPositionRewardExtension(
+  banksClient: BanksClient,
+  params: CreatePositionRewardExtensionParams
+): Promise<PublicKey> {
+  const { position, payer } = params;
+  const program = createCpAmmProgram();
+  const positionRewardExtension =
+    derivePositionRewardExtensionAddress(position);
+  const transaction = await program.methods
+    .createPositionRewardExtension()
+    .accountsPartial({
+      position,
+      positionRewardExtension,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return positionRewardExtension;
+}
+
+export type UpdateRewardEmissionScheduleParams = {
+  index: number;
+  signer: Keypair;
+  pool: PublicKey;
+  emissionPeriod: number;
+  emissionDecayBps: number;
+};
+
+export async function updateRewardEmissionSchedule(
+  banksClient: BanksClient,
+  params: UpdateRewardEmissionScheduleParams
+): Promise<void> {
+  const { pool, signer, index, emissionPeriod, emissionDecayBps } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewardEmissionSchedule(index, emissionPeriod, emissionDecayBps)
+    .accountsPartial({
+      pool,
+      signer: signer.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.rewardInfos[index].emissionPeriod).eq(emissionPeriod);
+  expect(poolState.rewardInfos[index].emissionDecayBps).eq(emissionDecayBps);
+}
+
+export type UpdateRewardFunderParams = {
+  index: number;
+  signer: Keypair;
+  pool: PublicKey;
+  newFunder: PublicKey;
+};
+
+export async function updateRewardFunder(
+  banksClient: BanksClient,
+  params: UpdateRewardFunderParams
+): Promise<void> {
+  const { pool, signer, index, newFunder } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewardFunder(index, newFunder)
+    .accountsPartial({
+      pool,
+      signer: signer.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.rewardInfos[index].funder.toString()).eq(
+    newFunder.toString()
+  );
+}
+
+export type FundRewardParams = {
+  funder: Keypair;
+  index: number;
+  pool: PublicKey;
+  carryForward: boolean;
+  amount: BN;
+};
+
+export async function fundReward(
+  banksClient: BanksClient,
+  params: FundRewardParams
+): Promise<void> {
+  const { index, carryForward, pool, funder, amount } = params;
+  const program = createCpAmmProgram();
+
+  const poolState = await getPool(banksClient, pool);
+  const rewardVault = poolState.rewardInfos[index].vault;
+  const tokenProgram = (
+    await banksClient.getAccount(poolState.rewardInfos[index].mint)
+  ).owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    poolState.rewardInfos[index].mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const rewardVaultPreBalance = Number(
+    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
+      .amount
+  );
+  const funderPreBalance = Number(
+    AccountLayout.decode(
+      (await banksClient.getAccount(funderTokenAccount)).data
+    ).amount
+  );
+
+  const transaction = await program.methods
+    .fundReward(index, amount, carryForward)
+    .accountsPartial({
+      pool,
+      rewardVault: poolState.rewardInfos[index].vault,
+      rewardMint: poolState.rewardInfos[index].mint,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const rewardVaultPostBalance = Number(
+    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
+      .amount
+  );
+  const funderPostBalance = Number(
+    AccountLayout.decode(
+      (await banksClient.getAccount(funderTokenAccount)).data
+    ).amount
+  );
+
+  // expect(funderPreBalance - funderPostBalance).eq(amount.toNumber());
+
+  // expect(rewardVaultPostBalance - rewardVaultPreBalance).eq(amount.toNumber());
+}
+
+export type ClaimRewardParams = {
   index: number;
-  rewardDuration: BN;
+  user: Keypair;
+  position: PublicKey;
+  pool: PublicKey;
+  // required by rewards with claim vesting
+  rewardVestingEscrow?: PublicKey;
+};
+
+export async function claimReward(
+  banksClient: BanksClient,
+  params: ClaimRewardParams
+): Promise<void> {
+  const { index, pool, user, position } = params;
+  const program = createCpAmmProgram();
+
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const poolAuthority = derivePoolAuthority();
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  // TODO should use token flag in pool state to get token program ID
+  const tokenProgram = (
+    await banksClient.getAccount(poolState.rewardInfos[index].mint)
+  ).owner;
+
+  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    user,
+    poolState.rewardInfos[index].mint,
+    user.publicKey,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .claimReward(index)
+    .accountsPartial({
+      pool,
+      positionNftAccount,
+      rewardVault: poolState.rewardInfos[index].vault,
+      rewardMint: poolState.rewardInfos[index].mint,
+      poolAuthority,
+      position,
+      userTokenAccount,
+      owner: user.publicKey,
+      tokenProgram,
+      rewardVestingEscrow: params.rewardVestingEscrow ?? null,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(user);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function createRewardVestingEscrow(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  index: number,
+  owner: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const rewardVestingEscrow = deriveRewardVestingEscrowAddress(
+    pool,
+    index,
+    owner
+  );
+  const transaction = await program.methods
+    .createRewardVestingEscrow(index)
+    .accountsPartial({
+      pool,
+      rewardVestingEscrow,
+      owner,
+      payer: payer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return rewardVestingEscrow;
+}
+
+export async function claimVestedReward(
+  banksClient: BanksClient,
+  user: Keypair,
+  pool: PublicKey,
+  index: number
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const rewardMint = poolState.rewardInfos[index].mint;
+  const tokenProgram = (await banksClient.getAccount(rewardMint)).owner;
+  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    user,
+    rewardMint,
+    user.publicKey,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .claimVestedReward(index)
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      rewardVestingEscrow: deriveRewardVestingEscrowAddress(
+        pool,
+        index,
+        user.publicKey
+      ),
+      rewardVault: poolState.rewardInfos[index].vault,
+      rewardMint,
+      userTokenAccount,
+      owner: user.publicKey,
+      tokenProgram,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(user);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type WithdrawIneligibleRewardParams = {
+  index: number;
+  funder: Keypair;
+  pool: PublicKey;
+};
+
+export async function withdrawIneligibleReward(
+  banksClient: BanksClient,
+  params: WithdrawIneligibleRewardParams
+): Promise<void> {
+  const { index, pool, funder } = params;
+  const program = createCpAmmProgram();
+
+  const poolState = await getPool(banksClient, pool);
+  const poolAuthority = derivePoolAuthority();
+  const tokenProgram = (
+    await banksClient.getAccount(poolState.rewardInfos[index].mint)
+  ).owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    poolState.rewardInfos[index].mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .withdrawIneligibleReward(index)
+    .accountsPartial({
+      pool,
+      rewardVault: poolState.rewardInfos[index].vault,
+      rewardMint: poolState.rewardInfos[index].mint,
+      poolAuthority,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function refreshVestings(
+  banksClient: BanksClient,
+  position: PublicKey,
+  pool: PublicKey,
+  owner: PublicKey,
+  payer: Keypair,
+  vestings: PublicKey[]
+) {
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const transaction = await program.methods
+    .refreshVesting()
+    .accountsPartial({
+      position,
+      positionNftAccount,
+      pool,
+      owner,
+    })
+    .remainingAccounts(
+      vestings.map((pubkey) => {
+        return {
+          isSigner: false,
+          isWritable: true,
+          pubkey,
+        };
+      })
+    )
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function batchRefreshVesting(
+  banksClient: BanksClient,
+  payer: Keypair,
+  positions: {
+    position: PublicKey;
+    owner: PublicKey;
+    vestings: PublicKey[];
+  }[]
+) {
+  const program = createCpAmmProgram();
+  const remainingAccounts: AccountMeta[] = [];
+  for (const { position, owner, vestings } of positions) {
+    const positionState = await getPosition(banksClient, position);
+    remainingAccounts.push(
+      { pubkey: positionState.pool, isSigner: false, isWritable: true },
+      { pubkey: position, isSigner: false, isWritable: true },
+      {
+        pubkey: derivePositionNftAccount(positionState.nftMint),
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: owner, isSigner: false, isWritable: true },
+      ...vestings.map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: true,
+      }))
+    );
+  }
+
+  const transaction = await program.methods
+    .batchRefreshVesting(
+      Buffer.from(positions.map(({ vestings }) => vestings.length))
+    )
+    .accountsPartial({ signer: payer.publicKey })
+    .remainingAccounts(remainingAccounts)
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function permanentLockPosition(
+  banksClient: BanksClient,
+  position: PublicKey,
+  owner: Keypair,
+  payer: Keypair
+) {
+  const program = createCpAmmProgram();
+
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const transaction = await program.methods
+    .permanentLockPosition(positionState.unlockedLiquidity)
+    .accountsPartial({
+      position,
+      positionNftAccount,
+      pool: positionState.pool,
+      owner: owner.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function permanentLockVesting(
+  banksClient: BanksClient,
+  position: PublicKey,
+  vesting: PublicKey,
+  owner: Keypair
+) {
+  const program = createCpAmmProgram();
+
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const transaction = await program.methods
+    .permanentLockVesting()
+    .accountsPartial({
+      position,
+      vesting,
+      positionNftAccount,
+      pool: positionState.pool,
+      owner: owner.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function lockPosition(
+  banksClient: BanksClient,
+  position: PublicKey,
+  owner: Keypair,
+  payer: Keypair,
+  params: LockPositionParams
+) {
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const vestingKP = Keypair.generate();
+
+  const transaction = await program.methods
+    .lockPosition(params)
+    .accountsPartial({
+      position,
+      positionNftAccount,
+      vesting: vestingKP.publicKey,
+      owner: owner.publicKey,
+      pool: positionState.pool,
+      program: CP_AMM_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      payer: payer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, owner, vestingKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return vestingKP.publicKey;
+}
+
+export async function createPosition(
+  banksClient: BanksClient,
+  payer: Keypair,
+  owner: PublicKey,
+  pool: PublicKey,
+  lpAllowlistEntry?: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+
+  const positionNftKP = Keypair.generate();
+  const position = derivePositionAddress(positionNftKP.publicKey);
+  const poolAuthority = derivePoolAuthority();
+  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+
+  const transaction = await program.methods
+    .createPosition()
+    .accountsPartial({
+      owner,
+      positionNftMint: positionNftKP.publicKey,
+      poolAuthority,
+      positionNftAccount,
+      payer: payer.publicKey,
+      pool,
+      position,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      lpAllowlistEntry: lpAllowlistEntry ?? null,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, positionNftKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const positionState = await getPosition(banksClient, position);
+
+  expect(positionState.nftMint.toString()).eq(
+    positionNftKP.publicKey.toString()
+  );
+
+  const positionNftData = AccountLayout.decode(
+    (await banksClient.getAccount(positionNftAccount)).data
+  );
+
+  // validate metadata
+  const tlvData = (
+    await banksClient.getAccount(positionState.nftMint)
+  ).data.slice(ACCOUNT_SIZE + ACCOUNT_TYPE_SIZE);
+  const metadata = unpack(
+    getExtensionData(ExtensionType.TokenMetadata, Buffer.from(tlvData))
+  );
+  expect(metadata.name).eq("Meteora Position NFT");
+  expect(metadata.symbol).eq("MPN");
+
+  // validate metadata pointer
+  const metadataAddress = MetadataPointerLayout.decode(
+    getExtensionData(ExtensionType.MetadataPointer, Buffer.from(tlvData))
+  ).metadataAddress;
+  expect(metadataAddress.toString()).eq(positionState.nftMint.toString());
+
+  // validate owner
+  expect(positionNftData.owner.toString()).eq(owner.toString());
+  expect(Number(positionNftData.amount)).eq(1);
+  expect(positionNftData.mint.toString()).eq(
+    positionNftKP.publicKey.toString()
+  );
+
+  return position;
+}
+
+export type AddLiquidityParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  liquidityDelta: BN;
+  tokenAAmountThreshold: BN;
+  tokenBAmountThreshold: BN;
+  // required for permissioned lp pool
+  lpAllowlistEntry?: PublicKey;
+  // token badges and extra accounts of transfer hooks
+  remainingAccounts?: AccountMeta[];
+};
+
+export async function addLiquidity(
+  banksClient: BanksClient,
+  params: AddLiquidityParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    liquidityDelta,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+    lpAllowlistEntry,
+    remainingAccounts = [],
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .addLiquidity({
+      liquidityDelta,
+      tokenAAmountThreshold,
+      tokenBAmountThreshold,
+    })
+    .accountsPartial({
+      pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+      lpAllowlistEntry: lpAllowlistEntry ?? null,
+    })
+    .remainingAccounts(remainingAccounts)
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type DonateParams = {
+  donor: Keypair;
+  pool: PublicKey;
+  tokenAAmount: BN;
+  tokenBAmount: BN;
+};
+
+export async function donate(banksClient: BanksClient, params: DonateParams) {
+  const { donor, pool, tokenAAmount, tokenBAmount } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const transaction = await program.methods
+    .donate({
+      tokenAAmount,
+      tokenBAmount,
+    })
+    .accountsPartial({
+      pool,
+      donor: donor.publicKey,
+      tokenAAccount: getAssociatedTokenAddressSync(
+        poolState.tokenAMint,
+        donor.publicKey,
+        true,
+        tokenAProgram
+      ),
+      tokenBAccount: getAssociatedTokenAddressSync(
+        poolState.tokenBMint,
+        donor.publicKey,
+        true,
+        tokenBProgram
+      ),
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(donor);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type AddLiquidityByAmountsParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  tokenAAmount: BN;
+  tokenBAmount: BN;
+  liquidityDeltaThreshold: BN;
+  // required for permissioned lp pool
+  lpAllowlistEntry?: PublicKey;
+};
+
+export async function addLiquidityByAmounts(
+  banksClient: BanksClient,
+  params: AddLiquidityByAmountsParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    tokenAAmount,
+    tokenBAmount,
+    liquidityDeltaThreshold,
+    lpAllowlistEntry,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .addLiquidityByAmounts({
+      tokenAAmount,
+      tokenBAmount,
+      liquidityDeltaThreshold,
+    })
+    .accountsPartial({
+      pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+      lpAllowlistEntry: lpAllowlistEntry ?? null,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type RemoveLiquidityParams = AddLiquidityParams;
+
+export async function removeLiquidity(
+  banksClient: BanksClient,
+  params: RemoveLiquidityParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    liquidityDelta,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+    remainingAccounts = [],
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .removeLiquidity({
+      liquidityDelta,
+      tokenAAmountThreshold,
+      tokenBAmountThreshold,
+    })
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+    })
+    .remainingAccounts(remainingAccounts)
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type RemoveLiquiditySingleSideParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  liquidityDelta: BN;
+  tradeDirection: number;
+  minimumAmountOut: BN;
+};
+
+export async function removeLiquiditySingleSide(
+  banksClient: BanksClient,
+  params: RemoveLiquiditySingleSideParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    liquidityDelta,
+    tradeDirection,
+    minimumAmountOut,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .removeLiquiditySingleSide({
+      liquidityDelta,
+      tradeDirection,
+      minimumAmountOut,
+    })
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type RemoveAllLiquidityParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  tokenAAmountThreshold: BN;
+  tokenBAmountThreshold: BN;
+};
+
+export async function removeAllLiquidity(
+  banksClient: BanksClient,
+  params: RemoveAllLiquidityParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .removeAllLiquidity(tokenAAmountThreshold, tokenBAmountThreshold)
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function closePosition(
+  banksClient: BanksClient,
+  params: {
+    owner: Keypair;
+    pool: PublicKey;
+    position: PublicKey;
+  }
+) {
+  const { owner, pool, position } = params;
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const poolAuthority = derivePoolAuthority();
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const transaction = await program.methods
+    .closePosition()
+    .accountsPartial({
+      positionNftMint: positionState.nftMint,
+      positionNftAccount,
+      pool,
+      position,
+      poolAuthority,
+      rentReceiver: owner.publicKey,
+      owner: owner.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function mergePositions(
+  banksClient: BanksClient,
+  params: {
+    owner: Keypair;
+    pool: PublicKey;
+    position: PublicKey;
+    sourcePosition: PublicKey;
+  }
+) {
+  const { owner, pool, position, sourcePosition } = params;
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const sourcePositionState = await getPosition(banksClient, sourcePosition);
+  const poolAuthority = derivePoolAuthority();
+
+  const transaction = await program.methods
+    .mergePositions()
+    .accountsPartial({
+      pool,
+      position,
+      positionNftAccount: derivePositionNftAccount(positionState.nftMint),
+      sourcePosition,
+      sourcePositionNftMint: sourcePositionState.nftMint,
+      sourcePositionNftAccount: derivePositionNftAccount(
+        sourcePositionState.nftMint
+      ),
+      poolAuthority,
+      rentReceiver: owner.publicKey,
+      owner: owner.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type SwapParams = {
+  payer: Keypair;
+  pool: PublicKey;
+  inputTokenMint: PublicKey;
+  outputTokenMint: PublicKey;
+  amountIn: BN;
+  minimumAmountOut: BN;
+  // referral account accruing referral fee
+  referral: PublicKey | null;
+  // token account receiving referral fee in the swap, instead of a referral
+  referralTokenAccount?: PublicKey;
+  feeDiscountRegistry?: PublicKey;
+  // token account of discount token, associated token account of payer by default
+  feeDiscountTokenAccount?: PublicKey;
+  vaultWhitelist?: PublicKey;
+  swapAllowlistEntry?: PublicKey;
+  launchBuyTracker?: PublicKey;
+  poolStats?: PublicKey;
+  // trade mining and trader reward of payer, to accrue trade mining rewards
+  tradeMining?: PublicKey;
+  traderReward?: PublicKey;
+  // required by pools with an active cpi guard
+  instructionsSysvar?: PublicKey;
+  // token badges and extra accounts of transfer hooks
+  remainingAccounts?: AccountMeta[];
+};
+
+export async function swap(banksClient: BanksClient, params: SwapParams) {
+  const {
+    payer,
+    pool,
+    inputTokenMint,
+    outputTokenMint,
+    amountIn,
+    minimumAmountOut,
+    referral,
+    referralTokenAccount,
+    feeDiscountRegistry,
+    vaultWhitelist,
+    swapAllowlistEntry,
+    launchBuyTracker,
+    poolStats,
+    tradeMining,
+    traderReward,
+    instructionsSysvar,
+    remainingAccounts = [],
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+  const inputTokenAccount = getAssociatedTokenAddressSync(
+    inputTokenMint,
+    payer.publicKey,
+    true,
+    tokenAProgram
+  );
+  const outputTokenAccount = getAssociatedTokenAddressSync(
+    outputTokenMint,
+    payer.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  let feeDiscountTokenAccount = params.feeDiscountTokenAccount ?? null;
+  if (feeDiscountRegistry && !feeDiscountTokenAccount) {
+    const { mint } = await getFeeDiscountRegistry(
+      banksClient,
+      feeDiscountRegistry
+    );
+    feeDiscountTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      payer.publicKey,
+      true,
+      (await banksClient.getAccount(mint)).owner
+    );
+  }
+
+  const transaction = await program.methods
+    .swap({
+      amountIn,
+      minimumAmountOut,
+    })
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      payer: payer.publicKey,
+      inputTokenAccount,
+      outputTokenAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+      referralTokenAccount: referralTokenAccount ?? null,
+    })
+    // optional accounts of swap are looked up in remaining accounts
+    .remainingAccounts(
+      [
+        { pubkey: referral, isWritable: true },
+        { pubkey: feeDiscountRegistry, isWritable: false },
+        { pubkey: feeDiscountTokenAccount, isWritable: false },
+        { pubkey: vaultWhitelist, isWritable: false },
+        { pubkey: swapAllowlistEntry, isWritable: false },
+        { pubkey: launchBuyTracker, isWritable: true },
+        { pubkey: instructionsSysvar, isWritable: false },
+        { pubkey: poolStats, isWritable: true },
+        { pubkey: tradeMining, isWritable: true },
+        { pubkey: traderReward, isWritable: true },
+      ]
+        .filter(({ pubkey }) => pubkey)
+        .map(({ pubkey, isWritable }) => ({
+          pubkey,
+          isSigner: false,
+          isWritable,
+        }))
+        .concat(remainingAccounts)
+    )
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type CreateFeeDiscountRegistryParams = {
+  admin: Keypair;
+  pool: PublicKey;
+  mint: PublicKey;
+  tiers: FeeDiscountTierParameters[];
+};
+export async function createFeeDiscountRegistry(
+  banksClient: BanksClient,
+  params: CreateFeeDiscountRegistryParams
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const { admin, pool, mint, tiers } = params;
+  const feeDiscountRegistry = deriveFeeDiscountRegistryAddress(pool);
+
+  const transaction = await program.methods
+    .createFeeDiscountRegistry(tiers)
+    .accountsPartial({
+      pool,
+      feeDiscountRegistry,
+      mint,
+      admin: admin.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return feeDiscountRegistry;
+}
+
+export type UpdateFeeDiscountRegistryParams = {
+  admin: Keypair;
+  feeDiscountRegistry: PublicKey;
+  tiers: FeeDiscountTierParameters[];
+};
+export async function updateFeeDiscountRegistry(
+  banksClient: BanksClient,
+  params: UpdateFeeDiscountRegistryParams
+) {
+  const program = createCpAmmProgram();
+  const { admin, feeDiscountRegistry, tiers } = params;
+
+  const transaction = await program.methods
+    .updateFeeDiscountRegistry(tiers)
+    .accountsPartial({
+      feeDiscountRegistry,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type CreateVaultWhitelistParams = {
+  signer: Keypair;
+  pool: PublicKey;
+  vaults: PublicKey[];
+};
+export async function createVaultWhitelist(
+  banksClient: BanksClient,
+  params: CreateVaultWhitelistParams
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const { signer, pool, vaults } = params;
+  const vaultWhitelist = deriveVaultWhitelistAddress(pool);
+
+  const transaction = await program.methods
+    .createVaultWhitelist(vaults)
+    .accountsPartial({
+      pool,
+      vaultWhitelist,
+      signer: signer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const vaultWhitelistState = await getVaultWhitelist(
+    banksClient,
+    vaultWhitelist
+  );
+  expect(vaultWhitelistState.pool.toString()).eq(pool.toString());
+
+  return vaultWhitelist;
+}
+
+export type CreatePoolMetadataParams = {
+  signer: Keypair;
+  pool: PublicKey;
+  params: PoolMetadataParameters;
+};
+export async function createPoolMetadata(
+  banksClient: BanksClient,
+  params: CreatePoolMetadataParams
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const { signer, pool } = params;
+  const poolMetadata = derivePoolMetadataAddress(pool);
+
+  const transaction = await program.methods
+    .createPoolMetadata(params.params)
+    .accountsPartial({
+      pool,
+      poolMetadata,
+      signer: signer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolMetadataState = await getPoolMetadata(banksClient, poolMetadata);
+  expect(poolMetadataState.pool.toString()).eq(pool.toString());
+
+  return poolMetadata;
+}
+
+export type UpdatePoolMetadataParams = CreatePoolMetadataParams;
+export async function updatePoolMetadata(
+  banksClient: BanksClient,
+  params: UpdatePoolMetadataParams
+) {
+  const program = createCpAmmProgram();
+  const { signer, pool } = params;
+
+  const transaction = await program.methods
+    .updatePoolMetadata(params.params)
+    .accountsPartial({
+      pool,
+      poolMetadata: derivePoolMetadataAddress(pool),
+      signer: signer.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type UpdateVaultWhitelistParams = {
+  signer: Keypair;
   pool: PublicKey;
-  rewardMint: PublicKey;
+  vaults: PublicKey[];
 };
-
-export async function initializeReward(
+export async function updateVaultWhitelist(
   banksClient: BanksClient,
-  params: InitializeRewardParams
-): Promise<void> {
-  const { index, rewardDuration, pool, rewardMint, payer } = params;
+  params: UpdateVaultWhitelistParams
+) {
   const program = createCpAmmProgram();
+  const { signer, pool, vaults } = params;
 
-  const poolAuthority = derivePoolAuthority();
-  const rewardVault = deriveRewardVaultAddress(pool, index);
+  const transaction = await program.methods
+    .updateVaultWhitelist(vaults)
+    .accountsPartial({
+      pool,
+      vaultWhitelist: deriveVaultWhitelistAddress(pool),
+      signer: signer.publicKey,
+    })
+    .transaction();
 
-  const tokenProgram = (await banksClient.getAccount(rewardMint)).owner;
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function createSwapAllowlistEntry(
+  banksClient: BanksClient,
+  swapAllowlistAuthority: Keypair,
+  pool: PublicKey,
+  wallet: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const swapAllowlistEntry = deriveSwapAllowlistEntryAddress(pool, wallet);
 
   const transaction = await program.methods
-    .initializeReward(index, rewardDuration, payer.publicKey)
+    .createSwapAllowlistEntry()
     .accountsPartial({
       pool,
-      poolAuthority,
-      rewardVault,
-      rewardMint,
-      admin: payer.publicKey,
-      tokenProgram,
+      swapAllowlistEntry,
+      wallet,
+      swapAllowlistAuthority: swapAllowlistAuthority.publicKey,
+      payer: swapAllowlistAuthority.publicKey,
       systemProgram: SystemProgram.programId,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer);
+  transaction.sign(swapAllowlistAuthority);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  // validate reward data
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.rewardInfos[index].initialized).eq(1);
-  expect(poolState.rewardInfos[index].vault.toString()).eq(
-    rewardVault.toString()
-  );
-  expect(poolState.rewardInfos[index].mint.toString()).eq(
-    rewardMint.toString()
-  );
+  return swapAllowlistEntry;
 }
 
-export type UpdateRewardDurationParams = {
-  index: number;
-  admin: Keypair;
-  pool: PublicKey;
-  newDuration: BN;
-};
-
-export async function updateRewardDuration(
+export async function closeSwapAllowlistEntry(
   banksClient: BanksClient,
-  params: UpdateRewardDurationParams
-): Promise<void> {
-  const { pool, admin, index, newDuration } = params;
+  swapAllowlistAuthority: Keypair,
+  pool: PublicKey,
+  swapAllowlistEntry: PublicKey
+) {
   const program = createCpAmmProgram();
+
   const transaction = await program.methods
-    .updateRewardDuration(index, newDuration)
+    .closeSwapAllowlistEntry()
     .accountsPartial({
       pool,
-      admin: admin.publicKey,
+      swapAllowlistEntry,
+      swapAllowlistAuthority: swapAllowlistAuthority.publicKey,
+      rentReceiver: swapAllowlistAuthority.publicKey,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(admin);
+  transaction.sign(swapAllowlistAuthority);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.rewardInfos[index].rewardDuration.toNumber()).eq(
-    newDuration.toNumber()
-  );
+  const account = await banksClient.getAccount(swapAllowlistEntry);
+  expect(account).to.be.null;
 }
 
-export type UpdateRewardFunderParams = {
-  index: number;
-  admin: Keypair;
-  pool: PublicKey;
-  newFunder: PublicKey;
-};
-
-export async function updateRewardFunder(
+export async function createLpAllowlistEntry(
   banksClient: BanksClient,
-  params: UpdateRewardFunderParams
-): Promise<void> {
-  const { pool, admin, index, newFunder } = params;
+  lpAllowlistAuthority: Keypair,
+  pool: PublicKey,
+  wallet: PublicKey
+): Promise<PublicKey> {
   const program = createCpAmmProgram();
+  const lpAllowlistEntry = deriveLpAllowlistEntryAddress(pool, wallet);
+
   const transaction = await program.methods
-    .updateRewardFunder(index, newFunder)
+    .createLpAllowlistEntry()
     .accountsPartial({
       pool,
-      admin: admin.publicKey,
+      lpAllowlistEntry,
+      wallet,
+      lpAllowlistAuthority: lpAllowlistAuthority.publicKey,
+      payer: lpAllowlistAuthority.publicKey,
+      systemProgram: SystemProgram.programId,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(admin);
+  transaction.sign(lpAllowlistAuthority);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.rewardInfos[index].funder.toString()).eq(
-    newFunder.toString()
-  );
+  return lpAllowlistEntry;
 }
 
-export type FundRewardParams = {
-  funder: Keypair;
-  index: number;
-  pool: PublicKey;
-  carryForward: boolean;
-  amount: BN;
-};
-
-export async function fundReward(
+export async function closeLpAllowlistEntry(
   banksClient: BanksClient,
-  params: FundRewardParams
-): Promise<void> {
-  const { index, carryForward, pool, funder, amount } = params;
+  lpAllowlistAuthority: Keypair,
+  pool: PublicKey,
+  lpAllowlistEntry: PublicKey
+) {
   const program = createCpAmmProgram();
 
-  const poolState = await getPool(banksClient, pool);
-  const rewardVault = poolState.rewardInfos[index].vault;
-  const tokenProgram = (
-    await banksClient.getAccount(poolState.rewardInfos[index].mint)
-  ).owner;
-  const funderTokenAccount = getAssociatedTokenAddressSync(
-    poolState.rewardInfos[index].mint,
-    funder.publicKey,
-    true,
-    tokenProgram
-  );
+  const transaction = await program.methods
+    .closeLpAllowlistEntry()
+    .accountsPartial({
+      pool,
+      lpAllowlistEntry,
+      lpAllowlistAuthority: lpAllowlistAuthority.publicKey,
+      rentReceiver: lpAllowlistAuthority.publicKey,
+    })
+    .transaction();
 
-  const rewardVaultPreBalance = Number(
-    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
-      .amount
-  );
-  const funderPreBalance = Number(
-    AccountLayout.decode(
-      (await banksClient.getAccount(funderTokenAccount)).data
-    ).amount
-  );
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(lpAllowlistAuthority);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const account = await banksClient.getAccount(lpAllowlistEntry);
+  expect(account).to.be.null;
+}
+
+export async function createLaunchBuyTracker(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  wallet: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const launchBuyTracker = deriveLaunchBuyTrackerAddress(pool, wallet);
 
   const transaction = await program.methods
-    .fundReward(index, amount, carryForward)
+    .createLaunchBuyTracker()
     .accountsPartial({
       pool,
-      rewardVault: poolState.rewardInfos[index].vault,
-      rewardMint: poolState.rewardInfos[index].mint,
-      funderTokenAccount,
-      funder: funder.publicKey,
-      tokenProgram,
+      launchBuyTracker,
+      wallet,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(funder);
+  transaction.sign(payer);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  const rewardVaultPostBalance = Number(
-    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
-      .amount
-  );
-  const funderPostBalance = Number(
-    AccountLayout.decode(
-      (await banksClient.getAccount(funderTokenAccount)).data
-    ).amount
-  );
+  return launchBuyTracker;
+}
 
-  // expect(funderPreBalance - funderPostBalance).eq(amount.toNumber());
+export async function createPoolStats(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const poolStats = derivePoolStatsAddress(pool);
 
-  // expect(rewardVaultPostBalance - rewardVaultPreBalance).eq(amount.toNumber());
+  const transaction = await program.methods
+    .createPoolStats()
+    .accountsPartial({
+      pool,
+      poolStats,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return poolStats;
 }
 
-export type ClaimRewardParams = {
-  index: number;
-  user: Keypair;
-  position: PublicKey;
+export async function getPoolStats(
+  banksClient: BanksClient,
+  poolStats: PublicKey
+): Promise<PoolStats> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(poolStats);
+  return program.coder.accounts.decode("poolStats", Buffer.from(account.data));
+}
+
+export type InitializeTradeMiningParams = {
+  signer: Keypair;
   pool: PublicKey;
+  rewardMint: PublicKey;
+  funder: PublicKey;
+  rewardPerFeeA: BN;
+  rewardPerFeeB: BN;
+  endTime: BN;
 };
 
-export async function claimReward(
+export async function initializeTradeMining(
   banksClient: BanksClient,
-  params: ClaimRewardParams
-): Promise<void> {
-  const { index, pool, user, position } = params;
+  params: InitializeTradeMiningParams
+): Promise<PublicKey> {
+  const {
+    signer,
+    pool,
+    rewardMint,
+    funder,
+    rewardPerFeeA,
+    rewardPerFeeB,
+    endTime,
+  } = params;
   const program = createCpAmmProgram();
+  const tradeMining = deriveTradeMiningAddress(pool);
+  const tokenProgram = (await banksClient.getAccount(rewardMint)).owner;
 
-  const poolState = await getPool(banksClient, pool);
-  const positionState = await getPosition(banksClient, position);
-  const poolAuthority = derivePoolAuthority();
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const transaction = await program.methods
+    .initializeTradeMining(funder, rewardPerFeeA, rewardPerFeeB, endTime)
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      tradeMining,
+      rewardVault: deriveTradeMiningVaultAddress(pool),
+      rewardMint,
+      programAuthority: deriveProgramAuthorityAddress(),
+      signer: signer.publicKey,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
 
-  // TODO should use token flag in pool state to get token program ID
-  const tokenProgram = (
-    await banksClient.getAccount(poolState.rewardInfos[index].mint)
-  ).owner;
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(signer);
 
-  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
-    banksClient,
-    user,
-    poolState.rewardInfos[index].mint,
-    user.publicKey,
-    tokenProgram
-  );
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return tradeMining;
+}
+
+export async function fundTradeMining(
+  banksClient: BanksClient,
+  funder: Keypair,
+  pool: PublicKey,
+  amount: BN
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const tradeMining = deriveTradeMiningAddress(pool);
+  const { mint, vault } = await getTradeMining(banksClient, tradeMining);
+  const tokenProgram = (await banksClient.getAccount(mint)).owner;
 
   const transaction = await program.methods
-    .claimReward(index)
+    .fundTradeMining(amount)
     .accountsPartial({
       pool,
-      positionNftAccount,
-      rewardVault: poolState.rewardInfos[index].vault,
-      rewardMint: poolState.rewardInfos[index].mint,
-      poolAuthority,
-      position,
-      userTokenAccount,
-      owner: user.publicKey,
+      tradeMining,
+      vault,
+      rewardMint: mint,
+      funderTokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        funder.publicKey,
+        true,
+        tokenProgram
+      ),
+      programAuthority: deriveProgramAuthorityAddress(),
+      funder: funder.publicKey,
       tokenProgram,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(user);
+  transaction.sign(funder);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type WithdrawIneligibleRewardParams = {
-  index: number;
-  funder: Keypair;
-  pool: PublicKey;
-};
-
-export async function withdrawIneligibleReward(
+export async function withdrawUnaccruedTradeMining(
   banksClient: BanksClient,
-  params: WithdrawIneligibleRewardParams
+  funder: Keypair,
+  pool: PublicKey
 ): Promise<void> {
-  const { index, pool, funder } = params;
   const program = createCpAmmProgram();
-
-  const poolState = await getPool(banksClient, pool);
-  const poolAuthority = derivePoolAuthority();
-  const tokenProgram = (
-    await banksClient.getAccount(poolState.rewardInfos[index].mint)
-  ).owner;
-  const funderTokenAccount = getAssociatedTokenAddressSync(
-    poolState.rewardInfos[index].mint,
+  const tradeMining = deriveTradeMiningAddress(pool);
+  const { mint, vault } = await getTradeMining(banksClient, tradeMining);
+  const tokenProgram = (await banksClient.getAccount(mint)).owner;
+  const funderTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    funder,
+    mint,
     funder.publicKey,
-    true,
     tokenProgram
   );
 
   const transaction = await program.methods
-    .withdrawIneligibleReward(index)
+    .withdrawUnaccruedTradeMining()
     .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
       pool,
-      rewardVault: poolState.rewardInfos[index].vault,
-      rewardMint: poolState.rewardInfos[index].mint,
-      poolAuthority,
+      tradeMining,
+      vault,
+      rewardMint: mint,
       funderTokenAccount,
+      programAuthority: deriveProgramAuthorityAddress(),
       funder: funder.publicKey,
       tokenProgram,
     })
@@ -1128,264 +3699,213 @@ export async function withdrawIneligibleReward(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export async function refreshVestings(
+export async function createTraderReward(
   banksClient: BanksClient,
-  position: PublicKey,
-  pool: PublicKey,
-  owner: PublicKey,
   payer: Keypair,
-  vestings: PublicKey[]
-) {
+  pool: PublicKey,
+  trader: PublicKey
+): Promise<PublicKey> {
   const program = createCpAmmProgram();
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const traderReward = deriveTraderRewardAddress(pool, trader);
+
   const transaction = await program.methods
-    .refreshVesting()
+    .createTraderReward()
     .accountsPartial({
-      position,
-      positionNftAccount,
       pool,
-      owner,
+      traderReward,
+      trader,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
     })
-    .remainingAccounts(
-      vestings.map((pubkey) => {
-        return {
-          isSigner: false,
-          isWritable: true,
-          pubkey,
-        };
-      })
-    )
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
   transaction.sign(payer);
 
   await processTransactionMaybeThrow(banksClient, transaction);
+
+  return traderReward;
 }
 
-export async function permanentLockPosition(
+export async function claimTradeMiningReward(
   banksClient: BanksClient,
-  position: PublicKey,
-  owner: Keypair,
-  payer: Keypair
-) {
+  trader: Keypair,
+  pool: PublicKey
+): Promise<void> {
   const program = createCpAmmProgram();
-
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const tradeMining = deriveTradeMiningAddress(pool);
+  const { mint, vault } = await getTradeMining(banksClient, tradeMining);
+  const tokenProgram = (await banksClient.getAccount(mint)).owner;
+  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    trader,
+    mint,
+    trader.publicKey,
+    tokenProgram
+  );
 
   const transaction = await program.methods
-    .permanentLockPosition(positionState.unlockedLiquidity)
+    .claimTradeMiningReward()
     .accountsPartial({
-      position,
-      positionNftAccount,
-      pool: positionState.pool,
-      owner: owner.publicKey,
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      tradeMining,
+      traderReward: deriveTraderRewardAddress(pool, trader.publicKey),
+      vault,
+      rewardMint: mint,
+      userTokenAccount,
+      trader: trader.publicKey,
+      tokenProgram,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, owner);
+  transaction.sign(trader);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export async function lockPosition(
+export async function getTradeMining(
   banksClient: BanksClient,
-  position: PublicKey,
-  owner: Keypair,
-  payer: Keypair,
-  params: LockPositionParams
-) {
+  tradeMining: PublicKey
+): Promise<TradeMining> {
   const program = createCpAmmProgram();
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
-
-  const vestingKP = Keypair.generate();
-
-  const transaction = await program.methods
-    .lockPosition(params)
-    .accountsPartial({
-      position,
-      positionNftAccount,
-      vesting: vestingKP.publicKey,
-      owner: owner.publicKey,
-      pool: positionState.pool,
-      program: CP_AMM_PROGRAM_ID,
-      systemProgram: SystemProgram.programId,
-      payer: payer.publicKey,
-    })
-    .transaction();
-
-  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, owner, vestingKP);
+  const account = await banksClient.getAccount(tradeMining);
+  return program.coder.accounts.decode(
+    "tradeMining",
+    Buffer.from(account.data)
+  );
+}
 
-  await processTransactionMaybeThrow(banksClient, transaction);
+export async function getTraderReward(
+  banksClient: BanksClient,
+  traderReward: PublicKey
+): Promise<TraderReward> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(traderReward);
+  return program.coder.accounts.decode(
+    "traderReward",
+    Buffer.from(account.data)
+  );
+}
 
-  return vestingKP.publicKey;
+export async function getRewardVestingEscrow(
+  banksClient: BanksClient,
+  rewardVestingEscrow: PublicKey
+): Promise<RewardVestingEscrow> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(rewardVestingEscrow);
+  return program.coder.accounts.decode(
+    "rewardVestingEscrow",
+    Buffer.from(account.data)
+  );
 }
 
-export async function createPosition(
+export async function createReferral(
   banksClient: BanksClient,
   payer: Keypair,
-  owner: PublicKey,
-  pool: PublicKey
+  pool: PublicKey,
+  referrer: PublicKey
 ): Promise<PublicKey> {
   const program = createCpAmmProgram();
-
-  const positionNftKP = Keypair.generate();
-  const position = derivePositionAddress(positionNftKP.publicKey);
-  const poolAuthority = derivePoolAuthority();
-  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+  const referral = deriveReferralAddress(pool, referrer);
 
   const transaction = await program.methods
-    .createPosition()
+    .createReferral()
     .accountsPartial({
-      owner,
-      positionNftMint: positionNftKP.publicKey,
-      poolAuthority,
-      positionNftAccount,
-      payer: payer.publicKey,
       pool,
-      position,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      referral,
+      referrer,
+      payer: payer.publicKey,
       systemProgram: SystemProgram.programId,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, positionNftKP);
+  transaction.sign(payer);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  const positionState = await getPosition(banksClient, position);
-
-  expect(positionState.nftMint.toString()).eq(
-    positionNftKP.publicKey.toString()
-  );
-
-  const positionNftData = AccountLayout.decode(
-    (await banksClient.getAccount(positionNftAccount)).data
-  );
-
-  // validate metadata
-  const tlvData = (
-    await banksClient.getAccount(positionState.nftMint)
-  ).data.slice(ACCOUNT_SIZE + ACCOUNT_TYPE_SIZE);
-  const metadata = unpack(
-    getExtensionData(ExtensionType.TokenMetadata, Buffer.from(tlvData))
-  );
-  expect(metadata.name).eq("Meteora Position NFT");
-  expect(metadata.symbol).eq("MPN");
-
-  // validate metadata pointer
-  const metadataAddress = MetadataPointerLayout.decode(
-    getExtensionData(ExtensionType.MetadataPointer, Buffer.from(tlvData))
-  ).metadataAddress;
-  expect(metadataAddress.toString()).eq(positionState.nftMint.toString());
-
-  // validate owner
-  expect(positionNftData.owner.toString()).eq(owner.toString());
-  expect(Number(positionNftData.amount)).eq(1);
-  expect(positionNftData.mint.toString()).eq(
-    positionNftKP.publicKey.toString()
-  );
+  const referralState = await getReferral(banksClient, referral);
+  expect(referralState.pool.toString()).eq(pool.toString());
+  expect(referralState.referrer.toString()).eq(referrer.toString());
 
-  return position;
+  return referral;
 }
 
-export type AddLiquidityParams = {
-  owner: Keypair;
+export type ClaimReferralFeeParams = {
+  referrer: Keypair;
   pool: PublicKey;
-  position: PublicKey;
-  liquidityDelta: BN;
-  tokenAAmountThreshold: BN;
-  tokenBAmountThreshold: BN;
+  maxAmountA?: BN;
+  maxAmountB?: BN;
 };
-
-export async function addLiquidity(
+export async function claimReferralFee(
   banksClient: BanksClient,
-  params: AddLiquidityParams
+  params: ClaimReferralFeeParams
 ) {
-  const {
-    owner,
-    pool,
-    position,
-    liquidityDelta,
-    tokenAAmountThreshold,
-    tokenBAmountThreshold,
-  } = params;
-
   const program = createCpAmmProgram();
+  const { referrer, pool, maxAmountA, maxAmountB } = params;
+  const poolAuthority = derivePoolAuthority();
+  const referral = deriveReferralAddress(pool, referrer.publicKey);
   const poolState = await getPool(banksClient, pool);
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
-
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
     .owner;
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
-
-  const tokenAAccount = getAssociatedTokenAddressSync(
+  const tokenAAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    referrer,
     poolState.tokenAMint,
-    owner.publicKey,
-    true,
+    referrer.publicKey,
     tokenAProgram
   );
-  const tokenBAccount = getAssociatedTokenAddressSync(
+  const tokenBAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    referrer,
     poolState.tokenBMint,
-    owner.publicKey,
-    true,
+    referrer.publicKey,
     tokenBProgram
   );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .addLiquidity({
-      liquidityDelta,
-      tokenAAmountThreshold,
-      tokenBAmountThreshold,
-    })
+    .claimReferralFee(maxAmountA ?? U64_MAX, maxAmountB ?? U64_MAX)
     .accountsPartial({
+      poolAuthority,
       pool,
-      position,
-      positionNftAccount,
-      owner: owner.publicKey,
+      referral,
       tokenAAccount,
       tokenBAccount,
-      tokenAVault,
-      tokenBVault,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      referrer: referrer.publicKey,
       tokenAProgram,
       tokenBProgram,
-      tokenAMint,
-      tokenBMint,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(owner);
+  transaction.sign(referrer);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type RemoveLiquidityParams = AddLiquidityParams;
+export type ClaimpositionFeeParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  feeBeneficiary?: PublicKey;
+  maxAmountA?: BN;
+  maxAmountB?: BN;
+};
 
-export async function removeLiquidity(
+export async function claimPositionFee(
   banksClient: BanksClient,
-  params: RemoveLiquidityParams
+  params: ClaimpositionFeeParams
 ) {
-  const {
-    owner,
-    pool,
-    position,
-    liquidityDelta,
-    tokenAAmountThreshold,
-    tokenBAmountThreshold,
-  } = params;
+  const { owner, pool, position, feeBeneficiary, maxAmountA, maxAmountB } =
+    params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
@@ -1398,16 +3918,19 @@ export async function removeLiquidity(
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
 
-  const tokenAAccount = getAssociatedTokenAddressSync(
+  const feeReceiver = feeBeneficiary ?? owner.publicKey;
+  const tokenAAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
     poolState.tokenAMint,
-    owner.publicKey,
-    true,
+    feeReceiver,
     tokenAProgram
   );
-  const tokenBAccount = getAssociatedTokenAddressSync(
+  const tokenBAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
     poolState.tokenBMint,
-    owner.publicKey,
-    true,
+    feeReceiver,
     tokenBProgram
   );
   const tokenAVault = poolState.tokenAVault;
@@ -1416,17 +3939,13 @@ export async function removeLiquidity(
   const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .removeLiquidity({
-      liquidityDelta,
-      tokenAAmountThreshold,
-      tokenBAmountThreshold,
-    })
+    .claimPositionFee(maxAmountA ?? U64_MAX, maxAmountB ?? U64_MAX)
     .accountsPartial({
       poolAuthority,
+      owner: owner.publicKey,
       pool,
       position,
       positionNftAccount,
-      owner: owner.publicKey,
       tokenAAccount,
       tokenBAccount,
       tokenAVault,
@@ -1444,25 +3963,17 @@ export async function removeLiquidity(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type RemoveAllLiquidityParams = {
+export type ClaimAllParams = {
   owner: Keypair;
   pool: PublicKey;
   position: PublicKey;
-  tokenAAmountThreshold: BN;
-  tokenBAmountThreshold: BN;
 };
 
-export async function removeAllLiquidity(
+export async function claimAll(
   banksClient: BanksClient,
-  params: RemoveAllLiquidityParams
+  params: ClaimAllParams
 ) {
-  const {
-    owner,
-    pool,
-    position,
-    tokenAAmountThreshold,
-    tokenBAmountThreshold,
-  } = params;
+  const { owner, pool, position } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
@@ -1487,28 +3998,47 @@ export async function removeAllLiquidity(
     true,
     tokenBProgram
   );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
+
+  // reward vault, reward mint, user token account and token program of every initialized reward
+  const remainingAccounts: AccountMeta[] = [];
+  for (const rewardInfo of poolState.rewardInfos) {
+    if (rewardInfo.initialized == 0) {
+      continue;
+    }
+    const tokenProgram = (await banksClient.getAccount(rewardInfo.mint)).owner;
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      banksClient,
+      owner,
+      rewardInfo.mint,
+      owner.publicKey,
+      tokenProgram
+    );
+    remainingAccounts.push(
+      { pubkey: rewardInfo.vault, isSigner: false, isWritable: true },
+      { pubkey: rewardInfo.mint, isSigner: false, isWritable: false },
+      { pubkey: userTokenAccount, isSigner: false, isWritable: true },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false }
+    );
+  }
 
   const transaction = await program.methods
-    .removeAllLiquidity(tokenAAmountThreshold, tokenBAmountThreshold)
+    .claimAll()
     .accountsPartial({
       poolAuthority,
+      owner: owner.publicKey,
       pool,
       position,
       positionNftAccount,
-      owner: owner.publicKey,
       tokenAAccount,
       tokenBAccount,
-      tokenAVault,
-      tokenBVault,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
       tokenAProgram,
       tokenBProgram,
-      tokenAMint,
-      tokenBMint,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
     })
+    .remainingAccounts(remainingAccounts)
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
@@ -1517,29 +4047,27 @@ export async function removeAllLiquidity(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export async function closePosition(
+export type SetPositionFeeBeneficiaryParams = {
+  owner: Keypair;
+  position: PublicKey;
+  feeBeneficiary: PublicKey;
+};
+
+export async function setPositionFeeBeneficiary(
   banksClient: BanksClient,
-  params: {
-    owner: Keypair;
-    pool: PublicKey;
-    position: PublicKey;
-  }
+  params: SetPositionFeeBeneficiaryParams
 ) {
-  const { owner, pool, position } = params;
+  const { owner, position, feeBeneficiary } = params;
+
   const program = createCpAmmProgram();
   const positionState = await getPosition(banksClient, position);
-  const poolAuthority = derivePoolAuthority();
   const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
 
   const transaction = await program.methods
-    .closePosition()
+    .setPositionFeeBeneficiary(feeBeneficiary)
     .accountsPartial({
-      positionNftMint: positionState.nftMint,
-      positionNftAccount,
-      pool,
       position,
-      poolAuthority,
-      rentReceiver: owner.publicKey,
+      positionNftAccount,
       owner: owner.publicKey,
     })
     .transaction();
@@ -1548,147 +4076,227 @@ export async function closePosition(
   transaction.sign(owner);
 
   await processTransactionMaybeThrow(banksClient, transaction);
+
+  const newPositionState = await getPosition(banksClient, position);
+  expect(newPositionState.feeBeneficiary.toString()).eq(
+    feeBeneficiary.toString()
+  );
 }
 
-export type SwapParams = {
-  payer: Keypair;
-  pool: PublicKey;
-  inputTokenMint: PublicKey;
-  outputTokenMint: PublicKey;
-  amountIn: BN;
-  minimumAmountOut: BN;
-  referralTokenAccount: PublicKey | null;
+export type SetPositionOperatorParams = {
+  owner: Keypair;
+  position: PublicKey;
+  operator: PublicKey;
 };
 
-export async function swap(banksClient: BanksClient, params: SwapParams) {
-  const {
-    payer,
-    pool,
-    inputTokenMint,
-    outputTokenMint,
-    amountIn,
-    minimumAmountOut,
-    referralTokenAccount,
-  } = params;
+export async function setPositionOperator(
+  banksClient: BanksClient,
+  params: SetPositionOperatorParams
+) {
+  const { owner, position, operator } = params;
 
   const program = createCpAmmProgram();
-  const poolState = await getPool(banksClient, pool);
-
-  const poolAuthority = derivePoolAuthority();
-  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
-    .owner;
-
-  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
-    .owner;
-  const inputTokenAccount = getAssociatedTokenAddressSync(
-    inputTokenMint,
-    payer.publicKey,
-    true,
-    tokenAProgram
-  );
-  const outputTokenAccount = getAssociatedTokenAddressSync(
-    outputTokenMint,
-    payer.publicKey,
-    true,
-    tokenBProgram
-  );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
 
   const transaction = await program.methods
-    .swap({
-      amountIn,
-      minimumAmountOut,
-    })
+    .setPositionOperator(operator)
     .accountsPartial({
-      poolAuthority,
-      pool,
-      payer: payer.publicKey,
-      inputTokenAccount,
-      outputTokenAccount,
-      tokenAVault,
-      tokenBVault,
-      tokenAProgram,
-      tokenBProgram,
-      tokenAMint,
-      tokenBMint,
-      referralTokenAccount,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer);
+  transaction.sign(owner);
 
   await processTransactionMaybeThrow(banksClient, transaction);
+
+  const newPositionState = await getPosition(banksClient, position);
+  expect(newPositionState.operator.toString()).eq(operator.toString());
 }
 
-export type ClaimpositionFeeParams = {
+export type MintFeeReceiptParams = {
   owner: Keypair;
-  pool: PublicKey;
   position: PublicKey;
+  receiver: PublicKey;
 };
 
-export async function claimPositionFee(
+export async function mintFeeReceipt(
   banksClient: BanksClient,
-  params: ClaimpositionFeeParams
+  params: MintFeeReceiptParams
 ) {
-  const { owner, pool, position } = params;
+  const { owner, position, receiver } = params;
 
   const program = createCpAmmProgram();
-  const poolState = await getPool(banksClient, pool);
   const positionState = await getPosition(banksClient, position);
   const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const feeReceiptMint = deriveFeeReceiptMint(position);
+  const feeReceiptAccount = deriveFeeReceiptAccount(feeReceiptMint);
 
-  const poolAuthority = derivePoolAuthority();
+  const transaction = await program.methods
+    .mintFeeReceipt()
+    .accountsPartial({
+      pool: positionState.pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      feeReceiptMint,
+      receiver,
+      feeReceiptAccount,
+      poolAuthority: derivePoolAuthority(),
+      payer: owner.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const newPositionState = await getPosition(banksClient, position);
+  expect(newPositionState.feeReceiptMinted).eq(1);
+
+  return feeReceiptAccount;
+}
+
+export type ClaimPositionFeeWithReceiptParams = {
+  holder: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  feeReceiptAccount: PublicKey;
+};
+
+export async function claimPositionFeeWithReceipt(
+  banksClient: BanksClient,
+  params: ClaimPositionFeeWithReceiptParams
+) {
+  const { holder, pool, position, feeReceiptAccount } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
     .owner;
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
 
-  const tokenAAccount = getAssociatedTokenAddressSync(
+  const tokenAAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    holder,
     poolState.tokenAMint,
-    owner.publicKey,
-    true,
+    holder.publicKey,
     tokenAProgram
   );
-  const tokenBAccount = getAssociatedTokenAddressSync(
+  const tokenBAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    holder,
     poolState.tokenBMint,
-    owner.publicKey,
-    true,
+    holder.publicKey,
     tokenBProgram
   );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .claimPositionFee()
+    .claimPositionFeeWithReceipt(U64_MAX, U64_MAX)
     .accountsPartial({
-      poolAuthority,
-      owner: owner.publicKey,
+      poolAuthority: derivePoolAuthority(),
       pool,
       position,
-      positionNftAccount,
       tokenAAccount,
       tokenBAccount,
-      tokenAVault,
-      tokenBVault,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      feeReceiptMint: deriveFeeReceiptMint(position),
+      feeReceiptAccount,
+      holder: holder.publicKey,
       tokenAProgram,
       tokenBProgram,
-      tokenAMint,
-      tokenBMint,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(owner);
+  transaction.sign(holder);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
+export type PoolStateView = IdlTypes<CpAmm>["poolStateView"];
+export type PositionValueView = IdlTypes<CpAmm>["positionValueView"];
+export type PositionPendingView = IdlTypes<CpAmm>["positionPendingView"];
+
+async function simulateReturnData(
+  banksClient: BanksClient,
+  payer: Keypair,
+  transaction: Transaction
+): Promise<Buffer> {
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+  const result = await banksClient.simulateTransaction(transaction);
+  expect(result.result).to.be.null;
+  return Buffer.from(result.meta.returnData.data);
+}
+
+export async function getPoolState(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  liquidityDelta: BN
+): Promise<PoolStateView> {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .getPoolState(liquidityDelta)
+    .accountsPartial({
+      pool,
+    })
+    .transaction();
+  const data = await simulateReturnData(banksClient, payer, transaction);
+  return program.coder.types.decode("poolStateView", data);
+}
+
+export async function getPositionValue(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  position: PublicKey
+): Promise<PositionValueView> {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .getPositionValue()
+    .accountsPartial({
+      pool,
+      position,
+    })
+    .transaction();
+  const data = await simulateReturnData(banksClient, payer, transaction);
+  return program.coder.types.decode("positionValueView", data);
+}
+
+export async function getPositionPending(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  position: PublicKey,
+  rewardExtension?: PublicKey,
+  positionRewardExtension?: PublicKey
+): Promise<PositionPendingView> {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .getPositionPending()
+    .accountsPartial({
+      pool,
+      position,
+      rewardExtension: rewardExtension ?? null,
+      positionRewardExtension: positionRewardExtension ?? null,
+    })
+    .transaction();
+  const data = await simulateReturnData(banksClient, payer, transaction);
+  return program.coder.types.decode("positionPendingView", data);
+}
+
 export async function getPool(
   banksClient: BanksClient,
   pool: PublicKey
@@ -1747,3 +4355,71 @@ export async function getTokenBadge(
   const account = await banksClient.getAccount(tokenBadge);
   return program.coder.accounts.decode("tokenBadge", Buffer.from(account.data));
 }
+
+export async function getPartnerFeeReceiver(
+  banksClient: BanksClient,
+  partnerFeeReceiver: PublicKey
+): Promise<PartnerFeeReceiver> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(partnerFeeReceiver);
+  return program.coder.accounts.decode(
+    "partnerFeeReceiver",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getProgramAuthority(
+  banksClient: BanksClient
+): Promise<ProgramAuthority> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(deriveProgramAuthorityAddress());
+  return program.coder.accounts.decode(
+    "programAuthority",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getReferral(
+  banksClient: BanksClient,
+  referral: PublicKey
+): Promise<Referral> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(referral);
+  return program.coder.accounts.decode("referral", Buffer.from(account.data));
+}
+
+export async function getVaultWhitelist(
+  banksClient: BanksClient,
+  vaultWhitelist: PublicKey
+): Promise<VaultWhitelist> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(vaultWhitelist);
+  return program.coder.accounts.decode(
+    "vaultWhitelist",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getPoolMetadata(
+  banksClient: BanksClient,
+  poolMetadata: PublicKey
+): Promise<PoolMetadata> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(poolMetadata);
+  return program.coder.accounts.decode(
+    "poolMetadata",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getFeeDiscountRegistry(
+  banksClient: BanksClient,
+  feeDiscountRegistry: PublicKey
+): Promise<FeeDiscountRegistry> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(feeDiscountRegistry);
+  return program.coder.accounts.decode(
+    "feeDiscountRegistry",
+    Buffer.from(account.data)
+  );
+}
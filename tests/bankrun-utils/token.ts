@@ -1,6 +1,7 @@
 import {
   AccountLayout,
   createAssociatedTokenAccountInstruction,
+  createFreezeAccountInstruction,
   createInitializeMint2Instruction,
   createInitializeMintInstruction,
   createMintToInstruction,
@@ -57,7 +58,8 @@ export async function getOrCreateAssociatedTokenAccount(
 export async function createToken(
   banksClient: BanksClient,
   payer: Keypair,
-  mintAuthority: PublicKey
+  mintAuthority: PublicKey,
+  freezeAuthority: PublicKey | null = null
 ): Promise<PublicKey> {
   const mintKeypair = Keypair.generate();
   const rent = await banksClient.getRent();
@@ -75,7 +77,7 @@ export async function createToken(
     mintKeypair.publicKey,
     DECIMALS,
     mintAuthority,
-    null
+    freezeAuthority
   );
 
   let transaction = new Transaction();
@@ -148,6 +150,27 @@ export async function mintSplTokenTo(
   await banksClient.processTransaction(transaction);
 }
 
+export async function freezeTokenAccount(
+  banksClient: BanksClient,
+  freezeAuthority: Keypair,
+  mint: PublicKey,
+  tokenAccount: PublicKey
+) {
+  const freezeIx = createFreezeAccountInstruction(
+    tokenAccount,
+    mint,
+    freezeAuthority.publicKey
+  );
+
+  let transaction = new Transaction();
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.add(freezeIx);
+  transaction.sign(freezeAuthority);
+
+  await banksClient.processTransaction(transaction);
+}
+
 export async function getMint(banksClient: BanksClient, mint: PublicKey) {
   const account = await banksClient.getAccount(mint);
   const mintState = MintLayout.decode(account.data);
@@ -6,6 +6,15 @@ import {
   TOKEN_2022_PROGRAM_ID,
   createInitializeMetadataPointerInstruction,
   createMintToInstruction,
+  createInitializeTransferHookInstruction,
+  createUpdateTransferHookInstruction,
+  createInitializeInterestBearingMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeMintCloseAuthorityInstruction,
+  createInitializeDefaultAccountStateInstruction,
+  AccountState,
+  MintLayout,
+  ACCOUNT_SIZE,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -13,7 +22,7 @@ import {
   SystemProgram,
   Transaction,
 } from "@solana/web3.js";
-import { BanksClient } from "solana-bankrun";
+import { BanksClient, ProgramTestContext } from "solana-bankrun";
 import { DECIMALS } from "./constants";
 import { getOrCreateAssociatedTokenAccount } from "./token";
 const rawAmount = 1_000_000 * 10 ** DECIMALS; // 1 millions
@@ -67,6 +76,227 @@ export async function createToken2022(
   return mintKeypair.publicKey;
 }
 
+export async function createTransferHookToken2022(
+  banksClient: BanksClient,
+  payer: Keypair,
+  transferHookProgram: PublicKey
+): Promise<PublicKey> {
+  const mintKeypair = Keypair.generate();
+
+  const mintLen = getMintLen([ExtensionType.TransferHook]);
+  const mintLamports = (await banksClient.getRent()).minimumBalance(
+    BigInt(mintLen)
+  );
+  const transaction = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: mintKeypair.publicKey,
+      space: mintLen,
+      lamports: Number(mintLamports.toString()),
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    createInitializeTransferHookInstruction(
+      mintKeypair.publicKey,
+      payer.publicKey,
+      transferHookProgram,
+      TOKEN_2022_PROGRAM_ID
+    ),
+    createInitializeMint2Instruction(
+      mintKeypair.publicKey,
+      DECIMALS,
+      payer.publicKey,
+      null,
+      TOKEN_2022_PROGRAM_ID
+    )
+  );
+
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.sign(payer, mintKeypair);
+
+  await banksClient.processTransaction(transaction);
+
+  return mintKeypair.publicKey;
+}
+
+// mint with a rug-capable extension, payer is the permanent delegate, close
+// authority or freeze authority of frozen default account state
+export async function createRugCapableToken2022(
+  banksClient: BanksClient,
+  payer: Keypair,
+  extension:
+    | ExtensionType.PermanentDelegate
+    | ExtensionType.MintCloseAuthority
+    | ExtensionType.DefaultAccountState
+): Promise<PublicKey> {
+  const mintKeypair = Keypair.generate();
+  const mint = mintKeypair.publicKey;
+
+  const mintLen = getMintLen([extension]);
+  const mintLamports = (await banksClient.getRent()).minimumBalance(
+    BigInt(mintLen)
+  );
+  const initializeExtensionIx = {
+    [ExtensionType.PermanentDelegate]: () =>
+      createInitializePermanentDelegateInstruction(
+        mint,
+        payer.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    [ExtensionType.MintCloseAuthority]: () =>
+      createInitializeMintCloseAuthorityInstruction(
+        mint,
+        payer.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    [ExtensionType.DefaultAccountState]: () =>
+      createInitializeDefaultAccountStateInstruction(
+        mint,
+        AccountState.Frozen,
+        TOKEN_2022_PROGRAM_ID
+      ),
+  }[extension]();
+  const freezeAuthority =
+    extension == ExtensionType.DefaultAccountState ? payer.publicKey : null;
+
+  const transaction = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: mint,
+      space: mintLen,
+      lamports: Number(mintLamports.toString()),
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    initializeExtensionIx,
+    createInitializeMint2Instruction(
+      mint,
+      DECIMALS,
+      payer.publicKey,
+      freezeAuthority,
+      TOKEN_2022_PROGRAM_ID
+    )
+  );
+
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.sign(payer, mintKeypair);
+
+  await banksClient.processTransaction(transaction);
+
+  return mint;
+}
+
+// Pausable extension is unknown to the token 2022 program of bankrun, so the
+// mint account is written directly
+export async function setPausableToken2022(
+  context: ProgramTestContext,
+  mintAuthority: PublicKey
+): Promise<PublicKey> {
+  const mint = Keypair.generate().publicKey;
+  const pausableExtensionType = 26;
+  // pause authority and paused flag
+  const pausableConfigLen = 33;
+
+  const data = Buffer.alloc(ACCOUNT_SIZE + 1 + 4 + pausableConfigLen);
+  MintLayout.encode(
+    {
+      mintAuthorityOption: 1,
+      mintAuthority,
+      supply: BigInt(0),
+      decimals: DECIMALS,
+      isInitialized: true,
+      freezeAuthorityOption: 0,
+      freezeAuthority: PublicKey.default,
+    },
+    data
+  );
+  // account type of mint, followed by the extension
+  data.writeUInt8(1, ACCOUNT_SIZE);
+  data.writeUInt16LE(pausableExtensionType, ACCOUNT_SIZE + 1);
+  data.writeUInt16LE(pausableConfigLen, ACCOUNT_SIZE + 3);
+  mintAuthority.toBuffer().copy(data, ACCOUNT_SIZE + 5);
+
+  context.setAccount(mint, {
+    lamports: Number(
+      (await context.banksClient.getRent()).minimumBalance(
+        BigInt(data.length)
+      )
+    ),
+    data,
+    owner: TOKEN_2022_PROGRAM_ID,
+    executable: false,
+  });
+
+  return mint;
+}
+
+// interest-bearing mint without rate authority, `rate` in basis points
+export async function createInterestBearingToken2022(
+  banksClient: BanksClient,
+  payer: Keypair,
+  rate: number
+): Promise<PublicKey> {
+  const mintKeypair = Keypair.generate();
+
+  const mintLen = getMintLen([ExtensionType.InterestBearingConfig]);
+  const mintLamports = (await banksClient.getRent()).minimumBalance(
+    BigInt(mintLen)
+  );
+  const transaction = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: mintKeypair.publicKey,
+      space: mintLen,
+      lamports: Number(mintLamports.toString()),
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    createInitializeInterestBearingMintInstruction(
+      mintKeypair.publicKey,
+      PublicKey.default,
+      rate,
+      TOKEN_2022_PROGRAM_ID
+    ),
+    createInitializeMint2Instruction(
+      mintKeypair.publicKey,
+      DECIMALS,
+      payer.publicKey,
+      null,
+      TOKEN_2022_PROGRAM_ID
+    )
+  );
+
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.sign(payer, mintKeypair);
+
+  await banksClient.processTransaction(transaction);
+
+  return mintKeypair.publicKey;
+}
+
+export async function updateTransferHookProgram(
+  banksClient: BanksClient,
+  authority: Keypair,
+  mint: PublicKey,
+  transferHookProgram: PublicKey
+) {
+  const transaction = new Transaction().add(
+    createUpdateTransferHookInstruction(
+      mint,
+      authority.publicKey,
+      transferHookProgram,
+      [],
+      TOKEN_2022_PROGRAM_ID
+    )
+  );
+
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.sign(authority);
+
+  await banksClient.processTransaction(transaction);
+}
+
 export async function mintToToken2022(
   banksClient: BanksClient,
   payer: Keypair,
@@ -1,5 +1,11 @@
+import { expect } from "chai";
 import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, randomID, startTest } from "./bankrun-utils/common";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   addLiquidity,
@@ -16,14 +22,33 @@ import {
   SwapParams,
   createClaimFeeOperator,
   claimProtocolFee,
+  deriveClaimFeeOperatorAddress,
+  updateClaimFeeOperator,
   TREASURY,
   claimPartnerFee,
   closeClaimFeeOperator,
   mintSplTokenTo,
   createToken,
+  getPool,
+  getOrCreateAssociatedTokenAccount,
+  createPartnerFeeReceiver,
+  closePartnerFeeReceiver,
+  U64_MAX,
+  initializeProgramAuthority,
+  getTokenAccount,
+  initializeInsuranceFund,
+  claimInsuranceFund,
+  getInsuranceFund,
+  initializeBuybackBurn,
+  buybackAndBurn,
+  getBuybackBurn,
+  getMint,
 } from "./bankrun-utils";
 import BN from "bn.js";
-import { ExtensionType } from "@solana/spl-token";
+import {
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 import { createToken2022, mintToToken2022 } from "./bankrun-utils/token2022";
 
 describe("Claim fee", () => {
@@ -47,6 +72,7 @@ describe("Claim fee", () => {
 
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       partner = await generateKpAndFund(context.banksClient, context.payer);
       operator = await generateKpAndFund(context.banksClient, context.payer);
 
@@ -171,7 +197,7 @@ describe("Claim fee", () => {
         outputTokenMint,
         amountIn: new BN(10),
         minimumAmountOut: new BN(0),
-        referralTokenAccount: null,
+        referral: null,
       };
 
       await swap(context.banksClient, swapParams);
@@ -200,6 +226,493 @@ describe("Claim fee", () => {
         rentReceiver: operator.publicKey,
       });
     });
+
+    it("Operator claim protocol fee partially", async () => {
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+
+      const beforePoolState = await getPool(context.banksClient, pool);
+      const protocolAFee = beforePoolState.protocolAFee;
+      const protocolBFee = beforePoolState.protocolBFee;
+      const maxAmountA = protocolAFee.divn(2);
+      const maxAmountB = protocolBFee.divn(2);
+
+      await claimProtocolFee(context.banksClient, {
+        operator,
+        pool,
+        treasury: TREASURY,
+        maxAmountA,
+        maxAmountB,
+      });
+
+      const afterPoolState = await getPool(context.banksClient, pool);
+      expect(afterPoolState.protocolAFee.toString()).eq(
+        protocolAFee.sub(maxAmountA).toString()
+      );
+      expect(afterPoolState.protocolBFee.toString()).eq(
+        protocolBFee.sub(maxAmountB).toString()
+      );
+
+      // claim the rest
+      await claimProtocolFee(context.banksClient, {
+        operator,
+        pool,
+        treasury: TREASURY,
+      });
+
+      const finalPoolState = await getPool(context.banksClient, pool);
+      expect(finalPoolState.protocolAFee.isZero()).to.be.true;
+      expect(finalPoolState.protocolBFee.isZero()).to.be.true;
+    });
+
+    it("Operator claim protocol fee settled in token b", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      });
+
+      // fees are collected in output token, swap both ways to accrue both
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: outputTokenMint,
+        outputTokenMint: inputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+
+      const beforePoolState = await getPool(context.banksClient, pool);
+      expect(beforePoolState.protocolAFee.isZero()).to.be.false;
+      expect(beforePoolState.protocolBFee.isZero()).to.be.false;
+
+      await expectThrowsAsync(async () => {
+        await claimProtocolFee(context.banksClient, {
+          operator,
+          pool,
+          treasury: TREASURY,
+          settlementMode: 2,
+          minimumSettlementAmount: U64_MAX,
+        });
+      }, "0x1772");
+
+      const treasuryTokenA = getAssociatedTokenAddressSync(
+        inputTokenMint,
+        TREASURY,
+        true
+      );
+      const treasuryTokenB = getAssociatedTokenAddressSync(
+        outputTokenMint,
+        TREASURY,
+        true
+      );
+      await claimProtocolFee(context.banksClient, {
+        operator,
+        pool,
+        treasury: TREASURY,
+        settlementMode: 2,
+        minimumSettlementAmount: beforePoolState.protocolBFee.addn(1),
+      });
+
+      const treasuryTokenAState = await getTokenAccount(
+        context.banksClient,
+        treasuryTokenA
+      );
+      const treasuryTokenBState = await getTokenAccount(
+        context.banksClient,
+        treasuryTokenB
+      );
+      expect(treasuryTokenAState.amount.toString()).eq("0");
+      expect(
+        new BN(treasuryTokenBState.amount.toString()).gt(
+          beforePoolState.protocolBFee
+        )
+      ).to.be.true;
+
+      // token a fee is swapped into token b, its trade fee accrues in token b
+      const afterPoolState = await getPool(context.banksClient, pool);
+      expect(afterPoolState.protocolAFee.isZero()).to.be.true;
+      expect(afterPoolState.sqrtPrice.lt(beforePoolState.sqrtPrice)).to.be
+        .true;
+    });
+
+    it("Insurance share of protocol fee is routed to insurance fund", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      });
+
+      const insuranceAuthority = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      // only config admin can initialize insurance fund
+      await expectThrowsAsync(async () => {
+        await initializeInsuranceFund(
+          context.banksClient,
+          user,
+          pool,
+          insuranceAuthority.publicKey,
+          25
+        );
+      }, "0x1775");
+      const insuranceFund = await initializeInsuranceFund(
+        context.banksClient,
+        admin,
+        pool,
+        insuranceAuthority.publicKey,
+        25
+      );
+      // insurance share can't be spent by buyback and burn
+      await expectThrowsAsync(async () => {
+        await initializeBuybackBurn(
+          context.banksClient,
+          admin,
+          pool,
+          U64_MAX,
+          new BN(3600)
+        );
+      }, "0x17ce");
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+      const { protocolBFee } = await getPool(context.banksClient, pool);
+      const insuranceBAmount = protocolBFee.muln(25).divn(100);
+
+      await claimProtocolFee(context.banksClient, {
+        operator,
+        pool,
+        treasury: TREASURY,
+      });
+
+      const insuranceFundState = await getInsuranceFund(
+        context.banksClient,
+        insuranceFund
+      );
+      expect(insuranceFundState.totalRoutedBAmount.toString()).eq(
+        insuranceBAmount.toString()
+      );
+      const treasuryTokenB = await getTokenAccount(
+        context.banksClient,
+        getAssociatedTokenAddressSync(outputTokenMint, TREASURY, true)
+      );
+      expect(treasuryTokenB.amount.toString()).eq(
+        protocolBFee.sub(insuranceBAmount).toString()
+      );
+
+      // only insurance authority can claim
+      await expectThrowsAsync(async () => {
+        await claimInsuranceFund(context.banksClient, operator, pool);
+      }, "0x1775");
+      await claimInsuranceFund(context.banksClient, insuranceAuthority, pool);
+
+      const authorityTokenB = await getTokenAccount(
+        context.banksClient,
+        getAssociatedTokenAddressSync(
+          outputTokenMint,
+          insuranceAuthority.publicKey
+        )
+      );
+      expect(authorityTokenB.amount.toString()).eq(
+        insuranceBAmount.toString()
+      );
+    });
+
+    it("Protocol fee in token b buys back and burns token a", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      });
+
+      // only config admin can enable buyback and burn
+      await expectThrowsAsync(async () => {
+        await initializeBuybackBurn(
+          context.banksClient,
+          user,
+          pool,
+          U64_MAX,
+          new BN(3600)
+        );
+      }, "0x1775");
+      const buybackBurn = await initializeBuybackBurn(
+        context.banksClient,
+        admin,
+        pool,
+        U64_MAX,
+        new BN(3600)
+      );
+      // protocol fee in token b is never claimed, so it can't be insured
+      await expectThrowsAsync(async () => {
+        await initializeInsuranceFund(
+          context.banksClient,
+          admin,
+          pool,
+          user.publicKey,
+          25
+        );
+      }, "0x17ce");
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+      const { protocolBFee } = await getPool(context.banksClient, pool);
+      expect(protocolBFee.isZero()).to.be.false;
+
+      // protocol fee in token b is reserved for buyback
+      await claimProtocolFee(context.banksClient, {
+        operator,
+        pool,
+        treasury: TREASURY,
+      });
+      expect(
+        (await getPool(context.banksClient, pool)).protocolBFee.toString()
+      ).eq(protocolBFee.toString());
+
+      const beforeSupply = (await getMint(context.banksClient, inputTokenMint))
+        .supply;
+      await buybackAndBurn(context.banksClient, user, pool);
+      const afterSupply = (await getMint(context.banksClient, inputTokenMint))
+        .supply;
+
+      const buybackBurnState = await getBuybackBurn(
+        context.banksClient,
+        buybackBurn
+      );
+      expect(buybackBurnState.totalSpentAmount.toString()).eq(
+        protocolBFee.toString()
+      );
+      expect(buybackBurnState.totalBurntAmount.isZero()).to.be.false;
+      expect((beforeSupply - afterSupply).toString()).eq(
+        buybackBurnState.totalBurntAmount.toString()
+      );
+
+      // next buyback waits for min buyback interval
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+      await expectThrowsAsync(async () => {
+        await buybackAndBurn(context.banksClient, user, pool);
+      }, "0x17cc");
+    });
+
+    it("Operator claim partner fee to partner fee receivers", async () => {
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+
+      const partnerTreasury = Keypair.generate().publicKey;
+      const treasuryTokenAAccount = await getOrCreateAssociatedTokenAccount(
+        context.banksClient,
+        partner,
+        inputTokenMint,
+        partnerTreasury
+      );
+      const treasuryTokenBAccount = await getOrCreateAssociatedTokenAccount(
+        context.banksClient,
+        partner,
+        outputTokenMint,
+        partnerTreasury
+      );
+      const partnerFeeReceiverA = await createPartnerFeeReceiver(
+        context.banksClient,
+        {
+          partner,
+          mint: inputTokenMint,
+          tokenAccount: treasuryTokenAAccount,
+          operator: operator.publicKey,
+        }
+      );
+      const partnerFeeReceiverB = await createPartnerFeeReceiver(
+        context.banksClient,
+        {
+          partner,
+          mint: outputTokenMint,
+          tokenAccount: treasuryTokenBAccount,
+          operator: operator.publicKey,
+        }
+      );
+
+      // operator can't claim without partner fee receivers
+      await expectThrowsAsync(async () => {
+        await claimPartnerFee(context.banksClient, {
+          partner,
+          pool,
+          maxAmountA: U64_MAX,
+          maxAmountB: U64_MAX,
+          operator,
+        });
+      }, "0x17a9");
+
+      // partner fee receiver must match token mint
+      await expectThrowsAsync(async () => {
+        await claimPartnerFee(context.banksClient, {
+          partner,
+          pool,
+          maxAmountA: U64_MAX,
+          maxAmountB: U64_MAX,
+          operator,
+          partnerFeeReceiverA: partnerFeeReceiverB,
+          partnerFeeReceiverB: partnerFeeReceiverA,
+        });
+      }, "0x17a8");
+
+      await claimPartnerFee(context.banksClient, {
+        partner,
+        pool,
+        maxAmountA: U64_MAX,
+        maxAmountB: U64_MAX,
+        operator,
+        partnerFeeReceiverA,
+        partnerFeeReceiverB,
+      });
+
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.partnerAFee.isZero()).to.be.true;
+      expect(poolState.partnerBFee.isZero()).to.be.true;
+
+      await closePartnerFeeReceiver(context.banksClient, {
+        partner,
+        partnerFeeReceiver: partnerFeeReceiverA,
+        rentReceiver: partner.publicKey,
+      });
+    });
+
+    it("Admin rotate operator and treasury of claim fee operator", async () => {
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+
+      const claimFeeOperator = deriveClaimFeeOperatorAddress(
+        operator.publicKey
+      );
+      const newOperator = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      const newTreasury = Keypair.generate().publicKey;
+      await updateClaimFeeOperator(context.banksClient, {
+        admin,
+        claimFeeOperator,
+        operator: newOperator.publicKey,
+        treasury: newTreasury,
+      });
+
+      // old operator can't claim
+      await expectThrowsAsync(async () => {
+        await claimProtocolFee(context.banksClient, {
+          operator,
+          pool,
+          treasury: newTreasury,
+          claimFeeOperator,
+        });
+      }, "0x7d1");
+
+      // protocol fee can only be claimed to the registered treasury
+      await expectThrowsAsync(async () => {
+        await claimProtocolFee(context.banksClient, {
+          operator: newOperator,
+          pool,
+          treasury: TREASURY,
+          claimFeeOperator,
+        });
+      }, "0x7df");
+
+      await claimProtocolFee(context.banksClient, {
+        operator: newOperator,
+        pool,
+        treasury: newTreasury,
+        claimFeeOperator,
+      });
+    });
   });
 
   describe("Token 2022", () => {
@@ -222,6 +735,7 @@ describe("Claim fee", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       partner = await generateKpAndFund(context.banksClient, context.payer);
       operator = await generateKpAndFund(context.banksClient, context.payer);
 
@@ -346,7 +860,7 @@ describe("Claim fee", () => {
         outputTokenMint,
         amountIn: new BN(10),
         minimumAmountOut: new BN(0),
-        referralTokenAccount: null,
+        referral: null,
       };
 
       await swap(context.banksClient, swapParams);
@@ -1,5 +1,10 @@
+import { expect } from "chai";
 import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   addLiquidity,
@@ -17,6 +22,19 @@ import {
   SwapParams,
   createToken,
   mintSplTokenTo,
+  setPositionFeeBeneficiary,
+  setPositionOperator,
+  permanentLockPosition,
+  mintFeeReceipt,
+  claimPositionFeeWithReceipt,
+  claimAll,
+  initializeProgramAuthority,
+  getPool,
+  getPoolState,
+  getPosition,
+  getPositionValue,
+  getPositionPending,
+  donate,
 } from "./bankrun-utils";
 import BN from "bn.js";
 
@@ -38,6 +56,7 @@ describe("Claim position fee", () => {
 
     user = await generateKpAndFund(context.banksClient, context.payer);
     admin = await generateKpAndFund(context.banksClient, context.payer);
+    await initializeProgramAuthority(context.banksClient, admin);
     creator = await generateKpAndFund(context.banksClient, context.payer);
 
     tokenAMint = await createToken(
@@ -152,7 +171,7 @@ describe("Claim position fee", () => {
       outputTokenMint: tokenBMint,
       amountIn: new BN(10),
       minimumAmountOut: new BN(0),
-      referralTokenAccount: null,
+      referral: null,
     };
 
     await swap(context.banksClient, swapParams);
@@ -165,4 +184,303 @@ describe("Claim position fee", () => {
     };
     await claimPositionFee(context.banksClient, claimParams);
   });
+
+  it("Simulate pool state and position value", async () => {
+    const liquidityDelta = new BN(MIN_SQRT_PRICE.muln(30));
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta,
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    });
+
+    await swap(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(1000),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    const poolState = await getPool(context.banksClient, pool);
+    const poolStateView = await getPoolState(
+      context.banksClient,
+      user,
+      pool,
+      liquidityDelta
+    );
+    expect(poolStateView.sqrtPrice.toString()).eq(
+      poolState.sqrtPrice.toString()
+    );
+    expect(poolStateView.liquidity.toString()).eq(
+      poolState.liquidity.toString()
+    );
+    expect(poolStateView.tradeFeeNumerator.toNumber()).eq(2_500_000);
+
+    // position state is not updated by swap, pending fee is accrued in the view
+    const positionState = await getPosition(context.banksClient, position);
+    expect(
+      positionState.feeAPending.add(positionState.feeBPending).toNumber()
+    ).eq(0);
+
+    const positionValue = await getPositionValue(
+      context.banksClient,
+      user,
+      pool,
+      position
+    );
+    expect(positionValue.unlockedLiquidity.toString()).eq(
+      liquidityDelta.toString()
+    );
+    expect(positionValue.tokenAAmount.toString()).eq(
+      poolStateView.tokenAAmount.toString()
+    );
+    expect(
+      positionValue.feeAPending.add(positionValue.feeBPending).toNumber()
+    ).gt(0);
+
+    const positionPending = await getPositionPending(
+      context.banksClient,
+      user,
+      pool,
+      position
+    );
+    expect(positionPending.feeAPending.toString()).eq(
+      positionValue.feeAPending.toString()
+    );
+    expect(positionPending.feeBPending.toString()).eq(
+      positionValue.feeBPending.toString()
+    );
+    expect(positionPending.rewardPendings.length).eq(5);
+  });
+
+  it("User claim position fee to fee beneficiary", async () => {
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    });
+
+    await swap(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    const feeBeneficiary = Keypair.generate().publicKey;
+    await setPositionFeeBeneficiary(context.banksClient, {
+      owner: user,
+      position,
+      feeBeneficiary,
+    });
+
+    await claimPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      feeBeneficiary,
+    });
+  });
+
+  it("Operator claim position fee to owner", async () => {
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    });
+
+    await swap(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    const operator = await generateKpAndFund(
+      context.banksClient,
+      context.payer
+    );
+    await setPositionOperator(context.banksClient, {
+      owner: user,
+      position,
+      operator: operator.publicKey,
+    });
+
+    // operator can't claim to its own token accounts
+    await expectThrowsAsync(async () => {
+      await claimPositionFee(context.banksClient, {
+        owner: operator,
+        pool,
+        position,
+        feeBeneficiary: operator.publicKey,
+      });
+    }, "0x179d");
+
+    await claimPositionFee(context.banksClient, {
+      owner: operator,
+      pool,
+      position,
+      feeBeneficiary: user.publicKey,
+    });
+  });
+
+  it("Fee receipt holder claim position fee", async () => {
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    });
+
+    const holder = await generateKpAndFund(context.banksClient, context.payer);
+
+    // position must be fully permanently locked
+    await expectThrowsAsync(async () => {
+      await mintFeeReceipt(context.banksClient, {
+        owner: user,
+        position,
+        receiver: holder.publicKey,
+      });
+    }, "0x17a3");
+
+    await permanentLockPosition(context.banksClient, position, user, user);
+    const feeReceiptAccount = await mintFeeReceipt(context.banksClient, {
+      owner: user,
+      position,
+      receiver: holder.publicKey,
+    });
+
+    await swap(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    // position owner can't claim fee anymore
+    await expectThrowsAsync(async () => {
+      await claimPositionFee(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+      });
+    }, "0x17a4");
+
+    // claim all skip position fee
+    await claimAll(context.banksClient, { owner: user, pool, position });
+
+    await claimPositionFeeWithReceipt(context.banksClient, {
+      holder,
+      pool,
+      position,
+      feeReceiptAccount,
+    });
+  });
+
+  it("User claim bounded position fee", async () => {
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    });
+
+    await swap(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    // claim nothing of token b
+    await claimPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      maxAmountA: new BN(1),
+      maxAmountB: new BN(0),
+    });
+
+    // claim the rest
+    await claimPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+    });
+  });
+
+  it("Donate to liquidity providers", async () => {
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    });
+
+    await expectThrowsAsync(async () => {
+      await donate(context.banksClient, {
+        donor: creator,
+        pool,
+        tokenAAmount: new BN(0),
+        tokenBAmount: new BN(0),
+      });
+    }, "0x1776");
+
+    const poolState = await getPool(context.banksClient, pool);
+    await donate(context.banksClient, {
+      donor: creator,
+      pool,
+      tokenAAmount: new BN(0),
+      tokenBAmount: new BN(1_000_000),
+    });
+
+    // donation is lp fee, price is unchanged
+    const donatedPoolState = await getPool(context.banksClient, pool);
+    expect(donatedPoolState.sqrtPrice.toString()).eq(
+      poolState.sqrtPrice.toString()
+    );
+    expect(
+      donatedPoolState.metrics.totalLpBFee
+        .sub(poolState.metrics.totalLpBFee)
+        .toNumber()
+    ).eq(1_000_000);
+
+    await claimPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+    });
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.metrics.totalClaimedBFee.gtn(0)).to.be.true;
+    expect(positionState.metrics.totalClaimedAFee.isZero()).to.be.true;
+  });
 });
@@ -1,5 +1,11 @@
 import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, randomID, startTest } from "./bankrun-utils/common";
+import { expect } from "chai";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   BASIS_POINT_MAX,
@@ -9,6 +15,13 @@ import {
   MAX_SQRT_PRICE,
   MIN_SQRT_PRICE,
   OFFSET,
+  getConfig,
+  updateConfigIx,
+  acceptAuthorityRole,
+  AUTHORITY_ROLE_CONFIG_ADMIN,
+  getProgramAuthority,
+  transferAuthorityRole,
+  initializeProgramAuthority,
 } from "./bankrun-utils";
 import { shlDiv } from "./bankrun-utils/math";
 import { BN } from "bn.js";
@@ -23,6 +36,7 @@ describe("Admin function: Create config", () => {
     const root = Keypair.generate();
     context = await startTest(root);
     admin = await generateKpAndFund(context.banksClient, context.payer);
+    await initializeProgramAuthority(context.banksClient, admin);
     createConfigParams = {
       poolFees: {
         baseFee: {
@@ -61,6 +75,97 @@ describe("Admin function: Create config", () => {
     await closeConfigIx(context.banksClient, admin, config);
   });
 
+  it("Transfer config admin role", async () => {
+    const newAdmin = await generateKpAndFund(
+      context.banksClient,
+      context.payer
+    );
+    await transferAuthorityRole(
+      context.banksClient,
+      admin,
+      AUTHORITY_ROLE_CONFIG_ADMIN,
+      newAdmin.publicKey
+    );
+
+    // role is kept until new key accepts it
+    await createConfigIx(context.banksClient, admin, index, createConfigParams);
+    await expectThrowsAsync(async () => {
+      await acceptAuthorityRole(
+        context.banksClient,
+        admin,
+        AUTHORITY_ROLE_CONFIG_ADMIN
+      );
+    }, "0x17ae");
+
+    await acceptAuthorityRole(
+      context.banksClient,
+      newAdmin,
+      AUTHORITY_ROLE_CONFIG_ADMIN
+    );
+    const programAuthority = await getProgramAuthority(context.banksClient);
+    expect(
+      programAuthority.roles[AUTHORITY_ROLE_CONFIG_ADMIN].toString()
+    ).eq(newAdmin.publicKey.toString());
+
+    await expectThrowsAsync(async () => {
+      await createConfigIx(
+        context.banksClient,
+        admin,
+        index.addn(1),
+        createConfigParams
+      );
+    }, "0x1775");
+    await createConfigIx(
+      context.banksClient,
+      newAdmin,
+      index.addn(1),
+      createConfigParams
+    );
+  });
+
+  it("Admin update config", async () => {
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      index,
+      createConfigParams
+    );
+    const poolCreatorAuthority = Keypair.generate().publicKey;
+    await updateConfigIx(context.banksClient, admin, config, {
+      poolFees: {
+        ...createConfigParams.poolFees,
+        baseFee: {
+          ...createConfigParams.poolFees.baseFee,
+          cliffFeeNumerator: new BN(5_000_000),
+        },
+        partnerFeePercent: 10,
+      },
+      vaultConfigKey: null,
+      poolCreatorAuthority,
+      activationType: 1,
+    });
+
+    const configState = await getConfig(context.banksClient, config);
+    expect(configState.poolFees.baseFee.cliffFeeNumerator.toNumber()).eq(
+      5_000_000
+    );
+    expect(configState.poolFees.partnerFeePercent).eq(10);
+    expect(configState.poolCreatorAuthority.toString()).eq(
+      poolCreatorAuthority.toString()
+    );
+    expect(configState.activationType).eq(1);
+
+    // partner fee requires pool creator authority
+    await expectThrowsAsync(async () => {
+      await updateConfigIx(context.banksClient, admin, config, {
+        poolFees: null,
+        vaultConfigKey: null,
+        poolCreatorAuthority: PublicKey.default,
+        activationType: null,
+      });
+    }, "0x1771");
+  });
+
   it("Admin create config with dynamic fee", async () => {
     // params
     const binStep = new BN(1);
@@ -1,5 +1,10 @@
 import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+import { expect } from "chai";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   InitializeCustomizeablePoolParams,
@@ -9,6 +14,20 @@ import {
   MIN_SQRT_PRICE,
   mintSplTokenTo,
   createToken,
+  createSwapAllowlistEntry,
+  closeSwapAllowlistEntry,
+  createLpAllowlistEntry,
+  closeLpAllowlistEntry,
+  createPosition,
+  addLiquidity,
+  AddLiquidityParams,
+  U64_MAX,
+  createLaunchBuyTracker,
+  getPool,
+  swap,
+  SwapParams,
+  initializeProgramAuthority,
+  updateActivationPoint,
 } from "./bankrun-utils";
 import BN from "bn.js";
 import { ExtensionType } from "@solana/spl-token";
@@ -85,6 +104,483 @@ describe("Initialize customizable pool", () => {
 
       await initializeCustomizeablePool(context.banksClient, params);
     });
+
+    it("Creator delays activation point of customizable pool", async () => {
+      const admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+
+      const currentSlot = Number(await context.banksClient.getSlot());
+      const { pool } = await initializeCustomizeablePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        tokenAMint,
+        tokenBMint,
+        liquidity: MIN_LP_AMOUNT,
+        sqrtPrice: MIN_SQRT_PRICE,
+        sqrtMinPrice: MIN_SQRT_PRICE,
+        sqrtMaxPrice: MAX_SQRT_PRICE,
+        hasAlphaVault: false,
+        activationPoint: new BN(currentSlot + 20),
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            numberOfPeriod: 0,
+            reductionFactor: new BN(0),
+            periodFrequency: new BN(0),
+            feeSchedulerMode: 0,
+          },
+          protocolFeePercent: 20,
+          partnerFeePercent: 0,
+          referralFeePercent: 20,
+          dynamicFee: null,
+        },
+        activationType: 0,
+        collectFeeMode: 0,
+      });
+      expect((await getPool(context.banksClient, pool)).creator.toBase58()).eq(
+        creator.publicKey.toBase58()
+      );
+
+      // customizable pool has no partner, only admin or creator
+      await expectThrowsAsync(async () => {
+        await updateActivationPoint(
+          context.banksClient,
+          user,
+          pool,
+          new BN(currentSlot + 25)
+        );
+      }, "0x17ba");
+
+      await updateActivationPoint(
+        context.banksClient,
+        creator,
+        pool,
+        new BN(currentSlot + 25)
+      );
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.activationPoint.toNumber()).eq(currentSlot + 25);
+    });
+
+    it("Only allowlisted wallets swap in permissioned pool", async () => {
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      const { pool } = await initializeCustomizeablePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_SQRT_PRICE.muln(30)),
+        sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+        sqrtMinPrice: MIN_SQRT_PRICE,
+        sqrtMaxPrice: MAX_SQRT_PRICE,
+        hasAlphaVault: false,
+        activationPoint: null,
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            numberOfPeriod: 0,
+            reductionFactor: new BN(0),
+            periodFrequency: new BN(0),
+            feeSchedulerMode: 0,
+          },
+          protocolFeePercent: 20,
+          partnerFeePercent: 0,
+          referralFeePercent: 20,
+          dynamicFee: null,
+        },
+        activationType: 0,
+        collectFeeMode: 0,
+        permissionedSwap: true,
+      });
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.swapAllowlistAuthority.toString()).eq(
+        creator.publicKey.toString()
+      );
+
+      const swapParams: SwapParams = {
+        payer: user,
+        pool,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      };
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, swapParams);
+      }, "0x1773");
+
+      // only creator manages the allowlist
+      await expectThrowsAsync(async () => {
+        await createSwapAllowlistEntry(
+          context.banksClient,
+          user,
+          pool,
+          user.publicKey
+        );
+      }, "0x17b4");
+      const swapAllowlistEntry = await createSwapAllowlistEntry(
+        context.banksClient,
+        creator,
+        pool,
+        user.publicKey
+      );
+      await swap(context.banksClient, { ...swapParams, swapAllowlistEntry });
+
+      await closeSwapAllowlistEntry(
+        context.banksClient,
+        creator,
+        pool,
+        swapAllowlistEntry
+      );
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, swapParams);
+      }, "0x1773");
+    });
+
+    it("Only lp allowlisted wallets add liquidity", async () => {
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      const { pool } = await initializeCustomizeablePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_SQRT_PRICE.muln(30)),
+        sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+        sqrtMinPrice: MIN_SQRT_PRICE,
+        sqrtMaxPrice: MAX_SQRT_PRICE,
+        hasAlphaVault: false,
+        activationPoint: null,
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            numberOfPeriod: 0,
+            reductionFactor: new BN(0),
+            periodFrequency: new BN(0),
+            feeSchedulerMode: 0,
+          },
+          protocolFeePercent: 20,
+          partnerFeePercent: 0,
+          referralFeePercent: 20,
+          dynamicFee: null,
+        },
+        activationType: 0,
+        collectFeeMode: 0,
+        permissionedLp: true,
+      });
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.lpAllowlistAuthority.toString()).eq(
+        creator.publicKey.toString()
+      );
+
+      await expectThrowsAsync(async () => {
+        await createPosition(context.banksClient, user, user.publicKey, pool);
+      }, "0x1773");
+
+      // only creator manages the allowlist
+      await expectThrowsAsync(async () => {
+        await createLpAllowlistEntry(
+          context.banksClient,
+          user,
+          pool,
+          user.publicKey
+        );
+      }, "0x17cd");
+      const lpAllowlistEntry = await createLpAllowlistEntry(
+        context.banksClient,
+        creator,
+        pool,
+        user.publicKey
+      );
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool,
+        lpAllowlistEntry
+      );
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_LP_AMOUNT,
+        tokenAAmountThreshold: U64_MAX,
+        tokenBAmountThreshold: U64_MAX,
+      };
+      await addLiquidity(context.banksClient, {
+        ...addLiquidityParams,
+        lpAllowlistEntry,
+      });
+
+      // swap stays permissionless
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+
+      await closeLpAllowlistEntry(
+        context.banksClient,
+        creator,
+        pool,
+        lpAllowlistEntry
+      );
+      await expectThrowsAsync(async () => {
+        await addLiquidity(context.banksClient, addLiquidityParams);
+      }, "0x1773");
+    });
+
+    it("Swap rate limiter caps buys per window", async () => {
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      const { pool } = await initializeCustomizeablePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_SQRT_PRICE.muln(30)),
+        sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+        sqrtMinPrice: MIN_SQRT_PRICE,
+        sqrtMaxPrice: MAX_SQRT_PRICE,
+        hasAlphaVault: false,
+        activationPoint: null,
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            numberOfPeriod: 0,
+            reductionFactor: new BN(0),
+            periodFrequency: new BN(0),
+            feeSchedulerMode: 0,
+          },
+          protocolFeePercent: 20,
+          partnerFeePercent: 0,
+          referralFeePercent: 20,
+          dynamicFee: null,
+        },
+        activationType: 0,
+        collectFeeMode: 0,
+        swapRateLimiter: {
+          maxAmountPerWindow: new BN(100),
+          windowDuration: 100,
+          windowCount: 10,
+        },
+      });
+
+      const buyParams: SwapParams = {
+        payer: user,
+        pool,
+        inputTokenMint: tokenBMint,
+        outputTokenMint: tokenAMint,
+        amountIn: new BN(60),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      };
+      await swap(context.banksClient, buyParams);
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, { ...buyParams, amountIn: new BN(50) });
+      }, "0x17b5");
+
+      // sells are not capped
+      await swap(context.banksClient, {
+        ...buyParams,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(10),
+      });
+    });
+
+    it("Launch guard caps buy amount per wallet", async () => {
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      const { pool } = await initializeCustomizeablePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_SQRT_PRICE.muln(30)),
+        sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+        sqrtMinPrice: MIN_SQRT_PRICE,
+        sqrtMaxPrice: MAX_SQRT_PRICE,
+        hasAlphaVault: false,
+        activationPoint: null,
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            numberOfPeriod: 0,
+            reductionFactor: new BN(0),
+            periodFrequency: new BN(0),
+            feeSchedulerMode: 0,
+          },
+          protocolFeePercent: 20,
+          partnerFeePercent: 0,
+          referralFeePercent: 20,
+          dynamicFee: null,
+        },
+        activationType: 0,
+        collectFeeMode: 0,
+        launchGuard: {
+          maxBuyAmountPerWallet: new BN(100),
+          duration: new BN(1_000),
+        },
+      });
+
+      const buyParams: SwapParams = {
+        payer: user,
+        pool,
+        inputTokenMint: tokenBMint,
+        outputTokenMint: tokenAMint,
+        amountIn: new BN(60),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      };
+      // buys require launch buy tracker of payer
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, buyParams);
+      }, "0x17b8");
+
+      const launchBuyTracker = await createLaunchBuyTracker(
+        context.banksClient,
+        user,
+        pool,
+        user.publicKey
+      );
+      await swap(context.banksClient, { ...buyParams, launchBuyTracker });
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, {
+          ...buyParams,
+          amountIn: new BN(50),
+          launchBuyTracker,
+        });
+      }, "0x17b7");
+    });
+
+    it("Price circuit breaker caps price change per slot", async () => {
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      const params = {
+        payer: creator,
+        creator: creator.publicKey,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_SQRT_PRICE.muln(30)),
+        sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+        sqrtMinPrice: MIN_SQRT_PRICE,
+        sqrtMaxPrice: MAX_SQRT_PRICE,
+        hasAlphaVault: false,
+        activationPoint: null,
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            numberOfPeriod: 0,
+            reductionFactor: new BN(0),
+            periodFrequency: new BN(0),
+            feeSchedulerMode: 0,
+          },
+          protocolFeePercent: 20,
+          partnerFeePercent: 0,
+          referralFeePercent: 20,
+          dynamicFee: null,
+        },
+        activationType: 0,
+        collectFeeMode: 0,
+      };
+
+      await expectThrowsAsync(async () => {
+        await initializeCustomizeablePool(context.banksClient, {
+          ...params,
+          priceCircuitBreaker: { maxSqrtPriceChangeBps: 0 },
+        });
+      }, "0x17c3");
+
+      const { pool } = await initializeCustomizeablePool(context.banksClient, {
+        ...params,
+        priceCircuitBreaker: { maxSqrtPriceChangeBps: 100 },
+      });
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.priceCircuitBreaker.maxSqrtPriceChangeBps).eq(100);
+
+      // a buy moving sqrt price of the thin pool over 1% in a slot fails
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, {
+          payer: user,
+          pool,
+          inputTokenMint: tokenBMint,
+          outputTokenMint: tokenAMint,
+          amountIn: new BN(60),
+          minimumAmountOut: new BN(0),
+          referral: null,
+        });
+      }, "0x17c2");
+    });
   });
 
   describe("Token 2022", () => {
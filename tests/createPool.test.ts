@@ -1,7 +1,11 @@
 import { expect } from "chai";
-import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, startTest } from "./bankrun-utils/common";
-import { Keypair, PublicKey } from "@solana/web3.js";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
 import {
   createConfigIx,
   CreateConfigParams,
@@ -9,15 +13,39 @@ import {
   initializePool,
   InitializePoolParams,
   MIN_LP_AMOUNT,
+  MINIMUM_LIQUIDITY,
+  getPosition,
   MAX_SQRT_PRICE,
   MIN_SQRT_PRICE,
   setPoolStatus,
+  closePool,
+  removeAllLiquidity,
+  migratePool,
+  createPoolMetadata,
+  updatePoolMetadata,
+  getPoolMetadata,
   createToken,
   mintSplTokenTo,
+  initializeProgramAuthority,
+  createTokenBadge,
+  derivePositionNftAccount,
+  deriveTokenBadgeAddress,
+  TOKEN_BADGE_ALLOW_PERMANENT_DELEGATE,
+  initializeTradeMining,
+  fundTradeMining,
+  withdrawUnaccruedTradeMining,
+  initializeInsuranceFund,
+  claimInsuranceFund,
+  deriveInsuranceVaultAddress,
 } from "./bankrun-utils";
 import BN from "bn.js";
-import { ExtensionType } from "@solana/spl-token";
-import { createToken2022, mintToToken2022 } from "./bankrun-utils/token2022";
+import { createMintToInstruction, ExtensionType } from "@solana/spl-token";
+import {
+  createRugCapableToken2022,
+  createToken2022,
+  mintToToken2022,
+  setPausableToken2022,
+} from "./bankrun-utils/token2022";
 
 describe("Initialize pool", () => {
   describe("SPL token", () => {
@@ -36,6 +64,7 @@ describe("Initialize pool", () => {
       context = await startTest(root);
       creator = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
 
       tokenAMint = await createToken(
         context.banksClient,
@@ -116,12 +145,429 @@ describe("Initialize pool", () => {
 
       const newStatus = 1;
       await setPoolStatus(context.banksClient, {
-        admin,
+        signer: admin,
         pool,
         status: newStatus,
       });
       const poolState = await getPool(context.banksClient, pool);
       expect(poolState.poolStatus).eq(newStatus);
+
+      // creator is neither admin nor partner of pool
+      await expectThrowsAsync(async () => {
+        await setPoolStatus(context.banksClient, {
+          signer: creator,
+          pool,
+          status: 0,
+        });
+      }, "0x17ac");
+
+      // only swap, add liquidity, remove liquidity and create position can be disabled
+      await expectThrowsAsync(async () => {
+        await setPoolStatus(context.banksClient, {
+          signer: admin,
+          pool,
+          status: 0b10000,
+        });
+      }, "0x1789");
+
+      // halt deposits only
+      const addLiquidityDisabled = 0b0010;
+      await setPoolStatus(context.banksClient, {
+        signer: admin,
+        pool,
+        status: addLiquidityDisabled,
+      });
+      const updatedPoolState = await getPool(context.banksClient, pool);
+      expect(updatedPoolState.poolStatus).eq(addLiquidityDisabled);
+    });
+
+    it("Minimum liquidity is permanently locked", async () => {
+      const initPoolParams: InitializePoolParams = {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: MINIMUM_LIQUIDITY,
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      };
+      await expectThrowsAsync(async () => {
+        await initializePool(context.banksClient, initPoolParams);
+      }, "0x1785");
+
+      const { pool, position } = await initializePool(context.banksClient, {
+        ...initPoolParams,
+        liquidity: new BN(MIN_LP_AMOUNT),
+      });
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.liquidity.toString()).eq(MIN_LP_AMOUNT.toString());
+      expect(poolState.permanentLockLiquidity.toString()).eq(
+        MINIMUM_LIQUIDITY.toString()
+      );
+      const positionState = await getPosition(context.banksClient, position);
+      expect(positionState.permanentLockedLiquidity.toString()).eq(
+        MINIMUM_LIQUIDITY.toString()
+      );
+      expect(positionState.unlockedLiquidity.toString()).eq(
+        new BN(MIN_LP_AMOUNT).sub(MINIMUM_LIQUIDITY).toString()
+      );
+    });
+
+    it("Only one pool per token pair and config", async () => {
+      const initPoolParams: InitializePoolParams = {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      };
+      await initializePool(context.banksClient, initPoolParams);
+
+      // pool address is derived from sorted mints, so flipping token order hits the same pool
+      await expectThrowsAsync(async () => {
+        await initializePool(context.banksClient, {
+          ...initPoolParams,
+          tokenAMint: tokenBMint,
+          tokenBMint: tokenAMint,
+        });
+      }, "0x0");
+    });
+
+    it("Migrate pool created before versioning", async () => {
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      });
+
+      const poolAccount = await context.banksClient.getAccount(pool);
+      expect(poolAccount.data.length).eq(8 + 1504);
+      expect((await getPool(context.banksClient, pool)).version).eq(1);
+
+      // latest pool can't be migrated again
+      await expectThrowsAsync(async () => {
+        await migratePool(context.banksClient, creator, pool);
+      }, "0x17bc");
+
+      // layout before versioning is 400 bytes shorter and has no version
+      context.setAccount(pool, {
+        ...poolAccount,
+        data: poolAccount.data.slice(0, 8 + 1104),
+      });
+
+      // anyone can migrate by paying the rent
+      await migratePool(context.banksClient, creator, pool);
+
+      const migratedAccount = await context.banksClient.getAccount(pool);
+      expect(migratedAccount.data.length).eq(8 + 1504);
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.version).eq(1);
+      // migrated pool doesn't track fees owed since initialization
+      expect(poolState.feeTrackingFlag).eq(0);
+      expect(poolState.liquidity.toString()).eq(MIN_LP_AMOUNT.toString());
+    });
+
+    it("Create and update pool metadata", async () => {
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      });
+      const params = {
+        name: "Token A - Token B",
+        symbol: "A-B",
+        uri: "https://example.com/logo.png",
+        projectUrl: "https://example.com",
+      };
+
+      // creator is neither admin nor partner of pool
+      await expectThrowsAsync(async () => {
+        await createPoolMetadata(context.banksClient, {
+          signer: creator,
+          pool,
+          params,
+        });
+      }, "0x17be");
+      // name is required
+      await expectThrowsAsync(async () => {
+        await createPoolMetadata(context.banksClient, {
+          signer: admin,
+          pool,
+          params: { ...params, name: "" },
+        });
+      }, "0x17bd");
+
+      const poolMetadata = await createPoolMetadata(context.banksClient, {
+        signer: admin,
+        pool,
+        params,
+      });
+
+      await updatePoolMetadata(context.banksClient, {
+        signer: admin,
+        pool,
+        params: { ...params, name: "A-B", uri: "" },
+      });
+      const poolMetadataState = await getPoolMetadata(
+        context.banksClient,
+        poolMetadata
+      );
+      const toString = (bytes: number[]) =>
+        Buffer.from(bytes).toString("utf8").replace(/\0+$/, "");
+      expect(toString(poolMetadataState.name)).eq("A-B");
+      expect(toString(poolMetadataState.symbol)).eq(params.symbol);
+      expect(toString(poolMetadataState.uri)).eq("");
+      expect(toString(poolMetadataState.projectUrl)).eq(params.projectUrl);
+    });
+
+    it("Can't close pool with liquidity", async () => {
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      });
+
+      await expectThrowsAsync(async () => {
+        await closePool(context.banksClient, admin, pool, admin.publicKey);
+      }, "0x17ad");
+    });
+
+    it("Close pool holding only the locked minimum liquidity", async () => {
+      const { pool, position } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      });
+
+      await removeAllLiquidity(context.banksClient, {
+        owner: creator,
+        pool,
+        position,
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.liquidity.toString()).eq(MINIMUM_LIQUIDITY.toString());
+
+      // position of the locked minimum liquidity is closed with pool
+      await expectThrowsAsync(async () => {
+        await closePool(context.banksClient, admin, pool, admin.publicKey);
+      }, "0x17ad");
+      // position owner must sign to burn the position nft
+      await expectThrowsAsync(async () => {
+        await closePool(
+          context.banksClient,
+          admin,
+          pool,
+          admin.publicKey,
+          position
+        );
+      }, "0x17d3");
+
+      const { nftMint } = await getPosition(context.banksClient, position);
+      const positionNftAccount = derivePositionNftAccount(nftMint);
+      let positionRent = 0;
+      for (const account of [position, nftMint, positionNftAccount]) {
+        positionRent += (await context.banksClient.getAccount(account))
+          .lamports;
+      }
+      const beforeOwnerLamports = await context.banksClient.getBalance(
+        creator.publicKey
+      );
+
+      await closePool(
+        context.banksClient,
+        admin,
+        pool,
+        admin.publicKey,
+        position,
+        creator
+      );
+
+      expect(await context.banksClient.getAccount(pool)).to.be.null;
+      expect(await context.banksClient.getAccount(position)).to.be.null;
+      expect(await context.banksClient.getAccount(nftMint)).to.be.null;
+      expect(await context.banksClient.getAccount(positionNftAccount)).to.be
+        .null;
+      // rent of position accounts is refunded to position owner
+      const afterOwnerLamports = await context.banksClient.getBalance(
+        creator.publicKey
+      );
+      expect(Number(afterOwnerLamports - beforeOwnerLamports)).eq(
+        positionRent
+      );
+    });
+
+    it("Close pool after trade mining budget is withdrawn", async () => {
+      const { pool, position } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      });
+      await removeAllLiquidity(context.banksClient, {
+        owner: creator,
+        pool,
+        position,
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+
+      const { unixTimestamp } = await context.banksClient.getClock();
+      const endTime = unixTimestamp + BigInt(3600);
+      await initializeTradeMining(context.banksClient, {
+        signer: admin,
+        pool,
+        rewardMint: tokenBMint,
+        funder: creator.publicKey,
+        rewardPerFeeA: new BN(0),
+        rewardPerFeeB: new BN(1).shln(64),
+        endTime: new BN(endTime.toString()),
+      });
+      await fundTradeMining(context.banksClient, creator, pool, new BN(1_000));
+
+      await expectThrowsAsync(async () => {
+        await closePool(
+          context.banksClient,
+          admin,
+          pool,
+          admin.publicKey,
+          position,
+          creator
+        );
+      }, "0x17ad");
+
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          endTime
+        )
+      );
+      await withdrawUnaccruedTradeMining(context.banksClient, creator, pool);
+      await closePool(
+        context.banksClient,
+        admin,
+        pool,
+        admin.publicKey,
+        position,
+        creator
+      );
+
+      expect(await context.banksClient.getAccount(pool)).to.be.null;
+    });
+
+    it("Close pool after insurance fund is claimed", async () => {
+      const { pool, position } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE),
+        activationPoint: null,
+      });
+      await removeAllLiquidity(context.banksClient, {
+        owner: creator,
+        pool,
+        position,
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+
+      const insuranceAuthority = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      const insuranceFund = await initializeInsuranceFund(
+        context.banksClient,
+        admin,
+        pool,
+        insuranceAuthority.publicKey,
+        25
+      );
+      const insuranceTokenAVault = deriveInsuranceVaultAddress(
+        insuranceFund,
+        tokenAMint
+      );
+      const insuranceTokenBVault = deriveInsuranceVaultAddress(
+        insuranceFund,
+        tokenBMint
+      );
+      // fund insurance vault as if protocol fee was routed to it
+      const transaction = new Transaction().add(
+        createMintToInstruction(
+          tokenBMint,
+          insuranceTokenBVault,
+          context.payer.publicKey,
+          1_000
+        )
+      );
+      transaction.recentBlockhash = (
+        await context.banksClient.getLatestBlockhash()
+      )[0];
+      transaction.sign(context.payer);
+      await context.banksClient.processTransaction(transaction);
+
+      await expectThrowsAsync(async () => {
+        await closePool(
+          context.banksClient,
+          admin,
+          pool,
+          admin.publicKey,
+          position,
+          creator
+        );
+      }, "0x17ad");
+
+      await claimInsuranceFund(context.banksClient, insuranceAuthority, pool);
+      await closePool(
+        context.banksClient,
+        admin,
+        pool,
+        admin.publicKey,
+        position,
+        creator
+      );
+
+      expect(await context.banksClient.getAccount(pool)).to.be.null;
+      expect(await context.banksClient.getAccount(insuranceFund)).to.be.null;
+      expect(await context.banksClient.getAccount(insuranceTokenAVault)).to.be
+        .null;
+      expect(await context.banksClient.getAccount(insuranceTokenBVault)).to.be
+        .null;
     });
   });
 
@@ -142,6 +588,7 @@ describe("Initialize pool", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       creator = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
 
       tokenAMint = await createToken2022(
         context.banksClient,
@@ -222,12 +669,178 @@ describe("Initialize pool", () => {
 
       const newStatus = 1;
       await setPoolStatus(context.banksClient, {
-        admin,
+        signer: admin,
         pool,
         status: newStatus,
       });
       const poolState = await getPool(context.banksClient, pool);
       expect(poolState.poolStatus).eq(newStatus);
     });
+
+    describe("Rug-capable mint", () => {
+      let initPoolParams: InitializePoolParams;
+
+      const tokenBadgeAccounts = (mint: PublicKey) => [
+        {
+          pubkey: deriveTokenBadgeAddress(mint),
+          isSigner: false,
+          isWritable: false,
+        },
+      ];
+
+      beforeEach(() => {
+        initPoolParams = {
+          payer: creator,
+          creator: creator.publicKey,
+          config,
+          tokenAMint,
+          tokenBMint,
+          liquidity: new BN(MIN_LP_AMOUNT),
+          sqrtPrice: new BN(MIN_SQRT_PRICE),
+          activationPoint: null,
+        };
+      });
+
+      it("Permanent delegate requires token badge allowing it", async () => {
+        const mint = await createRugCapableToken2022(
+          context.banksClient,
+          context.payer,
+          ExtensionType.PermanentDelegate
+        );
+        await mintToToken2022(
+          context.banksClient,
+          context.payer,
+          mint,
+          context.payer,
+          creator.publicKey
+        );
+        const params = { ...initPoolParams, tokenAMint: mint };
+
+        await expectThrowsAsync(async () => {
+          await initializePool(context.banksClient, params);
+        }, "0x1784");
+
+        await createTokenBadge(context.banksClient, {
+          tokenMint: mint,
+          admin,
+        });
+        await expectThrowsAsync(async () => {
+          await initializePool(context.banksClient, {
+            ...params,
+            remainingAccounts: tokenBadgeAccounts(mint),
+          });
+        }, "0x1784");
+      });
+
+      it("Permanent delegate is flagged in pool", async () => {
+        const mint = await createRugCapableToken2022(
+          context.banksClient,
+          context.payer,
+          ExtensionType.PermanentDelegate
+        );
+        await mintToToken2022(
+          context.banksClient,
+          context.payer,
+          mint,
+          context.payer,
+          creator.publicKey
+        );
+        await createTokenBadge(context.banksClient, {
+          tokenMint: mint,
+          admin,
+          policy: 1 << TOKEN_BADGE_ALLOW_PERMANENT_DELEGATE,
+        });
+
+        const { pool } = await initializePool(context.banksClient, {
+          ...initPoolParams,
+          tokenAMint: mint,
+          remainingAccounts: tokenBadgeAccounts(mint),
+        });
+        const poolState = await getPool(context.banksClient, pool);
+        // token 2022 program and permanent delegate finding
+        expect(poolState.tokenAFlag).eq(3);
+      });
+
+      it("Frozen default account state requires token badge", async () => {
+        const mint = await createRugCapableToken2022(
+          context.banksClient,
+          context.payer,
+          ExtensionType.DefaultAccountState
+        );
+        const params = { ...initPoolParams, tokenAMint: mint };
+
+        await expectThrowsAsync(async () => {
+          await initializePool(context.banksClient, params);
+        }, "0x1784");
+
+        // freeze authority is not allowed by the token badge
+        await createTokenBadge(context.banksClient, {
+          tokenMint: mint,
+          admin,
+        });
+        await expectThrowsAsync(async () => {
+          await initializePool(context.banksClient, {
+            ...params,
+            remainingAccounts: tokenBadgeAccounts(mint),
+          });
+        }, "0x1784");
+      });
+
+      it("Mint close authority requires token badge", async () => {
+        const mint = await createRugCapableToken2022(
+          context.banksClient,
+          context.payer,
+          ExtensionType.MintCloseAuthority
+        );
+        await mintToToken2022(
+          context.banksClient,
+          context.payer,
+          mint,
+          context.payer,
+          creator.publicKey
+        );
+        const params = { ...initPoolParams, tokenAMint: mint };
+
+        await expectThrowsAsync(async () => {
+          await initializePool(context.banksClient, params);
+        }, "0x1784");
+
+        await createTokenBadge(context.banksClient, {
+          tokenMint: mint,
+          admin,
+        });
+        const { pool } = await initializePool(context.banksClient, {
+          ...params,
+          remainingAccounts: tokenBadgeAccounts(mint),
+        });
+        const poolState = await getPool(context.banksClient, pool);
+        // token 2022 program and mint close authority finding
+        expect(poolState.tokenAFlag).eq(9);
+      });
+
+      it("Pausable mint is rejected even with token badge", async () => {
+        const mint = await setPausableToken2022(
+          context,
+          context.payer.publicKey
+        );
+
+        await expectThrowsAsync(async () => {
+          await createTokenBadge(context.banksClient, {
+            tokenMint: mint,
+            admin,
+          });
+        }, "0x17cf");
+
+        const params = { ...initPoolParams, tokenAMint: mint };
+        for (const remainingAccounts of [[], tokenBadgeAccounts(mint)]) {
+          await expectThrowsAsync(async () => {
+            await initializePool(context.banksClient, {
+              ...params,
+              remainingAccounts,
+            });
+          }, "invalid account data for instruction");
+        }
+      });
+    });
   });
 });
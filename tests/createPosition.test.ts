@@ -12,6 +12,7 @@ import {
   MIN_SQRT_PRICE,
   createToken,
   mintSplTokenTo,
+  initializeProgramAuthority,
 } from "./bankrun-utils";
 import BN from "bn.js";
 import { ExtensionType } from "@solana/spl-token";
@@ -35,6 +36,7 @@ describe("Create position", () => {
       creator = await generateKpAndFund(context.banksClient, context.payer);
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
 
       tokenAMint = await createToken(
         context.banksClient,
@@ -133,6 +135,7 @@ describe("Create position", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       creator = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       user = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken2022(
@@ -11,6 +11,7 @@ import {
   CreateDynamicConfigParams,
   InitializePoolWithCustomizeConfigParams,
   initializePoolWithCustomizeConfig,
+  initializeProgramAuthority,
 } from "./bankrun-utils";
 import BN from "bn.js";
 
@@ -30,6 +31,7 @@ describe("Dynamic config test", () => {
     context = await startTest(root);
     creator = await generateKpAndFund(context.banksClient, context.payer);
     admin = await generateKpAndFund(context.banksClient, context.payer);
+    await initializeProgramAuthority(context.banksClient, admin);
 
     tokenAMint = await createToken(
       context.banksClient,
@@ -5,6 +5,7 @@ import { ProgramTestContext } from "solana-bankrun";
 import {
   addLiquidity,
   AddLiquidityParams,
+  batchRefreshVesting,
   claimPositionFee,
   createConfigIx,
   CreateConfigParams,
@@ -20,13 +21,20 @@ import {
   MAX_SQRT_PRICE,
   MIN_SQRT_PRICE,
   permanentLockPosition,
+  permanentLockVesting,
   refreshVestings,
+  setPoolEmergencyMode,
   swap,
   SwapParams,
   mintSplTokenTo,
   createToken,
+  initializeProgramAuthority,
+  transferAuthorityRole,
+  acceptAuthorityRole,
+  AUTHORITY_ROLE_PAUSER,
 } from "./bankrun-utils";
 import {
+  expectThrowsAsync,
   generateKpAndFund,
   startTest,
   warpSlotBy,
@@ -58,6 +66,7 @@ describe("Lock position", () => {
 
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken(
@@ -235,7 +244,7 @@ describe("Lock position", () => {
           outputTokenMint: tokenBMint,
           amountIn: new BN(100),
           minimumAmountOut: new BN(0),
-          referralTokenAccount: null,
+          referral: null,
         };
 
         await swap(context.banksClient, swapParams);
@@ -337,6 +346,171 @@ describe("Lock position", () => {
         expect(positionState.unlockedLiquidity.eq(liquidityDelta)).to.be.true;
       });
 
+      it("Batch refresh vestings", async () => {
+        const beforePositionState = await getPosition(
+          context.banksClient,
+          position
+        );
+        const liquidityToLock = beforePositionState.unlockedLiquidity.div(
+          new BN(4)
+        );
+
+        const batchVestings = [];
+        for (let i = 0; i < 2; i++) {
+          batchVestings.push(
+            await lockPosition(context.banksClient, position, user, user, {
+              cliffPoint: null,
+              periodFrequency,
+              cliffUnlockLiquidity: liquidityToLock,
+              liquidityPerPeriod: new BN(0),
+              numberOfPeriod: 0,
+            })
+          );
+        }
+
+        await batchRefreshVesting(context.banksClient, user, [
+          { position, owner: user.publicKey, vestings: batchVestings },
+        ]);
+
+        for (const vesting of batchVestings) {
+          expect(await context.banksClient.getAccount(vesting)).is.null;
+        }
+
+        const positionState = await getPosition(context.banksClient, position);
+        expect(positionState.vestedLiquidity.isZero()).to.be.true;
+        expect(
+          positionState.unlockedLiquidity.eq(
+            beforePositionState.unlockedLiquidity
+          )
+        ).to.be.true;
+      });
+
+      it("Permanent lock vesting", async () => {
+        const beforePositionState = await getPosition(
+          context.banksClient,
+          position
+        );
+        const liquidityToLock = beforePositionState.unlockedLiquidity.div(
+          new BN(2)
+        );
+
+        const vesting = await lockPosition(
+          context.banksClient,
+          position,
+          user,
+          user,
+          {
+            cliffPoint: null,
+            periodFrequency,
+            cliffUnlockLiquidity: new BN(0),
+            liquidityPerPeriod: liquidityToLock,
+            numberOfPeriod: 1,
+          }
+        );
+
+        await permanentLockVesting(context.banksClient, position, vesting, user);
+
+        expect(await context.banksClient.getAccount(vesting)).is.null;
+
+        const positionState = await getPosition(context.banksClient, position);
+        expect(positionState.vestedLiquidity.isZero()).to.be.true;
+        expect(positionState.permanentLockedLiquidity.eq(liquidityToLock)).to.be
+          .true;
+      });
+
+      it("Emergency mode releases vesting", async () => {
+        const beforePositionState = await getPosition(
+          context.banksClient,
+          position
+        );
+        const liquidityToLock = beforePositionState.unlockedLiquidity.div(
+          new BN(2)
+        );
+        const currentClock = await context.banksClient.getClock();
+
+        const vesting = await lockPosition(
+          context.banksClient,
+          position,
+          user,
+          user,
+          {
+            cliffPoint: new BN(currentClock.slot.toString()).add(
+              new BN(1_000_000)
+            ),
+            periodFrequency: new BN(0),
+            cliffUnlockLiquidity: liquidityToLock,
+            liquidityPerPeriod: new BN(0),
+            numberOfPeriod: 0,
+          }
+        );
+
+        // pauser can't toggle emergency mode, vesting releases are permanent
+        const pauser = await generateKpAndFund(
+          context.banksClient,
+          context.payer
+        );
+        await transferAuthorityRole(
+          context.banksClient,
+          admin,
+          AUTHORITY_ROLE_PAUSER,
+          pauser.publicKey
+        );
+        await acceptAuthorityRole(
+          context.banksClient,
+          pauser,
+          AUTHORITY_ROLE_PAUSER
+        );
+        await expectThrowsAsync(async () => {
+          await setPoolEmergencyMode(context.banksClient, pauser, pool, true);
+        }, "0x1775");
+
+        await setPoolEmergencyMode(context.banksClient, admin, pool, true);
+
+        await expectThrowsAsync(async () => {
+          await swap(context.banksClient, {
+            payer: user,
+            pool,
+            inputTokenMint: tokenAMint,
+            outputTokenMint: tokenBMint,
+            amountIn: new BN(100),
+            minimumAmountOut: new BN(0),
+            referral: null,
+          });
+        }, "0x1773");
+
+        // locked liquidity is released before cliff point
+        await refreshVestings(
+          context.banksClient,
+          position,
+          pool,
+          user.publicKey,
+          user,
+          [vesting]
+        );
+        expect(await context.banksClient.getAccount(vesting)).is.null;
+
+        const positionState = await getPosition(context.banksClient, position);
+        expect(positionState.vestedLiquidity.isZero()).to.be.true;
+        expect(
+          positionState.unlockedLiquidity.eq(
+            beforePositionState.unlockedLiquidity
+          )
+        ).to.be.true;
+
+        // disabling emergency mode doesn't lock released liquidity again
+        await setPoolEmergencyMode(context.banksClient, admin, pool, false);
+        const afterPositionState = await getPosition(
+          context.banksClient,
+          position
+        );
+        expect(afterPositionState.vestedLiquidity.isZero()).to.be.true;
+        expect(
+          afterPositionState.unlockedLiquidity.eq(
+            beforePositionState.unlockedLiquidity
+          )
+        ).to.be.true;
+      });
+
       it("Permanent lock position", async () => {
         await permanentLockPosition(context.banksClient, position, user, user);
 
@@ -373,6 +547,7 @@ describe("Lock position", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken2022(
@@ -551,7 +726,7 @@ describe("Lock position", () => {
           outputTokenMint: tokenBMint,
           amountIn: new BN(100),
           minimumAmountOut: new BN(0),
-          referralTokenAccount: null,
+          referral: null,
         };
 
         await swap(context.banksClient, swapParams);
@@ -0,0 +1,316 @@
+import { expect } from "chai";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  cancelPendingUpdate,
+  createConfigIx,
+  CreateConfigParams,
+  createToken,
+  initializePool,
+  InitializePoolParams,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  overridePoolFee,
+  setConfigFeeOverrideBounds,
+  initializeProgramAuthority,
+  transferPoolCreatorAuthority,
+  acceptPoolCreatorAuthority,
+  getPool,
+  swap,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Override pool fee", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let creator: Keypair;
+  let config: PublicKey;
+  let pool: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    await initializeProgramAuthority(context.banksClient, admin);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      creator.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      creator.publicKey
+    );
+
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          numberOfPeriod: 0,
+          reductionFactor: new BN(0),
+          periodFrequency: new BN(0),
+          feeSchedulerMode: 0,
+        },
+        protocolFeePercent: 10,
+        partnerFeePercent: 0,
+        referralFeePercent: 0,
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: creator.publicKey,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(Math.floor(Math.random() * 1000)),
+      createConfigParams
+    );
+
+    const initPoolParams: InitializePoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    };
+    const result = await initializePool(context.banksClient, initPoolParams);
+    pool = result.pool;
+  });
+
+  it("Pool creator authority override pool fee within bounds", async () => {
+    // override is disabled until admin sets bounds
+    await expectThrowsAsync(async () => {
+      await overridePoolFee(context.banksClient, {
+        poolCreatorAuthority: creator,
+        config,
+        pool,
+        tradeFeeNumerator: new BN(5_000_000),
+      });
+    }, "0x17a7");
+
+    await setConfigFeeOverrideBounds(
+      context.banksClient,
+      admin,
+      config,
+      new BN(1_000_000),
+      new BN(10_000_000)
+    );
+
+    await expectThrowsAsync(async () => {
+      await overridePoolFee(context.banksClient, {
+        poolCreatorAuthority: creator,
+        config,
+        pool,
+        tradeFeeNumerator: new BN(20_000_000),
+      });
+    }, "0x1774");
+
+    await overridePoolFee(context.banksClient, {
+      poolCreatorAuthority: creator,
+      config,
+      pool,
+      tradeFeeNumerator: new BN(5_000_000),
+    });
+  });
+
+  it("Pool fee override takes effect after delay", async () => {
+    await setConfigFeeOverrideBounds(
+      context.banksClient,
+      admin,
+      config,
+      new BN(1_000_000),
+      new BN(10_000_000)
+    );
+    await overridePoolFee(context.banksClient, {
+      poolCreatorAuthority: creator,
+      config,
+      pool,
+      tradeFeeNumerator: new BN(5_000_000),
+    });
+
+    // base fee is unchanged until the activation point
+    let poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolFees.baseFee.cliffFeeNumerator.toNumber()).eq(
+      2_500_000
+    );
+
+    const { activationPoint } = poolState.pendingBaseFee;
+    const currentClock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        BigInt(activationPoint.toString())
+      )
+    );
+
+    // swap applies the pending override
+    await swap(context.banksClient, {
+      payer: creator,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolFees.baseFee.cliffFeeNumerator.toNumber()).eq(
+      5_000_000
+    );
+    expect(poolState.pendingBaseFee.activationPoint.isZero()).to.be.true;
+  });
+
+  it("Cancel pending pool fee override", async () => {
+    await setConfigFeeOverrideBounds(
+      context.banksClient,
+      admin,
+      config,
+      new BN(1_000_000),
+      new BN(10_000_000)
+    );
+    await overridePoolFee(context.banksClient, {
+      poolCreatorAuthority: creator,
+      config,
+      pool,
+      tradeFeeNumerator: new BN(5_000_000),
+    });
+
+    // pool partner is the pool creator authority at pool creation
+    await cancelPendingUpdate(context.banksClient, creator, pool);
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.pendingBaseFee.activationPoint.isZero()).to.be.true;
+    expect(poolState.poolFees.baseFee.cliffFeeNumerator.toNumber()).eq(
+      2_500_000
+    );
+  });
+
+  it("Only pool creator authority can override pool fee", async () => {
+    await setConfigFeeOverrideBounds(
+      context.banksClient,
+      admin,
+      config,
+      new BN(1_000_000),
+      new BN(10_000_000)
+    );
+
+    const randomUser = await generateKpAndFund(
+      context.banksClient,
+      context.payer
+    );
+    await expectThrowsAsync(async () => {
+      await overridePoolFee(context.banksClient, {
+        poolCreatorAuthority: randomUser,
+        config,
+        pool,
+        tradeFeeNumerator: new BN(5_000_000),
+      });
+    }, "0x1798");
+  });
+
+  it("Pool creator authority hands over to a new key", async () => {
+    await setConfigFeeOverrideBounds(
+      context.banksClient,
+      admin,
+      config,
+      new BN(1_000_000),
+      new BN(10_000_000)
+    );
+
+    const newCreator = await generateKpAndFund(
+      context.banksClient,
+      context.payer
+    );
+    const randomUser = await generateKpAndFund(
+      context.banksClient,
+      context.payer
+    );
+
+    // only current pool creator authority can propose
+    await expectThrowsAsync(async () => {
+      await transferPoolCreatorAuthority(
+        context.banksClient,
+        randomUser,
+        config,
+        newCreator.publicKey
+      );
+    }, "0x1798");
+
+    await transferPoolCreatorAuthority(
+      context.banksClient,
+      creator,
+      config,
+      newCreator.publicKey
+    );
+
+    // only pending pool creator authority can accept
+    await expectThrowsAsync(async () => {
+      await acceptPoolCreatorAuthority(context.banksClient, randomUser, config);
+    }, "0x1798");
+
+    // old key keeps authority until new key accepts
+    await overridePoolFee(context.banksClient, {
+      poolCreatorAuthority: creator,
+      config,
+      pool,
+      tradeFeeNumerator: new BN(4_000_000),
+    });
+
+    await acceptPoolCreatorAuthority(context.banksClient, newCreator, config);
+
+    await expectThrowsAsync(async () => {
+      await overridePoolFee(context.banksClient, {
+        poolCreatorAuthority: creator,
+        config,
+        pool,
+        tradeFeeNumerator: new BN(5_000_000),
+      });
+    }, "0x1798");
+
+    await overridePoolFee(context.banksClient, {
+      poolCreatorAuthority: newCreator,
+      config,
+      pool,
+      tradeFeeNumerator: new BN(5_000_000),
+    });
+  });
+});
@@ -1,5 +1,10 @@
 import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, randomID, startTest } from "./bankrun-utils/common";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   addLiquidity,
@@ -14,10 +19,22 @@ import {
   mintSplTokenTo,
   createToken,
   removeAllLiquidity,
+  removeLiquiditySingleSide,
   closePosition,
+  getPool,
+  getPosition,
+  mergePositions,
+  initializeProgramAuthority,
+  getTokenAccount,
 } from "./bankrun-utils";
 import BN from "bn.js";
-import { ExtensionType } from "@solana/spl-token";
+import {
+  AccountLayout,
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
 import { createToken2022, mintToToken2022 } from "./bankrun-utils/token2022";
 
 describe("Remove liquidity", () => {
@@ -38,6 +55,7 @@ describe("Remove liquidity", () => {
 
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken(
@@ -171,6 +189,104 @@ describe("Remove liquidity", () => {
       await removeAllLiquidity(context.banksClient, removeAllLiquidityParams);
 
       // close position
+      const prePoolState = await getPool(context.banksClient, pool);
+      await closePosition(context.banksClient, { owner: user, pool, position });
+      const postPoolState = await getPool(context.banksClient, pool);
+      expect(
+        prePoolState.metrics.totalPosition
+          .sub(postPoolState.metrics.totalPosition)
+          .toNumber()
+      ).eq(1);
+    });
+
+    it("User merge two positions", async () => {
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      const sourcePosition = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+
+      const liquidity = new BN("100000000000");
+      for (const p of [position, sourcePosition]) {
+        await addLiquidity(context.banksClient, {
+          owner: user,
+          pool,
+          position: p,
+          liquidityDelta: liquidity,
+          tokenAAmountThreshold: U64_MAX,
+          tokenBAmountThreshold: U64_MAX,
+        });
+      }
+
+      const prePoolState = await getPool(context.banksClient, pool);
+      await mergePositions(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        sourcePosition,
+      });
+      const postPoolState = await getPool(context.banksClient, pool);
+
+      const positionState = await getPosition(context.banksClient, position);
+      expect(positionState.unlockedLiquidity.toString()).eq(
+        liquidity.muln(2).toString()
+      );
+      expect(postPoolState.liquidity.toString()).eq(
+        prePoolState.liquidity.toString()
+      );
+      expect(
+        prePoolState.metrics.totalPosition
+          .sub(postPoolState.metrics.totalPosition)
+          .toNumber()
+      ).eq(1);
+      expect(await context.banksClient.getAccount(sourcePosition)).to.be.null;
+    });
+
+    it("User remove liquidity to a single token", async () => {
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+
+      const liquidity = new BN("100000000000");
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: liquidity,
+        tokenAAmountThreshold: U64_MAX,
+        tokenBAmountThreshold: U64_MAX,
+      });
+
+      const poolState = await getPool(context.banksClient, pool);
+      const preTokenBVaultBalance = AccountLayout.decode(
+        (await context.banksClient.getAccount(poolState.tokenBVault)).data
+      ).amount;
+
+      // receive only token a
+      await removeLiquiditySingleSide(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: liquidity,
+        tradeDirection: 1,
+        minimumAmountOut: new BN(1),
+      });
+
+      const postTokenBVaultBalance = AccountLayout.decode(
+        (await context.banksClient.getAccount(poolState.tokenBVault)).data
+      ).amount;
+      expect(postTokenBVaultBalance).eq(preTokenBVaultBalance);
+
       await closePosition(context.banksClient, { owner: user, pool, position });
     });
   });
@@ -192,6 +308,7 @@ describe("Remove liquidity", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       tokenAMint = await createToken2022(
@@ -314,5 +431,78 @@ describe("Remove liquidity", () => {
       };
       await removeLiquidity(context.banksClient, removeLiquidityParams);
     });
+
+    it("Slippage check uses transfer fee excluded amounts", async () => {
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      const liquidity = MIN_SQRT_PRICE.mul(new BN(1_000_000_000));
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: liquidity,
+        tokenAAmountThreshold: U64_MAX,
+        tokenBAmountThreshold: U64_MAX,
+      });
+
+      const { tokenAVault } = await getPool(context.banksClient, pool);
+      const userTokenA = getAssociatedTokenAddressSync(
+        tokenAMint,
+        user.publicKey,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const liquidityDelta = liquidity.divn(2);
+
+      const beforeVault = (
+        await getTokenAccount(context.banksClient, tokenAVault)
+      ).amount;
+      const beforeUser = (
+        await getTokenAccount(context.banksClient, userTokenA)
+      ).amount;
+      await removeLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta,
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+      const afterVault = (
+        await getTokenAccount(context.banksClient, tokenAVault)
+      ).amount;
+      const afterUser = (
+        await getTokenAccount(context.banksClient, userTokenA)
+      ).amount;
+      const amountA = new BN((beforeVault - afterVault).toString());
+      const receivedAmountA = new BN((afterUser - beforeUser).toString());
+      expect(amountA.gt(receivedAmountA)).to.be.true;
+
+      // same liquidity delta removes the same amount, a threshold above
+      // the amount received after transfer fee fails
+      await expectThrowsAsync(async () => {
+        await removeLiquidity(context.banksClient, {
+          owner: user,
+          pool,
+          position,
+          liquidityDelta,
+          tokenAAmountThreshold: amountA,
+          tokenBAmountThreshold: new BN(0),
+        });
+      }, "0x1772");
+
+      await removeLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta,
+        tokenAAmountThreshold: receivedAmountA,
+        tokenBAmountThreshold: new BN(0),
+      });
+    });
   });
 });
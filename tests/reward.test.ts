@@ -1,30 +1,49 @@
 import { Clock, ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import {
   addLiquidity,
   AddLiquidityParams,
+  claimAll,
   claimReward,
+  claimVestedReward,
   createConfigIx,
+  createRewardVestingEscrow,
+  getRewardVestingEscrow,
   CreateConfigParams,
   createPosition,
+  createPositionRewardExtension,
   fundReward,
   getPool,
+  getPosition,
+  getPositionPending,
   initializePool,
   InitializePoolParams,
   initializeReward,
   InitializeRewardParams,
+  initializeRewardExtension,
   MIN_LP_AMOUNT,
+  U64_MAX,
   MAX_SQRT_PRICE,
   MIN_SQRT_PRICE,
   updateRewardDuration,
+  updateRewardEmissionSchedule,
   updateRewardFunder,
+  updateRewards,
+  permanentLockPosition,
+  setConfigRewardBoost,
   withdrawIneligibleReward,
   createToken,
   mintSplTokenTo,
+  initializeProgramAuthority,
 } from "./bankrun-utils";
 import BN from "bn.js";
 import { describe } from "mocha";
+import { expect } from "chai";
 import { ExtensionType } from "@solana/spl-token";
 import { createToken2022, mintToToken2022 } from "./bankrun-utils/token2022";
 
@@ -52,6 +71,7 @@ describe("Reward unit-testing", () => {
       funder = await generateKpAndFund(context.banksClient, context.payer);
       creator = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
 
       tokenAMint = await createToken(
         context.banksClient,
@@ -207,7 +227,7 @@ describe("Reward unit-testing", () => {
       // update new funder
       await updateRewardFunder(context.banksClient, {
         index,
-        admin: admin,
+        signer: admin,
         pool,
         newFunder: funder.publicKey,
       });
@@ -251,6 +271,551 @@ describe("Reward unit-testing", () => {
         pool,
       });
     });
+
+    it("Anyone can crank pool rewards", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      const index = 0;
+      await initializeReward(context.banksClient, {
+        index,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      });
+
+      await fundReward(context.banksClient, {
+        index,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      // no user action for a while
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(60 * 60)
+        )
+      );
+
+      const keeper = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      await updateRewards(context.banksClient, {
+        payer: keeper,
+        pool,
+      });
+    });
+
+    it("Claim position fee and all rewards in one instruction", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(100),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+
+      const index = 0;
+      await initializeReward(context.banksClient, {
+        index,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      });
+      await fundReward(context.banksClient, {
+        index,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(60 * 60)
+        )
+      );
+
+      await claimAll(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+      });
+    });
+
+    it("Reward with decaying emission", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(100),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+
+      const index = 0;
+      await initializeReward(context.banksClient, {
+        index,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      });
+
+      // reward rate halves every 6 hours
+      await updateRewardEmissionSchedule(context.banksClient, {
+        index,
+        signer: admin,
+        pool,
+        emissionPeriod: 6 * 60 * 60,
+        emissionDecayBps: 5000,
+      });
+
+      await fundReward(context.banksClient, {
+        index,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(60 * 60)
+        )
+      );
+
+      await claimReward(context.banksClient, {
+        index,
+        user,
+        pool,
+        position,
+      });
+    });
+
+    it("Reward with claim vesting", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(100),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+
+      const index = 0;
+      const claimVestingDuration = 24 * 60 * 60;
+      // longer than 1 year
+      await expectThrowsAsync(async () => {
+        await initializeReward(context.banksClient, {
+          index,
+          payer: admin,
+          rewardDuration: new BN(24 * 60 * 60),
+          pool,
+          rewardMint,
+          claimVestingDuration: 31536000 + 1,
+        });
+      }, "0x17c6");
+      await initializeReward(context.banksClient, {
+        index,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+        claimVestingDuration,
+      });
+      await fundReward(context.banksClient, {
+        index,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(60 * 60)
+        )
+      );
+
+      // claimed rewards must be locked in escrow of the receiver
+      await expectThrowsAsync(async () => {
+        await claimReward(context.banksClient, {
+          index,
+          user,
+          pool,
+          position,
+        });
+      }, "0x17c7");
+
+      const rewardVestingEscrow = await createRewardVestingEscrow(
+        context.banksClient,
+        context.payer,
+        pool,
+        index,
+        user.publicKey
+      );
+      await claimReward(context.banksClient, {
+        index,
+        user,
+        pool,
+        position,
+        rewardVestingEscrow,
+      });
+
+      let escrowState = await getRewardVestingEscrow(
+        context.banksClient,
+        rewardVestingEscrow
+      );
+      const lockedAmount = escrowState.lockedAmount;
+      expect(lockedAmount.gtn(0)).to.be.true;
+      expect(escrowState.endTime.toString()).eq(
+        (currentClock.unixTimestamp + BigInt(60 * 60 + claimVestingDuration))
+          .toString()
+      );
+
+      // half of locked rewards are released after half of vesting duration
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp +
+            BigInt(60 * 60 + claimVestingDuration / 2)
+        )
+      );
+      await claimVestedReward(context.banksClient, user, pool, index);
+      escrowState = await getRewardVestingEscrow(
+        context.banksClient,
+        rewardVestingEscrow
+      );
+      expect(escrowState.totalClaimedAmount.toString()).eq(
+        lockedAmount.divn(2).toString()
+      );
+    });
+
+    it("Permanently locked liquidity earns boosted rewards", async () => {
+      // only config admin
+      await expectThrowsAsync(async () => {
+        await setConfigRewardBoost(
+          context.banksClient,
+          user,
+          config,
+          0,
+          10_000
+        );
+      }, "0x1775");
+      // at most 2x
+      await expectThrowsAsync(async () => {
+        await setConfigRewardBoost(
+          context.banksClient,
+          admin,
+          config,
+          0,
+          10_001
+        );
+      }, "0x17c8");
+      await setConfigRewardBoost(
+        context.banksClient,
+        admin,
+        config,
+        0,
+        10_000
+      );
+
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.vestingRewardBoostBps).eq(0);
+      expect(poolState.permanentLockRewardBoostBps).eq(10_000);
+
+      const positions = [];
+      for (let i = 0; i < 2; i++) {
+        const position = await createPosition(
+          context.banksClient,
+          user,
+          user.publicKey,
+          pool
+        );
+        await addLiquidity(context.banksClient, {
+          owner: user,
+          pool,
+          position,
+          liquidityDelta: liquidity,
+          tokenAAmountThreshold: U64_MAX,
+          tokenBAmountThreshold: U64_MAX,
+        });
+        positions.push(position);
+      }
+      const [unlockedPosition, lockedPosition] = positions;
+      await permanentLockPosition(
+        context.banksClient,
+        lockedPosition,
+        user,
+        user
+      );
+
+      const index = 0;
+      await initializeReward(context.banksClient, {
+        index,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      });
+      await fundReward(context.banksClient, {
+        index,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(24 * 60 * 60)
+        )
+      );
+
+      for (const position of positions) {
+        await claimReward(context.banksClient, {
+          index,
+          user,
+          pool,
+          position,
+        });
+      }
+
+      const unlockedReward = (
+        await getPosition(context.banksClient, unlockedPosition)
+      ).rewardInfos[index].totalClaimedRewards;
+      const lockedReward = (
+        await getPosition(context.banksClient, lockedPosition)
+      ).rewardInfos[index].totalClaimedRewards;
+      expect(unlockedReward.gtn(0)).to.be.true;
+      // permanently locked liquidity earns twice
+      expect(lockedReward.sub(unlockedReward.muln(2)).abs().lten(2)).to.be
+        .true;
+    });
+
+    it("Current funder can rotate reward funder", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      const index = 0;
+      await initializeReward(context.banksClient, {
+        index,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      });
+
+      await updateRewardFunder(context.banksClient, {
+        index,
+        signer: admin,
+        pool,
+        newFunder: funder.publicKey,
+      });
+
+      // current funder hands over the reward to a new funder
+      const newFunder = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      await updateRewardFunder(context.banksClient, {
+        index,
+        signer: funder,
+        pool,
+        newFunder: newFunder.publicKey,
+      });
+    });
+
+    it("Admin can initialize reward extension", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      const rewardExtension = await initializeRewardExtension(
+        context.banksClient,
+        {
+          admin,
+          pool,
+        }
+      );
+      const rewardExtensionAccount = await context.banksClient.getAccount(
+        rewardExtension
+      );
+      expect(rewardExtensionAccount).not.null;
+
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      const positionRewardExtension = await createPositionRewardExtension(
+        context.banksClient,
+        {
+          payer: user,
+          position,
+        }
+      );
+      const positionRewardExtensionAccount =
+        await context.banksClient.getAccount(positionRewardExtension);
+      expect(positionRewardExtensionAccount).not.null;
+
+      // pending of extension rewards requires reward extension accounts
+      await expectThrowsAsync(async () => {
+        await getPositionPending(context.banksClient, user, pool, position);
+      }, "0x179a");
+      const positionPending = await getPositionPending(
+        context.banksClient,
+        user,
+        pool,
+        position,
+        rewardExtension,
+        positionRewardExtension
+      );
+      expect(
+        positionPending.rewardPendings.every((pending) => pending.isZero())
+      ).to.be.true;
+    });
   });
 
   // SPL-Token2022
@@ -278,6 +843,7 @@ describe("Reward unit-testing", () => {
       funder = await generateKpAndFund(context.banksClient, context.payer);
       creator = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
 
       tokenAMint = await createToken2022(
         context.banksClient,
@@ -434,7 +1000,7 @@ describe("Reward unit-testing", () => {
       // update new funder
       await updateRewardFunder(context.banksClient, {
         index,
-        admin: admin,
+        signer: admin,
         pool,
         newFunder: funder.publicKey,
       });
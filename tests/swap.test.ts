@@ -1,6 +1,17 @@
-import { ProgramTestContext } from "solana-bankrun";
-import { generateKpAndFund, randomID, startTest } from "./bankrun-utils/common";
-import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
+import {
+  Keypair,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
 import {
   addLiquidity,
   AddLiquidityParams,
@@ -16,10 +27,47 @@ import {
   SwapParams,
   createToken,
   mintSplTokenTo,
+  createReferral,
+  claimReferralFee,
+  getReferral,
+  createFeeDiscountRegistry,
+  updateFeeDiscountRegistry,
+  initializeProgramAuthority,
+  disableFrozenPool,
+  freezeTokenAccount,
+  getPool,
+  createVaultWhitelist,
+  updateVaultWhitelist,
+  updateActivationPoint,
+  createPoolStats,
+  getPoolStats,
+  skimVaults,
+  setConfigCpiGuard,
+  initializeTradeMining,
+  fundTradeMining,
+  createTraderReward,
+  claimTradeMiningReward,
+  getTradeMining,
+  withdrawUnaccruedTradeMining,
+  getTraderReward,
+  getOrCreateAssociatedTokenAccount,
+  getTokenAccount,
+  removeLiquidity,
 } from "./bankrun-utils";
 import BN from "bn.js";
-import { ExtensionType } from "@solana/spl-token";
-import { createToken2022, mintToToken2022 } from "./bankrun-utils/token2022";
+import {
+  createMintToInstruction,
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+  getTransferFeeAmount,
+  TOKEN_2022_PROGRAM_ID,
+  unpackAccount,
+} from "@solana/spl-token";
+import {
+  createInterestBearingToken2022,
+  createToken2022,
+  mintToToken2022,
+} from "./bankrun-utils/token2022";
 
 describe("Swap token", () => {
   describe("SPL Token", () => {
@@ -41,11 +89,13 @@ describe("Swap token", () => {
 
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       inputTokenMint = await createToken(
         context.banksClient,
         context.payer,
+        context.payer.publicKey,
         context.payer.publicKey
       );
       outputTokenMint = await createToken(
@@ -98,7 +148,7 @@ describe("Swap token", () => {
           },
           protocolFeePercent: 10,
           partnerFeePercent: 0,
-          referralFeePercent: 0,
+          referralFeePercent: 20,
           dynamicFee: null,
         },
         sqrtMinPrice: new BN(MIN_SQRT_PRICE),
@@ -140,6 +190,40 @@ describe("Swap token", () => {
       );
     });
 
+    it("Frozen vault disables pool", async () => {
+      const swapParams: SwapParams = {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      };
+
+      // pool has no frozen vault
+      await expectThrowsAsync(async () => {
+        await disableFrozenPool(context.banksClient, user, pool);
+      }, "0x1789");
+
+      const { tokenAVault } = await getPool(context.banksClient, pool);
+      await freezeTokenAccount(
+        context.banksClient,
+        context.payer,
+        inputTokenMint,
+        tokenAVault
+      );
+
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, swapParams);
+      }, "0x17b1");
+
+      await disableFrozenPool(context.banksClient, user, pool);
+      const poolState = await getPool(context.banksClient, pool);
+      // swap and add liquidity are disabled
+      expect(poolState.poolStatus).eq(0b0011);
+    });
+
     it("User swap A->B", async () => {
       const addLiquidityParams: AddLiquidityParams = {
         owner: user,
@@ -158,10 +242,710 @@ describe("Swap token", () => {
         outputTokenMint,
         amountIn: new BN(10),
         minimumAmountOut: new BN(0),
-        referralTokenAccount: null,
+        referral: null,
       };
 
+      const { sequence } = await getPool(context.banksClient, pool);
       await swap(context.banksClient, swapParams);
+
+      // every instruction changing pool state increments sequence
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.sequence.toNumber()).eq(sequence.toNumber() + 1);
+    });
+
+    it("User swap A->B records pool stats", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+      const poolStats = await createPoolStats(context.banksClient, user, pool);
+
+      const swapParams: SwapParams = {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+        poolStats,
+      };
+      await swap(context.banksClient, swapParams);
+      await swap(context.banksClient, { ...swapParams, amountIn: new BN(500) });
+
+      const { unixTimestamp } = await context.banksClient.getClock();
+      const hour = 3600n;
+      const index = Number((unixTimestamp / hour) % 24n);
+      const poolStatsState = await getPoolStats(context.banksClient, poolStats);
+      const bucket = poolStatsState.hourlyBuckets[index];
+      expect(bucket.startTimestamp.toString()).eq(
+        (unixTimestamp - (unixTimestamp % hour)).toString()
+      );
+      expect(bucket.swapCount.toNumber()).eq(2);
+      expect(bucket.volumeA.toNumber()).eq(1500);
+      expect(bucket.totalFeeB.gte(bucket.lpFeeB)).to.be.true;
+      expect(bucket.totalFeeA.isZero()).to.be.true;
+
+      const poolState = await getPool(context.banksClient, pool);
+      expect(bucket.sqrtPrice.toString()).eq(poolState.sqrtPrice.toString());
+
+      // pool volume metrics count the same swaps
+      const { volumeMetrics } = poolState;
+      expect(volumeMetrics.totalVolumeA.toNumber()).eq(1500);
+      expect(volumeMetrics.totalVolumeB.toString()).eq(
+        bucket.volumeB.toString()
+      );
+      expect(volumeMetrics.currentWindowLpBFee.toString()).eq(
+        bucket.lpFeeB.toString()
+      );
+    });
+
+    it("User swap A->B accrues trade mining rewards", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+
+      const { unixTimestamp } = await context.banksClient.getClock();
+      const endTime = new BN((unixTimestamp + BigInt(3600)).toString());
+      const tradeMiningParams = {
+        signer: user,
+        pool,
+        rewardMint: outputTokenMint,
+        funder: creator.publicKey,
+        rewardPerFeeA: new BN(0),
+        // 1 reward token per unit of fee, in Q64.64
+        rewardPerFeeB: new BN(1).shln(64),
+        endTime,
+      };
+      // only reward admin or pool partner can initialize trade mining
+      await expectThrowsAsync(async () => {
+        await initializeTradeMining(context.banksClient, tradeMiningParams);
+      }, "0x17c9");
+
+      const tradeMining = await initializeTradeMining(context.banksClient, {
+        ...tradeMiningParams,
+        signer: admin,
+      });
+      await fundTradeMining(
+        context.banksClient,
+        creator,
+        pool,
+        new BN(1_000_000)
+      );
+      const traderReward = await createTraderReward(
+        context.banksClient,
+        user,
+        pool,
+        user.publicKey
+      );
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(100_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+        tradeMining,
+        traderReward,
+      });
+
+      const traderRewardState = await getTraderReward(
+        context.banksClient,
+        traderReward
+      );
+      expect(traderRewardState.totalFeeB.isZero()).to.be.false;
+      expect(traderRewardState.pendingAmount.toString()).eq(
+        traderRewardState.totalFeeB.toString()
+      );
+      const tradeMiningState = await getTradeMining(
+        context.banksClient,
+        tradeMining
+      );
+      expect(tradeMiningState.remainingBudget.toString()).eq(
+        new BN(1_000_000).sub(traderRewardState.pendingAmount).toString()
+      );
+
+      await claimTradeMiningReward(context.banksClient, user, pool);
+      const claimedState = await getTraderReward(
+        context.banksClient,
+        traderReward
+      );
+      expect(claimedState.pendingAmount.isZero()).to.be.true;
+      expect(claimedState.totalClaimedAmount.toString()).eq(
+        traderRewardState.pendingAmount.toString()
+      );
+
+      // unaccrued budget is withdrawn by funder once trade mining ended
+      await expectThrowsAsync(async () => {
+        await withdrawUnaccruedTradeMining(context.banksClient, creator, pool);
+      }, "0x1795");
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          BigInt(endTime.toString())
+        )
+      );
+      const creatorTokenAccount = getAssociatedTokenAddressSync(
+        outputTokenMint,
+        creator.publicKey
+      );
+      const beforeCreatorAmount = (
+        await getTokenAccount(context.banksClient, creatorTokenAccount)
+      ).amount;
+      await withdrawUnaccruedTradeMining(context.banksClient, creator, pool);
+      const afterCreatorAmount = (
+        await getTokenAccount(context.banksClient, creatorTokenAccount)
+      ).amount;
+      expect((afterCreatorAmount - beforeCreatorAmount).toString()).eq(
+        tradeMiningState.remainingBudget.toString()
+      );
+      const endedState = await getTradeMining(context.banksClient, tradeMining);
+      expect(endedState.remainingBudget.isZero()).to.be.true;
+    });
+
+    it("User swap A->B with referral", async () => {
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      const referrer = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      const referral = await createReferral(
+        context.banksClient,
+        referrer,
+        pool,
+        referrer.publicKey
+      );
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1000),
+        minimumAmountOut: new BN(0),
+        referral,
+      });
+
+      // referral fee is accrued instead of transferred
+      let referralState = await getReferral(context.banksClient, referral);
+      expect(referralState.feeBPending.toString()).eq(
+        referralState.totalFeeB.toString()
+      );
+
+      await claimReferralFee(context.banksClient, {
+        referrer,
+        pool,
+      });
+
+      referralState = await getReferral(context.banksClient, referral);
+      expect(referralState.feeAPending.isZero()).to.be.true;
+      expect(referralState.feeBPending.isZero()).to.be.true;
+    });
+
+    it("User swap A->B with referral token account", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+
+      const referrer = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      const referralTokenAccount = await getOrCreateAssociatedTokenAccount(
+        context.banksClient,
+        context.payer,
+        outputTokenMint,
+        referrer.publicKey
+      );
+      const referral = await createReferral(
+        context.banksClient,
+        referrer,
+        pool,
+        referrer.publicKey
+      );
+
+      // referral fee goes either to the token account or the referral account
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, {
+          payer: user,
+          pool,
+          inputTokenMint,
+          outputTokenMint,
+          amountIn: new BN(1000),
+          minimumAmountOut: new BN(0),
+          referral,
+          referralTokenAccount,
+        });
+      }, "0x17d0");
+
+      // callers of the account list before referral accounts still get
+      // referral fee transferred in the swap
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+        referralTokenAccount,
+      });
+      const referralTokenAccountState = await getTokenAccount(
+        context.banksClient,
+        referralTokenAccount
+      );
+      expect(referralTokenAccountState.amount > BigInt(0)).to.be.true;
+      const { unclaimedReferralBFee } = await getPool(
+        context.banksClient,
+        pool
+      );
+      expect(unclaimedReferralBFee.isZero()).to.be.true;
+    });
+
+    it("Skim tokens transferred to vaults", async () => {
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      });
+      const referrer = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      const referral = await createReferral(
+        context.banksClient,
+        referrer,
+        pool,
+        referrer.publicKey
+      );
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1000),
+        minimumAmountOut: new BN(0),
+        referral,
+      });
+
+      // sweep rounding dust of deposits and swap first
+      await skimVaults(context.banksClient, user, pool);
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.unclaimedReferralBFee.isZero()).to.be.false;
+
+      // airdrop to vault
+      const transaction = new Transaction().add(
+        createMintToInstruction(
+          inputTokenMint,
+          poolState.tokenAVault,
+          context.payer.publicKey,
+          1_000
+        )
+      );
+      transaction.recentBlockhash = (
+        await context.banksClient.getLatestBlockhash()
+      )[0];
+      transaction.sign(context.payer);
+      await context.banksClient.processTransaction(transaction);
+
+      await skimVaults(context.banksClient, user, pool);
+      const skimmedPoolState = await getPool(context.banksClient, pool);
+      expect(
+        skimmedPoolState.metrics.totalLpAFee
+          .sub(poolState.metrics.totalLpAFee)
+          .toNumber()
+      ).eq(1_000);
+      expect(skimmedPoolState.metrics.totalLpBFee.toString()).eq(
+        poolState.metrics.totalLpBFee.toString()
+      );
+
+      // referral fee stays owed to referrer
+      await claimReferralFee(context.banksClient, {
+        referrer,
+        pool,
+      });
+      expect(
+        (await getPool(context.banksClient, pool)).unclaimedReferralBFee.isZero()
+      ).to.be.true;
+    });
+
+    it("User swap A->B with fee discount", async () => {
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      // traders holding input token get discount
+      const feeDiscountRegistry = await createFeeDiscountRegistry(
+        context.banksClient,
+        {
+          admin,
+          pool,
+          mint: inputTokenMint,
+          tiers: [
+            { minAmount: new BN(1), discountBps: 1000 },
+            { minAmount: new BN(1_000_000), discountBps: 2000 },
+          ],
+        }
+      );
+
+      // discount token account must match discount mint
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, {
+          payer: user,
+          pool,
+          inputTokenMint,
+          outputTokenMint,
+          amountIn: new BN(1000),
+          minimumAmountOut: new BN(0),
+          referral: null,
+          feeDiscountRegistry,
+          feeDiscountTokenAccount: getAssociatedTokenAddressSync(
+            outputTokenMint,
+            user.publicKey
+          ),
+        });
+      }, "0x17ab");
+
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+        feeDiscountRegistry,
+      });
+
+      // tiers must be increasing
+      await expectThrowsAsync(async () => {
+        await updateFeeDiscountRegistry(context.banksClient, {
+          admin,
+          feeDiscountRegistry,
+          tiers: [
+            { minAmount: new BN(1_000_000), discountBps: 1000 },
+            { minAmount: new BN(1), discountBps: 2000 },
+          ],
+        });
+      }, "0x17aa");
+
+      // disable discount
+      await updateFeeDiscountRegistry(context.banksClient, {
+        admin,
+        feeDiscountRegistry,
+        tiers: [],
+      });
+    });
+
+    it("Whitelisted vaults swap before activation point", async () => {
+      const marketMaker = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        inputTokenMint,
+        context.payer,
+        marketMaker.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        outputTokenMint,
+        context.payer,
+        marketMaker.publicKey
+      );
+
+      const launchConfig = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(randomID()),
+        {
+          poolFees: {
+            baseFee: {
+              cliffFeeNumerator: new BN(2_500_000),
+              numberOfPeriod: 0,
+              reductionFactor: new BN(0),
+              periodFrequency: new BN(0),
+              feeSchedulerMode: 0,
+            },
+            protocolFeePercent: 10,
+            partnerFeePercent: 0,
+            referralFeePercent: 20,
+            dynamicFee: null,
+          },
+          sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+          sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+          vaultConfigKey: PublicKey.default,
+          poolCreatorAuthority: PublicKey.default,
+          activationType: 0,
+          collectFeeMode: 0,
+        }
+      );
+      const currentSlot = await context.banksClient.getSlot();
+      const activationPoint = new BN(Number(currentSlot) + 20);
+      const { pool: launchPool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config: launchConfig,
+        tokenAMint: inputTokenMint,
+        tokenBMint: outputTokenMint,
+        liquidity: new BN(MIN_SQRT_PRICE.muln(30)),
+        sqrtPrice,
+        activationPoint,
+      });
+
+      // partner is not set, so only admin manages the whitelist
+      await expectThrowsAsync(async () => {
+        await createVaultWhitelist(context.banksClient, {
+          signer: user,
+          pool: launchPool,
+          vaults: [marketMaker.publicKey],
+        });
+      }, "0x17b3");
+      await expectThrowsAsync(async () => {
+        await createVaultWhitelist(context.banksClient, {
+          signer: admin,
+          pool: launchPool,
+          vaults: [marketMaker.publicKey, marketMaker.publicKey],
+        });
+      }, "0x17b2");
+      const vaultWhitelist = await createVaultWhitelist(context.banksClient, {
+        signer: admin,
+        pool: launchPool,
+        vaults: [marketMaker.publicKey],
+      });
+
+      // warp into pre-activation window
+      context.warpToSlot(BigInt(activationPoint.toNumber() - 2));
+
+      const swapParams: SwapParams = {
+        payer: marketMaker,
+        pool: launchPool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      };
+      // vault whitelist is required before activation point
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, swapParams);
+      }, "0x1773");
+      await swap(context.banksClient, { ...swapParams, vaultWhitelist });
+
+      // non whitelisted user can't swap
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, {
+          ...swapParams,
+          payer: user,
+          vaultWhitelist,
+        });
+      }, "0x1773");
+
+      // removed vault can't swap anymore
+      await updateVaultWhitelist(context.banksClient, {
+        signer: admin,
+        pool: launchPool,
+        vaults: [],
+      });
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, { ...swapParams, vaultWhitelist });
+      }, "0x1773");
+    });
+
+    it("Delay activation point before launch", async () => {
+      const launchConfig = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(randomID()),
+        {
+          poolFees: {
+            baseFee: {
+              cliffFeeNumerator: new BN(2_500_000),
+              numberOfPeriod: 0,
+              reductionFactor: new BN(0),
+              periodFrequency: new BN(0),
+              feeSchedulerMode: 0,
+            },
+            protocolFeePercent: 10,
+            partnerFeePercent: 0,
+            referralFeePercent: 20,
+            dynamicFee: null,
+          },
+          sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+          sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+          vaultConfigKey: PublicKey.default,
+          poolCreatorAuthority: PublicKey.default,
+          activationType: 0,
+          collectFeeMode: 0,
+        }
+      );
+      const currentSlot = Number(await context.banksClient.getSlot());
+      const { pool: launchPool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config: launchConfig,
+        tokenAMint: inputTokenMint,
+        tokenBMint: outputTokenMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: new BN(currentSlot + 20),
+      });
+
+      // only admin or partner
+      await expectThrowsAsync(async () => {
+        await updateActivationPoint(
+          context.banksClient,
+          user,
+          launchPool,
+          new BN(currentSlot + 25)
+        );
+      }, "0x17ba");
+      // activation point can't be pulled forward
+      await expectThrowsAsync(async () => {
+        await updateActivationPoint(
+          context.banksClient,
+          admin,
+          launchPool,
+          new BN(currentSlot + 10)
+        );
+      }, "0x1778");
+
+      await updateActivationPoint(
+        context.banksClient,
+        admin,
+        launchPool,
+        new BN(currentSlot + 25)
+      );
+    });
+
+    it("Cpi guard requires top level swaps after launch", async () => {
+      const guardedConfig = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(randomID()),
+        {
+          poolFees: {
+            baseFee: {
+              cliffFeeNumerator: new BN(2_500_000),
+              numberOfPeriod: 0,
+              reductionFactor: new BN(0),
+              periodFrequency: new BN(0),
+              feeSchedulerMode: 0,
+            },
+            protocolFeePercent: 10,
+            partnerFeePercent: 0,
+            referralFeePercent: 20,
+            dynamicFee: null,
+          },
+          sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+          sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+          vaultConfigKey: PublicKey.default,
+          poolCreatorAuthority: PublicKey.default,
+          activationType: 0,
+          collectFeeMode: 0,
+        }
+      );
+
+      // only admin
+      await expectThrowsAsync(async () => {
+        await setConfigCpiGuard(
+          context.banksClient,
+          user,
+          guardedConfig,
+          new BN(100)
+        );
+      }, "0x1775");
+      // longer than 1 day
+      await expectThrowsAsync(async () => {
+        await setConfigCpiGuard(
+          context.banksClient,
+          admin,
+          guardedConfig,
+          new BN(9000 * 24 + 1)
+        );
+      }, "0x17c4");
+      await setConfigCpiGuard(
+        context.banksClient,
+        admin,
+        guardedConfig,
+        new BN(100)
+      );
+
+      const { pool: guardedPool } = await initializePool(
+        context.banksClient,
+        {
+          payer: creator,
+          creator: creator.publicKey,
+          config: guardedConfig,
+          tokenAMint: inputTokenMint,
+          tokenBMint: outputTokenMint,
+          liquidity,
+          sqrtPrice,
+          activationPoint: null,
+        }
+      );
+      const poolState = await getPool(context.banksClient, guardedPool);
+      expect(poolState.cpiGuardDuration.toNumber()).eq(100);
+
+      const swapParams: SwapParams = {
+        payer: user,
+        pool: guardedPool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(10),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      };
+      // instructions sysvar is required to check the swap is not a cpi
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, swapParams);
+      }, "0x17c5");
+
+      await swap(context.banksClient, {
+        ...swapParams,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      });
     });
   });
 
@@ -184,6 +968,7 @@ describe("Swap token", () => {
       const extensions = [ExtensionType.TransferFeeConfig];
       user = await generateKpAndFund(context.banksClient, context.payer);
       admin = await generateKpAndFund(context.banksClient, context.payer);
+      await initializeProgramAuthority(context.banksClient, admin);
       creator = await generateKpAndFund(context.banksClient, context.payer);
 
       inputTokenMint = await createToken2022(
@@ -301,10 +1086,202 @@ describe("Swap token", () => {
         outputTokenMint,
         amountIn: new BN(10),
         minimumAmountOut: new BN(0),
-        referralTokenAccount: null,
+        referral: null,
       };
 
+      const { tokenAVault } = await getPool(context.banksClient, pool);
+      const getVault = async () =>
+        unpackAccount(
+          tokenAVault,
+          {
+            ...(await context.banksClient.getAccount(tokenAVault)),
+            data: Buffer.from(
+              (await context.banksClient.getAccount(tokenAVault)).data
+            ),
+          },
+          TOKEN_2022_PROGRAM_ID
+        );
+      const vaultBefore = await getVault();
+
       await swap(context.banksClient, swapParams);
+
+      // pool is credited with the amount received by vault, transfer fee is withheld in vault
+      const vaultAfter = await getVault();
+      const withheldAmount =
+        getTransferFeeAmount(vaultAfter).withheldAmount -
+        getTransferFeeAmount(vaultBefore).withheldAmount;
+      expect(
+        (vaultAfter.amount - vaultBefore.amount + withheldAmount).toString()
+      ).eq(swapParams.amountIn.toString());
+    });
+  });
+
+  describe("Interest bearing token", () => {
+    it("Swap and remove liquidity in raw amounts", async () => {
+      const root = Keypair.generate();
+      const context = await startTest(root);
+      const user = await generateKpAndFund(context.banksClient, context.payer);
+      const admin = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+      await initializeProgramAuthority(context.banksClient, admin);
+      const creator = await generateKpAndFund(
+        context.banksClient,
+        context.payer
+      );
+
+      // supported permissionlessly without rate authority
+      const tokenAMint = await createInterestBearingToken2022(
+        context.banksClient,
+        context.payer,
+        32_767
+      );
+      const tokenBMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+      for (const wallet of [user.publicKey, creator.publicKey]) {
+        await mintToToken2022(
+          context.banksClient,
+          context.payer,
+          tokenAMint,
+          context.payer,
+          wallet
+        );
+        await mintSplTokenTo(
+          context.banksClient,
+          context.payer,
+          tokenBMint,
+          context.payer,
+          wallet
+        );
+      }
+
+      const config = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(randomID()),
+        {
+          poolFees: {
+            baseFee: {
+              cliffFeeNumerator: new BN(2_500_000),
+              numberOfPeriod: 0,
+              reductionFactor: new BN(0),
+              periodFrequency: new BN(0),
+              feeSchedulerMode: 0,
+            },
+            protocolFeePercent: 10,
+            partnerFeePercent: 0,
+            referralFeePercent: 0,
+            dynamicFee: null,
+          },
+          sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+          sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+          vaultConfigKey: PublicKey.default,
+          poolCreatorAuthority: PublicKey.default,
+          activationType: 0,
+          collectFeeMode: 0,
+        }
+      );
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity: new BN(MIN_LP_AMOUNT),
+        sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+        activationPoint: null,
+      });
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      const liquidityDelta = new BN(MIN_SQRT_PRICE.muln(1_000));
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      });
+
+      // a year of interest only scales ui amount
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(365 * 24 * 60 * 60)
+        )
+      );
+
+      const { tokenAVault, tokenBVault } = await getPool(
+        context.banksClient,
+        pool
+      );
+      const userTokenA = getAssociatedTokenAddressSync(
+        tokenAMint,
+        user.publicKey,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const userTokenB = getAssociatedTokenAddressSync(
+        tokenBMint,
+        user.publicKey
+      );
+      const getRawAmounts = async () =>
+        Promise.all(
+          [userTokenA, userTokenB, tokenAVault, tokenBVault].map(
+            async (account) =>
+              (await getTokenAccount(context.banksClient, account)).amount
+          )
+        );
+
+      const amountIn = BigInt(1_000_000);
+      let [userA, userB, vaultA, vaultB] = await getRawAmounts();
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(amountIn.toString()),
+        minimumAmountOut: new BN(0),
+        referral: null,
+      });
+      let [afterUserA, afterUserB, afterVaultA, afterVaultB] =
+        await getRawAmounts();
+      expect(userA - afterUserA).eq(amountIn);
+      expect(afterVaultA - vaultA).eq(amountIn);
+      expect(afterUserB - userB > BigInt(0)).to.be.true;
+      expect(afterUserB - userB).eq(vaultB - afterVaultB);
+
+      [userA, userB, vaultA, vaultB] = [
+        afterUserA,
+        afterUserB,
+        afterVaultA,
+        afterVaultB,
+      ];
+      await removeLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta,
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+      [afterUserA, afterUserB, afterVaultA, afterVaultB] =
+        await getRawAmounts();
+      expect(afterUserA - userA > BigInt(0)).to.be.true;
+      expect(afterUserA - userA).eq(vaultA - afterVaultA);
+      expect(afterUserB - userB).eq(vaultB - afterVaultB);
     });
   });
 });
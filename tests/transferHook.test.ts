@@ -0,0 +1,273 @@
+import { AccountMeta, Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  addLiquidity,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createToken,
+  createTokenBadge,
+  deriveTokenBadgeAddress,
+  getTokenAccount,
+  initializePool,
+  InitializePoolParams,
+  initializeProgramAuthority,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  removeLiquidity,
+  swap,
+  TEST_TRANSFER_HOOK_PROGRAM_ID,
+  TOKEN_BADGE_ALLOW_TRANSFER_HOOK,
+} from "./bankrun-utils";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import {
+  createTransferHookToken2022,
+  mintToToken2022,
+  updateTransferHookProgram,
+} from "./bankrun-utils/token2022";
+
+describe("Transfer hook token", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let config: PublicKey;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  let hookAccounts: AccountMeta[];
+  let initPoolParams: InitializePoolParams;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    await initializeProgramAuthority(context.banksClient, admin);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createTransferHookToken2022(
+      context.banksClient,
+      context.payer,
+      TEST_TRANSFER_HOOK_PROGRAM_ID
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const wallet of [user.publicKey, creator.publicKey]) {
+      await mintToToken2022(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        wallet
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        wallet
+      );
+    }
+
+    // token badge approves the current hook program of the mint
+    await createTokenBadge(context.banksClient, {
+      tokenMint: tokenAMint,
+      admin,
+      policy: 1 << TOKEN_BADGE_ALLOW_TRANSFER_HOOK,
+    });
+    hookAccounts = [
+      {
+        pubkey: deriveTokenBadgeAddress(tokenAMint),
+        isSigner: false,
+        isWritable: false,
+      },
+      {
+        pubkey: TEST_TRANSFER_HOOK_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+    ];
+
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          numberOfPeriod: 0,
+          reductionFactor: new BN(0),
+          periodFrequency: new BN(0),
+          feeSchedulerMode: 0,
+        },
+        protocolFeePercent: 10,
+        partnerFeePercent: 0,
+        referralFeePercent: 0,
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(Math.floor(Math.random() * 1000)),
+      createConfigParams
+    );
+
+    initPoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+      remainingAccounts: hookAccounts,
+    };
+  });
+
+  it("Pool creation requires token badge", async () => {
+    await expectThrowsAsync(async () => {
+      await initializePool(context.banksClient, {
+        ...initPoolParams,
+        remainingAccounts: [],
+      });
+    }, "0x1784");
+  });
+
+  describe("Pool of transfer hook token", () => {
+    beforeEach(async () => {
+      const result = await initializePool(context.banksClient, initPoolParams);
+      pool = result.pool;
+      position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+    });
+
+    it("Add liquidity, swap and remove liquidity", async () => {
+      const liquidityDelta = new BN(MIN_SQRT_PRICE.muln(1_000));
+      const liquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      };
+
+      // token badge is required with the extra accounts of the hook
+      await expectThrowsAsync(async () => {
+        await addLiquidity(context.banksClient, {
+          ...liquidityParams,
+          remainingAccounts: hookAccounts.slice(1),
+        });
+      }, "0x17d2");
+      await addLiquidity(context.banksClient, {
+        ...liquidityParams,
+        remainingAccounts: hookAccounts,
+      });
+
+      const userTokenB = getAssociatedTokenAddressSync(
+        tokenBMint,
+        user.publicKey
+      );
+      const beforeTokenB = (
+        await getTokenAccount(context.banksClient, userTokenB)
+      ).amount;
+      await swap(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(1_000),
+        minimumAmountOut: new BN(0),
+        referral: null,
+        remainingAccounts: hookAccounts,
+      });
+      const afterTokenB = (
+        await getTokenAccount(context.banksClient, userTokenB)
+      ).amount;
+      expect(afterTokenB > beforeTokenB).to.be.true;
+
+      await removeLiquidity(context.banksClient, {
+        ...liquidityParams,
+        liquidityDelta: liquidityDelta.divn(2),
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+        remainingAccounts: hookAccounts,
+      });
+    });
+
+    it("Reject hook program swapped by mint authority", async () => {
+      const liquidityDelta = new BN(MIN_SQRT_PRICE.muln(1_000));
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+        remainingAccounts: hookAccounts,
+      });
+
+      const swappedHookProgram = Keypair.generate().publicKey;
+      await updateTransferHookProgram(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        swappedHookProgram
+      );
+      const swappedHookAccounts = [
+        hookAccounts[0],
+        { ...hookAccounts[1], pubkey: swappedHookProgram },
+      ];
+
+      await expectThrowsAsync(async () => {
+        await swap(context.banksClient, {
+          payer: user,
+          pool,
+          inputTokenMint: tokenAMint,
+          outputTokenMint: tokenBMint,
+          amountIn: new BN(1_000),
+          minimumAmountOut: new BN(0),
+          referral: null,
+          remainingAccounts: swappedHookAccounts,
+        });
+      }, "0x17d2");
+
+      await expectThrowsAsync(async () => {
+        await removeLiquidity(context.banksClient, {
+          owner: user,
+          pool,
+          position,
+          liquidityDelta: liquidityDelta.divn(2),
+          tokenAAmountThreshold: new BN(0),
+          tokenBAmountThreshold: new BN(0),
+          remainingAccounts: swappedHookAccounts,
+        });
+      }, "0x17d2");
+    });
+  });
+});
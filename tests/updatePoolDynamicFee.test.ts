@@ -0,0 +1,203 @@
+import { expect } from "chai";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  BASIS_POINT_MAX,
+  cancelPendingUpdate,
+  createConfigIx,
+  CreateConfigParams,
+  createToken,
+  initializePool,
+  InitializePoolParams,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  OFFSET,
+  getPool,
+  swap,
+  updatePoolDynamicFee,
+  initializeProgramAuthority,
+} from "./bankrun-utils";
+import { shlDiv } from "./bankrun-utils/math";
+import BN from "bn.js";
+
+describe("Update pool dynamic fee", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let creator: Keypair;
+  let pool: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    await initializeProgramAuthority(context.banksClient, admin);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      creator.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      creator.publicKey
+    );
+
+    const binStep = new BN(1);
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          numberOfPeriod: 0,
+          reductionFactor: new BN(0),
+          periodFrequency: new BN(0),
+          feeSchedulerMode: 0,
+        },
+        protocolFeePercent: 10,
+        partnerFeePercent: 0,
+        referralFeePercent: 0,
+        dynamicFee: {
+          binStep: binStep.toNumber(),
+          binStepU128: shlDiv(binStep, new BN(BASIS_POINT_MAX), OFFSET),
+          filterPeriod: 2_000,
+          decayPeriod: 5_000,
+          reductionFactor: 5_000,
+          maxVolatilityAccumulator: 350_000,
+          variableFeeControl: 10_000,
+        },
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(Math.floor(Math.random() * 1000)),
+      createConfigParams
+    );
+
+    const initPoolParams: InitializePoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    };
+    const result = await initializePool(context.banksClient, initPoolParams);
+    pool = result.pool;
+  });
+
+  it("Admin update pool dynamic fee", async () => {
+    await updatePoolDynamicFee(context.banksClient, {
+      signer: admin,
+      pool,
+      decayPeriod: 10_000,
+      reductionFactor: 2_000,
+      variableFeeControl: 20_000,
+    });
+  });
+
+  it("Pending update takes effect after delay", async () => {
+    await updatePoolDynamicFee(context.banksClient, {
+      signer: admin,
+      pool,
+      decayPeriod: 10_000,
+      reductionFactor: 2_000,
+      variableFeeControl: 20_000,
+    });
+
+    const { activationPoint } = (await getPool(context.banksClient, pool))
+      .poolFees.pendingDynamicFee;
+    const currentClock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        BigInt(activationPoint.toString())
+      )
+    );
+
+    // swap applies the pending update
+    await swap(context.banksClient, {
+      payer: creator,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referral: null,
+    });
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolFees.dynamicFee.decayPeriod).eq(10_000);
+    expect(poolState.poolFees.dynamicFee.reductionFactor).eq(2_000);
+    expect(poolState.poolFees.dynamicFee.variableFeeControl).eq(20_000);
+    expect(poolState.poolFees.pendingDynamicFee.activationPoint.isZero()).to.be
+      .true;
+  });
+
+  it("Cancel pending update", async () => {
+    await expectThrowsAsync(async () => {
+      await cancelPendingUpdate(context.banksClient, admin, pool);
+    }, "0x17b0");
+
+    await updatePoolDynamicFee(context.banksClient, {
+      signer: admin,
+      pool,
+      decayPeriod: 10_000,
+      reductionFactor: 2_000,
+      variableFeeControl: 20_000,
+    });
+    await cancelPendingUpdate(context.banksClient, admin, pool);
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolFees.dynamicFee.decayPeriod).eq(5_000);
+  });
+
+  it("Decay period must be greater than filter period", async () => {
+    await expectThrowsAsync(async () => {
+      await updatePoolDynamicFee(context.banksClient, {
+        signer: admin,
+        pool,
+        decayPeriod: 1_000,
+        reductionFactor: 2_000,
+        variableFeeControl: 20_000,
+      });
+    }, "0x1782");
+  });
+});